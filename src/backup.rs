@@ -0,0 +1,145 @@
+//! Account backup format for `account export --file` / `account import`.
+
+use crate::error::AppError;
+use crate::models::Account;
+use argon2::Argon2;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Current backup format version.
+pub const BACKUP_VERSION: u32 = 1;
+
+const SALT_LEN: usize = 16;
+
+/// Portable snapshot of accounts, groups and (optionally) tokens.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupFile {
+    pub version: u32,
+    pub accounts: Vec<Account>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub groups: BTreeMap<String, Vec<String>>,
+    /// Plaintext tokens keyed by account ID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokens: Option<BTreeMap<String, String>>,
+    /// Passphrase-encrypted tokens keyed by account ID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encrypted_tokens: Option<EncryptedBlob>,
+}
+
+impl BackupFile {
+    /// Whether the backup carries tokens in any form.
+    pub fn has_tokens(&self) -> bool {
+        self.tokens.is_some() || self.encrypted_tokens.is_some()
+    }
+}
+
+/// ChaCha20-Poly1305 ciphertext with an Argon2id-derived key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptedBlob {
+    /// Hex-encoded Argon2 salt.
+    pub salt: String,
+    /// Hex-encoded AEAD nonce.
+    pub nonce: String,
+    /// Hex-encoded ciphertext of the JSON token map.
+    pub ciphertext: String,
+}
+
+/// Encrypt a token map with a passphrase.
+pub fn encrypt_tokens(
+    tokens: &BTreeMap<String, String>,
+    passphrase: &str,
+) -> Result<EncryptedBlob, AppError> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+
+    let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, &salt)?);
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let plaintext = serde_json::to_vec(tokens)?;
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_slice())
+        .map_err(|_| AppError::keychain("failed to encrypt tokens"))?;
+
+    Ok(EncryptedBlob {
+        salt: to_hex(&salt),
+        nonce: to_hex(&nonce),
+        ciphertext: to_hex(&ciphertext),
+    })
+}
+
+/// Decrypt a token map encrypted by [`encrypt_tokens`].
+pub fn decrypt_tokens(
+    blob: &EncryptedBlob,
+    passphrase: &str,
+) -> Result<BTreeMap<String, String>, AppError> {
+    let salt = from_hex(&blob.salt)?;
+    let nonce = from_hex(&blob.nonce)?;
+    let ciphertext = from_hex(&blob.ciphertext)?;
+    if nonce.len() != 12 {
+        return Err(AppError::invalid_input("backup has a malformed nonce"));
+    }
+
+    let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, &salt)?);
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| AppError::invalid_input("wrong passphrase or corrupted backup"))?;
+    Ok(serde_json::from_slice(&plaintext)?)
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<Key, AppError> {
+    let mut key = Key::default();
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| AppError::keychain(format!("failed to derive key: {e}")))?;
+    Ok(key)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn from_hex(s: &str) -> Result<Vec<u8>, AppError> {
+    if !s.len().is_multiple_of(2) {
+        return Err(AppError::invalid_input("backup contains malformed hex data"));
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&s[i..i + 2], 16)
+                .map_err(|_| AppError::invalid_input("backup contains malformed hex data"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens() -> BTreeMap<String, String> {
+        BTreeMap::from([("work".to_string(), "ghp_secret".to_string())])
+    }
+
+    #[test]
+    fn encrypt_decrypt_roundtrip() {
+        let blob = encrypt_tokens(&tokens(), "hunter2").unwrap();
+        assert!(!blob.ciphertext.contains("ghp_secret"));
+        assert_eq!(decrypt_tokens(&blob, "hunter2").unwrap(), tokens());
+    }
+
+    #[test]
+    fn decrypt_with_wrong_passphrase_fails() {
+        let blob = encrypt_tokens(&tokens(), "hunter2").unwrap();
+        let result = decrypt_tokens(&blob, "wrong");
+        assert!(matches!(result, Err(AppError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn hex_roundtrip() {
+        let bytes = [0x00, 0x7f, 0xff];
+        assert_eq!(to_hex(&bytes), "007fff");
+        assert_eq!(from_hex("007fff").unwrap(), bytes);
+        assert!(from_hex("abc").is_err());
+    }
+}
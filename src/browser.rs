@@ -0,0 +1,52 @@
+//! System browser integration.
+
+use crate::domain::browser_command;
+use crate::error::AppError;
+use crate::models::Account;
+use std::process::Command;
+
+/// Open a URL in the system browser.
+pub fn open(url: &str) -> Result<(), AppError> {
+    let (program, args) = opener_command();
+    let status = Command::new(program)
+        .args(args)
+        .arg(url)
+        .status()
+        .map_err(|e| AppError::config(format!("failed to launch browser: {e}")))?;
+
+    if !status.success() {
+        return Err(AppError::config(format!("browser exited with status {status}")));
+    }
+    Ok(())
+}
+
+/// Open a URL with the account's browser command, or the system browser when it has none.
+///
+/// This keeps each account's pages in the browser profile signed in as that account.
+pub fn open_for(account: Option<&Account>, url: &str) -> Result<(), AppError> {
+    let Some((account, template)) = account.and_then(|a| a.browser.as_deref().map(|t| (a, t)))
+    else {
+        return open(url);
+    };
+    let command = browser_command(template, url, &account.id)?;
+    let (program, args) = command.split_first().expect("browser command is never empty");
+    let status = Command::new(program)
+        .args(args)
+        .status()
+        .map_err(|e| AppError::config(format!("failed to launch '{program}': {e}")))?;
+
+    if !status.success() {
+        return Err(AppError::config(format!("'{program}' exited with status {status}")));
+    }
+    Ok(())
+}
+
+fn opener_command() -> (&'static str, &'static [&'static str]) {
+    if cfg!(target_os = "macos") {
+        ("open", &[])
+    } else if cfg!(target_os = "windows") {
+        ("cmd", &["/C", "start", ""])
+    } else {
+        ("xdg-open", &[])
+    }
+}
@@ -0,0 +1,98 @@
+//! Cross-account repository access (`gho access`).
+
+use crate::commands::account;
+use crate::error::AppError;
+use crate::github::{GitHubClient, ReposApi};
+use crate::models::{Account, AccountScoped, RepoAccess, RepoPermission, Repository};
+use crate::remote::resolve_repo;
+use crate::storage::Storage;
+
+/// Permission of every configured account on one repository.
+///
+/// Each account is checked with its own stored token (`GH_TOKEN` is ignored, it would make
+/// every account look the same). An account that cannot see the repository has `none`; one
+/// whose check failed, e.g. with an expired token, carries the error instead.
+pub fn diff(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+) -> Result<Vec<AccountScoped<RepoAccess>>, AppError> {
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+    let accounts = storage.load_accounts()?;
+    let accounts = accounts.all_accounts();
+    if accounts.is_empty() {
+        return Err(AppError::NoActiveAccount);
+    }
+
+    Ok(accounts
+        .into_iter()
+        .map(|account| {
+            let result = account::get_token(storage, &account.id)
+                .and_then(GitHubClient::new)
+                .and_then(|client| client.get_repo(&owner, &repo));
+            access_of(account, result)
+        })
+        .collect())
+}
+
+/// An account's access from its repository lookup; not found means no access.
+fn access_of(account: &Account, lookup: Result<Repository, AppError>) -> AccountScoped<RepoAccess> {
+    let (permission, error) = match lookup {
+        Ok(repo) => {
+            let flags = repo.permissions.unwrap_or_default();
+            (Some(RepoPermission::from_flags(&flags)), None)
+        }
+        Err(AppError::NotFound(_)) => (Some(RepoPermission::None), None),
+        Err(e) => (None, Some(e.to_string())),
+    };
+    AccountScoped {
+        account: account.id.clone(),
+        item: RepoAccess { username: account.username.clone(), permission, error },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{AccountKind, CloneLayout, CloneOptions, Protocol};
+
+    fn account(id: &str) -> Account {
+        Account {
+            id: id.to_string(),
+            kind: AccountKind::Work,
+            username: format!("{id}-user"),
+            default_org: None,
+            protocol: Protocol::Ssh,
+            clone_dir: None,
+            clone_layout: CloneLayout::Flat,
+            clone_options: CloneOptions::default(),
+            browser: None,
+            token_command: None,
+            token_expires_at: None,
+            host: None,
+            protected: false,
+            git: None,
+        }
+    }
+
+    #[test]
+    fn access_of_maps_lookups_to_permissions() {
+        let repo: Repository = serde_json::from_str(
+            r#"{
+                "name": "api", "full_name": "acme/api", "html_url": "", "ssh_url": "",
+                "clone_url": "", "owner": {"login": "acme"},
+                "permissions": {"admin": false, "push": true, "pull": true}
+            }"#,
+        )
+        .unwrap();
+        let write = access_of(&account("work"), Ok(repo));
+        assert_eq!(write.account, "work");
+        assert_eq!(write.item.permission, Some(RepoPermission::Write));
+
+        let hidden = access_of(&account("bot"), Err(AppError::NotFound("404".into())));
+        assert_eq!(hidden.item.permission, Some(RepoPermission::None));
+
+        let failed = access_of(&account("old"), Err(AppError::Unauthorized("401".into())));
+        assert_eq!(failed.item.permission, None);
+        assert!(failed.item.error.is_some());
+    }
+}
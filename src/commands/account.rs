@@ -1,9 +1,44 @@
 //! Account management commands.
 
+use crate::backup::{self, BACKUP_VERSION, BackupFile};
+use crate::domain;
 use crate::error::AppError;
+#[cfg(feature = "network")]
+use crate::github::UsersApi;
 use crate::keychain;
-use crate::models::{Account, AccountKind, AccountsFile, Protocol};
+use crate::models::{
+    Account, AccountKind, AccountsFile, CloneLayout, CloneOptions, Execution, ExecutionMode,
+    Protocol, SwitchResult,
+};
 use crate::storage::Storage;
+use crate::vault::{self, VaultKind};
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+/// Environment variable supplying the backup passphrase non-interactively.
+pub const BACKUP_PASSPHRASE_ENV: &str = "GHO_BACKUP_PASSPHRASE";
+
+/// How to handle an imported account whose ID already exists.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConflictAction {
+    Skip,
+    Overwrite,
+    Rename(String),
+}
+
+/// Outcome of an account import.
+#[derive(Debug, Clone, Default)]
+pub struct ImportReport {
+    /// IDs of imported accounts (after any rename).
+    pub imported: Vec<String>,
+    /// IDs of accounts skipped due to conflicts.
+    pub skipped: Vec<String>,
+    /// Number of tokens restored into the keychain.
+    pub tokens_restored: usize,
+}
 
 /// Add a new account.
 #[allow(clippy::too_many_arguments)]
@@ -13,37 +48,38 @@ pub fn add(
     username: &str,
     kind: AccountKind,
     token: &str,
+    host: Option<String>,
     default_org: Option<String>,
     protocol: Protocol,
     clone_dir: Option<String>,
+    clone_layout: CloneLayout,
+    token_expires_at: Option<DateTime<Utc>>,
 ) -> Result<(), AppError> {
     let mut accounts = storage.load_accounts()?;
 
-    // Check for duplicate ID
-    if accounts.find_account(id).is_some() {
-        return Err(AppError::invalid_input(format!("account '{id}' already exists")));
-    }
-
     let account = Account {
         id: id.to_string(),
         kind,
         username: username.to_string(),
+        host,
         default_org,
         protocol,
         clone_dir,
+        clone_layout,
+        clone_options: CloneOptions::default(),
+        browser: None,
+        token_command: None,
+        token_expires_at,
+        protected: false,
+        git: None,
     };
 
+    // Add account (the first one becomes active), rejecting duplicate IDs
+    accounts.register(account)?;
+
     // Store token in keychain
     keychain::store_token(id, token)?;
 
-    // Add account
-    accounts.add_account(account);
-
-    // If this is the first account, make it active
-    if accounts.active_account_id.is_none() {
-        accounts.active_account_id = Some(id.to_string());
-    }
-
     // Save accounts, rolling back keychain on failure
     if let Err(e) = storage.save_accounts(&accounts) {
         // Attempt to clean up the keychain entry
@@ -59,20 +95,18 @@ pub fn list(storage: &impl Storage) -> Result<AccountsFile, AppError> {
 }
 
 /// Switch the active account.
-pub fn switch(storage: &impl Storage, id: &str) -> Result<(), AppError> {
+pub fn switch(storage: &impl Storage, id: &str) -> Result<SwitchResult, AppError> {
     let mut accounts = storage.load_accounts()?;
-
-    if accounts.find_account(id).is_none() {
-        return Err(AppError::AccountNotFound(id.to_string()));
-    }
-
-    accounts.active_account_id = Some(id.to_string());
+    let previous = accounts.active_account_id.clone();
+    accounts.set_active(id)?;
     storage.save_accounts(&accounts)?;
-    Ok(())
+    let username = accounts.active_account().map(|a| a.username.clone()).unwrap_or_default();
+    Ok(SwitchResult { id: id.to_string(), username, previous, ssh_agent: None })
 }
 
 /// Switch account interactively.
-pub fn switch_interactive(storage: &impl Storage) -> Result<String, AppError> {
+#[cfg(feature = "interactive")]
+pub fn switch_interactive(storage: &impl Storage) -> Result<SwitchResult, AppError> {
     if !atty::is(atty::Stream::Stdin) {
         return Err(AppError::TtyRequired);
     }
@@ -102,8 +136,7 @@ pub fn switch_interactive(storage: &impl Storage) -> Result<String, AppError> {
     if selected_id.is_empty() {
         return Err(AppError::config(format!("could not parse selection: {}", selection)));
     }
-    switch(storage, selected_id)?;
-    Ok(selected_id.to_string())
+    switch(storage, selected_id)
 }
 
 /// Show the active account.
@@ -113,27 +146,708 @@ pub fn show(storage: &impl Storage) -> Result<Account, AppError> {
 }
 
 /// Remove an account.
-pub fn remove(storage: &impl Storage, id: &str) -> Result<(), AppError> {
+pub fn remove(
+    storage: &impl Storage,
+    id: &str,
+    mode: ExecutionMode,
+) -> Result<Execution<()>, AppError> {
     let mut accounts = storage.load_accounts()?;
+    let was_active = accounts.active_account_id.as_deref() == Some(id);
 
-    if accounts.remove_account(id).is_none() {
+    let Some(account) = accounts.remove_account(id) else {
         return Err(AppError::AccountNotFound(id.to_string()));
+    };
+    if mode.is_dry_run() {
+        let mut steps = vec![format!("remove account '{id}' ({})", account.username)];
+        if was_active {
+            steps.push("leave no account active".to_string());
+        }
+        steps.push(format!("delete the keychain token and sudo PIN of '{id}'"));
+        if account.git.is_some() {
+            steps.push(format!("remove the gitconfig include of '{id}'"));
+        }
+        return Ok(Execution::Planned(steps));
     }
 
-    // Delete token from keychain (ignore errors if not found)
+    // Delete token and sudo PIN from keychain (ignore errors if not found)
     let _ = keychain::delete_token(id);
+    let _ = keychain::delete_pin(id);
 
     storage.save_accounts(&accounts)?;
-    Ok(())
+    Ok(Execution::Done(()))
+}
+
+/// Define (or replace) a named account group.
+pub fn set_group(storage: &impl Storage, name: &str, ids: Vec<String>) -> Result<(), AppError> {
+    let mut accounts = storage.load_accounts()?;
+    accounts.set_group(name, ids)?;
+    storage.save_accounts(&accounts)
+}
+
+/// Remove a named account group.
+pub fn remove_group(storage: &impl Storage, name: &str) -> Result<(), AppError> {
+    let mut accounts = storage.load_accounts()?;
+    if accounts.groups.remove(name).is_none() {
+        return Err(AppError::invalid_input(format!("account group '{name}' not found")));
+    }
+    storage.save_accounts(&accounts)
+}
+
+/// Get every account in a group together with its token.
+pub fn get_group_with_tokens(
+    storage: &impl Storage,
+    name: &str,
+) -> Result<Vec<(Account, String)>, AppError> {
+    let accounts = storage.load_accounts()?;
+    accounts
+        .group_accounts(name)?
+        .into_iter()
+        .map(|account| {
+            let token = keychain::get_account_token(account)?;
+            Ok((account.clone(), token))
+        })
+        .collect()
 }
 
 /// Get the active account with its token.
 pub fn get_active_with_token(storage: &impl Storage) -> Result<(Account, String), AppError> {
     let account = show(storage)?;
-    let token = keychain::get_token(&account.id)?;
+    let token = keychain::get_account_token(&account)?;
+    Ok((account, token))
+}
+
+/// Get the active account with its token for a destructive API call.
+///
+/// Protected accounts must be re-confirmed (or their PIN re-entered) once per shell session.
+pub fn get_active_for_destructive(storage: &impl Storage) -> Result<(Account, String), AppError> {
+    let (account, token) = get_active_with_token(storage)?;
+    confirm_destructive(storage, &account)?;
     Ok((account, token))
 }
 
+/// Replace the stored token of an account and record its new expiry (`None` clears it).
+pub fn refresh_token(
+    storage: &impl Storage,
+    id: &str,
+    token: &str,
+    expires_at: Option<DateTime<Utc>>,
+) -> Result<(), AppError> {
+    set_token(storage, id, token)?;
+    let mut accounts = storage.load_accounts()?;
+    if let Some(account) = accounts.find_account_mut(id) {
+        account.token_expires_at = expires_at;
+    }
+    storage.save_accounts(&accounts)
+}
+
+/// Replace the stored token of an account without touching its metadata.
+pub fn set_token(storage: &impl Storage, id: &str, token: &str) -> Result<(), AppError> {
+    let account = keychain_account(storage, id)?;
+    let token = token.trim();
+    if token.is_empty() {
+        return Err(AppError::invalid_input("token must not be empty"));
+    }
+    keychain::store_token(&account.id, token)
+}
+
+/// Read the token of an account from its configured source, ignoring `GH_TOKEN`.
+pub fn get_token(storage: &impl Storage, id: &str) -> Result<String, AppError> {
+    let accounts = storage.load_accounts()?;
+    let account =
+        accounts.find_account(id).ok_or_else(|| AppError::AccountNotFound(id.to_string()))?;
+    match &account.token_command {
+        Some(command) => keychain::run_token_command(command),
+        None => keychain::get_stored_token(id),
+    }
+}
+
+/// Delete the stored token of an account, keeping the account itself.
+pub fn remove_token(storage: &impl Storage, id: &str) -> Result<(), AppError> {
+    let account = keychain_account(storage, id)?;
+    keychain::delete_token(&account.id)
+}
+
+/// Look up an account whose token lives in the keychain rather than a `token_command`.
+pub fn keychain_account(storage: &impl Storage, id: &str) -> Result<Account, AppError> {
+    let accounts = storage.load_accounts()?;
+    let account =
+        accounts.find_account(id).ok_or_else(|| AppError::AccountNotFound(id.to_string()))?;
+    if let Some(command) = &account.token_command {
+        return Err(AppError::invalid_input(format!(
+            "'{id}' reads its token from `{command}`; update the secret there"
+        )));
+    }
+    Ok(account.clone())
+}
+
+/// Ask GitHub when `token` expires. `None` when it never expires.
+#[cfg(feature = "network")]
+pub fn detect_token_expiry(token: &str) -> Result<Option<DateTime<Utc>>, AppError> {
+    crate::github::GitHubClient::new(token.to_string())?.token_expiration()
+}
+
+/// Warning for an account whose token has expired or expires within `warning_days`.
+pub fn token_expiry_warning(
+    account: &Account,
+    now: DateTime<Utc>,
+    warning_days: u32,
+) -> Option<String> {
+    let expires_at = account.token_expires_at?;
+    if warning_days == 0 {
+        return None;
+    }
+    let id = &account.id;
+    if expires_at <= now {
+        return Some(format!(
+            "token of '{id}' expired on {}; run `gho account token refresh {id}`",
+            expires_at.format("%Y-%m-%d")
+        ));
+    }
+    let days = (expires_at - now).num_days();
+    (days < i64::from(warning_days)).then(|| {
+        let when = match days {
+            0 => "today".to_string(),
+            1 => "in 1 day".to_string(),
+            n => format!("in {n} days"),
+        };
+        format!("token of '{id}' expires {when}; run `gho account token refresh {id}`")
+    })
+}
+
+/// Mark an account as protected, optionally requiring a PIN for confirmation.
+pub fn protect(storage: &impl Storage, id: &str, pin: Option<&str>) -> Result<(), AppError> {
+    let mut accounts = storage.load_accounts()?;
+    let account =
+        accounts.find_account_mut(id).ok_or_else(|| AppError::AccountNotFound(id.to_string()))?;
+    account.protected = true;
+
+    match pin {
+        Some(pin) if pin.trim().is_empty() => {
+            return Err(AppError::invalid_input("PIN must not be empty"));
+        }
+        Some(pin) => keychain::store_pin(id, pin)?,
+        None => {
+            let _ = keychain::delete_pin(id);
+        }
+    }
+    storage.save_accounts(&accounts)
+}
+
+/// Change the directory layout used when cloning with an account.
+pub fn set_clone_layout(
+    storage: &impl Storage,
+    id: &str,
+    layout: CloneLayout,
+) -> Result<(), AppError> {
+    let mut accounts = storage.load_accounts()?;
+    let account =
+        accounts.find_account_mut(id).ok_or_else(|| AppError::AccountNotFound(id.to_string()))?;
+    account.clone_layout = layout;
+    storage.save_accounts(&accounts)
+}
+
+/// Replace the default `git clone` options of an account (empty options clear them).
+pub fn set_clone_options(
+    storage: &impl Storage,
+    id: &str,
+    options: CloneOptions,
+) -> Result<(), AppError> {
+    validate_clone_options(&options)?;
+    let mut accounts = storage.load_accounts()?;
+    let account =
+        accounts.find_account_mut(id).ok_or_else(|| AppError::AccountNotFound(id.to_string()))?;
+    account.clone_options = options;
+    storage.save_accounts(&accounts)
+}
+
+/// Reject clone options git would refuse or misread.
+pub fn validate_clone_options(options: &CloneOptions) -> Result<(), AppError> {
+    if options.depth == Some(0) {
+        return Err(AppError::invalid_input("--depth must be at least 1"));
+    }
+    for (flag, value) in [("--filter", &options.filter), ("--branch", &options.branch)] {
+        if value.as_deref().is_some_and(|v| v.trim().is_empty() || v.starts_with('-')) {
+            return Err(AppError::invalid_input(format!("invalid {flag} value")));
+        }
+    }
+    if options.bare && options.recurse_submodules {
+        return Err(AppError::invalid_input("--bare clones have no submodules to check out"));
+    }
+    Ok(())
+}
+
+/// Set (or with `None`, clear) the browser command used to open an account's pages.
+pub fn set_browser(
+    storage: &impl Storage,
+    id: &str,
+    template: Option<String>,
+) -> Result<(), AppError> {
+    if let Some(template) = &template {
+        domain::browser_command(template, "", id)?;
+    }
+    let mut accounts = storage.load_accounts()?;
+    let account =
+        accounts.find_account_mut(id).ok_or_else(|| AppError::AccountNotFound(id.to_string()))?;
+    account.browser = template;
+    storage.save_accounts(&accounts)
+}
+
+/// Remove protection (and any PIN) from an account.
+pub fn unprotect(storage: &impl Storage, id: &str) -> Result<(), AppError> {
+    let mut accounts = storage.load_accounts()?;
+    let account =
+        accounts.find_account_mut(id).ok_or_else(|| AppError::AccountNotFound(id.to_string()))?;
+    account.protected = false;
+    let _ = keychain::delete_pin(id);
+    storage.save_accounts(&accounts)
+}
+
+fn confirm_destructive(storage: &impl Storage, account: &Account) -> Result<(), AppError> {
+    if !account.protected {
+        return Ok(());
+    }
+
+    let session = session_id();
+    let now = chrono::Utc::now();
+    let mut state = storage.load_state()?;
+    if state.has_sudo_grant(&account.id, session, now) {
+        return Ok(());
+    }
+
+    prompt_sudo(account)?;
+
+    state.grant_sudo(&account.id, session, now);
+    storage.save_state(&state)
+}
+
+#[cfg(feature = "interactive")]
+fn prompt_sudo(account: &Account) -> Result<(), AppError> {
+    if !atty::is(atty::Stream::Stdin) {
+        return Err(AppError::TtyRequired);
+    }
+
+    match keychain::get_pin(&account.id) {
+        Some(pin) => {
+            let entered =
+                inquire::Password::new(&format!("PIN for protected account '{}':", account.id))
+                    .without_confirmation()
+                    .prompt()
+                    .map_err(|e| AppError::config(format!("confirmation cancelled: {e}")))?;
+            if entered != pin {
+                return Err(AppError::invalid_input("incorrect PIN"));
+            }
+        }
+        None => {
+            let confirmed = inquire::Confirm::new(&format!(
+                "'{}' is a protected account. Continue with this destructive operation?",
+                account.id
+            ))
+            .with_default(false)
+            .prompt()
+            .map_err(|e| AppError::config(format!("confirmation cancelled: {e}")))?;
+            if !confirmed {
+                return Err(AppError::invalid_input("operation cancelled"));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Prompt for a new sudo PIN (entered twice, never echoed).
+#[cfg(feature = "interactive")]
+pub fn prompt_new_pin() -> Result<String, AppError> {
+    if !atty::is(atty::Stream::Stdin) {
+        return Err(AppError::TtyRequired);
+    }
+    inquire::Password::new("New PIN:")
+        .with_custom_confirmation_message("Confirm PIN:")
+        .prompt()
+        .map_err(|e| AppError::config(format!("PIN entry cancelled: {e}")))
+}
+
+/// Read a new token from stdin when it is piped, otherwise prompt for it (never echoed).
+///
+/// Tokens are never taken from arguments, which end up in shell history and `ps`.
+#[cfg(feature = "interactive")]
+pub fn read_new_token(id: &str) -> Result<String, AppError> {
+    if !atty::is(atty::Stream::Stdin) {
+        let mut token = String::new();
+        std::io::Read::read_to_string(&mut std::io::stdin(), &mut token)?;
+        return Ok(token.trim().to_string());
+    }
+    inquire::Password::new(&format!("New token for '{id}':"))
+        .without_confirmation()
+        .prompt()
+        .map_err(|e| AppError::config(format!("token entry cancelled: {e}")))
+}
+
+/// Confirm printing a token in plain text. Piped output counts as confirmed by `--plain`.
+#[cfg(feature = "interactive")]
+pub fn confirm_plain_token(id: &str) -> Result<bool, AppError> {
+    if !atty::is(atty::Stream::Stdout) {
+        return Ok(true);
+    }
+    if !atty::is(atty::Stream::Stdin) {
+        return Err(AppError::TtyRequired);
+    }
+    inquire::Confirm::new(&format!("Print the token of '{id}' in plain text?"))
+        .with_default(false)
+        .prompt()
+        .map_err(|e| AppError::config(format!("confirmation cancelled: {e}")))
+}
+
+#[cfg(not(feature = "interactive"))]
+fn prompt_sudo(_account: &Account) -> Result<(), AppError> {
+    Err(AppError::TtyRequired)
+}
+
+/// Identifier of the invoking shell session (the parent process).
+fn session_id() -> u32 {
+    #[cfg(unix)]
+    {
+        std::os::unix::process::parent_id()
+    }
+    #[cfg(not(unix))]
+    {
+        0
+    }
+}
+
+/// Export accounts (one or all) to a backup file, optionally with tokens.
+///
+/// Tokens are encrypted when a passphrase is given. Accounts with a `token_command` keep
+/// their command and never carry a token. Returns the number of accounts exported.
+pub fn export_to_file(
+    storage: &impl Storage,
+    id: Option<&str>,
+    path: &Path,
+    include_tokens: bool,
+    passphrase: Option<&str>,
+) -> Result<usize, AppError> {
+    let accounts = storage.load_accounts()?;
+    let selected: Vec<Account> = match id {
+        Some(id) => vec![
+            accounts
+                .find_account(id)
+                .cloned()
+                .ok_or_else(|| AppError::AccountNotFound(id.into()))?,
+        ],
+        None => accounts.all_accounts().into_iter().cloned().collect(),
+    };
+
+    let mut backup = BackupFile {
+        version: BACKUP_VERSION,
+        groups: if id.is_some() { BTreeMap::new() } else { accounts.groups.clone() },
+        accounts: selected,
+        tokens: None,
+        encrypted_tokens: None,
+    };
+
+    if include_tokens {
+        let mut tokens = BTreeMap::new();
+        for account in backup.accounts.iter().filter(|a| a.token_command.is_none()) {
+            tokens.insert(account.id.clone(), keychain::get_stored_token(&account.id)?);
+        }
+        match passphrase {
+            Some(passphrase) => {
+                backup.encrypted_tokens = Some(backup::encrypt_tokens(&tokens, passphrase)?)
+            }
+            None => backup.tokens = Some(tokens),
+        }
+    }
+
+    let json = serde_json::to_string_pretty(&backup)?;
+    if include_tokens {
+        write_private(path, &json)?;
+    } else {
+        fs::write(path, json)?;
+    }
+    Ok(backup.accounts.len())
+}
+
+/// Write `contents` to a new file at `path` that only the user can read, created with that
+/// mode so the tokens in it are never readable by others; fails when `path` exists.
+fn write_private(path: &Path, contents: &str) -> Result<(), AppError> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::AlreadyExists => {
+            AppError::invalid_input(format!("'{}' already exists", path.display()))
+        }
+        _ => AppError::Io(e),
+    })?;
+    file.write_all(contents.as_bytes())?;
+    Ok(())
+}
+
+/// Import accounts from a backup file, merging them into the existing configuration.
+///
+/// `resolve_conflict` decides what to do with accounts whose ID already exists, and
+/// `passphrase` is only called when the backup carries encrypted tokens.
+pub fn import_from_file(
+    storage: &impl Storage,
+    path: &Path,
+    mut resolve_conflict: impl FnMut(&Account) -> Result<ConflictAction, AppError>,
+    passphrase: impl FnOnce() -> Result<String, AppError>,
+) -> Result<ImportReport, AppError> {
+    let backup: BackupFile = serde_json::from_str(&fs::read_to_string(path)?)?;
+    if backup.version > BACKUP_VERSION {
+        return Err(AppError::invalid_input(format!(
+            "backup version {} is newer than supported version {BACKUP_VERSION}",
+            backup.version
+        )));
+    }
+
+    let tokens = match (&backup.tokens, &backup.encrypted_tokens) {
+        (_, Some(blob)) => backup::decrypt_tokens(blob, &passphrase()?)?,
+        (Some(tokens), None) => tokens.clone(),
+        (None, None) => BTreeMap::new(),
+    };
+
+    let mut accounts = storage.load_accounts()?;
+    let mut report = ImportReport::default();
+    let mut renamed: BTreeMap<String, String> = BTreeMap::new();
+    let mut restore: Vec<(String, String)> = Vec::new();
+
+    for mut account in backup.accounts {
+        let original_id = account.id.clone();
+        let mut overwrite = false;
+        if accounts.find_account(&original_id).is_some() {
+            match resolve_conflict(&account)? {
+                ConflictAction::Skip => {
+                    report.skipped.push(original_id);
+                    continue;
+                }
+                ConflictAction::Overwrite => overwrite = true,
+                ConflictAction::Rename(new_id) => {
+                    account.id = new_id;
+                    renamed.insert(original_id.clone(), account.id.clone());
+                }
+            }
+        }
+
+        let id = account.id.clone();
+        if overwrite {
+            replace_account(&mut accounts, account);
+        } else {
+            accounts.register(account)?;
+        }
+        if let Some(token) = tokens.get(&original_id) {
+            restore.push((id.clone(), token.clone()));
+        }
+        report.imported.push(id);
+    }
+
+    for (name, members) in backup.groups {
+        if accounts.groups.contains_key(&name) {
+            continue;
+        }
+        let members: Vec<String> = members
+            .into_iter()
+            .map(|id| renamed.get(&id).cloned().unwrap_or(id))
+            .filter(|id| accounts.find_account(id).is_some())
+            .collect();
+        if !members.is_empty() {
+            accounts.groups.insert(name, members);
+        }
+    }
+
+    storage.save_accounts(&accounts)?;
+
+    for (id, token) in restore {
+        keychain::store_token(&id, &token)?;
+        report.tokens_restored += 1;
+    }
+    Ok(report)
+}
+
+/// Replace the account with `account`'s ID in place, so it keeps its groups and active state.
+fn replace_account(accounts: &mut AccountsFile, account: Account) {
+    match accounts.find_account_mut(&account.id) {
+        Some(existing) if existing.kind == account.kind => *existing = account,
+        _ => {
+            accounts.personal.retain(|a| a.id != account.id);
+            accounts.work.retain(|a| a.id != account.id);
+            accounts.add_account(account);
+        }
+    }
+}
+
+/// Read the backup passphrase from the environment or prompt for it.
+#[cfg(feature = "interactive")]
+pub fn backup_passphrase(confirm: bool) -> Result<String, AppError> {
+    if let Ok(passphrase) = std::env::var(BACKUP_PASSPHRASE_ENV) {
+        return Ok(passphrase);
+    }
+    if !atty::is(atty::Stream::Stdin) {
+        return Err(AppError::TtyRequired);
+    }
+
+    let prompt = inquire::Password::new("Backup passphrase:");
+    let prompt = if confirm {
+        prompt.with_custom_confirmation_message("Confirm passphrase:")
+    } else {
+        prompt.without_confirmation()
+    };
+    prompt.prompt().map_err(|e| AppError::config(format!("passphrase entry cancelled: {e}")))
+}
+
+/// Ask how to resolve an import conflict.
+#[cfg(feature = "interactive")]
+pub fn prompt_conflict(account: &Account) -> Result<ConflictAction, AppError> {
+    if !atty::is(atty::Stream::Stdin) {
+        return Err(AppError::TtyRequired);
+    }
+
+    let options = vec!["Skip", "Overwrite", "Rename"];
+    let choice =
+        inquire::Select::new(&format!("Account '{}' already exists:", account.id), options)
+            .prompt()
+            .map_err(|e| AppError::config(format!("selection cancelled: {e}")))?;
+
+    match choice {
+        "Overwrite" => Ok(ConflictAction::Overwrite),
+        "Rename" => {
+            let new_id = inquire::Text::new("New account ID:")
+                .prompt()
+                .map_err(|e| AppError::config(format!("rename cancelled: {e}")))?;
+            Ok(ConflictAction::Rename(new_id))
+        }
+        _ => Ok(ConflictAction::Skip),
+    }
+}
+
+/// Move every account's token and sudo PIN, and the named `webhook_secrets`, to another
+/// Keychain service. Webhook secrets are not listed anywhere, so unnamed ones stay behind.
+///
+/// Each secret is copied and read back from `to_service` before anything is removed. If a copy
+/// fails, or `commit` (which switches the `keychain_service` setting) fails, the copies are
+/// deleted and the current service stays in use; otherwise the old entries are deleted.
+/// Returns the IDs of the accounts that had secrets to move.
+pub fn migrate_keychain(
+    storage: &impl Storage,
+    to_service: &str,
+    webhook_secrets: &[String],
+    commit: impl FnOnce() -> Result<(), AppError>,
+) -> Result<Vec<String>, AppError> {
+    let from_service = keychain::service();
+    let to_service = to_service.trim();
+    if to_service.is_empty() {
+        return Err(AppError::invalid_input("keychain service name cannot be empty"));
+    }
+    if to_service == from_service {
+        return Err(AppError::invalid_input(format!(
+            "tokens are already stored in keychain service '{to_service}'"
+        )));
+    }
+
+    let accounts = storage.load_accounts()?;
+    let mut copied = Vec::new();
+    let mut migrated = Vec::new();
+    let result = accounts
+        .all_accounts()
+        .into_iter()
+        .try_for_each(|account| {
+            let before = copied.len();
+            for name in [account.id.clone(), keychain::pin_entry(&account.id)] {
+                copy_secret(&from_service, to_service, &name, &mut copied)?;
+            }
+            if copied.len() > before {
+                migrated.push(account.id.clone());
+            }
+            Ok(())
+        })
+        .and_then(|()| {
+            webhook_secrets.iter().try_for_each(|name| {
+                let entry = keychain::webhook_secret_entry(name);
+                let before = copied.len();
+                copy_secret(&from_service, to_service, &entry, &mut copied)?;
+                if copied.len() == before {
+                    return Err(AppError::keychain(format!(
+                        "no Keychain entry '{entry}' in {from_service}"
+                    )));
+                }
+                Ok(())
+            })
+        })
+        .and_then(|()| commit());
+
+    if let Err(e) = result {
+        for name in &copied {
+            let _ = keychain::delete_in(to_service, name);
+        }
+        return Err(e);
+    }
+    for name in &copied {
+        let _ = keychain::delete_in(&from_service, name);
+    }
+    keychain::set_service(to_service);
+    Ok(migrated)
+}
+
+/// Copy one Keychain secret, if present, and verify it reads back identically.
+fn copy_secret(
+    from_service: &str,
+    to_service: &str,
+    name: &str,
+    copied: &mut Vec<String>,
+) -> Result<(), AppError> {
+    let Some(secret) = keychain::read_in(from_service, name)? else {
+        return Ok(());
+    };
+    keychain::store_in(to_service, name, &secret)?;
+    copied.push(name.to_string());
+    if keychain::read_in(to_service, name)?.as_deref() != Some(secret.as_str()) {
+        return Err(AppError::keychain(format!(
+            "'{name}' could not be read back from keychain service '{to_service}'"
+        )));
+    }
+    Ok(())
+}
+
+/// Move an account's token from the Keychain into a password manager vault.
+///
+/// The account is switched to a command-based lookup and the Keychain entry is removed once
+/// the token has been read back from the vault. Returns the lookup command.
+pub fn export_to_vault(
+    storage: &impl Storage,
+    id: &str,
+    kind: VaultKind,
+    vault_name: Option<&str>,
+) -> Result<String, AppError> {
+    let mut accounts = storage.load_accounts()?;
+    let account =
+        accounts.find_account_mut(id).ok_or_else(|| AppError::AccountNotFound(id.to_string()))?;
+
+    if let Some(command) = &account.token_command {
+        return Err(AppError::invalid_input(format!(
+            "account '{id}' already reads its token from a command: {command}"
+        )));
+    }
+
+    let token = keychain::get_stored_token(id)?;
+    vault::store_token(kind, id, &token, vault_name)?;
+
+    let command = vault::lookup_command(kind, id, vault_name);
+    if keychain::run_token_command(&command)? != token {
+        return Err(AppError::keychain(format!(
+            "token read back from {kind} does not match; keychain entry left in place"
+        )));
+    }
+
+    account.token_command = Some(command.clone());
+    storage.save_accounts(&accounts)?;
+
+    // The vault is now the source of truth
+    let _ = keychain::delete_token(id);
+    Ok(command)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -179,6 +893,206 @@ mod tests {
         assert!(matches!(result, Err(AppError::AccountNotFound(_))));
     }
 
+    #[test]
+    fn export_to_vault_unknown_account_fails() {
+        let storage = MockStorage::default();
+        let result = export_to_vault(&storage, "ghost", VaultKind::Bitwarden, None);
+        assert!(matches!(result, Err(AppError::AccountNotFound(_))));
+    }
+
+    #[test]
+    fn migrate_keychain_rejects_current_or_empty_service() {
+        let storage = MockStorage::default();
+        let commit = || -> Result<(), AppError> { panic!("nothing to commit") };
+        assert!(migrate_keychain(&storage, &keychain::service(), &[], commit).is_err());
+        assert!(migrate_keychain(&storage, "  ", &[], commit).is_err());
+    }
+
+    #[test]
+    fn confirm_destructive_skips_unprotected_accounts() {
+        let storage = MockStorage::default();
+        let account = Account {
+            id: "plain".to_string(),
+            kind: AccountKind::Personal,
+            username: "user".to_string(),
+            default_org: None,
+            protocol: Protocol::Ssh,
+            clone_dir: None,
+            clone_layout: CloneLayout::Flat,
+            clone_options: CloneOptions::default(),
+            browser: None,
+            token_command: None,
+            token_expires_at: None,
+            host: None,
+            protected: false,
+            git: None,
+        };
+        assert!(confirm_destructive(&storage, &account).is_ok());
+    }
+
+    #[test]
+    fn token_expiry_warning_thresholds() {
+        let now = Utc::now();
+        let mut account = Account {
+            id: "work".to_string(),
+            kind: AccountKind::Work,
+            username: "user".to_string(),
+            default_org: None,
+            protocol: Protocol::Ssh,
+            clone_dir: None,
+            clone_layout: CloneLayout::Flat,
+            clone_options: CloneOptions::default(),
+            browser: None,
+            token_command: None,
+            token_expires_at: None,
+            host: None,
+            protected: false,
+            git: None,
+        };
+        assert_eq!(token_expiry_warning(&account, now, 7), None);
+
+        account.token_expires_at = Some(now + chrono::Duration::days(30));
+        assert_eq!(token_expiry_warning(&account, now, 7), None);
+
+        account.token_expires_at = Some(now + chrono::Duration::hours(3 * 24 + 1));
+        let warning = token_expiry_warning(&account, now, 7).unwrap();
+        assert!(warning.contains("expires in 3 days"), "{warning}");
+        assert_eq!(token_expiry_warning(&account, now, 0), None);
+
+        account.token_expires_at = Some(now - chrono::Duration::days(1));
+        assert!(token_expiry_warning(&account, now, 7).unwrap().contains("expired on"));
+    }
+
+    #[test]
+    fn export_with_tokens_creates_a_new_private_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = MockStorage::default();
+        storage.accounts.borrow_mut().personal.push(Account {
+            id: "cmd".to_string(),
+            kind: AccountKind::Personal,
+            username: "user".to_string(),
+            default_org: None,
+            protocol: Protocol::Ssh,
+            clone_dir: None,
+            clone_layout: CloneLayout::Flat,
+            clone_options: CloneOptions::default(),
+            browser: None,
+            token_command: Some("echo token".to_string()),
+            token_expires_at: None,
+            host: None,
+            protected: false,
+            git: None,
+        });
+        let path = dir.path().join("accounts.backup");
+
+        assert_eq!(export_to_file(&storage, None, &path, true, None).unwrap(), 1);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+        let again = export_to_file(&storage, None, &path, true, None);
+        assert!(matches!(again, Err(AppError::InvalidInput(_))));
+    }
+
+    fn write_backup(dir: &tempfile::TempDir, accounts: &[&str]) -> std::path::PathBuf {
+        let backup = BackupFile {
+            version: BACKUP_VERSION,
+            accounts: accounts
+                .iter()
+                .map(|id| Account {
+                    id: id.to_string(),
+                    kind: AccountKind::Work,
+                    username: format!("{id}-user"),
+                    default_org: None,
+                    protocol: Protocol::Https,
+                    clone_dir: None,
+                    clone_layout: CloneLayout::Flat,
+                    clone_options: CloneOptions::default(),
+                    browser: None,
+                    token_command: None,
+                    token_expires_at: None,
+                    host: None,
+                    protected: false,
+                    git: None,
+                })
+                .collect(),
+            groups: BTreeMap::from([("team".to_string(), vec!["a".to_string(), "b".to_string()])]),
+            tokens: None,
+            encrypted_tokens: None,
+        };
+        let path = dir.path().join("accounts.backup");
+        fs::write(&path, serde_json::to_string(&backup).unwrap()).unwrap();
+        path
+    }
+
+    #[test]
+    fn import_merges_and_renames_conflicts() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = write_backup(&dir, &["a", "b"]);
+        let storage = MockStorage::default();
+        import_from_file(&storage, &path, |_| Ok(ConflictAction::Skip), || unreachable!()).unwrap();
+
+        let report = import_from_file(
+            &storage,
+            &path,
+            |acc| Ok(ConflictAction::Rename(format!("{}2", acc.id))),
+            || unreachable!(),
+        )
+        .unwrap();
+
+        assert_eq!(report.imported, vec!["a2", "b2"]);
+        let accounts = storage.load_accounts().unwrap();
+        assert_eq!(accounts.all_accounts().len(), 4);
+        assert_eq!(accounts.active_account_id.as_deref(), Some("a"));
+        assert_eq!(accounts.groups["team"], vec!["a", "b"]);
+    }
+
+    #[test]
+    fn import_overwrites_in_place_keeping_groups_and_active_state() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = write_backup(&dir, &["a"]);
+        let storage = MockStorage::default();
+        import_from_file(&storage, &path, |_| Ok(ConflictAction::Skip), || unreachable!()).unwrap();
+        {
+            let mut accounts = storage.accounts.borrow_mut();
+            accounts.find_account_mut("a").unwrap().username = "local-user".to_string();
+            accounts.set_group("mine", vec!["a".to_string()]).unwrap();
+        }
+
+        let report =
+            import_from_file(&storage, &path, |_| Ok(ConflictAction::Overwrite), || unreachable!())
+                .unwrap();
+
+        assert_eq!(report.imported, vec!["a"]);
+        let accounts = storage.load_accounts().unwrap();
+        assert_eq!(accounts.all_accounts().len(), 1);
+        assert_eq!(accounts.find_account("a").unwrap().username, "a-user");
+        assert_eq!(accounts.active_account_id.as_deref(), Some("a"));
+        assert_eq!(accounts.groups["mine"], vec!["a"]);
+    }
+
+    #[test]
+    fn import_skips_conflicts() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = write_backup(&dir, &["a"]);
+        let storage = MockStorage::default();
+        import_from_file(&storage, &path, |_| Ok(ConflictAction::Skip), || unreachable!()).unwrap();
+
+        let report =
+            import_from_file(&storage, &path, |_| Ok(ConflictAction::Skip), || unreachable!())
+                .unwrap();
+        assert!(report.imported.is_empty());
+        assert_eq!(report.skipped, vec!["a"]);
+    }
+
+    #[test]
+    fn remove_missing_group_fails() {
+        let storage = MockStorage::default();
+        let result = remove_group(&storage, "clients");
+        assert!(matches!(result, Err(AppError::InvalidInput(_))));
+    }
+
     #[test]
     fn show_without_active_fails() {
         let storage = MockStorage::default();
@@ -0,0 +1,168 @@
+//! GitHub Actions usage commands.
+
+use crate::commands::account;
+use crate::error::AppError;
+use crate::github::{GitHubClient, OrgsApi};
+use crate::models::{ActionsUsage, BillingUsageItem};
+use crate::storage::Storage;
+use chrono::{Datelike, NaiveDate, Utc};
+use std::collections::BTreeMap;
+
+const ACTIONS_PRODUCT: &str = "actions";
+
+/// Summarize an organization's Actions minutes and storage per repository.
+///
+/// Usage is read from the billing usage API for every year since `since` (the current year
+/// when omitted) and sorted by cost, most expensive first.
+pub fn usage(
+    storage: &impl Storage,
+    org: &str,
+    since: Option<&str>,
+) -> Result<Vec<ActionsUsage>, AppError> {
+    let since = since.map(parse_date).transpose()?;
+
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+
+    let this_year = Utc::now().year();
+    let first_year = since.map_or(this_year, |d| d.year());
+    let mut items = Vec::new();
+    for year in first_year..=this_year {
+        items.extend(client.get_org_billing_usage(org, year)?);
+    }
+    Ok(summarize(&items, since))
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate, AppError> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+        AppError::invalid_input(format!("invalid date '{value}', expected YYYY-MM-DD"))
+    })
+}
+
+fn summarize(items: &[BillingUsageItem], since: Option<NaiveDate>) -> Vec<ActionsUsage> {
+    let since = since.map(|d| d.format("%Y-%m-%d").to_string());
+    let mut by_repo: BTreeMap<String, ActionsUsage> = BTreeMap::new();
+
+    for item in items {
+        if !item.product.eq_ignore_ascii_case(ACTIONS_PRODUCT) {
+            continue;
+        }
+        if since.as_deref().is_some_and(|since| item.date.get(..10).unwrap_or("") < since) {
+            continue;
+        }
+
+        let repo = item.repository_name.clone().unwrap_or_else(|| "(unattributed)".to_string());
+        let entry = by_repo.entry(repo.clone()).or_insert_with(|| ActionsUsage {
+            repo,
+            minutes: 0.0,
+            storage_gb_hours: 0.0,
+            net_amount: 0.0,
+        });
+        let unit = item.unit_type.to_lowercase();
+        if unit == "minutes" {
+            entry.minutes += item.quantity;
+        } else if unit == "gigabytehours" {
+            entry.storage_gb_hours += item.quantity;
+        }
+        entry.net_amount += item.net_amount;
+    }
+
+    let mut rows: Vec<ActionsUsage> = by_repo.into_values().collect();
+    rows.sort_by(|a, b| b.net_amount.total_cmp(&a.net_amount).then(a.repo.cmp(&b.repo)));
+    rows
+}
+
+/// Render usage rows as CSV with a header line.
+pub fn to_csv(rows: &[ActionsUsage]) -> String {
+    let mut csv = String::from("repo,minutes,storage_gb_hours,net_amount_usd\n");
+    for r in rows {
+        csv.push_str(&format!(
+            "{},{:.0},{:.2},{:.2}\n",
+            csv_field(&r.repo),
+            r.minutes,
+            r.storage_gb_hours,
+            r.net_amount
+        ));
+    }
+    csv
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(
+        date: &str,
+        product: &str,
+        unit: &str,
+        quantity: f64,
+        amount: f64,
+        repo: &str,
+    ) -> BillingUsageItem {
+        BillingUsageItem {
+            date: date.to_string(),
+            product: product.to_string(),
+            sku: String::new(),
+            quantity,
+            unit_type: unit.to_string(),
+            net_amount: amount,
+            repository_name: Some(repo.to_string()),
+        }
+    }
+
+    #[test]
+    fn summarize_aggregates_actions_per_repo() {
+        let items = vec![
+            item("2026-09-01T00:00:00Z", "actions", "Minutes", 100.0, 0.8, "api"),
+            item("2026-09-02T00:00:00Z", "Actions", "Minutes", 50.0, 0.4, "api"),
+            item("2026-09-02T00:00:00Z", "actions", "GigabyteHours", 12.5, 0.1, "api"),
+            item("2026-09-02T00:00:00Z", "actions", "Minutes", 900.0, 7.2, "web"),
+            item("2026-09-02T00:00:00Z", "packages", "GigabyteHours", 99.0, 5.0, "api"),
+        ];
+
+        let rows = summarize(&items, None);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].repo, "web");
+        assert_eq!(rows[1].minutes, 150.0);
+        assert_eq!(rows[1].storage_gb_hours, 12.5);
+        assert!((rows[1].net_amount - 1.3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn summarize_filters_by_since() {
+        let items = vec![
+            item("2026-08-31T00:00:00Z", "actions", "Minutes", 100.0, 0.8, "api"),
+            item("2026-09-01T00:00:00Z", "actions", "Minutes", 10.0, 0.08, "api"),
+        ];
+        let since = NaiveDate::from_ymd_opt(2026, 9, 1);
+        assert_eq!(summarize(&items, since)[0].minutes, 10.0);
+    }
+
+    #[test]
+    fn to_csv_quotes_fields() {
+        let rows = vec![ActionsUsage {
+            repo: "a,b".to_string(),
+            minutes: 12.0,
+            storage_gb_hours: 1.5,
+            net_amount: 0.096,
+        }];
+        assert_eq!(
+            to_csv(&rows),
+            "repo,minutes,storage_gb_hours,net_amount_usd\n\"a,b\",12,1.50,0.10\n"
+        );
+    }
+
+    #[test]
+    fn parse_date_rejects_bad_format() {
+        assert!(parse_date("2026-09-01").is_ok());
+        assert!(parse_date("09/01/2026").is_err());
+    }
+}
@@ -0,0 +1,281 @@
+//! Raw GitHub API commands.
+
+use crate::commands::account;
+use crate::error::AppError;
+use crate::github::{GitHubClient, UsersApi};
+use crate::keychain;
+use crate::logging;
+use crate::models::{AccountRateLimits, RateLimit};
+use crate::remote::resolve_repo;
+use crate::storage::Storage;
+use chrono::{DateTime, Utc};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::process::Command;
+
+/// A request for [`request`], mirroring the `gho api` flags.
+#[derive(Debug, Clone, Default)]
+pub struct ApiRequest {
+    /// Path such as `/repos/{owner}/{repo}/pulls`, or a full URL.
+    pub endpoint: String,
+    /// HTTP method; `POST` when fields are given, otherwise `GET`.
+    pub method: Option<String>,
+    /// `key=value` fields sent as strings.
+    pub raw_fields: Vec<String>,
+    /// `key=value` fields with `true`, `false`, `null` and numbers converted, and `@file`
+    /// (or `@-` for stdin) replaced by the file's contents.
+    pub typed_fields: Vec<String>,
+    /// `Name: value` request headers.
+    pub headers: Vec<String>,
+    /// Follow `Link: rel="next"` headers and merge array pages.
+    pub paginate: bool,
+    /// Repository used for `{owner}` and `{repo}`, detected from git if omitted.
+    pub repo: Option<String>,
+}
+
+/// Send a request with the active account's token and return the response body.
+///
+/// `{owner}`, `{repo}` and `{branch}` in the endpoint are filled in from the repository and
+/// current branch. Fields become query parameters for `GET` and a JSON body otherwise.
+/// JSON responses are pretty-printed; with `paginate`, array pages are concatenated.
+pub fn request(storage: &impl Storage, req: &ApiRequest) -> Result<String, AppError> {
+    let mut fields = Vec::new();
+    for raw in &req.raw_fields {
+        fields.push(parse_field(raw, false)?);
+    }
+    for typed in &req.typed_fields {
+        fields.push(parse_field(typed, true)?);
+    }
+    let headers = req.headers.iter().map(|h| parse_header(h)).collect::<Result<Vec<_>, _>>()?;
+    let endpoint = expand_placeholders(&req.endpoint, |name| match name {
+        "owner" => resolve_repo(storage, req.repo.as_deref()).map(|(owner, _)| owner),
+        "repo" => resolve_repo(storage, req.repo.as_deref()).map(|(_, repo)| repo),
+        _ => current_branch(),
+    })?;
+
+    let method = match &req.method {
+        Some(method) => method.to_uppercase(),
+        None if fields.is_empty() => "GET".to_string(),
+        None => "POST".to_string(),
+    };
+    let (query, body) = if method == "GET" {
+        let query = fields.into_iter().map(|(k, v)| (k, value_to_param(v))).collect();
+        (query, None)
+    } else if fields.is_empty() {
+        (Vec::new(), None)
+    } else {
+        (Vec::new(), Some(Value::Object(fields.into_iter().collect::<Map<_, _>>())))
+    };
+
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+
+    let mut response = client.raw_request(&method, &endpoint, &query, body.as_ref(), &headers)?;
+    let mut pages = vec![response.body];
+    if req.paginate {
+        // Next-page URLs already carry the query string.
+        while let Some(next) = response.next {
+            response = client.raw_request(&method, &next, &[], body.as_ref(), &headers)?;
+            pages.push(response.body);
+        }
+    }
+    Ok(combine_pages(pages))
+}
+
+/// Split `key=value` and, for typed fields, convert the value.
+fn parse_field(field: &str, typed: bool) -> Result<(String, Value), AppError> {
+    let (key, value) =
+        field.split_once('=').filter(|(key, _)| !key.is_empty()).ok_or_else(|| {
+            AppError::invalid_input(format!("invalid field '{field}', expected key=value"))
+        })?;
+    if !typed {
+        return Ok((key.to_string(), Value::String(value.to_string())));
+    }
+
+    let value = match value {
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        "null" => Value::Null,
+        "@-" => {
+            let mut content = String::new();
+            std::io::stdin().read_to_string(&mut content)?;
+            Value::String(content)
+        }
+        _ if value.starts_with('@') => Value::String(std::fs::read_to_string(&value[1..])?),
+        _ => value
+            .parse::<i64>()
+            .map(Value::from)
+            .or_else(|_| value.parse::<f64>().map(Value::from))
+            .unwrap_or_else(|_| Value::String(value.to_string())),
+    };
+    Ok((key.to_string(), value))
+}
+
+fn parse_header(header: &str) -> Result<(String, String), AppError> {
+    header
+        .split_once(':')
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .filter(|(name, _)| !name.is_empty())
+        .ok_or_else(|| {
+            AppError::invalid_input(format!("invalid header '{header}', expected 'Name: value'"))
+        })
+}
+
+fn value_to_param(value: Value) -> String {
+    match value {
+        Value::String(s) => s,
+        other => other.to_string(),
+    }
+}
+
+/// Replace `{name}` placeholders using `lookup`; unknown names are left as is.
+fn expand_placeholders(
+    endpoint: &str,
+    lookup: impl Fn(&str) -> Result<String, AppError>,
+) -> Result<String, AppError> {
+    let mut result = endpoint.to_string();
+    for name in ["owner", "repo", "branch"] {
+        let placeholder = format!("{{{name}}}");
+        if result.contains(&placeholder) {
+            result = result.replace(&placeholder, &lookup(name)?);
+        }
+    }
+    Ok(result)
+}
+
+fn current_branch() -> Result<String, AppError> {
+    let output = logging::command(Command::new("git").args(["symbolic-ref", "--short", "HEAD"]))
+        .output()
+        .map_err(|e| AppError::git(format!("failed to run git: {e}")))?;
+    if !output.status.success() {
+        return Err(AppError::git("{branch} requires a checked-out branch"));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Pretty-print JSON pages, merging them when every page is an array.
+fn combine_pages(pages: Vec<String>) -> String {
+    let parsed: Option<Vec<Value>> = pages
+        .iter()
+        .map(|p| if p.trim().is_empty() { Some(Value::Null) } else { serde_json::from_str(p).ok() })
+        .collect();
+    let Some(values) = parsed else {
+        return pages.concat();
+    };
+    if values.is_empty() || values.iter().all(Value::is_null) {
+        return String::new();
+    }
+
+    let pretty = |v: &Value| serde_json::to_string_pretty(v).unwrap_or_default();
+    if values.len() > 1 && values.iter().all(Value::is_array) {
+        let merged: Vec<Value> =
+            values.into_iter().flat_map(|v| v.as_array().cloned().unwrap_or_default()).collect();
+        return pretty(&Value::Array(merged));
+    }
+    values.iter().map(pretty).collect::<Vec<_>>().join("\n")
+}
+
+/// Resources shown by `api rate-limit`; the API reports several more.
+pub const RATE_LIMIT_RESOURCES: [&str; 3] = ["core", "search", "graphql"];
+
+/// Rate limits of the active account, or of every stored account with `all_accounts`.
+///
+/// With `all_accounts`, an account whose token is missing or rejected is reported with an
+/// error instead of failing the whole command.
+pub fn rate_limit(
+    storage: &impl Storage,
+    all_accounts: bool,
+) -> Result<Vec<AccountRateLimits>, AppError> {
+    if !all_accounts {
+        let (account, token) = account::get_active_with_token(storage)?;
+        let resources = fetch_rate_limits(token)?;
+        return Ok(vec![AccountRateLimits { account: account.id, resources, error: None }]);
+    }
+
+    let accounts = storage.load_accounts()?;
+    Ok(accounts
+        .all_accounts()
+        .into_iter()
+        .map(|account| {
+            let result = keychain::get_account_token(account).and_then(fetch_rate_limits);
+            let (resources, error) = match result {
+                Ok(resources) => (resources, None),
+                Err(e) => (BTreeMap::new(), Some(e.to_string())),
+            };
+            AccountRateLimits { account: account.id.clone(), resources, error }
+        })
+        .collect())
+}
+
+fn fetch_rate_limits(token: String) -> Result<BTreeMap<String, RateLimit>, AppError> {
+    let client = GitHubClient::new(token)?;
+    let mut resources = client.get_rate_limits()?;
+    resources.retain(|name, _| RATE_LIMIT_RESOURCES.contains(&name.as_str()));
+    Ok(resources)
+}
+
+/// Human-readable time until a rate limit resets, e.g. `in 23m (14:05 UTC)`.
+pub fn format_reset(reset: i64, now: DateTime<Utc>) -> String {
+    let Some(at) = DateTime::from_timestamp(reset, 0) else {
+        return "unknown".to_string();
+    };
+    let secs = (at - now).num_seconds();
+    if secs <= 0 {
+        return "now".to_string();
+    }
+    let wait = if secs < 60 { format!("{secs}s") } else { format!("{}m", (secs + 59) / 60) };
+    format!("in {wait} ({} UTC)", at.format("%H:%M"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_field_converts_typed_values() {
+        assert_eq!(parse_field("draft=true", true).unwrap().1, Value::Bool(true));
+        assert_eq!(parse_field("per_page=50", true).unwrap().1, Value::from(50));
+        assert_eq!(parse_field("title=42", false).unwrap().1, Value::from("42"));
+        assert_eq!(parse_field("q=a=b", false).unwrap(), ("q".to_string(), Value::from("a=b")));
+        assert!(parse_field("novalue", false).is_err());
+        assert!(parse_field("=x", false).is_err());
+    }
+
+    #[test]
+    fn parse_header_splits_name_and_value() {
+        assert_eq!(
+            parse_header("Accept: application/vnd.github.raw").unwrap(),
+            ("Accept".to_string(), "application/vnd.github.raw".to_string())
+        );
+        assert!(parse_header("no-colon").is_err());
+    }
+
+    #[test]
+    fn expand_placeholders_only_resolves_used_names() {
+        let endpoint = expand_placeholders("/repos/{owner}/{repo}/pulls", |name| match name {
+            "owner" => Ok("acme".to_string()),
+            "repo" => Ok("api".to_string()),
+            _ => panic!("branch should not be looked up"),
+        })
+        .unwrap();
+        assert_eq!(endpoint, "/repos/acme/api/pulls");
+    }
+
+    #[test]
+    fn combine_pages_merges_arrays() {
+        let merged = combine_pages(vec!["[1, 2]".to_string(), "[3]".to_string()]);
+        assert_eq!(serde_json::from_str::<Value>(&merged).unwrap(), serde_json::json!([1, 2, 3]));
+
+        assert_eq!(combine_pages(vec!["plain text".to_string()]), "plain text");
+        assert_eq!(combine_pages(vec![String::new()]), "");
+    }
+
+    #[test]
+    fn format_reset_is_relative_to_now() {
+        let now = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        assert_eq!(format_reset(1_700_000_000 - 5, now), "now");
+        assert_eq!(format_reset(1_700_000_000 + 30, now), "in 30s (22:13 UTC)");
+        assert_eq!(format_reset(1_700_000_000 + 61, now), "in 2m (22:14 UTC)");
+    }
+}
@@ -0,0 +1,256 @@
+//! Token health of every account (`gho auth status`).
+//!
+//! "Why is gho using the wrong identity?" usually has one of a few answers: a `GH_TOKEN` or
+//! `GITHUB_TOKEN` in the environment overriding every account, a Keychain entry or token command
+//! that cannot be read, or a stored token that belongs to another user. `status` checks each of
+//! them per account and validates the token gho would use against `GET /user`. An environment
+//! override alone is reported through the token source, not as a problem.
+//!
+//! `whoami` answers the narrower question of who each token is, without the diagnostics, and
+//! checks every account at once.
+//!
+//! `git_credential` lets git fetch and push over HTTPS with the same tokens, as a credential
+//! helper (`gho auth git-credential get`).
+
+use crate::config::TokenPrecedence;
+use crate::domain::GITHUB_HOST;
+use crate::error::AppError;
+use crate::github::{GitHubClient, UsersApi};
+use crate::keychain;
+use crate::models::{Account, AccountsFile, AuthStatus, Identity, TokenSource};
+use crate::storage::Storage;
+use std::thread;
+
+/// Username and password git receives from [`git_credential`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitCredential {
+    pub username: String,
+    pub password: String,
+}
+
+impl GitCredential {
+    /// The `key=value` lines of git's credential helper protocol.
+    pub fn to_git(&self) -> String {
+        format!("username={}\npassword={}\n", self.username, self.password)
+    }
+}
+
+/// Check every stored account, in configuration order.
+pub fn status(storage: &impl Storage) -> Result<Vec<AuthStatus>, AppError> {
+    let accounts = storage.load_accounts()?;
+    let active = accounts.active_account_id.as_deref();
+    Ok(accounts
+        .all_accounts()
+        .into_iter()
+        .map(|account| check(account, active == Some(account.id.as_str())))
+        .collect())
+}
+
+/// Who the token of the active account authenticates as, with environment overrides applied
+/// like every other command.
+pub fn whoami(storage: &impl Storage) -> Result<Identity, AppError> {
+    let accounts = storage.load_accounts()?;
+    let account = accounts.active_account().ok_or(AppError::NoActiveAccount)?;
+    Ok(identify(account, true))
+}
+
+/// [`whoami`] for every account, in configuration order. The tokens are checked concurrently.
+pub fn whoami_all(storage: &impl Storage) -> Result<Vec<Identity>, AppError> {
+    let accounts = storage.load_accounts()?;
+    let active = accounts.active_account_id.as_deref();
+    Ok(thread::scope(|scope| {
+        let checks: Vec<_> = accounts
+            .all_accounts()
+            .into_iter()
+            .map(|account| {
+                let active = active == Some(account.id.as_str());
+                scope.spawn(move || identify(account, active))
+            })
+            .collect();
+        checks.into_iter().map(|check| check.join().expect("whoami check panicked")).collect()
+    }))
+}
+
+fn identify(account: &Account, active: bool) -> Identity {
+    let mut identity = Identity {
+        account: account.id.clone(),
+        username: account.username.clone(),
+        active,
+        source: keychain::token_source(account),
+        login: None,
+        mismatch: false,
+        error: None,
+    };
+    let info = keychain::get_account_token(account)
+        .and_then(GitHubClient::new)
+        .and_then(|client| client.get_token_info());
+    match info {
+        Ok(info) => {
+            identity.mismatch = !info.login.eq_ignore_ascii_case(&account.username);
+            identity.login = Some(info.login);
+        }
+        Err(e) => identity.error = Some(e.to_string()),
+    }
+    identity
+}
+
+fn check(account: &Account, active: bool) -> AuthStatus {
+    let source = keychain::token_source(account);
+    let mut status = AuthStatus {
+        account: account.id.clone(),
+        username: account.username.clone(),
+        active,
+        source,
+        stored_secret: false,
+        login: None,
+        scopes: None,
+        rate_limit_remaining: None,
+        problems: Vec::new(),
+    };
+
+    let stored = match &account.token_command {
+        Some(command) => keychain::run_token_command(command),
+        None => keychain::get_stored_token(&account.id),
+    };
+    status.stored_secret = stored.is_ok();
+    let token = match source {
+        TokenSource::GhToken | TokenSource::GithubToken => {
+            if let Err(e) = &stored {
+                status.problems.push(format!("stored token unavailable: {e}"));
+            }
+            keychain::get_account_token(account)
+        }
+        // Fails like every command would: stored tokens are not used at all.
+        _ if keychain::precedence() == TokenPrecedence::Env => keychain::get_account_token(account),
+        TokenSource::TokenCommand | TokenSource::Keychain => stored,
+    };
+    let token = match token {
+        Ok(token) => token,
+        Err(e) => {
+            status.problems.push(format!("no token: {e}"));
+            return status;
+        }
+    };
+
+    match GitHubClient::new(token).and_then(|client| client.get_token_info()) {
+        Ok(info) => {
+            if !info.login.eq_ignore_ascii_case(&account.username) {
+                status.problems.push(format!(
+                    "token authenticates as {}, not {}",
+                    info.login, account.username
+                ));
+            }
+            if info.rate_limit_remaining == Some(0) {
+                status.problems.push("rate limit exhausted".to_string());
+            }
+            status.login = Some(info.login);
+            status.scopes = info.scopes;
+            status.rate_limit_remaining = info.rate_limit_remaining;
+        }
+        Err(e) => status.problems.push(format!("token check failed: {e}")),
+    }
+    status
+}
+
+/// Answer git's credential `get` request, `input` being its `key=value` description of the
+/// remote.
+///
+/// Only HTTPS remotes on github.com or an account's Enterprise host are answered: by the active
+/// account when it belongs to that host, otherwise by the first account that does. A `username`
+/// in the request selects the account with that username. `None` lets git ask its next helper.
+pub fn git_credential(
+    storage: &impl Storage,
+    input: &str,
+) -> Result<Option<GitCredential>, AppError> {
+    let accounts = storage.load_accounts()?;
+    let Some(account) = credential_account(&accounts, input) else {
+        return Ok(None);
+    };
+    let password = keychain::get_account_token(account)?;
+    Ok(Some(GitCredential { username: account.username.clone(), password }))
+}
+
+fn credential_account<'a>(accounts: &'a AccountsFile, input: &str) -> Option<&'a Account> {
+    let field = |key: &str| {
+        input.lines().find_map(|line| line.strip_prefix(key)?.strip_prefix('=')).map(str::trim)
+    };
+    if field("protocol") != Some("https") {
+        return None;
+    }
+    let host = field("host")?;
+    let username = field("username");
+    let mut candidates: Vec<&Account> = accounts
+        .all_accounts()
+        .into_iter()
+        .filter(|a| a.host.as_deref().unwrap_or(GITHUB_HOST).eq_ignore_ascii_case(host))
+        .filter(|a| username.is_none_or(|u| a.username.eq_ignore_ascii_case(u)))
+        .collect();
+    let active = accounts.active_account_id.as_deref();
+    if let Some(i) = candidates.iter().position(|a| Some(a.id.as_str()) == active) {
+        return Some(candidates.swap_remove(i));
+    }
+    candidates.into_iter().next()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_api;
+
+    #[test]
+    fn status_reports_a_token_of_another_user() {
+        let api = mock_api::server();
+        api.route("GET", "/user", 200, serde_json::json!({ "login": "someone-else" }));
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = mock_api::storage(dir.path());
+
+        let statuses = status(&storage).unwrap();
+        assert_eq!(statuses.len(), 1);
+        let status = &statuses[0];
+        assert_eq!(status.account, "mock");
+        assert!(status.active);
+        assert!(status.stored_secret);
+        assert_eq!(status.login.as_deref(), Some("someone-else"));
+        assert!(
+            status.problems.contains(&"token authenticates as someone-else, not mock-user".into())
+        );
+    }
+
+    #[test]
+    fn whoami_all_flags_a_token_of_another_user() {
+        let api = mock_api::server();
+        // The same answer as in `status_reports_a_token_of_another_user`, which shares the route.
+        api.route("GET", "/user", 200, serde_json::json!({ "login": "someone-else" }));
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = mock_api::storage(dir.path());
+
+        let identities = whoami_all(&storage).unwrap();
+        assert_eq!(identities.len(), 1);
+        assert_eq!(identities[0].login.as_deref(), Some("someone-else"));
+        assert!(identities[0].mismatch && identities[0].active);
+        assert!(!identities[0].is_ok());
+        assert_eq!(whoami(&storage).unwrap(), identities[0]);
+    }
+
+    #[test]
+    fn credential_account_prefers_the_active_account_of_the_host() {
+        let accounts: AccountsFile = serde_json::from_str(
+            r#"{
+            "personal": [{"id": "me", "kind": "personal", "username": "dev"}],
+            "work": [
+                {"id": "work", "kind": "work", "username": "dev-acme"},
+                {"id": "ghe", "kind": "work", "username": "dev", "host": "github.acme.com"}
+            ],
+            "active_account_id": "work"
+        }"#,
+        )
+        .unwrap();
+        let id = |input: &str| credential_account(&accounts, input).map(|a| a.id.as_str());
+
+        assert_eq!(id("protocol=https\nhost=github.com\n"), Some("work"));
+        assert_eq!(id("protocol=https\nhost=github.com\nusername=dev\n"), Some("me"));
+        assert_eq!(id("protocol=https\nhost=github.acme.com\n"), Some("ghe"));
+        assert_eq!(id("protocol=https\nhost=gitlab.com\n"), None);
+        assert_eq!(id("protocol=http\nhost=github.com\n"), None);
+    }
+}
@@ -0,0 +1,222 @@
+//! Setup script generation (`gho bootstrap emit`).
+//!
+//! The script recreates settings, accounts, groups and the active account with `gho` commands.
+//! Tokens are never written to it: it prompts for each one, or runs the account's
+//! `token_command`.
+
+use crate::config::Settings;
+use crate::domain::shell_join;
+use crate::error::AppError;
+use crate::models::{Account, CloneLayout};
+use crate::storage::Storage;
+
+const HEADER: &str = r#"#!/usr/bin/env bash
+# Recreates a gho setup; generated by `gho bootstrap emit`.
+# Tokens are not included: you are prompted for each one, unless the account
+# reads it from a token command.
+set -euo pipefail
+
+command -v gho >/dev/null || { echo "gho is not installed" >&2; exit 1; }
+
+prompt_token() {
+  local token
+  read -rsp "Token for $1: " token </dev/tty
+  echo >&2
+  printf '%s' "$token"
+}
+"#;
+
+/// A bash script that recreates the current setup on another machine.
+pub fn emit(settings: &Settings, storage: &impl Storage) -> Result<String, AppError> {
+    let accounts = storage.load_accounts()?;
+    let mut script = HEADER.to_string();
+
+    let defaults = Settings::default();
+    let changed: Vec<&str> = Settings::KEYS
+        .into_iter()
+        .filter(|key| {
+            *key != "keychain_service" && setting(settings, key) != setting(&defaults, key)
+        })
+        .collect();
+    if !changed.is_empty() {
+        script.push_str("\n# Settings\n");
+        for key in changed {
+            push_gho(&mut script, &["config", "set", key, &setting(settings, key)]);
+        }
+    }
+
+    for account in accounts.all_accounts() {
+        script.push_str(&format!("\n# Account {} ({})\n", account.id, account.username));
+        push_account(&mut script, account);
+    }
+
+    if !accounts.groups.is_empty() {
+        script.push_str("\n# Groups\n");
+        for (name, ids) in &accounts.groups {
+            let mut args = vec!["account", "group", "set", name];
+            args.extend(ids.iter().map(String::as_str));
+            push_gho(&mut script, &args);
+        }
+    }
+
+    if settings.keychain_service != defaults.keychain_service {
+        script.push_str("\n# Keychain service\n");
+        push_gho(
+            &mut script,
+            &["account", "migrate-keychain", "--to-service", &settings.keychain_service],
+        );
+    }
+    if let Some(id) = &accounts.active_account_id {
+        script.push_str("\n# Active account\n");
+        push_gho(&mut script, &["account", "use", id]);
+    }
+    Ok(script)
+}
+
+fn push_account(script: &mut String, account: &Account) {
+    match &account.token_command {
+        Some(command) => script.push_str(&format!("token=$({command})\n")),
+        None => script.push_str(&format!(
+            "token=$(prompt_token {})\n",
+            shell_join(&[format!("{} ({})", account.id, account.username)])
+        )),
+    }
+
+    let kind = account.kind.to_string();
+    let protocol = account.protocol.to_string();
+    let mut args = vec!["-u", &account.username, "-k", &kind, "-p", &protocol];
+    if let Some(host) = &account.host {
+        args.extend(["--host", host]);
+    }
+    if let Some(org) = &account.default_org {
+        args.extend(["-o", org]);
+    }
+    if let Some(dir) = &account.clone_dir {
+        args.extend(["-d", dir]);
+    }
+    match account.clone_layout {
+        CloneLayout::Flat => {}
+        CloneLayout::OwnerRepo => args.extend(["--clone-layout", "owner/repo"]),
+        CloneLayout::HostOwnerRepo => args.extend(["--clone-layout", "host/owner/repo"]),
+    }
+    script.push_str(&format!(
+        "gho account add {} -t \"$token\" {}\n",
+        shell_join(std::slice::from_ref(&account.id)),
+        join(&args)
+    ));
+
+    if !account.clone_options.is_empty() {
+        let options = account.clone_options.args();
+        let mut args = vec!["account", "clone-defaults", &account.id];
+        args.extend(options.iter().map(String::as_str));
+        push_gho(script, &args);
+    }
+    if let Some(browser) = &account.browser {
+        push_gho(script, &["account", "browser", &account.id, browser]);
+    }
+    if account.protected {
+        push_gho(script, &["account", "protect", &account.id]);
+    }
+    if let Some(git) = &account.git {
+        let mut args = vec!["account", "setup-gitconfig", &account.id];
+        args.extend(["--name", &git.name, "--email", &git.email, "--gitdir", &git.gitdir]);
+        if let Some(key) = &git.signing_key {
+            args.extend(["--signing-key", key]);
+        }
+        if let Some(key) = &git.ssh_key {
+            args.extend(["--ssh-key", key]);
+        }
+        push_gho(script, &args);
+    }
+}
+
+fn push_gho(script: &mut String, args: &[&str]) {
+    script.push_str(&format!("gho {}\n", join(args)));
+}
+
+fn join(args: &[&str]) -> String {
+    shell_join(&args.iter().map(|a| a.to_string()).collect::<Vec<_>>())
+}
+
+fn setting(settings: &Settings, key: &str) -> String {
+    settings.get(key).expect("known key")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::models::{AccountKind, AccountsFile, CloneOptions, GitIdentity, Protocol};
+    use crate::storage::FilesystemStorage;
+    use tempfile::TempDir;
+
+    fn account(id: &str, kind: AccountKind) -> Account {
+        Account {
+            id: id.to_string(),
+            kind,
+            username: format!("{id}-user"),
+            default_org: None,
+            protocol: Protocol::Ssh,
+            clone_dir: None,
+            clone_layout: CloneLayout::Flat,
+            clone_options: CloneOptions::default(),
+            browser: None,
+            token_command: None,
+            token_expires_at: None,
+            host: None,
+            protected: false,
+            git: None,
+        }
+    }
+
+    #[test]
+    fn emit_recreates_accounts_without_tokens() {
+        let dir = TempDir::new().unwrap();
+        let storage = FilesystemStorage::new(&Config::with_path(dir.path().to_path_buf()));
+        let mut work = account("work", AccountKind::Work);
+        work.default_org = Some("acme".into());
+        work.clone_dir = Some("~/work".into());
+        work.clone_layout = CloneLayout::OwnerRepo;
+        work.clone_options = CloneOptions { depth: Some(1), ..Default::default() };
+        work.browser = Some("firefox -P {account}".into());
+        work.protected = true;
+        work.git = Some(GitIdentity {
+            name: "Work User".into(),
+            email: "work@acme.com".into(),
+            signing_key: None,
+            ssh_key: Some("~/.ssh/id_work".into()),
+            gitdir: "~/work/".into(),
+        });
+        let mut bot = account("bot", AccountKind::Personal);
+        bot.token_command = Some("op read op://Private/gho-bot/token".into());
+        storage
+            .save_accounts(&AccountsFile {
+                personal: vec![bot],
+                work: vec![work],
+                active_account_id: Some("work".into()),
+                groups: [("all".to_string(), vec!["work".to_string(), "bot".to_string()])].into(),
+            })
+            .unwrap();
+        let mut settings = Settings::default();
+        settings.set("limit", "50").unwrap();
+
+        let script = emit(&settings, &storage).unwrap();
+        assert!(script.contains("gho config set limit 50\n"));
+        assert!(!script.contains("config set format"));
+        assert!(script.contains("token=$(prompt_token 'work (work-user)')\n"));
+        assert!(script.contains(
+            "gho account add work -t \"$token\" -u work-user -k work -p ssh -o acme -d '~/work' \
+             --clone-layout owner/repo\n"
+        ));
+        assert!(script.contains("gho account clone-defaults work --depth=1\n"));
+        assert!(script.contains("gho account browser work 'firefox -P {account}'\n"));
+        assert!(script.contains("gho account protect work\n"));
+        assert!(script.contains(
+            "gho account setup-gitconfig work --name 'Work User' --email work@acme.com \
+             --gitdir '~/work/' --ssh-key '~/.ssh/id_work'\n"
+        ));
+        assert!(script.contains("token=$(op read op://Private/gho-bot/token)\n"));
+        assert!(script.contains("gho account group set all work bot\n"));
+        assert!(script.ends_with("gho account use work\n"));
+    }
+}
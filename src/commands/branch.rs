@@ -0,0 +1,257 @@
+//! Remote branch management (`gho branch`).
+//!
+//! Busy repositories collect hundreds of branches whose work landed long ago. `list` marks the
+//! ones the default branch already has, commit by commit or as the tip of a merged (e.g.
+//! squashed) pull request, and `delete_merged` removes them in bulk.
+
+use crate::commands::account;
+use crate::error::AppError;
+use crate::github::{GitHubClient, ReposApi};
+use crate::models::{Branch, BranchRefNode, Execution, ExecutionMode, ProgressOutcome};
+use crate::progress::Progress;
+use crate::remote::resolve_repo;
+use crate::storage::Storage;
+use chrono::{DateTime, Duration, Utc};
+
+/// Operation name of `branch delete --merged` progress events.
+const DELETE_OPERATION: &str = "branch delete";
+
+/// Which branches [`list`] and [`delete_merged`] keep. The default branch never matches a
+/// filter.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BranchFilter {
+    /// Only branches the default branch already has.
+    pub merged: bool,
+    /// Only branches without commits for this long.
+    pub stale: Option<Duration>,
+}
+
+impl BranchFilter {
+    fn is_empty(&self) -> bool {
+        !self.merged && self.stale.is_none()
+    }
+
+    fn matches(&self, branch: &Branch, now: DateTime<Utc>) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+        let stale = match self.stale {
+            Some(age) => branch.committed_at.is_some_and(|at| at < now - age),
+            None => true,
+        };
+        !branch.default && (!self.merged || branch.merged) && stale
+    }
+}
+
+/// Outcome of [`delete_merged`]: the matching branches and, unless a dry run, per-branch
+/// errors (e.g. protected branches).
+#[derive(Debug, Default)]
+pub struct BranchDeletion {
+    pub deleted: Vec<Branch>,
+    pub failed: Vec<(Branch, AppError)>,
+}
+
+/// List the branches of a repository matching `filter`, sorted by name.
+pub fn list(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    filter: BranchFilter,
+) -> Result<Vec<Branch>, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+    branches(&client, &owner, &repo, filter)
+}
+
+/// Delete one branch. A dry run reports the call instead.
+pub fn delete(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    name: &str,
+    mode: ExecutionMode,
+) -> Result<Execution<()>, AppError> {
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+    if mode.is_dry_run() {
+        return Ok(Execution::Planned(vec![format!(
+            "DELETE /repos/{owner}/{repo}/git/refs/heads/{name}"
+        )]));
+    }
+    let (_account, token) = account::get_active_for_destructive(storage)?;
+    GitHubClient::new(token)?.delete_branch(&owner, &repo, name).map(Execution::Done)
+}
+
+/// Delete every merged branch matching `stale` as well.
+///
+/// All branches are listed before anything is deleted. In a dry run, matching branches are
+/// returned without deleting.
+pub fn delete_merged(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    stale: Option<Duration>,
+    mode: ExecutionMode,
+    progress: Progress,
+) -> Result<BranchDeletion, AppError> {
+    let (_account, token) = if mode.is_dry_run() {
+        account::get_active_with_token(storage)?
+    } else {
+        account::get_active_for_destructive(storage)?
+    };
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+    let candidates = branches(&client, &owner, &repo, BranchFilter { merged: true, stale })?;
+
+    let mut result = BranchDeletion::default();
+    let total = candidates.len();
+    for (i, branch) in candidates.into_iter().enumerate() {
+        if mode.is_dry_run() {
+            result.deleted.push(branch);
+            continue;
+        }
+        progress.started(DELETE_OPERATION, &branch.name, i + 1, total);
+        match client.delete_branch(&owner, &repo, &branch.name) {
+            Ok(()) => {
+                progress.completed(
+                    DELETE_OPERATION,
+                    &branch.name,
+                    ProgressOutcome::Succeeded,
+                    None,
+                );
+                result.deleted.push(branch);
+            }
+            Err(e) => {
+                let error = e.to_string();
+                progress.completed(
+                    DELETE_OPERATION,
+                    &branch.name,
+                    ProgressOutcome::Failed,
+                    Some(&error),
+                );
+                result.failed.push((branch, e));
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Make `name` the default branch. Returns `false` when it already is.
+pub fn set_default(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    name: &str,
+) -> Result<bool, AppError> {
+    let (_account, token) = account::get_active_for_destructive(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+
+    if client.get_repo(&owner, &repo)?.default_branch.as_deref() == Some(name) {
+        return Ok(false);
+    }
+    client.set_default_branch(&owner, &repo, name)?;
+    Ok(true)
+}
+
+/// Parse an age like `90d` or `12w` (a bare number is days).
+pub fn parse_age(value: &str) -> Result<Duration, AppError> {
+    let invalid = || AppError::invalid_input(format!("invalid age '{value}', expected e.g. 90d"));
+    let (number, unit) = match value.strip_suffix(['d', 'w']) {
+        Some(number) => (number, &value[number.len()..]),
+        None => (value, "d"),
+    };
+    let number: i64 = number.parse().map_err(|_| invalid())?;
+    if number < 0 {
+        return Err(invalid());
+    }
+    Ok(if unit == "w" { Duration::weeks(number) } else { Duration::days(number) })
+}
+
+fn branches(
+    client: &impl ReposApi,
+    owner: &str,
+    repo: &str,
+    filter: BranchFilter,
+) -> Result<Vec<Branch>, AppError> {
+    let default_branch = client.get_repo(owner, repo)?.default_branch.ok_or_else(|| {
+        AppError::github_api(format!("{owner}/{repo} has no default branch (empty repository?)"))
+    })?;
+    let now = Utc::now();
+    let mut branches: Vec<Branch> = client
+        .list_branch_refs(owner, repo, &default_branch)?
+        .into_iter()
+        .map(|node| to_branch(node, &default_branch))
+        .filter(|branch| filter.matches(branch, now))
+        .collect();
+    branches.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(branches)
+}
+
+fn to_branch(node: BranchRefNode, default_branch: &str) -> Branch {
+    let default = node.name == default_branch;
+    let pull_request = node
+        .associated_pull_requests
+        .nodes
+        .iter()
+        .find(|pr| pr.head_ref_oid == node.target.oid)
+        .map(|pr| pr.number);
+    let contained = node.compare.as_ref().is_some_and(|c| c.behind_count == 0);
+    Branch {
+        name: node.name,
+        sha: node.target.oid,
+        committed_at: node.target.committed_date,
+        default,
+        merged: !default && (contained || pull_request.is_some()),
+        pull_request,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(name: &str, oid: &str, behind: u64, merged_head: Option<&str>) -> BranchRefNode {
+        let pulls: Vec<_> = merged_head
+            .map(|head| serde_json::json!({ "number": 42, "headRefOid": head }))
+            .into_iter()
+            .collect();
+        serde_json::from_value(serde_json::json!({
+            "name": name,
+            "target": { "oid": oid, "committedDate": "2026-01-01T00:00:00Z" },
+            "compare": { "behindCount": behind },
+            "associatedPullRequests": { "nodes": pulls },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn to_branch_counts_squash_merged_pull_requests_as_merged() {
+        assert!(to_branch(node("contained", "a1", 0, None), "main").merged);
+        let squashed = to_branch(node("squashed", "b2", 3, Some("b2")), "main");
+        assert!(squashed.merged);
+        assert_eq!(squashed.pull_request, Some(42));
+        // New commits after the merge are not on the default branch.
+        assert!(!to_branch(node("reused", "c3", 1, Some("old")), "main").merged);
+        assert!(!to_branch(node("main", "d4", 0, None), "main").merged);
+    }
+
+    #[test]
+    fn filter_skips_the_default_branch_and_recent_commits() {
+        let now: DateTime<Utc> = "2026-06-01T00:00:00Z".parse().unwrap();
+        let old = to_branch(node("old", "a1", 2, None), "main");
+        let main = to_branch(node("main", "b2", 0, None), "main");
+
+        let stale = BranchFilter { merged: false, stale: Some(Duration::days(90)) };
+        assert!(stale.matches(&old, now));
+        assert!(!stale.matches(&main, now));
+        assert!(!stale.matches(&old, "2026-02-01T00:00:00Z".parse().unwrap()));
+        assert!(!BranchFilter { merged: true, stale: None }.matches(&old, now));
+        assert!(BranchFilter::default().matches(&main, now));
+    }
+
+    #[test]
+    fn parse_age_accepts_days_and_weeks() {
+        assert_eq!(parse_age("90d").unwrap(), Duration::days(90));
+        assert_eq!(parse_age("2w").unwrap(), Duration::weeks(2));
+        assert_eq!(parse_age("30").unwrap(), Duration::days(30));
+        assert!(parse_age("3 months").is_err());
+        assert!(parse_age("-1d").is_err());
+    }
+}
@@ -0,0 +1,138 @@
+//! Required status check commands (`repo checks-required`).
+
+use crate::commands::account;
+use crate::domain::parse_repo_spec;
+use crate::error::AppError;
+use crate::github::{GitHubClient, ReposApi};
+use crate::models::{RequiredChecksChange, RequiredChecksEntry};
+use crate::storage::Storage;
+use serde::Deserialize;
+use std::path::Path;
+
+/// File format accepted by [`load_file`].
+#[derive(Debug, Deserialize)]
+struct RequiredChecksFile {
+    #[serde(default, rename = "repo")]
+    repos: Vec<RequiredChecksEntry>,
+}
+
+/// Apply required status check contexts to every entry.
+///
+/// Failures are recorded per repository instead of aborting, so one unprotected branch
+/// does not stop a bulk update.
+pub fn apply(
+    storage: &impl Storage,
+    entries: &[RequiredChecksEntry],
+) -> Result<Vec<RequiredChecksChange>, AppError> {
+    for entry in entries {
+        parse_repo_spec(&entry.repo)?;
+    }
+
+    let (_account, token) = account::get_active_for_destructive(storage)?;
+    let client = GitHubClient::new(token)?;
+
+    Ok(entries.iter().map(|entry| apply_entry(&client, entry)).collect())
+}
+
+fn apply_entry(client: &impl ReposApi, entry: &RequiredChecksEntry) -> RequiredChecksChange {
+    let mut change = RequiredChecksChange {
+        repo: entry.repo.clone(),
+        branch: entry.branch.clone(),
+        added: Vec::new(),
+        removed: Vec::new(),
+        error: None,
+    };
+
+    let (owner, repo) = parse_repo_spec(&entry.repo).expect("validated in apply");
+    let result =
+        client.get_required_status_checks(&owner, &repo, &entry.branch).and_then(|current| {
+            let (added, removed) = diff_contexts(&current.contexts, &entry.contexts);
+            if !added.is_empty() || !removed.is_empty() {
+                client.update_required_status_checks(
+                    &owner,
+                    &repo,
+                    &entry.branch,
+                    &entry.contexts,
+                )?;
+            }
+            Ok((added, removed))
+        });
+
+    match result {
+        Ok((added, removed)) => {
+            change.added = added;
+            change.removed = removed;
+        }
+        Err(AppError::NotFound(_)) => {
+            change.error = Some(format!("branch '{}' is not protected", entry.branch));
+        }
+        Err(e) => change.error = Some(e.to_string()),
+    }
+    change
+}
+
+/// Read desired required checks from a TOML file of `[[repo]]` tables.
+pub fn load_file(path: &Path) -> Result<Vec<RequiredChecksEntry>, AppError> {
+    let content = std::fs::read_to_string(path)?;
+    parse_file(&content)
+        .map_err(|e| AppError::invalid_input(format!("invalid {}: {e}", path.display())))
+}
+
+fn parse_file(content: &str) -> Result<Vec<RequiredChecksEntry>, String> {
+    let file: RequiredChecksFile = toml::from_str(content).map_err(|e| e.message().to_string())?;
+    if file.repos.is_empty() {
+        return Err("no [[repo]] entries".to_string());
+    }
+    Ok(file.repos)
+}
+
+/// Contexts to add and remove to turn `current` into `desired`.
+fn diff_contexts(current: &[String], desired: &[String]) -> (Vec<String>, Vec<String>) {
+    let added = desired.iter().filter(|c| !current.contains(c)).cloned().collect();
+    let removed = current.iter().filter(|c| !desired.contains(c)).cloned().collect();
+    (added, removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn diff_contexts_reports_renames() {
+        let (added, removed) =
+            diff_contexts(&strings(&["build", "ci/test"]), &strings(&["build", "test"]));
+        assert_eq!(added, strings(&["test"]));
+        assert_eq!(removed, strings(&["ci/test"]));
+    }
+
+    #[test]
+    fn parse_file_defaults_branch_to_main() {
+        let entries = parse_file(
+            r#"
+            [[repo]]
+            repo = "acme/api"
+            contexts = ["build", "test"]
+
+            [[repo]]
+            repo = "acme/web"
+            branch = "develop"
+            contexts = ["lint"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].branch, "main");
+        assert_eq!(entries[1].branch, "develop");
+        assert_eq!(entries[1].contexts, strings(&["lint"]));
+    }
+
+    #[test]
+    fn parse_file_rejects_empty() {
+        assert!(parse_file("").is_err());
+    }
+}
@@ -0,0 +1,161 @@
+//! Working state of local clones (`gho repo status`).
+//!
+//! Before switching machines it is easy to forget a dirty tree or a branch that was never
+//! pushed. `status` finds the active account's clones with the `scan` module and asks git for
+//! each one's branch, changes and commits missing on the remote. Only local data is read, so
+//! ahead and behind counts are as of the last fetch.
+
+use crate::domain::GITHUB_HOST;
+use crate::error::AppError;
+use crate::logging;
+use crate::models::{Account, CloneStatus};
+use crate::remote;
+use crate::scan;
+use crate::storage::Storage;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Status of every clone of the active account under `root`, sorted by path.
+///
+/// Without `root`, the account's clone directory (or the current directory) is scanned. A clone
+/// belongs to the account when its `origin` is on the account's host and, outside the clone
+/// directory, owned by its user or default organization.
+pub fn status(
+    storage: &impl Storage,
+    root: Option<&Path>,
+    max_depth: usize,
+    refresh: bool,
+) -> Result<Vec<CloneStatus>, AppError> {
+    let account =
+        storage.load_accounts()?.active_account().cloned().ok_or(AppError::NoActiveAccount)?;
+    let in_clone_dir = root.is_none() && account.clone_dir.is_some();
+    let root = match root {
+        Some(root) => root.to_path_buf(),
+        None => PathBuf::from(account.clone_dir.as_deref().unwrap_or(".")),
+    };
+
+    let host = account.host.as_deref().unwrap_or(GITHUB_HOST);
+    Ok(scan::discover(storage, &root, max_depth, refresh)?
+        .into_iter()
+        .filter_map(|dir| {
+            let (origin_host, owner, name) = remote::origin_of(&dir)?;
+            let owned =
+                origin_host.eq_ignore_ascii_case(host) && (in_clone_dir || owns(&account, &owner));
+            owned.then(|| inspect(format!("{owner}/{name}"), dir))
+        })
+        .collect())
+}
+
+fn owns(account: &Account, owner: &str) -> bool {
+    owner.eq_ignore_ascii_case(&account.username)
+        || account.default_org.as_deref().is_some_and(|org| owner.eq_ignore_ascii_case(org))
+}
+
+/// Read the state of the clone in `dir`.
+fn inspect(repo: String, path: PathBuf) -> CloneStatus {
+    let mut status = CloneStatus { repo, path, ..Default::default() };
+    match git(&status.path, &["status", "--porcelain=v2", "--branch"]) {
+        Ok(output) => parse_porcelain(&output, &mut status),
+        Err(e) => {
+            status.error = Some(e);
+            return status;
+        }
+    }
+    // Without an upstream, count what no remote branch has, e.g. a new local branch.
+    status.unpushed = match status.upstream {
+        Some(_) => status.ahead,
+        None => git(&status.path, &["rev-list", "--count", "HEAD", "--not", "--remotes"])
+            .ok()
+            .and_then(|count| count.trim().parse().ok())
+            .unwrap_or(0),
+    };
+    status
+}
+
+/// Fill branch, tracking and change counts from `git status --porcelain=v2 --branch`.
+fn parse_porcelain(output: &str, status: &mut CloneStatus) {
+    for line in output.lines() {
+        if let Some(head) = line.strip_prefix("# branch.head ") {
+            status.branch = (head != "(detached)").then(|| head.to_string());
+        } else if let Some(upstream) = line.strip_prefix("# branch.upstream ") {
+            status.upstream = Some(upstream.to_string());
+        } else if let Some(counts) = line.strip_prefix("# branch.ab ") {
+            for count in counts.split_whitespace() {
+                if let Some(ahead) = count.strip_prefix('+') {
+                    status.ahead = ahead.parse().unwrap_or(0);
+                } else if let Some(behind) = count.strip_prefix('-') {
+                    status.behind = behind.parse().unwrap_or(0);
+                }
+            }
+        } else if line.starts_with("1 ") || line.starts_with("2 ") || line.starts_with("u ") {
+            status.changed += 1;
+        } else if line.starts_with("? ") {
+            status.untracked += 1;
+        }
+    }
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<String, String> {
+    let mut command = Command::new("git");
+    let output = logging::command(command.arg("-C").arg(dir).args(args))
+        .output()
+        .map_err(|e| format!("failed to run git: {e}"))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn run_git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    #[test]
+    fn parse_porcelain_reads_branch_tracking_and_changes() {
+        let output = "# branch.oid 1f3c\n# branch.head main\n# branch.upstream origin/main\n\
+                      # branch.ab +2 -1\n1 .M N... 100644 100644 100644 a b src/lib.rs\n\
+                      2 R. N... 100644 100644 100644 a b R100 new.rs\told.rs\n\
+                      u UU N... 1 2 3 4 a b c conflict.rs\n? notes.txt\n! target\n";
+        let mut status = CloneStatus::default();
+        parse_porcelain(output, &mut status);
+        assert_eq!(status.branch.as_deref(), Some("main"));
+        assert_eq!(status.upstream.as_deref(), Some("origin/main"));
+        assert_eq!((status.ahead, status.behind), (2, 1));
+        assert_eq!((status.changed, status.untracked), (3, 1));
+
+        let mut detached = CloneStatus::default();
+        parse_porcelain("# branch.oid 1f3c\n# branch.head (detached)\n", &mut detached);
+        assert_eq!(detached.branch, None);
+    }
+
+    #[test]
+    fn inspect_counts_commits_no_remote_has() {
+        let dir = TempDir::new().unwrap();
+        let repo = dir.path().join("api");
+        fs::create_dir(&repo).unwrap();
+        run_git(&repo, &["init", "--quiet", "--initial-branch", "feature"]);
+        run_git(&repo, &["commit", "--quiet", "--allow-empty", "-m", "wip"]);
+        fs::write(repo.join("todo.txt"), "").unwrap();
+
+        let status = inspect("acme/api".into(), repo);
+        assert_eq!(status.branch.as_deref(), Some("feature"));
+        assert_eq!(status.upstream, None);
+        assert_eq!((status.unpushed, status.untracked, status.changed), (1, 1, 0));
+        assert!(status.in_flight());
+        assert_eq!(status.error, None);
+    }
+}
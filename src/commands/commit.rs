@@ -0,0 +1,71 @@
+//! Commit commands.
+
+use crate::commands::account;
+use crate::error::AppError;
+use crate::github::{GitHubClient, ReposApi};
+use crate::models::{Commit, CommitSignature};
+use crate::remote::resolve_repo;
+use crate::storage::Storage;
+
+/// Fetch a commit by SHA, branch or tag with its signature status.
+pub fn view(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    reference: &str,
+) -> Result<CommitSignature, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+
+    Ok(signature(client.get_commit(&owner, &repo, reference)?))
+}
+
+/// Summarize a commit's first message line, author and verification.
+pub(crate) fn signature(commit: Commit) -> CommitSignature {
+    let (verified, reason) = match commit.commit.verification {
+        Some(v) => (v.verified, v.reason),
+        None => (false, "unknown".to_string()),
+    };
+    let git_author = commit.commit.author;
+    let author = commit
+        .author
+        .map(|a| a.login)
+        .or_else(|| git_author.as_ref().map(|a| a.name.clone()))
+        .unwrap_or_else(|| "unknown".to_string());
+
+    CommitSignature {
+        sha: commit.sha,
+        subject: commit.commit.message.lines().next().unwrap_or_default().to_string(),
+        author,
+        date: git_author.map(|a| a.date),
+        verified,
+        reason,
+        url: commit.html_url,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signature_uses_subject_login_and_verification() {
+        let commit: Commit = serde_json::from_str(
+            r#"{
+                "sha": "abc123",
+                "author": {"login": "octocat"},
+                "commit": {
+                    "message": "Fix build\n\nDetails",
+                    "author": {"name": "Octo Cat", "date": "2026-10-01T00:00:00Z"},
+                    "verification": {"verified": false, "reason": "unsigned"}
+                }
+            }"#,
+        )
+        .unwrap();
+        let s = signature(commit);
+        assert_eq!(s.subject, "Fix build");
+        assert_eq!(s.author, "octocat");
+        assert!(!s.verified);
+        assert_eq!(s.reason, "unsigned");
+    }
+}
@@ -0,0 +1,20 @@
+//! User settings commands (`gho config`).
+
+use crate::config::{Config, Settings};
+use crate::error::AppError;
+
+/// Read a single setting.
+pub fn get(config: &Config, key: &str) -> Result<String, AppError> {
+    config.settings.get(key)
+}
+
+/// Update a setting and persist it to `config.toml`.
+pub fn set(config: &mut Config, key: &str, value: &str) -> Result<(), AppError> {
+    config.settings.set(key, value)?;
+    config.save_settings()
+}
+
+/// All settings as `(key, value)` pairs.
+pub fn list(config: &Config) -> Vec<(&'static str, String)> {
+    Settings::KEYS.iter().map(|key| (*key, config.settings.get(key).expect("known key"))).collect()
+}
@@ -0,0 +1,253 @@
+//! Conflict resolution assistant (`pr fix-conflicts`).
+//!
+//! The pull request is rebased onto its base branch in a dedicated worktree, so the user's
+//! own checkout is never touched. When the rebase stops on conflicts the worktree is left
+//! for the user to resolve; `--continue` finishes the rebase and pushes.
+
+use crate::commands::account;
+use crate::domain::{GITHUB_HOST, build_clone_url, parse_repo_spec};
+use crate::error::AppError;
+use crate::github::{GitHubClient, PullsApi};
+use crate::logging;
+use crate::models::{Account, PullRequest};
+use crate::remote::resolve_repo;
+use crate::storage::Storage;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Result of a `fix-conflicts` step.
+#[derive(Debug, Clone)]
+pub enum FixOutcome {
+    /// The rebase completed and the branch was force-pushed.
+    Pushed { branch: String, sha: String },
+    /// The rebase stopped; conflicts must be resolved in `worktree`.
+    Conflicts { worktree: PathBuf, files: Vec<String> },
+}
+
+/// Start rebasing a pull request onto its base branch in a new worktree.
+pub fn start(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    number: u64,
+) -> Result<FixOutcome, AppError> {
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+    let worktree = worktree_dir(&owner, &repo, number);
+    if worktree.exists() {
+        return Err(AppError::invalid_input(format!(
+            "a fix for #{number} is already in progress at {}, use --continue or --abort",
+            worktree.display()
+        )));
+    }
+
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let pull_request = client.get_pull_request(&owner, &repo, number)?;
+    let base = base_branch(&pull_request)?;
+
+    git(None, &["fetch", "origin", base, &format!("pull/{number}/head")])?;
+    let worktree_arg = worktree.to_string_lossy();
+    git(None, &["worktree", "add", "--detach", &worktree_arg, &pull_request.head.sha])?;
+
+    if git(Some(&worktree), &["rebase", &format!("origin/{base}")]).is_err() {
+        return conflicts(worktree);
+    }
+    finish(storage, &owner, &repo, number, &worktree)
+}
+
+/// Continue an interrupted rebase after conflicts were resolved, then push.
+pub fn resume(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    number: u64,
+) -> Result<FixOutcome, AppError> {
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+    let worktree = existing_worktree(&owner, &repo, number)?;
+
+    if rebase_in_progress(&worktree)?
+        && git(Some(&worktree), &["-c", "core.editor=true", "rebase", "--continue"]).is_err()
+    {
+        return conflicts(worktree);
+    }
+    finish(storage, &owner, &repo, number, &worktree)
+}
+
+/// Abandon a fix in progress and remove its worktree.
+pub fn abort(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    number: u64,
+) -> Result<PathBuf, AppError> {
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+    let worktree = existing_worktree(&owner, &repo, number)?;
+
+    if rebase_in_progress(&worktree)? {
+        git(Some(&worktree), &["rebase", "--abort"])?;
+    }
+    remove_worktree(&worktree)?;
+    Ok(worktree)
+}
+
+/// Push the rebased branch and remove the worktree.
+///
+/// The push uses `--force-with-lease` against the pre-rebase head, so commits pushed to the
+/// pull request while conflicts were being resolved are never overwritten.
+fn finish(
+    storage: &impl Storage,
+    owner: &str,
+    repo: &str,
+    number: u64,
+    worktree: &Path,
+) -> Result<FixOutcome, AppError> {
+    let (account, token) = account::get_active_for_destructive(storage)?;
+    let client = GitHubClient::new(token)?;
+    let pull_request = client.get_pull_request(owner, repo, number)?;
+    let branch = pull_request.head.branch.clone();
+
+    let original = git(Some(worktree), &["rev-parse", "ORIG_HEAD"])?;
+    let sha = git(Some(worktree), &["rev-parse", "HEAD"])?;
+    let remote = push_remote(&account, owner, repo, &pull_request)?;
+    git(
+        Some(worktree),
+        &[
+            "push",
+            &format!("--force-with-lease=refs/heads/{branch}:{original}"),
+            &remote,
+            &format!("HEAD:refs/heads/{branch}"),
+        ],
+    )?;
+
+    remove_worktree(worktree)?;
+    Ok(FixOutcome::Pushed { branch, sha })
+}
+
+fn conflicts(worktree: PathBuf) -> Result<FixOutcome, AppError> {
+    let files = git(Some(&worktree), &["diff", "--name-only", "--diff-filter=U"])?
+        .lines()
+        .map(str::to_string)
+        .collect();
+    Ok(FixOutcome::Conflicts { worktree, files })
+}
+
+fn base_branch(pull_request: &PullRequest) -> Result<&str, AppError> {
+    pull_request
+        .base
+        .as_ref()
+        .map(|b| b.branch.as_str())
+        .ok_or_else(|| AppError::github_api("pull request has no base branch"))
+}
+
+/// `origin` for same-repository branches, otherwise the fork's clone URL.
+fn push_remote(
+    account: &Account,
+    owner: &str,
+    repo: &str,
+    pull_request: &PullRequest,
+) -> Result<String, AppError> {
+    let head_repo = pull_request.head.repo.as_ref().ok_or_else(|| {
+        AppError::github_api("the pull request's head repository no longer exists")
+    })?;
+    if head_repo.full_name.eq_ignore_ascii_case(&format!("{owner}/{repo}")) {
+        return Ok("origin".to_string());
+    }
+    let (fork_owner, fork_repo) = parse_repo_spec(&head_repo.full_name)?;
+    let host = account.host.as_deref().unwrap_or(GITHUB_HOST);
+    Ok(build_clone_url(host, &fork_owner, &fork_repo, account.protocol))
+}
+
+/// Location of the worktree used to fix a pull request.
+fn worktree_dir(owner: &str, repo: &str, number: u64) -> PathBuf {
+    std::env::temp_dir().join(format!("gho-{owner}-{repo}-pr-{number}"))
+}
+
+fn existing_worktree(owner: &str, repo: &str, number: u64) -> Result<PathBuf, AppError> {
+    let worktree = worktree_dir(owner, repo, number);
+    if !worktree.exists() {
+        return Err(AppError::invalid_input(format!(
+            "no fix-conflicts in progress for #{number} (expected {})",
+            worktree.display()
+        )));
+    }
+    Ok(worktree)
+}
+
+fn rebase_in_progress(worktree: &Path) -> Result<bool, AppError> {
+    let merge = git(Some(worktree), &["rev-parse", "--git-path", "rebase-merge"])?;
+    let apply = git(Some(worktree), &["rev-parse", "--git-path", "rebase-apply"])?;
+    Ok([merge, apply].iter().any(|p| worktree.join(p).exists()))
+}
+
+fn remove_worktree(worktree: &Path) -> Result<(), AppError> {
+    git(None, &["worktree", "remove", "--force", &worktree.to_string_lossy()])?;
+    Ok(())
+}
+
+/// Run git, in `dir` when given, returning trimmed stdout.
+pub(crate) fn git(dir: Option<&Path>, args: &[&str]) -> Result<String, AppError> {
+    let mut command = Command::new("git");
+    if let Some(dir) = dir {
+        command.arg("-C").arg(dir);
+    }
+    let output = logging::command(command.args(args))
+        .output()
+        .map_err(|e| AppError::git(format!("failed to run git: {e}")))?;
+
+    if !output.status.success() {
+        return Err(AppError::git(format!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{AccountKind, CloneLayout, CloneOptions, Protocol};
+
+    fn pull_request(head_repo: &str) -> PullRequest {
+        serde_json::from_value(serde_json::json!({
+            "number": 7,
+            "title": "Fix",
+            "user": { "login": "octocat" },
+            "head": { "ref": "fix", "sha": "abc", "repo": { "full_name": head_repo } },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn worktree_dir_is_unique_per_pull_request() {
+        assert_ne!(worktree_dir("acme", "api", 1), worktree_dir("acme", "api", 2));
+        assert!(worktree_dir("acme", "api", 1).ends_with("gho-acme-api-pr-1"));
+    }
+
+    #[test]
+    fn push_remote_uses_fork_url_for_cross_repo_prs() {
+        let account = Account {
+            id: "work".to_string(),
+            kind: AccountKind::Work,
+            username: "octocat".to_string(),
+            default_org: None,
+            protocol: Protocol::Https,
+            clone_dir: None,
+            clone_layout: CloneLayout::Flat,
+            clone_options: CloneOptions::default(),
+            browser: None,
+            token_command: None,
+            token_expires_at: None,
+            host: None,
+            protected: false,
+            git: None,
+        };
+
+        assert_eq!(
+            push_remote(&account, "acme", "api", &pull_request("acme/api")).unwrap(),
+            "origin"
+        );
+        assert_eq!(
+            push_remote(&account, "acme", "api", &pull_request("octocat/api")).unwrap(),
+            "https://github.com/octocat/api.git"
+        );
+    }
+}
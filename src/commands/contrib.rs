@@ -0,0 +1,136 @@
+//! Contribution summaries (`gho contrib`).
+
+use crate::commands::account;
+use crate::error::AppError;
+use crate::github::{GitHubClient, UsersApi};
+use crate::models::{Account, AccountScoped, Contributions};
+use crate::storage::Storage;
+use chrono::{Days, NaiveDate, NaiveTime, Utc};
+
+/// Days summarized when no start date is given.
+const DEFAULT_DAYS: u64 = 7;
+/// Longest period the contributions collection covers in one query.
+const MAX_DAYS: i64 = 365;
+
+/// Accounts whose contributions are summarized.
+#[derive(Debug, Clone, Copy)]
+pub enum ContribAccounts<'a> {
+    /// The active account.
+    Active,
+    /// One configured account, by ID.
+    Account(&'a str),
+    /// Every account of a group (`--accounts`).
+    Group(&'a str),
+    /// Every configured account.
+    All,
+}
+
+/// Commits, pull requests, reviews and issues since `since` (`YYYY-MM-DD`, the last 7 days
+/// when omitted), per account.
+///
+/// Each account is queried with its own token. When several accounts are summarized, one
+/// whose query failed, e.g. with an expired token, carries the error instead of counts.
+pub fn summary(
+    storage: &impl Storage,
+    accounts: ContribAccounts,
+    since: Option<&str>,
+) -> Result<Vec<AccountScoped<Contributions>>, AppError> {
+    let today = Utc::now().date_naive();
+    let since = since_date(since, today)?;
+    let from = since.and_time(NaiveTime::MIN).and_utc();
+    let to = Utc::now();
+
+    let targets: Vec<(Account, Result<String, AppError>)> = match accounts {
+        ContribAccounts::Active => {
+            let (account, token) = account::get_active_with_token(storage)?;
+            vec![(account, Ok(token))]
+        }
+        ContribAccounts::Account(id) => {
+            let accounts = storage.load_accounts()?;
+            let account = accounts
+                .find_account(id)
+                .cloned()
+                .ok_or_else(|| AppError::AccountNotFound(id.to_string()))?;
+            let token = account::get_token(storage, id)?;
+            vec![(account, Ok(token))]
+        }
+        ContribAccounts::Group(name) => account::get_group_with_tokens(storage, name)?
+            .into_iter()
+            .map(|(account, token)| (account, Ok(token)))
+            .collect(),
+        ContribAccounts::All => {
+            let accounts = storage.load_accounts()?;
+            let accounts = accounts.all_accounts();
+            if accounts.is_empty() {
+                return Err(AppError::NoActiveAccount);
+            }
+            accounts
+                .into_iter()
+                .map(|account| (account.clone(), account::get_token(storage, &account.id)))
+                .collect()
+        }
+    };
+
+    let single = targets.len() == 1;
+    targets
+        .into_iter()
+        .map(|(account, token)| {
+            let result = token
+                .and_then(GitHubClient::new)
+                .and_then(|client| client.get_contributions(from, to));
+            let item = match result {
+                Ok(viewer) => Contributions {
+                    username: viewer.login,
+                    since,
+                    counts: Some(viewer.contributions_collection.into()),
+                    error: None,
+                },
+                Err(e) if single => return Err(e),
+                Err(e) => Contributions {
+                    username: account.username.clone(),
+                    since,
+                    counts: None,
+                    error: Some(e.to_string()),
+                },
+            };
+            Ok(AccountScoped { account: account.id, item })
+        })
+        .collect()
+}
+
+/// Start date of the summary; GitHub covers at most a year per query.
+fn since_date(since: Option<&str>, today: NaiveDate) -> Result<NaiveDate, AppError> {
+    let Some(value) = since else {
+        return Ok(today - Days::new(DEFAULT_DAYS));
+    };
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+        AppError::invalid_input(format!("invalid date '{value}', expected YYYY-MM-DD"))
+    })?;
+    if date > today {
+        return Err(AppError::invalid_input(format!("--since {value} is in the future")));
+    }
+    if (today - date).num_days() > MAX_DAYS {
+        return Err(AppError::invalid_input(format!(
+            "--since {value} is more than a year ago; GitHub summarizes at most a year at a time"
+        )));
+    }
+    Ok(date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn since_date_defaults_to_a_week_and_caps_at_a_year() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        assert_eq!(since_date(None, today).unwrap(), NaiveDate::from_ymd_opt(2026, 10, 9).unwrap());
+        assert_eq!(
+            since_date(Some("2026-01-01"), today).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()
+        );
+        assert!(since_date(Some("2025-10-01"), today).is_err());
+        assert!(since_date(Some("2026-10-17"), today).is_err());
+        assert!(since_date(Some("10/01/2026"), today).is_err());
+    }
+}
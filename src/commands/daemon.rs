@@ -0,0 +1,216 @@
+//! Opt-in background refresh (`gho daemon`).
+//!
+//! The daemon periodically fetches the PR dashboard, unread notifications and rate limits of
+//! the active account and writes each as a JSON [`Snapshot`] under the cache directory, so
+//! `gho pr status --cached` and shell prompts can read them without touching the network.
+
+use crate::commands::{account, api, pr};
+use crate::config::Config;
+use crate::error::AppError;
+use crate::github::{GitHubClient, UsersApi};
+use crate::models::{DaemonStatus, Notification, NotificationFilter, Snapshot, SnapshotStatus};
+use crate::storage::Storage;
+use chrono::Utc;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fs::{self, File};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+
+/// Default seconds between refreshes.
+pub const DEFAULT_INTERVAL_SECS: u64 = 300;
+/// Snapshot of `pr status`.
+pub const DASHBOARD: &str = "dashboard";
+/// Snapshot of unread notifications.
+pub const NOTIFICATIONS: &str = "notifications";
+/// Snapshot of the active account's rate limits.
+pub const RATE_LIMIT: &str = "rate_limit";
+
+const SNAPSHOTS: [&str; 3] = [DASHBOARD, NOTIFICATIONS, RATE_LIMIT];
+const PID_FILE: &str = "daemon.pid";
+const LOG_FILE: &str = "daemon.log";
+const MIN_INTERVAL_SECS: u64 = 30;
+const NOTIFICATION_LIMIT: usize = 50;
+
+/// Start the refresh loop as a detached `gho daemon run` process and return its pid.
+pub fn start(config: &Config, interval_secs: u64) -> Result<u32, AppError> {
+    validate_interval(interval_secs)?;
+    let cache = config.cache_path();
+    if let Some(pid) = running_pid(&cache) {
+        return Err(AppError::invalid_input(format!("daemon already running (pid {pid})")));
+    }
+
+    fs::create_dir_all(&cache)?;
+    let log = File::options().create(true).append(true).open(cache.join(LOG_FILE))?;
+    let mut command = Command::new(std::env::current_exe()?);
+    command
+        .args(["daemon", "run", "--interval", &interval_secs.to_string()])
+        .stdin(Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log);
+    // Leave the terminal's process group so closing the shell does not stop the daemon.
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut command, 0);
+
+    let child = command.spawn()?;
+    fs::write(cache.join(PID_FILE), child.id().to_string())?;
+    Ok(child.id())
+}
+
+/// Stop the running daemon and return its pid.
+pub fn stop(config: &Config) -> Result<u32, AppError> {
+    let cache = config.cache_path();
+    let pid =
+        running_pid(&cache).ok_or_else(|| AppError::NotFound("daemon is not running".into()))?;
+    if !signal(pid, None) {
+        return Err(AppError::invalid_input(format!("failed to stop daemon (pid {pid})")));
+    }
+    let _ = fs::remove_file(cache.join(PID_FILE));
+    Ok(pid)
+}
+
+/// Report whether the daemon runs and when each snapshot was last refreshed.
+pub fn status(config: &Config) -> Result<DaemonStatus, AppError> {
+    let cache = config.cache_path();
+    let pid = running_pid(&cache);
+    let snapshots = SNAPSHOTS
+        .iter()
+        .map(|name| SnapshotStatus {
+            name: name.to_string(),
+            refreshed_at: load_snapshot::<serde_json::Value>(config, name)
+                .ok()
+                .map(|s| s.refreshed_at),
+        })
+        .collect();
+    Ok(DaemonStatus { pid, running: pid.is_some(), snapshots })
+}
+
+/// Refresh snapshots every `interval_secs` until the process is killed.
+pub fn run(storage: &impl Storage, config: &Config, interval_secs: u64) -> Result<(), AppError> {
+    validate_interval(interval_secs)?;
+    loop {
+        for (name, error) in refresh(storage, config) {
+            eprintln!("{} {name}: {error}", Utc::now().to_rfc3339());
+        }
+        thread::sleep(Duration::from_secs(interval_secs));
+    }
+}
+
+/// Fetch every snapshot once, returning the ones that failed with their error.
+///
+/// A failed refresh keeps the previous snapshot.
+pub fn refresh(storage: &impl Storage, config: &Config) -> Vec<(&'static str, AppError)> {
+    let limit = config.settings.limit;
+    let results = [
+        (DASHBOARD, pr::status(storage, limit).and_then(|d| save_snapshot(config, DASHBOARD, d))),
+        (
+            NOTIFICATIONS,
+            notifications(storage).and_then(|n| save_snapshot(config, NOTIFICATIONS, n)),
+        ),
+        (
+            RATE_LIMIT,
+            api::rate_limit(storage, false).and_then(|r| save_snapshot(config, RATE_LIMIT, r)),
+        ),
+    ];
+    results.into_iter().filter_map(|(name, result)| result.err().map(|e| (name, e))).collect()
+}
+
+fn notifications(storage: &impl Storage) -> Result<Vec<Notification>, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let unread = NotificationFilter { unread: true, participating: false };
+    GitHubClient::new(token)?.list_notifications(unread, NOTIFICATION_LIMIT)
+}
+
+/// Read the snapshot `name` written by the daemon.
+pub fn load_snapshot<T: DeserializeOwned>(
+    config: &Config,
+    name: &str,
+) -> Result<Snapshot<T>, AppError> {
+    let path = config.cache_path().join(format!("{name}.json"));
+    if !path.exists() {
+        return Err(AppError::NotFound(format!(
+            "no cached {name} snapshot (start one with `gho daemon start`)"
+        )));
+    }
+    tracing::debug!(path = %path.display(), "reading cached {name} snapshot");
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+fn save_snapshot<T: Serialize>(config: &Config, name: &str, data: T) -> Result<(), AppError> {
+    let cache = config.cache_path();
+    fs::create_dir_all(&cache)?;
+    let content = serde_json::to_string_pretty(&Snapshot { refreshed_at: Utc::now(), data })?;
+    // Write then rename so readers never see a partial snapshot.
+    let tmp = cache.join(format!("{name}.json.tmp"));
+    fs::write(&tmp, content)?;
+    fs::rename(tmp, cache.join(format!("{name}.json")))?;
+    Ok(())
+}
+
+fn validate_interval(interval_secs: u64) -> Result<(), AppError> {
+    if interval_secs < MIN_INTERVAL_SECS {
+        return Err(AppError::invalid_input(format!(
+            "interval must be at least {MIN_INTERVAL_SECS} seconds"
+        )));
+    }
+    Ok(())
+}
+
+/// Pid from the pid file if that process is still alive; a stale file is removed.
+fn running_pid(cache: &Path) -> Option<u32> {
+    let path = cache.join(PID_FILE);
+    let pid = fs::read_to_string(&path).ok()?.trim().parse().ok()?;
+    if signal(pid, Some("0")) {
+        Some(pid)
+    } else {
+        let _ = fs::remove_file(path);
+        None
+    }
+}
+
+/// Send a signal with `kill`; `Some("0")` only checks that the process exists.
+fn signal(pid: u32, signal: Option<&str>) -> bool {
+    let mut command = Command::new("kill");
+    if let Some(signal) = signal {
+        command.arg(format!("-{signal}"));
+    }
+    command
+        .arg(pid.to_string())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::PullRequestDashboard;
+    use tempfile::TempDir;
+
+    #[test]
+    fn snapshot_round_trips_through_cache() {
+        let dir = TempDir::new().unwrap();
+        let config = Config::with_path(dir.path().to_path_buf());
+        let dashboard =
+            PullRequestDashboard { created: vec![], review_requested: vec![], assigned: vec![] };
+        save_snapshot(&config, DASHBOARD, &dashboard).unwrap();
+
+        let snapshot: Snapshot<PullRequestDashboard> = load_snapshot(&config, DASHBOARD).unwrap();
+        assert!(snapshot.data.created.is_empty());
+        let status = status(&config).unwrap();
+        assert!(!status.running);
+        assert!(status.snapshots.iter().any(|s| s.name == DASHBOARD && s.refreshed_at.is_some()));
+        assert!(status.snapshots.iter().any(|s| s.name == RATE_LIMIT && s.refreshed_at.is_none()));
+    }
+
+    #[test]
+    fn stale_pid_file_is_ignored() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join(PID_FILE), "4194303").unwrap();
+        assert_eq!(running_pid(dir.path()), None);
+        assert!(!dir.path().join(PID_FILE).exists());
+    }
+}
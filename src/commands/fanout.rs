@@ -0,0 +1,226 @@
+//! Multi-repository pull requests (`pr fanout`).
+
+use crate::commands::account;
+use crate::commands::conflicts::git;
+use crate::domain::{GITHUB_HOST, build_clone_url, parse_repo_spec};
+use crate::error::AppError;
+use crate::github::{GitHubClient, PullsApi};
+use crate::models::{Account, ExecutionMode, FanoutResult, NewPullRequest, ProgressOutcome};
+use crate::platform;
+use crate::progress::Progress;
+use crate::storage::Storage;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// How each repository is changed.
+#[derive(Debug, Clone)]
+pub enum FanoutChange {
+    /// Apply a patch file with `git apply`.
+    Patch(PathBuf),
+    /// Run a shell command in the repository root.
+    Script(String),
+}
+
+/// Options for [`fanout`].
+#[derive(Debug, Clone)]
+pub struct FanoutOptions {
+    pub branch: String,
+    pub title: String,
+    pub body: Option<String>,
+    /// Base branch; each repository's default branch when omitted.
+    pub base: Option<String>,
+    pub draft: bool,
+    /// In a dry run, make and commit the change locally without pushing or opening pull
+    /// requests.
+    pub mode: ExecutionMode,
+    pub progress: Progress,
+}
+
+/// Operation name of `pr fanout` progress events.
+const OPERATION: &str = "pr fanout";
+
+/// Apply the same change to every repository and open a pull request in each.
+///
+/// Each repository is shallow-cloned into a temporary directory, changed on a new branch,
+/// committed, pushed and proposed. Repositories where the change produces no diff are
+/// skipped, and failures are recorded per repository instead of aborting the run.
+pub fn fanout(
+    storage: &impl Storage,
+    repos: &[String],
+    change: &FanoutChange,
+    options: &FanoutOptions,
+) -> Result<Vec<FanoutResult>, AppError> {
+    for repo in repos {
+        parse_repo_spec(repo)?;
+    }
+    let change = match change {
+        FanoutChange::Patch(path) => FanoutChange::Patch(fs::canonicalize(path).map_err(|e| {
+            AppError::invalid_input(format!("cannot read patch '{}': {e}", path.display()))
+        })?),
+        script => script.clone(),
+    };
+
+    let (account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let workdir = std::env::temp_dir().join(format!("gho-fanout-{}", std::process::id()));
+    fs::create_dir_all(&workdir)?;
+
+    let progress = options.progress;
+    let results = repos
+        .iter()
+        .enumerate()
+        .map(|(i, spec)| {
+            progress.started(OPERATION, spec, i + 1, repos.len());
+            let mut result =
+                FanoutResult { repo: spec.clone(), url: None, unchanged: false, error: None };
+            let checkout = workdir.join(spec.replace('/', "-"));
+            let outcome = match fanout_repo(&client, &account, spec, &checkout, &change, options) {
+                Ok(Outcome::Opened(url)) => {
+                    result.url = Some(url);
+                    ProgressOutcome::Succeeded
+                }
+                Ok(Outcome::Committed) => ProgressOutcome::Succeeded,
+                Ok(Outcome::Unchanged) => {
+                    result.unchanged = true;
+                    ProgressOutcome::Skipped
+                }
+                Err(e) => {
+                    result.error = Some(e.to_string());
+                    ProgressOutcome::Failed
+                }
+            };
+            progress.completed(OPERATION, spec, outcome, result.error.as_deref());
+            let _ = fs::remove_dir_all(&checkout);
+            result
+        })
+        .collect();
+
+    let _ = fs::remove_dir_all(&workdir);
+    Ok(results)
+}
+
+enum Outcome {
+    Unchanged,
+    /// Committed locally only (dry run).
+    Committed,
+    /// Pushed, with the URL of the opened pull request.
+    Opened(String),
+}
+
+fn fanout_repo(
+    client: &impl PullsApi,
+    account: &Account,
+    spec: &str,
+    checkout: &Path,
+    change: &FanoutChange,
+    options: &FanoutOptions,
+) -> Result<Outcome, AppError> {
+    let (owner, repo) = parse_repo_spec(spec)?;
+    let host = account.host.as_deref().unwrap_or(GITHUB_HOST);
+    let url = build_clone_url(host, &owner, &repo, account.protocol);
+    let checkout_arg = checkout.to_string_lossy();
+    options.progress.updated(OPERATION, spec, "cloning");
+    git(None, &["clone", "--quiet", "--depth", "1", &url, &checkout_arg])?;
+
+    let dir = Some(checkout);
+    let default_branch = git(dir, &["symbolic-ref", "--short", "HEAD"])?;
+    let base = options.base.clone().unwrap_or_else(|| default_branch.clone());
+    if base != default_branch {
+        git(dir, &["fetch", "--quiet", "--depth", "1", "origin", &base])?;
+        git(dir, &["checkout", "--quiet", "FETCH_HEAD"])?;
+    }
+    git(dir, &["checkout", "--quiet", "-b", &options.branch])?;
+
+    options.progress.updated(OPERATION, spec, "applying change");
+    match change {
+        FanoutChange::Patch(patch) => {
+            git(dir, &["apply", &patch.to_string_lossy()])?;
+        }
+        FanoutChange::Script(script) => run_script(checkout, script)?,
+    }
+
+    if git(dir, &["status", "--porcelain"])?.is_empty() {
+        return Ok(Outcome::Unchanged);
+    }
+    git(dir, &["add", "--all"])?;
+    git(dir, &["commit", "--quiet", "-m", &options.title])?;
+
+    if options.mode.is_dry_run() {
+        return Ok(Outcome::Committed);
+    }
+    options.progress.updated(OPERATION, spec, "pushing");
+    git(dir, &["push", "--quiet", "origin", &format!("HEAD:refs/heads/{}", options.branch)])?;
+
+    options.progress.updated(OPERATION, spec, "opening pull request");
+    let pull_request = client.create_pull_request(
+        &owner,
+        &repo,
+        &NewPullRequest {
+            title: options.title.clone(),
+            head: options.branch.clone(),
+            base,
+            body: options.body.clone(),
+            draft: options.draft,
+        },
+    )?;
+    Ok(Outcome::Opened(
+        pull_request.html_url.unwrap_or_else(|| format!("{spec}#{}", pull_request.number)),
+    ))
+}
+
+fn run_script(dir: &Path, script: &str) -> Result<(), AppError> {
+    let output = platform::shell(script)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| AppError::invalid_input(format!("failed to run script: {e}")))?;
+    if !output.status.success() {
+        return Err(AppError::invalid_input(format!(
+            "script exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+/// Read `owner/repo` lines, ignoring blank lines and `#` comments.
+pub fn load_repo_list(path: &Path) -> Result<Vec<String>, AppError> {
+    let content = fs::read_to_string(path)?;
+    let repos = parse_repo_list(&content)?;
+    if repos.is_empty() {
+        return Err(AppError::invalid_input(format!("{} lists no repositories", path.display())));
+    }
+    Ok(repos)
+}
+
+fn parse_repo_list(content: &str) -> Result<Vec<String>, AppError> {
+    let mut repos = Vec::new();
+    for line in content.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        parse_repo_spec(line)?;
+        if !repos.iter().any(|r: &String| r.eq_ignore_ascii_case(line)) {
+            repos.push(line.to_string());
+        }
+    }
+    Ok(repos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_repo_list_skips_comments_and_duplicates() {
+        let repos =
+            parse_repo_list("# services\nacme/api\n\nacme/web  # frontend\nACME/api\n").unwrap();
+        assert_eq!(repos, vec!["acme/api".to_string(), "acme/web".to_string()]);
+    }
+
+    #[test]
+    fn parse_repo_list_rejects_invalid_lines() {
+        assert!(parse_repo_list("acme/api\nnot-a-repo\n").is_err());
+    }
+}
@@ -0,0 +1,103 @@
+//! Gist commands.
+
+use crate::commands::account;
+use crate::domain::build_gist_clone_url;
+use crate::error::AppError;
+use crate::github::{GitHubClient, UsersApi};
+use crate::logging;
+use crate::models::{Gist, NewGist, NewGistFile};
+use crate::storage::Storage;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Create a gist from local files, named after their file names. Gists are secret unless
+/// `public` is set.
+pub fn create(
+    storage: &impl Storage,
+    paths: &[PathBuf],
+    public: bool,
+    description: Option<&str>,
+) -> Result<Gist, AppError> {
+    let gist =
+        NewGist { description: description.map(str::to_string), public, files: read_files(paths)? };
+    let (_account, token) = account::get_active_with_token(storage)?;
+    GitHubClient::new(token)?.create_gist(&gist)
+}
+
+/// List gists of the active account, most recently updated first.
+pub fn list(storage: &impl Storage, limit: usize) -> Result<Vec<Gist>, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    GitHubClient::new(token)?.list_gists(limit)
+}
+
+/// Clone a gist into `<clone_dir>/<id>` with the account's protocol and return the directory.
+pub fn clone(storage: &impl Storage, id: &str) -> Result<PathBuf, AppError> {
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(AppError::invalid_input(format!("invalid gist id '{id}'")));
+    }
+    let (account, _token) = account::get_active_with_token(storage)?;
+
+    let target_dir = account.clone_dir.as_deref().map(PathBuf::from).unwrap_or_default().join(id);
+    if target_dir.exists() {
+        return Err(AppError::git(format!("directory '{}' already exists", target_dir.display())));
+    }
+
+    let url = build_gist_clone_url(id, account.protocol);
+    let status = logging::command(Command::new("git").arg("clone").arg(url).arg(&target_dir))
+        .status()
+        .map_err(|e| AppError::git(format!("failed to run git: {e}")))?;
+    if !status.success() {
+        return Err(AppError::git(format!("git clone failed with status {status}")));
+    }
+    Ok(target_dir)
+}
+
+/// File contents keyed by file name; GitHub rejects empty files and duplicate names.
+fn read_files(paths: &[PathBuf]) -> Result<BTreeMap<String, NewGistFile>, AppError> {
+    if paths.is_empty() {
+        return Err(AppError::invalid_input("at least one file is required"));
+    }
+    let mut files = BTreeMap::new();
+    for path in paths {
+        let name = file_name(path)?;
+        let content = fs::read_to_string(path).map_err(|e| {
+            AppError::invalid_input(format!("cannot read '{}': {e}", path.display()))
+        })?;
+        if content.trim().is_empty() {
+            return Err(AppError::invalid_input(format!("'{}' is empty", path.display())));
+        }
+        if files.insert(name.clone(), NewGistFile { content }).is_some() {
+            return Err(AppError::invalid_input(format!("more than one file is named '{name}'")));
+        }
+    }
+    Ok(files)
+}
+
+fn file_name(path: &Path) -> Result<String, AppError> {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| AppError::invalid_input(format!("'{}' is not a file", path.display())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn read_files_keys_by_name_and_rejects_duplicates() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("b")).unwrap();
+        let a = dir.path().join("notes.md");
+        let b = dir.path().join("b").join("notes.md");
+        fs::write(&a, "# Notes\n").unwrap();
+        fs::write(&b, "# More\n").unwrap();
+
+        let files = read_files(std::slice::from_ref(&a)).unwrap();
+        assert_eq!(files["notes.md"].content, "# Notes\n");
+        assert!(read_files(&[a, b]).is_err());
+        assert!(read_files(&[]).is_err());
+    }
+}
@@ -0,0 +1,279 @@
+//! Per-account git identity (`gho account setup-gitconfig`).
+//!
+//! Each account gets a generated gitconfig with its identity, signing key and SSH key, which the
+//! global gitconfig includes for repositories under the account's directory. The include lives
+//! in a marked block that gho rewrites in place, so running the command again after changing a
+//! setting updates both files instead of stacking includes.
+
+use crate::config::Config;
+use crate::domain::shell_join;
+use crate::error::AppError;
+use crate::models::{Account, GitIdentity};
+use crate::platform;
+use crate::storage::Storage;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Settings given to `setup-gitconfig`; unset ones keep their saved value.
+#[derive(Debug, Clone, Default)]
+pub struct GitIdentityOptions {
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub signing_key: Option<String>,
+    pub ssh_key: Option<String>,
+    pub gitdir: Option<String>,
+}
+
+/// Files written by [`setup`].
+#[derive(Debug, Clone)]
+pub struct GitconfigSetup {
+    pub identity: GitIdentity,
+    /// Generated per-account gitconfig.
+    pub account_file: PathBuf,
+    /// Global gitconfig holding the `includeIf` block.
+    pub global_file: PathBuf,
+}
+
+/// Save the git identity of account `id` and (re)write its gitconfig and include.
+///
+/// The name defaults to the GitHub username and the directory to the account's clone
+/// directory; the email has to be given the first time.
+pub fn setup(
+    config: &Config,
+    storage: &impl Storage,
+    id: &str,
+    options: GitIdentityOptions,
+) -> Result<GitconfigSetup, AppError> {
+    let mut accounts = storage.load_accounts()?;
+    let account =
+        accounts.find_account_mut(id).ok_or_else(|| AppError::AccountNotFound(id.to_string()))?;
+    let identity = merge(account, options)?;
+    account.git = Some(identity.clone());
+
+    let account_file = account_file(config, id);
+    if let Some(dir) = account_file.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&account_file, render(id, &identity))?;
+
+    let global_file = global_file()?;
+    let include = include_block(&identity.gitdir, &account_file);
+    update_global(&global_file, id, Some(&include))?;
+
+    storage.save_accounts(&accounts)?;
+    Ok(GitconfigSetup { identity, account_file, global_file })
+}
+
+/// Remove the include and generated gitconfig of account `id`, if it has them.
+pub fn remove(config: &Config, id: &str) -> Result<(), AppError> {
+    let account_file = account_file(config, id);
+    if !account_file.exists() {
+        return Ok(());
+    }
+    update_global(&global_file()?, id, None)?;
+    fs::remove_file(account_file)?;
+    Ok(())
+}
+
+/// Generated gitconfig of account `id`.
+pub fn account_file(config: &Config, id: &str) -> PathBuf {
+    config.config_path.join("git").join(format!("{id}.gitconfig"))
+}
+
+/// The global gitconfig git reads: `$GIT_CONFIG_GLOBAL`, or `~/.gitconfig`.
+fn global_file() -> Result<PathBuf, AppError> {
+    if let Some(path) = std::env::var_os("GIT_CONFIG_GLOBAL") {
+        return Ok(PathBuf::from(path));
+    }
+    Ok(platform::home_dir()?.join(".gitconfig"))
+}
+
+fn merge(account: &Account, options: GitIdentityOptions) -> Result<GitIdentity, AppError> {
+    let saved = account.git.clone();
+    let name = options
+        .name
+        .or_else(|| saved.as_ref().map(|g| g.name.clone()))
+        .unwrap_or_else(|| account.username.clone());
+    let email =
+        options.email.or_else(|| saved.as_ref().map(|g| g.email.clone())).ok_or_else(|| {
+            AppError::invalid_input(format!("--email is required for '{}'", account.id))
+        })?;
+    let gitdir = options
+        .gitdir
+        .or_else(|| saved.as_ref().map(|g| g.gitdir.clone()))
+        .or_else(|| account.clone_dir.clone())
+        .ok_or_else(|| {
+            AppError::invalid_input(format!(
+                "--gitdir is required, '{}' has no clone directory",
+                account.id
+            ))
+        })?;
+    let signing_key = options.signing_key.or_else(|| saved.as_ref()?.signing_key.clone());
+    let ssh_key = options.ssh_key.or_else(|| saved.as_ref()?.ssh_key.clone());
+
+    for (flag, value) in [("--name", &name), ("--email", &email), ("--gitdir", &gitdir)] {
+        if value.trim().is_empty() {
+            return Err(AppError::invalid_input(format!("{flag} must not be empty")));
+        }
+    }
+    // `gitdir:` only matches repositories below the directory when it ends with a slash.
+    let gitdir = if gitdir.ends_with('/') { gitdir } else { format!("{gitdir}/") };
+    Ok(GitIdentity { name, email, signing_key, ssh_key, gitdir })
+}
+
+fn render(id: &str, identity: &GitIdentity) -> String {
+    let mut out = format!(
+        "# Generated by `gho account setup-gitconfig {id}`; changes here are overwritten.\n\
+         [user]\n\tname = {}\n\temail = {}\n",
+        quote(&identity.name),
+        quote(&identity.email)
+    );
+    if let Some(key) = &identity.signing_key {
+        out.push_str(&format!("\tsigningkey = {}\n", quote(key)));
+        // SSH public keys (or their files) sign through ssh-keygen rather than GnuPG.
+        if key.starts_with("ssh-") || key.ends_with(".pub") {
+            out.push_str("[gpg]\n\tformat = ssh\n");
+        }
+        out.push_str("[commit]\n\tgpgsign = true\n[tag]\n\tgpgsign = true\n");
+    }
+    if let Some(key) = &identity.ssh_key {
+        let command =
+            format!("ssh -i {} -o IdentitiesOnly=yes", shell_join(std::slice::from_ref(key)));
+        out.push_str(&format!("[core]\n\tsshCommand = {}\n", quote(&command)));
+    }
+    out
+}
+
+fn include_block(gitdir: &str, account_file: &Path) -> String {
+    format!(
+        "[includeIf {}]\n\tpath = {}\n",
+        quote(&format!("gitdir:{gitdir}")),
+        quote(&platform::git_path(account_file))
+    )
+}
+
+fn update_global(path: &Path, id: &str, block: Option<&str>) -> Result<(), AppError> {
+    let content = if path.exists() { fs::read_to_string(path)? } else { String::new() };
+    let updated = replace_block(&content, id, block);
+    if updated != content {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, updated)?;
+    }
+    Ok(())
+}
+
+/// `content` with the marked block of account `id` replaced by `block`, appended when there is
+/// none yet, or dropped when `block` is `None`.
+fn replace_block(content: &str, id: &str, block: Option<&str>) -> String {
+    let begin = format!("# BEGIN gho account {id}");
+    let end = format!("# END gho account {id}");
+    let marked = block.map(|block| format!("{begin}\n{block}{end}\n"));
+
+    let mut out = String::new();
+    let mut lines = content.lines();
+    let mut replaced = false;
+    while let Some(line) = lines.next() {
+        if line.trim() != begin {
+            out.push_str(line);
+            out.push('\n');
+            continue;
+        }
+        for line in lines.by_ref() {
+            if line.trim() == end {
+                break;
+            }
+        }
+        if let Some(marked) = &marked
+            && !replaced
+        {
+            out.push_str(marked);
+        }
+        replaced = true;
+    }
+    if let Some(marked) = marked
+        && !replaced
+    {
+        if !out.is_empty() && !out.ends_with("\n\n") {
+            out.push('\n');
+        }
+        out.push_str(&marked);
+    }
+    out
+}
+
+/// A gitconfig value in double quotes, so `#`, `;` and spaces survive.
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity() -> GitIdentity {
+        GitIdentity {
+            name: "Alice Example".into(),
+            email: "alice@acme.com".into(),
+            signing_key: Some("~/.ssh/id_work.pub".into()),
+            ssh_key: Some("~/.ssh/id_work".into()),
+            gitdir: "~/src/work/".into(),
+        }
+    }
+
+    #[test]
+    fn render_writes_identity_signing_and_ssh_command() {
+        let config = render("work", &identity());
+        assert!(
+            config.contains("[user]\n\tname = \"Alice Example\"\n\temail = \"alice@acme.com\"\n")
+        );
+        assert!(config.contains("\tsigningkey = \"~/.ssh/id_work.pub\"\n[gpg]\n\tformat = ssh\n"));
+        assert!(config.contains("[commit]\n\tgpgsign = true\n"));
+        assert!(
+            config.contains("\tsshCommand = \"ssh -i '~/.ssh/id_work' -o IdentitiesOnly=yes\"\n")
+        );
+
+        let plain = GitIdentity { signing_key: None, ssh_key: None, ..identity() };
+        assert!(!render("work", &plain).contains("gpgsign"));
+    }
+
+    #[test]
+    fn replace_block_updates_in_place_and_removes() {
+        let original = "[user]\n\tname = Me\n";
+        let first = replace_block(original, "work", Some("[includeIf \"gitdir:~/a/\"]\n"));
+        assert_eq!(
+            first,
+            "[user]\n\tname = Me\n\n# BEGIN gho account work\n[includeIf \"gitdir:~/a/\"]\n\
+             # END gho account work\n"
+        );
+
+        let second = replace_block(&first, "work", Some("[includeIf \"gitdir:~/b/\"]\n"));
+        assert!(second.contains("gitdir:~/b/") && !second.contains("gitdir:~/a/"));
+        assert_eq!(second.matches("# BEGIN gho account work").count(), 1);
+
+        let other = replace_block(&second, "home", Some("[includeIf \"gitdir:~/c/\"]\n"));
+        let removed = replace_block(&other, "work", None);
+        assert!(!removed.contains("account work") && removed.contains("account home"));
+    }
+
+    #[test]
+    fn merge_defaults_to_username_and_clone_dir() {
+        let account: Account = serde_json::from_value(serde_json::json!({
+            "id": "work", "kind": "work", "username": "alice-acme", "clone_dir": "~/src/work"
+        }))
+        .unwrap();
+        let options =
+            GitIdentityOptions { email: Some("alice@acme.com".into()), ..Default::default() };
+
+        let identity = merge(&account, options).unwrap();
+        assert_eq!(identity.name, "alice-acme");
+        assert_eq!(identity.gitdir, "~/src/work/");
+        assert!(merge(&account, GitIdentityOptions::default()).is_err());
+
+        let saved = Account { git: Some(identity.clone()), ..account };
+        let renamed = GitIdentityOptions { name: Some("Alice".into()), ..Default::default() };
+        let updated = merge(&saved, renamed).unwrap();
+        assert_eq!(updated, GitIdentity { name: "Alice".into(), ..identity });
+    }
+}
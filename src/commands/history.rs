@@ -0,0 +1,208 @@
+//! Command history and replay (`gho history`, `gho redo`).
+//!
+//! Every invocation is appended to `history.jsonl` with the active account, where its token
+//! came from, and the working directory. Tokens passed on the command line and `Authorization`
+//! headers are never written to the history.
+
+use crate::config::Config;
+use crate::error::AppError;
+use crate::models::{HistoryEntry, TokenSource};
+use crate::storage::Storage;
+use chrono::Utc;
+use std::fs;
+use std::process::{Command, ExitStatus};
+
+/// How many invocations the history keeps.
+pub const HISTORY_LIMIT: usize = 1000;
+
+/// Append an invocation (`args` without the program name) to the history.
+///
+/// `token_source` is where the active account's token is read from (see
+/// `keychain::token_source`), so an entry shows whether it ran with an environment override.
+pub fn record(
+    config: &Config,
+    storage: &impl Storage,
+    args: &[String],
+    token_source: Option<TokenSource>,
+) -> Result<(), AppError> {
+    let args = without_secrets(args);
+    if args.is_empty() {
+        return Ok(());
+    }
+    let entry = HistoryEntry {
+        at: Utc::now(),
+        account: storage.load_accounts()?.active_account_id,
+        token_source,
+        cwd: std::env::current_dir()?,
+        args,
+    };
+
+    let mut entries = entries(config)?;
+    entries.push(entry);
+    let excess = entries.len().saturating_sub(HISTORY_LIMIT);
+    save(config, &entries[excess..])
+}
+
+/// Recorded invocations, oldest first; an entry's number is its position counting from 1.
+pub fn entries(config: &Config) -> Result<Vec<HistoryEntry>, AppError> {
+    let path = config.history_path();
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    fs::read_to_string(path)?
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| Ok(serde_json::from_str(line)?))
+        .collect()
+}
+
+/// Numbered entries whose command line contains `term`, ignoring case.
+pub fn search(config: &Config, term: &str) -> Result<Vec<(usize, HistoryEntry)>, AppError> {
+    let term = term.to_lowercase();
+    Ok(numbered(entries(config)?)
+        .filter(|(_, entry)| entry.args.join(" ").to_lowercase().contains(&term))
+        .collect())
+}
+
+/// Entry number `n`, or the latest entry when `n` is `None`.
+pub fn get(config: &Config, n: Option<usize>) -> Result<(usize, HistoryEntry), AppError> {
+    let entries = entries(config)?;
+    let n = n.unwrap_or(entries.len());
+    numbered(entries)
+        .find(|(i, _)| *i == n)
+        .ok_or_else(|| AppError::NotFound(format!("no history entry {n} (see `gho history`)")))
+}
+
+/// Re-run an entry in its original directory, as the account that ran it.
+///
+/// Fails when another account is active, unless `as_active` allows running it as that one.
+pub fn replay(
+    storage: &impl Storage,
+    entry: &HistoryEntry,
+    as_active: bool,
+) -> Result<ExitStatus, AppError> {
+    let active = storage.load_accounts()?.active_account_id;
+    if let Some(recorded) = &entry.account
+        && active.as_ref() != Some(recorded)
+        && !as_active
+    {
+        return Err(AppError::invalid_input(format!(
+            "this command ran as '{recorded}', but '{}' is active; switch with \
+             `gho account use {recorded}` or pass --as-active",
+            active.as_deref().unwrap_or("no account")
+        )));
+    }
+
+    let mut command = Command::new(std::env::current_exe()?);
+    command.args(&entry.args);
+    if entry.cwd.is_dir() {
+        command.current_dir(&entry.cwd);
+    }
+    Ok(command.status()?)
+}
+
+fn numbered(entries: Vec<HistoryEntry>) -> impl Iterator<Item = (usize, HistoryEntry)> {
+    entries.into_iter().enumerate().map(|(i, entry)| (i + 1, entry))
+}
+
+fn save(config: &Config, entries: &[HistoryEntry]) -> Result<(), AppError> {
+    let path = config.history_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut content = String::new();
+    for entry in entries {
+        content.push_str(&serde_json::to_string(entry)?);
+        content.push('\n');
+    }
+    // Write then rename so a concurrent reader never sees a partial history.
+    let tmp = path.with_extension("jsonl.tmp");
+    fs::write(&tmp, content)?;
+    fs::rename(tmp, path)?;
+    Ok(())
+}
+
+/// `args` without token values of `account add` and `Authorization` headers.
+fn without_secrets(args: &[String]) -> Vec<String> {
+    let is_account_add = args
+        .iter()
+        .position(|a| a == "add")
+        .is_some_and(|i| args[..i].iter().any(|a| a == "account" || a == "a"));
+    let mut kept = Vec::with_capacity(args.len());
+    let mut iter = args.iter().peekable();
+    while let Some(arg) = iter.next() {
+        if is_account_add && (arg == "-t" || arg == "--token") {
+            iter.next();
+            continue;
+        }
+        // `--token=<tok>` and the attached short form `-t<tok>`.
+        if is_account_add && (arg.starts_with("--token=") || arg.starts_with("-t")) {
+            continue;
+        }
+        if (arg == "-H" || arg == "--header")
+            && iter.peek().is_some_and(|value| is_authorization(value))
+        {
+            iter.next();
+            continue;
+        }
+        if arg.strip_prefix("--header=").is_some_and(is_authorization) {
+            continue;
+        }
+        kept.push(arg.clone());
+    }
+    kept
+}
+
+fn is_authorization(header: &str) -> bool {
+    header.split(':').next().is_some_and(|name| name.trim().eq_ignore_ascii_case("authorization"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::FilesystemStorage;
+    use tempfile::TempDir;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split(' ').map(String::from).collect()
+    }
+
+    #[test]
+    fn without_secrets_drops_tokens_and_authorization_headers() {
+        assert_eq!(
+            without_secrets(&args("account add work -u alice -t ghp_secret --kind work")),
+            args("account add work -u alice --kind work")
+        );
+        assert_eq!(
+            without_secrets(&args("account add work -tghp_secret --token=ghp_other")),
+            args("account add work")
+        );
+        assert_eq!(
+            without_secrets(&args("api /user -H Authorization:token -H Accept:raw")),
+            args("api /user -H Accept:raw")
+        );
+        assert_eq!(
+            without_secrets(&args("release create v1 -t Title")),
+            args("release create v1 -t Title")
+        );
+    }
+
+    #[test]
+    fn record_numbers_entries_and_search_finds_them() {
+        let dir = TempDir::new().unwrap();
+        let config = Config::with_path(dir.path().to_path_buf());
+        let storage = FilesystemStorage::new(&config);
+        record(&config, &storage, &args("repo list --org acme"), None).unwrap();
+        record(&config, &storage, &args("pr list -R acme/api"), Some(TokenSource::GhToken))
+            .unwrap();
+
+        assert_eq!(get(&config, None).unwrap().0, 2);
+        assert_eq!(get(&config, Some(1)).unwrap().1.args, args("repo list --org acme"));
+        assert!(get(&config, Some(3)).is_err());
+        assert_eq!(get(&config, Some(2)).unwrap().1.token_source, Some(TokenSource::GhToken));
+        assert!(fs::read_to_string(config.history_path()).unwrap().contains("\"env:GH_TOKEN\""));
+        let found = search(&config, "ACME/API").unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0, 2);
+    }
+}
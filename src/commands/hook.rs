@@ -0,0 +1,175 @@
+//! Repository webhook commands (`gho hook`).
+//!
+//! Besides creating and deleting webhooks, `ping` and `deliveries` answer "is the receiver
+//! getting our events?" without opening the repository settings page. Secrets are never taken
+//! from arguments: they are prompted for (or piped in) or read from a Keychain entry.
+
+use crate::commands::account;
+use crate::error::AppError;
+use crate::github::{GitHubClient, ReposApi};
+use crate::keychain;
+use crate::models::{HookDelivery, NewWebhook, Webhook, WebhookConfig, WebhookContentType};
+use crate::remote::resolve_repo;
+use crate::storage::Storage;
+
+/// Events a webhook subscribes to when none are given.
+pub const DEFAULT_EVENTS: [&str; 1] = ["push"];
+
+/// Where the secret of a new webhook comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecretSource<'a> {
+    /// The webhook is not signed.
+    None,
+    /// Prompt for it, or read it from stdin when piped.
+    Prompt,
+    /// The Keychain entry `webhook:<name>` of gho's Keychain service.
+    Keychain(&'a str),
+}
+
+/// Settings of a new webhook.
+#[derive(Debug, Clone)]
+pub struct HookOptions<'a> {
+    pub url: String,
+    pub content_type: WebhookContentType,
+    /// Events to subscribe to; [`DEFAULT_EVENTS`] if empty, `*` for all.
+    pub events: Vec<String>,
+    pub secret: SecretSource<'a>,
+    pub insecure_ssl: bool,
+    pub active: bool,
+}
+
+/// List the webhooks of a repository.
+pub fn list(storage: &impl Storage, repo_spec: Option<&str>) -> Result<Vec<Webhook>, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+    client.list_hooks(&owner, &repo)
+}
+
+/// Create a webhook.
+pub fn create(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    options: HookOptions,
+) -> Result<Webhook, AppError> {
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+    let secret = match options.secret {
+        SecretSource::None => None,
+        SecretSource::Prompt => Some(read_secret()?),
+        SecretSource::Keychain(name) => {
+            let entry = keychain::webhook_secret_entry(name);
+            let secret = keychain::read_in(&keychain::service(), &entry)?.ok_or_else(|| {
+                AppError::keychain(format!(
+                    "no Keychain entry '{entry}' in {}",
+                    keychain::service()
+                ))
+            })?;
+            Some(secret)
+        }
+    };
+    if secret.as_deref().is_some_and(str::is_empty) {
+        return Err(AppError::invalid_input("webhook secret is empty"));
+    }
+
+    let events = if options.events.is_empty() {
+        DEFAULT_EVENTS.iter().map(|e| e.to_string()).collect()
+    } else {
+        options.events
+    };
+    let hook = NewWebhook {
+        name: "web".to_string(),
+        active: options.active,
+        events,
+        config: WebhookConfig {
+            url: Some(options.url),
+            content_type: Some(options.content_type),
+            secret,
+            insecure_ssl: Some(if options.insecure_ssl { "1" } else { "0" }.to_string()),
+        },
+    };
+    let (_account, token) = account::get_active_with_token(storage)?;
+    GitHubClient::new(token)?.create_hook(&owner, &repo, &hook)
+}
+
+/// Delete a webhook.
+pub fn delete(storage: &impl Storage, repo_spec: Option<&str>, id: u64) -> Result<(), AppError> {
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+    let (_account, token) = account::get_active_for_destructive(storage)?;
+    GitHubClient::new(token)?.delete_hook(&owner, &repo, id)
+}
+
+/// Send a `ping` event to a webhook; its delivery shows up in [`deliveries`].
+pub fn ping(storage: &impl Storage, repo_spec: Option<&str>, id: u64) -> Result<(), AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+    client.ping_hook(&owner, &repo, id)
+}
+
+/// The most recent deliveries of a webhook, newest first.
+pub fn deliveries(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    id: u64,
+    limit: usize,
+) -> Result<Vec<HookDelivery>, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+    client.list_hook_deliveries(&owner, &repo, id, limit)
+}
+
+/// Read a webhook secret from stdin when it is piped, otherwise prompt for it (never echoed).
+#[cfg(feature = "interactive")]
+fn read_secret() -> Result<String, AppError> {
+    if !atty::is(atty::Stream::Stdin) {
+        let mut secret = String::new();
+        std::io::Read::read_to_string(&mut std::io::stdin(), &mut secret)?;
+        return Ok(secret.trim().to_string());
+    }
+    inquire::Password::new("Webhook secret:")
+        .prompt()
+        .map_err(|e| AppError::config(format!("secret entry cancelled: {e}")))
+}
+
+#[cfg(not(feature = "interactive"))]
+fn read_secret() -> Result<String, AppError> {
+    Err(AppError::TtyRequired)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_api;
+
+    #[test]
+    fn create_sends_config_and_default_events() {
+        let api = mock_api::server();
+        let hook = serde_json::json!({
+            "id": 12, "active": true, "events": ["push"],
+            "config": { "url": "https://ci.acme.com/hook", "content_type": "json",
+                        "insecure_ssl": "0" }
+        });
+        api.route("POST", "/repos/mock-hook/api/hooks", 201, hook);
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = mock_api::storage(dir.path());
+
+        let options = HookOptions {
+            url: "https://ci.acme.com/hook".into(),
+            content_type: WebhookContentType::Json,
+            events: Vec::new(),
+            secret: SecretSource::None,
+            insecure_ssl: false,
+            active: true,
+        };
+        let created = create(&storage, Some("mock-hook/api"), options).unwrap();
+        assert_eq!(created.id, 12);
+
+        let requests = api.requests("/repos/mock-hook/api/hooks");
+        let body: serde_json::Value = serde_json::from_str(&requests[0].body).unwrap();
+        assert_eq!(body["name"], "web");
+        assert_eq!(body["events"], serde_json::json!(["push"]));
+        assert_eq!(body["config"]["content_type"], "json");
+        assert!(body["config"].get("secret").is_none());
+    }
+}
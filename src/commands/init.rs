@@ -0,0 +1,305 @@
+//! First-run setup (`gho init`).
+//!
+//! `account add` needs a username, kind, protocol and token up front, which is a lot to look up
+//! before the first command works. `run` asks for the token first, takes the username from
+//! GitHub, and offers defaults for everything else. It then offers the two steps people tend to
+//! discover late: shell completions and gho as git's credential helper for github.com.
+
+use crate::commands::account;
+use crate::domain::{GITHUB_HOST, shell_join};
+use crate::error::AppError;
+use crate::github::{GitHubClient, UsersApi};
+use crate::logging;
+use crate::models::{AccountKind, CloneLayout, Protocol};
+use crate::platform;
+use crate::storage::Storage;
+use inquire::autocompletion::{Autocomplete, Replacement};
+use inquire::{Confirm, CustomUserError, Password, Select, Text};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// A shell gho can install completions for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl CompletionShell {
+    /// The login shell named by `$SHELL`, if it is one of the supported shells.
+    pub fn detect() -> Option<Self> {
+        std::env::var("SHELL").ok().and_then(|shell| Self::from_path(&shell))
+    }
+
+    fn from_path(shell: &str) -> Option<Self> {
+        match Path::new(shell).file_name()?.to_str()? {
+            "bash" => Some(Self::Bash),
+            "zsh" => Some(Self::Zsh),
+            "fish" => Some(Self::Fish),
+            _ => None,
+        }
+    }
+
+    /// Where the shell picks up completions for `gho`. Zsh only reads `~/.zfunc` when it is on
+    /// `fpath`.
+    pub fn completion_file(self, home: &Path) -> PathBuf {
+        match self {
+            Self::Bash => home.join(".local/share/bash-completion/completions/gho"),
+            Self::Zsh => home.join(".zfunc/_gho"),
+            Self::Fish => home.join(".config/fish/completions/gho.fish"),
+        }
+    }
+}
+
+impl fmt::Display for CompletionShell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bash => write!(f, "bash"),
+            Self::Zsh => write!(f, "zsh"),
+            Self::Fish => write!(f, "fish"),
+        }
+    }
+}
+
+/// What [`run`] set up.
+#[derive(Debug, Clone)]
+pub struct InitReport {
+    pub account: String,
+    pub username: String,
+    /// The shell and file completions were installed to.
+    pub completions: Option<(CompletionShell, PathBuf)>,
+    /// Whether gho became git's credential helper for github.com.
+    pub credential_helper: bool,
+}
+
+/// Walk through adding an account, then offer completions and the credential helper.
+///
+/// `completions` renders the completion script for a shell. When accounts already exist, the
+/// wizard asks before adding another one.
+pub fn run(
+    storage: &impl Storage,
+    completions: impl Fn(CompletionShell) -> String,
+) -> Result<InitReport, AppError> {
+    if !atty::is(atty::Stream::Stdin) {
+        return Err(AppError::TtyRequired);
+    }
+
+    let existing = storage.load_accounts()?.all_accounts().len();
+    if existing > 0
+        && !confirm(&format!("gho already has {existing} account(s). Add another?"), false)?
+    {
+        return Err(AppError::invalid_input("operation cancelled"));
+    }
+
+    let token = Password::new("GitHub token:")
+        .with_help_message("a classic or fine-grained personal access token; kept in the Keychain")
+        .without_confirmation()
+        .prompt()
+        .map_err(|e| AppError::config(format!("token entry cancelled: {e}")))?;
+    let token = token.trim().to_string();
+    let username = GitHubClient::new(token.clone())?.get_token_info()?.login;
+
+    let kind =
+        match select(&format!("What kind of account is {username}?"), vec!["personal", "work"])? {
+            "work" => AccountKind::Work,
+            _ => AccountKind::Personal,
+        };
+    let id = Text::new("Account ID:")
+        .with_default(&kind.to_string())
+        .with_help_message("the name you switch to with `gho account use`")
+        .prompt()
+        .map_err(|e| AppError::config(format!("input cancelled: {e}")))?;
+    let protocol = match select("Clone protocol:", vec!["ssh", "https"])? {
+        "https" => Protocol::Https,
+        _ => Protocol::Ssh,
+    };
+    let clone_dir = Text::new("Clone directory:")
+        .with_autocomplete(DirCompleter)
+        .with_help_message("Tab completes; leave empty to clone into the current directory")
+        .prompt()
+        .map_err(|e| AppError::config(format!("input cancelled: {e}")))?;
+    let clone_dir = match clone_dir.trim() {
+        "" => None,
+        dir => Some(std::path::absolute(expand_home(dir, home().as_deref()))?),
+    };
+
+    let expires_at = account::detect_token_expiry(&token).ok().flatten();
+    account::add(
+        storage,
+        id.trim(),
+        &username,
+        kind,
+        &token,
+        None,
+        None,
+        protocol,
+        clone_dir.map(|dir| dir.to_string_lossy().into_owned()),
+        CloneLayout::Flat,
+        expires_at,
+    )?;
+
+    let mut report = InitReport {
+        account: id.trim().to_string(),
+        username,
+        completions: None,
+        credential_helper: false,
+    };
+    if let (Some(shell), Some(home)) = (CompletionShell::detect(), home()) {
+        let file = shell.completion_file(&home);
+        if confirm(&format!("Install {shell} completions to {}?", file.display()), true)? {
+            if let Some(dir) = file.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(&file, completions(shell))?;
+            report.completions = Some((shell, file));
+        }
+    }
+    let question = format!("Let git fetch and push over https://{GITHUB_HOST} with gho's tokens?");
+    if confirm(&question, protocol == Protocol::Https)? {
+        install_credential_helper()?;
+        report.credential_helper = true;
+    }
+    Ok(report)
+}
+
+/// Make `gho auth git-credential` the only credential helper git uses for github.com.
+///
+/// The empty entry resets helpers configured for every host, e.g. `osxkeychain`, so they
+/// cannot answer with a stale password first.
+fn install_credential_helper() -> Result<(), AppError> {
+    // Git runs `!` helpers with its own `sh`, also on Windows.
+    let exe = platform::git_path(&std::env::current_exe()?);
+    let helper = format!("!{} auth git-credential", shell_join(&[exe]));
+    let key = format!("credential.https://{GITHUB_HOST}.helper");
+    git_config(&["--replace-all", &key, ""])?;
+    git_config(&["--add", &key, &helper])
+}
+
+fn git_config(args: &[&str]) -> Result<(), AppError> {
+    let status = logging::command(Command::new("git").args(["config", "--global"]).args(args))
+        .status()
+        .map_err(|e| AppError::git(format!("failed to run git: {e}")))?;
+    if !status.success() {
+        return Err(AppError::git(format!("git config failed with status {status}")));
+    }
+    Ok(())
+}
+
+fn confirm(message: &str, default: bool) -> Result<bool, AppError> {
+    Confirm::new(message)
+        .with_default(default)
+        .prompt()
+        .map_err(|e| AppError::config(format!("confirmation cancelled: {e}")))
+}
+
+fn select(message: &str, options: Vec<&'static str>) -> Result<&'static str, AppError> {
+    Select::new(message, options)
+        .prompt()
+        .map_err(|e| AppError::config(format!("selection cancelled: {e}")))
+}
+
+fn home() -> Option<PathBuf> {
+    platform::home_dir().ok()
+}
+
+/// `path` with a leading `~` replaced by `home`.
+fn expand_home(path: &str, home: Option<&Path>) -> PathBuf {
+    match (path.strip_prefix('~'), home) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with('/') => {
+            home.join(rest.trim_start_matches('/'))
+        }
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Completes directory names in the clone directory prompt.
+#[derive(Debug, Clone, Default)]
+struct DirCompleter;
+
+impl Autocomplete for DirCompleter {
+    fn get_suggestions(&mut self, input: &str) -> Result<Vec<String>, CustomUserError> {
+        Ok(complete_dir(input, home().as_deref()))
+    }
+
+    fn get_completion(
+        &mut self,
+        input: &str,
+        highlighted: Option<String>,
+    ) -> Result<Replacement, CustomUserError> {
+        Ok(highlighted.or_else(|| common_prefix(&complete_dir(input, home().as_deref()))))
+    }
+}
+
+/// Subdirectories that complete the last component of `input`, written the way `input` is
+/// (`~` stays unexpanded). Hidden directories are only offered once `input` asks for them.
+fn complete_dir(input: &str, home: Option<&Path>) -> Vec<String> {
+    let (dir, prefix) = match input.rfind('/') {
+        Some(i) => input.split_at(i + 1),
+        None => ("", input),
+    };
+    let listed = if dir.is_empty() { PathBuf::from(".") } else { expand_home(dir, home) };
+    let Ok(entries) = fs::read_dir(listed) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| {
+            name.starts_with(prefix) && (prefix.starts_with('.') || !name.starts_with('.'))
+        })
+        .map(|name| format!("{dir}{name}/"))
+        .collect();
+    names.sort();
+    names
+}
+
+/// The longest prefix every suggestion shares, if there are any.
+fn common_prefix(suggestions: &[String]) -> Option<String> {
+    let mut prefix = suggestions.first()?.clone();
+    for suggestion in &suggestions[1..] {
+        while !suggestion.starts_with(&prefix) {
+            prefix.pop();
+        }
+    }
+    Some(prefix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn complete_dir_lists_matching_subdirectories() {
+        let root = TempDir::new().unwrap();
+        for dir in ["src", "scratch", ".cache"] {
+            fs::create_dir(root.path().join(dir)).unwrap();
+        }
+        fs::write(root.path().join("setup.sh"), "").unwrap();
+        let home = root.path();
+
+        assert_eq!(complete_dir("~/s", Some(home)), ["~/scratch/", "~/src/"]);
+        assert_eq!(complete_dir("~/.c", Some(home)), ["~/.cache/"]);
+        assert_eq!(common_prefix(&complete_dir("~/s", Some(home))).as_deref(), Some("~/s"));
+        let absolute = format!("{}/sr", home.display());
+        assert_eq!(complete_dir(&absolute, None), [format!("{}/src/", home.display())]);
+        assert!(complete_dir("~/missing/", Some(home)).is_empty());
+    }
+
+    #[test]
+    fn completion_shell_follows_the_login_shell() {
+        assert_eq!(CompletionShell::from_path("/bin/zsh"), Some(CompletionShell::Zsh));
+        assert_eq!(CompletionShell::from_path("/usr/local/bin/fish"), Some(CompletionShell::Fish));
+        assert_eq!(CompletionShell::from_path("/bin/tcsh"), None);
+        assert_eq!(
+            CompletionShell::Bash.completion_file(Path::new("/home/dev")),
+            Path::new("/home/dev/.local/share/bash-completion/completions/gho")
+        );
+        assert_eq!(expand_home("~/src", Some(Path::new("/home/dev"))), Path::new("/home/dev/src"));
+        assert_eq!(expand_home("~dev/src", Some(Path::new("/home/dev"))), Path::new("~dev/src"));
+    }
+}
@@ -0,0 +1,253 @@
+//! Issue commands (`gho issue`).
+//!
+//! `triage` is an inbox-zero pass over the open issues nobody has sorted yet, those without a
+//! label or without an assignee. It shows them one at a time, oldest first, and applies each
+//! label, assignment, comment or close as soon as it is picked, so quitting halfway keeps
+//! everything done so far.
+
+#[cfg(feature = "interactive")]
+use crate::commands::account;
+use crate::error::AppError;
+#[cfg(feature = "interactive")]
+use crate::github::GitHubClient;
+use crate::github::IssuesApi;
+use crate::models::Issue;
+#[cfg(feature = "interactive")]
+use crate::models::IssueUpdate;
+#[cfg(feature = "interactive")]
+use crate::remote::resolve_repo;
+#[cfg(feature = "interactive")]
+use crate::storage::Storage;
+
+/// Lines of an issue body shown before the rest is cut off.
+#[cfg(feature = "interactive")]
+const BODY_LINES: usize = 30;
+
+/// Open issues to triage, with what they can be labeled with and assigned to.
+#[derive(Debug, Clone)]
+pub struct TriageQueue {
+    pub issues: Vec<Issue>,
+    /// Label names of the repository.
+    pub labels: Vec<String>,
+    /// Logins issues can be assigned to.
+    pub assignees: Vec<String>,
+}
+
+/// What a triage session did.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TriageSummary {
+    /// Issues shown.
+    pub reviewed: usize,
+    /// Issues labeled, assigned or commented on.
+    pub updated: usize,
+    pub closed: usize,
+    /// Issues of the queue not reached before quitting.
+    pub remaining: usize,
+}
+
+/// Whether an issue still needs triage: it has no label or no assignee.
+pub fn needs_triage(issue: &Issue) -> bool {
+    issue.labels.is_empty() || issue.assignees.is_empty()
+}
+
+/// The oldest `limit` open issues of a repository that need triage.
+pub fn queue(
+    client: &impl IssuesApi,
+    owner: &str,
+    repo: &str,
+    limit: usize,
+) -> Result<TriageQueue, AppError> {
+    let issues: Vec<Issue> = client
+        .list_open_issues(owner, repo)?
+        .into_iter()
+        .filter(needs_triage)
+        .take(limit)
+        .collect();
+    let labels = client.list_labels(owner, repo)?.into_iter().map(|label| label.name).collect();
+    let assignees =
+        client.list_assignees(owner, repo)?.into_iter().map(|user| user.login).collect();
+    Ok(TriageQueue { issues, labels, assignees })
+}
+
+/// Walk the oldest `limit` issues needing triage, asking what to do with each.
+///
+/// Esc in a prompt goes back to the issue's menu (or, in the menu, on to the next issue);
+/// Ctrl-C ends the session like Quit.
+#[cfg(feature = "interactive")]
+pub fn triage(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    limit: usize,
+) -> Result<TriageSummary, AppError> {
+    use inquire::{InquireError, MultiSelect, Select, Text};
+
+    if !atty::is(atty::Stream::Stdin) {
+        return Err(AppError::TtyRequired);
+    }
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+    let queue = queue(&client, &owner, &repo, limit)?;
+
+    let cancelled = |e: InquireError| AppError::config(format!("triage cancelled: {e}"));
+    let total = queue.issues.len();
+    let mut summary = TriageSummary { remaining: total, ..TriageSummary::default() };
+    for (i, mut issue) in queue.issues.into_iter().enumerate() {
+        show(&issue, i + 1, total);
+        summary.reviewed += 1;
+        summary.remaining -= 1;
+        let mut updated = false;
+        loop {
+            let actions = vec!["Label", "Assign", "Comment", "Close", "Next issue", "Quit"];
+            let action = match Select::new("Action:", actions).prompt() {
+                Ok(action) => action,
+                Err(InquireError::OperationCanceled) => "Next issue",
+                Err(InquireError::OperationInterrupted) => "Quit",
+                Err(e) => return Err(cancelled(e)),
+            };
+            let update = match action {
+                "Label" => {
+                    let current = selected(&queue.labels, issue.labels.iter().map(|l| &l.name));
+                    MultiSelect::new("Labels:", queue.labels.clone())
+                        .with_default(&current)
+                        .prompt_skippable()
+                        .map_err(cancelled)?
+                        .map(|labels| IssueUpdate { labels: Some(labels), ..Default::default() })
+                }
+                "Assign" => {
+                    let current =
+                        selected(&queue.assignees, issue.assignees.iter().map(|u| &u.login));
+                    MultiSelect::new("Assignees:", queue.assignees.clone())
+                        .with_default(&current)
+                        .prompt_skippable()
+                        .map_err(cancelled)?
+                        .map(|users| IssueUpdate { assignees: Some(users), ..Default::default() })
+                }
+                "Comment" => {
+                    let body = Text::new("Comment:").prompt_skippable().map_err(cancelled)?;
+                    if let Some(body) = body.filter(|body| !body.trim().is_empty()) {
+                        client.create_issue_comment(&owner, &repo, issue.number, &body)?;
+                        updated = true;
+                    }
+                    None
+                }
+                "Close" => {
+                    let reasons = vec!["completed", "not planned"];
+                    match Select::new("Close as:", reasons).prompt_skippable().map_err(cancelled)? {
+                        Some(reason) => {
+                            let update = IssueUpdate {
+                                state: Some("closed".to_string()),
+                                state_reason: Some(reason.replace(' ', "_")),
+                                ..Default::default()
+                            };
+                            client.update_issue(&owner, &repo, issue.number, &update)?;
+                            summary.closed += 1;
+                            updated = false;
+                            break;
+                        }
+                        None => None,
+                    }
+                }
+                "Next issue" => break,
+                _ => {
+                    summary.updated += usize::from(updated);
+                    return Ok(summary);
+                }
+            };
+            if let Some(update) = update {
+                issue = client.update_issue(&owner, &repo, issue.number, &update)?;
+                updated = true;
+            }
+        }
+        summary.updated += usize::from(updated);
+    }
+    Ok(summary)
+}
+
+/// Indices of `chosen` among `options`.
+#[cfg(feature = "interactive")]
+fn selected<'a>(options: &[String], chosen: impl Iterator<Item = &'a String>) -> Vec<usize> {
+    let chosen: Vec<_> = chosen.collect();
+    options.iter().enumerate().filter(|(_, o)| chosen.contains(o)).map(|(i, _)| i).collect()
+}
+
+#[cfg(feature = "interactive")]
+fn show(issue: &Issue, position: usize, total: usize) {
+    let names =
+        |names: Vec<&str>| if names.is_empty() { "none".to_string() } else { names.join(", ") };
+    let author = issue.user.as_ref().map_or("ghost", |user| user.login.as_str());
+    println!();
+    println!("#{} {}  ({position}/{total})", issue.number, issue.title);
+    println!(
+        "opened by {author} on {} · {} comments · {}",
+        issue.created_at.format("%Y-%m-%d"),
+        issue.comments,
+        issue.html_url
+    );
+    println!(
+        "labels: {} · assignees: {}",
+        names(issue.labels.iter().map(|l| l.name.as_str()).collect()),
+        names(issue.assignees.iter().map(|u| u.login.as_str()).collect())
+    );
+    println!();
+    let body = issue.body.as_deref().map(str::trim).filter(|body| !body.is_empty());
+    let lines: Vec<&str> = body.unwrap_or("(no description)").lines().collect();
+    for line in lines.iter().take(BODY_LINES) {
+        println!("  {line}");
+    }
+    if lines.len() > BODY_LINES {
+        println!("  … {} more lines", lines.len() - BODY_LINES);
+    }
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::GitHubClient;
+    use crate::mock_api;
+
+    #[test]
+    fn queue_skips_pull_requests_and_sorted_issues() {
+        let api = mock_api::server();
+        let base = "/repos/mock-issue-triage/api";
+        let issue = |number: u64, labels: &[&str], assignees: &[&str], pull: bool| {
+            let labels: Vec<_> =
+                labels.iter().map(|name| serde_json::json!({ "name": name })).collect();
+            let assignees: Vec<_> =
+                assignees.iter().map(|login| serde_json::json!({ "login": login })).collect();
+            let mut issue = serde_json::json!({
+                "number": number, "title": "Crash", "labels": labels, "assignees": assignees,
+                "created_at": "2026-01-01T00:00:00Z", "html_url": ""
+            });
+            if pull {
+                issue["pull_request"] = serde_json::json!({ "url": "" });
+            }
+            issue
+        };
+        let issues = [
+            issue(1, &["bug"], &["octocat"], false),
+            issue(2, &[], &["octocat"], false),
+            issue(3, &[], &[], true),
+            issue(4, &["bug"], &[], false),
+            issue(5, &[], &[], false),
+        ];
+        api.route("GET", &format!("{base}/issues"), 200, serde_json::json!(issues));
+        api.route("GET", &format!("{base}/labels"), 200, serde_json::json!([{ "name": "bug" }]));
+        api.route(
+            "GET",
+            &format!("{base}/assignees"),
+            200,
+            serde_json::json!([{ "login": "hubot" }]),
+        );
+
+        let client = GitHubClient::new("mock-token".to_string()).unwrap();
+        let queue = queue(&client, "mock-issue-triage", "api", 2).unwrap();
+        let numbers: Vec<u64> = queue.issues.iter().map(|issue| issue.number).collect();
+        assert_eq!(numbers, [2, 4]);
+        assert_eq!(queue.labels, ["bug"]);
+        assert_eq!(queue.assignees, ["hubot"]);
+        let listed = &api.requests(&format!("{base}/issues"))[0].path;
+        assert!(listed.contains("state=open&sort=created&direction=asc"));
+    }
+}
@@ -0,0 +1,278 @@
+//! Issue and pull request label commands.
+
+use crate::commands::account;
+use crate::domain::parse_repo_spec;
+use crate::error::AppError;
+use crate::github::{GitHubClient, IssuesApi};
+use crate::models::{ExecutionMode, Label, LabelSyncChange, ProgressOutcome};
+use crate::progress::Progress;
+use crate::remote::resolve_repo;
+use crate::storage::Storage;
+use std::path::Path;
+
+/// List the labels of a repository.
+pub fn list(storage: &impl Storage, repo_spec: Option<&str>) -> Result<Vec<Label>, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+    client.list_labels(&owner, &repo)
+}
+
+/// Create a label.
+pub fn create(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    label: Label,
+) -> Result<Label, AppError> {
+    let label = normalize(label)?;
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+    client.create_label(&owner, &repo, &label)
+}
+
+/// Delete a label.
+pub fn delete(storage: &impl Storage, repo_spec: Option<&str>, name: &str) -> Result<(), AppError> {
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+    let (_account, token) = account::get_active_for_destructive(storage)?;
+    let client = GitHubClient::new(token)?;
+    client.delete_label(&owner, &repo, name)
+}
+
+/// Read a label scheme from a YAML or JSON list of `{name, color, description}`.
+pub fn load_file(path: &Path) -> Result<Vec<Label>, AppError> {
+    let content = std::fs::read_to_string(path)?;
+    parse_file(&content)
+        .map_err(|e| AppError::invalid_input(format!("invalid {}: {e}", path.display())))
+}
+
+fn parse_file(content: &str) -> Result<Vec<Label>, String> {
+    // JSON is valid YAML, so one parser handles both formats.
+    let labels: Vec<Label> = serde_yaml::from_str(content).map_err(|e| e.to_string())?;
+    if labels.is_empty() {
+        return Err("no labels defined".to_string());
+    }
+    let labels = labels
+        .into_iter()
+        .map(|label| normalize(label).map_err(|e| e.to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+    for (i, label) in labels.iter().enumerate() {
+        if labels[..i].iter().any(|l| l.name.eq_ignore_ascii_case(&label.name)) {
+            return Err(format!("duplicate label '{}'", label.name));
+        }
+    }
+    Ok(labels)
+}
+
+/// Operation name of `label sync` progress events.
+const SYNC_OPERATION: &str = "label sync";
+
+/// Reconcile the labels of each repository with `labels`.
+///
+/// Missing labels are created and labels whose color or description differ are updated;
+/// with `prune`, labels not in the scheme are deleted. Label names match case-insensitively,
+/// as on GitHub. Failures are recorded per repository.
+pub fn sync(
+    storage: &impl Storage,
+    repos: &[String],
+    labels: &[Label],
+    prune: bool,
+    mode: ExecutionMode,
+    progress: Progress,
+) -> Result<Vec<LabelSyncChange>, AppError> {
+    let targets = if repos.is_empty() {
+        let (owner, repo) = resolve_repo(storage, None)?;
+        vec![format!("{owner}/{repo}")]
+    } else {
+        for repo in repos {
+            parse_repo_spec(repo)?;
+        }
+        repos.to_vec()
+    };
+
+    let (_account, token) = if prune && !mode.is_dry_run() {
+        account::get_active_for_destructive(storage)?
+    } else {
+        account::get_active_with_token(storage)?
+    };
+    let client = GitHubClient::new(token)?;
+
+    Ok(targets
+        .iter()
+        .enumerate()
+        .map(|(i, repo)| {
+            progress.started(SYNC_OPERATION, repo, i + 1, targets.len());
+            let change = sync_repo(&client, repo, labels, prune, mode);
+            let outcome = if change.error.is_some() {
+                ProgressOutcome::Failed
+            } else if change.created.is_empty()
+                && change.updated.is_empty()
+                && change.deleted.is_empty()
+            {
+                ProgressOutcome::Skipped
+            } else {
+                ProgressOutcome::Succeeded
+            };
+            progress.completed(SYNC_OPERATION, repo, outcome, change.error.as_deref());
+            change
+        })
+        .collect())
+}
+
+fn sync_repo(
+    client: &impl IssuesApi,
+    spec: &str,
+    labels: &[Label],
+    prune: bool,
+    mode: ExecutionMode,
+) -> LabelSyncChange {
+    let mut change = LabelSyncChange {
+        repo: spec.to_string(),
+        created: Vec::new(),
+        updated: Vec::new(),
+        deleted: Vec::new(),
+        error: None,
+    };
+
+    let (owner, repo) = parse_repo_spec(spec).expect("validated in sync");
+    let result = client.list_labels(&owner, &repo).and_then(|current| {
+        let plan = plan_sync(&current, labels, prune);
+        for label in plan.create {
+            if !mode.is_dry_run() {
+                client.create_label(&owner, &repo, label)?;
+            }
+            change.created.push(label.name.clone());
+        }
+        for label in plan.update {
+            if !mode.is_dry_run() {
+                client.update_label(&owner, &repo, &label)?;
+            }
+            change.updated.push(label.name);
+        }
+        for name in plan.delete {
+            if !mode.is_dry_run() {
+                client.delete_label(&owner, &repo, &name)?;
+            }
+            change.deleted.push(name);
+        }
+        Ok(())
+    });
+    if let Err(e) = result {
+        change.error = Some(e.to_string());
+    }
+    change
+}
+
+/// Changes needed to turn `current` into `desired`.
+#[derive(Debug, Default)]
+struct SyncPlan<'a> {
+    create: Vec<&'a Label>,
+    /// Desired labels carrying the existing (case-preserved) name to address them by.
+    update: Vec<Label>,
+    delete: Vec<String>,
+}
+
+fn plan_sync<'a>(current: &[Label], desired: &'a [Label], prune: bool) -> SyncPlan<'a> {
+    let mut plan = SyncPlan::default();
+    for label in desired {
+        match current.iter().find(|c| c.name.eq_ignore_ascii_case(&label.name)) {
+            None => plan.create.push(label),
+            Some(existing) if differs(existing, label) => {
+                plan.update.push(Label { name: existing.name.clone(), ..label.clone() })
+            }
+            Some(_) => {}
+        }
+    }
+    if prune {
+        plan.delete = current
+            .iter()
+            .filter(|c| !desired.iter().any(|d| d.name.eq_ignore_ascii_case(&c.name)))
+            .map(|c| c.name.clone())
+            .collect();
+    }
+    plan
+}
+
+/// Whether `desired` sets a color or description different from `existing`.
+fn differs(existing: &Label, desired: &Label) -> bool {
+    let color_differs = desired.color.as_ref().is_some_and(|color| {
+        existing.color.as_ref().is_none_or(|c| !c.eq_ignore_ascii_case(color))
+    });
+    let description_differs = desired
+        .description
+        .as_ref()
+        .is_some_and(|d| existing.description.as_deref().unwrap_or("") != d);
+    color_differs || description_differs
+}
+
+/// Strip a leading `#` from the color and check it is six hex digits.
+fn normalize(mut label: Label) -> Result<Label, AppError> {
+    label.name = label.name.trim().to_string();
+    if label.name.is_empty() {
+        return Err(AppError::invalid_input("label name cannot be empty"));
+    }
+    if let Some(color) = &label.color {
+        let color = color.trim_start_matches('#').to_lowercase();
+        if color.len() != 6 || !color.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(AppError::invalid_input(format!(
+                "invalid color '{color}' for label '{}', expected 6 hex digits",
+                label.name
+            )));
+        }
+        label.color = Some(color);
+    }
+    Ok(label)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn label(name: &str, color: Option<&str>, description: Option<&str>) -> Label {
+        Label {
+            name: name.to_string(),
+            color: color.map(str::to_string),
+            description: description.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn plan_sync_creates_updates_and_prunes() {
+        let current = vec![
+            label("Bug", Some("d73a4a"), Some("Something is broken")),
+            label("docs", Some("0075ca"), None),
+            label("wontfix", Some("ffffff"), None),
+        ];
+        let desired = vec![
+            label("bug", Some("D73A4A"), Some("Something is broken")),
+            label("docs", Some("1d76db"), None),
+            label("security", Some("b60205"), None),
+        ];
+
+        let plan = plan_sync(&current, &desired, true);
+        assert_eq!(plan.create.iter().map(|l| l.name.as_str()).collect::<Vec<_>>(), ["security"]);
+        assert_eq!(plan.update.len(), 1);
+        assert_eq!(plan.update[0].name, "docs");
+        assert_eq!(plan.update[0].color.as_deref(), Some("1d76db"));
+        assert_eq!(plan.delete, vec!["wontfix".to_string()]);
+
+        assert!(plan_sync(&current, &desired, false).delete.is_empty());
+    }
+
+    #[test]
+    fn parse_file_accepts_yaml_and_json() {
+        let yaml = parse_file("- name: bug\n  color: '#D73A4A'\n  description: Broken\n").unwrap();
+        assert_eq!(yaml[0].color.as_deref(), Some("d73a4a"));
+
+        let json = parse_file(r#"[{"name": "docs"}]"#).unwrap();
+        assert_eq!(json[0].name, "docs");
+        assert_eq!(json[0].color, None);
+    }
+
+    #[test]
+    fn parse_file_rejects_invalid_schemes() {
+        assert!(parse_file("[]").is_err());
+        assert!(parse_file("- name: bug\n  color: red\n").is_err());
+        assert!(parse_file("- name: bug\n- name: Bug\n").is_err());
+    }
+}
@@ -0,0 +1,210 @@
+//! Clone manifests (`gho-clones.json`).
+//!
+//! Bulk clones record the repository, commit and directory of every checkout, so
+//! `repo clone --from-manifest` can rebuild the same layout on another machine or in CI.
+
+use crate::commands::account;
+use crate::domain::{GITHUB_HOST, build_clone_url, parse_repo_spec};
+use crate::error::AppError;
+use crate::logging;
+use crate::models::{
+    CloneManifest, ClonedRepo, Execution, ExecutionMode, ManifestCheckout, ProgressOutcome,
+};
+use crate::progress::Progress;
+use crate::storage::Storage;
+use chrono::Utc;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Manifest file name used when no `--manifest` is given.
+pub const MANIFEST_FILE: &str = "gho-clones.json";
+
+/// Operation name of `repo clone --from-manifest` progress events.
+const RESTORE_OPERATION: &str = "repo clone";
+
+/// Write the manifest at `path` for `checkouts` (`(owner/repo, dir)` pairs) made by `account`.
+///
+/// Directories that are not git checkouts, e.g. failed clones, are left out.
+pub fn write(
+    path: &Path,
+    account: &str,
+    checkouts: &[(String, PathBuf)],
+) -> Result<CloneManifest, AppError> {
+    let base = base_dir(path)?;
+    let mut repos = Vec::new();
+    for (repo, dir) in checkouts {
+        let Some(commit) = head_commit(dir) else {
+            continue;
+        };
+        let dir = std::path::absolute(dir)?;
+        let path = dir.strip_prefix(&base).map(Path::to_path_buf).unwrap_or(dir);
+        repos.push(ClonedRepo { repo: repo.clone(), commit, path, account: account.to_string() });
+    }
+    repos.sort_by(|a, b| a.repo.cmp(&b.repo));
+
+    let manifest = CloneManifest { generated_at: Utc::now(), repos };
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(&manifest)? + "\n")?;
+    Ok(manifest)
+}
+
+/// Read a clone manifest.
+pub fn load(path: &Path) -> Result<CloneManifest, AppError> {
+    let content = fs::read_to_string(path).map_err(|e| {
+        AppError::invalid_input(format!("cannot read clone manifest {}: {e}", path.display()))
+    })?;
+    serde_json::from_str(&content)
+        .map_err(|e| AppError::invalid_input(format!("invalid {}: {e}", path.display())))
+}
+
+/// Clone every repository of the manifest at `path` into its recorded directory and check out
+/// its recorded commit.
+///
+/// Clones use the active account's protocol. Directories that already exist are left alone.
+pub fn restore(
+    storage: &impl Storage,
+    path: &Path,
+    mode: ExecutionMode,
+    progress: Progress,
+) -> Result<Execution<Vec<ManifestCheckout>>, AppError> {
+    let manifest = load(path)?;
+    let (account, _token) = account::get_active_with_token(storage)?;
+    let base = base_dir(path)?;
+
+    let host = account.host.as_deref().unwrap_or(GITHUB_HOST);
+    let mut targets = Vec::new();
+    for entry in &manifest.repos {
+        let (owner, repo) = parse_repo_spec(&entry.repo)?;
+        targets.push((
+            entry,
+            build_clone_url(host, &owner, &repo, account.protocol),
+            base.join(&entry.path),
+        ));
+    }
+
+    if mode.is_dry_run() {
+        let steps = targets
+            .iter()
+            .filter(|(_, _, dir)| !dir.exists())
+            .map(|(entry, url, dir)| {
+                format!("git clone {url} {} && checkout {}", dir.display(), entry.commit)
+            })
+            .collect();
+        return Ok(Execution::Planned(steps));
+    }
+
+    let total = targets.len();
+    Ok(Execution::Done(
+        targets
+            .into_iter()
+            .enumerate()
+            .map(|(i, (entry, url, dir))| {
+                progress.started(RESTORE_OPERATION, &entry.repo, i + 1, total);
+                let checkout = checkout(entry, &url, dir, progress.is_json());
+                let outcome = match (&checkout.error, checkout.cloned) {
+                    (Some(_), _) => ProgressOutcome::Failed,
+                    (None, true) => ProgressOutcome::Succeeded,
+                    (None, false) => ProgressOutcome::Skipped,
+                };
+                progress.completed(
+                    RESTORE_OPERATION,
+                    &entry.repo,
+                    outcome,
+                    checkout.error.as_deref(),
+                );
+                checkout
+            })
+            .collect(),
+    ))
+}
+
+/// Absolute directory holding the manifest at `path`, which manifest paths are relative to.
+fn base_dir(path: &Path) -> Result<PathBuf, AppError> {
+    let dir = path.parent().unwrap_or(Path::new(""));
+    if dir.as_os_str().is_empty() {
+        return Ok(std::env::current_dir()?);
+    }
+    Ok(std::path::absolute(dir)?)
+}
+
+fn head_commit(dir: &Path) -> Option<String> {
+    let mut command = Command::new("git");
+    let output =
+        logging::command(command.arg("-C").arg(dir).args(["rev-parse", "HEAD"])).output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn checkout(entry: &ClonedRepo, url: &str, dir: PathBuf, quiet: bool) -> ManifestCheckout {
+    let mut checkout =
+        ManifestCheckout { repo: entry.repo.clone(), dir, cloned: false, error: None };
+    if checkout.dir.exists() {
+        return checkout;
+    }
+
+    let mut clone = Command::new("git");
+    clone.arg("clone");
+    if quiet {
+        clone.arg("--quiet");
+    }
+    let result = run(clone.arg(url).arg(&checkout.dir), "git clone").and_then(|()| {
+        let mut detach = Command::new("git");
+        detach.arg("-C").arg(&checkout.dir).args(["checkout", "--quiet", "--detach"]);
+        run(detach.arg(&entry.commit), "git checkout")
+    });
+    match result {
+        Ok(()) => checkout.cloned = true,
+        Err(e) => checkout.error = Some(e),
+    }
+    checkout
+}
+
+fn run(command: &mut Command, what: &str) -> Result<(), String> {
+    match logging::command(command).status() {
+        Ok(status) if status.success() => Ok(()),
+        Ok(status) => Err(format!("{what} failed with status {status}")),
+        Err(e) => Err(format!("failed to run git: {e}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    #[test]
+    fn write_records_commits_relative_to_the_manifest() {
+        let root = TempDir::new().unwrap();
+        let api = root.path().join("acme").join("api");
+        fs::create_dir_all(&api).unwrap();
+        git(&api, &["init", "--quiet"]);
+        git(&api, &["commit", "--quiet", "--allow-empty", "-m", "init"]);
+        let missing = root.path().join("acme").join("web");
+
+        let path = root.path().join(MANIFEST_FILE);
+        let checkouts = vec![("acme/web".to_string(), missing), ("acme/api".to_string(), api)];
+        let manifest = write(&path, "work", &checkouts).unwrap();
+
+        assert_eq!(manifest.repos.len(), 1);
+        let entry = &manifest.repos[0];
+        assert_eq!(entry.repo, "acme/api");
+        assert_eq!(entry.path, Path::new("acme").join("api"));
+        assert_eq!(entry.commit.len(), 40);
+        assert_eq!(entry.account, "work");
+        assert_eq!(load(&path).unwrap(), manifest);
+    }
+}
@@ -1,5 +1,77 @@
 //! CLI commands for gho.
 
+#[cfg(all(feature = "network", feature = "keychain"))]
+pub mod access;
+#[cfg(feature = "keychain")]
 pub mod account;
+#[cfg(all(feature = "network", feature = "keychain"))]
+pub mod actions;
+#[cfg(all(feature = "network", feature = "keychain"))]
+pub mod api;
+#[cfg(all(feature = "network", feature = "keychain"))]
+pub mod auth;
+pub mod bootstrap;
+#[cfg(all(feature = "network", feature = "keychain"))]
+pub mod branch;
+#[cfg(all(feature = "network", feature = "keychain"))]
+pub mod checks;
+#[cfg(feature = "scan")]
+pub mod clones;
+#[cfg(all(feature = "network", feature = "keychain"))]
+pub mod commit;
+pub mod config;
+#[cfg(all(feature = "network", feature = "keychain"))]
+pub mod conflicts;
+#[cfg(all(feature = "network", feature = "keychain"))]
+pub mod contrib;
+#[cfg(all(feature = "network", feature = "keychain"))]
+pub mod daemon;
+pub mod doctor;
+#[cfg(all(feature = "network", feature = "keychain"))]
+pub mod fanout;
+#[cfg(all(feature = "network", feature = "keychain"))]
+pub mod gist;
+pub mod gitconfig;
+pub mod history;
+#[cfg(all(feature = "network", feature = "keychain"))]
+pub mod hook;
+#[cfg(all(feature = "network", feature = "keychain", feature = "interactive"))]
+pub mod init;
+#[cfg(all(feature = "network", feature = "keychain"))]
+pub mod issue;
+#[cfg(all(feature = "network", feature = "keychain"))]
+pub mod label;
+#[cfg(all(feature = "network", feature = "keychain"))]
+pub mod manifest;
+#[cfg(all(feature = "network", feature = "keychain"))]
+pub mod notify;
+#[cfg(all(feature = "network", feature = "keychain"))]
+pub mod org;
+#[cfg(all(feature = "network", feature = "keychain"))]
+pub mod package;
+#[cfg(all(feature = "network", feature = "keychain"))]
 pub mod pr;
+#[cfg(all(feature = "network", feature = "keychain"))]
+pub mod protection;
+#[cfg(all(feature = "network", feature = "keychain"))]
+pub mod queue;
+pub mod recent;
+#[cfg(all(feature = "network", feature = "keychain"))]
+pub mod release;
+#[cfg(all(feature = "network", feature = "keychain"))]
 pub mod repo;
+#[cfg(all(feature = "network", feature = "keychain"))]
+pub mod run;
+#[cfg(all(feature = "network", feature = "keychain"))]
+pub mod runner;
+#[cfg(all(feature = "network", feature = "keychain"))]
+pub mod secret;
+#[cfg(feature = "selftest")]
+pub mod selftest;
+pub mod sshagent;
+#[cfg(all(feature = "network", feature = "keychain"))]
+pub mod variable;
+#[cfg(all(feature = "network", feature = "keychain"))]
+pub mod workflow;
+#[cfg(all(feature = "network", feature = "keychain"))]
+pub mod workspace;
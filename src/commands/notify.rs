@@ -0,0 +1,89 @@
+//! Notification inbox commands (`gho notify`).
+
+use crate::commands::account;
+use crate::domain::notification_web_url;
+use crate::error::AppError;
+use crate::github::{GitHubClient, Poller, UsersApi};
+use crate::models::{Notification, NotificationFilter};
+use crate::storage::Storage;
+use std::time::Duration;
+
+/// Interval between inbox polls while it keeps changing; GitHub usually asks for this too.
+const WATCH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// List notification threads of the active account, most recent first.
+pub fn list(
+    storage: &impl Storage,
+    filter: NotificationFilter,
+    limit: usize,
+) -> Result<Vec<Notification>, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    GitHubClient::new(token)?.list_notifications(filter, limit)
+}
+
+/// Poll the inbox until the process is killed, calling `on_change` with the threads whenever
+/// they changed.
+///
+/// Polls are conditional on the previous response and spaced by a [`Poller`], so an unchanged
+/// inbox costs no rate limit.
+pub fn watch(
+    storage: &impl Storage,
+    filter: NotificationFilter,
+    limit: usize,
+    mut on_change: impl FnMut(&[Notification]),
+) -> Result<(), AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?.polling();
+    let mut poller = Poller::new(WATCH_INTERVAL);
+
+    loop {
+        let notifications = client.list_notifications(filter, limit)?;
+        let state = client.poll_state();
+        if state.changed {
+            on_change(&notifications);
+        }
+        poller.wait(&state);
+    }
+}
+
+/// Mark a thread as read, or every thread when `id` is `None`.
+pub fn mark_read(storage: &impl Storage, id: Option<&str>) -> Result<(), AppError> {
+    if let Some(id) = id {
+        validate_id(id)?;
+    }
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    match id {
+        Some(id) => client.mark_notification_read(id),
+        None => client.mark_all_notifications_read(),
+    }
+}
+
+/// Web page of a thread's issue, pull request or commit.
+pub fn web_url(storage: &impl Storage, id: &str) -> Result<String, AppError> {
+    validate_id(id)?;
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let thread = GitHubClient::new(token)?.get_notification_thread(id)?;
+    Ok(notification_web_url(&thread.repository.full_name, thread.subject.url.as_deref()))
+}
+
+fn validate_id(id: &str) -> Result<(), AppError> {
+    if id.is_empty() || !id.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(AppError::invalid_input(format!(
+            "invalid notification id '{id}', expected the number shown by `gho notify list`"
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_id_accepts_only_numbers() {
+        assert!(validate_id("123456").is_ok());
+        assert!(validate_id("").is_err());
+        assert!(validate_id("12a").is_err());
+    }
+}
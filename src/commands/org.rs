@@ -0,0 +1,173 @@
+//! Organization commands (`gho org`) and organization selection for commands whose `--org`
+//! is optional.
+
+use crate::commands::account;
+use crate::error::AppError;
+use crate::github::{GitHubClient, OrgsApi};
+use crate::models::{OrgMember, OrgRole, Organization, RepoPermission, Team, TeamRepository};
+use crate::storage::Storage;
+
+/// Organizations the active account's token can see.
+pub fn list(storage: &impl Storage) -> Result<Vec<Organization>, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    GitHubClient::new(token)?.list_user_orgs()
+}
+
+/// Members of an organization sorted by login, optionally only those with `role`.
+pub fn members(
+    storage: &impl Storage,
+    org: &str,
+    role: Option<OrgRole>,
+) -> Result<Vec<OrgMember>, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    remember(storage, org)?;
+
+    // The API only reports roles through its filter, so "everyone" is admins plus members.
+    let roles = match role {
+        Some(role) => vec![role],
+        None => vec![OrgRole::Admin, OrgRole::Member],
+    };
+    let mut members = Vec::new();
+    for role in roles {
+        members.extend(client.list_org_members(org, role)?);
+    }
+    members.sort_by_key(|m| m.login.to_lowercase());
+    Ok(members)
+}
+
+/// Teams of an organization.
+pub fn teams(storage: &impl Storage, org: &str) -> Result<Vec<Team>, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    remember(storage, org)?;
+    client.list_org_teams(org)
+}
+
+/// Repositories a team (`org/team-slug`) can access, with the team's role on each.
+pub fn team_repos(
+    storage: &impl Storage,
+    team_spec: &str,
+) -> Result<Vec<TeamRepository>, AppError> {
+    let (org, slug) = parse_team_spec(team_spec)?;
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    remember(storage, org)?;
+
+    Ok(client
+        .list_team_repos(org, slug)?
+        .into_iter()
+        .map(|repo| TeamRepository {
+            permission: RepoPermission::from_flags(&repo.permissions.unwrap_or_default()),
+            full_name: repo.full_name,
+        })
+        .collect())
+}
+
+fn parse_team_spec(spec: &str) -> Result<(&str, &str), AppError> {
+    match spec.split_once('/') {
+        Some((org, slug)) if !org.is_empty() && !slug.is_empty() && !slug.contains('/') => {
+            Ok((org, slug))
+        }
+        _ => Err(AppError::invalid_input(format!("invalid team '{spec}', expected org/team-slug"))),
+    }
+}
+
+/// Organization for a command, recording explicit choices as the last used one.
+///
+/// When `--org` is omitted and the active account has no default org, a terminal session is
+/// offered the account's organizations in a picker with the last used one preselected.
+/// `personal` adds the account's own repositories as a choice, which resolves to `None`.
+/// Without a terminal nothing is picked and the command keeps its usual fallback.
+pub fn resolve(
+    storage: &impl Storage,
+    org: Option<&str>,
+    personal: bool,
+) -> Result<Option<String>, AppError> {
+    if let Some(org) = org {
+        remember(storage, org)?;
+        return Ok(Some(org.to_string()));
+    }
+    if !is_interactive() || account::show(storage)?.default_org.is_some() {
+        return Ok(None);
+    }
+    pick(storage, personal)
+}
+
+fn remember(storage: &impl Storage, org: &str) -> Result<(), AppError> {
+    let mut state = storage.load_state()?;
+    if state.last_org.as_deref() != Some(org) {
+        state.last_org = Some(org.to_string());
+        storage.save_state(&state)?;
+    }
+    Ok(())
+}
+
+#[cfg(feature = "interactive")]
+fn is_interactive() -> bool {
+    atty::is(atty::Stream::Stdin) && atty::is(atty::Stream::Stdout)
+}
+
+#[cfg(not(feature = "interactive"))]
+fn is_interactive() -> bool {
+    false
+}
+
+#[cfg(feature = "interactive")]
+fn pick(storage: &impl Storage, personal: bool) -> Result<Option<String>, AppError> {
+    let (account, token) = account::get_active_with_token(storage)?;
+    let orgs = GitHubClient::new(token)?.list_user_orgs()?;
+    if orgs.is_empty() {
+        return Ok(None);
+    }
+
+    let mut options: Vec<String> = Vec::new();
+    if personal {
+        options.push(format!("{} (personal)", account.username));
+    }
+    let offset = options.len();
+    options.extend(orgs.iter().map(|o| o.login.clone()));
+    let last = storage.load_state()?.last_org;
+    let cursor = last
+        .and_then(|last| orgs.iter().position(|o| o.login.eq_ignore_ascii_case(&last)))
+        .map_or(0, |i| i + offset);
+
+    let selection = inquire::Select::new("Select organization:", options)
+        .with_starting_cursor(cursor)
+        .raw_prompt()
+        .map_err(|e| AppError::config(format!("selection cancelled: {e}")))?;
+    let Some(org) = selection.index.checked_sub(offset).map(|i| orgs[i].login.clone()) else {
+        return Ok(None);
+    };
+    remember(storage, &org)?;
+    Ok(Some(org))
+}
+
+#[cfg(not(feature = "interactive"))]
+fn pick(_storage: &impl Storage, _personal: bool) -> Result<Option<String>, AppError> {
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::storage::FilesystemStorage;
+    use tempfile::TempDir;
+
+    #[test]
+    fn parse_team_spec_requires_org_and_slug() {
+        assert_eq!(parse_team_spec("acme/platform").unwrap(), ("acme", "platform"));
+        assert!(parse_team_spec("acme").is_err());
+        assert!(parse_team_spec("acme/").is_err());
+        assert!(parse_team_spec("acme/a/b").is_err());
+    }
+
+    #[test]
+    fn explicit_org_is_remembered() {
+        let dir = TempDir::new().unwrap();
+        let storage = FilesystemStorage::new(&Config::with_path(dir.path().to_path_buf()));
+        assert_eq!(resolve(&storage, Some("acme"), true).unwrap(), Some("acme".to_string()));
+        assert_eq!(storage.load_state().unwrap().last_org, Some("acme".to_string()));
+    }
+}
@@ -0,0 +1,159 @@
+//! GitHub Packages commands.
+
+use crate::commands::account;
+use crate::error::AppError;
+use crate::github::{GitHubClient, PackageOwner, PackagesApi};
+use crate::models::{
+    Account, ExecutionMode, Package, PackageType, PackageVersion, ProgressOutcome,
+};
+use crate::progress::Progress;
+use crate::storage::Storage;
+use chrono::{DateTime, Duration, Utc};
+
+/// List packages of one type for an organization, or the account's default org or user.
+pub fn list(
+    storage: &impl Storage,
+    org: Option<&str>,
+    package_type: PackageType,
+) -> Result<Vec<Package>, AppError> {
+    let (account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    client.list_packages(owner(&account, org), package_type)
+}
+
+/// List every version of a package.
+pub fn versions(
+    storage: &impl Storage,
+    org: Option<&str>,
+    package_type: PackageType,
+    name: &str,
+) -> Result<Vec<PackageVersion>, AppError> {
+    let (account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    client.list_package_versions(owner(&account, org), package_type, name)
+}
+
+/// Delete a package, or only one of its versions when `version_id` is given.
+pub fn delete(
+    storage: &impl Storage,
+    org: Option<&str>,
+    package_type: PackageType,
+    name: &str,
+    version_id: Option<u64>,
+) -> Result<(), AppError> {
+    let (account, token) = account::get_active_for_destructive(storage)?;
+    let client = GitHubClient::new(token)?;
+    let owner = owner(&account, org);
+    match version_id {
+        Some(id) => client.delete_package_version(owner, package_type, name, id),
+        None => client.delete_package(owner, package_type, name),
+    }
+}
+
+/// Outcome of [`prune`]: the matching versions and, unless a dry run, per-version errors.
+#[derive(Debug, Default)]
+pub struct PruneResult {
+    pub deleted: Vec<PackageVersion>,
+    pub failed: Vec<(PackageVersion, AppError)>,
+}
+
+/// Operation name of `package prune` progress events.
+const PRUNE_OPERATION: &str = "package prune";
+
+/// Delete untagged container versions not updated in `older_than_days` days.
+///
+/// All versions are fetched before anything is deleted, so deletions cannot shift pages
+/// and skip versions. In a dry run, matching versions are returned without deleting.
+pub fn prune(
+    storage: &impl Storage,
+    org: Option<&str>,
+    name: &str,
+    older_than_days: u32,
+    mode: ExecutionMode,
+    progress: Progress,
+) -> Result<PruneResult, AppError> {
+    let (account, token) = if mode.is_dry_run() {
+        account::get_active_with_token(storage)?
+    } else {
+        account::get_active_for_destructive(storage)?
+    };
+    let client = GitHubClient::new(token)?;
+    let owner = owner(&account, org);
+
+    let versions = client.list_package_versions(owner, PackageType::Container, name)?;
+    let cutoff = Utc::now() - Duration::days(older_than_days.into());
+    let candidates: Vec<PackageVersion> =
+        prune_candidates(&versions, cutoff).into_iter().cloned().collect();
+
+    let mut result = PruneResult::default();
+    let total = candidates.len();
+    for (i, version) in candidates.into_iter().enumerate() {
+        if mode.is_dry_run() {
+            result.deleted.push(version);
+            continue;
+        }
+        let item = format!("{name}@{}", version.id);
+        progress.started(PRUNE_OPERATION, &item, i + 1, total);
+        match client.delete_package_version(owner, PackageType::Container, name, version.id) {
+            Ok(()) => {
+                progress.completed(PRUNE_OPERATION, &item, ProgressOutcome::Succeeded, None);
+                result.deleted.push(version);
+            }
+            Err(e) => {
+                let error = e.to_string();
+                progress.completed(PRUNE_OPERATION, &item, ProgressOutcome::Failed, Some(&error));
+                result.failed.push((version, e));
+            }
+        }
+    }
+    Ok(result)
+}
+
+fn owner<'a>(account: &'a Account, org: Option<&'a str>) -> PackageOwner<'a> {
+    match org.or(account.default_org.as_deref()) {
+        Some(org) => PackageOwner::Org(org),
+        None => PackageOwner::User,
+    }
+}
+
+/// Untagged versions last updated before `cutoff`.
+fn prune_candidates(versions: &[PackageVersion], cutoff: DateTime<Utc>) -> Vec<&PackageVersion> {
+    versions.iter().filter(|v| v.tags().is_empty() && v.updated_at < cutoff).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ContainerMetadata, PackageVersionMetadata};
+
+    fn version(id: u64, tags: &[&str], days_ago: i64) -> PackageVersion {
+        let at = Utc::now() - Duration::days(days_ago);
+        PackageVersion {
+            id,
+            name: format!("sha256:{id}"),
+            created_at: at,
+            updated_at: at,
+            metadata: Some(PackageVersionMetadata {
+                container: Some(ContainerMetadata {
+                    tags: tags.iter().map(|t| t.to_string()).collect(),
+                }),
+            }),
+        }
+    }
+
+    #[test]
+    fn prune_candidates_selects_old_untagged_versions() {
+        let versions = vec![version(1, &[], 40), version(2, &["latest"], 40), version(3, &[], 5)];
+        let cutoff = Utc::now() - Duration::days(30);
+
+        let ids: Vec<u64> = prune_candidates(&versions, cutoff).iter().map(|v| v.id).collect();
+        assert_eq!(ids, vec![1]);
+    }
+
+    #[test]
+    fn version_without_metadata_has_no_tags() {
+        let mut v = version(1, &["v1"], 0);
+        v.metadata = None;
+        assert!(v.tags().is_empty());
+    }
+}
@@ -1,11 +1,40 @@
 //! Pull request commands.
 
-use crate::commands::account;
+use crate::commands::{account, commit, conflicts, run};
+use crate::domain::{glob_match, parse_remote_url_with_host, split_diff};
 use crate::error::AppError;
-use crate::github::GitHubClient;
-use crate::models::PullRequestOutput;
+use crate::github::{ActionsApi, GitHubClient, IssuesApi, Poller, PullsApi, ReposApi};
+use crate::models::{
+    AccountScoped, CheckKind, CheckRun, CheckRunAnnotation, ChecksSummary, CommitStatus, Execution,
+    ExecutionMode, IssueComment, IssueUpdate, MergeMethod, MergeQueueEntry, MergeReadiness,
+    MergeResult, NewPullRequest, PullRequest, PullRequestCheck, PullRequestComment,
+    PullRequestDashboard, PullRequestOutput, PullRequestSearchNode, PullRequestStateChange,
+    PullRequestStatusNode, PullRequestUpdate, PullRequestUser, PullRequestView, RepoPullRequest,
+    Review, ReviewComment, ReviewEvent, ReviewRequest, TimelineEntry, TimelineEvent, WorkflowRun,
+};
+use crate::output::style;
+use crate::remote::resolve_repo;
 use crate::storage::Storage;
-use std::process::Command;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+/// Which open pull requests [`search`] lists across repositories.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PrSearch<'a> {
+    /// Repositories of this organization; the active account's own repositories when `None`.
+    pub org: Option<&'a str>,
+    /// Only pull requests the active account opened.
+    pub mine: bool,
+    /// Only pull requests requesting the active account's review. Combined with `mine`, pull
+    /// requests matching either are listed.
+    pub review_requested: bool,
+}
 
 /// List open pull requests for a repository.
 pub fn list(
@@ -14,100 +43,1809 @@ pub fn list(
     limit: usize,
 ) -> Result<Vec<PullRequestOutput>, AppError> {
     let (_account, token) = account::get_active_with_token(storage)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+    list_with_token(token, &owner, &repo, limit)
+}
+
+/// List open pull requests as seen by every account in a group, tagged by account.
+pub fn list_group(
+    storage: &impl Storage,
+    group: &str,
+    repo_spec: Option<&str>,
+    limit: usize,
+) -> Result<Vec<AccountScoped<PullRequestOutput>>, AppError> {
+    let members = account::get_group_with_tokens(storage, group)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+
+    let mut results = Vec::new();
+    for (account, token) in members {
+        let prs = list_with_token(token, &owner, &repo, limit)?;
+        results.extend(
+            prs.into_iter().map(|item| AccountScoped { account: account.id.clone(), item }),
+        );
+    }
+    Ok(results)
+}
+
+fn list_with_token(
+    token: String,
+    owner: &str,
+    repo: &str,
+    limit: usize,
+) -> Result<Vec<PullRequestOutput>, AppError> {
     let client = GitHubClient::new(token)?;
+    let mut prs: Vec<PullRequestOutput> =
+        client.list_pull_request_statuses(owner, repo, limit)?.into_iter().map(to_output).collect();
+    fill_mergeable(&mut prs, |number| {
+        client.get_pull_request(owner, repo, number).map(|pr| pr.mergeable)
+    });
+    Ok(prs)
+}
 
-    let (owner, repo) = match repo_spec {
-        Some(spec) => parse_repo_spec(spec)?,
-        None => detect_repo_from_git()?,
+/// Open pull requests across every repository of an organization (or the active account's
+/// own), grouped by repository and most recently updated first within each.
+///
+/// Pull requests come from the search API; `mergeable` states GitHub had not computed yet are
+/// looked up with up to `jobs` repositories at once.
+pub fn search(
+    storage: &impl Storage,
+    search: &PrSearch,
+    limit: usize,
+    jobs: usize,
+) -> Result<Vec<RepoPullRequest>, AppError> {
+    let (account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+
+    let scope = match search.org {
+        Some(org) => format!("org:{org}"),
+        None => format!("user:{}", account.username),
     };
+    let mut involvements = Vec::new();
+    if search.mine {
+        involvements.push("author:@me");
+    }
+    if search.review_requested {
+        involvements.push("review-requested:@me");
+    }
+    if involvements.is_empty() {
+        involvements.push("");
+    }
 
-    let prs = client.list_pull_requests(&owner, &repo, limit)?;
+    let mut nodes = Vec::new();
+    for involvement in involvements {
+        let query = format!("is:open is:pr archived:false sort:updated-desc {scope} {involvement}");
+        nodes.extend(client.search_pull_request_statuses(query.trim_end(), limit)?);
+    }
 
-    let output: Vec<PullRequestOutput> = prs
+    let mut by_repo: Vec<(String, Vec<PullRequestOutput>)> = Vec::new();
+    for pr in newest_by_repo(nodes, limit) {
+        match by_repo.last_mut() {
+            Some((repo, outputs)) if *repo == pr.repo => outputs.push(pr.pull_request),
+            _ => by_repo.push((pr.repo, vec![pr.pull_request])),
+        }
+    }
+    fill_mergeable_across(&client, &mut by_repo, jobs);
+    Ok(by_repo
         .into_iter()
-        .map(|pr| PullRequestOutput {
-            number: pr.number,
-            title: pr.title,
-            author: pr.user.login,
-            branch: pr.head.branch,
-            mergeable: pr.mergeable,
-            actions_in_progress: false, // Would require additional API call
-            ci_status: "unknown".to_string(), // Would require check runs API
+        .flat_map(|(repo, outputs)| {
+            outputs
+                .into_iter()
+                .map(move |pull_request| RepoPullRequest { repo: repo.clone(), pull_request })
         })
-        .collect();
+        .collect())
+}
 
-    Ok(output)
+/// The `limit` most recently updated of the search results, without duplicates, sorted by
+/// repository and most recently updated first within each.
+///
+/// Results of several queries are merged before the limit applies, so no query crowds out the
+/// others.
+fn newest_by_repo(nodes: Vec<PullRequestSearchNode>, limit: usize) -> Vec<RepoPullRequest> {
+    let mut found: Vec<(Option<DateTime<Utc>>, RepoPullRequest)> = Vec::new();
+    for node in nodes {
+        let repo = node.repository.name_with_owner;
+        let number = node.pull_request.number;
+        if !found.iter().any(|(_, p)| p.repo == repo && p.pull_request.number == number) {
+            let pull_request = to_output(node.pull_request);
+            found.push((node.updated_at, RepoPullRequest { repo, pull_request }));
+        }
+    }
+    found.sort_by(|(a, _), (b, _)| b.cmp(a));
+    found.truncate(limit);
+    let mut prs: Vec<RepoPullRequest> = found.into_iter().map(|(_, pr)| pr).collect();
+    prs.sort_by(|a, b| a.repo.cmp(&b.repo));
+    prs
 }
 
-fn parse_repo_spec(spec: &str) -> Result<(String, String), AppError> {
-    let parts: Vec<&str> = spec.split('/').collect();
-    if parts.len() != 2 {
+/// [`fill_mergeable`] for the pull requests of several repositories, up to `jobs` repositories
+/// at once. Once any lookup is rate limited, the remaining ones are skipped everywhere.
+fn fill_mergeable_across(
+    client: &GitHubClient,
+    by_repo: &mut [(String, Vec<PullRequestOutput>)],
+    jobs: usize,
+) {
+    let workers = jobs.clamp(1, by_repo.len().max(1));
+    let pending = Mutex::new(
+        by_repo.iter_mut().filter(|(_, prs)| prs.iter().any(|pr| pr.mergeable.is_none())),
+    );
+    let rate_limited = AtomicBool::new(false);
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                loop {
+                    let Some((repo, prs)) = pending.lock().expect("pending repos poisoned").next()
+                    else {
+                        break;
+                    };
+                    let (owner, name) = repo.split_once('/').unwrap_or((repo.as_str(), ""));
+                    fill_mergeable(prs, |number| {
+                        if rate_limited.load(Ordering::SeqCst) {
+                            return Err(AppError::RateLimited("skipped after rate limit".into()));
+                        }
+                        let result =
+                            client.get_pull_request(owner, name, number).map(|pr| pr.mergeable);
+                        if matches!(result, Err(AppError::RateLimited(_))) {
+                            rate_limited.store(true, Ordering::SeqCst);
+                        }
+                        result
+                    });
+                }
+            });
+        }
+    });
+}
+
+/// Look up `mergeable` of pull requests whose state GitHub had not computed yet.
+///
+/// A failed lookup marks the pull request as partial instead of failing the listing. After
+/// hitting the rate limit no further lookups are made and the remaining ones are partial too.
+fn fill_mergeable(
+    prs: &mut [PullRequestOutput],
+    mut lookup: impl FnMut(u64) -> Result<Option<bool>, AppError>,
+) {
+    let mut rate_limited = false;
+    for pr in prs.iter_mut().filter(|pr| pr.mergeable.is_none()) {
+        if rate_limited {
+            pr.partial = true;
+            continue;
+        }
+        match lookup(pr.number) {
+            Ok(mergeable) => pr.mergeable = mergeable,
+            Err(e) => {
+                rate_limited = matches!(e, AppError::RateLimited(_));
+                pr.partial = true;
+            }
+        }
+    }
+}
+
+fn to_output(pr: PullRequestStatusNode) -> PullRequestOutput {
+    let mergeable = match pr.mergeable.as_str() {
+        "MERGEABLE" => Some(true),
+        "CONFLICTING" => Some(false),
+        _ => None,
+    };
+    let rollup = pr
+        .commits
+        .nodes
+        .into_iter()
+        .next()
+        .and_then(|node| node.commit.status_check_rollup)
+        .map(|rollup| rollup.state);
+    let actions_in_progress = matches!(rollup.as_deref(), Some("PENDING" | "EXPECTED"));
+    let ci_status = rollup.map(|s| s.to_lowercase()).unwrap_or_else(|| "none".to_string());
+
+    PullRequestOutput {
+        number: pr.number,
+        title: pr.title,
+        author: pr.author.map(|a| a.login).unwrap_or_else(|| "ghost".to_string()),
+        branch: pr.head_ref_name,
+        mergeable,
+        actions_in_progress,
+        ci_status,
+        review_decision: pr.review_decision.map(|d| d.to_lowercase()),
+        partial: false,
+    }
+}
+
+/// Open pull requests involving the active account's user.
+pub fn status(storage: &impl Storage, limit: usize) -> Result<PullRequestDashboard, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+
+    let data = client.pull_request_dashboard(limit)?;
+    let section = |nodes: Vec<PullRequestSearchNode>| {
+        nodes
+            .into_iter()
+            .map(|node| RepoPullRequest {
+                repo: node.repository.name_with_owner,
+                pull_request: to_output(node.pull_request),
+            })
+            .collect()
+    };
+
+    Ok(PullRequestDashboard {
+        created: section(data.created.nodes),
+        review_requested: section(data.review_requested.nodes),
+        assigned: section(data.assigned.nodes),
+    })
+}
+
+/// Fetch a single pull request with its checks summary.
+pub fn view(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    number: u64,
+) -> Result<PullRequestView, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+
+    let pull_request = client.get_pull_request(&owner, &repo, number)?;
+    let check_runs = client.list_check_runs(&owner, &repo, &pull_request.head.sha)?;
+    let commits = client.list_pull_request_commits(&owner, &repo, number)?;
+
+    Ok(PullRequestView {
+        pull_request,
+        checks: summarize_checks(&check_runs),
+        commits: commits.into_iter().map(commit::signature).collect(),
+    })
+}
+
+/// Fetch only a pull request's browser URL, skipping the checks and commits [`view`] loads.
+pub fn web_url(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    number: u64,
+) -> Result<String, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+
+    client
+        .get_pull_request(&owner, &repo, number)?
+        .html_url
+        .filter(|url| !url.is_empty())
+        .ok_or_else(|| AppError::github_api(format!("pull request #{number} has no URL")))
+}
+
+/// Check whether a pull request can be merged: open, not a draft, free of conflicts, with
+/// all checks finished and passing, and with `require_signed` every commit verified.
+pub fn ready_to_merge(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    number: u64,
+    require_signed: bool,
+) -> Result<MergeReadiness, AppError> {
+    let view = view(storage, repo_spec, number)?;
+    let blockers = readiness_blockers(&view, require_signed);
+    Ok(MergeReadiness { number, ready: blockers.is_empty(), blockers })
+}
+
+fn readiness_blockers(view: &PullRequestView, require_signed: bool) -> Vec<String> {
+    let p = &view.pull_request;
+    let mut blockers = Vec::new();
+    if let Some(state) = p.state.as_deref().filter(|s| *s != "open") {
+        blockers.push(format!("pull request is {state}"));
+    }
+    if p.draft {
+        blockers.push("pull request is a draft".to_string());
+    }
+    if p.mergeable == Some(false) {
+        blockers.push("merge conflicts with the base branch".to_string());
+    }
+    if view.checks.failed > 0 {
+        blockers.push(format!("{} check(s) failed", view.checks.failed));
+    }
+    if view.checks.pending > 0 {
+        blockers.push(format!("{} check(s) pending", view.checks.pending));
+    }
+    if require_signed {
+        for c in view.commits.iter().filter(|c| !c.verified) {
+            blockers.push(format!("commit {} is not verified ({})", short_sha(&c.sha), c.reason));
+        }
+    }
+    blockers
+}
+
+fn short_sha(sha: &str) -> &str {
+    &sha[..sha.len().min(7)]
+}
+
+/// Per-file sections of a pull request's diff as `(path, diff)`.
+///
+/// With `patterns`, only files whose path matches one of the globs are kept.
+pub fn diff(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    number: u64,
+    patterns: &[String],
+) -> Result<Vec<(String, String)>, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+
+    let diff = client.get_pull_request_diff(&owner, &repo, number)?;
+    Ok(split_diff(&diff)
+        .into_iter()
+        .filter(|(path, _)| patterns.is_empty() || patterns.iter().any(|p| glob_match(p, path)))
+        .map(|(path, section)| (path, section.to_string()))
+        .collect())
+}
+
+/// Check runs and commit statuses of a pull request's head commit, passed to `on_poll` as they
+/// are fetched.
+///
+/// With `required_only`, only the checks the base branch's protection requires are kept, and a
+/// required check that has not reported yet is listed as `expected`. With `watch`, the checks
+/// are polled from that interval, conditionally and backing off while nothing changes (see
+/// [`Poller`]), until none is pending. The head commit is read once, so commits pushed
+/// meanwhile are not followed.
+pub fn checks(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    number: u64,
+    required_only: bool,
+    watch: Option<Duration>,
+    mut on_poll: impl FnMut(&[PullRequestCheck]),
+) -> Result<Vec<PullRequestCheck>, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?.polling();
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+
+    let pull_request = client.get_pull_request(&owner, &repo, number)?;
+    let required = match pull_request.base.filter(|_| required_only) {
+        Some(base) => match client.get_required_status_checks(&owner, &repo, &base.branch) {
+            Ok(checks) => Some(checks.contexts),
+            // GitHub answers 404 both for an unprotected branch and for a token without admin
+            // rights, so the required checks are unknown rather than empty.
+            Err(AppError::NotFound(_)) => {
+                return Err(AppError::NotFound(format!(
+                    "required checks of {owner}/{repo}@{} are not readable: the branch is not \
+                     protected or the token lacks admin access",
+                    base.branch
+                )));
+            }
+            Err(e) => return Err(e),
+        },
+        None if required_only => {
+            return Err(AppError::github_api(format!("pull request #{number} has no base branch")));
+        }
+        None => None,
+    };
+    let sha = pull_request.head.sha;
+    let mut poller = watch.map(Poller::new);
+    loop {
+        let check_runs = client.list_check_runs(&owner, &repo, &sha)?;
+        let statuses = client.list_commit_statuses(&owner, &repo, &sha)?;
+        let checks = merge_checks(check_runs, statuses, required.as_deref());
+        on_poll(&checks);
+        match &mut poller {
+            Some(poller) if checks.iter().any(PullRequestCheck::is_pending) => {
+                poller.wait(&client.poll_state())
+            }
+            _ => return Ok(checks),
+        }
+    }
+}
+
+/// Check runs and statuses as one list, in the order GitHub lists them; with `required`, only
+/// the required ones, plus an `expected` entry for each required check yet to report.
+fn merge_checks(
+    check_runs: Vec<CheckRun>,
+    statuses: Vec<CommitStatus>,
+    required: Option<&[String]>,
+) -> Vec<PullRequestCheck> {
+    let runs = check_runs.into_iter().map(|run| PullRequestCheck {
+        name: run.name,
+        kind: CheckKind::CheckRun,
+        status: run.status,
+        conclusion: run.conclusion,
+        started_at: run.started_at,
+        completed_at: run.completed_at,
+        url: run.details_url.or(run.html_url),
+    });
+    let statuses = statuses.into_iter().map(|status| {
+        let pending = status.state == "pending";
+        PullRequestCheck {
+            name: status.context,
+            kind: CheckKind::Status,
+            status: if pending { status.state.clone() } else { "completed".to_string() },
+            conclusion: (!pending).then_some(status.state),
+            started_at: Some(status.created_at),
+            completed_at: (!pending).then_some(status.updated_at),
+            url: status.target_url,
+        }
+    });
+    let mut checks: Vec<PullRequestCheck> = runs.chain(statuses).collect();
+    let Some(required) = required else {
+        return checks;
+    };
+    checks.retain(|check| required.contains(&check.name));
+    for name in required {
+        if !checks.iter().any(|check| &check.name == name) {
+            checks.push(PullRequestCheck {
+                name: name.clone(),
+                kind: CheckKind::Expected,
+                status: "expected".to_string(),
+                conclusion: None,
+                started_at: None,
+                completed_at: None,
+                url: None,
+            });
+        }
+    }
+    checks
+}
+
+/// One line of `pr checks`: state, name, duration and link.
+pub fn format_check(check: &PullRequestCheck) -> String {
+    let state = run::state(&check.status, check.conclusion.as_deref());
+    let mut line = format!("{} {}", style::status(&format!("{state:<12}")), check.name);
+    if let Some(secs) = check.duration_secs() {
+        let duration = match secs {
+            0..60 => format!("{secs}s"),
+            60..3600 => format!("{}m{:02}s", secs / 60, secs % 60),
+            _ => format!("{}h{:02}m", secs / 3600, secs % 3600 / 60),
+        };
+        line.push_str(&format!(" ({duration})"));
+    }
+    if let Some(url) = &check.url {
+        line.push_str(&format!("  {url}"));
+    }
+    line
+}
+
+/// Collect annotations from the check runs of a pull request's head commit.
+pub fn annotations(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    number: u64,
+) -> Result<Vec<CheckRunAnnotation>, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+
+    let pull_request = client.get_pull_request(&owner, &repo, number)?;
+    let check_runs = client.list_check_runs(&owner, &repo, &pull_request.head.sha)?;
+
+    let mut results = Vec::new();
+    for check_run in check_runs {
+        if check_run.output.as_ref().is_none_or(|o| o.annotations_count == 0) {
+            continue;
+        }
+        let annotations = client.list_check_run_annotations(&owner, &repo, check_run.id)?;
+        results.extend(run::tag_annotations(&check_run.name, annotations));
+    }
+    Ok(results)
+}
+
+/// Re-run the workflow runs of a pull request's head commit that did not pass, or with
+/// `failed_only` only their failed jobs. Returns the runs re-run.
+pub fn rerun_checks(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    number: u64,
+    failed_only: bool,
+) -> Result<Vec<WorkflowRun>, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+
+    let pull_request = client.get_pull_request(&owner, &repo, number)?;
+    let runs = client.list_commit_workflow_runs(&owner, &repo, &pull_request.head.sha)?;
+    let failed = run::failed_runs(runs);
+    for failed_run in &failed {
+        client.rerun_workflow_run(&owner, &repo, failed_run.id, failed_only)?;
+    }
+    Ok(failed)
+}
+
+/// Merge a pull request directly.
+///
+/// A dry run skips the confirmation of protected accounts and reports the merge call instead.
+pub fn merge(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    number: u64,
+    method: MergeMethod,
+    mode: ExecutionMode,
+) -> Result<Execution<MergeResult>, AppError> {
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+    if mode.is_dry_run() {
+        let (_account, token) = account::get_active_with_token(storage)?;
+        let client = GitHubClient::new(token)?;
+        return Ok(Execution::Planned(vec![
+            merge_target(&client, &owner, &repo, number, &method.to_string())?,
+            format!("PUT /repos/{owner}/{repo}/pulls/{number}/merge (merge_method: {method})"),
+        ]));
+    }
+    let (_account, token) = account::get_active_for_destructive(storage)?;
+    let client = GitHubClient::new(token)?;
+
+    client.merge_pull_request(&owner, &repo, number, method).map(Execution::Done).map_err(|e| {
+        match e {
+            AppError::GitHubApi(msg) if msg.to_lowercase().contains("merge queue") => {
+                AppError::github_api(format!(
+                    "{msg}\nhint: this branch requires the merge queue, retry with --queue"
+                ))
+            }
+            other => other,
+        }
+    })
+}
+
+/// Add a pull request to its base branch's merge queue.
+pub fn enqueue(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    number: u64,
+    mode: ExecutionMode,
+) -> Result<Execution<MergeQueueEntry>, AppError> {
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+    if mode.is_dry_run() {
+        let (_account, token) = account::get_active_with_token(storage)?;
+        let client = GitHubClient::new(token)?;
+        return Ok(Execution::Planned(vec![
+            merge_target(&client, &owner, &repo, number, "the merge queue")?,
+            "POST /graphql (enqueuePullRequest)".to_string(),
+        ]));
+    }
+    let (_account, token) = account::get_active_for_destructive(storage)?;
+    let client = GitHubClient::new(token)?;
+
+    let node_id = client.pull_request_node_id(&owner, &repo, number)?;
+    client.enqueue_pull_request(&node_id).map(Execution::Done)
+}
+
+/// A pull request to open with [`create`].
+#[derive(Debug, Clone, Default)]
+pub struct PrCreate {
+    pub title: String,
+    pub body: Option<String>,
+    /// Branch to merge into; the repository's default branch when `None`.
+    pub base: Option<String>,
+    /// Branch to merge, as `owner:branch` for a fork's; the upstream of the checked-out branch
+    /// when `None`.
+    pub head: Option<String>,
+    pub draft: bool,
+}
+
+/// Reviewers, labels, assignees and milestone to give a pull request, and with [`edit`] the
+/// ones to take away.
+#[derive(Debug, Clone, Default)]
+pub struct PrMetadata {
+    /// Logins, or `org/team` (also `@org/team`) to request a team's review.
+    pub add_reviewers: Vec<String>,
+    pub remove_reviewers: Vec<String>,
+    pub add_labels: Vec<String>,
+    pub remove_labels: Vec<String>,
+    pub add_assignees: Vec<String>,
+    pub remove_assignees: Vec<String>,
+    /// Title or number of an open milestone.
+    pub milestone: Option<String>,
+    /// Clear the milestone.
+    pub remove_milestone: bool,
+}
+
+impl PrMetadata {
+    fn is_empty(&self) -> bool {
+        self.add_reviewers.is_empty()
+            && self.remove_reviewers.is_empty()
+            && self.add_labels.is_empty()
+            && self.remove_labels.is_empty()
+            && self.add_assignees.is_empty()
+            && self.remove_assignees.is_empty()
+            && self.milestone.is_none()
+            && !self.remove_milestone
+    }
+}
+
+/// Open a pull request and give it the reviewers, labels, assignees and milestone of
+/// `metadata`.
+///
+/// The milestone is looked up first, so a mistyped one fails before anything is created.
+pub fn create(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    new: PrCreate,
+    metadata: &PrMetadata,
+) -> Result<PullRequest, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+
+    let milestone = find_milestone(&client, &owner, &repo, metadata.milestone.as_deref())?;
+    let head = match new.head {
+        Some(head) => head,
+        None => upstream_head(None, &owner)?,
+    };
+    let base = match new.base {
+        Some(base) => base,
+        None => client.get_repo(&owner, &repo)?.default_branch.ok_or_else(|| {
+            AppError::invalid_input(format!("{owner}/{repo} has no default branch, pass --base"))
+        })?,
+    };
+    let pull_request = client.create_pull_request(
+        &owner,
+        &repo,
+        &NewPullRequest { title: new.title, head, base, body: new.body, draft: new.draft },
+    )?;
+    let number = pull_request.number;
+    apply_metadata(&client, &owner, &repo, number, metadata, milestone)
+        .map_err(|e| AppError::github_api(format!("opened #{number}, but {e}")))?;
+    Ok(pull_request)
+}
+
+/// Head of a pull request from the branch checked out in `dir`: its upstream branch, as
+/// `owner:branch` when the upstream is on a fork rather than on `owner`'s repository.
+///
+/// Fails when the branch was never pushed, since GitHub only knows pushed branches.
+fn upstream_head(dir: Option<&Path>, owner: &str) -> Result<String, AppError> {
+    let branch = conflicts::git(dir, &["symbolic-ref", "--short", "HEAD"])
+        .map_err(|_| AppError::invalid_input("no branch is checked out, pass --head"))?;
+    let config = |key: &str| conflicts::git(dir, &["config", &format!("branch.{branch}.{key}")]);
+    let (Ok(remote), Ok(merge)) = (config("remote"), config("merge")) else {
         return Err(AppError::invalid_input(format!(
-            "invalid repository format '{}', expected owner/repo",
-            spec
+            "branch '{branch}' has no upstream; push it with `git push -u origin {branch}` or \
+             pass --head owner:branch"
         )));
+    };
+    let upstream = merge.strip_prefix("refs/heads/").unwrap_or(&merge);
+    let url = conflicts::git(dir, &["remote", "get-url", &remote])?;
+    match parse_remote_url_with_host(&url) {
+        Ok((_host, fork_owner, _repo)) if !fork_owner.eq_ignore_ascii_case(owner) => {
+            Ok(format!("{fork_owner}:{upstream}"))
+        }
+        _ => Ok(upstream.to_string()),
+    }
+}
+
+/// Change the title, body or base branch of a pull request and its reviewers, labels,
+/// assignees and milestone. Returns the pull request as it is afterwards.
+pub fn edit(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    number: u64,
+    update: &PullRequestUpdate,
+    metadata: &PrMetadata,
+) -> Result<PullRequest, AppError> {
+    let unchanged = update.title.is_none() && update.body.is_none() && update.base.is_none();
+    if unchanged && metadata.is_empty() {
+        return Err(AppError::invalid_input("nothing to change; pass at least one option"));
+    }
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+
+    let milestone = find_milestone(&client, &owner, &repo, metadata.milestone.as_deref())?;
+    if !unchanged {
+        client.update_pull_request(&owner, &repo, number, update)?;
+    }
+    apply_metadata(&client, &owner, &repo, number, metadata, milestone)?;
+    client.get_pull_request(&owner, &repo, number)
+}
+
+/// Number of the open milestone titled or numbered `milestone`.
+fn find_milestone(
+    client: &impl IssuesApi,
+    owner: &str,
+    repo: &str,
+    milestone: Option<&str>,
+) -> Result<Option<u64>, AppError> {
+    let Some(wanted) = milestone else {
+        return Ok(None);
+    };
+    let milestones = client.list_milestones(owner, repo)?;
+    milestones
+        .iter()
+        .find(|m| m.title == wanted || m.number.to_string() == wanted)
+        .or_else(|| milestones.iter().find(|m| m.title.eq_ignore_ascii_case(wanted)))
+        .map(|m| Some(m.number))
+        .ok_or_else(|| {
+            AppError::NotFound(format!("{owner}/{repo} has no open milestone '{wanted}'"))
+        })
+}
+
+fn apply_metadata(
+    client: &(impl PullsApi + IssuesApi),
+    owner: &str,
+    repo: &str,
+    number: u64,
+    metadata: &PrMetadata,
+    milestone: Option<u64>,
+) -> Result<(), AppError> {
+    let add = review_request(&metadata.add_reviewers);
+    if add != ReviewRequest::default() {
+        client.request_reviewers(owner, repo, number, &add)?;
+    }
+    let remove = review_request(&metadata.remove_reviewers);
+    if remove != ReviewRequest::default() {
+        client.remove_requested_reviewers(owner, repo, number, &remove)?;
+    }
+    if !metadata.add_labels.is_empty() {
+        client.add_labels(owner, repo, number, &metadata.add_labels)?;
+    }
+    for name in &metadata.remove_labels {
+        client.remove_label(owner, repo, number, name)?;
+    }
+    if !metadata.add_assignees.is_empty() {
+        client.add_assignees(owner, repo, number, &metadata.add_assignees)?;
+    }
+    if !metadata.remove_assignees.is_empty() {
+        client.remove_assignees(owner, repo, number, &metadata.remove_assignees)?;
+    }
+    if milestone.is_some() || metadata.remove_milestone {
+        let update = IssueUpdate { milestone: Some(milestone), ..Default::default() };
+        client.update_issue(owner, repo, number, &update)?;
+    }
+    Ok(())
+}
+
+/// Split reviewers into users and team slugs: `org/team` and `@org/team` name a team, and a
+/// leading `@` on a login is dropped.
+fn review_request(reviewers: &[String]) -> ReviewRequest {
+    let mut request = ReviewRequest::default();
+    for reviewer in reviewers {
+        let reviewer = reviewer.trim().trim_start_matches('@');
+        match reviewer.split_once('/') {
+            Some((_org, team)) => request.team_reviewers.push(team.to_string()),
+            None => request.reviewers.push(reviewer.to_string()),
+        }
     }
-    Ok((parts[0].to_string(), parts[1].to_string()))
+    request
 }
 
-fn detect_repo_from_git() -> Result<(String, String), AppError> {
-    // Check GITHUB_REPOSITORY environment variable first
-    if let Ok(repo) = std::env::var("GITHUB_REPOSITORY") {
-        return parse_repo_spec(&repo);
+/// Convert a pull request to a draft (`draft`) or mark it ready for review.
+///
+/// Returns `false` without changing anything when it already is in that state.
+pub fn set_draft(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    number: u64,
+    draft: bool,
+) -> Result<bool, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+
+    if client.get_pull_request(&owner, &repo, number)?.draft == draft {
+        return Ok(false);
+    }
+    let node_id = client.pull_request_node_id(&owner, &repo, number)?;
+    client.set_pull_request_draft(&node_id, draft)?;
+    Ok(true)
+}
+
+/// Close a pull request, optionally explaining why in a comment and deleting its head branch.
+///
+/// The comment is posted before closing so it reads as the reason in the timeline. Only
+/// branches in the same repository are deleted; fork branches belong to their author. A dry
+/// run reports the calls instead.
+pub fn close(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    number: u64,
+    comment: Option<&str>,
+    delete_branch: bool,
+    mode: ExecutionMode,
+) -> Result<Execution<PullRequestStateChange>, AppError> {
+    if comment.is_some_and(|c| c.trim().is_empty()) {
+        return Err(AppError::invalid_input("comment body must not be empty"));
     }
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+    let (_account, token) = if delete_branch && !mode.is_dry_run() {
+        account::get_active_for_destructive(storage)?
+    } else {
+        account::get_active_with_token(storage)?
+    };
+    let client = GitHubClient::new(token)?;
 
-    // Try to get from git remote
-    let output = Command::new("git")
-        .args(["remote", "get-url", "origin"])
-        .output()
-        .map_err(|e| AppError::git(format!("failed to run git: {e}")))?;
+    let pull_request = client.get_pull_request(&owner, &repo, number)?;
+    let branch = pull_request.head.branch.clone();
+    if delete_branch {
+        let in_repo = pull_request
+            .head
+            .repo
+            .as_ref()
+            .is_some_and(|r| r.full_name.eq_ignore_ascii_case(&format!("{owner}/{repo}")));
+        if !in_repo {
+            return Err(AppError::invalid_input(format!(
+                "'{branch}' is not a branch of {owner}/{repo}; not deleting a fork's branch"
+            )));
+        }
+    }
+    let open = pull_request.state.as_deref() == Some("open");
 
-    if !output.status.success() {
-        return Err(AppError::git("no repository detected, provide owner/repo argument"));
+    if mode.is_dry_run() {
+        let mut steps = Vec::new();
+        if comment.is_some() {
+            steps.push(format!("POST /repos/{owner}/{repo}/issues/{number}/comments"));
+        }
+        if open {
+            steps.push(format!("PATCH /repos/{owner}/{repo}/pulls/{number} (state: closed)"));
+        }
+        if delete_branch {
+            steps.push(format!("DELETE /repos/{owner}/{repo}/git/refs/heads/{branch}"));
+        }
+        return Ok(Execution::Planned(steps));
     }
 
-    let url = String::from_utf8_lossy(&output.stdout).trim().to_string();
-    parse_remote_url(&url)
+    let mut change = PullRequestStateChange { changed: open, ..Default::default() };
+    if let Some(body) = comment {
+        change.comment_url =
+            Some(client.create_issue_comment(&owner, &repo, number, body)?.html_url);
+    }
+    if open {
+        client.set_pull_request_open(&owner, &repo, number, false)?;
+    }
+    if delete_branch {
+        client.delete_branch(&owner, &repo, &branch)?;
+        change.deleted_branch = Some(branch);
+    }
+    Ok(Execution::Done(change))
 }
 
-fn parse_remote_url(url: &str) -> Result<(String, String), AppError> {
-    // Handle SSH URLs: git@github.com:owner/repo.git
-    if let Some(path) = url.strip_prefix("git@github.com:") {
-        let path = path.trim_end_matches(".git");
-        return parse_repo_spec(path);
+/// Reopen a closed pull request, optionally commenting on why.
+///
+/// Merged pull requests cannot be reopened.
+pub fn reopen(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    number: u64,
+    comment: Option<&str>,
+) -> Result<PullRequestStateChange, AppError> {
+    if comment.is_some_and(|c| c.trim().is_empty()) {
+        return Err(AppError::invalid_input("comment body must not be empty"));
     }
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
 
-    // Handle HTTPS URLs: https://github.com/owner/repo.git
-    if let Some(path) = url.strip_prefix("https://github.com/") {
-        let path = path.trim_end_matches(".git");
-        return parse_repo_spec(path);
+    let pull_request = client.get_pull_request(&owner, &repo, number)?;
+    if pull_request.merged_at.is_some() {
+        return Err(AppError::invalid_input(format!("#{number} is merged and cannot be reopened")));
     }
+    let closed = pull_request.state.as_deref() != Some("open");
+    if closed {
+        client.set_pull_request_open(&owner, &repo, number, true)?;
+    }
+    let mut change = PullRequestStateChange { changed: closed, ..Default::default() };
+    if let Some(body) = comment {
+        change.comment_url =
+            Some(client.create_issue_comment(&owner, &repo, number, body)?.html_url);
+    }
+    Ok(change)
+}
+
+/// Describe the pull request a dry-run merge targets, failing if it does not exist.
+fn merge_target(
+    client: &impl PullsApi,
+    owner: &str,
+    repo: &str,
+    number: u64,
+    via: &str,
+) -> Result<String, AppError> {
+    let pull_request = client.get_pull_request(owner, repo, number)?;
+    let base = pull_request.base.as_ref().map_or("base", |b| b.branch.as_str());
+    Ok(format!(
+        "merge {owner}/{repo}#{number} \"{}\" ({} → {base}) via {via}",
+        pull_request.title, pull_request.head.branch
+    ))
+}
+
+/// Submit a review on a pull request.
+pub fn review(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    number: u64,
+    event: ReviewEvent,
+    body: Option<&str>,
+) -> Result<Review, AppError> {
+    if event.requires_body() && body.is_none_or(|b| b.trim().is_empty()) {
+        return Err(AppError::invalid_input("a message (-m) is required for this review type"));
+    }
+
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+
+    client.create_review(&owner, &repo, number, event, body)
+}
+
+/// List reviews submitted on a pull request.
+pub fn reviews(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    number: u64,
+) -> Result<Vec<Review>, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+
+    client.list_reviews(&owner, &repo, number)
+}
 
-    Err(AppError::git(format!("unrecognized remote URL format: {url}")))
+/// Comment on the conversation of a pull request.
+pub fn comment(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    number: u64,
+    body: &str,
+) -> Result<IssueComment, AppError> {
+    if body.trim().is_empty() {
+        return Err(AppError::invalid_input("comment body must not be empty"));
+    }
+
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+
+    client.create_issue_comment(&owner, &repo, number, body)
+}
+
+/// Read a comment body from a file, or from stdin when `path` is `-`.
+pub fn read_body(path: &Path) -> Result<String, AppError> {
+    if path == Path::new("-") {
+        let mut body = String::new();
+        std::io::stdin().read_to_string(&mut body)?;
+        return Ok(body);
+    }
+    std::fs::read_to_string(path)
+        .map_err(|e| AppError::invalid_input(format!("cannot read '{}': {e}", path.display())))
+}
+
+/// Conversation and review comments of a pull request in chronological order, with replies
+/// right after the comment that started their review thread.
+pub fn comments(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    number: u64,
+) -> Result<Vec<PullRequestComment>, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+
+    let issue = client.list_issue_comments(&owner, &repo, number)?;
+    let review = client.list_review_comments(&owner, &repo, number)?;
+    Ok(thread_comments(issue, review))
+}
+
+fn thread_comments(
+    issue: Vec<IssueComment>,
+    review: Vec<ReviewComment>,
+) -> Vec<PullRequestComment> {
+    let author =
+        |user: Option<PullRequestUser>| user.map_or_else(|| "ghost".to_string(), |u| u.login);
+    let started: HashMap<u64, _> = review.iter().map(|c| (c.id, c.created_at)).collect();
+
+    let mut comments: Vec<_> = issue
+        .into_iter()
+        .map(|c| {
+            let comment = PullRequestComment {
+                id: c.id,
+                author: author(c.user),
+                created_at: c.created_at,
+                location: None,
+                in_reply_to: None,
+                body: c.body,
+                url: c.html_url,
+            };
+            ((c.created_at, c.id), comment)
+        })
+        .chain(review.into_iter().map(|c| {
+            // A reply sorts with the comment that started its thread, if that one is listed.
+            let thread = c
+                .in_reply_to_id
+                .and_then(|root| started.get(&root).map(|at| (*at, root)))
+                .unwrap_or((c.created_at, c.id));
+            let location = match c.line {
+                Some(line) => format!("{}:{line}", c.path),
+                None => c.path,
+            };
+            let comment = PullRequestComment {
+                id: c.id,
+                author: author(c.user),
+                created_at: c.created_at,
+                location: Some(location),
+                in_reply_to: c.in_reply_to_id,
+                body: c.body,
+                url: c.html_url,
+            };
+            (thread, comment)
+        }))
+        .collect();
+    comments.sort_by_key(|(thread, c)| (*thread, c.created_at, c.id));
+    comments.into_iter().map(|(_, c)| c).collect()
+}
+
+/// Condensed chronological history of a pull request.
+///
+/// Noise such as subscriptions and mentions is dropped, and consecutive commits by the same
+/// author are folded into one entry.
+pub fn timeline(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    number: u64,
+) -> Result<Vec<TimelineEntry>, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+
+    let events = client.list_timeline(&owner, &repo, number)?;
+    Ok(condense(events.iter().filter_map(timeline_entry).collect()))
+}
+
+fn timeline_entry(event: &TimelineEvent) -> Option<TimelineEntry> {
+    let login = |user: &Option<PullRequestUser>| {
+        user.as_ref().map_or_else(|| "ghost".to_string(), |u| u.login.clone())
+    };
+    let actor = login(&event.actor);
+    let label = || event.label.as_ref().map_or("", |l| l.name.as_str());
+
+    let (time, actor, kind, summary) = match event.event.as_str() {
+        "committed" => {
+            let author = event.author.as_ref()?;
+            let sha = event.sha.as_deref().unwrap_or("");
+            let subject = event.message.as_deref().unwrap_or("").lines().next().unwrap_or("");
+            let summary = format!("{} {subject}", &sha[..sha.len().min(7)]);
+            (Some(author.date.clone()), author.name.clone(), "commit", summary)
+        }
+        "reviewed" => {
+            let state = event.state.as_deref().unwrap_or("commented").replace('_', " ");
+            (event.submitted_at.clone(), login(&event.user), "review", state)
+        }
+        "commented" => {
+            let body = event.body.as_deref().unwrap_or("");
+            (event.created_at.clone(), login(&event.user), "comment", excerpt(body))
+        }
+        "head_ref_force_pushed" => {
+            (event.created_at.clone(), actor, "force-push", "force-pushed the branch".into())
+        }
+        "labeled" => (event.created_at.clone(), actor, "label", format!("+{}", label())),
+        "unlabeled" => (event.created_at.clone(), actor, "label", format!("-{}", label())),
+        "review_requested" | "review_request_removed" => {
+            let verb = if event.event == "review_requested" { "requested" } else { "unrequested" };
+            let reviewer = login(&event.requested_reviewer);
+            (event.created_at.clone(), actor, "review-request", format!("{verb} {reviewer}"))
+        }
+        "renamed" => {
+            let rename = event.rename.as_ref()?;
+            let summary = format!("renamed '{}' → '{}'", rename.from, rename.to);
+            (event.created_at.clone(), actor, "rename", summary)
+        }
+        "deployed" => (event.created_at.clone(), actor, "deploy", "deployed".into()),
+        "ready_for_review" | "convert_to_draft" | "merged" | "closed" | "reopened"
+        | "head_ref_deleted" | "head_ref_restored" | "base_ref_changed" => {
+            let kind = event.event.replace('_', "-");
+            (event.created_at.clone(), actor, "state", kind)
+        }
+        _ => return None,
+    };
+
+    Some(TimelineEntry { time: time?, actor, kind: kind.to_string(), summary })
+}
+
+/// Fold runs of consecutive commits by the same author into one entry.
+fn condense(entries: Vec<TimelineEntry>) -> Vec<TimelineEntry> {
+    let mut out: Vec<TimelineEntry> = Vec::new();
+    let mut run = 0;
+    for entry in entries {
+        match out.last_mut() {
+            Some(last)
+                if entry.kind == "commit" && last.kind == "commit" && last.actor == entry.actor =>
+            {
+                run += 1;
+                last.summary = format!("{} commits, latest: {}", run, entry.summary);
+            }
+            _ => {
+                run = 1;
+                out.push(entry);
+            }
+        }
+    }
+    out
+}
+
+/// First line of a comment, shortened to fit on one line.
+fn excerpt(body: &str) -> String {
+    const MAX_CHARS: usize = 60;
+    let line = body.lines().find(|l| !l.trim().is_empty()).unwrap_or("").trim();
+    if line.chars().count() > MAX_CHARS {
+        format!("{}…", line.chars().take(MAX_CHARS).collect::<String>())
+    } else {
+        line.to_string()
+    }
+}
+
+fn summarize_checks(check_runs: &[CheckRun]) -> ChecksSummary {
+    let mut summary = ChecksSummary::default();
+    for run in check_runs {
+        match (run.status.as_str(), run.conclusion.as_deref()) {
+            ("completed", Some("success")) => summary.passed += 1,
+            ("completed", Some("skipped" | "neutral")) => summary.skipped += 1,
+            ("completed", _) => summary.failed += 1,
+            _ => summary.pending += 1,
+        }
+    }
+    summary
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::mock_api;
+    use crate::models::CommitSignature;
+
+    fn check_run(status: &str, conclusion: Option<&str>) -> CheckRun {
+        CheckRun {
+            id: 1,
+            name: "ci".to_string(),
+            status: status.to_string(),
+            conclusion: conclusion.map(str::to_string),
+            output: None,
+            started_at: None,
+            completed_at: None,
+            details_url: None,
+            html_url: None,
+        }
+    }
+
+    #[test]
+    fn timeline_entry_summarizes_known_events() {
+        let events: Vec<TimelineEvent> = serde_json::from_str(
+            r#"[
+                {"event": "committed", "sha": "abcdef123456", "message": "Add x\n\nbody",
+                 "author": {"name": "Ann", "date": "2026-01-01T10:00:00Z"}},
+                {"event": "reviewed", "state": "changes_requested", "user": {"login": "bob"},
+                 "submitted_at": "2026-01-02T10:00:00Z"},
+                {"event": "labeled", "label": {"name": "bug"}, "actor": {"login": "ann"},
+                 "created_at": "2026-01-03T10:00:00Z"},
+                {"event": "subscribed", "actor": {"login": "ann"},
+                 "created_at": "2026-01-03T10:00:00Z"}
+            ]"#,
+        )
+        .unwrap();
+
+        let entries: Vec<TimelineEntry> = events.iter().filter_map(timeline_entry).collect();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].summary, "abcdef1 Add x");
+        assert_eq!(entries[1].summary, "changes requested");
+        assert_eq!(entries[1].actor, "bob");
+        assert_eq!(entries[2].summary, "+bug");
+    }
+
+    #[test]
+    fn condense_folds_consecutive_commits_by_author() {
+        let entry = |actor: &str, kind: &str, summary: &str| TimelineEntry {
+            time: "2026-01-01T00:00:00Z".to_string(),
+            actor: actor.to_string(),
+            kind: kind.to_string(),
+            summary: summary.to_string(),
+        };
+        let entries = condense(vec![
+            entry("ann", "commit", "a1 one"),
+            entry("ann", "commit", "a2 two"),
+            entry("ann", "commit", "a3 three"),
+            entry("bob", "commit", "b1 four"),
+            entry("ann", "review", "approved"),
+        ]);
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].summary, "3 commits, latest: a3 three");
+        assert_eq!(entries[1].summary, "b1 four");
+    }
+
+    #[test]
+    fn merge_checks_keeps_required_checks_and_expects_missing_ones() {
+        let mut build = check_run("completed", Some("success"));
+        build.name = "build".to_string();
+        build.started_at = Some("2026-01-01T00:00:00Z".parse().unwrap());
+        build.completed_at = Some("2026-01-01T00:03:05Z".parse().unwrap());
+        let status = |context: &str, state: &str| CommitStatus {
+            context: context.to_string(),
+            state: state.to_string(),
+            description: None,
+            target_url: Some("https://ci.example.com/1".to_string()),
+            created_at: "2026-01-01T00:00:00Z".parse().unwrap(),
+            updated_at: "2026-01-01T00:00:42Z".parse().unwrap(),
+        };
+        let statuses = vec![status("deploy/preview", "pending"), status("coverage", "error")];
+        let required = ["build".to_string(), "coverage".to_string(), "e2e".to_string()];
+
+        let checks = merge_checks(vec![build], statuses, Some(&required));
+        let names: Vec<&str> = checks.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["build", "coverage", "e2e"]);
+        assert_eq!(checks[0].duration_secs(), Some(185));
+        assert!(checks[1].is_failure());
+        assert_eq!(checks[1].duration_secs(), Some(42));
+        assert_eq!(checks[2].kind, CheckKind::Expected);
+        assert!(checks[2].is_pending() && !checks[2].is_failure());
+        assert!(format_check(&checks[0]).ends_with("build (3m05s)"));
+    }
+
+    #[test]
+    fn summarize_checks_counts_by_outcome() {
+        let runs = vec![
+            check_run("completed", Some("success")),
+            check_run("completed", Some("success")),
+            check_run("completed", Some("failure")),
+            check_run("completed", Some("skipped")),
+            check_run("in_progress", None),
+        ];
+        let summary = summarize_checks(&runs);
+        assert_eq!(summary, ChecksSummary { passed: 2, failed: 1, pending: 1, skipped: 1 });
+    }
+
+    #[test]
+    fn to_output_maps_rollup_and_review_decision() {
+        let node: PullRequestStatusNode = serde_json::from_str(
+            r#"{
+                "number": 7,
+                "title": "Add feature",
+                "author": {"login": "octocat"},
+                "headRefName": "feature",
+                "mergeable": "CONFLICTING",
+                "reviewDecision": "APPROVED",
+                "commits": {"nodes": [{"commit": {"statusCheckRollup": {"state": "PENDING"}}}]}
+            }"#,
+        )
+        .unwrap();
+
+        let output = to_output(node);
+        assert_eq!(output.mergeable, Some(false));
+        assert!(output.actions_in_progress);
+        assert_eq!(output.ci_status, "pending");
+        assert_eq!(output.review_decision.as_deref(), Some("approved"));
+    }
+
+    #[test]
+    fn to_output_without_checks_reports_none() {
+        let node: PullRequestStatusNode = serde_json::from_str(
+            r#"{
+                "number": 8,
+                "title": "Docs",
+                "author": null,
+                "headRefName": "docs",
+                "mergeable": "UNKNOWN",
+                "reviewDecision": null,
+                "commits": {"nodes": [{"commit": {"statusCheckRollup": null}}]}
+            }"#,
+        )
+        .unwrap();
+
+        let output = to_output(node);
+        assert_eq!(output.mergeable, None);
+        assert!(!output.actions_in_progress);
+        assert_eq!(output.ci_status, "none");
+        assert_eq!(output.author, "ghost");
+    }
+
+    #[test]
+    fn newest_by_repo_merges_queries_before_the_limit() {
+        let node = |repo: &str, number: u64, updated_at: &str| -> PullRequestSearchNode {
+            serde_json::from_value(serde_json::json!({
+                "number": number, "title": "PR", "author": null, "headRefName": "b",
+                "mergeable": "MERGEABLE", "reviewDecision": null, "updatedAt": updated_at,
+                "repository": {"nameWithOwner": repo},
+                "commits": {"nodes": []}
+            }))
+            .unwrap()
+        };
+        let authored = vec![
+            node("acme/web", 1, "2026-03-01T09:00:00Z"),
+            node("acme/api", 2, "2026-03-01T08:00:00Z"),
+        ];
+        let review_requested = vec![
+            node("acme/api", 3, "2026-03-01T10:00:00Z"),
+            node("acme/web", 1, "2026-03-01T09:00:00Z"),
+        ];
+
+        let prs = newest_by_repo(authored.into_iter().chain(review_requested).collect(), 2);
+        let found: Vec<(&str, u64)> =
+            prs.iter().map(|p| (p.repo.as_str(), p.pull_request.number)).collect();
+        assert_eq!(found, [("acme/api", 3), ("acme/web", 1)]);
+    }
+
+    #[test]
+    fn search_node_flattens_pull_request_fields() {
+        let node: PullRequestSearchNode = serde_json::from_str(
+            r#"{
+                "number": 3,
+                "title": "Bump deps",
+                "author": {"login": "bot"},
+                "headRefName": "deps",
+                "mergeable": "MERGEABLE",
+                "reviewDecision": "REVIEW_REQUIRED",
+                "repository": {"nameWithOwner": "acme/api"},
+                "commits": {"nodes": []}
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(node.repository.name_with_owner, "acme/api");
+        let output = to_output(node.pull_request);
+        assert_eq!(output.number, 3);
+        assert_eq!(output.ci_status, "none");
+    }
+
+    #[test]
+    fn readiness_blockers_cover_draft_checks_and_signatures() {
+        let pull_request = serde_json::from_str(
+            r#"{
+                "number": 9,
+                "title": "Bump deps",
+                "user": {"login": "octocat"},
+                "head": {"ref": "bump", "sha": "abc"},
+                "state": "open",
+                "draft": true,
+                "mergeable": true
+            }"#,
+        )
+        .unwrap();
+        let commit = |sha: &str, verified: bool| CommitSignature {
+            sha: sha.to_string(),
+            subject: "Bump".to_string(),
+            author: "octocat".to_string(),
+            date: None,
+            verified,
+            reason: if verified { "valid" } else { "unsigned" }.to_string(),
+            url: None,
+        };
+        let view = PullRequestView {
+            pull_request,
+            checks: ChecksSummary { passed: 3, failed: 1, pending: 0, skipped: 0 },
+            commits: vec![commit("1111111aaaa", true), commit("2222222bbbb", false)],
+        };
+
+        assert_eq!(
+            readiness_blockers(&view, false),
+            vec!["pull request is a draft", "1 check(s) failed"]
+        );
+        let signed = readiness_blockers(&view, true);
+        assert_eq!(signed.last().unwrap(), "commit 2222222 is not verified (unsigned)");
+        assert_eq!(signed.len(), 3);
+    }
+
+    #[test]
+    fn fill_mergeable_degrades_after_rate_limit() {
+        let pr = |number: u64, mergeable: Option<bool>| PullRequestOutput {
+            number,
+            title: "Fix".to_string(),
+            author: "octocat".to_string(),
+            branch: "fix".to_string(),
+            mergeable,
+            actions_in_progress: false,
+            ci_status: "none".to_string(),
+            review_decision: None,
+            partial: false,
+        };
+        let mut prs = vec![pr(1, None), pr(2, Some(false)), pr(3, None), pr(4, None)];
+        let mut looked_up = Vec::new();
+        fill_mergeable(&mut prs, |number| {
+            looked_up.push(number);
+            match number {
+                1 => Ok(Some(true)),
+                _ => Err(AppError::RateLimited("rate limit exceeded".into())),
+            }
+        });
+
+        assert_eq!(looked_up, [1, 3]);
+        assert_eq!(prs[0].mergeable, Some(true));
+        let partial: Vec<u64> = prs.iter().filter(|p| p.partial).map(|p| p.number).collect();
+        assert_eq!(partial, [3, 4]);
+    }
+
+    #[test]
+    fn fill_mergeable_across_looks_up_each_repository() {
+        let api = mock_api::server();
+        let pull = |number: u64, mergeable: bool| {
+            serde_json::json!({
+                "number": number, "title": "Fix", "user": { "login": "octocat" },
+                "head": { "ref": "fix", "sha": "abc" }, "mergeable": mergeable
+            })
+        };
+        api.route("GET", "/repos/mock-pr-across/api/pulls/1", 200, pull(1, true));
+        api.route("GET", "/repos/mock-pr-across/web/pulls/2", 200, pull(2, false));
+        let output = |number: u64, mergeable: Option<bool>| PullRequestOutput {
+            number,
+            title: "Fix".to_string(),
+            author: "octocat".to_string(),
+            branch: "fix".to_string(),
+            mergeable,
+            actions_in_progress: false,
+            ci_status: "none".to_string(),
+            review_decision: None,
+            partial: false,
+        };
+        let mut by_repo = vec![
+            ("mock-pr-across/api".to_string(), vec![output(1, None)]),
+            ("mock-pr-across/cli".to_string(), vec![output(7, Some(true))]),
+            ("mock-pr-across/web".to_string(), vec![output(2, None)]),
+        ];
+
+        let client = GitHubClient::new("token".into()).unwrap();
+        fill_mergeable_across(&client, &mut by_repo, 4);
+        assert_eq!(by_repo[0].1[0].mergeable, Some(true));
+        assert_eq!(by_repo[2].1[0].mergeable, Some(false));
+        assert!(api.requests("/repos/mock-pr-across/cli").is_empty());
+    }
+
+    #[test]
+    fn rerun_checks_reruns_runs_that_did_not_pass() {
+        let api = mock_api::server();
+        let base = "/repos/mock-pr-rerun/api";
+        api.route(
+            "GET",
+            &format!("{base}/pulls/5"),
+            200,
+            serde_json::json!({
+                "number": 5, "title": "Fix", "user": { "login": "octocat" },
+                "head": { "ref": "fix", "sha": "c0ffee" }
+            }),
+        );
+        let run = |id: u64, status: &str, conclusion: Option<&str>| {
+            serde_json::json!({
+                "id": id, "run_number": id, "name": "ci", "display_title": "Fix",
+                "status": status, "conclusion": conclusion, "event": "pull_request",
+                "head_branch": "fix", "html_url": "", "created_at": "2026-01-01T00:00:00Z"
+            })
+        };
+        let runs = [
+            run(1, "completed", Some("success")),
+            run(2, "completed", Some("failure")),
+            run(3, "in_progress", None),
+            run(4, "completed", Some("timed_out")),
+        ];
+        api.route(
+            "GET",
+            &format!("{base}/actions/runs"),
+            200,
+            serde_json::json!({ "workflow_runs": runs }),
+        );
+        api.route(
+            "POST",
+            &format!("{base}/actions/runs/2/rerun-failed-jobs"),
+            201,
+            serde_json::json!({}),
+        );
+        api.route(
+            "POST",
+            &format!("{base}/actions/runs/4/rerun-failed-jobs"),
+            201,
+            serde_json::json!({}),
+        );
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = mock_api::storage(dir.path());
+
+        let rerun = rerun_checks(&storage, Some("mock-pr-rerun/api"), 5, true).unwrap();
+        assert_eq!(rerun.iter().map(|r| r.id).collect::<Vec<_>>(), [2, 4]);
+        let listed = api.requests(&format!("{base}/actions/runs?"));
+        assert!(listed[0].path.contains("head_sha=c0ffee"));
+        let posts: Vec<String> =
+            api.requests(base).into_iter().filter(|r| r.method == "POST").map(|r| r.path).collect();
+        assert_eq!(
+            posts,
+            [
+                format!("{base}/actions/runs/2/rerun-failed-jobs"),
+                format!("{base}/actions/runs/4/rerun-failed-jobs")
+            ]
+        );
+    }
+
+    #[test]
+    fn web_url_fetches_only_the_pull_request() {
+        let api = mock_api::server();
+        let base = "/repos/mock-pr-web/api";
+        api.route(
+            "GET",
+            &format!("{base}/pulls/9"),
+            200,
+            serde_json::json!({
+                "number": 9, "title": "Fix", "user": { "login": "octocat" },
+                "head": { "ref": "fix", "sha": "c0ffee" }, "html_url": "https://github.com/pr/9"
+            }),
+        );
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = mock_api::storage(dir.path());
+
+        let url = web_url(&storage, Some("mock-pr-web/api"), 9).unwrap();
+        assert_eq!(url, "https://github.com/pr/9");
+        let paths: Vec<String> = api.requests(base).into_iter().map(|r| r.path).collect();
+        assert_eq!(paths, [format!("{base}/pulls/9")]);
+    }
+
+    #[test]
+    fn edit_requests_teams_and_resolves_the_milestone_by_title() {
+        let api = mock_api::server();
+        let base = "/repos/mock-pr-edit/api";
+        let pull_request = serde_json::json!({
+            "number": 9, "title": "Fix", "user": { "login": "octocat" },
+            "head": { "ref": "fix", "sha": "c0ffee" }, "html_url": "https://github.com/pr/9"
+        });
+        api.route("GET", &format!("{base}/pulls/9"), 200, pull_request.clone());
+        api.route("PATCH", &format!("{base}/pulls/9"), 200, pull_request);
+        api.route(
+            "GET",
+            &format!("{base}/milestones"),
+            200,
+            serde_json::json!([{ "number": 1, "title": "v1" }, { "number": 3, "title": "v2" }]),
+        );
+        api.route(
+            "POST",
+            &format!("{base}/pulls/9/requested_reviewers"),
+            201,
+            serde_json::json!({}),
+        );
+        api.route("POST", &format!("{base}/issues/9/labels"), 200, serde_json::json!([]));
+        api.route(
+            "DELETE",
+            &format!("{base}/issues/9/labels/needs%20triage"),
+            200,
+            serde_json::json!([]),
+        );
+        api.route("POST", &format!("{base}/issues/9/assignees"), 201, serde_json::json!({}));
+        api.route(
+            "PATCH",
+            &format!("{base}/issues/9"),
+            200,
+            serde_json::json!({
+                "number": 9, "title": "Fix", "created_at": "2026-01-01T00:00:00Z", "html_url": ""
+            }),
+        );
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = mock_api::storage(dir.path());
+
+        let update = PullRequestUpdate { title: Some("Fix it".to_string()), ..Default::default() };
+        let metadata = PrMetadata {
+            add_reviewers: vec!["@alice".to_string(), "@acme/core".to_string()],
+            add_labels: vec!["bug".to_string()],
+            remove_labels: vec!["needs triage".to_string()],
+            add_assignees: vec!["bob".to_string()],
+            milestone: Some("v2".to_string()),
+            ..Default::default()
+        };
+        edit(&storage, Some("mock-pr-edit/api"), 9, &update, &metadata).unwrap();
+
+        let calls: Vec<(String, String)> = api
+            .requests(base)
+            .into_iter()
+            .filter(|r| r.method != "GET")
+            .map(|r| (r.method, r.body))
+            .collect();
+        let bodies: Vec<&str> = calls.iter().map(|(_, body)| body.as_str()).collect();
+        assert_eq!(
+            bodies,
+            [
+                r#"{"title":"Fix it"}"#,
+                r#"{"reviewers":["alice"],"team_reviewers":["core"]}"#,
+                r#"{"labels":["bug"]}"#,
+                "",
+                r#"{"assignees":["bob"]}"#,
+                r#"{"milestone":3}"#,
+            ]
+        );
+        assert_eq!(calls[3].0, "DELETE");
+
+        let missing = PrMetadata { milestone: Some("v9".to_string()), ..Default::default() };
+        let err = edit(&storage, Some("mock-pr-edit/api"), 9, &update, &missing).unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+
+        let clear = PrMetadata { remove_milestone: true, ..Default::default() };
+        let unchanged = PullRequestUpdate::default();
+        edit(&storage, Some("mock-pr-edit/api"), 9, &unchanged, &clear).unwrap();
+        let cleared = api.requests(&format!("{base}/issues/9"));
+        assert_eq!(cleared.last().unwrap().body, r#"{"milestone":null}"#);
+    }
 
     #[test]
-    fn parse_remote_url_ssh() {
-        let (owner, repo) = parse_remote_url("git@github.com:octocat/hello-world.git").unwrap();
-        assert_eq!(owner, "octocat");
-        assert_eq!(repo, "hello-world");
+    fn upstream_head_requires_a_pushed_branch_and_names_forks() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let git = |args: &[&str]| conflicts::git(Some(dir.path()), args).unwrap();
+        git(&["init", "--quiet", "--initial-branch", "fix"]);
+        let err = upstream_head(Some(dir.path()), "acme").unwrap_err();
+        assert!(matches!(err, AppError::InvalidInput(message) if message.contains("upstream")));
+
+        git(&["remote", "add", "origin", "git@github.com:acme/api.git"]);
+        git(&["remote", "add", "fork", "https://github.com/alice/api.git"]);
+        git(&["config", "branch.fix.remote", "origin"]);
+        git(&["config", "branch.fix.merge", "refs/heads/fix"]);
+        assert_eq!(upstream_head(Some(dir.path()), "acme").unwrap(), "fix");
+        git(&["config", "branch.fix.remote", "fork"]);
+        git(&["config", "branch.fix.merge", "refs/heads/alice-fix"]);
+        assert_eq!(upstream_head(Some(dir.path()), "acme").unwrap(), "alice:alice-fix");
+    }
+
+    #[test]
+    fn checks_watch_stops_once_nothing_is_pending() {
+        let api = mock_api::server();
+        let base = "/repos/mock-pr-checks/api";
+        api.route(
+            "GET",
+            &format!("{base}/pulls/8"),
+            200,
+            serde_json::json!({
+                "number": 8, "title": "Fix", "user": { "login": "octocat" },
+                "head": { "ref": "fix", "sha": "beef" }
+            }),
+        );
+        api.route(
+            "GET",
+            &format!("{base}/commits/beef/check-runs"),
+            200,
+            serde_json::json!({ "check_runs": [
+                { "id": 1, "name": "build", "status": "completed", "conclusion": "success" },
+                { "id": 2, "name": "lint", "status": "completed", "conclusion": "failure" }
+            ] }),
+        );
+        api.route(
+            "GET",
+            &format!("{base}/commits/beef/status"),
+            200,
+            serde_json::json!({ "state": "success", "statuses": [] }),
+        );
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = mock_api::storage(dir.path());
+
+        let mut polls = 0;
+        let watch = Some(Duration::from_secs(60));
+        let checks =
+            checks(&storage, Some("mock-pr-checks/api"), 8, false, watch, |_| polls += 1).unwrap();
+        assert_eq!(polls, 1);
+        let failed: Vec<&str> =
+            checks.iter().filter(|c| c.is_failure()).map(|c| c.name.as_str()).collect();
+        assert_eq!(failed, ["lint"]);
     }
 
     #[test]
-    fn parse_remote_url_https() {
-        let (owner, repo) = parse_remote_url("https://github.com/octocat/hello-world.git").unwrap();
-        assert_eq!(owner, "octocat");
-        assert_eq!(repo, "hello-world");
+    fn checks_required_only_fails_when_protection_is_unreadable() {
+        let api = mock_api::server();
+        let base = "/repos/mock-pr-checks-required/api";
+        api.route(
+            "GET",
+            &format!("{base}/pulls/9"),
+            200,
+            serde_json::json!({
+                "number": 9, "title": "Fix", "user": { "login": "octocat" },
+                "head": { "ref": "fix", "sha": "beef" }, "base": { "ref": "main", "sha": "cafe" }
+            }),
+        );
+        api.route(
+            "GET",
+            &format!("{base}/branches/main/protection/required_status_checks"),
+            404,
+            serde_json::json!({ "message": "Not Found" }),
+        );
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = mock_api::storage(dir.path());
+
+        let result = checks(&storage, Some("mock-pr-checks-required/api"), 9, true, None, |_| {});
+        assert!(matches!(result, Err(AppError::NotFound(message)) if message.contains("admin")));
     }
 
     #[test]
-    fn parse_remote_url_https_no_git_suffix() {
-        let (owner, repo) = parse_remote_url("https://github.com/octocat/hello-world").unwrap();
-        assert_eq!(owner, "octocat");
-        assert_eq!(repo, "hello-world");
+    fn thread_comments_orders_replies_after_their_thread() {
+        let at = |minute: u32| format!("2026-03-01T10:{minute:02}:00Z");
+        let issue: Vec<IssueComment> = serde_json::from_value(serde_json::json!([
+            {"id": 1, "user": {"login": "alice"}, "body": "LGTM?", "created_at": at(0),
+             "html_url": "https://github.com/acme/api/pull/7#issuecomment-1"},
+            {"id": 2, "user": null, "body": "Ping", "created_at": at(30),
+             "html_url": "https://github.com/acme/api/pull/7#issuecomment-2"},
+        ]))
+        .unwrap();
+        let review: Vec<ReviewComment> = serde_json::from_value(serde_json::json!([
+            {"id": 10, "user": {"login": "bob"}, "body": "Typo", "created_at": at(10),
+             "html_url": "https://github.com/acme/api/pull/7#r10", "path": "src/lib.rs",
+             "line": 4},
+            {"id": 11, "user": {"login": "alice"}, "body": "Fixed", "created_at": at(40),
+             "html_url": "https://github.com/acme/api/pull/7#r11", "path": "src/lib.rs",
+             "line": 4, "in_reply_to_id": 10},
+            {"id": 12, "user": {"login": "bob"}, "body": "Outdated", "created_at": at(20),
+             "html_url": "https://github.com/acme/api/pull/7#r12", "path": "README.md",
+             "line": null},
+        ]))
+        .unwrap();
+
+        let comments = thread_comments(issue, review);
+        let ids: Vec<u64> = comments.iter().map(|c| c.id).collect();
+        assert_eq!(ids, [1, 10, 11, 12, 2]);
+        assert_eq!(comments[1].location.as_deref(), Some("src/lib.rs:4"));
+        assert_eq!(comments[2].in_reply_to, Some(10));
+        assert_eq!(comments[3].location.as_deref(), Some("README.md"));
+        assert_eq!(comments[4].author, "ghost");
+    }
+
+    #[test]
+    fn comment_posts_to_the_conversation() {
+        let api = mock_api::server();
+        let path = "/repos/mock-pr-comment/api/issues/7/comments";
+        let created = serde_json::json!({
+            "id": 99, "user": {"login": "mock-user"}, "body": "Ship it",
+            "created_at": "2026-03-01T10:00:00Z",
+            "html_url": "https://github.com/mock-pr-comment/api/pull/7#issuecomment-99",
+        });
+        api.route("POST", path, 201, created);
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = mock_api::storage(dir.path());
+
+        let created = comment(&storage, Some("mock-pr-comment/api"), 7, "Ship it").unwrap();
+        assert_eq!(created.id, 99);
+        let requests = api.requests(path);
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].body, r#"{"body":"Ship it"}"#);
+
+        assert!(matches!(
+            comment(&storage, Some("mock-pr-comment/api"), 7, " "),
+            Err(AppError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn comments_surface_api_errors() {
+        let api = mock_api::server();
+        let base = "/repos/mock-pr-comments/api";
+        api.route("GET", &format!("{base}/issues/3/comments"), 200, serde_json::json!([]));
+        api.route(
+            "GET",
+            &format!("{base}/pulls/3/comments"),
+            404,
+            serde_json::json!({"message": "Not Found"}),
+        );
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = mock_api::storage(dir.path());
+
+        let result = comments(&storage, Some("mock-pr-comments/api"), 3);
+        assert!(matches!(result, Err(AppError::NotFound(_))), "{result:?}");
+    }
+
+    #[test]
+    fn close_comments_first_and_deletes_the_head_branch() {
+        let api = mock_api::server();
+        let base = "/repos/mock-pr-close/api";
+        let pull_request = serde_json::json!({
+            "number": 5, "title": "Try", "user": {"login": "mock-user"}, "state": "open",
+            "head": {"ref": "spike/try", "repo": {"full_name": "mock-pr-close/api"}},
+        });
+        api.route("GET", &format!("{base}/pulls/5"), 200, pull_request.clone());
+        api.route("PATCH", &format!("{base}/pulls/5"), 200, pull_request);
+        let comment = serde_json::json!({
+            "id": 1, "user": {"login": "mock-user"}, "body": "Superseded",
+            "created_at": "2026-03-01T10:00:00Z",
+            "html_url": "https://github.com/mock-pr-close/api/pull/5#issuecomment-1",
+        });
+        api.route("POST", &format!("{base}/issues/5/comments"), 201, comment);
+        api.route(
+            "DELETE",
+            &format!("{base}/git/refs/heads/spike/try"),
+            204,
+            serde_json::json!({}),
+        );
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = mock_api::storage(dir.path());
+
+        let spec = Some("mock-pr-close/api");
+        let Execution::Done(change) =
+            close(&storage, spec, 5, Some("Superseded"), true, ExecutionMode::Live).unwrap()
+        else {
+            panic!("expected the pull request to be closed");
+        };
+        assert!(change.changed);
+        assert_eq!(change.deleted_branch.as_deref(), Some("spike/try"));
+
+        let calls: Vec<String> = api
+            .requests(base)
+            .iter()
+            .filter(|r| r.method != "GET")
+            .map(|r| format!("{} {}", r.method, r.path))
+            .collect();
+        assert_eq!(
+            calls,
+            [
+                format!("POST {base}/issues/5/comments"),
+                format!("PATCH {base}/pulls/5"),
+                format!("DELETE {base}/git/refs/heads/spike/try"),
+            ]
+        );
     }
 }
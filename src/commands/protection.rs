@@ -0,0 +1,216 @@
+//! Branch protection commands (`repo protection`).
+
+use crate::commands::account;
+use crate::domain::parse_repo_spec;
+use crate::error::AppError;
+use crate::github::{GitHubClient, ReposApi};
+use crate::models::{BranchProtectionRules, ExecutionMode, ProgressOutcome, ProtectionChange};
+use crate::progress::Progress;
+use crate::remote::resolve_repo;
+use crate::storage::Storage;
+use serde_json::Value;
+use std::path::Path;
+
+/// Operation name of `repo protection apply` progress events.
+const APPLY_OPERATION: &str = "repo protection apply";
+
+/// Rule names in the order they are compared and reported.
+const RULES: [&str; 7] = [
+    "required_reviews",
+    "dismiss_stale_reviews",
+    "require_code_owner_reviews",
+    "status_checks",
+    "strict_status_checks",
+    "linear_history",
+    "enforce_admins",
+];
+
+/// Repositories `apply` works on.
+#[derive(Debug, Clone, Copy)]
+pub enum ProtectionTargets<'a> {
+    /// The given `owner/repo` specs, or the repository detected from git when empty.
+    Repos(&'a [String]),
+    /// Up to `limit` non-archived repositories of an organization.
+    Org { org: &'a str, limit: usize },
+}
+
+/// Current protection of a branch as declarative rules.
+pub fn show(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    branch: &str,
+) -> Result<BranchProtectionRules, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+
+    match client.get_branch_protection(&owner, &repo, branch) {
+        Err(AppError::NotFound(_)) => {
+            Err(AppError::NotFound(format!("branch '{branch}' of {owner}/{repo} is not protected")))
+        }
+        result => result.map(Into::into),
+    }
+}
+
+/// Every rule with its value for display, e.g. `("required_reviews", "2")`.
+pub fn describe(rules: &BranchProtectionRules) -> Vec<(&'static str, String)> {
+    let value = serde_json::to_value(rules).expect("rules serialize to JSON");
+    RULES.iter().map(|rule| (*rule, rule_value(&value[*rule]))).collect()
+}
+
+/// Read rules from a JSON file in the format printed by `repo protection show --json`.
+pub fn load_file(path: &Path) -> Result<BranchProtectionRules, AppError> {
+    let content = std::fs::read_to_string(path)?;
+    serde_json::from_str(&content)
+        .map_err(|e| AppError::invalid_input(format!("invalid {}: {e}", path.display())))
+}
+
+/// Replace the protection of `branch` with `rules` wherever it differs.
+///
+/// Branches that already match are left alone and failures are recorded per repository. A dry
+/// run reports the differences without updating anything.
+pub fn apply(
+    storage: &impl Storage,
+    targets: ProtectionTargets,
+    branch: &str,
+    rules: &BranchProtectionRules,
+    mode: ExecutionMode,
+    progress: Progress,
+) -> Result<Vec<ProtectionChange>, AppError> {
+    if let ProtectionTargets::Repos(repos) = targets {
+        for repo in repos {
+            parse_repo_spec(repo)?;
+        }
+    }
+
+    let (_account, token) = if mode.is_dry_run() {
+        account::get_active_with_token(storage)?
+    } else {
+        account::get_active_for_destructive(storage)?
+    };
+    let client = GitHubClient::new(token)?;
+    let repos = match targets {
+        ProtectionTargets::Repos([]) => {
+            let (owner, repo) = resolve_repo(storage, None)?;
+            vec![format!("{owner}/{repo}")]
+        }
+        ProtectionTargets::Repos(repos) => repos.to_vec(),
+        ProtectionTargets::Org { org, limit } => client
+            .list_org_repos(org, limit)?
+            .into_iter()
+            .filter(|r| !r.archived)
+            .map(|r| r.full_name)
+            .collect(),
+    };
+
+    Ok(repos
+        .iter()
+        .enumerate()
+        .map(|(i, repo)| {
+            progress.started(APPLY_OPERATION, repo, i + 1, repos.len());
+            let change = apply_repo(&client, repo, branch, rules, mode);
+            let outcome = match (&change.error, change.changed.is_empty()) {
+                (Some(_), _) => ProgressOutcome::Failed,
+                (None, true) => ProgressOutcome::Skipped,
+                (None, false) => ProgressOutcome::Succeeded,
+            };
+            progress.completed(APPLY_OPERATION, repo, outcome, change.error.as_deref());
+            change
+        })
+        .collect())
+}
+
+fn apply_repo(
+    client: &impl ReposApi,
+    spec: &str,
+    branch: &str,
+    rules: &BranchProtectionRules,
+    mode: ExecutionMode,
+) -> ProtectionChange {
+    let mut change = ProtectionChange {
+        repo: spec.to_string(),
+        branch: branch.to_string(),
+        changed: Vec::new(),
+        error: None,
+    };
+
+    let (owner, repo) = parse_repo_spec(spec).expect("validated in apply");
+    let result = match client.get_branch_protection(&owner, &repo, branch) {
+        Ok(current) => Ok(Some(current.into())),
+        Err(AppError::NotFound(_)) => Ok(None),
+        Err(e) => Err(e),
+    }
+    .and_then(|current| {
+        let changed = diff_rules(current.as_ref(), rules);
+        if !changed.is_empty() && !mode.is_dry_run() {
+            client.update_branch_protection(&owner, &repo, branch, rules)?;
+        }
+        Ok(changed)
+    });
+
+    match result {
+        Ok(changed) => change.changed = changed,
+        Err(e) => change.error = Some(e.to_string()),
+    }
+    change
+}
+
+/// Rules that differ between `current` (`None` when unprotected) and `desired`.
+fn diff_rules(
+    current: Option<&BranchProtectionRules>,
+    desired: &BranchProtectionRules,
+) -> Vec<String> {
+    let before = current.cloned().unwrap_or_default();
+    let before = serde_json::to_value(before).expect("rules serialize to JSON");
+    let after = serde_json::to_value(desired).expect("rules serialize to JSON");
+
+    let mut changed: Vec<String> =
+        current.is_none().then(|| "protection: none -> enabled".to_string()).into_iter().collect();
+    changed.extend(RULES.iter().filter(|rule| before[*rule] != after[*rule]).map(|rule| {
+        format!("{rule}: {} -> {}", rule_value(&before[*rule]), rule_value(&after[*rule]))
+    }));
+    changed
+}
+
+fn rule_value(value: &Value) -> String {
+    match value {
+        Value::Null => "none".to_string(),
+        Value::Array(items) => {
+            let items: Vec<&str> = items.iter().filter_map(Value::as_str).collect();
+            format!("[{}]", items.join(", "))
+        }
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_rules_lists_changed_rules() {
+        let current = BranchProtectionRules { required_reviews: Some(1), ..Default::default() };
+        let desired = BranchProtectionRules {
+            required_reviews: Some(2),
+            status_checks: Some(vec!["build".into(), "test".into()]),
+            ..Default::default()
+        };
+        assert_eq!(
+            diff_rules(Some(&current), &desired),
+            vec!["required_reviews: 1 -> 2", "status_checks: none -> [build, test]"]
+        );
+        assert!(diff_rules(Some(&desired), &desired).is_empty());
+        assert_eq!(diff_rules(None, &BranchProtectionRules::default()).len(), 1);
+    }
+
+    #[test]
+    fn load_file_rejects_unknown_rules() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("rules.json");
+        std::fs::write(&path, r#"{"required_reviews": 1, "linear_history": true}"#).unwrap();
+        assert_eq!(load_file(&path).unwrap().required_reviews, Some(1));
+
+        std::fs::write(&path, r#"{"required_review": 1}"#).unwrap();
+        assert!(load_file(&path).is_err());
+    }
+}
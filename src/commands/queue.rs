@@ -0,0 +1,49 @@
+//! Merge queue commands.
+
+use crate::commands::account;
+use crate::error::AppError;
+use crate::github::{GitHubClient, PullsApi};
+use crate::models::MergeQueue;
+use crate::remote::resolve_repo;
+use crate::storage::Storage;
+
+/// Get the merge queue for a branch (the default branch when omitted).
+pub fn status(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    branch: Option<&str>,
+) -> Result<MergeQueue, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+
+    let branch_label = branch.unwrap_or("default branch");
+    client.get_merge_queue(&owner, &repo, branch)?.ok_or_else(|| {
+        AppError::github_api(format!(
+            "no merge queue configured for {branch_label} of {owner}/{repo}"
+        ))
+    })
+}
+
+/// Format an estimated time to merge in seconds as a short human string.
+pub fn format_eta(seconds: Option<u64>) -> String {
+    match seconds {
+        None => "unknown".to_string(),
+        Some(s) if s < 60 => format!("~{s}s"),
+        Some(s) if s < 3600 => format!("~{}m", s / 60),
+        Some(s) => format!("~{}h{:02}m", s / 3600, (s % 3600) / 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_eta_scales_units() {
+        assert_eq!(format_eta(None), "unknown");
+        assert_eq!(format_eta(Some(45)), "~45s");
+        assert_eq!(format_eta(Some(600)), "~10m");
+        assert_eq!(format_eta(Some(3900)), "~1h05m");
+    }
+}
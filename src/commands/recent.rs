@@ -0,0 +1,55 @@
+//! Recently used repositories and organization (`gho recent`).
+//!
+//! Every repository resolved by a repo or PR command is recorded in the state file, and `-`
+//! in place of a repository argument stands for the last one.
+
+use crate::error::AppError;
+use crate::models::RecentHistory;
+use crate::storage::Storage;
+
+/// Recently used repositories, most recent first, and the last used organization.
+pub fn history(storage: &impl Storage) -> Result<RecentHistory, AppError> {
+    let state = storage.load_state()?;
+    Ok(RecentHistory { repos: state.recent_repos, last_org: state.last_org })
+}
+
+/// Let the user pick a recent repository and make it the last used one, so `-` refers to it.
+#[cfg(feature = "interactive")]
+pub fn select(storage: &impl Storage) -> Result<String, AppError> {
+    if !atty::is(atty::Stream::Stdin) {
+        return Err(AppError::TtyRequired);
+    }
+    let repos = history(storage)?.repos;
+    if repos.is_empty() {
+        return Err(AppError::invalid_input("no recently used repositories"));
+    }
+
+    let selection = inquire::Select::new("Select repository:", repos)
+        .prompt()
+        .map_err(|e| AppError::config(format!("selection cancelled: {e}")))?;
+    crate::remote::remember_repo(storage, &selection)?;
+    Ok(selection)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::remote::{expand_recent, resolve_repo};
+    use crate::storage::FilesystemStorage;
+    use tempfile::TempDir;
+
+    #[test]
+    fn resolved_repos_become_recent_and_dash_expands_to_last() {
+        let dir = TempDir::new().unwrap();
+        let storage = FilesystemStorage::new(&Config::with_path(dir.path().to_path_buf()));
+        assert!(expand_recent(&storage, "-").is_err());
+
+        resolve_repo(&storage, Some("acme/api")).unwrap();
+        resolve_repo(&storage, Some("acme/web")).unwrap();
+        assert_eq!(history(&storage).unwrap().repos, vec!["acme/web", "acme/api"]);
+
+        assert_eq!(resolve_repo(&storage, Some("-")).unwrap(), ("acme".into(), "web".into()));
+        assert_eq!(expand_recent(&storage, "acme/api").unwrap(), "acme/api");
+    }
+}
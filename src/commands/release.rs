@@ -0,0 +1,397 @@
+//! Release commands.
+
+use crate::commands::account;
+use crate::domain::glob_match;
+use crate::error::AppError;
+use crate::esay;
+use crate::github::{GitHubClient, ReposApi, UploadSource};
+use crate::logging;
+use crate::models::{AssetDownloadResult, AssetUploadResult, NewRelease, Release};
+use crate::remote::resolve_repo;
+use crate::storage::Storage;
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+/// Name of the checksum manifest uploaded with `--generate-checksums`.
+pub const CHECKSUMS_ASSET: &str = "SHA256SUMS";
+
+const MAX_BACKOFF_SECS: u64 = 60;
+
+/// List releases of a repository, newest first.
+pub fn list(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    limit: usize,
+) -> Result<Vec<Release>, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+    client.list_releases(&owner, &repo, limit)
+}
+
+/// Where the notes of a new release come from.
+#[derive(Debug, Clone)]
+pub enum ReleaseNotes {
+    /// Let GitHub generate notes from merged pull requests.
+    Generated,
+    /// Use the given text.
+    Text(String),
+    /// Use the message of the annotated tag in the local repository.
+    FromTag,
+}
+
+/// Options for [`create`].
+#[derive(Debug, Clone)]
+pub struct CreateOptions {
+    pub title: Option<String>,
+    pub notes: ReleaseNotes,
+    pub draft: bool,
+    pub prerelease: bool,
+}
+
+/// Create a release for `tag`.
+pub fn create(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    tag: &str,
+    options: &CreateOptions,
+) -> Result<Release, AppError> {
+    let body = match &options.notes {
+        ReleaseNotes::Generated => None,
+        ReleaseNotes::Text(text) => Some(text.clone()),
+        ReleaseNotes::FromTag => Some(tag_message(tag)?),
+    };
+
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+
+    let release = NewRelease {
+        tag_name: tag.to_string(),
+        name: options.title.clone(),
+        generate_release_notes: body.is_none(),
+        body,
+        draft: options.draft,
+        prerelease: options.prerelease,
+    };
+    client.create_release(&owner, &repo, &release)
+}
+
+/// Message of an annotated tag in the current git repository.
+fn tag_message(tag: &str) -> Result<String, AppError> {
+    let output =
+        logging::command(Command::new("git").args(["tag", "--list", "--format=%(contents)", tag]))
+            .output()
+            .map_err(|e| AppError::git(format!("failed to run git: {e}")))?;
+    if !output.status.success() {
+        return Err(AppError::git(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+    }
+
+    let message = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if message.is_empty() {
+        return Err(AppError::invalid_input(format!(
+            "tag '{tag}' not found locally or has no annotation message"
+        )));
+    }
+    Ok(message)
+}
+
+/// Download assets of the release tagged `tag` into `dir`.
+///
+/// Only assets matching `pattern` are fetched when one is given. Existing files are skipped
+/// unless `clobber` is set; each asset is written to a `.part` file and renamed when
+/// complete, so an interrupted download never leaves a truncated file behind.
+pub fn download(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    tag: &str,
+    pattern: Option<&str>,
+    dir: &Path,
+    clobber: bool,
+) -> Result<Vec<AssetDownloadResult>, AppError> {
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let release = client.get_release_by_tag(&owner, &repo, tag)?;
+
+    let assets: Vec<_> =
+        release.assets.iter().filter(|a| pattern.is_none_or(|p| glob_match(p, &a.name))).collect();
+    if assets.is_empty() {
+        return Err(AppError::NotFound(match pattern {
+            Some(p) => format!("no assets of release '{tag}' match '{p}'"),
+            None => format!("release '{tag}' has no assets"),
+        }));
+    }
+    fs::create_dir_all(dir)?;
+
+    let mut results = Vec::new();
+    for asset in assets {
+        let path = dir.join(&asset.name);
+        let mut result =
+            AssetDownloadResult { name: asset.name.clone(), path, skipped: false, error: None };
+        if result.path.exists() && !clobber {
+            result.skipped = true;
+        } else if let Err(e) = download_to(&client, &owner, &repo, asset.id, &result.path) {
+            result.error = Some(e.to_string());
+        }
+        results.push(result);
+    }
+    Ok(results)
+}
+
+fn download_to(
+    client: &impl ReposApi,
+    owner: &str,
+    repo: &str,
+    asset_id: u64,
+    path: &Path,
+) -> Result<(), AppError> {
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(".part");
+    let partial = PathBuf::from(partial);
+
+    let result = File::create(&partial).map_err(AppError::from).and_then(|file| {
+        let mut writer = BufWriter::new(file);
+        client.download_release_asset(owner, repo, asset_id, &mut writer)?;
+        writer.flush()?;
+        Ok(())
+    });
+    match result {
+        Ok(()) => Ok(fs::rename(&partial, path)?),
+        Err(e) => {
+            let _ = fs::remove_file(&partial);
+            Err(e)
+        }
+    }
+}
+
+/// Options for [`upload`].
+#[derive(Debug, Clone)]
+pub struct UploadOptions {
+    /// Replace assets that already exist with the same name.
+    pub clobber: bool,
+    /// Also upload a `SHA256SUMS` manifest for the files.
+    pub generate_checksums: bool,
+    /// Number of assets uploaded concurrently.
+    pub jobs: usize,
+    /// Retries per asset after a transient failure.
+    pub retries: u32,
+}
+
+/// Upload files as assets of the release tagged `tag`.
+///
+/// GitHub accepts each asset as a single request, so a failed upload is retried as a whole
+/// with exponential backoff; leftover partial assets are removed before each retry. Failures
+/// are recorded per asset instead of aborting the remaining uploads.
+pub fn upload(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    tag: &str,
+    files: &[PathBuf],
+    options: &UploadOptions,
+) -> Result<Vec<AssetUploadResult>, AppError> {
+    let assets = files.iter().map(|path| asset_name(path)).collect::<Result<Vec<_>, _>>()?;
+
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+    let (_account, token) = if options.clobber {
+        account::get_active_for_destructive(storage)?
+    } else {
+        account::get_active_with_token(storage)?
+    };
+    let client = GitHubClient::new(token)?;
+    let release = client.get_release_by_tag(&owner, &repo, tag)?;
+
+    let checksums = if options.generate_checksums {
+        let mut sums = Vec::new();
+        for (path, name) in files.iter().zip(&assets) {
+            sums.push((name.clone(), sha256_file(path)?));
+        }
+        Some(checksums_manifest(&sums))
+    } else {
+        None
+    };
+
+    let mut uploads: Vec<(String, UploadSource<'_>)> = files
+        .iter()
+        .zip(assets)
+        .map(|(path, name)| (name, UploadSource::File(path.as_path())))
+        .collect();
+    if let Some(manifest) = &checksums {
+        uploads.push((CHECKSUMS_ASSET.to_string(), UploadSource::Bytes(manifest.as_bytes())));
+    }
+
+    let uploader = Uploader { client: &client, owner: &owner, repo: &repo, release: &release };
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::new());
+
+    thread::scope(|scope| {
+        for _ in 0..options.jobs.clamp(1, uploads.len().max(1)) {
+            scope.spawn(|| {
+                while let Some((name, source)) = uploads.get(next.fetch_add(1, Ordering::SeqCst)) {
+                    let result = uploader.upload_with_retry(name, *source, options);
+                    results.lock().expect("upload results poisoned").push(result);
+                }
+            });
+        }
+    });
+
+    let mut results = results.into_inner().expect("upload results poisoned");
+    results.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(results)
+}
+
+struct Uploader<'a> {
+    client: &'a GitHubClient,
+    owner: &'a str,
+    repo: &'a str,
+    release: &'a Release,
+}
+
+impl Uploader<'_> {
+    fn upload_with_retry(
+        &self,
+        name: &str,
+        source: UploadSource<'_>,
+        options: &UploadOptions,
+    ) -> AssetUploadResult {
+        let mut result =
+            AssetUploadResult { name: name.to_string(), attempts: 0, url: None, error: None };
+
+        if let Some(existing) = self.release.assets.iter().find(|a| a.name == name) {
+            if !options.clobber {
+                result.error = Some("asset already exists (use --clobber to replace)".into());
+                return result;
+            }
+            if let Err(e) = self.client.delete_release_asset(self.owner, self.repo, existing.id) {
+                result.error = Some(format!("failed to delete existing asset: {e}"));
+                return result;
+            }
+        }
+
+        loop {
+            result.attempts += 1;
+            match self.client.upload_release_asset(&self.release.upload_url, name, source) {
+                Ok(asset) => {
+                    result.url = asset.browser_download_url;
+                    return result;
+                }
+                Err(e) if is_transient(&e) && result.attempts <= options.retries => {
+                    let delay = backoff_delay(result.attempts);
+                    esay!(
+                        "⚠️  {name}: attempt {} failed ({e}), retrying in {}s",
+                        result.attempts,
+                        delay.as_secs()
+                    );
+                    thread::sleep(delay);
+                    self.remove_partial(name);
+                }
+                Err(e) => {
+                    result.error = Some(e.to_string());
+                    return result;
+                }
+            }
+        }
+    }
+
+    /// Delete a half-uploaded asset left behind by a failed attempt, if any.
+    fn remove_partial(&self, name: &str) {
+        let Ok(release) =
+            self.client.get_release_by_tag(self.owner, self.repo, &self.release.tag_name)
+        else {
+            return;
+        };
+        if let Some(partial) = release.assets.iter().find(|a| a.name == name) {
+            let _ = self.client.delete_release_asset(self.owner, self.repo, partial.id);
+        }
+    }
+}
+
+fn asset_name(path: &Path) -> Result<String, AppError> {
+    if !path.is_file() {
+        return Err(AppError::invalid_input(format!("'{}' is not a file", path.display())));
+    }
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .map(str::to_string)
+        .ok_or_else(|| AppError::invalid_input(format!("invalid file name '{}'", path.display())))
+}
+
+/// Network failures, rate limits and 5xx responses are worth retrying.
+fn is_transient(err: &AppError) -> bool {
+    match err {
+        AppError::Network(_) | AppError::RateLimited(_) => true,
+        AppError::GitHubApi(msg) => msg.starts_with("API error 5"),
+        _ => false,
+    }
+}
+
+/// Delay before retry `attempt` (1-based): 2s, 4s, 8s, ... capped at a minute.
+fn backoff_delay(attempt: u32) -> Duration {
+    Duration::from_secs(2u64.saturating_pow(attempt).min(MAX_BACKOFF_SECS))
+}
+
+/// Hex-encoded SHA-256 digest of a file.
+pub fn sha256_file(path: &Path) -> Result<String, AppError> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut hasher = Sha256::new();
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hasher.finalize().iter().map(|b| format!("{b:02x}")).collect())
+}
+
+/// `sha256sum`-compatible manifest of `(name, digest)` pairs.
+fn checksums_manifest(sums: &[(String, String)]) -> String {
+    sums.iter().map(|(name, digest)| format!("{digest}  {name}\n")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn sha256_file_matches_known_digest() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("hello.txt");
+        std::fs::write(&path, "hello\n").unwrap();
+
+        assert_eq!(
+            sha256_file(&path).unwrap(),
+            "5891b5b522d5df086d0ff0b110fbd9d21bb4fc7163af34d08286a2e846f6be03"
+        );
+    }
+
+    #[test]
+    fn checksums_manifest_uses_sha256sum_format() {
+        let sums = vec![("a.tar.gz".to_string(), "ab12".to_string())];
+        assert_eq!(checksums_manifest(&sums), "ab12  a.tar.gz\n");
+    }
+
+    #[test]
+    fn backoff_delay_doubles_and_caps() {
+        assert_eq!(backoff_delay(1), Duration::from_secs(2));
+        assert_eq!(backoff_delay(3), Duration::from_secs(8));
+        assert_eq!(backoff_delay(10), Duration::from_secs(MAX_BACKOFF_SECS));
+    }
+
+    #[test]
+    fn is_transient_retries_server_and_network_errors() {
+        assert!(is_transient(&AppError::network("reset")));
+        assert!(is_transient(&AppError::github_api("API error 502 Bad Gateway: ")));
+        assert!(!is_transient(&AppError::github_api("API error 422 Unprocessable Entity: ")));
+        assert!(!is_transient(&AppError::NotFound("API error 404".into())));
+    }
+}
@@ -1,50 +1,170 @@
 //! Repository management commands.
 
-use crate::commands::account;
+use crate::commands::{account, conflicts, manifest};
+use crate::domain::{
+    GITHUB_HOST, RepoSpec, build_clone_url, clone_target_dir, moved_remote_url, repo_web_url,
+    shell_join,
+};
 use crate::error::AppError;
-use crate::github::GitHubClient;
-use crate::models::{Protocol, Repository};
+use crate::github::{GitHubClient, MAX_PAGE_SIZE, OrgsApi, ReposApi};
+use crate::logging;
+use crate::models::{
+    Account, AccountScoped, BulkClone, BulkCloneRepo, CloneFailure, CloneOptions, CloneReport,
+    Execution, ExecutionMode, ForeachResult, ProgressOutcome, Protocol, RepoFilter, RepoMove,
+    Repository, SearchSort,
+};
+use crate::output::style;
+use crate::platform;
+use crate::progress::Progress;
+use crate::remote;
 use crate::storage::Storage;
-use std::path::Path;
-use std::process::Command;
+use crate::{esay, say};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, mpsc};
+use std::thread;
+use std::time::{Duration, Instant};
 
-/// List repositories for the active account.
+/// List repositories for the active account that pass `filter`.
 pub fn list(
     storage: &impl Storage,
     org: Option<&str>,
+    filter: &RepoFilter,
     limit: usize,
 ) -> Result<Vec<Repository>, AppError> {
     let (account, token) = account::get_active_with_token(storage)?;
+    list_for_account(&account, token, org, filter, limit)
+}
+
+/// List repositories for every account in a group, tagged by account.
+pub fn list_group(
+    storage: &impl Storage,
+    group: &str,
+    org: Option<&str>,
+    filter: &RepoFilter,
+    limit: usize,
+) -> Result<Vec<AccountScoped<Repository>>, AppError> {
+    let mut results = Vec::new();
+    for (account, token) in account::get_group_with_tokens(storage, group)? {
+        let repos = list_for_account(&account, token, org, filter, limit)?;
+        results.extend(
+            repos.into_iter().map(|item| AccountScoped { account: account.id.clone(), item }),
+        );
+    }
+    Ok(results)
+}
+
+/// Repositories of `org` (the default org, or the account's own when `None`).
+///
+/// Without a filter one page of `limit` repositories is requested. Otherwise organization
+/// listings let the API apply what its `type` parameter covers, and full pages are fetched and
+/// filtered here until `limit` repositories match or the listing ends.
+fn list_for_account(
+    account: &Account,
+    token: String,
+    org: Option<&str>,
+    filter: &RepoFilter,
+    limit: usize,
+) -> Result<Vec<Repository>, AppError> {
     let client = GitHubClient::new(token)?;
+    let org = org.or(account.default_org.as_deref());
 
-    let repos = match org.or(account.default_org.as_deref()) {
-        Some(org) => client.list_org_repos(org, limit)?,
-        None => client.list_user_repos(&account.username, limit)?,
-    };
+    if *filter == RepoFilter::default() {
+        return match org {
+            Some(org) => client.list_org_repos(org, limit),
+            None => client.list_user_repos(&account.username, limit),
+        };
+    }
 
+    let mut repos = Vec::new();
+    for page in 1.. {
+        let batch = match org {
+            Some(org) => {
+                client.list_org_repos_page(org, filter.org_type_param(), MAX_PAGE_SIZE, page)?
+            }
+            None => client.list_user_repos_page(&account.username, MAX_PAGE_SIZE, page)?,
+        };
+        let done = batch.len() < MAX_PAGE_SIZE;
+        repos.extend(batch.into_iter().filter(|r| filter.matches(r)));
+        if done || repos.len() >= limit {
+            break;
+        }
+    }
+    repos.truncate(limit);
     Ok(repos)
 }
 
-/// Clone a repository.
-pub fn clone(storage: &impl Storage, repo_spec: &str) -> Result<(), AppError> {
-    let (account, _token) = account::get_active_with_token(storage)?;
+/// Search repositories, optionally scoped to an organization and language.
+pub fn search(
+    storage: &impl Storage,
+    query: &str,
+    org: Option<&str>,
+    language: Option<&str>,
+    sort: SearchSort,
+    limit: usize,
+) -> Result<Vec<Repository>, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
 
-    let (owner, repo) = parse_repo_spec(repo_spec)?;
-    let clone_url = build_clone_url(&owner, repo, account.protocol);
+    let query = build_search_query(query, org, language);
+    let results = client.search_repos(&query, sort, limit)?;
+    Ok(results.items)
+}
 
-    let target_dir = match &account.clone_dir {
-        Some(dir) => Path::new(dir).join(repo),
-        None => Path::new(repo).to_path_buf(),
-    };
+/// Let the user pick one repository from a list.
+#[cfg(feature = "interactive")]
+pub fn select_interactive(repos: Vec<Repository>) -> Result<Repository, AppError> {
+    if !atty::is(atty::Stream::Stdin) {
+        return Err(AppError::TtyRequired);
+    }
+    if repos.is_empty() {
+        return Err(AppError::invalid_input("no repositories to choose from"));
+    }
+
+    let options: Vec<String> = repos.iter().map(|r| r.full_name.clone()).collect();
+    let selection = inquire::Select::new("Select repository to clone:", options)
+        .raw_prompt()
+        .map_err(|e| AppError::config(format!("selection cancelled: {e}")))?;
+
+    Ok(repos.into_iter().nth(selection.index).expect("selection index is within options"))
+}
+
+/// Clone a repository, with `options` falling back to the account's clone defaults. `quiet`
+/// silences git's progress output.
+///
+/// A repository URL is cloned from its own host, anything else from the account's host.
+pub fn clone(
+    storage: &impl Storage,
+    repo_spec: &str,
+    options: CloneOptions,
+    quiet: bool,
+    mode: ExecutionMode,
+) -> Result<Execution<CloneReport>, AppError> {
+    let (account, _token) = account::get_active_with_token(storage)?;
+    let options = options.or(&account.clone_options);
+    account::validate_clone_options(&options)?;
+
+    let (host, owner, repo) = remote::resolve_repo_with_host(storage, Some(repo_spec))?;
+    let host = host.as_deref().or(account.host.as_deref()).unwrap_or(GITHUB_HOST);
+    let clone_url = build_clone_url(host, &owner, &repo, account.protocol);
+    let target_dir =
+        clone_target_dir(account.clone_dir.as_deref(), account.clone_layout, host, &owner, &repo);
 
     if target_dir.exists() {
         return Err(AppError::git(format!("directory '{}' already exists", target_dir.display())));
     }
+    if mode.is_dry_run() {
+        return Ok(Execution::Planned(vec![clone_step(&clone_url, &target_dir, &options)]));
+    }
 
-    let status = Command::new("git")
-        .arg("clone")
-        .arg(&clone_url)
-        .arg(&target_dir)
+    let mut command = Command::new("git");
+    command.arg("clone").args(options.args());
+    if quiet {
+        command.arg("--quiet");
+    }
+    let status = logging::command(command.arg(&clone_url).arg(&target_dir))
         .status()
         .map_err(|e| AppError::git(format!("failed to run git: {e}")))?;
 
@@ -52,94 +172,1107 @@ pub fn clone(storage: &impl Storage, repo_spec: &str) -> Result<(), AppError> {
         return Err(AppError::git(format!("git clone failed with status {status}")));
     }
 
-    Ok(())
+    Ok(Execution::Done(CloneReport {
+        cloned: vec![format!("{owner}/{repo}")],
+        dir: Some(target_dir),
+        ..Default::default()
+    }))
+}
+
+/// Web URL of a repository, detected from the `origin` remote when `repo_spec` is omitted.
+///
+/// Detection keeps the remote's host, so Enterprise repositories open on their own server.
+pub fn web_url(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    branch: Option<&str>,
+    path: Option<&str>,
+) -> Result<String, AppError> {
+    let (host, owner, repo) = match repo_spec {
+        Some(spec) => {
+            let (host, owner, repo) = remote::resolve_repo_with_host(storage, Some(spec))?;
+            (host.unwrap_or_else(|| GITHUB_HOST.to_string()), owner, repo)
+        }
+        None => remote::detect_remote_any_host()?,
+    };
+    Ok(repo_web_url(&host, &owner, &repo, branch, path))
+}
+
+/// Delete a repository permanently.
+///
+/// A dry run checks that the repository exists and reports the call instead.
+pub fn delete(
+    storage: &impl Storage,
+    repo_spec: &str,
+    mode: ExecutionMode,
+) -> Result<Execution<()>, AppError> {
+    let (owner, repo) =
+        remote::parse_spec(storage, repo_spec)?.resolve(|| remote::default_owner(storage))?;
+    if mode.is_dry_run() {
+        let (_account, token) = account::get_active_with_token(storage)?;
+        let found = GitHubClient::new(token)?.get_repo(&owner, &repo)?;
+        return Ok(Execution::Planned(vec![
+            format!("delete {} ({})", found.full_name, found.html_url),
+            format!("DELETE /repos/{owner}/{repo}"),
+        ]));
+    }
+    let (_account, token) = account::get_active_for_destructive(storage)?;
+    GitHubClient::new(token)?.delete_repo(&owner, &repo)?;
+    Ok(Execution::Done(()))
+}
+
+/// Archive (or unarchive) a repository, detected from git when `repo_spec` is omitted.
+pub fn set_archived(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    archived: bool,
+    mode: ExecutionMode,
+) -> Result<Execution<Repository>, AppError> {
+    let (owner, repo) = remote::resolve_repo(storage, repo_spec)?;
+    if mode.is_dry_run() {
+        let (_account, token) = account::get_active_with_token(storage)?;
+        let found = GitHubClient::new(token)?.get_repo(&owner, &repo)?;
+        let verb = if archived { "archive" } else { "unarchive" };
+        let state = if found.archived { "archived" } else { "not archived" };
+        return Ok(Execution::Planned(vec![
+            format!("{verb} {} (currently {state})", found.full_name),
+            format!("PATCH /repos/{owner}/{repo} (archived: {archived})"),
+        ]));
+    }
+    let (_account, token) = account::get_active_for_destructive(storage)?;
+    GitHubClient::new(token)?.set_repo_archived(&owner, &repo, archived).map(Execution::Done)
+}
+
+/// Rename a repository, then point the `origin` of its clone in the account's clone directory,
+/// when there is one, at the new name.
+pub fn rename(
+    storage: &impl Storage,
+    repo_spec: &str,
+    new_name: &str,
+    mode: ExecutionMode,
+) -> Result<Execution<RepoMove>, AppError> {
+    let RepoSpec::Name(new_name) = RepoSpec::parse(new_name)? else {
+        return Err(AppError::invalid_input(format!(
+            "invalid repository name '{new_name}'; use `repo transfer` to change the owner"
+        )));
+    };
+    let (owner, repo) =
+        remote::parse_spec(storage, repo_spec)?.resolve(|| remote::default_owner(storage))?;
+    let (account, token) = move_account(storage, mode)?;
+    let clone = find_clone(&account, &owner, &repo, &format!("{owner}/{new_name}"));
+    if mode.is_dry_run() {
+        let step = format!("PATCH /repos/{owner}/{repo} (name: {new_name})");
+        return Ok(Execution::Planned(move_plan(step, clone.as_ref())));
+    }
+
+    let renamed = GitHubClient::new(token)?.rename_repo(&owner, &repo, &new_name)?;
+    Ok(Execution::Done(RepoMove {
+        from: format!("{owner}/{repo}"),
+        to: renamed.full_name,
+        html_url: renamed.html_url,
+        pending: false,
+        clone: repoint_clone(clone),
+    }))
+}
+
+/// Transfer a repository to another user or organization, giving the organization's `teams`
+/// (by slug) access to it, then point the `origin` of its clone in the account's clone
+/// directory, when there is one, at the new owner.
+///
+/// A transfer to a user completes once they accept it, so until GitHub reports the repository
+/// under the new owner the move is `pending` and the clone is left alone.
+pub fn transfer(
+    storage: &impl Storage,
+    repo_spec: &str,
+    new_owner: &str,
+    teams: &[String],
+    mode: ExecutionMode,
+) -> Result<Execution<RepoMove>, AppError> {
+    let (owner, repo) =
+        remote::parse_spec(storage, repo_spec)?.resolve(|| remote::default_owner(storage))?;
+    let (account, token) = move_account(storage, mode)?;
+    let client = GitHubClient::new(token)?;
+    let team_ids = teams
+        .iter()
+        .map(|slug| client.get_team(new_owner, slug).map(|team| team.id))
+        .collect::<Result<Vec<u64>, AppError>>()?;
+    let to = format!("{new_owner}/{repo}");
+    let clone = find_clone(&account, &owner, &repo, &to);
+    if mode.is_dry_run() {
+        let teams =
+            if teams.is_empty() { String::new() } else { format!(", teams: {}", teams.join(", ")) };
+        let step = format!("POST /repos/{owner}/{repo}/transfer (new_owner: {new_owner}{teams})");
+        return Ok(Execution::Planned(move_plan(step, clone.as_ref())));
+    }
+
+    let moved = client.transfer_repo(&owner, &repo, new_owner, &team_ids)?;
+    let pending = !moved.full_name.eq_ignore_ascii_case(&to);
+    Ok(Execution::Done(RepoMove {
+        from: format!("{owner}/{repo}"),
+        to,
+        html_url: moved.html_url,
+        pending,
+        clone: if pending { None } else { repoint_clone(clone) },
+    }))
+}
+
+/// The active account with its token, confirmed for destructive use unless in a dry run.
+fn move_account(
+    storage: &impl Storage,
+    mode: ExecutionMode,
+) -> Result<(Account, String), AppError> {
+    if mode.is_dry_run() {
+        account::get_active_with_token(storage)
+    } else {
+        account::get_active_for_destructive(storage)
+    }
+}
+
+/// The clone of `owner/repo` in the account's clone directory, with its `origin` URL moved to
+/// `to`; `None` without a clone directory, a clone there, or an origin of that repository.
+fn find_clone(account: &Account, owner: &str, repo: &str, to: &str) -> Option<(PathBuf, String)> {
+    account.clone_dir.as_ref()?;
+    let host = account.host.as_deref().unwrap_or(GITHUB_HOST);
+    let dir =
+        clone_target_dir(account.clone_dir.as_deref(), account.clone_layout, host, owner, repo);
+    if !dir.join(".git").exists() {
+        return None;
+    }
+    let url = conflicts::git(Some(&dir), &["remote", "get-url", "origin"]).ok()?;
+    let moved = moved_remote_url(&url, &format!("{owner}/{repo}"), to)?;
+    Some((dir, moved))
+}
+
+fn move_plan(step: String, clone: Option<&(PathBuf, String)>) -> Vec<String> {
+    let mut steps = vec![step];
+    if let Some((dir, url)) = clone {
+        steps.push(format!("git -C {} remote set-url origin {url}", dir.display()));
+    }
+    steps
+}
+
+/// Point the clone's `origin` at its moved URL; the repository has moved already, so a failure
+/// is only warned about.
+fn repoint_clone(clone: Option<(PathBuf, String)>) -> Option<PathBuf> {
+    let (dir, url) = clone?;
+    match conflicts::git(Some(&dir), &["remote", "set-url", "origin", &url]) {
+        Ok(_) => Some(dir),
+        Err(e) => {
+            esay!("⚠️  Could not update the origin of {}: {e}", dir.display());
+            None
+        }
+    }
+}
+
+/// Star (or unstar) a repository, detected from git when `repo_spec` is omitted.
+///
+/// Returns the repository as `owner/repo`.
+pub fn set_starred(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    starred: bool,
+) -> Result<String, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = remote::resolve_repo(storage, repo_spec)?;
+    client.set_repo_starred(&owner, &repo, starred)?;
+    Ok(format!("{owner}/{repo}"))
+}
+
+/// Watch (or stop watching) all activity of a repository.
+///
+/// Returns the repository as `owner/repo`.
+pub fn set_watched(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    watched: bool,
+) -> Result<String, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = remote::resolve_repo(storage, repo_spec)?;
+    client.set_repo_watched(&owner, &repo, watched)?;
+    Ok(format!("{owner}/{repo}"))
+}
+
+/// The active account's starred repositories that pass `filter`, most recently starred first.
+///
+/// Full pages are fetched and filtered until `limit` repositories match or the list ends.
+pub fn starred(
+    storage: &impl Storage,
+    filter: &RepoFilter,
+    limit: usize,
+) -> Result<Vec<Repository>, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+
+    let mut repos = Vec::new();
+    for page in 1.. {
+        let batch = client.list_starred_repos_page(MAX_PAGE_SIZE, page)?;
+        let done = batch.len() < MAX_PAGE_SIZE;
+        repos.extend(batch.into_iter().filter(|r| filter.matches(r)));
+        if done || repos.len() >= limit {
+            break;
+        }
+    }
+    repos.truncate(limit);
+    Ok(repos)
+}
+
+/// Most topics GitHub allows on one repository.
+const MAX_TOPICS: usize = 20;
+
+/// How [`update_topics`] changes a repository's topics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TopicsChange {
+    /// Replace all topics; no topics clears them.
+    Set,
+    /// Add topics the repository does not have yet.
+    Add,
+    /// Remove topics, ignoring ones the repository does not have.
+    Remove,
+}
+
+/// Change the topics of a repository and return the topics it ends up with.
+///
+/// Topics are lowercased the way GitHub stores them. Adding or removing reads the current
+/// topics first and skips the update when nothing changes.
+pub fn update_topics(
+    storage: &impl Storage,
+    repo_spec: &str,
+    change: TopicsChange,
+    topics: &[String],
+) -> Result<Vec<String>, AppError> {
+    let topics = topics.iter().map(|t| validate_topic(t)).collect::<Result<Vec<_>, _>>()?;
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = remote::resolve_repo(storage, Some(repo_spec))?;
+
+    let updated = match change {
+        TopicsChange::Set => unique(topics),
+        TopicsChange::Add | TopicsChange::Remove => {
+            let current = client.get_repo_topics(&owner, &repo)?;
+            let updated = if change == TopicsChange::Add {
+                unique(current.iter().cloned().chain(topics))
+            } else {
+                current.iter().filter(|t| !topics.contains(t)).cloned().collect()
+            };
+            if updated == current {
+                return Ok(current);
+            }
+            updated
+        }
+    };
+    if updated.len() > MAX_TOPICS {
+        return Err(AppError::invalid_input(format!(
+            "{owner}/{repo} would have {} topics; GitHub allows at most {MAX_TOPICS}",
+            updated.len()
+        )));
+    }
+    client.replace_repo_topics(&owner, &repo, &updated)
+}
+
+/// `topics` without repeats, in first-seen order.
+fn unique(topics: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut unique = Vec::new();
+    for topic in topics {
+        if !unique.contains(&topic) {
+            unique.push(topic);
+        }
+    }
+    unique
+}
+
+/// Check a topic against GitHub's rules and return it lowercased.
+fn validate_topic(topic: &str) -> Result<String, AppError> {
+    let lower = topic.to_ascii_lowercase();
+    let valid = lower.len() <= 50
+        && lower.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        && lower.chars().next().is_some_and(|c| c != '-');
+    if !valid {
+        return Err(AppError::invalid_input(format!(
+            "invalid topic '{topic}': use up to 50 letters, digits and hyphens, not starting \
+             with a hyphen"
+        )));
+    }
+    Ok(lower)
+}
+
+/// Ask the user to type the repository's name before it is deleted.
+#[cfg(feature = "interactive")]
+pub fn confirm_delete(repo_spec: &str) -> Result<bool, AppError> {
+    require_confirmation_tty("delete", repo_spec)?;
+    let typed = inquire::Text::new(&format!("Type '{repo_spec}' to delete it permanently:"))
+        .prompt()
+        .map_err(|e| AppError::config(format!("confirmation cancelled: {e}")))?;
+    Ok(typed.trim().eq_ignore_ascii_case(repo_spec))
+}
+
+/// Ask the user to confirm archiving a repository.
+#[cfg(feature = "interactive")]
+pub fn confirm_archive(repo_spec: &str) -> Result<bool, AppError> {
+    require_confirmation_tty("archive", repo_spec)?;
+    inquire::Confirm::new(&format!("Archive '{repo_spec}'? It becomes read-only."))
+        .with_default(false)
+        .prompt()
+        .map_err(|e| AppError::config(format!("confirmation cancelled: {e}")))
 }
 
-/// Bulk clone repositories from an organization.
-pub fn clone_org(storage: &impl Storage, org: &str, limit: usize) -> Result<Vec<String>, AppError> {
+/// Ask the user to confirm renaming or transferring `from` to `to`.
+#[cfg(feature = "interactive")]
+pub fn confirm_move(from: &str, to: &str) -> Result<bool, AppError> {
+    require_confirmation_tty("move", from)?;
+    inquire::Confirm::new(&format!("Move '{from}' to '{to}'? GitHub redirects the old name."))
+        .with_default(false)
+        .prompt()
+        .map_err(|e| AppError::config(format!("confirmation cancelled: {e}")))
+}
+
+#[cfg(feature = "interactive")]
+fn require_confirmation_tty(verb: &str, repo_spec: &str) -> Result<(), AppError> {
+    if atty::is(atty::Stream::Stdin) {
+        return Ok(());
+    }
+    Err(AppError::invalid_input(format!(
+        "refusing to {verb} '{repo_spec}' without confirmation, pass --yes to skip the prompt"
+    )))
+}
+
+/// Operation name of `repo clone --org` progress events.
+const CLONE_OPERATION: &str = "repo clone";
+
+/// Bulk clone repositories from an organization, running up to `jobs` clones at once.
+///
+/// git's output is captured per repository. Unless `quiet`, a line with the count done and the
+/// time left is printed to stderr as each clone finishes, and failed clones show git's reason.
+/// Afterwards every checkout of the listed repositories is recorded in a clone manifest at
+/// `manifest` (by default `gho-clones.json` in the account's clone directory), whose path is
+/// reported with the cloned, skipped and failed repositories. In a dry run the organization is
+/// still listed, but only the clones are reported. `options` fall back to the account's clone
+/// defaults.
+#[allow(clippy::too_many_arguments)]
+pub fn clone_org(
+    storage: &impl Storage,
+    org: &str,
+    limit: usize,
+    jobs: usize,
+    manifest_path: Option<&Path>,
+    options: CloneOptions,
+    quiet: bool,
+    mode: ExecutionMode,
+    progress: Progress,
+) -> Result<Execution<CloneReport>, AppError> {
     let (account, token) = account::get_active_with_token(storage)?;
+    let options = options.or(&account.clone_options);
+    account::validate_clone_options(&options)?;
     let client = GitHubClient::new(token)?;
+    let manifest_path = manifest_path.map(Path::to_path_buf).unwrap_or_else(|| {
+        Path::new(account.clone_dir.as_deref().unwrap_or("")).join(manifest::MANIFEST_FILE)
+    });
 
     let repos = client.list_org_repos(org, limit)?;
-    let mut cloned = Vec::new();
+    if mode.is_dry_run() {
+        let mut steps: Vec<String> = repos
+            .iter()
+            .map(|repo| {
+                let (clone_url, target_dir) = clone_org_target(&account, repo);
+                if target_dir.exists() {
+                    format!("skip {} ({} already exists)", repo.name, target_dir.display())
+                } else {
+                    clone_step(clone_url, &target_dir, &options)
+                }
+            })
+            .collect();
+        steps.push(format!("write clone manifest {}", manifest_path.display()));
+        return Ok(Execution::Planned(steps));
+    }
+
+    let plan = BulkClone {
+        org: org.to_string(),
+        account: account.id.clone(),
+        manifest: manifest_path,
+        options,
+        repos: repos
+            .iter()
+            .map(|repo| {
+                let (url, dir) = clone_org_target(&account, repo);
+                BulkCloneRepo {
+                    full_name: repo.full_name.clone(),
+                    name: repo.name.clone(),
+                    url: url.to_string(),
+                    dir,
+                    outcome: None,
+                    error: None,
+                }
+            })
+            .collect(),
+    };
+    run_bulk_clone(storage, plan, jobs, quiet, progress).map(Execution::Done)
+}
 
-    for repo in repos {
-        let clone_url = match account.protocol {
-            Protocol::Ssh => &repo.ssh_url,
-            Protocol::Https => &repo.clone_url,
+/// Continue the `repo clone --org` recorded in state, cloning the repositories it has not
+/// reached yet and retrying the failed ones.
+///
+/// The recorded account, clone options and manifest path are used, and the organization is not
+/// listed again, so a run stopped by the rate limit picks up without API calls.
+pub fn resume_clone_org(
+    storage: &impl Storage,
+    jobs: usize,
+    quiet: bool,
+    mode: ExecutionMode,
+    progress: Progress,
+) -> Result<Execution<CloneReport>, AppError> {
+    let plan = storage.load_state()?.bulk_clone.ok_or_else(|| {
+        AppError::invalid_input("there is no interrupted `repo clone --org` to resume")
+    })?;
+    let active = account::show(storage)?;
+    if active.id != plan.account {
+        return Err(AppError::invalid_input(format!(
+            "the clone of '{}' was started with account '{}', switch with `gho account use {}`",
+            plan.org, plan.account, plan.account
+        )));
+    }
+    if mode.is_dry_run() {
+        let mut steps: Vec<String> = (plan.repos.iter())
+            .filter(|repo| !repo.is_done())
+            .map(|repo| clone_step(&repo.url, &repo.dir, &plan.options))
+            .collect();
+        steps.push(format!("write clone manifest {}", plan.manifest.display()));
+        return Ok(Execution::Planned(steps));
+    }
+    run_bulk_clone(storage, plan, jobs, quiet, progress).map(Execution::Done)
+}
+
+/// Clone the repositories of `plan` that are not done, then write its clone manifest.
+///
+/// The plan is saved to state before the first clone and after each one, so a run that dies
+/// can be resumed; it is dropped once every repository is cloned or skipped.
+fn run_bulk_clone(
+    storage: &impl Storage,
+    mut plan: BulkClone,
+    jobs: usize,
+    quiet: bool,
+    progress: Progress,
+) -> Result<CloneReport, AppError> {
+    save_bulk_clone(storage, Some(&plan))?;
+    let todo: Vec<(usize, BulkCloneRepo)> =
+        plan.repos.iter().cloned().enumerate().filter(|(_, repo)| !repo.is_done()).collect();
+    let options = plan.options.clone();
+    let next = AtomicUsize::new(0);
+    let counter = CloneCounter {
+        total: todo.len(),
+        done: AtomicUsize::new(0),
+        started: Instant::now(),
+        quiet: quiet || progress.is_json(),
+    };
+    let mut report = CloneReport::default();
+
+    thread::scope(|scope| -> Result<(), AppError> {
+        let (sender, receiver) = mpsc::channel();
+        let (todo, next, counter, options) = (&todo, &next, &counter, &options);
+        for _ in 0..jobs.clamp(1, todo.len().max(1)) {
+            let sender = sender.clone();
+            scope.spawn(move || {
+                loop {
+                    let index = next.fetch_add(1, Ordering::SeqCst);
+                    let Some((slot, repo)) = todo.get(index) else {
+                        break;
+                    };
+                    progress.started(CLONE_OPERATION, &repo.full_name, index + 1, todo.len());
+                    let outcome = clone_quietly(&repo.url, &repo.dir, options);
+                    let error = counter.finished(&repo.name, &outcome);
+                    let outcome = outcome.unwrap_or(ProgressOutcome::Failed);
+                    progress.completed(CLONE_OPERATION, &repo.full_name, outcome, error.as_deref());
+                    if sender.send((*slot, outcome, error)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        // Only this thread touches state, so workers never race on the file.
+        for (slot, outcome, error) in receiver {
+            let repo = &mut plan.repos[slot];
+            match outcome {
+                ProgressOutcome::Succeeded => report.cloned.push(repo.name.clone()),
+                ProgressOutcome::Skipped => report.skipped.push(repo.name.clone()),
+                ProgressOutcome::Failed => report.failed.push(CloneFailure {
+                    repo: repo.name.clone(),
+                    error: error.clone().unwrap_or_default(),
+                }),
+            }
+            repo.outcome = Some(outcome);
+            repo.error = error;
+            save_bulk_clone(storage, Some(&plan))?;
+        }
+        Ok(())
+    })?;
+
+    report.cloned.sort();
+    report.skipped.sort();
+    report.failed.sort_by(|a, b| a.repo.cmp(&b.repo));
+    let checkouts: Vec<(String, PathBuf)> =
+        plan.repos.iter().map(|repo| (repo.full_name.clone(), repo.dir.clone())).collect();
+    manifest::write(&plan.manifest, &plan.account, &checkouts)?;
+    let finished = plan.repos.iter().all(BulkCloneRepo::is_done);
+    save_bulk_clone(storage, (!finished).then_some(&plan))?;
+    report.manifest = Some(plan.manifest);
+    Ok(report)
+}
+
+fn save_bulk_clone(storage: &impl Storage, plan: Option<&BulkClone>) -> Result<(), AppError> {
+    let mut state = storage.load_state()?;
+    state.bulk_clone = plan.cloned();
+    storage.save_state(&state)
+}
+
+/// Counts the finished clones of one `clone_org` and prints a line for each.
+struct CloneCounter {
+    total: usize,
+    done: AtomicUsize,
+    started: Instant,
+    quiet: bool,
+}
+
+impl CloneCounter {
+    /// Count a finished clone and, unless quiet, print it with the estimated time left.
+    /// Returns the reason of a skip or failure.
+    fn finished(&self, name: &str, outcome: &Result<ProgressOutcome, AppError>) -> Option<String> {
+        let done = self.done.fetch_add(1, Ordering::SeqCst) + 1;
+        let (line, error) = match outcome {
+            Ok(ProgressOutcome::Skipped) => {
+                (format!("⏭️  {} (already exists)", name), Some("already exists".to_string()))
+            }
+            Ok(_) => (format!("✅ {}", name), None),
+            Err(e) => {
+                let error = match e {
+                    AppError::Git(message) => message.clone(),
+                    other => other.to_string(),
+                };
+                (format!("❌ {}: {error}", name), Some(error))
+            }
         };
+        if !self.quiet {
+            let left = self.total - done;
+            let eta = if left == 0 {
+                String::new()
+            } else {
+                let per_clone = self.started.elapsed() / done as u32;
+                format!(", about {} left", format_eta(per_clone * left as u32))
+            };
+            esay!("{line} ({done}/{}{eta})", self.total);
+        }
+        error
+    }
+}
+
+/// A remaining time such as `42s` or `3m05s`.
+fn format_eta(left: Duration) -> String {
+    let secs = left.as_secs();
+    if secs < 60 { format!("{secs}s") } else { format!("{}m{:02}s", secs / 60, secs % 60) }
+}
+
+/// Operation name of `repo foreach` progress events.
+const FOREACH_OPERATION: &str = "repo foreach";
+
+/// Run `command` in the clone of every repository of an organization (the default org, or the
+/// account's own repositories, when `org` is `None`), up to `jobs` at once.
+///
+/// Missing clones are cloned first. With `clone` false nothing is cloned and the command runs in
+/// the current directory, for commands that only need the repository name, e.g.
+/// `gho pr list -R "$GHO_REPO"`. A single argument is run with `sh -c`, several as a program and
+/// its arguments. Each run gets `GHO_REPO`, `GHO_OWNER`, `GHO_REPO_NAME`, `GHO_REPO_DIR` and
+/// `GHO_ACCOUNT` in its environment. Output is printed per repository once its run finishes
+/// when more than one runs at a time.
+#[allow(clippy::too_many_arguments)]
+pub fn foreach(
+    storage: &impl Storage,
+    org: Option<&str>,
+    limit: usize,
+    jobs: usize,
+    command: &[String],
+    clone: bool,
+    mode: ExecutionMode,
+    progress: Progress,
+) -> Result<Execution<Vec<ForeachResult>>, AppError> {
+    if command.is_empty() {
+        return Err(AppError::invalid_input("a command to run is required after --"));
+    }
+    let (account, token) = account::get_active_with_token(storage)?;
+    let repos = list_for_account(&account, token, org, &RepoFilter::default(), limit)?;
+    let cwd = std::env::current_dir()?;
+
+    if mode.is_dry_run() {
+        let mut steps = Vec::new();
+        for repo in &repos {
+            let (clone_url, clone_dir) = clone_org_target(&account, repo);
+            if clone && !clone_dir.exists() {
+                steps.push(clone_step(clone_url, &clone_dir, &account.clone_options));
+            }
+            let dir = if clone { &clone_dir } else { &cwd };
+            steps.push(format!("run `{}` in {}", shell_join(command), dir.display()));
+        }
+        return Ok(Execution::Planned(steps));
+    }
+
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::new());
+    let capture = jobs > 1;
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, repos.len().max(1)) {
+            scope.spawn(|| {
+                loop {
+                    let index = next.fetch_add(1, Ordering::SeqCst);
+                    let Some(repo) = repos.get(index) else {
+                        break;
+                    };
+                    progress.started(FOREACH_OPERATION, &repo.full_name, index + 1, repos.len());
+                    let run = ForeachRun { account: &account, command, clone, capture, progress };
+                    let result = run.repo(repo, &cwd);
+                    let outcome = match result.error {
+                        Some(_) => ProgressOutcome::Failed,
+                        None => ProgressOutcome::Succeeded,
+                    };
+                    progress.completed(
+                        FOREACH_OPERATION,
+                        &repo.full_name,
+                        outcome,
+                        result.error.as_deref(),
+                    );
+                    results.lock().expect("foreach results poisoned").push(result);
+                }
+            });
+        }
+    });
+
+    let mut results = results.into_inner().expect("foreach results poisoned");
+    results.sort_by(|a, b| a.repo.cmp(&b.repo));
+    Ok(Execution::Done(results))
+}
+
+/// Settings shared by the runs of one `foreach`.
+struct ForeachRun<'a> {
+    account: &'a Account,
+    command: &'a [String],
+    clone: bool,
+    capture: bool,
+    progress: Progress,
+}
 
-        let target_dir = match &account.clone_dir {
-            Some(dir) => Path::new(dir).join(&repo.name),
-            None => Path::new(&repo.name).to_path_buf(),
+impl ForeachRun<'_> {
+    fn repo(&self, repo: &Repository, cwd: &Path) -> ForeachResult {
+        let (clone_url, clone_dir) = clone_org_target(self.account, repo);
+        let mut result = ForeachResult {
+            repo: repo.full_name.clone(),
+            dir: if self.clone { clone_dir.clone() } else { cwd.to_path_buf() },
+            cloned: false,
+            error: None,
         };
 
-        if target_dir.exists() {
-            eprintln!("⏭️  Skipping {} (already exists)", repo.name);
-            continue;
+        if self.clone && !clone_dir.exists() {
+            self.progress.updated(FOREACH_OPERATION, &repo.full_name, "cloning");
+            match clone_quietly(clone_url, &clone_dir, &self.account.clone_options) {
+                Ok(_) => result.cloned = true,
+                Err(e) => {
+                    result.error = Some(e.to_string());
+                    return result;
+                }
+            }
         }
 
-        let status = Command::new("git")
-            .arg("clone")
-            .arg(clone_url)
-            .arg(&target_dir)
-            .status()
-            .map_err(|e| AppError::git(format!("failed to run git: {e}")))?;
+        self.progress.updated(FOREACH_OPERATION, &repo.full_name, "running");
+        let mut command = match self.command {
+            [script] => platform::shell(script),
+            [program, args @ ..] => {
+                let mut command = Command::new(program);
+                command.args(args);
+                command
+            }
+            [] => unreachable!("checked in foreach"),
+        };
+        command
+            .current_dir(&result.dir)
+            .env("GHO_REPO", &repo.full_name)
+            .env("GHO_OWNER", &repo.owner.login)
+            .env("GHO_REPO_NAME", &repo.name)
+            .env("GHO_ACCOUNT", &self.account.id);
+        if clone_dir.exists() {
+            command.env("GHO_REPO_DIR", &clone_dir);
+        }
 
-        if status.success() {
-            cloned.push(repo.name);
+        let status = if self.capture {
+            command.output().map(|output| {
+                print_output(&repo.full_name, &output.stdout, &output.stderr);
+                output.status
+            })
         } else {
-            eprintln!("⚠️  Failed to clone {}", repo.name);
+            say!("▶ {}", repo.full_name);
+            command.status()
+        };
+        match status {
+            Ok(status) if status.success() => {}
+            Ok(status) => result.error = Some(format!("command failed with {status}")),
+            Err(e) => result.error = Some(format!("failed to run {}: {e}", self.command[0])),
         }
+        result
     }
+}
 
-    Ok(cloned)
+/// Print a run's captured output in one piece, so parallel runs never interleave.
+fn print_output(repo: &str, stdout: &[u8], stderr: &[u8]) {
+    let mut out = std::io::stdout().lock();
+    let _ = writeln!(out, "{}", style::line(&format!("▶ {repo}")));
+    let _ = out.write_all(stdout);
+    let _ = out.flush();
+    let _ = std::io::stderr().write_all(stderr);
 }
 
-fn parse_repo_spec(spec: &str) -> Result<(String, &str), AppError> {
-    let parts: Vec<&str> = spec.split('/').collect();
-    if parts.len() != 2 || parts[0].is_empty() || parts[1].is_empty() {
-        return Err(AppError::invalid_input(format!(
-            "invalid repository format '{}', expected owner/repo",
-            spec
-        )));
+/// Clone URL and target directory of an organization repository.
+fn clone_org_target<'a>(account: &Account, repo: &'a Repository) -> (&'a str, PathBuf) {
+    let clone_url = match account.protocol {
+        Protocol::Ssh => &repo.ssh_url,
+        Protocol::Https => &repo.clone_url,
+    };
+    let target_dir = clone_target_dir(
+        account.clone_dir.as_deref(),
+        account.clone_layout,
+        account.host.as_deref().unwrap_or(GITHUB_HOST),
+        &repo.owner.login,
+        &repo.name,
+    );
+    (clone_url, target_dir)
+}
+
+fn clone_step(clone_url: &str, target_dir: &Path, options: &CloneOptions) -> String {
+    let mut args = options.args();
+    args.push(clone_url.to_string());
+    format!("git clone {} {}", args.join(" "), target_dir.display())
+}
+
+/// Clone `clone_url` into `target_dir`, skipping it when the directory exists. git's output is
+/// captured, and a failure carries the last line git printed.
+fn clone_quietly(
+    clone_url: &str,
+    target_dir: &Path,
+    options: &CloneOptions,
+) -> Result<ProgressOutcome, AppError> {
+    if target_dir.exists() {
+        return Ok(ProgressOutcome::Skipped);
+    }
+
+    let mut command = Command::new("git");
+    command.arg("clone").args(options.args());
+    let output = logging::command(command.arg(clone_url).arg(target_dir))
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| AppError::git(format!("failed to run git: {e}")))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(AppError::git(match stderr.lines().map(str::trim).rfind(|l| !l.is_empty()) {
+            Some(reason) => reason.to_string(),
+            None => format!("git clone failed with status {}", output.status),
+        }));
     }
-    Ok((parts[0].to_string(), parts[1]))
+    Ok(ProgressOutcome::Succeeded)
 }
 
-fn build_clone_url(owner: &str, repo: &str, protocol: Protocol) -> String {
-    match protocol {
-        Protocol::Ssh => format!("git@github.com:{}/{}.git", owner, repo),
-        Protocol::Https => format!("https://github.com/{}/{}.git", owner, repo),
+fn build_search_query(query: &str, org: Option<&str>, language: Option<&str>) -> String {
+    let mut parts = vec![query.trim().to_string()];
+    if let Some(org) = org {
+        parts.push(format!("org:{org}"));
     }
+    if let Some(language) = language {
+        parts.push(format!("language:{language}"));
+    }
+    parts.retain(|p| !p.is_empty());
+    parts.join(" ")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::mock_api;
+    use crate::models::CloneLayout;
+    use std::fs;
+
+    #[test]
+    fn build_search_query_adds_qualifiers() {
+        let query = build_search_query("cli tool", Some("acme"), Some("rust"));
+        assert_eq!(query, "cli tool org:acme language:rust");
+    }
+
+    #[test]
+    fn format_eta_uses_minutes_past_a_minute() {
+        assert_eq!(format_eta(Duration::from_millis(42_900)), "42s");
+        assert_eq!(format_eta(Duration::from_secs(185)), "3m05s");
+    }
+
+    #[test]
+    fn build_search_query_without_qualifiers() {
+        assert_eq!(build_search_query(" gho ", None, None), "gho");
+    }
+
+    fn repository(owner: &str, name: &str, language: &str) -> serde_json::Value {
+        serde_json::json!({
+            "name": name,
+            "full_name": format!("{owner}/{name}"),
+            "html_url": format!("https://github.com/{owner}/{name}"),
+            "ssh_url": format!("git@github.com:{owner}/{name}.git"),
+            "clone_url": format!("https://github.com/{owner}/{name}.git"),
+            "owner": {"login": owner},
+            "language": language,
+        })
+    }
+
+    #[test]
+    fn list_filters_full_pages_of_org_repos() {
+        let api = mock_api::server();
+        let owner = "mock-repo-list";
+        let repos =
+            serde_json::json!([repository(owner, "api", "Rust"), repository(owner, "web", "Go")]);
+        api.route("GET", &format!("/orgs/{owner}/repos"), 200, repos);
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = mock_api::storage(dir.path());
+
+        let filter = RepoFilter { language: Some("rust".into()), ..Default::default() };
+        let found = list(&storage, Some(owner), &filter, 10).unwrap();
+        assert_eq!(found.iter().map(|r| r.name.as_str()).collect::<Vec<_>>(), ["api"]);
+
+        let requests = api.requests(&format!("/orgs/{owner}/"));
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].path.ends_with("&per_page=100&page=1"), "{}", requests[0].path);
+    }
+
+    #[test]
+    fn set_archived_patches_the_repository() {
+        let api = mock_api::server();
+        let owner = "mock-repo-archive";
+        let mut archived = repository(owner, "legacy", "Rust");
+        archived["archived"] = true.into();
+        api.route("PATCH", &format!("/repos/{owner}/legacy"), 200, archived);
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = mock_api::storage(dir.path());
+
+        let spec = format!("{owner}/legacy");
+        let Execution::Done(repo) =
+            set_archived(&storage, Some(&spec), true, ExecutionMode::Live).unwrap()
+        else {
+            panic!("expected a live run");
+        };
+        assert!(repo.archived);
+        let requests = api.requests(&format!("/repos/{owner}/"));
+        assert_eq!(requests[0].method, "PATCH");
+        assert_eq!(requests[0].body, r#"{"archived":true}"#);
+    }
 
     #[test]
-    fn parse_repo_spec_valid() {
-        let (owner, repo) = parse_repo_spec("octocat/hello-world").unwrap();
-        assert_eq!(owner, "octocat");
-        assert_eq!(repo, "hello-world");
+    fn clone_uses_the_enterprise_host_of_the_account() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = mock_api::storage(dir.path());
+        let clones = dir.path().join("src");
+        let mut accounts = storage.load_accounts().unwrap();
+        accounts.personal[0].host = Some("ghe.acme.com".to_string());
+        accounts.personal[0].clone_dir = Some(clones.display().to_string());
+        accounts.personal[0].clone_layout = CloneLayout::HostOwnerRepo;
+        storage.save_accounts(&accounts).unwrap();
+
+        let options = CloneOptions::default();
+        let Execution::Planned(steps) =
+            clone(&storage, "acme/api", options, true, ExecutionMode::DryRun).unwrap()
+        else {
+            panic!("expected a dry run");
+        };
+        let target = clones.join("ghe.acme.com").join("acme").join("api");
+        let expected = format!("git clone https://ghe.acme.com/acme/api.git {}", target.display());
+        assert_eq!(steps, [expected]);
+    }
+
+    #[test]
+    fn clone_keeps_the_host_of_a_repository_url() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = mock_api::storage(dir.path());
+        let mut accounts = storage.load_accounts().unwrap();
+        accounts.personal[0].clone_dir = Some(dir.path().join("src").display().to_string());
+        let mut work = accounts.personal[0].clone();
+        work.id = "work".to_string();
+        work.host = Some("ghe.acme.com".to_string());
+        accounts.personal.push(work);
+        storage.save_accounts(&accounts).unwrap();
+
+        let dry_run = |spec: &str| {
+            clone(&storage, spec, CloneOptions::default(), true, ExecutionMode::DryRun)
+        };
+        let Execution::Planned(steps) = dry_run("https://ghe.acme.com/acme/api").unwrap() else {
+            panic!("expected a dry run");
+        };
+        assert!(steps[0].contains("https://ghe.acme.com/acme/api.git"), "{}", steps[0]);
+        assert!(matches!(dry_run("https://ghe.corp/acme/api"), Err(AppError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn rename_points_the_clone_origin_at_the_new_name() {
+        let api = mock_api::server();
+        let owner = "mock-repo-rename";
+        let renamed = repository(owner, "api-v2", "Rust");
+        api.route("PATCH", &format!("/repos/{owner}/api"), 200, renamed);
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = mock_api::storage(dir.path());
+        let clones = dir.path().join("src");
+        let mut accounts = storage.load_accounts().unwrap();
+        accounts.personal[0].clone_dir = Some(clones.display().to_string());
+        storage.save_accounts(&accounts).unwrap();
+        let clone = clones.join("api");
+        std::fs::create_dir_all(&clone).unwrap();
+        conflicts::git(Some(&clone), &["init", "--quiet"]).unwrap();
+        let origin = format!("git@github.com:{owner}/api.git");
+        conflicts::git(Some(&clone), &["remote", "add", "origin", &origin]).unwrap();
+
+        let spec = format!("{owner}/api");
+        let Execution::Planned(steps) =
+            rename(&storage, &spec, "api-v2", ExecutionMode::DryRun).unwrap()
+        else {
+            panic!("expected a dry run");
+        };
+        assert_eq!(steps.len(), 2);
+        assert!(api.requests(&format!("/repos/{owner}/")).is_empty());
+
+        let Execution::Done(moved) =
+            rename(&storage, &spec, "api-v2", ExecutionMode::Live).unwrap()
+        else {
+            panic!("expected a live run");
+        };
+        assert_eq!(moved.to, format!("{owner}/api-v2"));
+        assert_eq!(moved.clone.as_deref(), Some(clone.as_path()));
+        assert_eq!(api.requests(&format!("/repos/{owner}/"))[0].body, r#"{"name":"api-v2"}"#);
+        let origin = conflicts::git(Some(&clone), &["remote", "get-url", "origin"]).unwrap();
+        assert_eq!(origin, format!("git@github.com:{owner}/api-v2.git"));
+        assert!(rename(&storage, &spec, "other/api", ExecutionMode::Live).is_err());
     }
 
     #[test]
-    fn parse_repo_spec_invalid() {
-        let result = parse_repo_spec("invalid");
-        assert!(result.is_err());
+    fn transfer_to_a_user_leaves_the_clone_until_accepted() {
+        let api = mock_api::server();
+        let owner = "mock-repo-transfer";
+        let unmoved = repository(owner, "api", "Rust");
+        api.route("POST", &format!("/repos/{owner}/api/transfer"), 202, unmoved);
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = mock_api::storage(dir.path());
+        let clones = dir.path().join("src");
+        let mut accounts = storage.load_accounts().unwrap();
+        accounts.personal[0].clone_dir = Some(clones.display().to_string());
+        storage.save_accounts(&accounts).unwrap();
+        let clone = clones.join("api");
+        std::fs::create_dir_all(&clone).unwrap();
+        conflicts::git(Some(&clone), &["init", "--quiet"]).unwrap();
+        let origin = format!("git@github.com:{owner}/api.git");
+        conflicts::git(Some(&clone), &["remote", "add", "origin", &origin]).unwrap();
+
+        let spec = format!("{owner}/api");
+        let Execution::Done(moved) =
+            transfer(&storage, &spec, "octocat", &[], ExecutionMode::Live).unwrap()
+        else {
+            panic!("expected a live run");
+        };
+        assert!(moved.pending);
+        assert_eq!(moved.to, "octocat/api");
+        assert_eq!(moved.clone, None);
+        let current = conflicts::git(Some(&clone), &["remote", "get-url", "origin"]).unwrap();
+        assert_eq!(current, origin);
     }
 
     #[test]
-    fn build_clone_url_ssh() {
-        let url = build_clone_url("octocat", "hello-world", Protocol::Ssh);
-        assert_eq!(url, "git@github.com:octocat/hello-world.git");
+    fn star_and_watch_use_the_user_and_subscription_endpoints() {
+        let api = mock_api::server();
+        let owner = "mock-repo-star";
+        api.route("PUT", &format!("/user/starred/{owner}/api"), 204, serde_json::json!({}));
+        api.route("PUT", &format!("/repos/{owner}/api/subscription"), 200, serde_json::json!({}));
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = mock_api::storage(dir.path());
+
+        let spec = format!("{owner}/api");
+        assert_eq!(set_starred(&storage, Some(&spec), true).unwrap(), spec);
+        assert_eq!(set_watched(&storage, Some(&spec), true).unwrap(), spec);
+        let subscription = api.requests(&format!("/repos/{owner}/api/subscription"));
+        assert_eq!(subscription[0].body, r#"{"ignored":false,"subscribed":true}"#);
+        assert_eq!(api.requests(&format!("/user/starred/{owner}/api")).len(), 1);
     }
 
     #[test]
-    fn build_clone_url_https() {
-        let url = build_clone_url("octocat", "hello-world", Protocol::Https);
-        assert_eq!(url, "https://github.com/octocat/hello-world.git");
+    fn update_topics_merges_with_the_current_topics() {
+        let api = mock_api::server();
+        let path = "/repos/mock-repo-topics/api/topics";
+        api.route("GET", path, 200, serde_json::json!({ "names": ["rust", "cli"] }));
+        api.route("PUT", path, 200, serde_json::json!({ "names": ["rust", "cli", "github"] }));
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = mock_api::storage(dir.path());
+
+        let added = vec!["GitHub".to_string(), "rust".to_string()];
+        let topics =
+            update_topics(&storage, "mock-repo-topics/api", TopicsChange::Add, &added).unwrap();
+        assert_eq!(topics, ["rust", "cli", "github"]);
+        let requests = api.requests(path);
+        assert_eq!(requests[1].method, "PUT");
+        assert_eq!(requests[1].body, r#"{"names":["rust","cli","github"]}"#);
+
+        // Removing a topic the repository does not have changes nothing.
+        let missing = vec!["go".to_string()];
+        update_topics(&storage, "mock-repo-topics/api", TopicsChange::Remove, &missing).unwrap();
+        assert_eq!(api.requests(path).len(), 3);
+        assert!(validate_topic("-rust").is_err());
+        assert!(validate_topic("rust lang").is_err());
+    }
+
+    fn init_origin(dir: &Path) {
+        fs::create_dir_all(dir).unwrap();
+        for args in
+            [&["init", "--quiet"][..], &["commit", "--quiet", "--allow-empty", "-m", "init"]]
+        {
+            let status = Command::new("git")
+                .arg("-C")
+                .arg(dir)
+                .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {args:?} failed");
+        }
+    }
+
+    #[test]
+    fn run_bulk_clone_keeps_failed_repositories_for_resume() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = mock_api::storage(dir.path());
+        init_origin(&dir.path().join("origin/api"));
+        let repo = |name: &str| BulkCloneRepo {
+            full_name: format!("acme/{name}"),
+            name: name.to_string(),
+            url: dir.path().join("origin").join(name).display().to_string(),
+            dir: dir.path().join("clones").join(name),
+            outcome: None,
+            error: None,
+        };
+        let plan = BulkClone {
+            org: "acme".into(),
+            account: "mock".into(),
+            manifest: dir.path().join("clones/gho-clones.json"),
+            options: CloneOptions::default(),
+            repos: vec![repo("api"), repo("web")],
+        };
+
+        let report = run_bulk_clone(&storage, plan, 2, true, Progress::Human).unwrap();
+        assert_eq!(report.cloned, ["api"]);
+        assert_eq!(report.failed[0].repo, "web");
+        let saved = storage.load_state().unwrap().bulk_clone.unwrap();
+        assert!(saved.repos[0].is_done() && !saved.repos[1].is_done());
+
+        // Resuming only retries the failed repository, then forgets the plan.
+        init_origin(&dir.path().join("origin/web"));
+        let report = run_bulk_clone(&storage, saved, 2, true, Progress::Human).unwrap();
+        assert_eq!(report.cloned, ["web"]);
+        assert!(report.skipped.is_empty() && report.failed.is_empty());
+        assert!(storage.load_state().unwrap().bulk_clone.is_none());
+        assert!(dir.path().join("clones/web/.git").exists());
     }
 }
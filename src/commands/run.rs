@@ -0,0 +1,613 @@
+//! Workflow run commands.
+
+use crate::commands::account;
+use crate::error::AppError;
+use crate::github::{ActionsApi, GitHubClient, Poller, ReposApi};
+use crate::models::{
+    ArtifactDownloadResult, CheckAnnotation, CheckRunAnnotation, WorkflowJob, WorkflowRun,
+    WorkflowStep,
+};
+use crate::remote::resolve_repo;
+use crate::storage::Storage;
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+use zip::ZipArchive;
+use zip::result::ZipError;
+
+/// List recent workflow runs, optionally only for one branch.
+pub fn list(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    branch: Option<&str>,
+    limit: usize,
+) -> Result<Vec<WorkflowRun>, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+    client.list_workflow_runs(&owner, &repo, branch, limit)
+}
+
+/// Poll a workflow run until it completes, calling `on_poll` with its jobs after each poll.
+///
+/// Polls are conditional and back off from `interval` while the run does not change (see
+/// [`Poller`]). Returns the completed run; callers decide how to treat its conclusion.
+pub fn watch(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    run_id: u64,
+    interval: Duration,
+    mut on_poll: impl FnMut(&WorkflowRun, &[WorkflowJob]),
+) -> Result<WorkflowRun, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?.polling();
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+    let mut poller = Poller::new(interval);
+
+    loop {
+        let run = client.get_workflow_run(&owner, &repo, run_id)?;
+        let jobs = client.list_workflow_run_jobs(&owner, &repo, run_id)?;
+        on_poll(&run, &jobs);
+        if run.status == "completed" {
+            return Ok(run);
+        }
+        poller.wait(&client.poll_state());
+    }
+}
+
+/// Log of one job, split into its steps.
+#[derive(Debug, Clone)]
+pub struct JobLog {
+    pub job: WorkflowJob,
+    pub steps: Vec<StepLog>,
+}
+
+/// Log lines of one step, without their timestamps.
+#[derive(Debug, Clone)]
+pub struct StepLog {
+    pub name: String,
+    pub conclusion: Option<String>,
+    pub lines: Vec<String>,
+}
+
+/// Which logs [`logs`] fetches.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogFilter<'a> {
+    /// Only the job with this name.
+    pub job: Option<&'a str>,
+    /// Only jobs and steps that did not pass.
+    pub failed: bool,
+}
+
+/// Fetch the logs of the completed jobs of run `run_id`, calling `on_job` with each.
+///
+/// GitHub only serves the log of a job once it has finished. With `follow`, the run is polled
+/// as by [`watch`], starting at that interval, and each job's log is passed on as it completes, until the whole run has;
+/// otherwise the names of jobs still running are returned.
+pub fn logs(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    run_id: u64,
+    filter: LogFilter<'_>,
+    follow: Option<Duration>,
+    mut on_job: impl FnMut(&JobLog),
+) -> Result<Vec<String>, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?.polling();
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+    let mut poller = follow.map(Poller::new);
+
+    let mut shown = HashSet::new();
+    loop {
+        let run = client.get_workflow_run(&owner, &repo, run_id)?;
+        let jobs = client.list_workflow_run_jobs(&owner, &repo, run_id)?;
+        let completed = run.status == "completed";
+        if let Some(name) = filter.job
+            && (completed || follow.is_none())
+            && !jobs.iter().any(|job| job.name == name)
+        {
+            return Err(AppError::NotFound(format!("run {run_id} has no job '{name}'")));
+        }
+
+        let mut running = Vec::new();
+        for job in jobs {
+            if shown.contains(&job.id) || filter.job.is_some_and(|name| job.name != name) {
+                continue;
+            }
+            if job.status != "completed" {
+                running.push(job.name);
+                continue;
+            }
+            if job.conclusion.as_deref() == Some("skipped")
+                || (filter.failed && is_success(job.conclusion.as_deref()))
+            {
+                shown.insert(job.id);
+                continue;
+            }
+            let log = match client.get_job_log(&owner, &repo, job.id) {
+                // The log of a job that just finished may not be uploaded yet.
+                Err(AppError::NotFound(_)) if follow.is_some() && !completed => {
+                    running.push(job.name);
+                    continue;
+                }
+                log => log?,
+            };
+            let mut steps = split_steps(&log, &job.steps);
+            if filter.failed && steps.iter().any(|step| !is_success(step.conclusion.as_deref())) {
+                steps.retain(|step| !is_success(step.conclusion.as_deref()));
+            }
+            shown.insert(job.id);
+            on_job(&JobLog { job, steps });
+        }
+
+        match &mut poller {
+            Some(poller) if !completed || !running.is_empty() => poller.wait(&client.poll_state()),
+            _ => return Ok(running),
+        }
+    }
+}
+
+/// Split a job log into its steps.
+///
+/// The log does not mark where steps start, so each line goes to the first step that had not
+/// completed by the line's timestamp. Step times only have whole seconds, so a line written in
+/// the second one step ends and the next begins is counted to the earlier one. Lines without a
+/// timestamp stay with the line before. `##[group]` markers are dropped.
+fn split_steps(log: &str, steps: &[WorkflowStep]) -> Vec<StepLog> {
+    let ends: Vec<Option<DateTime<Utc>>> = steps
+        .iter()
+        .map(|step| step.completed_at.as_deref().and_then(|at| at.parse().ok()))
+        .collect();
+    let mut logs: Vec<StepLog> = steps
+        .iter()
+        .map(|step| StepLog {
+            name: step.name.clone(),
+            conclusion: step.conclusion.clone(),
+            lines: Vec::new(),
+        })
+        .collect();
+    if logs.is_empty() {
+        logs.push(StepLog { name: "log".to_string(), conclusion: None, lines: Vec::new() });
+    }
+
+    let mut current = 0;
+    for line in log.trim_start_matches('\u{feff}').lines() {
+        let (at, text) = match line.split_once(' ') {
+            Some((at, text)) => match at.parse::<DateTime<Utc>>() {
+                Ok(at) => (Some(at), text),
+                Err(_) => (None, line),
+            },
+            None => (None, line),
+        };
+        if let Some(at) = at {
+            // Before the end of the second the step completed in.
+            let running = |end: &Option<DateTime<Utc>>| {
+                end.is_none_or(|end| at < end + chrono::Duration::seconds(1))
+            };
+            while current + 1 < logs.len() && !ends.get(current).is_some_and(running) {
+                current += 1;
+            }
+        }
+        if text == "##[endgroup]" {
+            continue;
+        }
+        let text = text.strip_prefix("##[group]").unwrap_or(text);
+        logs[current].lines.push(text.to_string());
+    }
+    logs.retain(|step| !step.lines.is_empty());
+    logs
+}
+
+/// Download the artifacts of run `id` into `dir` and extract them.
+///
+/// With `name`, only that artifact is fetched and extracted straight into `dir`; otherwise each
+/// artifact gets a subdirectory named after it. Archives are written next to their destination
+/// and removed once extracted. Failures, such as expired artifacts, are recorded per artifact.
+pub fn download(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    id: u64,
+    name: Option<&str>,
+    dir: &Path,
+) -> Result<Vec<ArtifactDownloadResult>, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+
+    let artifacts = client.list_run_artifacts(&owner, &repo, id)?;
+    let available = artifacts.iter().map(|a| a.name.as_str()).collect::<Vec<_>>().join(", ");
+    let selected: Vec<_> = artifacts.iter().filter(|a| name.is_none_or(|n| a.name == n)).collect();
+    if selected.is_empty() {
+        return Err(AppError::NotFound(match name {
+            Some(name) if !artifacts.is_empty() => {
+                format!("run {id} has no artifact '{name}' (it has {available})")
+            }
+            _ => format!("run {id} has no artifacts"),
+        }));
+    }
+    fs::create_dir_all(dir)?;
+
+    let mut results = Vec::new();
+    for artifact in selected {
+        let path = if name.is_some() { dir.to_path_buf() } else { dir.join(&artifact.name) };
+        let mut result =
+            ArtifactDownloadResult { name: artifact.name.clone(), path, files: 0, error: None };
+        let archive = dir.join(format!("{}.zip.part", artifact.name));
+        let outcome = if artifact.expired {
+            Err(AppError::NotFound("the artifact has expired".to_string()))
+        } else {
+            File::create(&archive)
+                .map_err(AppError::from)
+                .and_then(|file| {
+                    let mut writer = BufWriter::new(file);
+                    client.download_artifact(&owner, &repo, artifact.id, &mut writer)?;
+                    Ok(writer.flush()?)
+                })
+                .and_then(|()| extract(&archive, &result.path))
+        };
+        let _ = fs::remove_file(&archive);
+        match outcome {
+            Ok(files) => result.files = files,
+            Err(e) => result.error = Some(e.to_string()),
+        }
+        results.push(result);
+    }
+    Ok(results)
+}
+
+/// Extract the zip `archive` into `dir`, returning the number of files. Entries that would land
+/// outside `dir` are rejected.
+fn extract(archive: &Path, dir: &Path) -> Result<usize, AppError> {
+    let invalid = |e: ZipError| AppError::github_api(format!("cannot extract the artifact: {e}"));
+    let mut zip = ZipArchive::new(File::open(archive)?).map_err(invalid)?;
+    let files = zip.file_names().filter(|name| !name.ends_with('/')).count();
+    zip.extract(dir).map_err(invalid)?;
+    Ok(files)
+}
+
+/// How long [`dispatch`] looks for the run its event started.
+const DISPATCH_LOOKUP: Duration = Duration::from_secs(30);
+
+/// Outcome of [`dispatch`].
+#[derive(Debug, Clone)]
+pub struct Dispatch {
+    /// Branch or tag the workflow was dispatched on.
+    pub git_ref: String,
+    /// The run the event started, unless it did not show up in time.
+    pub run: Option<WorkflowRun>,
+}
+
+/// Trigger `workflow` (a file name such as `deploy.yml`, or its ID) on `git_ref`, the default
+/// branch when omitted, with `inputs` given as `key=value`.
+///
+/// The dispatch endpoint does not say which run it started, so the run is looked up among the
+/// workflow's `workflow_dispatch` runs on the ref created since just before the request,
+/// polling every `interval` for up to 30 seconds.
+pub fn dispatch(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    workflow: &str,
+    git_ref: Option<&str>,
+    inputs: &[String],
+    interval: Duration,
+) -> Result<Dispatch, AppError> {
+    let inputs = inputs.iter().map(|input| parse_input(input)).collect::<Result<_, _>>()?;
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+    let git_ref = match git_ref {
+        Some(git_ref) => git_ref.to_string(),
+        None => client.get_repo(&owner, &repo)?.default_branch.ok_or_else(|| {
+            AppError::invalid_input(format!("{owner}/{repo} has no default branch, pass --ref"))
+        })?,
+    };
+
+    // Allow for some clock skew between this machine and GitHub.
+    let since = Utc::now() - chrono::Duration::seconds(10);
+    client.dispatch_workflow(&owner, &repo, workflow, &git_ref, &inputs)?;
+
+    let branch = git_ref.trim_start_matches("refs/heads/").trim_start_matches("refs/tags/");
+    let deadline = Instant::now() + DISPATCH_LOOKUP;
+    loop {
+        let runs = client.list_dispatched_runs(&owner, &repo, workflow, branch)?;
+        let run = created_since(runs, since);
+        if run.is_some() || Instant::now() + interval > deadline {
+            return Ok(Dispatch { git_ref, run });
+        }
+        thread::sleep(interval);
+    }
+}
+
+/// The newest run created at or after `since`.
+fn created_since(runs: Vec<WorkflowRun>, since: DateTime<Utc>) -> Option<WorkflowRun> {
+    runs.into_iter()
+        .filter(|run| run.created_at.parse::<DateTime<Utc>>().is_ok_and(|at| at >= since))
+        .max_by(|a, b| a.created_at.cmp(&b.created_at))
+}
+
+fn parse_input(input: &str) -> Result<(String, String), AppError> {
+    let (key, value) =
+        input.split_once('=').filter(|(key, _)| !key.is_empty()).ok_or_else(|| {
+            AppError::invalid_input(format!("invalid input '{input}', expected key=value"))
+        })?;
+    Ok((key.to_string(), value.to_string()))
+}
+
+/// Re-run a workflow run, or with `failed_only` only its failed jobs. Returns the run as it was
+/// before the re-run.
+pub fn rerun(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    run_id: u64,
+    failed_only: bool,
+) -> Result<WorkflowRun, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+
+    let run = client.get_workflow_run(&owner, &repo, run_id)?;
+    client.rerun_workflow_run(&owner, &repo, run_id, failed_only)?;
+    Ok(run)
+}
+
+/// Runs that completed without passing, e.g. failed, cancelled or timed out.
+pub fn failed_runs(runs: Vec<WorkflowRun>) -> Vec<WorkflowRun> {
+    runs.into_iter()
+        .filter(|run| run.status == "completed" && !is_success(run.conclusion.as_deref()))
+        .collect()
+}
+
+/// Display state of a run or job: its conclusion once completed, else its status.
+pub fn state<'a>(status: &'a str, conclusion: Option<&'a str>) -> &'a str {
+    conclusion.filter(|_| status == "completed").unwrap_or(status)
+}
+
+/// Whether a completed run or job counts as passing.
+pub fn is_success(conclusion: Option<&str>) -> bool {
+    matches!(conclusion, Some("success" | "skipped" | "neutral"))
+}
+
+/// Collect annotations from every job of a workflow run.
+pub fn annotations(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    run_id: u64,
+) -> Result<Vec<CheckRunAnnotation>, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+
+    let mut results = Vec::new();
+    for job in client.list_workflow_run_jobs(&owner, &repo, run_id)? {
+        let annotations = client.list_check_run_annotations(&owner, &repo, job.id)?;
+        results.extend(tag_annotations(&job.name, annotations));
+    }
+    Ok(results)
+}
+
+pub(crate) fn tag_annotations(
+    check: &str,
+    annotations: Vec<CheckAnnotation>,
+) -> impl Iterator<Item = CheckRunAnnotation> + '_ {
+    annotations
+        .into_iter()
+        .map(move |annotation| CheckRunAnnotation { check: check.to_string(), annotation })
+}
+
+/// Format an annotation as a compiler-style `file:line:col: level: message` line.
+///
+/// The output matches the default `errorformat` of Vim/Neovim quickfix lists and the
+/// problem matchers of most editors.
+pub fn format_annotation(item: &CheckRunAnnotation) -> String {
+    let a = &item.annotation;
+    let level = match a.annotation_level.as_str() {
+        "failure" => "error",
+        "warning" => "warning",
+        _ => "note",
+    };
+    let location = match a.start_column {
+        Some(col) => format!("{}:{}:{}", a.path, a.start_line, col),
+        None => format!("{}:{}", a.path, a.start_line),
+    };
+    let message = a.message.split_whitespace().collect::<Vec<_>>().join(" ");
+    match a.title.as_deref().filter(|t| !t.is_empty()) {
+        Some(title) => format!("{location}: {level}: {title}: {message} [{}]", item.check),
+        None => format!("{location}: {level}: {message} [{}]", item.check),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_api;
+
+    fn annotation(level: &str, column: Option<u64>, title: Option<&str>) -> CheckRunAnnotation {
+        CheckRunAnnotation {
+            check: "lint".to_string(),
+            annotation: CheckAnnotation {
+                path: "src/main.rs".to_string(),
+                start_line: 12,
+                start_column: column,
+                annotation_level: level.to_string(),
+                message: "unused variable\n  `x`".to_string(),
+                title: title.map(str::to_string),
+            },
+        }
+    }
+
+    #[test]
+    fn state_prefers_conclusion_when_completed() {
+        assert_eq!(state("in_progress", None), "in_progress");
+        assert_eq!(state("completed", Some("failure")), "failure");
+        assert_eq!(state("queued", Some("stale")), "queued");
+    }
+
+    #[test]
+    fn is_success_accepts_skipped_and_neutral() {
+        assert!(is_success(Some("success")));
+        assert!(is_success(Some("skipped")));
+        assert!(!is_success(Some("cancelled")));
+        assert!(!is_success(None));
+    }
+
+    fn workflow_run(id: u64, created_at: &str) -> WorkflowRun {
+        serde_json::from_value(serde_json::json!({
+            "id": id, "run_number": id, "name": "deploy", "display_title": "Deploy",
+            "status": "queued", "conclusion": null, "event": "workflow_dispatch",
+            "head_branch": "main", "html_url": "", "created_at": created_at
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn created_since_picks_the_newest_run_after_the_dispatch() {
+        let since: DateTime<Utc> = "2026-03-01T12:00:00Z".parse().unwrap();
+        let runs = vec![
+            workflow_run(1, "2026-03-01T11:59:00Z"),
+            workflow_run(2, "2026-03-01T12:00:04Z"),
+            workflow_run(3, "2026-03-01T12:00:09Z"),
+        ];
+        assert_eq!(created_since(runs, since).map(|run| run.id), Some(3));
+        assert!(created_since(vec![workflow_run(1, "2026-03-01T11:59:00Z")], since).is_none());
+    }
+
+    #[test]
+    fn dispatch_posts_inputs_and_finds_the_started_run() {
+        let api = mock_api::server();
+        let base = "/repos/mock-run-dispatch/api/actions/workflows/deploy.yml";
+        api.route("POST", &format!("{base}/dispatches"), 204, serde_json::json!(null));
+        let created_at = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        let runs = [workflow_run(7, "2026-01-01T00:00:00Z"), workflow_run(8, &created_at)];
+        api.route(
+            "GET",
+            &format!("{base}/runs"),
+            200,
+            serde_json::json!({ "workflow_runs": runs }),
+        );
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = mock_api::storage(dir.path());
+
+        let inputs = ["env=staging".to_string(), "note=a=b".to_string()];
+        let dispatch = dispatch(
+            &storage,
+            Some("mock-run-dispatch/api"),
+            "deploy.yml",
+            Some("refs/heads/main"),
+            &inputs,
+            Duration::from_millis(10),
+        )
+        .unwrap();
+        assert_eq!(dispatch.run.map(|run| run.id), Some(8));
+
+        let body: serde_json::Value =
+            serde_json::from_str(&api.requests(&format!("{base}/dispatches"))[0].body).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "ref": "refs/heads/main",
+                "inputs": { "env": "staging", "note": "a=b" }
+            })
+        );
+        assert!(api.requests(&format!("{base}/runs"))[0].path.contains("branch=main"));
+        assert!(parse_input("=staging").is_err());
+    }
+
+    #[test]
+    fn split_steps_assigns_lines_by_completion_time() {
+        let step = |number: u64, name: &str, conclusion: &str, completed_at: &str| WorkflowStep {
+            name: name.to_string(),
+            number,
+            status: "completed".to_string(),
+            conclusion: Some(conclusion.to_string()),
+            completed_at: Some(completed_at.to_string()),
+        };
+        let steps = [
+            step(1, "Set up job", "success", "2026-03-01T12:00:02Z"),
+            step(2, "Checkout", "success", "2026-03-01T12:00:05Z"),
+            step(3, "Skipped", "skipped", "2026-03-01T12:00:05Z"),
+            step(4, "Test", "failure", "2026-03-01T12:00:09Z"),
+        ];
+        let log = "\u{feff}2026-03-01T12:00:01.5000000Z Current runner version: '2.320.0'\n\
+                   2026-03-01T12:00:03.1000000Z ##[group]Run actions/checkout@v4\n\
+                   2026-03-01T12:00:05.9000000Z ##[endgroup]\n\
+                   2026-03-01T12:00:06.2000000Z test parse ... FAILED\n\
+                   \tat src/lib.rs:12\n";
+        let logs = split_steps(log, &steps);
+        let names: Vec<&str> = logs.iter().map(|step| step.name.as_str()).collect();
+        assert_eq!(names, ["Set up job", "Checkout", "Test"]);
+        assert_eq!(logs[0].lines, ["Current runner version: '2.320.0'"]);
+        assert_eq!(logs[1].lines, ["Run actions/checkout@v4"]);
+        assert_eq!(logs[2].lines, ["test parse ... FAILED", "\tat src/lib.rs:12"]);
+        assert_eq!(logs[2].conclusion.as_deref(), Some("failure"));
+    }
+
+    fn write_zip(path: &Path, entries: &[&str]) {
+        let mut zip = zip::ZipWriter::new(File::create(path).unwrap());
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Stored);
+        for entry in entries {
+            if entry.ends_with('/') {
+                zip.add_directory(*entry, options).unwrap();
+            } else {
+                zip.start_file(*entry, options).unwrap();
+                zip.write_all(b"report").unwrap();
+            }
+        }
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn extract_counts_files_and_rejects_paths_outside_the_directory() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let archive = dir.path().join("coverage.zip");
+        write_zip(&archive, &["lcov.info", "html/", "html/index.html"]);
+        let out = dir.path().join("coverage");
+        assert_eq!(extract(&archive, &out).unwrap(), 2);
+        assert_eq!(fs::read_to_string(out.join("html/index.html")).unwrap(), "report");
+
+        write_zip(&archive, &["../escaped.txt"]);
+        assert!(extract(&archive, &out).is_err());
+        assert!(!dir.path().join("escaped.txt").exists());
+    }
+
+    #[test]
+    fn download_reports_expired_artifacts_and_unknown_names() {
+        let api = mock_api::server();
+        let artifacts = "/repos/mock-run-download/api/actions/runs/9/artifacts";
+        api.route(
+            "GET",
+            artifacts,
+            200,
+            serde_json::json!({ "artifacts": [
+                { "id": 1, "name": "coverage", "size_in_bytes": 10, "expired": true }
+            ] }),
+        );
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = mock_api::storage(dir.path());
+        let out = dir.path().join("out");
+
+        let results = download(&storage, Some("mock-run-download/api"), 9, None, &out).unwrap();
+        assert_eq!(results[0].path, out.join("coverage"));
+        assert!(results[0].error.as_deref().unwrap().contains("expired"));
+        assert!(!out.join("coverage.zip.part").exists());
+
+        let missing = download(&storage, Some("mock-run-download/api"), 9, Some("logs"), &out);
+        assert!(matches!(missing, Err(AppError::NotFound(msg)) if msg.contains("coverage")));
+    }
+
+    #[test]
+    fn format_annotation_uses_quickfix_layout() {
+        assert_eq!(
+            format_annotation(&annotation("failure", Some(5), None)),
+            "src/main.rs:12:5: error: unused variable `x` [lint]"
+        );
+        assert_eq!(
+            format_annotation(&annotation("notice", None, Some("clippy"))),
+            "src/main.rs:12: note: clippy: unused variable `x` [lint]"
+        );
+    }
+}
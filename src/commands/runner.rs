@@ -0,0 +1,70 @@
+//! Self-hosted runner commands.
+
+use crate::commands::account;
+use crate::error::AppError;
+use crate::github::{ActionsApi, GitHubClient};
+use crate::models::{Runner, RunnerRegistrationToken};
+use crate::storage::Storage;
+
+/// List the self-hosted runners of an organization.
+pub fn list(storage: &impl Storage, org: &str) -> Result<Vec<Runner>, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    client.list_org_runners(org)
+}
+
+/// Remove a runner, identified by name or numeric ID, from an organization.
+pub fn remove(storage: &impl Storage, org: &str, runner: &str) -> Result<Runner, AppError> {
+    let (_account, token) = account::get_active_for_destructive(storage)?;
+    let client = GitHubClient::new(token)?;
+
+    let runners = client.list_org_runners(org)?;
+    let target = find_runner(&runners, runner)
+        .cloned()
+        .ok_or_else(|| AppError::NotFound(format!("runner '{runner}' not found in {org}")))?;
+    client.delete_org_runner(org, target.id)?;
+    Ok(target)
+}
+
+/// Create a registration token for enrolling a new runner in an organization.
+pub fn registration_token(
+    storage: &impl Storage,
+    org: &str,
+) -> Result<RunnerRegistrationToken, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    client.create_org_runner_registration_token(org)
+}
+
+/// Find a runner by exact name, falling back to its numeric ID.
+fn find_runner<'a>(runners: &'a [Runner], query: &str) -> Option<&'a Runner> {
+    runners.iter().find(|r| r.name == query).or_else(|| {
+        let id: u64 = query.parse().ok()?;
+        runners.iter().find(|r| r.id == id)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runner(id: u64, name: &str) -> Runner {
+        Runner {
+            id,
+            name: name.to_string(),
+            os: "linux".to_string(),
+            status: "online".to_string(),
+            busy: false,
+            labels: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn find_runner_matches_name_before_id() {
+        let runners = vec![runner(7, "build-1"), runner(8, "7")];
+        assert_eq!(find_runner(&runners, "build-1").unwrap().id, 7);
+        assert_eq!(find_runner(&runners, "7").unwrap().id, 8);
+        assert_eq!(find_runner(&runners, "8").unwrap().id, 8);
+        assert!(find_runner(&runners, "missing").is_none());
+    }
+}
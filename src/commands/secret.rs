@@ -0,0 +1,167 @@
+//! Actions secrets (`gho secret`).
+//!
+//! GitHub only accepts secret values sealed with the repository's or organization's public key
+//! (a libsodium sealed box), so `set` fetches the key and encrypts locally. Values are read from
+//! stdin or a prompt, never from arguments, which keeps them out of shell and `gho` history.
+
+use crate::commands::account;
+use crate::error::AppError;
+use crate::github::{ActionsApi, ActionsOwner, GitHubClient};
+use crate::models::{ActionsSecret, SecretVisibility};
+use crate::remote::resolve_repo;
+use crate::storage::Storage;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+
+/// Where secrets and variables are managed: the organization `org` when given, otherwise the
+/// repository `repo_spec` (or the current directory's).
+pub fn owner(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    org: Option<&str>,
+) -> Result<ActionsOwner, AppError> {
+    match org {
+        Some(org) => Ok(ActionsOwner::Org(org.to_string())),
+        None => {
+            let (owner, repo) = resolve_repo(storage, repo_spec)?;
+            Ok(ActionsOwner::Repo { owner, repo })
+        }
+    }
+}
+
+/// Check a secret or variable name against GitHub's rules and return it uppercased, the way
+/// GitHub stores it.
+pub fn validate_name(name: &str) -> Result<String, AppError> {
+    let upper = name.to_ascii_uppercase();
+    let valid = upper.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && upper.chars().next().is_some_and(|c| !c.is_ascii_digit())
+        && !upper.starts_with("GITHUB_");
+    if !valid {
+        return Err(AppError::invalid_input(format!(
+            "invalid name '{name}': use letters, digits and underscores, not starting with a \
+             digit or GITHUB_"
+        )));
+    }
+    Ok(upper)
+}
+
+/// List the secrets of a repository or organization.
+pub fn list(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    org: Option<&str>,
+) -> Result<Vec<ActionsSecret>, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    client.list_secrets(&owner(storage, repo_spec, org)?)
+}
+
+/// Create or update the secret `name` with a value read from stdin or a prompt.
+///
+/// An organization secret keeps its visibility unless `visibility` is given; new ones default
+/// to private repositories. Returns where the secret was set.
+pub fn set(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    org: Option<&str>,
+    name: &str,
+    visibility: Option<SecretVisibility>,
+) -> Result<ActionsOwner, AppError> {
+    let name = validate_name(name)?;
+    let owner = owner(storage, repo_spec, org)?;
+    let value = read_value(&name)?;
+    if value.is_empty() {
+        return Err(AppError::invalid_input("secret value is empty"));
+    }
+
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let visibility = match (&owner, visibility) {
+        (ActionsOwner::Org(_), None) => match client.get_secret(&owner, &name) {
+            Ok(existing) => existing.visibility,
+            Err(AppError::NotFound(_)) => None,
+            Err(e) => return Err(e),
+        },
+        _ => visibility,
+    };
+    let key = client.get_secrets_public_key(&owner)?;
+    let sealed = seal(&key.key, &value)?;
+    client.put_secret(&owner, &name, &sealed, &key.key_id, visibility)?;
+    Ok(owner)
+}
+
+/// Delete the secret `name`.
+pub fn delete(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    org: Option<&str>,
+    name: &str,
+) -> Result<ActionsOwner, AppError> {
+    let name = validate_name(name)?;
+    let owner = owner(storage, repo_spec, org)?;
+    let (_account, token) = account::get_active_for_destructive(storage)?;
+    GitHubClient::new(token)?.delete_secret(&owner, &name)?;
+    Ok(owner)
+}
+
+/// Encrypt `value` into a sealed box for the base64-encoded Curve25519 `public_key` and return
+/// it base64-encoded, as the secrets API expects.
+pub fn seal(public_key: &str, value: &str) -> Result<String, AppError> {
+    let key: [u8; crypto_box::KEY_SIZE] = BASE64
+        .decode(public_key)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| AppError::github_api("invalid secrets public key"))?;
+    let sealed = crypto_box::PublicKey::from(key)
+        .seal(&mut crypto_box::aead::OsRng, value.as_bytes())
+        .map_err(|e| AppError::github_api(format!("failed to encrypt secret: {e}")))?;
+    Ok(BASE64.encode(sealed))
+}
+
+/// Read a secret value from stdin when it is piped, otherwise prompt for it (never echoed).
+///
+/// Only the trailing newline of piped input is dropped, so keys and certificates keep their
+/// content.
+#[cfg(feature = "interactive")]
+fn read_value(name: &str) -> Result<String, AppError> {
+    if !atty::is(atty::Stream::Stdin) {
+        let mut value = String::new();
+        std::io::Read::read_to_string(&mut std::io::stdin(), &mut value)?;
+        return Ok(value.trim_end_matches(['\n', '\r']).to_string());
+    }
+    inquire::Password::new(&format!("Value of {name}:"))
+        .prompt()
+        .map_err(|e| AppError::config(format!("secret entry cancelled: {e}")))
+}
+
+/// Without prompts, the value can only be piped in.
+#[cfg(not(feature = "interactive"))]
+fn read_value(_name: &str) -> Result<String, AppError> {
+    let mut value = String::new();
+    std::io::Read::read_to_string(&mut std::io::stdin(), &mut value)?;
+    Ok(value.trim_end_matches(['\n', '\r']).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seal_opens_with_the_matching_secret_key() {
+        let secret_key = crypto_box::SecretKey::generate(&mut crypto_box::aead::OsRng);
+        let public_key = BASE64.encode(secret_key.public_key().as_bytes());
+
+        let sealed = BASE64.decode(seal(&public_key, "hunter2").unwrap()).unwrap();
+        assert_eq!(secret_key.unseal(&sealed).unwrap(), b"hunter2");
+        assert!(seal("c2hvcnQ=", "hunter2").is_err());
+    }
+
+    #[test]
+    fn validate_name_uppercases_and_rejects_reserved_names() {
+        assert_eq!(validate_name("npm_token").unwrap(), "NPM_TOKEN");
+        assert!(validate_name("github_token").is_err());
+        assert!(validate_name("1PASSWORD").is_err());
+        assert!(validate_name("API-KEY").is_err());
+        assert!(validate_name("").is_err());
+    }
+}
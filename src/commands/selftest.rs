@@ -0,0 +1,161 @@
+//! End-to-end self-test against a sandbox organization (`gho selftest`).
+//!
+//! Built with the `selftest` feature. `run` creates a throwaway repository in the organization,
+//! clones it, pushes a branch, opens and squash-merges a pull request and deletes the repository
+//! again, all as the active account, so maintainers can check in one command that gho works
+//! with a given GitHub setup (proxy, Enterprise policies, token scopes). Every step is timed;
+//! the steps after a failure are skipped, but the repository is always deleted.
+
+use crate::commands::account;
+use crate::commands::conflicts::git;
+use crate::domain::{GITHUB_HOST, build_clone_url};
+use crate::error::AppError;
+use crate::github::{GitHubClient, PullsApi, ReposApi};
+use crate::models::{
+    Account, MergeMethod, NewPullRequest, NewRepository, SelftestReport, SelftestStep,
+};
+use crate::storage::Storage;
+use chrono::Utc;
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+
+/// Prefix of the throwaway repositories, so leftovers of an aborted run are easy to spot.
+pub const REPO_PREFIX: &str = "gho-selftest-";
+
+/// Branch the pull request is opened from.
+const BRANCH: &str = "gho-selftest";
+
+/// Run the self-test in `org`.
+pub fn run(storage: &impl Storage, org: &str) -> Result<SelftestReport, AppError> {
+    let org = org.trim();
+    if org.is_empty() || org.contains('/') {
+        return Err(AppError::invalid_input(format!("invalid organization '{org}'")));
+    }
+    let (account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let name = format!("{REPO_PREFIX}{}", Utc::now().format("%Y%m%d%H%M%S"));
+    let workdir = std::env::temp_dir().join(format!("{name}-{}", std::process::id()));
+
+    let mut report =
+        SelftestReport { repo: format!("{org}/{name}"), steps: Vec::new(), cleaned_up: true };
+    let repo = NewRepository {
+        name: name.clone(),
+        description: Some("Temporary repository of `gho selftest`; safe to delete.".to_string()),
+        private: true,
+        auto_init: true,
+    };
+    if step(&mut report, "create repository", || client.create_org_repo(org, &repo)).is_some() {
+        exercise(&client, &account, org, &name, &workdir, &mut report);
+        report.cleaned_up =
+            step(&mut report, "delete repository", || client.delete_repo(org, &name)).is_some();
+    }
+    let _ = fs::remove_dir_all(&workdir);
+    Ok(report)
+}
+
+/// Clone, push, open and merge; stops at the first failing step.
+fn exercise(
+    client: &(impl ReposApi + PullsApi),
+    account: &Account,
+    org: &str,
+    name: &str,
+    workdir: &Path,
+    report: &mut SelftestReport,
+) -> Option<()> {
+    let url = build_clone_url(
+        account.host.as_deref().unwrap_or(GITHUB_HOST),
+        org,
+        name,
+        account.protocol,
+    );
+    let checkout = workdir.join(name);
+    let checkout_arg = checkout.to_string_lossy().into_owned();
+    step(report, "clone", || {
+        fs::create_dir_all(workdir)?;
+        git(None, &["clone", "--quiet", &url, &checkout_arg])
+    })?;
+
+    let dir = Some(checkout.as_path());
+    let base = step(report, "push branch", || {
+        let base = git(dir, &["symbolic-ref", "--short", "HEAD"])?;
+        git(dir, &["checkout", "--quiet", "-b", BRANCH])?;
+        fs::write(
+            checkout.join("SELFTEST.md"),
+            format!("Written by gho selftest at {}.\n", Utc::now()),
+        )?;
+        let user = format!("user.name={}", account.username);
+        let email = format!("user.email={}@users.noreply.github.com", account.username);
+        git(dir, &["add", "SELFTEST.md"])?;
+        git(dir, &["-c", &user, "-c", &email, "commit", "--quiet", "-m", "gho selftest"])?;
+        git(dir, &["push", "--quiet", "origin", BRANCH])?;
+        Ok(base)
+    })?;
+
+    let pull_request = step(report, "create pull request", || {
+        client.create_pull_request(
+            org,
+            name,
+            &NewPullRequest {
+                title: "gho selftest".to_string(),
+                head: BRANCH.to_string(),
+                base,
+                body: None,
+                draft: false,
+            },
+        )
+    })?;
+    step(report, "merge pull request", || {
+        client.merge_pull_request(org, name, pull_request.number, MergeMethod::Squash)
+    })?;
+    Some(())
+}
+
+/// Run `f` as the step `name`, recording its duration and error; its value on success.
+fn step<T>(
+    report: &mut SelftestReport,
+    name: &str,
+    f: impl FnOnce() -> Result<T, AppError>,
+) -> Option<T> {
+    let start = Instant::now();
+    let result = f();
+    report.steps.push(SelftestStep {
+        step: name.to_string(),
+        error: result.as_ref().err().map(ToString::to_string),
+        elapsed_ms: start.elapsed().as_millis() as u64,
+    });
+    result.ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_api;
+
+    #[test]
+    fn run_stops_when_the_repository_cannot_be_created() {
+        let api = mock_api::server();
+        api.route(
+            "POST",
+            "/orgs/mock-selftest/repos",
+            403,
+            serde_json::json!({ "message": "Must have admin rights" }),
+        );
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = mock_api::storage(dir.path());
+
+        let report = run(&storage, "mock-selftest").unwrap();
+        assert!(report.repo.starts_with("mock-selftest/gho-selftest-"));
+        assert_eq!(report.steps.len(), 1);
+        assert_eq!(report.steps[0].step, "create repository");
+        assert!(report.steps[0].error.as_deref().unwrap().contains("Must have admin rights"));
+        assert_eq!(report.failed(), 1);
+        assert!(report.cleaned_up);
+
+        let requests = api.requests("/orgs/mock-selftest/repos");
+        let body: serde_json::Value = serde_json::from_str(&requests[0].body).unwrap();
+        assert_eq!(body["private"], true);
+        assert_eq!(body["auto_init"], true);
+        assert!(run(&storage, "acme/api").is_err());
+    }
+}
@@ -0,0 +1,90 @@
+//! Per-account SSH identities in ssh-agent (`account use --ssh-agent`).
+//!
+//! An agent holding the keys of several accounts offers them in order, and GitHub takes the
+//! first one it knows, so pushes can go out as the wrong user. Switching with this enabled
+//! removes the other accounts' keys (set with `account setup-gitconfig --ssh-key`) from the
+//! agent and adds the active account's. Repositories under an account's directory already use
+//! its key through `core.sshCommand`; this covers clones everywhere else.
+
+use crate::error::AppError;
+use crate::logging;
+use crate::models::{AccountsFile, SshAgentSwitch};
+use crate::platform;
+use std::process::{Command, Stdio};
+
+/// Make the SSH key of account `id` the only account key in the running agent.
+///
+/// Keys of other accounts that the agent does not hold are skipped. Adding the key may prompt
+/// for its passphrase.
+pub fn switch(accounts: &AccountsFile, id: &str) -> Result<SshAgentSwitch, AppError> {
+    if std::env::var_os("SSH_AUTH_SOCK").is_none() {
+        return Err(AppError::config("no ssh-agent is running (SSH_AUTH_SOCK is not set)"));
+    }
+    let account = accounts.find_account(id).ok_or_else(|| AppError::AccountNotFound(id.into()))?;
+    let key = account.git.as_ref().and_then(|g| g.ssh_key.clone());
+
+    let mut result = SshAgentSwitch::default();
+    for other in other_keys(accounts, id) {
+        let status =
+            logging::command(Command::new("ssh-add").arg("-d").arg(platform::expand_home(&other)))
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status();
+        if status.is_ok_and(|s| s.success()) {
+            result.removed.push(other);
+        }
+    }
+    if let Some(key) = key {
+        let status = logging::command(Command::new("ssh-add").arg(platform::expand_home(&key)))
+            .stdout(Stdio::null())
+            .status()
+            .map_err(|e| AppError::config(format!("failed to run ssh-add: {e}")))?;
+        if !status.success() {
+            return Err(AppError::config(format!("ssh-add could not load '{key}'")));
+        }
+        result.added = Some(key);
+    }
+    Ok(result)
+}
+
+/// SSH keys of the accounts other than `id`, without the key `id` uses itself.
+fn other_keys(accounts: &AccountsFile, id: &str) -> Vec<String> {
+    let own = accounts.find_account(id).and_then(|a| a.git.as_ref()?.ssh_key.as_deref());
+    let mut keys: Vec<String> = accounts
+        .all_accounts()
+        .into_iter()
+        .filter(|a| a.id != id)
+        .filter_map(|a| a.git.as_ref()?.ssh_key.clone())
+        .filter(|key| Some(key.as_str()) != own)
+        .collect();
+    keys.sort();
+    keys.dedup();
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn other_keys_skips_the_active_key_and_duplicates() {
+        let account = |id: &str, key: Option<&str>| {
+            let git = key.map(|key| {
+                serde_json::json!({ "name": id, "email": "a@b.c", "ssh_key": key, "gitdir": "~/" })
+            });
+            serde_json::json!({ "id": id, "kind": "personal", "username": id, "git": git })
+        };
+        let accounts: AccountsFile = serde_json::from_value(serde_json::json!({
+            "personal": [account("home", Some("~/.ssh/id_home")), account("bare", None)],
+            "work": [
+                account("work", Some("~/.ssh/id_work")),
+                account("oss", Some("~/.ssh/id_work")),
+                account("shared", Some("~/.ssh/id_home")),
+            ],
+        }))
+        .unwrap();
+
+        assert_eq!(other_keys(&accounts, "home"), ["~/.ssh/id_work"]);
+        assert_eq!(other_keys(&accounts, "bare"), ["~/.ssh/id_home", "~/.ssh/id_work"]);
+    }
+}
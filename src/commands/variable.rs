@@ -0,0 +1,85 @@
+//! Actions configuration variables (`gho variable`).
+//!
+//! Unlike secrets, variable values are stored and returned in plain text, so `set` takes the
+//! value as an argument and `list` shows it.
+
+use crate::commands::account;
+use crate::commands::secret::{owner, validate_name};
+use crate::error::AppError;
+use crate::github::{ActionsApi, ActionsOwner, GitHubClient};
+use crate::models::{ActionsVariable, SecretVisibility};
+use crate::storage::Storage;
+
+/// List the variables of a repository or organization.
+pub fn list(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    org: Option<&str>,
+) -> Result<Vec<ActionsVariable>, AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    client.list_variables(&owner(storage, repo_spec, org)?)
+}
+
+/// Create the variable `name` or update its value.
+///
+/// An organization variable keeps its visibility unless `visibility` is given; new ones default
+/// to private repositories. Returns where the variable was set.
+pub fn set(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    org: Option<&str>,
+    name: &str,
+    value: &str,
+    visibility: Option<SecretVisibility>,
+) -> Result<ActionsOwner, AppError> {
+    let name = validate_name(name)?;
+    let owner = owner(storage, repo_spec, org)?;
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    // Creating fails for existing variables and updating for missing ones; most `set`s update.
+    match client.update_variable(&owner, &name, value, visibility) {
+        Err(AppError::NotFound(_)) => client.create_variable(&owner, &name, value, visibility)?,
+        result => result?,
+    }
+    Ok(owner)
+}
+
+/// Delete the variable `name`.
+pub fn delete(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    org: Option<&str>,
+    name: &str,
+) -> Result<ActionsOwner, AppError> {
+    let name = validate_name(name)?;
+    let owner = owner(storage, repo_spec, org)?;
+    let (_account, token) = account::get_active_for_destructive(storage)?;
+    GitHubClient::new(token)?.delete_variable(&owner, &name)?;
+    Ok(owner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_api;
+
+    #[test]
+    fn set_creates_missing_organization_variables_as_private() {
+        let api = mock_api::server();
+        api.route("POST", "/orgs/mock-variable/actions/variables", 201, serde_json::json!({}));
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = mock_api::storage(dir.path());
+
+        let owner = set(&storage, None, Some("mock-variable"), "aws_region", "eu-west-1", None);
+        assert_eq!(owner.unwrap(), ActionsOwner::Org("mock-variable".into()));
+
+        let requests = api.requests("/orgs/mock-variable/actions/variables");
+        let methods: Vec<&str> = requests.iter().map(|r| r.method.as_str()).collect();
+        assert_eq!(methods, ["PATCH", "POST"]);
+        assert!(!requests[0].body.contains("visibility"));
+        let body: serde_json::Value = serde_json::from_str(&requests[1].body).unwrap();
+        assert_eq!(body["name"], "AWS_REGION");
+        assert_eq!(body["visibility"], "private");
+    }
+}
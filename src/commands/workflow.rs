@@ -0,0 +1,136 @@
+//! Workflow commands.
+
+use crate::commands::account;
+use crate::error::AppError;
+use crate::github::{ActionsApi, ActionsScope, GitHubClient};
+use crate::remote::resolve_repo;
+use crate::storage::Storage;
+use crate::workflow::{self, Level, LintReport};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Default location of workflow files.
+pub const WORKFLOWS_DIR: &str = ".github/workflows";
+
+/// Lint result for one workflow file.
+#[derive(Debug, Clone)]
+pub struct FileReport {
+    pub path: PathBuf,
+    pub content: String,
+    pub report: LintReport,
+}
+
+/// Lint a workflow file, or every `.yml`/`.yaml` file in a directory.
+pub fn lint(path: Option<&Path>) -> Result<Vec<FileReport>, AppError> {
+    let path = path.unwrap_or(Path::new(WORKFLOWS_DIR));
+    let files = collect_files(path)?;
+    if files.is_empty() {
+        return Err(AppError::invalid_input(format!(
+            "no workflow files found in '{}'",
+            path.display()
+        )));
+    }
+
+    files
+        .into_iter()
+        .map(|path| {
+            let content = fs::read_to_string(&path)?;
+            let report = workflow::lint(&content);
+            Ok(FileReport { path, content, report })
+        })
+        .collect()
+}
+
+/// Report referenced secrets and variables that do not exist for the repository.
+///
+/// Repository, organization and the workflow's deployment environments are consulted.
+/// Organization entries are skipped when the token cannot read them.
+pub fn check_remote(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+    reports: &mut [FileReport],
+) -> Result<(), AppError> {
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let (owner, repo) = resolve_repo(storage, repo_spec)?;
+    let full_name = format!("{owner}/{repo}");
+
+    let environments: BTreeSet<&str> =
+        reports.iter().flat_map(|r| r.report.environments.iter().map(String::as_str)).collect();
+    let mut scopes = vec![ActionsScope::Repository, ActionsScope::Organization];
+    scopes.extend(environments.iter().map(|env| ActionsScope::Environment(env)));
+
+    let mut secrets = BTreeSet::new();
+    let mut variables = BTreeSet::new();
+    for scope in scopes {
+        let optional = scope != ActionsScope::Repository;
+        secrets.extend(skip_if(optional, client.list_secret_names(&owner, &repo, scope))?);
+        variables.extend(skip_if(optional, client.list_variable_names(&owner, &repo, scope))?);
+    }
+
+    for file in reports.iter_mut() {
+        let report = &mut file.report;
+        let missing_secrets: Vec<String> =
+            report.secrets.iter().filter(|s| !secrets.contains(*s)).cloned().collect();
+        let missing_variables: Vec<String> =
+            report.variables.iter().filter(|v| !variables.contains(*v)).cloned().collect();
+
+        for name in missing_secrets {
+            let needle = format!("secrets.{name}");
+            let message = format!("secret '{name}' not found in {full_name}");
+            report.push(Level::Error, &file.content, Some(&needle), message);
+        }
+        for name in missing_variables {
+            let needle = format!("vars.{name}");
+            let message = format!("variable '{name}' not found in {full_name}");
+            report.push(Level::Error, &file.content, Some(&needle), message);
+        }
+    }
+    Ok(())
+}
+
+/// Treat permission and not-found errors as "no entries" for optional scopes.
+fn skip_if(optional: bool, result: Result<Vec<String>, AppError>) -> Result<Vec<String>, AppError> {
+    match result {
+        Err(AppError::NotFound(_) | AppError::Unauthorized(_)) if optional => Ok(Vec::new()),
+        other => other,
+    }
+}
+
+fn collect_files(path: &Path) -> Result<Vec<PathBuf>, AppError> {
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    if !path.is_dir() {
+        return Err(AppError::invalid_input(format!("'{}' does not exist", path.display())));
+    }
+
+    let mut files: Vec<PathBuf> = fs::read_dir(path)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.is_file() && matches!(p.extension().and_then(|e| e.to_str()), Some("yml" | "yaml"))
+        })
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn collect_files_filters_yaml_extensions() {
+        let tmp = TempDir::new().unwrap();
+        for name in ["ci.yml", "release.yaml", "README.md"] {
+            fs::write(tmp.path().join(name), "").unwrap();
+        }
+
+        let files = collect_files(tmp.path()).unwrap();
+        let names: Vec<_> =
+            files.iter().map(|p| p.file_name().unwrap().to_str().unwrap()).collect();
+        assert_eq!(names, ["ci.yml", "release.yaml"]);
+    }
+}
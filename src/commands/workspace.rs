@@ -0,0 +1,232 @@
+//! Topic-based workspaces (`gho workspace`).
+//!
+//! A workspace manifest lists the repositories a team checks out together. `discover` fills it
+//! from the repositories of an organization tagged with a topic, so the list follows how
+//! repositories are tagged on GitHub; `sync` clones whatever is missing next to the manifest.
+
+use crate::commands::{account, manifest};
+use crate::domain::{GITHUB_HOST, build_clone_url, parse_repo_spec};
+use crate::error::AppError;
+use crate::github::{GitHubClient, ReposApi};
+use crate::logging;
+use crate::models::{
+    Execution, ExecutionMode, ProgressOutcome, SearchSort, WorkspaceCheckout, WorkspaceDiscovery,
+    WorkspaceManifest, WorkspaceRepo,
+};
+use crate::progress::Progress;
+use crate::storage::Storage;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Manifest file name used when no `--file` is given.
+pub const MANIFEST_FILE: &str = "gho-workspace.toml";
+
+/// Operation name of `workspace sync` progress events.
+const SYNC_OPERATION: &str = "workspace sync";
+
+/// Create or update the manifest at `path` with the non-archived repositories of `org` that
+/// have `topic`.
+///
+/// `org` and `topic` default to the ones saved in the manifest. Repositories that are still
+/// tagged keep their checkout path; ones that lost the topic are dropped from the manifest, but
+/// their checkouts are left alone.
+pub fn discover(
+    storage: &impl Storage,
+    path: &Path,
+    org: Option<&str>,
+    topic: Option<&str>,
+    limit: usize,
+) -> Result<WorkspaceDiscovery, AppError> {
+    let mut manifest = if path.exists() { load_manifest(path)? } else { Default::default() };
+    let org = org
+        .map(str::to_string)
+        .or(manifest.org.take())
+        .ok_or_else(|| AppError::invalid_input("--org is required for a new workspace manifest"))?;
+    let topic = topic.map(str::to_string).or(manifest.topic.take()).ok_or_else(|| {
+        AppError::invalid_input("--topic is required for a new workspace manifest")
+    })?;
+
+    let (_account, token) = account::get_active_with_token(storage)?;
+    let client = GitHubClient::new(token)?;
+    let query = format!("org:{org} topic:{topic} archived:false");
+    let found: Vec<(String, String)> = client
+        .search_repos(&query, SearchSort::BestMatch, limit)?
+        .items
+        .into_iter()
+        .map(|r| (r.full_name, r.name))
+        .collect();
+
+    let discovery = merge(&mut manifest, found);
+    manifest.org = Some(org);
+    manifest.topic = Some(topic);
+    save_manifest(path, &manifest)?;
+    Ok(discovery)
+}
+
+/// Clone every repository of the manifest at `path` that is not checked out yet.
+///
+/// The checkouts are then recorded in a clone manifest, `gho-clones.json` next to the workspace
+/// manifest unless `clone_manifest` is given, whose path is returned with them.
+pub fn sync(
+    storage: &impl Storage,
+    path: &Path,
+    clone_manifest: Option<&Path>,
+    mode: ExecutionMode,
+    progress: Progress,
+) -> Result<Execution<(Vec<WorkspaceCheckout>, PathBuf)>, AppError> {
+    let manifest = load_manifest(path)?;
+    let (account, _token) = account::get_active_with_token(storage)?;
+    let root = path.parent().map(Path::to_path_buf).unwrap_or_default();
+    let clone_manifest =
+        clone_manifest.map(Path::to_path_buf).unwrap_or_else(|| root.join(manifest::MANIFEST_FILE));
+
+    let host = account.host.as_deref().unwrap_or(GITHUB_HOST);
+    let mut targets = Vec::new();
+    for entry in &manifest.repos {
+        let (owner, repo) = parse_repo_spec(&entry.repo)?;
+        let dir = root.join(&entry.path);
+        targets.push((entry, build_clone_url(host, &owner, &repo, account.protocol), dir));
+    }
+
+    if mode.is_dry_run() {
+        let mut steps: Vec<String> = targets
+            .iter()
+            .filter(|(_, _, dir)| !dir.exists())
+            .map(|(_, url, dir)| format!("git clone {url} {}", dir.display()))
+            .collect();
+        steps.push(format!("write clone manifest {}", clone_manifest.display()));
+        return Ok(Execution::Planned(steps));
+    }
+
+    let total = targets.len();
+    let checkouts: Vec<WorkspaceCheckout> = targets
+        .into_iter()
+        .enumerate()
+        .map(|(i, (entry, url, dir))| {
+            progress.started(SYNC_OPERATION, &entry.repo, i + 1, total);
+            let checkout = checkout(&entry.repo, &url, dir, progress.is_json());
+            let outcome = match (&checkout.error, checkout.cloned) {
+                (Some(_), _) => ProgressOutcome::Failed,
+                (None, true) => ProgressOutcome::Succeeded,
+                (None, false) => ProgressOutcome::Skipped,
+            };
+            progress.completed(SYNC_OPERATION, &entry.repo, outcome, checkout.error.as_deref());
+            checkout
+        })
+        .collect();
+
+    let cloned: Vec<(String, PathBuf)> =
+        checkouts.iter().map(|c| (c.repo.clone(), c.dir.clone())).collect();
+    manifest::write(&clone_manifest, &account.id, &cloned)?;
+    Ok(Execution::Done((checkouts, clone_manifest)))
+}
+
+/// Read a workspace manifest.
+pub fn load_manifest(path: &Path) -> Result<WorkspaceManifest, AppError> {
+    let content = fs::read_to_string(path).map_err(|e| {
+        AppError::invalid_input(format!("cannot read workspace manifest {}: {e}", path.display()))
+    })?;
+    toml::from_str(&content).map_err(|e| {
+        AppError::invalid_input(format!("invalid {}: {}", path.display(), e.message()))
+    })
+}
+
+fn save_manifest(path: &Path, manifest: &WorkspaceManifest) -> Result<(), AppError> {
+    let content = toml::to_string_pretty(manifest)
+        .map_err(|e| AppError::config(format!("failed to serialize workspace manifest: {e}")))?;
+    fs::write(path, format!("# Updated by `gho workspace discover`.\n{content}"))?;
+    Ok(())
+}
+
+/// Replace the manifest's repositories with `found` (`(owner/repo, name)` pairs), keeping the
+/// paths of repositories it already lists.
+fn merge(manifest: &mut WorkspaceManifest, found: Vec<(String, String)>) -> WorkspaceDiscovery {
+    let mut discovery = WorkspaceDiscovery::default();
+    let mut repos: Vec<WorkspaceRepo> = found
+        .into_iter()
+        .map(|(repo, name)| match manifest.repos.iter().find(|r| r.repo == repo) {
+            Some(existing) => existing.clone(),
+            None => {
+                discovery.added.push(repo.clone());
+                WorkspaceRepo { repo, path: name }
+            }
+        })
+        .collect();
+    repos.sort_by(|a, b| a.repo.cmp(&b.repo));
+
+    discovery.removed = manifest
+        .repos
+        .iter()
+        .filter(|r| !repos.iter().any(|kept| kept.repo == r.repo))
+        .map(|r| r.repo.clone())
+        .collect();
+    discovery.added.sort();
+    discovery.total = repos.len();
+    manifest.repos = repos;
+    discovery
+}
+
+fn checkout(repo: &str, url: &str, dir: PathBuf, quiet: bool) -> WorkspaceCheckout {
+    let mut checkout =
+        WorkspaceCheckout { repo: repo.to_string(), dir, cloned: false, error: None };
+    if checkout.dir.exists() {
+        return checkout;
+    }
+
+    let mut command = Command::new("git");
+    command.arg("clone");
+    if quiet {
+        command.arg("--quiet");
+    }
+    match logging::command(command.arg(url).arg(&checkout.dir)).status() {
+        Ok(status) if status.success() => checkout.cloned = true,
+        Ok(status) => checkout.error = Some(format!("git clone failed with status {status}")),
+        Err(e) => checkout.error = Some(format!("failed to run git: {e}")),
+    }
+    checkout
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(repo: &str, path: &str) -> WorkspaceRepo {
+        WorkspaceRepo { repo: repo.to_string(), path: path.to_string() }
+    }
+
+    #[test]
+    fn merge_keeps_paths_and_reports_changes() {
+        let mut manifest = WorkspaceManifest {
+            org: Some("acme".into()),
+            topic: Some("team-payments".into()),
+            repos: vec![entry("acme/api", "services/api"), entry("acme/legacy", "legacy")],
+        };
+        let found = vec![
+            ("acme/web".to_string(), "web".to_string()),
+            ("acme/api".to_string(), "api".to_string()),
+        ];
+
+        let discovery = merge(&mut manifest, found);
+        assert_eq!(discovery.added, ["acme/web"]);
+        assert_eq!(discovery.removed, ["acme/legacy"]);
+        assert_eq!(discovery.total, 2);
+        assert_eq!(manifest.repos, [entry("acme/api", "services/api"), entry("acme/web", "web")]);
+    }
+
+    #[test]
+    fn manifest_roundtrips_through_toml() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(MANIFEST_FILE);
+        let manifest = WorkspaceManifest {
+            org: Some("acme".into()),
+            topic: Some("team-payments".into()),
+            repos: vec![entry("acme/api", "api")],
+        };
+        save_manifest(&path, &manifest).unwrap();
+        assert_eq!(load_manifest(&path).unwrap(), manifest);
+
+        fs::write(&path, "[[repo]]\nrepo = \"acme/api\"\n").unwrap();
+        assert!(load_manifest(&path).is_err());
+    }
+}
@@ -1,6 +1,9 @@
 //! Application configuration for gho.
 
 use crate::error::AppError;
+use crate::platform;
+use serde::{Deserialize, Serialize};
+use std::fs;
 use std::path::PathBuf;
 
 /// Application-wide configuration.
@@ -8,22 +11,35 @@ use std::path::PathBuf;
 pub struct Config {
     /// Base path for configuration files.
     pub config_path: PathBuf,
+    /// User preferences from `config.toml`.
+    pub settings: Settings,
 }
 
 impl Config {
-    /// Create a new configuration with custom config path.
+    /// Create a new configuration with custom config path and default settings.
     pub fn with_path(path: PathBuf) -> Self {
-        Self { config_path: path }
+        Self { config_path: path, settings: Settings::default() }
     }
 
-    /// Create configuration using the HOME-based config directory.
+    /// Create a configuration at `path`, reading `config.toml` if present.
+    pub fn load(path: PathBuf) -> Result<Self, AppError> {
+        let mut config = Self::with_path(path);
+        let settings_path = config.settings_path();
+        if settings_path.exists() {
+            let content = fs::read_to_string(&settings_path)?;
+            config.settings = toml::from_str(&content).map_err(|e| {
+                AppError::config(format!("invalid {}: {}", settings_path.display(), e.message()))
+            })?;
+        }
+        Ok(config)
+    }
+
+    /// Create configuration using the config directory under the home directory.
     ///
-    /// Uses $HOME/.config/gho for consistency across platforms and tests.
+    /// Uses ~/.config/gho on every platform (`%USERPROFILE%\.config\gho` on Windows), for
+    /// consistency across platforms and tests.
     pub fn new_default() -> Result<Self, AppError> {
-        let home = std::env::var("HOME")
-            .map_err(|_| AppError::config("HOME environment variable not set"))?;
-        let config_path = PathBuf::from(home).join(".config").join("gho");
-        Ok(Self { config_path })
+        Self::load(platform::home_dir()?.join(".config").join("gho"))
     }
 
     /// Path to the accounts file.
@@ -35,4 +51,338 @@ impl Config {
     pub fn state_path(&self) -> PathBuf {
         self.config_path.join("state.json")
     }
+
+    /// Path to the command history (one JSON entry per line).
+    pub fn history_path(&self) -> PathBuf {
+        self.config_path.join("history.jsonl")
+    }
+
+    /// Directory for `gho daemon` snapshots, pid file and log.
+    pub fn cache_path(&self) -> PathBuf {
+        self.config_path.join("cache")
+    }
+
+    /// Path to the user settings file.
+    pub fn settings_path(&self) -> PathBuf {
+        self.config_path.join("config.toml")
+    }
+
+    /// Write the current settings to `config.toml`.
+    pub fn save_settings(&self) -> Result<(), AppError> {
+        fs::create_dir_all(&self.config_path)?;
+        let content = toml::to_string_pretty(&self.settings)
+            .map_err(|e| AppError::config(format!("failed to serialize settings: {e}")))?;
+        fs::write(self.settings_path(), content)?;
+        Ok(())
+    }
+}
+
+/// Output format used when a command is not given `--json`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+/// When to emit colored output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorChoice {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+/// Whether `GH_TOKEN`/`GITHUB_TOKEN` replace the stored token of the active account.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenPrecedence {
+    /// The environment wins when set, with a warning naming the account it overrides.
+    #[default]
+    Auto,
+    /// Always use the account's Keychain entry or token command; the environment is ignored.
+    Keychain,
+    /// Only use the environment; commands fail when neither variable is set.
+    Env,
+}
+
+/// User preferences stored in `config.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Default `--limit` for list and search commands.
+    pub limit: usize,
+    /// Default output format.
+    pub format: OutputFormat,
+    /// Number of parallel `git clone` processes for bulk clones.
+    pub clone_jobs: usize,
+    /// Colored output.
+    pub color: ColorChoice,
+    /// Editor for composing messages; falls back to `$VISUAL`, `$EDITOR`, then `vi`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub editor: Option<String>,
+    /// Command `pr diff` pipes diffs into instead of the pager, e.g. `delta`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff_tool: Option<String>,
+    /// Command template run by `pr diff --review`; `{diff}` is a file holding the diff.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub review_command: Option<String>,
+    /// Warn when the active account's token expires within this many days (0 disables).
+    pub token_expiry_warning_days: u32,
+    /// API root to use instead of `https://api.github.com`; `GHO_API_BASE` overrides it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_base: Option<String>,
+    /// Keychain service holding tokens; changed with `gho account migrate-keychain`.
+    pub keychain_service: String,
+    /// Whether token environment variables override stored tokens; `--no-env-token` forces
+    /// `keychain` for one run.
+    pub token_source: TokenPrecedence,
+    /// Swap the SSH keys in ssh-agent on every `account use`, as with `--ssh-agent`.
+    pub ssh_agent: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            limit: 30,
+            format: OutputFormat::default(),
+            clone_jobs: 4,
+            color: ColorChoice::default(),
+            editor: None,
+            diff_tool: None,
+            review_command: None,
+            token_expiry_warning_days: 7,
+            api_base: None,
+            keychain_service: "gho".to_string(),
+            token_source: TokenPrecedence::default(),
+            ssh_agent: false,
+        }
+    }
+}
+
+impl Settings {
+    /// Keys accepted by [`Settings::get`] and [`Settings::set`].
+    pub const KEYS: [&'static str; 12] = [
+        "limit",
+        "format",
+        "clone_jobs",
+        "color",
+        "editor",
+        "diff_tool",
+        "review_command",
+        "token_expiry_warning_days",
+        "api_base",
+        "keychain_service",
+        "token_source",
+        "ssh_agent",
+    ];
+
+    /// Read a setting as a string.
+    pub fn get(&self, key: &str) -> Result<String, AppError> {
+        let value = match key {
+            "limit" => self.limit.to_string(),
+            "format" => enum_value(&self.format),
+            "clone_jobs" => self.clone_jobs.to_string(),
+            "color" => enum_value(&self.color),
+            "editor" => self.editor.clone().unwrap_or_default(),
+            "diff_tool" => self.diff_tool.clone().unwrap_or_default(),
+            "review_command" => self.review_command.clone().unwrap_or_default(),
+            "token_expiry_warning_days" => self.token_expiry_warning_days.to_string(),
+            "api_base" => self.api_base.clone().unwrap_or_default(),
+            "keychain_service" => self.keychain_service.clone(),
+            "token_source" => enum_value(&self.token_source),
+            "ssh_agent" => self.ssh_agent.to_string(),
+            _ => return Err(unknown_key(key)),
+        };
+        Ok(value)
+    }
+
+    /// Parse and assign a setting. An empty `editor`, `diff_tool`, `review_command` or `api_base`
+    /// clears it.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), AppError> {
+        match key {
+            "limit" => self.limit = parse_positive(key, value)?,
+            "format" => self.format = parse_enum(key, value, "text, json")?,
+            "clone_jobs" => self.clone_jobs = parse_positive(key, value)?,
+            "color" => self.color = parse_enum(key, value, "auto, always, never")?,
+            "editor" => self.editor = optional(value),
+            "diff_tool" => self.diff_tool = optional(value),
+            "review_command" => {
+                if !value.trim().is_empty() && !value.contains("{diff}") {
+                    return Err(AppError::invalid_input(
+                        "review_command must contain {diff}, the file holding the diff",
+                    ));
+                }
+                self.review_command = optional(value);
+            }
+            "token_expiry_warning_days" => {
+                self.token_expiry_warning_days = value.parse().map_err(|_| {
+                    AppError::invalid_input(format!(
+                        "invalid value '{value}' for {key}, expected a number of days"
+                    ))
+                })?;
+            }
+            "api_base" => {
+                let base = value.trim();
+                if !base.is_empty() && !base.starts_with("https://") && !base.starts_with("http://")
+                {
+                    return Err(AppError::invalid_input(format!(
+                        "invalid value '{value}' for api_base, expected an http(s) URL"
+                    )));
+                }
+                self.api_base = optional(base.trim_end_matches('/'));
+            }
+            // Renaming the service without moving the tokens would orphan them.
+            "keychain_service" => {
+                return Err(AppError::invalid_input(
+                    "keychain_service is changed with `gho account migrate-keychain --to-service`",
+                ));
+            }
+            "token_source" => {
+                self.token_source = parse_enum(key, value, "auto, keychain, env")?;
+            }
+            "ssh_agent" => {
+                self.ssh_agent = value.to_lowercase().parse().map_err(|_| {
+                    AppError::invalid_input(format!(
+                        "invalid value '{value}' for {key}, expected true or false"
+                    ))
+                })?;
+            }
+            _ => return Err(unknown_key(key)),
+        }
+        Ok(())
+    }
+
+    /// Whether a command should print JSON, given its `--json` flag.
+    pub fn wants_json(&self, flag: bool) -> bool {
+        flag || self.format == OutputFormat::Json
+    }
+
+    /// Whether to color output going to a terminal (`is_terminal`); `auto` honors `NO_COLOR`.
+    pub fn use_color(&self, is_terminal: bool) -> bool {
+        match self.color {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => is_terminal && std::env::var_os("NO_COLOR").is_none(),
+        }
+    }
+
+    /// Resolve the editor command.
+    pub fn editor(&self) -> String {
+        self.editor
+            .clone()
+            .or_else(|| std::env::var("VISUAL").ok().filter(|v| !v.is_empty()))
+            .or_else(|| std::env::var("EDITOR").ok().filter(|v| !v.is_empty()))
+            .unwrap_or_else(|| "vi".to_string())
+    }
+}
+
+fn enum_value<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn parse_enum<T: for<'de> Deserialize<'de>>(
+    key: &str,
+    value: &str,
+    expected: &str,
+) -> Result<T, AppError> {
+    serde_json::from_value(serde_json::Value::String(value.to_lowercase())).map_err(|_| {
+        AppError::invalid_input(format!("invalid value '{value}' for {key}, expected {expected}"))
+    })
+}
+
+fn optional(value: &str) -> Option<String> {
+    Some(value.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+fn parse_positive(key: &str, value: &str) -> Result<usize, AppError> {
+    value.parse().ok().filter(|n| *n > 0).ok_or_else(|| {
+        AppError::invalid_input(format!("invalid value '{value}' for {key}, expected a number > 0"))
+    })
+}
+
+fn unknown_key(key: &str) -> AppError {
+    AppError::invalid_input(format!(
+        "unknown config key '{key}', expected one of: {}",
+        Settings::KEYS.join(", ")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn set_and_get_roundtrip() {
+        let mut settings = Settings::default();
+        settings.set("limit", "50").unwrap();
+        settings.set("format", "JSON").unwrap();
+        settings.set("editor", "nvim").unwrap();
+
+        assert_eq!(settings.get("limit").unwrap(), "50");
+        assert_eq!(settings.get("format").unwrap(), "json");
+        assert_eq!(settings.get("editor").unwrap(), "nvim");
+        assert!(settings.wants_json(false));
+
+        settings.set("editor", "").unwrap();
+        assert_eq!(settings.editor, None);
+
+        settings.set("diff_tool", "delta --side-by-side").unwrap();
+        assert_eq!(settings.get("diff_tool").unwrap(), "delta --side-by-side");
+        assert!(settings.set("review_command", "nvim -R").is_err());
+        settings.set("review_command", "nvim -R {diff}").unwrap();
+        assert_eq!(settings.review_command.as_deref(), Some("nvim -R {diff}"));
+
+        assert!(settings.set("api_base", "ghe.acme.com/api/v3").is_err());
+        settings.set("api_base", "https://ghe.acme.com/api/v3/").unwrap();
+        assert_eq!(settings.get("api_base").unwrap(), "https://ghe.acme.com/api/v3");
+
+        settings.set("token_source", "Keychain").unwrap();
+        assert_eq!(settings.token_source, TokenPrecedence::Keychain);
+        assert_eq!(settings.get("token_source").unwrap(), "keychain");
+        assert!(settings.set("token_source", "vault").is_err());
+
+        settings.set("ssh_agent", "True").unwrap();
+        assert!(settings.ssh_agent);
+        assert!(settings.set("ssh_agent", "yes").is_err());
+    }
+
+    #[test]
+    fn set_rejects_invalid_values() {
+        let mut settings = Settings::default();
+        assert!(settings.set("limit", "0").is_err());
+        assert!(settings.set("color", "sometimes").is_err());
+        assert!(settings.set("nope", "1").is_err());
+        assert!(settings.set("keychain_service", "other").is_err());
+        assert!(settings.get("nope").is_err());
+    }
+
+    #[test]
+    fn load_reads_partial_file_with_defaults() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join("config.toml"), "limit = 10\ncolor = \"never\"\n").unwrap();
+
+        let config = Config::load(tmp.path().to_path_buf()).unwrap();
+        assert_eq!(config.settings.limit, 10);
+        assert_eq!(config.settings.color, ColorChoice::Never);
+        assert_eq!(config.settings.clone_jobs, 4);
+    }
+
+    #[test]
+    fn save_settings_roundtrip() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = Config::with_path(tmp.path().join("gho"));
+        config.settings.set("clone_jobs", "8").unwrap();
+        config.save_settings().unwrap();
+
+        let loaded = Config::load(tmp.path().join("gho")).unwrap();
+        assert_eq!(loaded.settings, config.settings);
+    }
 }
@@ -0,0 +1,840 @@
+//! Pure domain logic shared by the CLI and embedders.
+//!
+//! Nothing in this module touches the filesystem, network, keychain or environment, so it
+//! builds for any target (including `wasm32-unknown-unknown`) with `default-features = false`.
+
+use crate::error::AppError;
+use crate::models::{CloneLayout, Protocol, RepoSort, Repository};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Parse an `owner/repo` specification.
+pub fn parse_repo_spec(spec: &str) -> Result<(String, String), AppError> {
+    let parts: Vec<&str> = spec.split('/').collect();
+    if parts.len() != 2 || parts[0].is_empty() || parts[1].is_empty() {
+        return Err(AppError::invalid_input(format!(
+            "invalid repository format '{}', expected owner/repo",
+            spec
+        )));
+    }
+    Ok((parts[0].to_string(), parts[1].to_string()))
+}
+
+/// A repository argument: `owner/repo`, an SSH or HTTPS URL, or a bare repository name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RepoSpec {
+    /// Owner and repository, given directly or through a URL, which also gives the host.
+    Full { host: Option<String>, owner: String, repo: String },
+    /// A repository name whose owner is left to the caller, e.g. the active account's.
+    Name(String),
+}
+
+impl RepoSpec {
+    /// Parse `owner/repo`, `git@host:owner/repo.git`, `ssh://…`, `https://host/owner/repo`
+    /// or a bare `repo`.
+    pub fn parse(spec: &str) -> Result<Self, AppError> {
+        let spec = spec.trim();
+        if spec.contains("://") || spec.starts_with("git@") {
+            let (host, owner, repo) = parse_remote_url_with_host(spec)
+                .map_err(|_| AppError::invalid_input(format!("invalid repository URL '{spec}'")))?;
+            return Ok(RepoSpec::Full { host: Some(host), owner, repo });
+        }
+        if spec.contains('/') {
+            let (owner, repo) = parse_repo_spec(spec)?;
+            return Ok(RepoSpec::Full { host: None, owner, repo });
+        }
+
+        let name = spec.trim_end_matches(".git");
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || "-_.".contains(c))
+        {
+            return Err(AppError::invalid_input(format!(
+                "invalid repository '{spec}', expected owner/repo, a repository URL or a name"
+            )));
+        }
+        Ok(RepoSpec::Name(name.to_string()))
+    }
+
+    /// Host of a repository given by URL.
+    pub fn host(&self) -> Option<&str> {
+        match self {
+            RepoSpec::Full { host, .. } => host.as_deref(),
+            RepoSpec::Name(_) => None,
+        }
+    }
+
+    /// `(owner, repo)`, asking `default_owner` for the owner of a bare name.
+    pub fn resolve(
+        self,
+        default_owner: impl FnOnce() -> Result<String, AppError>,
+    ) -> Result<(String, String), AppError> {
+        match self {
+            RepoSpec::Full { owner, repo, .. } => Ok((owner, repo)),
+            RepoSpec::Name(repo) => Ok((default_owner()?, repo)),
+        }
+    }
+}
+
+/// Host of github.com repositories.
+pub const GITHUB_HOST: &str = "github.com";
+
+/// Parse a GitHub remote URL into `(owner, repo)`.
+pub fn parse_remote_url(url: &str) -> Result<(String, String), AppError> {
+    match parse_remote_url_with_host(url)? {
+        (host, owner, repo) if host == GITHUB_HOST => Ok((owner, repo)),
+        _ => Err(AppError::git(format!("unrecognized remote URL format: {url}"))),
+    }
+}
+
+/// Parse a remote URL on any host (github.com or Enterprise) into `(host, owner, repo)`.
+///
+/// Accepts `git@host:owner/repo.git`, `ssh://git@host/owner/repo.git` and
+/// `https://host/owner/repo.git`.
+pub fn parse_remote_url_with_host(url: &str) -> Result<(String, String, String), AppError> {
+    let unrecognized = || AppError::git(format!("unrecognized remote URL format: {url}"));
+
+    let (host, path) =
+        if let Some(rest) = url.strip_prefix("https://").or_else(|| url.strip_prefix("ssh://")) {
+            let rest = rest.rsplit_once('@').map_or(rest, |(_, r)| r);
+            rest.split_once('/').ok_or_else(unrecognized)?
+        } else if let Some((user_host, path)) = url.split_once(':') {
+            let host = user_host.rsplit_once('@').map_or(user_host, |(_, h)| h);
+            (host, path)
+        } else {
+            return Err(unrecognized());
+        };
+
+    let host = host.split(':').next().unwrap_or(host);
+    if host.is_empty() {
+        return Err(unrecognized());
+    }
+    let (owner, repo) = parse_repo_spec(path.trim_end_matches('/').trim_end_matches(".git"))
+        .map_err(|_| unrecognized())?;
+    Ok((host.to_string(), owner, repo))
+}
+
+/// Map `Host` aliases in an OpenSSH client config to their `HostName`.
+///
+/// Patterns with wildcards are skipped; aliases without a `HostName` map to themselves.
+pub fn parse_ssh_config_aliases(content: &str) -> BTreeMap<String, String> {
+    let mut aliases = BTreeMap::new();
+    let mut current: Vec<String> = Vec::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (key, value) = match line.split_once(|c: char| c == '=' || c.is_whitespace()) {
+            Some((key, value)) => (key, value.trim_start_matches(['=', ' ', '\t']).trim()),
+            None => continue,
+        };
+        if key.eq_ignore_ascii_case("host") {
+            current = value
+                .split_whitespace()
+                .filter(|p| !p.contains(['*', '?', '!']))
+                .map(str::to_string)
+                .collect();
+            for alias in &current {
+                aliases.entry(alias.clone()).or_insert_with(|| alias.clone());
+            }
+        } else if key.eq_ignore_ascii_case("match") {
+            current.clear();
+        } else if key.eq_ignore_ascii_case("hostname") {
+            for alias in &current {
+                aliases.insert(alias.clone(), value.to_string());
+            }
+        }
+    }
+    aliases.retain(|alias, host| alias != host);
+    aliases
+}
+
+/// Parse a remote URL into `(host, owner, repo)`, resolving SSH aliases (see
+/// [`parse_ssh_config_aliases`]) to their real hostname, so `git@github-work:owner/repo.git`
+/// is recognized as a github.com remote.
+pub fn resolve_remote_url(
+    url: &str,
+    aliases: &BTreeMap<String, String>,
+) -> Result<(String, String, String), AppError> {
+    let (host, owner, repo) = parse_remote_url_with_host(url)?;
+    let host = aliases.get(&host).cloned().unwrap_or(host);
+    Ok((host, owner, repo))
+}
+
+/// Web URL of a repository, optionally pointing at a branch or a file.
+///
+/// A file path without a branch links to the default branch via `HEAD`.
+pub fn repo_web_url(
+    host: &str,
+    owner: &str,
+    repo: &str,
+    branch: Option<&str>,
+    path: Option<&str>,
+) -> String {
+    let base = format!("https://{host}/{owner}/{repo}");
+    match (branch, path) {
+        (branch, Some(path)) => {
+            format!("{base}/blob/{}/{}", branch.unwrap_or("HEAD"), path.trim_start_matches('/'))
+        }
+        (Some(branch), None) => format!("{base}/tree/{branch}"),
+        (None, None) => base,
+    }
+}
+
+/// Web page of a notification thread's subject, derived from its API URL.
+///
+/// Issues, pull requests and commits link to their own page; other subjects (releases, check
+/// suites, discussions) fall back to the repository.
+pub fn notification_web_url(repo_full_name: &str, subject_url: Option<&str>) -> String {
+    let repo_url = format!("https://{GITHUB_HOST}/{repo_full_name}");
+    let api_prefix = format!("https://api.{GITHUB_HOST}/repos/{repo_full_name}/");
+    match subject_url.and_then(|u| u.strip_prefix(&api_prefix)).and_then(|p| p.split_once('/')) {
+        Some(("issues", number)) => format!("{repo_url}/issues/{number}"),
+        Some(("pulls", number)) => format!("{repo_url}/pull/{number}"),
+        Some(("commits", sha)) => format!("{repo_url}/commit/{sha}"),
+        _ => repo_url,
+    }
+}
+
+/// Build the clone URL for a repository on `host` in the given protocol.
+pub fn build_clone_url(host: &str, owner: &str, repo: &str, protocol: Protocol) -> String {
+    match protocol {
+        Protocol::Ssh => format!("git@{host}:{owner}/{repo}.git"),
+        Protocol::Https => format!("https://{host}/{owner}/{repo}.git"),
+    }
+}
+
+/// Build the clone URL for a gist in the given protocol.
+pub fn build_gist_clone_url(id: &str, protocol: Protocol) -> String {
+    match protocol {
+        Protocol::Ssh => format!("git@gist.github.com:{id}.git"),
+        Protocol::Https => format!("https://gist.github.com/{id}.git"),
+    }
+}
+
+/// `url`, a remote of `from` (`owner/repo`), pointed at `to` instead, keeping its protocol,
+/// host or SSH alias and `.git` suffix; `None` when `url` is not a remote of `from`.
+pub fn moved_remote_url(url: &str, from: &str, to: &str) -> Option<String> {
+    let start = url.to_ascii_lowercase().rfind(&from.to_ascii_lowercase())?;
+    let rest = &url[start + from.len()..];
+    let separated = url[..start].ends_with(['/', ':']);
+    (separated && matches!(rest, "" | ".git" | "/")).then(|| format!("{}{to}{rest}", &url[..start]))
+}
+
+/// Directory a repository is cloned into, relative to the account's clone directory.
+pub fn clone_target_dir(
+    clone_dir: Option<&str>,
+    layout: CloneLayout,
+    host: &str,
+    owner: &str,
+    repo: &str,
+) -> PathBuf {
+    let base = clone_dir.map(Path::new).unwrap_or(Path::new(""));
+    match layout {
+        CloneLayout::Flat => base.join(repo),
+        CloneLayout::OwnerRepo => base.join(owner).join(repo),
+        CloneLayout::HostOwnerRepo => base.join(host).join(owner).join(repo),
+    }
+}
+
+/// Match `name` against a shell-style pattern supporting `*` and `?`.
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` and the name index it is currently matched up to.
+    let mut star: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    p = star_p + 1;
+                    n = star_n + 1;
+                    star = Some((star_p, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Split a unified diff into `(path, section)` pairs, one per `diff --git` header.
+///
+/// The path is the post-image (`b/`) path, so renamed files are listed under their new name.
+pub fn split_diff(diff: &str) -> Vec<(String, &str)> {
+    let mut starts: Vec<usize> = diff
+        .match_indices("diff --git ")
+        .map(|(i, _)| i)
+        .filter(|&i| i == 0 || diff.as_bytes()[i - 1] == b'\n')
+        .collect();
+    starts.push(diff.len());
+    starts
+        .windows(2)
+        .map(|w| {
+            let section = &diff[w[0]..w[1]];
+            let header = section.lines().next().unwrap_or_default();
+            let path = header.rsplit_once(" b/").map_or(header, |(_, path)| path);
+            (path.to_string(), section)
+        })
+        .collect()
+}
+
+/// Color a unified diff with ANSI escapes: file headers bold, hunk headers cyan, added lines
+/// green and removed lines red.
+pub fn colorize_diff(diff: &str) -> String {
+    let mut out = String::with_capacity(diff.len() + diff.len() / 8);
+    for line in diff.split_inclusive('\n') {
+        let (text, newline) = match line.strip_suffix('\n') {
+            Some(text) => (text, "\n"),
+            None => (line, ""),
+        };
+        let color = if text.starts_with("diff --git")
+            || text.starts_with("+++")
+            || text.starts_with("---")
+            || text.starts_with("index ")
+        {
+            "1"
+        } else if text.starts_with("@@") {
+            "36"
+        } else if text.starts_with('+') {
+            "32"
+        } else if text.starts_with('-') {
+            "31"
+        } else {
+            ""
+        };
+        if color.is_empty() {
+            out.push_str(line);
+        } else {
+            out.push_str(&format!("\x1b[{color}m{text}\x1b[0m{newline}"));
+        }
+    }
+    out
+}
+
+/// Longest description shown in the `repo list` table.
+const REPO_DESCRIPTION_CHARS: usize = 60;
+
+/// Sort `repo list` items, whose repository `repo` returns; ties keep the API order.
+pub fn sort_repos<T>(items: &mut [T], sort: RepoSort, repo: impl Fn(&T) -> &Repository) {
+    match sort {
+        RepoSort::Pushed => items.sort_by(|a, b| repo(b).pushed_at.cmp(&repo(a).pushed_at)),
+        RepoSort::Stars => items.sort_by_key(|r| Reverse(repo(r).stargazers_count)),
+        RepoSort::Name => items.sort_by_key(|r| repo(r).full_name.to_lowercase()),
+    }
+}
+
+/// Render repositories as an aligned table with a header row, optionally with ANSI colors:
+/// names bold, private repositories yellow, archived ones dimmed.
+pub fn repo_table(repos: &[Repository], color: bool) -> String {
+    let rows: Vec<[String; 7]> = repos
+        .iter()
+        .map(|r| {
+            let mut visibility = r.visibility.clone().unwrap_or_else(|| "-".to_string());
+            if r.fork {
+                visibility.push_str(" fork");
+            }
+            if r.archived {
+                visibility.push_str(" archived");
+            }
+            let description = r.description.as_deref().unwrap_or("").trim();
+            [
+                r.full_name.clone(),
+                visibility,
+                r.language.clone().unwrap_or_default(),
+                r.stargazers_count.to_string(),
+                r.open_issues_count.to_string(),
+                r.pushed_at.as_deref().and_then(|p| p.get(..10)).unwrap_or("-").to_string(),
+                truncate(description, REPO_DESCRIPTION_CHARS),
+            ]
+        })
+        .collect();
+    let header = ["NAME", "VISIBILITY", "LANGUAGE", "STARS", "ISSUES", "PUSHED", "DESCRIPTION"]
+        .map(String::from);
+
+    let mut widths = header.clone().map(|h| h.chars().count());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let paint = |text: String, code: &str| {
+        if color && !code.is_empty() && !text.is_empty() {
+            format!("\x1b[{code}m{text}\x1b[0m")
+        } else {
+            text
+        }
+    };
+    let mut out = String::new();
+    for (i, row) in std::iter::once(&header).chain(&rows).enumerate() {
+        let repo = i.checked_sub(1).map(|i| &repos[i]);
+        let cells: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .enumerate()
+            .map(|(column, (cell, width))| {
+                // Numbers are right-aligned; the last column is not padded.
+                let text = match column {
+                    3 | 4 => format!("{cell:>width$}"),
+                    6 => cell.clone(),
+                    _ => format!("{cell:<width$}"),
+                };
+                let code = match (repo, column) {
+                    (None, _) => "1",
+                    (Some(r), _) if r.archived => "2",
+                    (Some(_), 0) => "1",
+                    (Some(r), 1) if r.visibility.as_deref() != Some("public") => "33",
+                    (Some(_), 2) => "36",
+                    _ => "",
+                };
+                paint(text, code)
+            })
+            .collect();
+        out.push_str(cells.join("  ").trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max_chars - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+/// Parse a token expiry given as `YYYY-MM-DD` (end of that day, UTC) or RFC 3339.
+pub fn parse_expiry(value: &str) -> Result<DateTime<Utc>, AppError> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(23, 59, 59).unwrap_or_default().and_utc());
+    }
+    DateTime::parse_from_rfc3339(value).map(|t| t.with_timezone(&Utc)).map_err(|_| {
+        AppError::invalid_input(format!("invalid expiry '{value}', expected YYYY-MM-DD"))
+    })
+}
+
+/// Parse the `GitHub-Authentication-Token-Expiration` header, e.g. `2026-11-01 08:00:00 UTC`
+/// or `2026-11-01 00:00:00 -0800`.
+pub fn parse_token_expiration_header(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Some(naive) = value.strip_suffix(" UTC") {
+        return NaiveDateTime::parse_from_str(naive, "%Y-%m-%d %H:%M:%S").ok().map(|t| t.and_utc());
+    }
+    DateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S %z").ok().map(|t| t.with_timezone(&Utc))
+}
+
+/// Program and arguments of a browser command template, with placeholders filled in.
+///
+/// Words are split on whitespace; single or double quotes keep spaces inside a word, e.g.
+/// `chrome --profile-directory="Profile 1"`. `{account}` becomes the account ID and `{url}`
+/// the URL, which is appended as the last argument when the template has no `{url}`.
+pub fn browser_command(template: &str, url: &str, account: &str) -> Result<Vec<String>, AppError> {
+    let words = split_command_words(template)?;
+    if words.is_empty() {
+        return Err(AppError::invalid_input("browser command is empty"));
+    }
+    let mut command: Vec<String> =
+        words.iter().map(|w| w.replace("{account}", account).replace("{url}", url)).collect();
+    if !template.contains("{url}") {
+        command.push(url.to_string());
+    }
+    Ok(command)
+}
+
+/// Join command-line arguments into one line a shell would split back into the same words.
+///
+/// Arguments with whitespace, quotes or other shell syntax are single-quoted.
+pub fn shell_join(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| {
+            let plain = !arg.is_empty()
+                && arg.chars().all(|c| c.is_ascii_alphanumeric() || "-_./:=@,+%".contains(c));
+            if plain { arg.clone() } else { format!("'{}'", arg.replace('\'', r"'\''")) }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn split_command_words(template: &str) -> Result<Vec<String>, AppError> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut quote = None;
+    for c in template.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => word.get_or_insert_with(String::new).push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                word.get_or_insert_with(String::new);
+            }
+            (None, c) if c.is_whitespace() => words.extend(word.take()),
+            (None, c) => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if quote.is_some() {
+        return Err(AppError::invalid_input(format!(
+            "unterminated quote in browser command '{template}'"
+        )));
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repo(full_name: &str, stars: u64, pushed_at: &str) -> Repository {
+        serde_json::from_value(serde_json::json!({
+            "name": full_name.split('/').nth(1), "full_name": full_name, "html_url": "",
+            "ssh_url": "", "clone_url": "", "owner": {"login": "acme"},
+            "stargazers_count": stars, "pushed_at": pushed_at, "visibility": "private",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn sort_repos_by_stars_and_name() {
+        let mut repos = vec![
+            repo("acme/web", 3, "2026-01-02T00:00:00Z"),
+            repo("acme/API", 9, "2026-01-01T00:00:00Z"),
+            repo("acme/cli", 5, "2026-01-03T00:00:00Z"),
+        ];
+        let names = |repos: &[Repository]| -> Vec<String> {
+            repos.iter().map(|r| r.full_name.clone()).collect()
+        };
+        sort_repos(&mut repos, RepoSort::Stars, |r| r);
+        assert_eq!(names(&repos), ["acme/API", "acme/cli", "acme/web"]);
+        sort_repos(&mut repos, RepoSort::Pushed, |r| r);
+        assert_eq!(names(&repos), ["acme/cli", "acme/web", "acme/API"]);
+        sort_repos(&mut repos, RepoSort::Name, |r| r);
+        assert_eq!(names(&repos), ["acme/API", "acme/cli", "acme/web"]);
+    }
+
+    #[test]
+    fn repo_table_aligns_columns() {
+        let mut api = repo("acme/api", 12, "2026-01-01T00:00:00Z");
+        api.archived = true;
+        api.description = Some("x".repeat(80));
+        let table = repo_table(&[api, repo("acme/website", 3, "2026-02-01T00:00:00Z")], false);
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines[0].starts_with("NAME          VISIBILITY        LANGUAGE  STARS  ISSUES"));
+        assert!(
+            lines[1]
+                .starts_with("acme/api      private archived               12       0  2026-01-01")
+        );
+        assert!(lines[1].ends_with(&format!("{}…", "x".repeat(59))));
+        assert_eq!(lines[2], "acme/website  private                         3       0  2026-02-01");
+        assert!(repo_table(&[repo("acme/api", 0, "")], true).contains("\x1b[1macme/api"));
+    }
+
+    #[test]
+    fn shell_join_quotes_only_when_needed() {
+        let args: Vec<String> =
+            ["pr", "create", "--title", "Fix it's build", "--body", ""].map(String::from).into();
+        assert_eq!(shell_join(&args), r"pr create --title 'Fix it'\''s build' --body ''");
+        assert_eq!(shell_join(&["repo".into(), "acme/api".into()]), "repo acme/api");
+    }
+
+    #[test]
+    fn parse_repo_spec_valid() {
+        let (owner, repo) = parse_repo_spec("octocat/hello-world").unwrap();
+        assert_eq!(owner, "octocat");
+        assert_eq!(repo, "hello-world");
+    }
+
+    #[test]
+    fn repo_spec_accepts_urls_and_bare_names() {
+        let full = |host: Option<&str>, owner: &str, repo: &str| RepoSpec::Full {
+            host: host.map(str::to_string),
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+        };
+        let github = Some(GITHUB_HOST);
+        assert_eq!(RepoSpec::parse("acme/api").unwrap(), full(None, "acme", "api"));
+        assert_eq!(
+            RepoSpec::parse("git@github.com:acme/api.git").unwrap(),
+            full(github, "acme", "api")
+        );
+        assert_eq!(
+            RepoSpec::parse("https://github.com/acme/api").unwrap(),
+            full(github, "acme", "api")
+        );
+        let enterprise = RepoSpec::parse("ssh://git@ghe.acme.com/platform/web.git").unwrap();
+        assert_eq!(enterprise.host(), Some("ghe.acme.com"));
+        assert_eq!(enterprise, full(Some("ghe.acme.com"), "platform", "web"));
+        assert_eq!(RepoSpec::parse("api.git").unwrap(), RepoSpec::Name("api".to_string()));
+        assert!(RepoSpec::parse("https://github.com/acme").is_err());
+        assert!(RepoSpec::parse("my repo").is_err());
+        assert!(RepoSpec::parse("").is_err());
+
+        let resolved = RepoSpec::parse("api").unwrap().resolve(|| Ok("acme".to_string()));
+        assert_eq!(resolved.unwrap(), ("acme".to_string(), "api".to_string()));
+    }
+
+    #[test]
+    fn parse_repo_spec_invalid() {
+        assert!(parse_repo_spec("invalid").is_err());
+        assert!(parse_repo_spec("owner/").is_err());
+    }
+
+    #[test]
+    fn parse_remote_url_ssh() {
+        let (owner, repo) = parse_remote_url("git@github.com:octocat/hello-world.git").unwrap();
+        assert_eq!(owner, "octocat");
+        assert_eq!(repo, "hello-world");
+    }
+
+    #[test]
+    fn parse_remote_url_https() {
+        let (owner, repo) = parse_remote_url("https://github.com/octocat/hello-world.git").unwrap();
+        assert_eq!(owner, "octocat");
+        assert_eq!(repo, "hello-world");
+    }
+
+    #[test]
+    fn parse_remote_url_https_no_git_suffix() {
+        let (owner, repo) = parse_remote_url("https://github.com/octocat/hello-world").unwrap();
+        assert_eq!(owner, "octocat");
+        assert_eq!(repo, "hello-world");
+    }
+
+    #[test]
+    fn parse_remote_url_rejects_enterprise_host() {
+        assert!(parse_remote_url("git@github.acme.com:octocat/hello-world.git").is_err());
+    }
+
+    #[test]
+    fn parse_remote_url_with_host_handles_enterprise_forms() {
+        let expected =
+            ("github.acme.com".to_string(), "octocat".to_string(), "hello-world".to_string());
+        for url in [
+            "git@github.acme.com:octocat/hello-world.git",
+            "ssh://git@github.acme.com:2222/octocat/hello-world.git",
+            "https://github.acme.com/octocat/hello-world",
+            "https://user@github.acme.com/octocat/hello-world.git",
+        ] {
+            assert_eq!(parse_remote_url_with_host(url).unwrap(), expected, "{url}");
+        }
+        assert!(parse_remote_url_with_host("/local/path").is_err());
+    }
+
+    #[test]
+    fn repo_web_url_variants() {
+        let base = "https://github.com/octocat/hello-world";
+        assert_eq!(repo_web_url("github.com", "octocat", "hello-world", None, None), base);
+        assert_eq!(
+            repo_web_url("github.com", "octocat", "hello-world", Some("dev"), None),
+            format!("{base}/tree/dev")
+        );
+        assert_eq!(
+            repo_web_url("github.com", "octocat", "hello-world", None, Some("src/lib.rs")),
+            format!("{base}/blob/HEAD/src/lib.rs")
+        );
+        assert_eq!(
+            repo_web_url("github.com", "octocat", "hello-world", Some("main"), Some("/a.rs")),
+            format!("{base}/blob/main/a.rs")
+        );
+    }
+
+    #[test]
+    fn notification_web_url_maps_api_urls() {
+        let api = "https://api.github.com/repos/acme/api";
+        let url = |subject: Option<&str>| notification_web_url("acme/api", subject);
+        assert_eq!(url(Some(&format!("{api}/pulls/7"))), "https://github.com/acme/api/pull/7");
+        assert_eq!(url(Some(&format!("{api}/issues/3"))), "https://github.com/acme/api/issues/3");
+        assert_eq!(
+            url(Some(&format!("{api}/commits/abc"))),
+            "https://github.com/acme/api/commit/abc"
+        );
+        assert_eq!(url(Some(&format!("{api}/releases/42"))), "https://github.com/acme/api");
+        assert_eq!(url(None), "https://github.com/acme/api");
+    }
+
+    #[test]
+    fn build_clone_url_ssh() {
+        let url = build_clone_url(GITHUB_HOST, "octocat", "hello-world", Protocol::Ssh);
+        assert_eq!(url, "git@github.com:octocat/hello-world.git");
+        let url = build_clone_url("ghe.acme.com", "octocat", "hello-world", Protocol::Ssh);
+        assert_eq!(url, "git@ghe.acme.com:octocat/hello-world.git");
+    }
+
+    #[test]
+    fn build_clone_url_https() {
+        let url = build_clone_url(GITHUB_HOST, "octocat", "hello-world", Protocol::Https);
+        assert_eq!(url, "https://github.com/octocat/hello-world.git");
+    }
+
+    #[test]
+    fn build_gist_clone_url_per_protocol() {
+        assert_eq!(build_gist_clone_url("aa5a31", Protocol::Ssh), "git@gist.github.com:aa5a31.git");
+        assert_eq!(
+            build_gist_clone_url("aa5a31", Protocol::Https),
+            "https://gist.github.com/aa5a31.git"
+        );
+    }
+
+    #[test]
+    fn moved_remote_url_keeps_protocol_and_alias() {
+        let moved = |url| moved_remote_url(url, "acme/api", "platform/api-v2");
+        assert_eq!(
+            moved("git@github-work:Acme/API.git").as_deref(),
+            Some("git@github-work:platform/api-v2.git")
+        );
+        assert_eq!(
+            moved("https://github.com/acme/api").as_deref(),
+            Some("https://github.com/platform/api-v2")
+        );
+        assert_eq!(moved("git@github.com:acme/api-docs.git"), None);
+        assert_eq!(moved("git@github.com:notacme/api.git"), None);
+    }
+
+    #[test]
+    fn clone_target_dir_uses_clone_dir() {
+        let flat = CloneLayout::Flat;
+        assert_eq!(
+            clone_target_dir(Some("/src"), flat, GITHUB_HOST, "acme", "api"),
+            PathBuf::from("/src/api")
+        );
+        assert_eq!(clone_target_dir(None, flat, GITHUB_HOST, "acme", "api"), PathBuf::from("api"));
+    }
+
+    #[test]
+    fn clone_target_dir_namespaces_by_layout() {
+        assert_eq!(
+            clone_target_dir(Some("/src"), CloneLayout::OwnerRepo, GITHUB_HOST, "acme", "api"),
+            PathBuf::from("/src/acme/api")
+        );
+        assert_eq!(
+            clone_target_dir(None, CloneLayout::HostOwnerRepo, GITHUB_HOST, "acme", "api"),
+            PathBuf::from("github.com/acme/api")
+        );
+    }
+
+    #[test]
+    fn glob_match_wildcards() {
+        assert!(glob_match("*.tar.gz", "gho-linux.tar.gz"));
+        assert!(glob_match("gho-*-x86_64.*", "gho-1.2.0-x86_64.zip"));
+        assert!(glob_match("v?.txt", "v1.txt"));
+        assert!(glob_match("*", ""));
+        assert!(!glob_match("*.tar.gz", "gho.zip"));
+        assert!(!glob_match("v?.txt", "v10.txt"));
+    }
+
+    #[test]
+    fn parse_ssh_config_aliases_maps_hosts() {
+        let aliases = parse_ssh_config_aliases(
+            "Host github-work gh-w\n  HostName github.com\n  IdentityFile ~/.ssh/work\n\
+             Host *.internal\n  HostName proxy\n\
+             Host ghe\n  HostName=ghe.acme.com\n\
+             Host plain\n  User git\n",
+        );
+        assert_eq!(aliases.get("github-work").map(String::as_str), Some("github.com"));
+        assert_eq!(aliases.get("gh-w").map(String::as_str), Some("github.com"));
+        assert_eq!(aliases.get("ghe").map(String::as_str), Some("ghe.acme.com"));
+        assert!(!aliases.contains_key("plain"));
+        assert_eq!(aliases.len(), 3);
+    }
+
+    #[test]
+    fn resolve_remote_url_resolves_ssh_aliases() {
+        let aliases = BTreeMap::from([("github-work".to_string(), "github.com".to_string())]);
+
+        let (host, owner, repo) =
+            resolve_remote_url("git@github-work:acme/api.git", &aliases).unwrap();
+        assert_eq!((host.as_str(), owner.as_str(), repo.as_str()), ("github.com", "acme", "api"));
+        let (host, _, _) =
+            resolve_remote_url("ssh://git@ghe.acme.com/platform/web", &aliases).unwrap();
+        assert_eq!(host, "ghe.acme.com");
+    }
+
+    #[test]
+    fn parse_expiry_accepts_date_and_rfc3339() {
+        assert_eq!(parse_expiry("2026-11-01").unwrap().to_rfc3339(), "2026-11-01T23:59:59+00:00");
+        assert_eq!(
+            parse_expiry("2026-11-01T08:00:00+09:00").unwrap().to_rfc3339(),
+            "2026-10-31T23:00:00+00:00"
+        );
+        assert!(parse_expiry("next week").is_err());
+    }
+
+    #[test]
+    fn parse_token_expiration_header_formats() {
+        let utc = parse_token_expiration_header("2026-11-01 08:00:00 UTC").unwrap();
+        assert_eq!(utc.to_rfc3339(), "2026-11-01T08:00:00+00:00");
+        let offset = parse_token_expiration_header("2026-11-01 00:00:00 -0800").unwrap();
+        assert_eq!(offset.to_rfc3339(), "2026-11-01T08:00:00+00:00");
+        assert!(parse_token_expiration_header("soon").is_none());
+    }
+
+    #[test]
+    fn browser_command_fills_placeholders() {
+        let url = "https://github.com/acme/api";
+        assert_eq!(
+            browser_command("firefox -P {account}", url, "work").unwrap(),
+            vec!["firefox", "-P", "work", url]
+        );
+        assert_eq!(
+            browser_command(
+                "open -a 'Google Chrome' --args --profile-directory=\"Profile 1\" {url}",
+                url,
+                "work"
+            )
+            .unwrap(),
+            vec!["open", "-a", "Google Chrome", "--args", "--profile-directory=Profile 1", url]
+        );
+    }
+
+    #[test]
+    fn browser_command_rejects_bad_templates() {
+        assert!(browser_command("  ", "u", "a").is_err());
+        assert!(browser_command("firefox -P 'work", "u", "a").is_err());
+    }
+
+    const DIFF: &str = "diff --git a/src/main.rs b/src/main.rs
+index 1111111..2222222 100644
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -1 +1 @@
+-old
++new
+diff --git a/docs/old.md b/docs/new.md
+similarity index 100%
+rename from docs/old.md
+rename to docs/new.md
+";
+
+    #[test]
+    fn split_diff_sections_by_new_path() {
+        let files = split_diff(DIFF);
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].0, "src/main.rs");
+        assert!(files[0].1.ends_with("+new\n"));
+        assert_eq!(files[1].0, "docs/new.md");
+        assert_eq!(files.iter().map(|(_, s)| *s).collect::<String>(), DIFF);
+        assert!(split_diff("").is_empty());
+    }
+
+    #[test]
+    fn colorize_diff_marks_changes() {
+        let colored = colorize_diff(DIFF);
+        assert!(colored.contains("\x1b[31m-old\x1b[0m\n"));
+        assert!(colored.contains("\x1b[32m+new\x1b[0m\n"));
+        assert!(colored.contains("\x1b[36m@@ -1 +1 @@\x1b[0m"));
+        assert!(colored.contains("similarity index 100%\n"));
+    }
+}
@@ -23,6 +23,15 @@ pub enum AppError {
     #[error("GitHub API error: {0}")]
     GitHubApi(String),
 
+    #[error("Authentication failed: {0}")]
+    Unauthorized(String),
+
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    #[error("Rate limit exceeded: {0}")]
+    RateLimited(String),
+
     #[error("Git error: {0}")]
     Git(String),
 
@@ -39,7 +48,43 @@ pub enum AppError {
     InvalidInput(String),
 }
 
+/// Process exit codes, one per error category.
+pub mod exit_code {
+    /// Unclassified failure.
+    pub const GENERAL: i32 = 1;
+    /// Invalid arguments or input (also used by clap for usage errors).
+    pub const INVALID_INPUT: i32 = 2;
+    /// Missing, invalid or insufficient credentials.
+    pub const AUTH: i32 = 3;
+    /// Account or remote resource not found.
+    pub const NOT_FOUND: i32 = 4;
+    /// Network failure talking to GitHub.
+    pub const NETWORK: i32 = 5;
+    /// GitHub API rate limit exhausted.
+    pub const RATE_LIMIT: i32 = 6;
+    /// Checks still pending (`pr checks` without `--watch`).
+    pub const PENDING: i32 = 7;
+}
+
 impl AppError {
+    /// Exit code for this error's category.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            AppError::InvalidInput(_) | AppError::TtyRequired => exit_code::INVALID_INPUT,
+            AppError::Unauthorized(_) | AppError::NoActiveAccount | AppError::Keychain(_) => {
+                exit_code::AUTH
+            }
+            AppError::NotFound(_) | AppError::AccountNotFound(_) => exit_code::NOT_FOUND,
+            AppError::Network(_) => exit_code::NETWORK,
+            AppError::RateLimited(_) => exit_code::RATE_LIMIT,
+            AppError::Io(_)
+            | AppError::Config(_)
+            | AppError::GitHubApi(_)
+            | AppError::Git(_)
+            | AppError::Json(_) => exit_code::GENERAL,
+        }
+    }
+
     pub fn config<S: Into<String>>(msg: S) -> Self {
         AppError::Config(msg.into())
     }
@@ -64,3 +109,20 @@ impl AppError {
         AppError::InvalidInput(msg.into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exit_codes_follow_categories() {
+        assert_eq!(AppError::invalid_input("bad").exit_code(), 2);
+        assert_eq!(AppError::NoActiveAccount.exit_code(), 3);
+        assert_eq!(AppError::Unauthorized("401".into()).exit_code(), 3);
+        assert_eq!(AppError::AccountNotFound("x".into()).exit_code(), 4);
+        assert_eq!(AppError::NotFound("404".into()).exit_code(), 4);
+        assert_eq!(AppError::network("timeout").exit_code(), 5);
+        assert_eq!(AppError::RateLimited("403".into()).exit_code(), 6);
+        assert_eq!(AppError::git("failed").exit_code(), 1);
+    }
+}
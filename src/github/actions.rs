@@ -0,0 +1,617 @@
+//! GitHub Actions: workflow runs, jobs and artifacts, check runs, self-hosted runners,
+//! secrets and variables.
+
+use super::{GitHubClient, MAX_PAGE_SIZE, api_base};
+use crate::error::AppError;
+use crate::models::{
+    ActionsSecret, ActionsVariable, Artifact, ArtifactsResponse, CheckAnnotation, CheckRun,
+    CheckRunsResponse, Runner, RunnerRegistrationToken, RunnersResponse, SecretVisibility,
+    SecretsPublicKey, SecretsResponse, VariablesResponse, WorkflowJob, WorkflowJobsResponse,
+    WorkflowRun, WorkflowRunsResponse,
+};
+use reqwest::{Method, Url};
+use std::collections::BTreeMap;
+use std::io::Write;
+
+/// Variables are listed at most 30 per page.
+const VARIABLES_PAGE_SIZE: usize = 30;
+
+/// Where Actions secrets and variables are defined, as seen from a repository.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionsScope<'a> {
+    /// Defined on the repository itself.
+    Repository,
+    /// Organization-level entries shared with the repository.
+    Organization,
+    /// Defined on a deployment environment of the repository.
+    Environment(&'a str),
+}
+
+impl ActionsScope<'_> {
+    fn path(&self, owner: &str, repo: &str, kind: &str) -> String {
+        let base = api_base();
+        match self {
+            ActionsScope::Repository => {
+                format!("{base}/repos/{owner}/{repo}/actions/{kind}")
+            }
+            ActionsScope::Organization => {
+                format!("{base}/repos/{owner}/{repo}/actions/organization-{kind}")
+            }
+            ActionsScope::Environment(env) => {
+                format!("{base}/repos/{owner}/{repo}/environments/{env}/{kind}")
+            }
+        }
+    }
+}
+
+/// Repository or organization whose own Actions secrets and variables are managed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActionsOwner {
+    Repo { owner: String, repo: String },
+    Org(String),
+}
+
+impl ActionsOwner {
+    /// URL of `kind` (`secrets` or `variables`), or of one entry when `name` is given.
+    fn url(&self, kind: &str, name: Option<&str>) -> String {
+        let base = api_base();
+        let collection = match self {
+            ActionsOwner::Repo { owner, repo } => {
+                format!("{base}/repos/{owner}/{repo}/actions/{kind}")
+            }
+            ActionsOwner::Org(org) => format!("{base}/orgs/{org}/actions/{kind}"),
+        };
+        match name {
+            Some(name) => format!("{collection}/{name}"),
+            None => collection,
+        }
+    }
+
+    /// Visibility to send when creating an entry: organization entries need one, repository
+    /// entries reject one.
+    fn visibility(&self, visibility: Option<SecretVisibility>) -> Option<SecretVisibility> {
+        match self {
+            ActionsOwner::Repo { .. } => None,
+            ActionsOwner::Org(_) => Some(visibility.unwrap_or_default()),
+        }
+    }
+}
+
+impl std::fmt::Display for ActionsOwner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ActionsOwner::Repo { owner, repo } => write!(f, "{owner}/{repo}"),
+            ActionsOwner::Org(org) => write!(f, "organization {org}"),
+        }
+    }
+}
+
+/// Workflow run, check run, runner, secret and variable endpoints.
+pub trait ActionsApi {
+    /// List the names of Actions secrets visible to a repository in `scope`.
+    fn list_secret_names(
+        &self,
+        owner: &str,
+        repo: &str,
+        scope: ActionsScope<'_>,
+    ) -> Result<Vec<String>, AppError>;
+
+    /// List the names of Actions variables visible to a repository in `scope`.
+    fn list_variable_names(
+        &self,
+        owner: &str,
+        repo: &str,
+        scope: ActionsScope<'_>,
+    ) -> Result<Vec<String>, AppError>;
+
+    /// List all Actions secrets of `owner`.
+    fn list_secrets(&self, owner: &ActionsOwner) -> Result<Vec<ActionsSecret>, AppError>;
+
+    /// Get a secret's metadata.
+    fn get_secret(&self, owner: &ActionsOwner, name: &str) -> Result<ActionsSecret, AppError>;
+
+    /// Get the public key secrets of `owner` are encrypted with.
+    fn get_secrets_public_key(&self, owner: &ActionsOwner) -> Result<SecretsPublicKey, AppError>;
+
+    /// Create or update a secret from its sealed, base64-encoded value.
+    ///
+    /// `visibility` only applies to organization secrets and defaults to private.
+    fn put_secret(
+        &self,
+        owner: &ActionsOwner,
+        name: &str,
+        encrypted_value: &str,
+        key_id: &str,
+        visibility: Option<SecretVisibility>,
+    ) -> Result<(), AppError>;
+
+    /// Delete a secret.
+    fn delete_secret(&self, owner: &ActionsOwner, name: &str) -> Result<(), AppError>;
+
+    /// List all Actions variables of `owner`.
+    fn list_variables(&self, owner: &ActionsOwner) -> Result<Vec<ActionsVariable>, AppError>;
+
+    /// Create a variable; fails when it already exists.
+    fn create_variable(
+        &self,
+        owner: &ActionsOwner,
+        name: &str,
+        value: &str,
+        visibility: Option<SecretVisibility>,
+    ) -> Result<(), AppError>;
+
+    /// Update an existing variable; fails with `NotFound` when there is none.
+    ///
+    /// The visibility of an organization variable is only changed when `visibility` is given.
+    fn update_variable(
+        &self,
+        owner: &ActionsOwner,
+        name: &str,
+        value: &str,
+        visibility: Option<SecretVisibility>,
+    ) -> Result<(), AppError>;
+
+    /// Delete a variable.
+    fn delete_variable(&self, owner: &ActionsOwner, name: &str) -> Result<(), AppError>;
+
+    /// List all self-hosted runners registered to an organization.
+    fn list_org_runners(&self, org: &str) -> Result<Vec<Runner>, AppError>;
+
+    /// Remove a self-hosted runner from an organization.
+    fn delete_org_runner(&self, org: &str, runner_id: u64) -> Result<(), AppError>;
+
+    /// Create a registration token for adding a self-hosted runner to an organization.
+    fn create_org_runner_registration_token(
+        &self,
+        org: &str,
+    ) -> Result<RunnerRegistrationToken, AppError>;
+
+    /// List check runs for a commit.
+    fn list_check_runs(
+        &self,
+        owner: &str,
+        repo: &str,
+        git_ref: &str,
+    ) -> Result<Vec<CheckRun>, AppError>;
+
+    /// List annotations reported by a check run.
+    fn list_check_run_annotations(
+        &self,
+        owner: &str,
+        repo: &str,
+        check_run_id: u64,
+    ) -> Result<Vec<CheckAnnotation>, AppError>;
+
+    /// List recent workflow runs of a repository, newest first.
+    fn list_workflow_runs(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<WorkflowRun>, AppError>;
+
+    /// Get a workflow run.
+    fn get_workflow_run(
+        &self,
+        owner: &str,
+        repo: &str,
+        run_id: u64,
+    ) -> Result<WorkflowRun, AppError>;
+
+    /// List the jobs of a workflow run.
+    fn list_workflow_run_jobs(
+        &self,
+        owner: &str,
+        repo: &str,
+        run_id: u64,
+    ) -> Result<Vec<WorkflowJob>, AppError>;
+
+    /// Get the plain-text log of a completed job, each line prefixed with its timestamp.
+    fn get_job_log(&self, owner: &str, repo: &str, job_id: u64) -> Result<String, AppError>;
+
+    /// List the workflow runs of a commit.
+    fn list_commit_workflow_runs(
+        &self,
+        owner: &str,
+        repo: &str,
+        sha: &str,
+    ) -> Result<Vec<WorkflowRun>, AppError>;
+
+    /// Re-run a workflow run, or with `failed_only` only its failed jobs and their dependents.
+    fn rerun_workflow_run(
+        &self,
+        owner: &str,
+        repo: &str,
+        run_id: u64,
+        failed_only: bool,
+    ) -> Result<(), AppError>;
+
+    /// List the artifacts a workflow run uploaded.
+    fn list_run_artifacts(
+        &self,
+        owner: &str,
+        repo: &str,
+        run_id: u64,
+    ) -> Result<Vec<Artifact>, AppError>;
+
+    /// Stream the zip archive of an artifact into `writer`, returning the bytes written.
+    fn download_artifact(
+        &self,
+        owner: &str,
+        repo: &str,
+        artifact_id: u64,
+        writer: &mut impl Write,
+    ) -> Result<u64, AppError>;
+
+    /// Trigger a `workflow_dispatch` event for a workflow (file name or ID) on `git_ref`.
+    fn dispatch_workflow(
+        &self,
+        owner: &str,
+        repo: &str,
+        workflow: &str,
+        git_ref: &str,
+        inputs: &BTreeMap<String, String>,
+    ) -> Result<(), AppError>;
+
+    /// List the newest `workflow_dispatch` runs of a workflow on a branch or tag.
+    fn list_dispatched_runs(
+        &self,
+        owner: &str,
+        repo: &str,
+        workflow: &str,
+        branch: &str,
+    ) -> Result<Vec<WorkflowRun>, AppError>;
+}
+
+impl ActionsApi for GitHubClient {
+    fn list_secret_names(
+        &self,
+        owner: &str,
+        repo: &str,
+        scope: ActionsScope<'_>,
+    ) -> Result<Vec<String>, AppError> {
+        let url = format!("{}?per_page=100", scope.path(owner, repo, "secrets"));
+        let response: SecretsResponse = Self::parse_json(self.request(&url)?)?;
+        Ok(response.secrets.into_iter().map(|secret| secret.name).collect())
+    }
+
+    fn list_variable_names(
+        &self,
+        owner: &str,
+        repo: &str,
+        scope: ActionsScope<'_>,
+    ) -> Result<Vec<String>, AppError> {
+        let url = format!("{}?per_page=30", scope.path(owner, repo, "variables"));
+        let response: VariablesResponse = Self::parse_json(self.request(&url)?)?;
+        Ok(response.variables.into_iter().map(|variable| variable.name).collect())
+    }
+
+    fn list_secrets(&self, owner: &ActionsOwner) -> Result<Vec<ActionsSecret>, AppError> {
+        let mut secrets = Vec::new();
+        for page in 1.. {
+            let url =
+                format!("{}?per_page={}&page={}", owner.url("secrets", None), MAX_PAGE_SIZE, page);
+            let response: SecretsResponse = Self::parse_json(self.request(&url)?)?;
+            let done = response.secrets.len() < MAX_PAGE_SIZE
+                || secrets.len() + response.secrets.len() >= response.total_count;
+            secrets.extend(response.secrets);
+            if done {
+                break;
+            }
+        }
+        Ok(secrets)
+    }
+
+    fn get_secret(&self, owner: &ActionsOwner, name: &str) -> Result<ActionsSecret, AppError> {
+        Self::parse_json(self.request(&owner.url("secrets", Some(name)))?)
+    }
+
+    fn get_secrets_public_key(&self, owner: &ActionsOwner) -> Result<SecretsPublicKey, AppError> {
+        Self::parse_json(self.request(&owner.url("secrets", Some("public-key")))?)
+    }
+
+    fn put_secret(
+        &self,
+        owner: &ActionsOwner,
+        name: &str,
+        encrypted_value: &str,
+        key_id: &str,
+        visibility: Option<SecretVisibility>,
+    ) -> Result<(), AppError> {
+        let mut payload =
+            serde_json::json!({ "encrypted_value": encrypted_value, "key_id": key_id });
+        if let Some(visibility) = owner.visibility(visibility) {
+            payload["visibility"] = serde_json::json!(visibility);
+        }
+        self.request_with_body(Method::PUT, &owner.url("secrets", Some(name)), &payload)?;
+        Ok(())
+    }
+
+    fn delete_secret(&self, owner: &ActionsOwner, name: &str) -> Result<(), AppError> {
+        self.send(self.client.delete(owner.url("secrets", Some(name))))?;
+        Ok(())
+    }
+
+    fn list_variables(&self, owner: &ActionsOwner) -> Result<Vec<ActionsVariable>, AppError> {
+        let mut variables = Vec::new();
+        for page in 1.. {
+            let url = format!(
+                "{}?per_page={}&page={}",
+                owner.url("variables", None),
+                VARIABLES_PAGE_SIZE,
+                page
+            );
+            let response: VariablesResponse = Self::parse_json(self.request(&url)?)?;
+            let done = response.variables.len() < VARIABLES_PAGE_SIZE
+                || variables.len() + response.variables.len() >= response.total_count;
+            variables.extend(response.variables);
+            if done {
+                break;
+            }
+        }
+        Ok(variables)
+    }
+
+    fn create_variable(
+        &self,
+        owner: &ActionsOwner,
+        name: &str,
+        value: &str,
+        visibility: Option<SecretVisibility>,
+    ) -> Result<(), AppError> {
+        let mut payload = serde_json::json!({ "name": name, "value": value });
+        if let Some(visibility) = owner.visibility(visibility) {
+            payload["visibility"] = serde_json::json!(visibility);
+        }
+        self.request_with_body(Method::POST, &owner.url("variables", None), &payload)?;
+        Ok(())
+    }
+
+    fn update_variable(
+        &self,
+        owner: &ActionsOwner,
+        name: &str,
+        value: &str,
+        visibility: Option<SecretVisibility>,
+    ) -> Result<(), AppError> {
+        let mut payload = serde_json::json!({ "name": name, "value": value });
+        if let (ActionsOwner::Org(_), Some(visibility)) = (owner, visibility) {
+            payload["visibility"] = serde_json::json!(visibility);
+        }
+        self.request_with_body(Method::PATCH, &owner.url("variables", Some(name)), &payload)?;
+        Ok(())
+    }
+
+    fn delete_variable(&self, owner: &ActionsOwner, name: &str) -> Result<(), AppError> {
+        self.send(self.client.delete(owner.url("variables", Some(name))))?;
+        Ok(())
+    }
+
+    fn list_org_runners(&self, org: &str) -> Result<Vec<Runner>, AppError> {
+        let mut runners = Vec::new();
+        for page in 1.. {
+            let url = format!(
+                "{}/orgs/{}/actions/runners?per_page={}&page={}",
+                api_base(),
+                org,
+                MAX_PAGE_SIZE,
+                page
+            );
+            let response: RunnersResponse = Self::parse_json(self.request(&url)?)?;
+            let done = response.runners.len() < MAX_PAGE_SIZE
+                || runners.len() + response.runners.len() >= response.total_count;
+            runners.extend(response.runners);
+            if done {
+                break;
+            }
+        }
+        Ok(runners)
+    }
+
+    fn delete_org_runner(&self, org: &str, runner_id: u64) -> Result<(), AppError> {
+        let url = format!("{}/orgs/{}/actions/runners/{}", api_base(), org, runner_id);
+        self.send(self.client.delete(&url))?;
+        Ok(())
+    }
+
+    fn create_org_runner_registration_token(
+        &self,
+        org: &str,
+    ) -> Result<RunnerRegistrationToken, AppError> {
+        let url = format!("{}/orgs/{}/actions/runners/registration-token", api_base(), org);
+        let response = self.send(self.client.post(&url))?;
+        Self::parse_json(response)
+    }
+
+    fn list_check_runs(
+        &self,
+        owner: &str,
+        repo: &str,
+        git_ref: &str,
+    ) -> Result<Vec<CheckRun>, AppError> {
+        let url = format!(
+            "{}/repos/{}/{}/commits/{}/check-runs?per_page=100",
+            api_base(),
+            owner,
+            repo,
+            git_ref
+        );
+        let response = self.request(&url)?;
+        let checks: CheckRunsResponse = Self::parse_json(response)?;
+        Ok(checks.check_runs)
+    }
+
+    fn list_check_run_annotations(
+        &self,
+        owner: &str,
+        repo: &str,
+        check_run_id: u64,
+    ) -> Result<Vec<CheckAnnotation>, AppError> {
+        let url = format!(
+            "{}/repos/{}/{}/check-runs/{}/annotations?per_page=100",
+            api_base(),
+            owner,
+            repo,
+            check_run_id
+        );
+        let response = self.request(&url)?;
+        Self::parse_json(response)
+    }
+
+    fn list_workflow_runs(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<WorkflowRun>, AppError> {
+        let url = format!("{}/repos/{}/{}/actions/runs", api_base(), owner, repo);
+        let mut params = vec![("per_page", limit.min(MAX_PAGE_SIZE).to_string())];
+        if let Some(branch) = branch {
+            params.push(("branch", branch.to_string()));
+        }
+        let url = Url::parse_with_params(&url, &params)
+            .map_err(|e| AppError::invalid_input(format!("invalid URL: {e}")))?;
+        let response: WorkflowRunsResponse = Self::parse_json(self.request(url.as_str())?)?;
+        Ok(response.workflow_runs)
+    }
+
+    fn get_workflow_run(
+        &self,
+        owner: &str,
+        repo: &str,
+        run_id: u64,
+    ) -> Result<WorkflowRun, AppError> {
+        let url = format!("{}/repos/{}/{}/actions/runs/{}", api_base(), owner, repo, run_id);
+        let response = self.request(&url)?;
+        Self::parse_json(response)
+    }
+
+    fn list_workflow_run_jobs(
+        &self,
+        owner: &str,
+        repo: &str,
+        run_id: u64,
+    ) -> Result<Vec<WorkflowJob>, AppError> {
+        let url = format!(
+            "{}/repos/{}/{}/actions/runs/{}/jobs?per_page=100",
+            api_base(),
+            owner,
+            repo,
+            run_id
+        );
+        let response = self.request(&url)?;
+        let jobs: WorkflowJobsResponse = Self::parse_json(response)?;
+        Ok(jobs.jobs)
+    }
+
+    fn list_commit_workflow_runs(
+        &self,
+        owner: &str,
+        repo: &str,
+        sha: &str,
+    ) -> Result<Vec<WorkflowRun>, AppError> {
+        let url = format!(
+            "{}/repos/{}/{}/actions/runs?head_sha={}&per_page=100",
+            api_base(),
+            owner,
+            repo,
+            sha
+        );
+        let response: WorkflowRunsResponse = Self::parse_json(self.request(&url)?)?;
+        Ok(response.workflow_runs)
+    }
+
+    fn rerun_workflow_run(
+        &self,
+        owner: &str,
+        repo: &str,
+        run_id: u64,
+        failed_only: bool,
+    ) -> Result<(), AppError> {
+        let action = if failed_only { "rerun-failed-jobs" } else { "rerun" };
+        let url =
+            format!("{}/repos/{}/{}/actions/runs/{}/{}", api_base(), owner, repo, run_id, action);
+        self.send(self.client.post(&url))?;
+        Ok(())
+    }
+
+    fn get_job_log(&self, owner: &str, repo: &str, job_id: u64) -> Result<String, AppError> {
+        let url = format!("{}/repos/{}/{}/actions/jobs/{}/logs", api_base(), owner, repo, job_id);
+        let mut log = Vec::new();
+        self.download(&url, "application/vnd.github+json", &mut log)?;
+        Ok(String::from_utf8_lossy(&log).into_owned())
+    }
+
+    fn list_run_artifacts(
+        &self,
+        owner: &str,
+        repo: &str,
+        run_id: u64,
+    ) -> Result<Vec<Artifact>, AppError> {
+        let url = format!(
+            "{}/repos/{}/{}/actions/runs/{}/artifacts?per_page={}",
+            api_base(),
+            owner,
+            repo,
+            run_id,
+            MAX_PAGE_SIZE
+        );
+        let response: ArtifactsResponse = Self::parse_json(self.request(&url)?)?;
+        Ok(response.artifacts)
+    }
+
+    fn download_artifact(
+        &self,
+        owner: &str,
+        repo: &str,
+        artifact_id: u64,
+        writer: &mut impl Write,
+    ) -> Result<u64, AppError> {
+        let url = format!(
+            "{}/repos/{}/{}/actions/artifacts/{}/zip",
+            api_base(),
+            owner,
+            repo,
+            artifact_id
+        );
+        self.download(&url, "application/vnd.github+json", writer)
+    }
+
+    fn dispatch_workflow(
+        &self,
+        owner: &str,
+        repo: &str,
+        workflow: &str,
+        git_ref: &str,
+        inputs: &BTreeMap<String, String>,
+    ) -> Result<(), AppError> {
+        let url = format!(
+            "{}/repos/{}/{}/actions/workflows/{}/dispatches",
+            api_base(),
+            owner,
+            repo,
+            workflow
+        );
+        let payload = serde_json::json!({ "ref": git_ref, "inputs": inputs });
+        self.request_with_body(Method::POST, &url, &payload)?;
+        Ok(())
+    }
+
+    fn list_dispatched_runs(
+        &self,
+        owner: &str,
+        repo: &str,
+        workflow: &str,
+        branch: &str,
+    ) -> Result<Vec<WorkflowRun>, AppError> {
+        let url =
+            format!("{}/repos/{}/{}/actions/workflows/{}/runs", api_base(), owner, repo, workflow);
+        let params = [("event", "workflow_dispatch"), ("branch", branch), ("per_page", "10")];
+        let url = Url::parse_with_params(&url, params)
+            .map_err(|e| AppError::invalid_input(format!("invalid URL: {e}")))?;
+        let response: WorkflowRunsResponse = Self::parse_json(self.request(url.as_str())?)?;
+        Ok(response.workflow_runs)
+    }
+}
@@ -0,0 +1,270 @@
+//! Issues and pull request conversations: labels, assignees, comments and timelines.
+
+use super::{GitHubClient, api_base};
+use crate::error::AppError;
+use crate::models::{
+    Issue, IssueComment, IssueUpdate, Label, Milestone, PullRequestUser, TimelineEvent,
+};
+use reqwest::{Method, Url};
+
+/// Issue, label, issue comment and timeline endpoints.
+pub trait IssuesApi {
+    /// List every open issue of a repository, oldest first, without pull requests.
+    fn list_open_issues(&self, owner: &str, repo: &str) -> Result<Vec<Issue>, AppError>;
+
+    /// Change the labels, assignees, milestone or state of an issue (or pull request).
+    fn update_issue(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        update: &IssueUpdate,
+    ) -> Result<Issue, AppError>;
+
+    /// List the users issues of a repository can be assigned to.
+    fn list_assignees(&self, owner: &str, repo: &str) -> Result<Vec<PullRequestUser>, AppError>;
+
+    /// Assign users to an issue or pull request, keeping its other assignees.
+    fn add_assignees(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        logins: &[String],
+    ) -> Result<(), AppError>;
+
+    /// Unassign users from an issue or pull request.
+    fn remove_assignees(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        logins: &[String],
+    ) -> Result<(), AppError>;
+
+    /// Add labels to an issue or pull request, keeping its other labels.
+    fn add_labels(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        names: &[String],
+    ) -> Result<(), AppError>;
+
+    /// Remove a label from an issue or pull request.
+    fn remove_label(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        name: &str,
+    ) -> Result<(), AppError>;
+
+    /// List the open milestones of a repository.
+    fn list_milestones(&self, owner: &str, repo: &str) -> Result<Vec<Milestone>, AppError>;
+
+    /// List all labels of a repository.
+    fn list_labels(&self, owner: &str, repo: &str) -> Result<Vec<Label>, AppError>;
+
+    /// Create a label.
+    fn create_label(&self, owner: &str, repo: &str, label: &Label) -> Result<Label, AppError>;
+
+    /// Update the color and description of the label named `label.name`.
+    fn update_label(&self, owner: &str, repo: &str, label: &Label) -> Result<Label, AppError>;
+
+    /// Delete a label.
+    fn delete_label(&self, owner: &str, repo: &str, name: &str) -> Result<(), AppError>;
+
+    /// List every timeline event of an issue or pull request, oldest first.
+    fn list_timeline(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<Vec<TimelineEvent>, AppError>;
+
+    /// List conversation comments of an issue or pull request, oldest first.
+    fn list_issue_comments(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<Vec<IssueComment>, AppError>;
+
+    /// Comment on the conversation of an issue or pull request.
+    fn create_issue_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        body: &str,
+    ) -> Result<IssueComment, AppError>;
+}
+
+impl IssuesApi for GitHubClient {
+    fn list_open_issues(&self, owner: &str, repo: &str) -> Result<Vec<Issue>, AppError> {
+        let url = format!(
+            "{}/repos/{}/{}/issues?state=open&sort=created&direction=asc",
+            api_base(),
+            owner,
+            repo
+        );
+        let issues: Vec<Issue> = self.get_all_pages(&url)?;
+        Ok(issues.into_iter().filter(|issue| issue.pull_request.is_none()).collect())
+    }
+
+    fn update_issue(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        update: &IssueUpdate,
+    ) -> Result<Issue, AppError> {
+        let url = format!("{}/repos/{}/{}/issues/{}", api_base(), owner, repo, number);
+        let response = self.request_with_body(Method::PATCH, &url, update)?;
+        Self::parse_json(response)
+    }
+
+    fn list_assignees(&self, owner: &str, repo: &str) -> Result<Vec<PullRequestUser>, AppError> {
+        let url = format!("{}/repos/{}/{}/assignees", api_base(), owner, repo);
+        self.get_all_pages(&url)
+    }
+
+    fn add_assignees(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        logins: &[String],
+    ) -> Result<(), AppError> {
+        let url = format!("{}/repos/{}/{}/issues/{}/assignees", api_base(), owner, repo, number);
+        let payload = serde_json::json!({ "assignees": logins });
+        self.request_with_body(Method::POST, &url, &payload)?;
+        Ok(())
+    }
+
+    fn remove_assignees(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        logins: &[String],
+    ) -> Result<(), AppError> {
+        let url = format!("{}/repos/{}/{}/issues/{}/assignees", api_base(), owner, repo, number);
+        let payload = serde_json::json!({ "assignees": logins });
+        self.request_with_body(Method::DELETE, &url, &payload)?;
+        Ok(())
+    }
+
+    fn add_labels(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        names: &[String],
+    ) -> Result<(), AppError> {
+        let url = format!("{}/repos/{}/{}/issues/{}/labels", api_base(), owner, repo, number);
+        let payload = serde_json::json!({ "labels": names });
+        self.request_with_body(Method::POST, &url, &payload)?;
+        Ok(())
+    }
+
+    fn remove_label(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        name: &str,
+    ) -> Result<(), AppError> {
+        let base = format!("{}/repos/{}/{}/issues/{}/labels", api_base(), owner, repo, number);
+        self.send(self.client.delete(named_url(&base, name)?))?;
+        Ok(())
+    }
+
+    fn list_milestones(&self, owner: &str, repo: &str) -> Result<Vec<Milestone>, AppError> {
+        let url = format!("{}/repos/{}/{}/milestones?state=open", api_base(), owner, repo);
+        self.get_all_pages(&url)
+    }
+
+    fn list_labels(&self, owner: &str, repo: &str) -> Result<Vec<Label>, AppError> {
+        let url = format!("{}/repos/{}/{}/labels", api_base(), owner, repo);
+        self.get_all_pages(&url)
+    }
+
+    fn create_label(&self, owner: &str, repo: &str, label: &Label) -> Result<Label, AppError> {
+        let url = format!("{}/repos/{}/{}/labels", api_base(), owner, repo);
+        let response = self.request_with_body(Method::POST, &url, label)?;
+        Self::parse_json(response)
+    }
+
+    fn update_label(&self, owner: &str, repo: &str, label: &Label) -> Result<Label, AppError> {
+        let url = label_url(owner, repo, &label.name)?;
+        let response = self.request_with_body(Method::PATCH, url.as_str(), label)?;
+        Self::parse_json(response)
+    }
+
+    fn delete_label(&self, owner: &str, repo: &str, name: &str) -> Result<(), AppError> {
+        self.send(self.client.delete(label_url(owner, repo, name)?))?;
+        Ok(())
+    }
+
+    fn list_timeline(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<Vec<TimelineEvent>, AppError> {
+        let url = format!("{}/repos/{}/{}/issues/{}/timeline", api_base(), owner, repo, number);
+        self.get_all_pages(&url)
+    }
+
+    fn list_issue_comments(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<Vec<IssueComment>, AppError> {
+        let url = format!("{}/repos/{}/{}/issues/{}/comments", api_base(), owner, repo, number);
+        self.get_all_pages(&url)
+    }
+
+    fn create_issue_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        body: &str,
+    ) -> Result<IssueComment, AppError> {
+        let url = format!("{}/repos/{}/{}/issues/{}/comments", api_base(), owner, repo, number);
+        let payload = serde_json::json!({ "body": body });
+        let response = self.request_with_body(Method::POST, &url, &payload)?;
+        Self::parse_json(response)
+    }
+}
+
+/// URL of a label; names may contain spaces and other characters that need encoding.
+fn label_url(owner: &str, repo: &str, name: &str) -> Result<Url, AppError> {
+    named_url(&format!("{}/repos/{}/{}/labels", api_base(), owner, repo), name)
+}
+
+/// `base` with `name` appended as one encoded path segment.
+fn named_url(base: &str, name: &str) -> Result<Url, AppError> {
+    let mut url = Url::parse(base)
+        .map_err(|e| AppError::invalid_input(format!("invalid repository: {e}")))?;
+    url.path_segments_mut().map_err(|_| AppError::invalid_input("invalid repository"))?.push(name);
+    Ok(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn label_url_encodes_name() {
+        let url = label_url("acme", "api", "good first issue").unwrap();
+        assert_eq!(
+            url.as_str(),
+            format!("{}/repos/acme/api/labels/good%20first%20issue", api_base())
+        );
+    }
+}
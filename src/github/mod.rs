@@ -0,0 +1,402 @@
+//! GitHub API client.
+//!
+//! [`GitHubClient`] owns the HTTP transport: authentication, error classification, pagination
+//! and logging. The endpoints are grouped into per-domain traits ([`ReposApi`], [`PullsApi`],
+//! [`IssuesApi`], [`ActionsApi`], [`OrgsApi`], [`PackagesApi`] and [`UsersApi`]), each
+//! implemented for the client in its own module, so callers can depend on the traits they use
+//! and library consumers can provide their own implementation to code written against them.
+//! The command functions build a [`GitHubClient`] themselves; [`set_api_base`] points them at
+//! another server, such as a fake one in tests.
+
+mod actions;
+mod issues;
+mod orgs;
+mod packages;
+mod poll;
+mod pulls;
+mod repos;
+mod users;
+
+pub use actions::{ActionsApi, ActionsOwner, ActionsScope};
+pub use issues::IssuesApi;
+pub use orgs::OrgsApi;
+pub use packages::{PackageOwner, PackagesApi};
+pub use poll::{PollState, Poller};
+pub use pulls::PullsApi;
+pub use repos::{ReposApi, UploadSource};
+pub use users::UsersApi;
+
+use crate::error::AppError;
+use crate::models::GraphQlResponse;
+use poll::ResponseCache;
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use reqwest::header::{ACCEPT, AUTHORIZATION, USER_AGENT};
+use reqwest::{Method, StatusCode, Url};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::io::Write;
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+/// API root used unless `GHO_API_BASE` or the `api_base` setting points elsewhere.
+pub const DEFAULT_API_BASE: &str = "https://api.github.com";
+/// Environment variable overriding the API root, e.g. for a proxy or a test server.
+pub const API_BASE_ENV: &str = "GHO_API_BASE";
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+const TRANSFER_TIMEOUT_SECS: u64 = 60 * 60;
+const DEFAULT_LIMIT: usize = 30;
+const GRAPHQL_MAX_PAGE: usize = 100;
+/// Largest `per_page` the REST API accepts.
+pub const MAX_PAGE_SIZE: usize = 100;
+
+/// Unparsed response of [`GitHubClient::raw_request`].
+#[derive(Debug, Clone)]
+pub struct RawResponse {
+    pub body: String,
+    /// URL of the next page from the `Link` header, if any.
+    pub next: Option<String>,
+}
+
+static API_BASE: RwLock<Option<String>> = RwLock::new(None);
+
+/// Send later requests to `base` instead of api.github.com (the `api_base` setting); `None`
+/// restores the default. `GHO_API_BASE` still takes precedence.
+pub fn set_api_base(base: Option<&str>) {
+    *API_BASE.write().unwrap_or_else(|e| e.into_inner()) = base.map(str::to_string);
+}
+
+/// The API root in use, without a trailing slash.
+pub fn api_base() -> String {
+    let base = std::env::var(API_BASE_ENV)
+        .ok()
+        .filter(|base| !base.trim().is_empty())
+        .or_else(|| API_BASE.read().unwrap_or_else(|e| e.into_inner()).clone())
+        .unwrap_or_else(|| DEFAULT_API_BASE.to_string());
+    base.trim().trim_end_matches('/').to_string()
+}
+
+/// The whole GitHub API surface gho uses, one supertrait over the per-domain traits.
+///
+/// Implemented for every type that implements all of them, so an alternative client (e.g. a
+/// fake in a library consumer's tests) only needs to implement the domain traits.
+pub trait GitHubApi:
+    ReposApi + PullsApi + IssuesApi + ActionsApi + OrgsApi + PackagesApi + UsersApi
+{
+}
+
+impl<T> GitHubApi for T where
+    T: ReposApi + PullsApi + IssuesApi + ActionsApi + OrgsApi + PackagesApi + UsersApi
+{
+}
+
+/// GitHub API client over HTTPS, implementing every per-domain API trait.
+pub struct GitHubClient {
+    client: Client,
+    token: String,
+    /// Remembered GET responses of a [`polling`](Self::polling) client.
+    cache: Option<Mutex<ResponseCache>>,
+}
+
+impl GitHubClient {
+    /// Create a new GitHub client with the given token.
+    pub fn new(token: String) -> Result<Self, AppError> {
+        let client =
+            Client::builder()
+                .timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS))
+                .build()
+                .map_err(|e| AppError::network(format!("failed to create HTTP client: {e}")))?;
+        Ok(Self { client, token, cache: None })
+    }
+
+    /// This client, made to send GETs conditionally on the previous response for the same URL,
+    /// so that polling an unchanged resource costs no rate limit (see [`Poller`]).
+    pub fn polling(mut self) -> Self {
+        self.cache = Some(Mutex::default());
+        self
+    }
+
+    /// What responses said since the last call: whether anything changed, and GitHub's poll
+    /// interval and rate limit. Always unchanged for a client that is not
+    /// [`polling`](Self::polling).
+    pub fn poll_state(&self) -> PollState {
+        match &self.cache {
+            Some(cache) => cache.lock().unwrap_or_else(|e| e.into_inner()).take_state(),
+            None => PollState::default(),
+        }
+    }
+
+    fn request(&self, url: &str) -> Result<Response, AppError> {
+        self.send(self.client.get(url))
+    }
+
+    /// GET `url` as the media type `accept` (e.g. a diff) and return the body as text.
+    fn request_text(&self, url: &str, accept: &str) -> Result<String, AppError> {
+        self.send_accepting(self.client.get(url), accept)?
+            .text()
+            .map_err(|e| AppError::network(format!("failed to read response: {e}")))
+    }
+
+    /// Stream the body of `url`, requested as the media type `accept`, into `writer` with a
+    /// timeout for large transfers, returning the bytes written.
+    ///
+    /// Redirects to storage hosts (release assets, artifacts) are followed; the token is not
+    /// sent along to another host.
+    fn download(&self, url: &str, accept: &str, writer: &mut impl Write) -> Result<u64, AppError> {
+        let builder = self.client.get(url).timeout(Duration::from_secs(TRANSFER_TIMEOUT_SECS));
+        self.send_accepting(builder, accept)?
+            .copy_to(writer)
+            .map_err(|e| AppError::network(format!("download interrupted: {e}")))
+    }
+
+    fn request_with_body<B: Serialize>(
+        &self,
+        method: Method,
+        url: &str,
+        body: &B,
+    ) -> Result<Response, AppError> {
+        self.send(self.client.request(method, url).json(body))
+    }
+
+    fn send(&self, builder: RequestBuilder) -> Result<Response, AppError> {
+        self.send_accepting(builder, "application/vnd.github+json")
+    }
+
+    fn send_accepting(&self, builder: RequestBuilder, accept: &str) -> Result<Response, AppError> {
+        let mut request = builder
+            .header(USER_AGENT, "gho")
+            .header(AUTHORIZATION, format!("Bearer {}", self.token))
+            .header(ACCEPT, accept)
+            .build()
+            .map_err(|e| AppError::network(format!("invalid request: {e}")))?;
+        let (method, url) = (request.method().clone(), request.url().clone());
+        let cache = self.cache.as_ref().filter(|_| method == Method::GET);
+        if let Some(cache) = cache {
+            cache.lock().unwrap_or_else(|e| e.into_inner()).prepare(&url, request.headers_mut());
+        }
+        tracing::debug!("{method} {url}");
+        let started = Instant::now();
+        let response = self.client.execute(request).map_err(|e| {
+            tracing::debug!("{method} {url} failed: {e}");
+            AppError::network(format!("request failed: {e}"))
+        })?;
+        tracing::debug!(
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            "{method} {url} -> {}",
+            response.status()
+        );
+        tracing::trace!(
+            rate_limit_remaining = header(&response, "x-ratelimit-remaining"),
+            request_id = header(&response, "x-github-request-id"),
+            "{method} {url} response headers"
+        );
+        let response =
+            match cache {
+                Some(cache) => cache
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .record(&url, response)
+                    .map_err(|e| AppError::network(format!("failed to read response: {e}")))?,
+                None => response,
+            };
+
+        // `304 Not Modified` only answers conditional requests, whose callers handle it.
+        if !response.status().is_success() && response.status() != StatusCode::NOT_MODIFIED {
+            let status = response.status();
+            let remaining = response
+                .headers()
+                .get("x-ratelimit-remaining")
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string);
+            let body = response.text().unwrap_or_default();
+            return Err(classify_error(status, remaining.as_deref(), body));
+        }
+
+        Ok(response)
+    }
+
+    fn parse_json<T: DeserializeOwned>(response: Response) -> Result<T, AppError> {
+        response.json().map_err(|e| AppError::github_api(format!("failed to parse response: {e}")))
+    }
+
+    /// Execute a GraphQL query and return its typed `data` payload.
+    pub fn graphql<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> Result<T, AppError> {
+        let url = format!("{}/graphql", api_base());
+        let payload = serde_json::json!({ "query": query, "variables": variables });
+        let response = self.request_with_body(Method::POST, &url, &payload)?;
+        let envelope: GraphQlResponse<T> = Self::parse_json(response)?;
+        graphql_data(envelope)
+    }
+
+    /// Send an arbitrary request to `endpoint`, a path relative to the API root or a full URL.
+    ///
+    /// `query` is appended to the URL and `body`, when given, is sent as JSON. Headers named
+    /// `Accept` replace the default media type.
+    pub fn raw_request(
+        &self,
+        method: &str,
+        endpoint: &str,
+        query: &[(String, String)],
+        body: Option<&serde_json::Value>,
+        headers: &[(String, String)],
+    ) -> Result<RawResponse, AppError> {
+        let method = Method::from_bytes(method.to_uppercase().as_bytes())
+            .map_err(|_| AppError::invalid_input(format!("invalid HTTP method '{method}'")))?;
+        let mut url = Url::parse(&api_url(endpoint))
+            .map_err(|e| AppError::invalid_input(format!("invalid endpoint '{endpoint}': {e}")))?;
+        if !query.is_empty() {
+            url.query_pairs_mut().extend_pairs(query);
+        }
+
+        let mut builder = self.client.request(method, url);
+        if let Some(body) = body {
+            builder = builder.json(body);
+        }
+        let mut accept = "application/vnd.github+json";
+        for (name, value) in headers {
+            if name.eq_ignore_ascii_case("accept") {
+                accept = value;
+            } else {
+                builder = builder.header(name.as_str(), value.as_str());
+            }
+        }
+
+        let response = self.send_accepting(builder, accept)?;
+        let next = response.headers().get("link").and_then(|v| v.to_str().ok()).and_then(next_link);
+        let body = response
+            .text()
+            .map_err(|e| AppError::network(format!("failed to read response: {e}")))?;
+        Ok(RawResponse { body, next })
+    }
+
+    /// Fetch every page of an endpoint that returns a JSON array.
+    fn get_all_pages<T: DeserializeOwned>(&self, url: &str) -> Result<Vec<T>, AppError> {
+        let separator = if url.contains('?') { '&' } else { '?' };
+        let mut items = Vec::new();
+        for page in 1.. {
+            let url = format!("{url}{separator}per_page={MAX_PAGE_SIZE}&page={page}");
+            let batch: Vec<T> = Self::parse_json(self.request(&url)?)?;
+            let done = batch.len() < MAX_PAGE_SIZE;
+            items.extend(batch);
+            if done {
+                break;
+            }
+        }
+        Ok(items)
+    }
+}
+
+/// Value of the response header `name`, or `-` when missing.
+fn header<'a>(response: &'a Response, name: &str) -> &'a str {
+    response.headers().get(name).and_then(|v| v.to_str().ok()).unwrap_or("-")
+}
+
+fn api_url(endpoint: &str) -> String {
+    if endpoint.starts_with("https://") || endpoint.starts_with("http://") {
+        endpoint.to_string()
+    } else {
+        format!("{}/{}", api_base(), endpoint.trim_start_matches('/'))
+    }
+}
+
+/// Target of the `rel="next"` entry of a `Link` header.
+fn next_link(header: &str) -> Option<String> {
+    header.split(',').find_map(|part| {
+        let (target, params) = part.split_once(';')?;
+        params
+            .split(';')
+            .any(|p| p.trim() == r#"rel="next""#)
+            .then(|| target.trim().trim_start_matches('<').trim_end_matches('>').to_string())
+    })
+}
+
+fn classify_error(
+    status: StatusCode,
+    rate_limit_remaining: Option<&str>,
+    body: String,
+) -> AppError {
+    let message = format!("API error {status}: {body}");
+    match status {
+        StatusCode::TOO_MANY_REQUESTS => AppError::RateLimited(message),
+        StatusCode::FORBIDDEN if rate_limit_remaining == Some("0") => {
+            AppError::RateLimited(message)
+        }
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => AppError::Unauthorized(message),
+        StatusCode::NOT_FOUND => AppError::NotFound(message),
+        _ => AppError::GitHubApi(message),
+    }
+}
+
+fn graphql_data<T>(envelope: GraphQlResponse<T>) -> Result<T, AppError> {
+    if !envelope.errors.is_empty() {
+        let messages: Vec<&str> = envelope.errors.iter().map(|e| e.message.as_str()).collect();
+        return Err(AppError::github_api(format!("GraphQL error: {}", messages.join("; "))));
+    }
+    envelope.data.ok_or_else(|| AppError::github_api("GraphQL response contained no data"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_error_maps_status_codes() {
+        let err = classify_error(StatusCode::UNAUTHORIZED, None, "Bad credentials".into());
+        assert!(matches!(err, AppError::Unauthorized(_)));
+
+        let err = classify_error(StatusCode::FORBIDDEN, Some("0"), String::new());
+        assert!(matches!(err, AppError::RateLimited(_)));
+
+        let err = classify_error(StatusCode::FORBIDDEN, Some("42"), String::new());
+        assert!(matches!(err, AppError::Unauthorized(_)));
+
+        let err = classify_error(StatusCode::NOT_FOUND, None, String::new());
+        assert!(matches!(err, AppError::NotFound(_)));
+
+        let err = classify_error(StatusCode::UNPROCESSABLE_ENTITY, None, String::new());
+        assert!(matches!(err, AppError::GitHubApi(_)));
+    }
+
+    #[test]
+    fn api_url_accepts_paths_and_urls() {
+        // Unit tests of commands point the base at a mock server, so compare against it.
+        let base = api_base();
+        assert_eq!(api_url("/user"), format!("{base}/user"));
+        assert_eq!(api_url("repos/a/b"), format!("{base}/repos/a/b"));
+        assert_eq!(
+            api_url("https://ghe.example.com/api/v3/user"),
+            "https://ghe.example.com/api/v3/user"
+        );
+    }
+
+    #[test]
+    fn next_link_finds_next_page() {
+        let header = concat!(
+            r#"<https://api.github.com/x?page=2>; rel="next", "#,
+            r#"<https://api.github.com/x?page=5>; rel="last""#
+        );
+        assert_eq!(next_link(header).as_deref(), Some("https://api.github.com/x?page=2"));
+        assert_eq!(next_link(r#"<https://api.github.com/x?page=1>; rel="prev""#), None);
+    }
+
+    #[test]
+    fn graphql_data_returns_payload() {
+        let envelope: GraphQlResponse<serde_json::Value> =
+            serde_json::from_str(r#"{"data": {"viewer": {"login": "octocat"}}}"#).unwrap();
+        let data = graphql_data(envelope).unwrap();
+        assert_eq!(data["viewer"]["login"], "octocat");
+    }
+
+    #[test]
+    fn graphql_data_surfaces_errors() {
+        let envelope: GraphQlResponse<serde_json::Value> = serde_json::from_str(
+            r#"{"data": null, "errors": [{"message": "Field 'x' doesn't exist"}]}"#,
+        )
+        .unwrap();
+        let err = graphql_data(envelope).unwrap_err();
+        assert!(err.to_string().contains("Field 'x' doesn't exist"));
+    }
+}
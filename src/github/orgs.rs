@@ -0,0 +1,69 @@
+//! Organizations: memberships, members, teams and billing.
+
+use super::{GitHubClient, api_base};
+use crate::error::AppError;
+use crate::models::{
+    BillingUsageItem, BillingUsageResponse, OrgMember, OrgRole, Organization, PullRequestUser,
+    Repository, Team,
+};
+
+/// Organization, team and billing endpoints.
+pub trait OrgsApi {
+    /// List the organizations of the authenticated user.
+    fn list_user_orgs(&self) -> Result<Vec<Organization>, AppError>;
+
+    /// List members of an organization with the given role.
+    fn list_org_members(&self, org: &str, role: OrgRole) -> Result<Vec<OrgMember>, AppError>;
+
+    /// List teams of an organization visible to the authenticated user.
+    fn list_org_teams(&self, org: &str) -> Result<Vec<Team>, AppError>;
+
+    /// List repositories a team can access; `permissions` holds the team's role on each.
+    fn list_team_repos(&self, org: &str, team_slug: &str) -> Result<Vec<Repository>, AppError>;
+
+    /// Get a team of an organization by its slug.
+    fn get_team(&self, org: &str, team_slug: &str) -> Result<Team, AppError>;
+
+    /// Get an organization's metered billing usage for a year.
+    fn get_org_billing_usage(
+        &self,
+        org: &str,
+        year: i32,
+    ) -> Result<Vec<BillingUsageItem>, AppError>;
+}
+
+impl OrgsApi for GitHubClient {
+    fn list_user_orgs(&self) -> Result<Vec<Organization>, AppError> {
+        self.get_all_pages(&format!("{}/user/orgs", api_base()))
+    }
+
+    fn list_org_members(&self, org: &str, role: OrgRole) -> Result<Vec<OrgMember>, AppError> {
+        let url = format!("{}/orgs/{}/members?role={}", api_base(), org, role.as_str());
+        let users: Vec<PullRequestUser> = self.get_all_pages(&url)?;
+        Ok(users.into_iter().map(|u| OrgMember { login: u.login, role }).collect())
+    }
+
+    fn list_org_teams(&self, org: &str) -> Result<Vec<Team>, AppError> {
+        self.get_all_pages(&format!("{}/orgs/{}/teams", api_base(), org))
+    }
+
+    fn list_team_repos(&self, org: &str, team_slug: &str) -> Result<Vec<Repository>, AppError> {
+        self.get_all_pages(&format!("{}/orgs/{}/teams/{}/repos", api_base(), org, team_slug))
+    }
+
+    fn get_team(&self, org: &str, team_slug: &str) -> Result<Team, AppError> {
+        let url = format!("{}/orgs/{}/teams/{}", api_base(), org, team_slug);
+        Self::parse_json(self.request(&url)?)
+    }
+
+    fn get_org_billing_usage(
+        &self,
+        org: &str,
+        year: i32,
+    ) -> Result<Vec<BillingUsageItem>, AppError> {
+        let url =
+            format!("{}/organizations/{}/settings/billing/usage?year={}", api_base(), org, year);
+        let response: BillingUsageResponse = Self::parse_json(self.request(&url)?)?;
+        Ok(response.usage_items)
+    }
+}
@@ -0,0 +1,108 @@
+//! GitHub Packages of the authenticated user or an organization.
+
+use super::{GitHubClient, api_base};
+use crate::error::AppError;
+use crate::models::{Package, PackageType, PackageVersion};
+
+/// Owner of packages in GitHub Packages.
+#[derive(Debug, Clone, Copy)]
+pub enum PackageOwner<'a> {
+    /// The authenticated user.
+    User,
+    /// An organization.
+    Org(&'a str),
+}
+
+impl PackageOwner<'_> {
+    fn packages_url(&self) -> String {
+        let base = api_base();
+        match self {
+            PackageOwner::User => format!("{base}/user/packages"),
+            PackageOwner::Org(org) => format!("{base}/orgs/{org}/packages"),
+        }
+    }
+
+    fn package_url(&self, package_type: PackageType, name: &str) -> String {
+        // Container names may contain slashes, which must be encoded in the path.
+        let name = name.replace('/', "%2F");
+        format!("{}/{}/{}", self.packages_url(), package_type.as_param(), name)
+    }
+}
+
+/// Package and package version endpoints.
+pub trait PackagesApi {
+    /// List all packages of one type owned by `owner`.
+    fn list_packages(
+        &self,
+        owner: PackageOwner<'_>,
+        package_type: PackageType,
+    ) -> Result<Vec<Package>, AppError>;
+
+    /// List every version of a package, newest first.
+    fn list_package_versions(
+        &self,
+        owner: PackageOwner<'_>,
+        package_type: PackageType,
+        name: &str,
+    ) -> Result<Vec<PackageVersion>, AppError>;
+
+    /// Delete a package and all of its versions.
+    fn delete_package(
+        &self,
+        owner: PackageOwner<'_>,
+        package_type: PackageType,
+        name: &str,
+    ) -> Result<(), AppError>;
+
+    /// Delete one version of a package.
+    fn delete_package_version(
+        &self,
+        owner: PackageOwner<'_>,
+        package_type: PackageType,
+        name: &str,
+        version_id: u64,
+    ) -> Result<(), AppError>;
+}
+
+impl PackagesApi for GitHubClient {
+    fn list_packages(
+        &self,
+        owner: PackageOwner<'_>,
+        package_type: PackageType,
+    ) -> Result<Vec<Package>, AppError> {
+        let url = format!("{}?package_type={}", owner.packages_url(), package_type.as_param());
+        self.get_all_pages(&url)
+    }
+
+    fn list_package_versions(
+        &self,
+        owner: PackageOwner<'_>,
+        package_type: PackageType,
+        name: &str,
+    ) -> Result<Vec<PackageVersion>, AppError> {
+        let url = format!("{}/versions?state=active", owner.package_url(package_type, name));
+        self.get_all_pages(&url)
+    }
+
+    fn delete_package(
+        &self,
+        owner: PackageOwner<'_>,
+        package_type: PackageType,
+        name: &str,
+    ) -> Result<(), AppError> {
+        self.send(self.client.delete(owner.package_url(package_type, name)))?;
+        Ok(())
+    }
+
+    fn delete_package_version(
+        &self,
+        owner: PackageOwner<'_>,
+        package_type: PackageType,
+        name: &str,
+        version_id: u64,
+    ) -> Result<(), AppError> {
+        let url = format!("{}/versions/{}", owner.package_url(package_type, name), version_id);
+        self.send(self.client.delete(&url))?;
+        Ok(())
+    }
+}
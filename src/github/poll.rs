@@ -0,0 +1,203 @@
+//! Quota-friendly polling for watch commands.
+//!
+//! A client made with [`GitHubClient::polling`](super::GitHubClient::polling) remembers the
+//! `ETag` and `Last-Modified` of every successful GET and sends them back as `If-None-Match` and
+//! `If-Modified-Since` the next time it requests the same URL. GitHub answers an unchanged
+//! resource with `304 Not Modified`, which does not count against the rate limit, and the
+//! client hands on the remembered response as if it had been fetched again, so the API traits
+//! work unchanged.
+//!
+//! [`Poller`] spaces the polls: the requested interval while something changes, half as long
+//! again after each poll that changes nothing (up to four times the interval), never shorter
+//! than the `X-Poll-Interval` GitHub asks for, and stretched when the rate limit runs low so
+//! that the rest of it lasts until it resets.
+
+use reqwest::StatusCode;
+use reqwest::Url;
+use reqwest::blocking::Response;
+use reqwest::header::{ETAG, HeaderMap, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use std::collections::HashMap;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Longest wait between polls, as a multiple of the requested interval.
+const MAX_BACKOFF: u32 = 4;
+/// Remaining requests below which polls are spread over the time until the rate limit resets.
+const LOW_RATE_LIMIT: u64 = 100;
+
+/// What the responses of a polling client said since the state was last taken.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PollState {
+    /// Some response was new rather than `304 Not Modified`.
+    pub changed: bool,
+    /// Seconds GitHub asks clients to wait between polls (`X-Poll-Interval`).
+    pub poll_interval: Option<u64>,
+    /// Requests left in the rate limit window, and seconds until the window resets.
+    pub rate_limit: Option<(u64, u64)>,
+}
+
+/// Remembered responses of a polling client, by URL.
+#[derive(Debug, Default)]
+pub(super) struct ResponseCache {
+    entries: HashMap<Url, Cached>,
+    state: PollState,
+}
+
+#[derive(Debug)]
+struct Cached {
+    headers: HeaderMap,
+    body: Vec<u8>,
+}
+
+impl ResponseCache {
+    /// Make a GET of `url` conditional on its remembered response, if there is one.
+    pub(super) fn prepare(&self, url: &Url, headers: &mut HeaderMap) {
+        let Some(cached) = self.entries.get(url) else {
+            return;
+        };
+        if let Some(etag) = cached.headers.get(ETAG) {
+            headers.insert(IF_NONE_MATCH, etag.clone());
+        }
+        if let Some(modified) = cached.headers.get(LAST_MODIFIED) {
+            headers.insert(IF_MODIFIED_SINCE, modified.clone());
+        }
+    }
+
+    /// Note the response to a GET of `url` and return the one to hand on: the remembered
+    /// response when GitHub answered `304 Not Modified`.
+    pub(super) fn record(&mut self, url: &Url, response: Response) -> reqwest::Result<Response> {
+        let number = |name: &str| {
+            let value = response.headers().get(name)?.to_str().ok()?;
+            value.parse::<u64>().ok()
+        };
+        if let Some(interval) = number("x-poll-interval") {
+            self.state.poll_interval = Some(interval);
+        }
+        if let (Some(remaining), Some(reset)) =
+            (number("x-ratelimit-remaining"), number("x-ratelimit-reset"))
+        {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+            self.state.rate_limit = Some((remaining, reset.saturating_sub(now)));
+        }
+
+        if response.status() == StatusCode::NOT_MODIFIED {
+            return Ok(self.entries.get(url).map_or(response, Cached::replay));
+        }
+        if !response.status().is_success() {
+            return Ok(response);
+        }
+        self.state.changed = true;
+        let headers = response.headers();
+        if !headers.contains_key(ETAG) && !headers.contains_key(LAST_MODIFIED) {
+            return Ok(response);
+        }
+        let headers = headers.clone();
+        let cached = Cached { headers, body: response.bytes()?.to_vec() };
+        let replay = cached.replay();
+        self.entries.insert(url.clone(), cached);
+        Ok(replay)
+    }
+
+    /// The state since the last call; `changed` starts over.
+    pub(super) fn take_state(&mut self) -> PollState {
+        let state = self.state;
+        self.state.changed = false;
+        state
+    }
+}
+
+impl Cached {
+    fn replay(&self) -> Response {
+        let mut response = http::Response::new(self.body.clone());
+        *response.headers_mut() = self.headers.clone();
+        response.into()
+    }
+}
+
+/// Spaces the polls of a watch command; see the [module documentation](self).
+#[derive(Debug, Clone)]
+pub struct Poller {
+    interval: Duration,
+    current: Duration,
+}
+
+impl Poller {
+    /// A poller waiting `interval` between polls while something changes.
+    pub fn new(interval: Duration) -> Self {
+        Self { interval, current: interval }
+    }
+
+    /// How long to wait before the next poll, given what the last one said.
+    pub fn next_delay(&mut self, state: &PollState) -> Duration {
+        self.current = if state.changed {
+            self.interval
+        } else {
+            (self.current * 3 / 2).min(self.interval * MAX_BACKOFF)
+        };
+        let mut delay = self.current;
+        if let Some(secs) = state.poll_interval {
+            delay = delay.max(Duration::from_secs(secs));
+        }
+        if let Some((remaining, reset_in)) = state.rate_limit
+            && remaining < LOW_RATE_LIMIT
+        {
+            delay = delay.max(Duration::from_secs(reset_in / remaining.max(1)));
+        }
+        delay
+    }
+
+    /// Sleep until the next poll.
+    pub fn wait(&mut self, state: &PollState) {
+        thread::sleep(self.next_delay(state));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(status: u16, headers: &[(&'static str, &str)], body: &str) -> Response {
+        let mut response = http::Response::new(body.as_bytes().to_vec());
+        *response.status_mut() = StatusCode::from_u16(status).unwrap();
+        for (name, value) in headers {
+            response.headers_mut().insert(*name, value.parse().unwrap());
+        }
+        response.into()
+    }
+
+    #[test]
+    fn cache_replays_the_remembered_response_on_not_modified() {
+        let url = Url::parse("https://api.github.com/repos/o/r/actions/runs/1").unwrap();
+        let mut cache = ResponseCache::default();
+        let first = cache.record(&url, response(200, &[("etag", "\"v1\"")], "{\"id\":1}")).unwrap();
+        assert_eq!(first.text().unwrap(), "{\"id\":1}");
+        assert!(cache.take_state().changed);
+
+        let mut headers = HeaderMap::new();
+        cache.prepare(&url, &mut headers);
+        assert_eq!(headers[IF_NONE_MATCH], "\"v1\"");
+
+        let limits = [("x-ratelimit-remaining", "4000"), ("x-ratelimit-reset", "0")];
+        let replayed = cache.record(&url, response(304, &limits, "")).unwrap();
+        assert_eq!(replayed.status(), StatusCode::OK);
+        assert_eq!(replayed.text().unwrap(), "{\"id\":1}");
+        let state = cache.take_state();
+        assert!(!state.changed);
+        assert_eq!(state.rate_limit, Some((4000, 0)));
+    }
+
+    #[test]
+    fn poller_backs_off_while_nothing_changes() {
+        let mut poller = Poller::new(Duration::from_secs(4));
+        let unchanged = PollState::default();
+        let delays: Vec<u64> = (0..5).map(|_| poller.next_delay(&unchanged).as_secs()).collect();
+        assert_eq!(delays, [6, 9, 13, 16, 16]);
+        let changed = PollState { changed: true, ..PollState::default() };
+        assert_eq!(poller.next_delay(&changed), Duration::from_secs(4));
+
+        let asked = PollState { changed: true, poll_interval: Some(60), rate_limit: None };
+        assert_eq!(poller.next_delay(&asked), Duration::from_secs(60));
+        let low = PollState { changed: true, poll_interval: None, rate_limit: Some((10, 600)) };
+        assert_eq!(poller.next_delay(&low), Duration::from_secs(60));
+    }
+}
@@ -0,0 +1,531 @@
+//! Pull requests: listing, creation and merging, reviews, drafts and merge queues.
+
+use super::{DEFAULT_LIMIT, GRAPHQL_MAX_PAGE, GitHubClient, api_base};
+use crate::error::AppError;
+use crate::models::{
+    Commit, MergeMethod, MergeQueue, MergeQueueEntry, MergeResult, NewPullRequest, PullRequest,
+    PullRequestDashboardData, PullRequestSearchData, PullRequestSearchNode, PullRequestStatusData,
+    PullRequestStatusNode, PullRequestUpdate, Review, ReviewComment, ReviewEvent, ReviewRequest,
+};
+use reqwest::Method;
+
+const PULL_REQUEST_STATUS_QUERY: &str = r#"
+query($owner: String!, $name: String!, $limit: Int!) {
+  repository(owner: $owner, name: $name) {
+    pullRequests(states: OPEN, first: $limit, orderBy: {field: UPDATED_AT, direction: DESC}) {
+      nodes {
+        number
+        title
+        author { login }
+        headRefName
+        mergeable
+        reviewDecision
+        commits(last: 1) { nodes { commit { statusCheckRollup { state } } } }
+      }
+    }
+  }
+}
+"#;
+
+const PULL_REQUEST_DASHBOARD_QUERY: &str = r#"
+query($created: String!, $reviewRequested: String!, $assigned: String!, $limit: Int!) {
+  created: search(query: $created, type: ISSUE, first: $limit) {
+    nodes { ...dashboardPullRequest }
+  }
+  reviewRequested: search(query: $reviewRequested, type: ISSUE, first: $limit) {
+    nodes { ...dashboardPullRequest }
+  }
+  assigned: search(query: $assigned, type: ISSUE, first: $limit) {
+    nodes { ...dashboardPullRequest }
+  }
+}
+
+fragment dashboardPullRequest on PullRequest {
+  number
+  title
+  author { login }
+  headRefName
+  mergeable
+  reviewDecision
+  repository { nameWithOwner }
+  commits(last: 1) { nodes { commit { statusCheckRollup { state } } } }
+}
+"#;
+
+const PULL_REQUEST_SEARCH_QUERY: &str = r#"
+query($query: String!, $limit: Int!, $cursor: String) {
+  search(query: $query, type: ISSUE, first: $limit, after: $cursor) {
+    pageInfo { hasNextPage endCursor }
+    nodes {
+      ... on PullRequest {
+        number
+        title
+        author { login }
+        headRefName
+        mergeable
+        reviewDecision
+        updatedAt
+        repository { nameWithOwner }
+        commits(last: 1) { nodes { commit { statusCheckRollup { state } } } }
+      }
+    }
+  }
+}
+"#;
+
+const PULL_REQUEST_ID_QUERY: &str = r#"
+query($owner: String!, $name: String!, $number: Int!) {
+  repository(owner: $owner, name: $name) { pullRequest(number: $number) { id } }
+}
+"#;
+
+const ENQUEUE_PULL_REQUEST_MUTATION: &str = r#"
+mutation($id: ID!) {
+  enqueuePullRequest(input: {pullRequestId: $id}) {
+    mergeQueueEntry {
+      position
+      state
+      estimatedTimeToMerge
+      enqueuedAt
+      pullRequest { number title }
+    }
+  }
+}
+"#;
+
+const MARK_READY_FOR_REVIEW_MUTATION: &str = r#"
+mutation($id: ID!) {
+  markPullRequestReadyForReview(input: {pullRequestId: $id}) { pullRequest { isDraft } }
+}
+"#;
+
+const CONVERT_TO_DRAFT_MUTATION: &str = r#"
+mutation($id: ID!) {
+  convertPullRequestToDraft(input: {pullRequestId: $id}) { pullRequest { isDraft } }
+}
+"#;
+
+const MERGE_QUEUE_QUERY: &str = r#"
+query($owner: String!, $name: String!, $branch: String) {
+  repository(owner: $owner, name: $name) {
+    mergeQueue(branch: $branch) {
+      url
+      entries(first: 100) {
+        nodes {
+          position
+          state
+          estimatedTimeToMerge
+          enqueuedAt
+          pullRequest { number title }
+        }
+      }
+    }
+  }
+}
+"#;
+
+/// Pull request, review and merge queue endpoints.
+pub trait PullsApi {
+    /// List open pull requests for a repository.
+    fn list_pull_requests(
+        &self,
+        owner: &str,
+        repo: &str,
+        limit: usize,
+    ) -> Result<Vec<PullRequest>, AppError>;
+
+    /// List open pull requests with CI rollup and review decision in one GraphQL call.
+    fn list_pull_request_statuses(
+        &self,
+        owner: &str,
+        repo: &str,
+        limit: usize,
+    ) -> Result<Vec<PullRequestStatusNode>, AppError>;
+
+    /// Search open pull requests created by, requesting review from, or assigned to the
+    /// authenticated user.
+    fn pull_request_dashboard(&self, limit: usize) -> Result<PullRequestDashboardData, AppError>;
+
+    /// Search pull requests with a search `query` (e.g. `is:open is:pr org:acme`), with CI
+    /// rollup and review decision, up to `limit` results in search order.
+    fn search_pull_request_statuses(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<PullRequestSearchNode>, AppError>;
+
+    /// Get a single pull request.
+    fn get_pull_request(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<PullRequest, AppError>;
+
+    /// Get the unified diff of a pull request.
+    fn get_pull_request_diff(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<String, AppError>;
+
+    /// Open a pull request.
+    fn create_pull_request(
+        &self,
+        owner: &str,
+        repo: &str,
+        pull_request: &NewPullRequest,
+    ) -> Result<PullRequest, AppError>;
+
+    /// Change the title, body or base branch of a pull request.
+    fn update_pull_request(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        update: &PullRequestUpdate,
+    ) -> Result<PullRequest, AppError>;
+
+    /// Request reviews of a pull request from users and teams.
+    fn request_reviewers(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        request: &ReviewRequest,
+    ) -> Result<(), AppError>;
+
+    /// Withdraw review requests of a pull request.
+    fn remove_requested_reviewers(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        request: &ReviewRequest,
+    ) -> Result<(), AppError>;
+
+    /// Merge a pull request directly.
+    fn merge_pull_request(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        method: MergeMethod,
+    ) -> Result<MergeResult, AppError>;
+
+    /// Close (`open == false`) or reopen a pull request.
+    fn set_pull_request_open(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        open: bool,
+    ) -> Result<PullRequest, AppError>;
+
+    /// List the commits of a pull request, oldest first.
+    fn list_pull_request_commits(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<Vec<Commit>, AppError>;
+
+    /// Get the GraphQL node ID of a pull request.
+    fn pull_request_node_id(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<String, AppError>;
+
+    /// Add a pull request to its base branch's merge queue.
+    fn enqueue_pull_request(&self, node_id: &str) -> Result<MergeQueueEntry, AppError>;
+
+    /// Convert a pull request to a draft, or mark it ready for review. Returns the new draft
+    /// state.
+    fn set_pull_request_draft(&self, node_id: &str, draft: bool) -> Result<bool, AppError>;
+
+    /// Get the merge queue for a branch (the default branch when `branch` is `None`).
+    fn get_merge_queue(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: Option<&str>,
+    ) -> Result<Option<MergeQueue>, AppError>;
+
+    /// List reviews submitted on a pull request.
+    fn list_reviews(&self, owner: &str, repo: &str, number: u64) -> Result<Vec<Review>, AppError>;
+
+    /// List review comments on the diff of a pull request, oldest first.
+    fn list_review_comments(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<Vec<ReviewComment>, AppError>;
+
+    /// Submit a review on a pull request.
+    fn create_review(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        event: ReviewEvent,
+        body: Option<&str>,
+    ) -> Result<Review, AppError>;
+}
+
+impl PullsApi for GitHubClient {
+    fn list_pull_requests(
+        &self,
+        owner: &str,
+        repo: &str,
+        limit: usize,
+    ) -> Result<Vec<PullRequest>, AppError> {
+        let limit = if limit == 0 { DEFAULT_LIMIT } else { limit };
+        let url = format!(
+            "{}/repos/{}/{}/pulls?state=open&sort=updated&direction=desc&per_page={}",
+            api_base(),
+            owner,
+            repo,
+            limit
+        );
+        let response = self.request(&url)?;
+        Self::parse_json(response)
+    }
+
+    fn list_pull_request_statuses(
+        &self,
+        owner: &str,
+        repo: &str,
+        limit: usize,
+    ) -> Result<Vec<PullRequestStatusNode>, AppError> {
+        let limit = if limit == 0 { DEFAULT_LIMIT } else { limit.min(GRAPHQL_MAX_PAGE) };
+        let variables = serde_json::json!({ "owner": owner, "name": repo, "limit": limit });
+        let data: PullRequestStatusData = self.graphql(PULL_REQUEST_STATUS_QUERY, variables)?;
+        let repository = data.repository.ok_or_else(|| {
+            AppError::github_api(format!("repository '{owner}/{repo}' not found"))
+        })?;
+        Ok(repository.pull_requests.nodes)
+    }
+
+    fn pull_request_dashboard(&self, limit: usize) -> Result<PullRequestDashboardData, AppError> {
+        let limit = if limit == 0 { DEFAULT_LIMIT } else { limit.min(GRAPHQL_MAX_PAGE) };
+        let query =
+            |qualifier: &str| format!("is:open is:pr archived:false sort:updated-desc {qualifier}");
+        let variables = serde_json::json!({
+            "created": query("author:@me"),
+            "reviewRequested": query("review-requested:@me"),
+            "assigned": query("assignee:@me"),
+            "limit": limit,
+        });
+        self.graphql(PULL_REQUEST_DASHBOARD_QUERY, variables)
+    }
+
+    fn search_pull_request_statuses(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<PullRequestSearchNode>, AppError> {
+        let mut prs = Vec::new();
+        let mut cursor: Option<String> = None;
+        while prs.len() < limit {
+            let page = (limit - prs.len()).min(GRAPHQL_MAX_PAGE);
+            let variables = serde_json::json!({ "query": query, "limit": page, "cursor": cursor });
+            let data: PullRequestSearchData = self.graphql(PULL_REQUEST_SEARCH_QUERY, variables)?;
+            prs.extend(data.search.nodes);
+            match data.search.page_info.end_cursor {
+                Some(next) if data.search.page_info.has_next_page => cursor = Some(next),
+                _ => break,
+            }
+        }
+        prs.truncate(limit);
+        Ok(prs)
+    }
+
+    fn get_pull_request(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<PullRequest, AppError> {
+        let url = format!("{}/repos/{}/{}/pulls/{}", api_base(), owner, repo, number);
+        let response = self.request(&url)?;
+        Self::parse_json(response)
+    }
+
+    fn get_pull_request_diff(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<String, AppError> {
+        let url = format!("{}/repos/{}/{}/pulls/{}", api_base(), owner, repo, number);
+        self.request_text(&url, "application/vnd.github.diff")
+    }
+
+    fn create_pull_request(
+        &self,
+        owner: &str,
+        repo: &str,
+        pull_request: &NewPullRequest,
+    ) -> Result<PullRequest, AppError> {
+        let url = format!("{}/repos/{}/{}/pulls", api_base(), owner, repo);
+        let response = self.request_with_body(Method::POST, &url, pull_request)?;
+        Self::parse_json(response)
+    }
+
+    fn update_pull_request(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        update: &PullRequestUpdate,
+    ) -> Result<PullRequest, AppError> {
+        let url = format!("{}/repos/{}/{}/pulls/{}", api_base(), owner, repo, number);
+        let response = self.request_with_body(Method::PATCH, &url, update)?;
+        Self::parse_json(response)
+    }
+
+    fn request_reviewers(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        request: &ReviewRequest,
+    ) -> Result<(), AppError> {
+        let url = requested_reviewers_url(owner, repo, number);
+        self.request_with_body(Method::POST, &url, request)?;
+        Ok(())
+    }
+
+    fn remove_requested_reviewers(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        request: &ReviewRequest,
+    ) -> Result<(), AppError> {
+        let url = requested_reviewers_url(owner, repo, number);
+        self.request_with_body(Method::DELETE, &url, request)?;
+        Ok(())
+    }
+
+    fn merge_pull_request(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        method: MergeMethod,
+    ) -> Result<MergeResult, AppError> {
+        let url = format!("{}/repos/{}/{}/pulls/{}/merge", api_base(), owner, repo, number);
+        let payload = serde_json::json!({ "merge_method": method });
+        let response = self.request_with_body(Method::PUT, &url, &payload)?;
+        Self::parse_json(response)
+    }
+
+    fn set_pull_request_open(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        open: bool,
+    ) -> Result<PullRequest, AppError> {
+        let url = format!("{}/repos/{}/{}/pulls/{}", api_base(), owner, repo, number);
+        let payload = serde_json::json!({ "state": if open { "open" } else { "closed" } });
+        let response = self.request_with_body(Method::PATCH, &url, &payload)?;
+        Self::parse_json(response)
+    }
+
+    fn list_pull_request_commits(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<Vec<Commit>, AppError> {
+        let url = format!("{}/repos/{}/{}/pulls/{}/commits", api_base(), owner, repo, number);
+        self.get_all_pages(&url)
+    }
+
+    fn pull_request_node_id(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<String, AppError> {
+        let variables = serde_json::json!({ "owner": owner, "name": repo, "number": number });
+        let data: serde_json::Value = self.graphql(PULL_REQUEST_ID_QUERY, variables)?;
+        data["repository"]["pullRequest"]["id"].as_str().map(str::to_string).ok_or_else(|| {
+            AppError::github_api(format!("pull request #{number} not found in {owner}/{repo}"))
+        })
+    }
+
+    fn enqueue_pull_request(&self, node_id: &str) -> Result<MergeQueueEntry, AppError> {
+        let data: serde_json::Value =
+            self.graphql(ENQUEUE_PULL_REQUEST_MUTATION, serde_json::json!({ "id": node_id }))?;
+        let entry = data["enqueuePullRequest"]["mergeQueueEntry"].clone();
+        serde_json::from_value(entry)
+            .map_err(|e| AppError::github_api(format!("failed to parse merge queue entry: {e}")))
+    }
+
+    fn set_pull_request_draft(&self, node_id: &str, draft: bool) -> Result<bool, AppError> {
+        let (mutation, field) = if draft {
+            (CONVERT_TO_DRAFT_MUTATION, "convertPullRequestToDraft")
+        } else {
+            (MARK_READY_FOR_REVIEW_MUTATION, "markPullRequestReadyForReview")
+        };
+        let data: serde_json::Value =
+            self.graphql(mutation, serde_json::json!({ "id": node_id }))?;
+        data[field]["pullRequest"]["isDraft"]
+            .as_bool()
+            .ok_or_else(|| AppError::github_api(format!("unexpected {field} response")))
+    }
+
+    fn get_merge_queue(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: Option<&str>,
+    ) -> Result<Option<MergeQueue>, AppError> {
+        let variables = serde_json::json!({ "owner": owner, "name": repo, "branch": branch });
+        let data: serde_json::Value = self.graphql(MERGE_QUEUE_QUERY, variables)?;
+        let queue = data["repository"]["mergeQueue"].clone();
+        serde_json::from_value(queue)
+            .map_err(|e| AppError::github_api(format!("failed to parse merge queue: {e}")))
+    }
+
+    fn list_reviews(&self, owner: &str, repo: &str, number: u64) -> Result<Vec<Review>, AppError> {
+        let url = format!("{}/repos/{}/{}/pulls/{}/reviews", api_base(), owner, repo, number);
+        let response = self.request(&url)?;
+        Self::parse_json(response)
+    }
+
+    fn list_review_comments(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<Vec<ReviewComment>, AppError> {
+        let url = format!("{}/repos/{}/{}/pulls/{}/comments", api_base(), owner, repo, number);
+        self.get_all_pages(&url)
+    }
+
+    fn create_review(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        event: ReviewEvent,
+        body: Option<&str>,
+    ) -> Result<Review, AppError> {
+        let url = format!("{}/repos/{}/{}/pulls/{}/reviews", api_base(), owner, repo, number);
+        let payload = serde_json::json!({ "event": event, "body": body });
+        let response = self.request_with_body(Method::POST, &url, &payload)?;
+        Self::parse_json(response)
+    }
+}
+
+fn requested_reviewers_url(owner: &str, repo: &str, number: u64) -> String {
+    format!("{}/repos/{}/{}/pulls/{}/requested_reviewers", api_base(), owner, repo, number)
+}
@@ -0,0 +1,741 @@
+//! Repositories: listing and search, branches and their protection, commits, releases and
+//! webhooks.
+
+use super::{
+    DEFAULT_LIMIT, GRAPHQL_MAX_PAGE, GitHubClient, MAX_PAGE_SIZE, TRANSFER_TIMEOUT_SECS, api_base,
+};
+use crate::error::AppError;
+use crate::models::{
+    BranchProtection, BranchProtectionRules, BranchRefNode, BranchRefsData, CombinedStatusResponse,
+    Commit, CommitStatus, HookDelivery, NewRelease, NewRepository, NewWebhook, Release,
+    ReleaseAsset, Repository, RepositoryTopics, RequiredStatusChecks, SearchResponse, SearchSort,
+    Webhook,
+};
+use reqwest::blocking::Body;
+use reqwest::header::{CONTENT_LENGTH, CONTENT_TYPE};
+use reqwest::{Method, Url};
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+const BRANCH_REFS_QUERY: &str = r#"
+query($owner: String!, $name: String!, $default: String!, $limit: Int!, $cursor: String) {
+  repository(owner: $owner, name: $name) {
+    refs(refPrefix: "refs/heads/", first: $limit, after: $cursor) {
+      pageInfo { hasNextPage endCursor }
+      nodes {
+        name
+        target { oid ... on Commit { committedDate } }
+        compare(headRef: $default) { behindCount }
+        associatedPullRequests(
+          first: 1, states: MERGED, orderBy: {field: UPDATED_AT, direction: DESC}
+        ) {
+          nodes { number headRefOid }
+        }
+      }
+    }
+  }
+}
+"#;
+
+/// Media type of the topics endpoints, which older GitHub Enterprise Server versions still keep
+/// behind the `mercy` preview.
+const TOPICS_MEDIA_TYPE: &str = "application/vnd.github.mercy-preview+json";
+
+/// Content of a release asset upload.
+#[derive(Debug, Clone, Copy)]
+pub enum UploadSource<'a> {
+    /// Stream the file from disk.
+    File(&'a Path),
+    /// Upload an in-memory buffer.
+    Bytes(&'a [u8]),
+}
+
+/// Repository, branch, commit, release and webhook endpoints.
+pub trait ReposApi {
+    /// List repositories for a user.
+    fn list_user_repos(&self, username: &str, limit: usize) -> Result<Vec<Repository>, AppError>;
+
+    /// One page of a user's repositories, most recently pushed first.
+    fn list_user_repos_page(
+        &self,
+        username: &str,
+        per_page: usize,
+        page: usize,
+    ) -> Result<Vec<Repository>, AppError>;
+
+    /// List repositories for an organization.
+    fn list_org_repos(&self, org: &str, limit: usize) -> Result<Vec<Repository>, AppError>;
+
+    /// One page of an organization's repositories, most recently pushed first.
+    ///
+    /// `repo_type` is the API's `type` filter: `public`, `private`, `forks` or `sources`.
+    fn list_org_repos_page(
+        &self,
+        org: &str,
+        repo_type: Option<&str>,
+        per_page: usize,
+        page: usize,
+    ) -> Result<Vec<Repository>, AppError>;
+
+    /// Get a specific repository.
+    fn get_repo(&self, owner: &str, repo: &str) -> Result<Repository, AppError>;
+
+    /// Create a repository in an organization.
+    fn create_org_repo(&self, org: &str, repo: &NewRepository) -> Result<Repository, AppError>;
+
+    /// Delete a repository. Requires the `delete_repo` scope.
+    fn delete_repo(&self, owner: &str, repo: &str) -> Result<(), AppError>;
+
+    /// Rename a repository; GitHub redirects the old name.
+    fn rename_repo(&self, owner: &str, repo: &str, name: &str) -> Result<Repository, AppError>;
+
+    /// Start transferring a repository to `new_owner`, giving the teams `team_ids` of an
+    /// organization owner access to it.
+    fn transfer_repo(
+        &self,
+        owner: &str,
+        repo: &str,
+        new_owner: &str,
+        team_ids: &[u64],
+    ) -> Result<Repository, AppError>;
+
+    /// Archive or unarchive a repository.
+    fn set_repo_archived(
+        &self,
+        owner: &str,
+        repo: &str,
+        archived: bool,
+    ) -> Result<Repository, AppError>;
+
+    /// One page of the authenticated user's starred repositories, most recently starred first.
+    fn list_starred_repos_page(
+        &self,
+        per_page: usize,
+        page: usize,
+    ) -> Result<Vec<Repository>, AppError>;
+
+    /// Star (or unstar) a repository as the authenticated user.
+    fn set_repo_starred(&self, owner: &str, repo: &str, starred: bool) -> Result<(), AppError>;
+
+    /// Watch all activity of a repository, or stop watching it (back to participating and
+    /// @mentions only).
+    fn set_repo_watched(&self, owner: &str, repo: &str, watched: bool) -> Result<(), AppError>;
+
+    /// Topics of a repository.
+    fn get_repo_topics(&self, owner: &str, repo: &str) -> Result<Vec<String>, AppError>;
+
+    /// Replace all topics of a repository; returns the topics GitHub stored.
+    fn replace_repo_topics(
+        &self,
+        owner: &str,
+        repo: &str,
+        topics: &[String],
+    ) -> Result<Vec<String>, AppError>;
+
+    /// Search repositories using the search API query syntax.
+    fn search_repos(
+        &self,
+        query: &str,
+        sort: SearchSort,
+        limit: usize,
+    ) -> Result<SearchResponse<Repository>, AppError>;
+
+    /// Get a commit by SHA, branch or tag.
+    fn get_commit(&self, owner: &str, repo: &str, reference: &str) -> Result<Commit, AppError>;
+
+    /// Latest status of each context reported for a commit through the statuses API.
+    fn list_commit_statuses(
+        &self,
+        owner: &str,
+        repo: &str,
+        reference: &str,
+    ) -> Result<Vec<CommitStatus>, AppError>;
+
+    /// Get the required status checks of a protected branch.
+    fn get_required_status_checks(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+    ) -> Result<RequiredStatusChecks, AppError>;
+
+    /// Replace the required status check contexts of a protected branch.
+    fn update_required_status_checks(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+        contexts: &[String],
+    ) -> Result<RequiredStatusChecks, AppError>;
+
+    /// Get the protection of a branch; `NotFound` when the branch is not protected.
+    fn get_branch_protection(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+    ) -> Result<BranchProtection, AppError>;
+
+    /// Replace the protection of a branch with `rules`.
+    ///
+    /// Settings `rules` does not cover (push restrictions, force pushes, deletions) are reset
+    /// to GitHub's defaults.
+    fn update_branch_protection(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+        rules: &BranchProtectionRules,
+    ) -> Result<(), AppError>;
+
+    /// List every branch with its head commit, compared with `default_branch`.
+    fn list_branch_refs(
+        &self,
+        owner: &str,
+        repo: &str,
+        default_branch: &str,
+    ) -> Result<Vec<BranchRefNode>, AppError>;
+
+    /// Delete a branch.
+    fn delete_branch(&self, owner: &str, repo: &str, branch: &str) -> Result<(), AppError>;
+
+    /// Make `branch` the default branch of a repository.
+    fn set_default_branch(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+    ) -> Result<Repository, AppError>;
+
+    /// List releases of a repository, newest first.
+    fn list_releases(
+        &self,
+        owner: &str,
+        repo: &str,
+        limit: usize,
+    ) -> Result<Vec<Release>, AppError>;
+
+    /// Create a release.
+    fn create_release(
+        &self,
+        owner: &str,
+        repo: &str,
+        release: &NewRelease,
+    ) -> Result<Release, AppError>;
+
+    /// Get a release by its tag name.
+    fn get_release_by_tag(&self, owner: &str, repo: &str, tag: &str) -> Result<Release, AppError>;
+
+    /// Stream the content of a release asset into `writer`, returning the bytes written.
+    ///
+    /// Works for private repositories, unlike `browser_download_url`.
+    fn download_release_asset(
+        &self,
+        owner: &str,
+        repo: &str,
+        asset_id: u64,
+        writer: &mut impl Write,
+    ) -> Result<u64, AppError>;
+
+    /// Delete a release asset.
+    fn delete_release_asset(&self, owner: &str, repo: &str, asset_id: u64) -> Result<(), AppError>;
+
+    /// Upload a release asset to a release's `upload_url`.
+    ///
+    /// Files are streamed from disk rather than buffered, and the request uses a long
+    /// timeout suitable for large artifacts.
+    fn upload_release_asset(
+        &self,
+        upload_url: &str,
+        name: &str,
+        source: UploadSource<'_>,
+    ) -> Result<ReleaseAsset, AppError>;
+
+    /// List the webhooks of a repository.
+    fn list_hooks(&self, owner: &str, repo: &str) -> Result<Vec<Webhook>, AppError>;
+
+    /// Create a repository webhook.
+    fn create_hook(&self, owner: &str, repo: &str, hook: &NewWebhook) -> Result<Webhook, AppError>;
+
+    /// Delete a repository webhook.
+    fn delete_hook(&self, owner: &str, repo: &str, hook_id: u64) -> Result<(), AppError>;
+
+    /// Send a `ping` event to a webhook.
+    fn ping_hook(&self, owner: &str, repo: &str, hook_id: u64) -> Result<(), AppError>;
+
+    /// The most recent deliveries of a webhook, newest first (at most 100).
+    fn list_hook_deliveries(
+        &self,
+        owner: &str,
+        repo: &str,
+        hook_id: u64,
+        limit: usize,
+    ) -> Result<Vec<HookDelivery>, AppError>;
+}
+
+impl ReposApi for GitHubClient {
+    fn list_user_repos(&self, username: &str, limit: usize) -> Result<Vec<Repository>, AppError> {
+        let limit = if limit == 0 { DEFAULT_LIMIT } else { limit };
+        self.list_user_repos_page(username, limit, 1)
+    }
+
+    fn list_user_repos_page(
+        &self,
+        username: &str,
+        per_page: usize,
+        page: usize,
+    ) -> Result<Vec<Repository>, AppError> {
+        let url = format!(
+            "{}/users/{}/repos?sort=pushed&direction=desc&per_page={}&page={}",
+            api_base(),
+            username,
+            per_page,
+            page
+        );
+        let response = self.request(&url)?;
+        Self::parse_json(response)
+    }
+
+    fn list_org_repos(&self, org: &str, limit: usize) -> Result<Vec<Repository>, AppError> {
+        let limit = if limit == 0 { DEFAULT_LIMIT } else { limit };
+        self.list_org_repos_page(org, None, limit, 1)
+    }
+
+    fn list_org_repos_page(
+        &self,
+        org: &str,
+        repo_type: Option<&str>,
+        per_page: usize,
+        page: usize,
+    ) -> Result<Vec<Repository>, AppError> {
+        let url = format!(
+            "{}/orgs/{}/repos?sort=pushed&direction=desc&type={}&per_page={}&page={}",
+            api_base(),
+            org,
+            repo_type.unwrap_or("all"),
+            per_page,
+            page
+        );
+        let response = self.request(&url)?;
+        Self::parse_json(response)
+    }
+
+    fn get_repo(&self, owner: &str, repo: &str) -> Result<Repository, AppError> {
+        let url = format!("{}/repos/{}/{}", api_base(), owner, repo);
+        let response = self.request(&url)?;
+        Self::parse_json(response)
+    }
+
+    fn create_org_repo(&self, org: &str, repo: &NewRepository) -> Result<Repository, AppError> {
+        let url = format!("{}/orgs/{}/repos", api_base(), org);
+        let response = self.request_with_body(Method::POST, &url, repo)?;
+        Self::parse_json(response)
+    }
+
+    fn delete_repo(&self, owner: &str, repo: &str) -> Result<(), AppError> {
+        let url = format!("{}/repos/{}/{}", api_base(), owner, repo);
+        self.send(self.client.delete(url))?;
+        Ok(())
+    }
+
+    fn rename_repo(&self, owner: &str, repo: &str, name: &str) -> Result<Repository, AppError> {
+        let url = format!("{}/repos/{}/{}", api_base(), owner, repo);
+        let payload = serde_json::json!({ "name": name });
+        Self::parse_json(self.request_with_body(Method::PATCH, &url, &payload)?)
+    }
+
+    fn transfer_repo(
+        &self,
+        owner: &str,
+        repo: &str,
+        new_owner: &str,
+        team_ids: &[u64],
+    ) -> Result<Repository, AppError> {
+        let url = format!("{}/repos/{}/{}/transfer", api_base(), owner, repo);
+        let mut payload = serde_json::json!({ "new_owner": new_owner });
+        if !team_ids.is_empty() {
+            payload["team_ids"] = serde_json::json!(team_ids);
+        }
+        Self::parse_json(self.request_with_body(Method::POST, &url, &payload)?)
+    }
+
+    fn set_repo_archived(
+        &self,
+        owner: &str,
+        repo: &str,
+        archived: bool,
+    ) -> Result<Repository, AppError> {
+        let url = format!("{}/repos/{}/{}", api_base(), owner, repo);
+        let payload = serde_json::json!({ "archived": archived });
+        let response = self.request_with_body(Method::PATCH, &url, &payload)?;
+        Self::parse_json(response)
+    }
+
+    fn list_starred_repos_page(
+        &self,
+        per_page: usize,
+        page: usize,
+    ) -> Result<Vec<Repository>, AppError> {
+        let url = format!(
+            "{}/user/starred?sort=created&direction=desc&per_page={}&page={}",
+            api_base(),
+            per_page,
+            page
+        );
+        let response = self.request(&url)?;
+        Self::parse_json(response)
+    }
+
+    fn set_repo_starred(&self, owner: &str, repo: &str, starred: bool) -> Result<(), AppError> {
+        let url = format!("{}/user/starred/{}/{}", api_base(), owner, repo);
+        // Starring is a PUT without a body, which GitHub only accepts with an explicit length.
+        let builder = if starred {
+            self.client.put(&url).header(CONTENT_LENGTH, 0)
+        } else {
+            self.client.delete(&url)
+        };
+        self.send(builder)?;
+        Ok(())
+    }
+
+    fn set_repo_watched(&self, owner: &str, repo: &str, watched: bool) -> Result<(), AppError> {
+        let url = format!("{}/repos/{}/{}/subscription", api_base(), owner, repo);
+        if watched {
+            let payload = serde_json::json!({ "subscribed": true, "ignored": false });
+            self.request_with_body(Method::PUT, &url, &payload)?;
+        } else {
+            self.send(self.client.delete(&url))?;
+        }
+        Ok(())
+    }
+
+    fn get_repo_topics(&self, owner: &str, repo: &str) -> Result<Vec<String>, AppError> {
+        let url = format!("{}/repos/{}/{}/topics", api_base(), owner, repo);
+        let response = self.send_accepting(self.client.get(&url), TOPICS_MEDIA_TYPE)?;
+        Self::parse_json::<RepositoryTopics>(response).map(|t| t.names)
+    }
+
+    fn replace_repo_topics(
+        &self,
+        owner: &str,
+        repo: &str,
+        topics: &[String],
+    ) -> Result<Vec<String>, AppError> {
+        let url = format!("{}/repos/{}/{}/topics", api_base(), owner, repo);
+        let payload = RepositoryTopics { names: topics.to_vec() };
+        let builder = self.client.put(&url).json(&payload);
+        let response = self.send_accepting(builder, TOPICS_MEDIA_TYPE)?;
+        Self::parse_json::<RepositoryTopics>(response).map(|t| t.names)
+    }
+
+    fn search_repos(
+        &self,
+        query: &str,
+        sort: SearchSort,
+        limit: usize,
+    ) -> Result<SearchResponse<Repository>, AppError> {
+        let limit = if limit == 0 { DEFAULT_LIMIT } else { limit };
+        let mut params = vec![("q", query.to_string()), ("per_page", limit.to_string())];
+        if let Some(sort) = sort.as_param() {
+            params.push(("sort", sort.to_string()));
+        }
+        let url = Url::parse_with_params(&format!("{}/search/repositories", api_base()), &params)
+            .map_err(|e| AppError::invalid_input(format!("invalid search query: {e}")))?;
+        let response = self.request(url.as_str())?;
+        Self::parse_json(response)
+    }
+
+    fn get_commit(&self, owner: &str, repo: &str, reference: &str) -> Result<Commit, AppError> {
+        let url = format!("{}/repos/{}/{}/commits/{}", api_base(), owner, repo, reference);
+        Self::parse_json(self.request(&url)?)
+    }
+
+    fn list_commit_statuses(
+        &self,
+        owner: &str,
+        repo: &str,
+        reference: &str,
+    ) -> Result<Vec<CommitStatus>, AppError> {
+        let url = format!(
+            "{}/repos/{}/{}/commits/{}/status?per_page={}",
+            api_base(),
+            owner,
+            repo,
+            reference,
+            MAX_PAGE_SIZE
+        );
+        let combined: CombinedStatusResponse = Self::parse_json(self.request(&url)?)?;
+        Ok(combined.statuses)
+    }
+
+    fn get_required_status_checks(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+    ) -> Result<RequiredStatusChecks, AppError> {
+        let url = required_status_checks_url(owner, repo, branch);
+        let response = self.request(&url)?;
+        Self::parse_json(response)
+    }
+
+    fn update_required_status_checks(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+        contexts: &[String],
+    ) -> Result<RequiredStatusChecks, AppError> {
+        let url = required_status_checks_url(owner, repo, branch);
+        let payload = serde_json::json!({ "contexts": contexts });
+        let response = self.request_with_body(Method::PATCH, &url, &payload)?;
+        Self::parse_json(response)
+    }
+
+    fn get_branch_protection(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+    ) -> Result<BranchProtection, AppError> {
+        let url = branch_protection_url(owner, repo, branch);
+        Self::parse_json(self.request(&url)?)
+    }
+
+    fn update_branch_protection(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+        rules: &BranchProtectionRules,
+    ) -> Result<(), AppError> {
+        let url = branch_protection_url(owner, repo, branch);
+        self.request_with_body(Method::PUT, &url, &branch_protection_body(rules))?;
+        Ok(())
+    }
+
+    fn list_branch_refs(
+        &self,
+        owner: &str,
+        repo: &str,
+        default_branch: &str,
+    ) -> Result<Vec<BranchRefNode>, AppError> {
+        let mut branches = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let variables = serde_json::json!({
+                "owner": owner, "name": repo, "default": default_branch,
+                "limit": GRAPHQL_MAX_PAGE, "cursor": cursor,
+            });
+            let data: BranchRefsData = self.graphql(BRANCH_REFS_QUERY, variables)?;
+            let refs = data
+                .repository
+                .ok_or_else(|| {
+                    AppError::github_api(format!("repository '{owner}/{repo}' not found"))
+                })?
+                .refs;
+            branches.extend(refs.nodes);
+            match refs.page_info.end_cursor {
+                Some(next) if refs.page_info.has_next_page => cursor = Some(next),
+                _ => break,
+            }
+        }
+        Ok(branches)
+    }
+
+    fn delete_branch(&self, owner: &str, repo: &str, branch: &str) -> Result<(), AppError> {
+        let url = format!("{}/repos/{}/{}/git/refs/heads/{}", api_base(), owner, repo, branch);
+        self.send(self.client.delete(&url))?;
+        Ok(())
+    }
+
+    fn set_default_branch(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+    ) -> Result<Repository, AppError> {
+        let url = format!("{}/repos/{}/{}", api_base(), owner, repo);
+        let payload = serde_json::json!({ "default_branch": branch });
+        let response = self.request_with_body(Method::PATCH, &url, &payload)?;
+        Self::parse_json(response)
+    }
+
+    fn list_releases(
+        &self,
+        owner: &str,
+        repo: &str,
+        limit: usize,
+    ) -> Result<Vec<Release>, AppError> {
+        let url = format!(
+            "{}/repos/{}/{}/releases?per_page={}",
+            api_base(),
+            owner,
+            repo,
+            limit.min(MAX_PAGE_SIZE)
+        );
+        let response = self.request(&url)?;
+        Self::parse_json(response)
+    }
+
+    fn create_release(
+        &self,
+        owner: &str,
+        repo: &str,
+        release: &NewRelease,
+    ) -> Result<Release, AppError> {
+        let url = format!("{}/repos/{}/{}/releases", api_base(), owner, repo);
+        let response = self.request_with_body(Method::POST, &url, release)?;
+        Self::parse_json(response)
+    }
+
+    fn get_release_by_tag(&self, owner: &str, repo: &str, tag: &str) -> Result<Release, AppError> {
+        let url = format!("{}/repos/{}/{}/releases/tags/{}", api_base(), owner, repo, tag);
+        let response = self.request(&url)?;
+        Self::parse_json(response)
+    }
+
+    fn download_release_asset(
+        &self,
+        owner: &str,
+        repo: &str,
+        asset_id: u64,
+        writer: &mut impl Write,
+    ) -> Result<u64, AppError> {
+        let url = format!("{}/repos/{}/{}/releases/assets/{}", api_base(), owner, repo, asset_id);
+        self.download(&url, "application/octet-stream", writer)
+    }
+
+    fn delete_release_asset(&self, owner: &str, repo: &str, asset_id: u64) -> Result<(), AppError> {
+        let url = format!("{}/repos/{}/{}/releases/assets/{}", api_base(), owner, repo, asset_id);
+        self.send(self.client.delete(&url))?;
+        Ok(())
+    }
+
+    fn upload_release_asset(
+        &self,
+        upload_url: &str,
+        name: &str,
+        source: UploadSource<'_>,
+    ) -> Result<ReleaseAsset, AppError> {
+        let base = upload_url.split('{').next().unwrap_or(upload_url);
+        let url = Url::parse_with_params(base, &[("name", name)])
+            .map_err(|e| AppError::github_api(format!("invalid upload URL: {e}")))?;
+
+        let body = match source {
+            UploadSource::File(path) => {
+                let file = File::open(path)?;
+                let len = file.metadata()?.len();
+                Body::sized(file, len)
+            }
+            UploadSource::Bytes(bytes) => Body::from(bytes.to_vec()),
+        };
+
+        let builder = self
+            .client
+            .post(url)
+            .header(CONTENT_TYPE, "application/octet-stream")
+            .timeout(Duration::from_secs(TRANSFER_TIMEOUT_SECS))
+            .body(body);
+        let response = self.send(builder)?;
+        Self::parse_json(response)
+    }
+
+    fn list_hooks(&self, owner: &str, repo: &str) -> Result<Vec<Webhook>, AppError> {
+        let url = format!("{}/repos/{}/{}/hooks", api_base(), owner, repo);
+        self.get_all_pages(&url)
+    }
+
+    fn create_hook(&self, owner: &str, repo: &str, hook: &NewWebhook) -> Result<Webhook, AppError> {
+        let url = format!("{}/repos/{}/{}/hooks", api_base(), owner, repo);
+        let response = self.request_with_body(Method::POST, &url, hook)?;
+        Self::parse_json(response)
+    }
+
+    fn delete_hook(&self, owner: &str, repo: &str, hook_id: u64) -> Result<(), AppError> {
+        let url = format!("{}/repos/{}/{}/hooks/{}", api_base(), owner, repo, hook_id);
+        self.send(self.client.delete(&url))?;
+        Ok(())
+    }
+
+    fn ping_hook(&self, owner: &str, repo: &str, hook_id: u64) -> Result<(), AppError> {
+        let url = format!("{}/repos/{}/{}/hooks/{}/pings", api_base(), owner, repo, hook_id);
+        self.send(self.client.post(&url))?;
+        Ok(())
+    }
+
+    fn list_hook_deliveries(
+        &self,
+        owner: &str,
+        repo: &str,
+        hook_id: u64,
+        limit: usize,
+    ) -> Result<Vec<HookDelivery>, AppError> {
+        // Deliveries page by cursor, not page number; one page is enough for recent results.
+        let url = format!(
+            "{}/repos/{}/{}/hooks/{}/deliveries?per_page={}",
+            api_base(),
+            owner,
+            repo,
+            hook_id,
+            limit.clamp(1, MAX_PAGE_SIZE)
+        );
+        Self::parse_json(self.request(&url)?)
+    }
+}
+
+fn required_status_checks_url(owner: &str, repo: &str, branch: &str) -> String {
+    format!(
+        "{}/repos/{}/{}/branches/{}/protection/required_status_checks",
+        api_base(),
+        owner,
+        repo,
+        branch
+    )
+}
+
+fn branch_protection_url(owner: &str, repo: &str, branch: &str) -> String {
+    format!("{}/repos/{}/{}/branches/{}/protection", api_base(), owner, repo, branch)
+}
+
+/// Body of the update branch protection API; disabled sections are sent as `null`.
+fn branch_protection_body(rules: &BranchProtectionRules) -> serde_json::Value {
+    let checks = rules.status_checks.as_ref().map(|contexts| {
+        serde_json::json!({ "strict": rules.strict_status_checks, "contexts": contexts })
+    });
+    let reviews = rules.required_reviews.map(|count| {
+        serde_json::json!({
+            "required_approving_review_count": count,
+            "dismiss_stale_reviews": rules.dismiss_stale_reviews,
+            "require_code_owner_reviews": rules.require_code_owner_reviews,
+        })
+    });
+    serde_json::json!({
+        "required_status_checks": checks,
+        "enforce_admins": rules.enforce_admins,
+        "required_pull_request_reviews": reviews,
+        "restrictions": null,
+        "required_linear_history": rules.linear_history,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn branch_protection_body_nulls_disabled_sections() {
+        let rules = BranchProtectionRules {
+            required_reviews: Some(1),
+            linear_history: true,
+            ..Default::default()
+        };
+        let body = branch_protection_body(&rules);
+        assert_eq!(body["required_status_checks"], serde_json::Value::Null);
+        assert_eq!(body["required_pull_request_reviews"]["required_approving_review_count"], 1);
+        assert_eq!(body["required_linear_history"], true);
+        assert_eq!(body["restrictions"], serde_json::Value::Null);
+    }
+}
@@ -0,0 +1,164 @@
+//! The authenticated user: rate limits, token expiry, notifications, gists and
+//! contributions.
+
+use super::{GitHubClient, MAX_PAGE_SIZE, api_base};
+use crate::domain::parse_token_expiration_header;
+use crate::error::AppError;
+use crate::models::{
+    AuthenticatedUser, ContributionsData, ContributionsViewer, Gist, NewGist, Notification,
+    NotificationFilter, RateLimit, RateLimitResponse, TokenInfo,
+};
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+use std::collections::BTreeMap;
+
+const CONTRIBUTIONS_QUERY: &str = r#"
+query($from: DateTime!, $to: DateTime!) {
+  viewer {
+    login
+    contributionsCollection(from: $from, to: $to) {
+      totalCommitContributions
+      totalPullRequestContributions
+      totalPullRequestReviewContributions
+      totalIssueContributions
+      restrictedContributionsCount
+      commitContributionsByRepository(maxRepositories: 100) {
+        repository { nameWithOwner }
+        contributions { totalCount }
+      }
+    }
+  }
+}
+"#;
+
+/// Response header carrying the expiry of fine-grained and expiring classic tokens.
+const TOKEN_EXPIRATION_HEADER: &str = "github-authentication-token-expiration";
+
+/// Endpoints scoped to the authenticated user.
+pub trait UsersApi {
+    /// The user the token authenticates as, with its OAuth scopes and remaining rate limit.
+    fn get_token_info(&self) -> Result<TokenInfo, AppError>;
+
+    /// Get the rate limit quotas of the token, by resource.
+    ///
+    /// Checking the rate limit does not count against it.
+    fn get_rate_limits(&self) -> Result<BTreeMap<String, RateLimit>, AppError>;
+
+    /// Expiry of the client's token, read from the `GitHub-Authentication-Token-Expiration`
+    /// header; `None` for tokens that never expire.
+    fn token_expiration(&self) -> Result<Option<DateTime<Utc>>, AppError>;
+
+    /// List notification threads of the authenticated user, most recent first.
+    fn list_notifications(
+        &self,
+        filter: NotificationFilter,
+        limit: usize,
+    ) -> Result<Vec<Notification>, AppError>;
+
+    /// Get a single notification thread.
+    fn get_notification_thread(&self, id: &str) -> Result<Notification, AppError>;
+
+    /// Mark a notification thread as read.
+    fn mark_notification_read(&self, id: &str) -> Result<(), AppError>;
+
+    /// Mark every notification thread as read.
+    fn mark_all_notifications_read(&self) -> Result<(), AppError>;
+
+    /// Create a gist.
+    fn create_gist(&self, gist: &NewGist) -> Result<Gist, AppError>;
+
+    /// List gists of the authenticated user, most recently updated first.
+    fn list_gists(&self, limit: usize) -> Result<Vec<Gist>, AppError>;
+
+    /// Contributions of the authenticated user between `from` and `to`, at most a year apart.
+    fn get_contributions(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<ContributionsViewer, AppError>;
+}
+
+impl UsersApi for GitHubClient {
+    fn get_token_info(&self) -> Result<TokenInfo, AppError> {
+        let url = format!("{}/user", api_base());
+        let response = self.request(&url)?;
+        let header = |name| response.headers().get(name).and_then(|v| v.to_str().ok());
+        let scopes = header("x-oauth-scopes").map(|scopes| {
+            scopes.split(',').map(str::trim).filter(|s| !s.is_empty()).map(str::to_string).collect()
+        });
+        let rate_limit_remaining = header("x-ratelimit-remaining").and_then(|v| v.parse().ok());
+        let user: AuthenticatedUser = Self::parse_json(response)?;
+        Ok(TokenInfo { login: user.login, scopes, rate_limit_remaining })
+    }
+
+    fn get_rate_limits(&self) -> Result<BTreeMap<String, RateLimit>, AppError> {
+        let url = format!("{}/rate_limit", api_base());
+        let response: RateLimitResponse = Self::parse_json(self.request(&url)?)?;
+        Ok(response.resources)
+    }
+
+    fn token_expiration(&self) -> Result<Option<DateTime<Utc>>, AppError> {
+        let url = format!("{}/rate_limit", api_base());
+        let response = self.request(&url)?;
+        Ok(response
+            .headers()
+            .get(TOKEN_EXPIRATION_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(parse_token_expiration_header))
+    }
+
+    fn list_notifications(
+        &self,
+        filter: NotificationFilter,
+        limit: usize,
+    ) -> Result<Vec<Notification>, AppError> {
+        let limit = limit.clamp(1, MAX_PAGE_SIZE);
+        let url = format!(
+            "{}/notifications?per_page={}&all={}&participating={}",
+            api_base(),
+            limit,
+            !filter.unread,
+            filter.participating
+        );
+        Self::parse_json(self.request(&url)?)
+    }
+
+    fn get_notification_thread(&self, id: &str) -> Result<Notification, AppError> {
+        let url = format!("{}/notifications/threads/{}", api_base(), id);
+        Self::parse_json(self.request(&url)?)
+    }
+
+    fn mark_notification_read(&self, id: &str) -> Result<(), AppError> {
+        let url = format!("{}/notifications/threads/{}", api_base(), id);
+        self.send(self.client.patch(url))?;
+        Ok(())
+    }
+
+    fn mark_all_notifications_read(&self) -> Result<(), AppError> {
+        let url = format!("{}/notifications", api_base());
+        self.request_with_body(Method::PUT, &url, &serde_json::json!({ "read": true }))?;
+        Ok(())
+    }
+
+    fn create_gist(&self, gist: &NewGist) -> Result<Gist, AppError> {
+        let url = format!("{}/gists", api_base());
+        let response = self.request_with_body(Method::POST, &url, gist)?;
+        Self::parse_json(response)
+    }
+
+    fn list_gists(&self, limit: usize) -> Result<Vec<Gist>, AppError> {
+        let limit = limit.clamp(1, MAX_PAGE_SIZE);
+        let url = format!("{}/gists?per_page={}", api_base(), limit);
+        Self::parse_json(self.request(&url)?)
+    }
+
+    fn get_contributions(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<ContributionsViewer, AppError> {
+        let variables = serde_json::json!({ "from": from.to_rfc3339(), "to": to.to_rfc3339() });
+        let data: ContributionsData = self.graphql(CONTRIBUTIONS_QUERY, variables)?;
+        Ok(data.viewer)
+    }
+}
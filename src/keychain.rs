@@ -1,45 +1,197 @@
 //! Keychain integration for token storage.
+//!
+//! Secrets go to the platform credential store through `keyring`: the Keychain on macOS and
+//! the Credential Manager on Windows. gho calls either one "the Keychain".
 
+use crate::config::TokenPrecedence;
 use crate::error::AppError;
+use crate::models::{Account, TokenSource};
+use crate::platform;
 use keyring::Entry;
+use std::sync::RwLock;
 
-const SERVICE_NAME: &str = "gho";
+/// Keychain service used until [`set_service`] selects another.
+pub const DEFAULT_SERVICE: &str = "gho";
 
-/// Store a token in the macOS Keychain.
+static SERVICE: RwLock<Option<String>> = RwLock::new(None);
+
+static PRECEDENCE: RwLock<TokenPrecedence> = RwLock::new(TokenPrecedence::Auto);
+
+/// Use `service` for every later Keychain access (the `keychain_service` setting).
+pub fn set_service(service: &str) {
+    *SERVICE.write().unwrap_or_else(|e| e.into_inner()) = Some(service.to_string());
+}
+
+/// The Keychain service in use.
+pub fn service() -> String {
+    SERVICE
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_else(|| DEFAULT_SERVICE.to_string())
+}
+
+/// Whether later token lookups honor `GH_TOKEN`/`GITHUB_TOKEN` (the `token_source` setting, or
+/// `keychain` with `--no-env-token`).
+pub fn set_precedence(precedence: TokenPrecedence) {
+    *PRECEDENCE.write().unwrap_or_else(|e| e.into_inner()) = precedence;
+}
+
+/// The token precedence in use.
+pub fn precedence() -> TokenPrecedence {
+    *PRECEDENCE.read().unwrap_or_else(|e| e.into_inner())
+}
+
+fn entry(service: &str, name: &str) -> Result<Entry, AppError> {
+    Entry::new(service, name).map_err(|e| failure("create keychain entry", e))
+}
+
+/// A credential store error, with a hint for the failures users can act on.
+fn failure(action: &str, error: keyring::Error) -> AppError {
+    let hint = match &error {
+        keyring::Error::NoStorageAccess(_) => " (is the credential store locked or unavailable?)",
+        keyring::Error::TooLong(..) => " (the Windows Credential Manager limits their length)",
+        _ => "",
+    };
+    AppError::keychain(format!("failed to {action}: {error}{hint}"))
+}
+
+/// Store a token in the Keychain.
 pub fn store_token(account_id: &str, token: &str) -> Result<(), AppError> {
-    let entry = Entry::new(SERVICE_NAME, account_id)
-        .map_err(|e| AppError::keychain(format!("failed to create keychain entry: {e}")))?;
-    entry
-        .set_password(token)
-        .map_err(|e| AppError::keychain(format!("failed to store token: {e}")))?;
-    Ok(())
+    store_in(&service(), account_id, token)
 }
 
-/// Retrieve a token from the macOS Keychain.
+/// Store a secret under `name` in a specific Keychain service.
+pub fn store_in(service: &str, name: &str, secret: &str) -> Result<(), AppError> {
+    entry(service, name)?.set_password(secret).map_err(|e| failure("store token", e))
+}
+
+/// Read the secret under `name` from a specific Keychain service, if there is one.
+pub fn read_in(service: &str, name: &str) -> Result<Option<String>, AppError> {
+    match entry(service, name)?.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(failure("retrieve token", e)),
+    }
+}
+
+/// Delete the secret under `name` from a specific Keychain service.
+pub fn delete_in(service: &str, name: &str) -> Result<(), AppError> {
+    entry(service, name)?.delete_credential().map_err(|e| failure("delete token", e))
+}
+
+/// Retrieve a token from the Keychain.
 pub fn get_token(account_id: &str) -> Result<String, AppError> {
-    // Check for environment variable overrides first
-    if let Ok(token) = std::env::var("GH_TOKEN") {
+    if let Some(token) = env_token() {
         return Ok(token);
     }
-    if let Ok(token) = std::env::var("GITHUB_TOKEN") {
+    require_stored_allowed()?;
+    get_stored_token(account_id)
+}
+
+/// Retrieve the token for an account from its configured source.
+///
+/// Environment overrides win (unless the precedence is `keychain`), then the account's
+/// `token_command`, then the Keychain.
+pub fn get_account_token(account: &Account) -> Result<String, AppError> {
+    if let Some(token) = env_token() {
         return Ok(token);
     }
+    require_stored_allowed()?;
+    match &account.token_command {
+        Some(command) => run_token_command(command),
+        None => get_stored_token(&account.id),
+    }
+}
 
-    let entry = Entry::new(SERVICE_NAME, account_id)
-        .map_err(|e| AppError::keychain(format!("failed to create keychain entry: {e}")))?;
-    entry.get_password().map_err(|e| AppError::keychain(format!("failed to retrieve token: {e}")))
+/// Retrieve a token from the Keychain, ignoring environment overrides.
+pub fn get_stored_token(account_id: &str) -> Result<String, AppError> {
+    entry(&service(), account_id)?.get_password().map_err(|e| failure("retrieve token", e))
 }
 
-/// Delete a token from the macOS Keychain.
-pub fn delete_token(account_id: &str) -> Result<(), AppError> {
-    let entry = Entry::new(SERVICE_NAME, account_id)
-        .map_err(|e| AppError::keychain(format!("failed to create keychain entry: {e}")))?;
-    entry
-        .delete_credential()
-        .map_err(|e| AppError::keychain(format!("failed to delete token: {e}")))?;
+/// Run a token lookup command and return its trimmed stdout.
+pub fn run_token_command(command: &str) -> Result<String, AppError> {
+    let output = platform::shell(command)
+        .output()
+        .map_err(|e| AppError::keychain(format!("failed to run token command: {e}")))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(AppError::keychain(format!("token command failed: {}", stderr.trim())));
+    }
+
+    let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if token.is_empty() {
+        return Err(AppError::keychain("token command returned no output"));
+    }
+    Ok(token)
+}
+
+/// Where [`get_account_token`] reads the token of `account` from, without reading it.
+pub fn token_source(account: &Account) -> TokenSource {
+    if let Some((source, _)) = env_override() {
+        return source;
+    }
+    match account.token_command {
+        Some(_) => TokenSource::TokenCommand,
+        None => TokenSource::Keychain,
+    }
+}
+
+fn env_token() -> Option<String> {
+    env_override().map(|(_, token)| token)
+}
+
+/// Stored tokens are off limits when the precedence is `env`.
+fn require_stored_allowed() -> Result<(), AppError> {
+    if precedence() == TokenPrecedence::Env {
+        return Err(AppError::keychain(
+            "token_source is env, but neither GH_TOKEN nor GITHUB_TOKEN is set",
+        ));
+    }
     Ok(())
 }
 
+fn env_override() -> Option<(TokenSource, String)> {
+    if precedence() == TokenPrecedence::Keychain {
+        return None;
+    }
+    std::env::var("GH_TOKEN")
+        .map(|token| (TokenSource::GhToken, token))
+        .or_else(|_| std::env::var("GITHUB_TOKEN").map(|token| (TokenSource::GithubToken, token)))
+        .ok()
+}
+
+/// Delete a token from the Keychain.
+pub fn delete_token(account_id: &str) -> Result<(), AppError> {
+    delete_in(&service(), account_id)
+}
+
+/// Store the sudo PIN for a protected account.
+pub fn store_pin(account_id: &str, pin: &str) -> Result<(), AppError> {
+    store_token(&pin_entry(account_id), pin)
+}
+
+/// Retrieve the sudo PIN for a protected account, if one is configured.
+pub fn get_pin(account_id: &str) -> Option<String> {
+    get_stored_token(&pin_entry(account_id)).ok()
+}
+
+/// Delete the sudo PIN for a protected account.
+pub fn delete_pin(account_id: &str) -> Result<(), AppError> {
+    delete_token(&pin_entry(account_id))
+}
+
+/// Keychain entry name of an account's sudo PIN.
+pub fn pin_entry(account_id: &str) -> String {
+    format!("{account_id}:sudo-pin")
+}
+
+/// Keychain entry name of the webhook secret `name` (`hook create --secret-keychain`).
+pub fn webhook_secret_entry(name: &str) -> String {
+    format!("webhook:{name}")
+}
+
 /// Mask a token for display.
 pub fn mask_token(token: &str) -> String {
     if token.len() <= 8 {
@@ -60,6 +212,18 @@ mod tests {
         assert!(masked.ends_with("cdef"));
     }
 
+    #[test]
+    fn run_token_command_trims_output() {
+        let token = run_token_command("echo ghp_abc").unwrap();
+        assert_eq!(token, "ghp_abc");
+    }
+
+    #[test]
+    fn run_token_command_rejects_empty_output() {
+        assert!(run_token_command("true").is_err());
+        assert!(run_token_command("exit 3").is_err());
+    }
+
     #[test]
     fn mask_token_short_string() {
         let masked = mask_token("short");
@@ -1,15 +1,47 @@
 //! gho - GitHub operator CLI for multi-account workflows.
+//!
+//! The models, storage, configuration and `domain` layers are always available. API access,
+//! keychain token storage, interactive prompts and clone discovery are gated behind the
+//! `network`, `keychain`, `interactive` and `scan` features so embedders can depend on the
+//! core without pulling in their dependencies.
 
+#[cfg(feature = "keychain")]
+pub mod backup;
+pub mod browser;
 pub mod commands;
 pub mod config;
+pub mod domain;
 pub mod error;
+#[cfg(feature = "network")]
 pub mod github;
+#[cfg(feature = "keychain")]
 pub mod keychain;
+pub mod logging;
+#[cfg(all(test, feature = "network", feature = "keychain"))]
+mod mock_api;
 pub mod models;
+pub mod output;
+pub mod pager;
+pub mod platform;
+pub mod progress;
+pub mod remote;
+#[cfg(feature = "scan")]
+pub mod scan;
 pub mod storage;
+pub mod vault;
+pub mod workflow;
 
-pub use commands::{account, pr, repo};
-pub use config::Config;
+#[cfg(feature = "keychain")]
+pub use commands::account;
+#[cfg(feature = "selftest")]
+pub use commands::selftest;
+#[cfg(all(feature = "network", feature = "keychain"))]
+pub use commands::{
+    access, actions, api, auth, branch, checks, commit, conflicts, contrib, daemon, fanout, gist,
+    hook, issue, label, manifest, notify, org, package, pr, protection, queue, release, repo, run,
+    runner, secret, variable, workspace,
+};
+pub use config::{Config, Settings};
 pub use error::AppError;
 pub use models::{Account, AccountKind, AccountsFile, Protocol, Repository};
 pub use storage::{FilesystemStorage, Storage};
@@ -0,0 +1,174 @@
+//! Diagnostic logging (`-v`, `-vv`, `GHO_LOG`).
+//!
+//! gho emits `tracing` events for API requests and responses, cache hits and git
+//! invocations. [`init`] installs a small subscriber that prints gho's own events to stderr;
+//! every line passes through [`redact`] so tokens never end up in a terminal or CI log.
+
+use std::fmt::{self, Write as _};
+use std::process::Command;
+use tracing::field::{Field, Visit};
+use tracing::level_filters::LevelFilter;
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+/// Environment variable selecting the log level when no `-v` is given.
+pub const LOG_ENV: &str = "GHO_LOG";
+
+const LEVELS: &str = "off, error, warn, info, debug or trace";
+
+/// Prefixes of GitHub token formats (classic, OAuth, user-to-server, server-to-server,
+/// refresh and fine-grained).
+const TOKEN_PREFIXES: [&str; 6] = ["ghp_", "gho_", "ghu_", "ghs_", "ghr_", "github_pat_"];
+
+/// Install the stderr logger.
+///
+/// `verbose` is the number of `-v` flags: one logs debug events, two or more trace events.
+/// Without any, the level comes from `GHO_LOG` (`off`, `error`, `warn`, `info`, `debug` or
+/// `trace`) and defaults to warnings; an invalid value is reported and ignored.
+pub fn init(verbose: u8) {
+    let max = match verbose {
+        0 => match std::env::var(LOG_ENV) {
+            Ok(value) if !value.trim().is_empty() => parse_level(&value).unwrap_or_else(|| {
+                eprintln!("⚠️  Ignoring {LOG_ENV}={value} (expected {LEVELS})");
+                LevelFilter::WARN
+            }),
+            _ => LevelFilter::WARN,
+        },
+        1 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    };
+    // A second call (e.g. from an embedder that installed its own subscriber) keeps the first.
+    let _ = tracing::subscriber::set_global_default(StderrLogger { max });
+}
+
+/// Parse a `GHO_LOG` level, case-insensitively.
+pub fn parse_level(value: &str) -> Option<LevelFilter> {
+    value.trim().parse().ok()
+}
+
+/// Log a subprocess about to run, e.g. `git clone <url> <dir>`, and hand it back for chaining.
+pub fn command(command: &mut Command) -> &mut Command {
+    if tracing::enabled!(tracing::Level::DEBUG) {
+        let mut line = command.get_program().to_string_lossy().into_owned();
+        for arg in command.get_args() {
+            line.push(' ');
+            line.push_str(&arg.to_string_lossy());
+        }
+        if let Some(dir) = command.get_current_dir() {
+            tracing::debug!(target: "gho::command", dir = %dir.display(), "{line}");
+        } else {
+            tracing::debug!(target: "gho::command", "{line}");
+        }
+    }
+    command
+}
+
+/// `text` with GitHub tokens and `Bearer` credentials replaced by `***`.
+pub fn redact(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    loop {
+        let next = TOKEN_PREFIXES
+            .iter()
+            .chain(["Bearer "].iter())
+            .filter_map(|prefix| rest.find(prefix).map(|at| (at, prefix.len())))
+            .min();
+        let Some((at, prefix)) = next else {
+            out.push_str(rest);
+            return out;
+        };
+        let secret = &rest[at + prefix..];
+        let len =
+            secret.find(|c: char| !c.is_ascii_alphanumeric() && c != '_').unwrap_or(secret.len());
+        out.push_str(&rest[..at + prefix]);
+        if len > 0 {
+            out.push_str("***");
+        }
+        rest = &secret[len..];
+    }
+}
+
+/// Prints gho's events as `LEVEL target: message key=value` lines on stderr.
+struct StderrLogger {
+    max: LevelFilter,
+}
+
+impl Subscriber for StderrLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.level() <= &self.max && metadata.target().starts_with("gho")
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        Some(self.max)
+    }
+
+    // gho only logs events; spans are accepted and ignored.
+    fn new_span(&self, _span: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let metadata = event.metadata();
+        let mut line = Line::default();
+        event.record(&mut line);
+        eprintln!(
+            "{:>5} {}: {}{}",
+            metadata.level(),
+            metadata.target(),
+            redact(&line.message),
+            redact(&line.fields)
+        );
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+#[derive(Default)]
+struct Line {
+    message: String,
+    fields: String,
+}
+
+impl Visit for Line {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record_debug(field, &format_args!("{value}"));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{value:?}");
+        } else {
+            let _ = write!(self.fields, " {}={value:?}", field.name());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redact_hides_tokens_and_credentials() {
+        assert_eq!(
+            redact("GET /user with ghp_abc123XYZ and github_pat_11AB_cd"),
+            "GET /user with ghp_*** and github_pat_***"
+        );
+        assert_eq!(redact("Authorization: Bearer s3cr3t."), "Authorization: Bearer ***.");
+        assert_eq!(redact("repo gho_ is fine"), "repo gho_ is fine");
+        assert_eq!(redact("gho repo list"), "gho repo list");
+    }
+
+    #[test]
+    fn parse_level_accepts_tracing_levels() {
+        assert_eq!(parse_level("debug"), Some(LevelFilter::DEBUG));
+        assert_eq!(parse_level(" TRACE "), Some(LevelFilter::TRACE));
+        assert_eq!(parse_level("off"), Some(LevelFilter::OFF));
+        assert_eq!(parse_level("loud"), None);
+    }
+}
@@ -1,21 +1,109 @@
-use clap::{Parser, Subcommand, ValueEnum};
-use gho::error::AppError;
+use chrono::{DateTime, Utc};
+use clap::{ArgGroup, Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+use gho::account::ConflictAction;
+use gho::api::ApiRequest;
+use gho::branch::BranchFilter;
+use gho::browser;
+use gho::commands::bootstrap;
+use gho::commands::clones;
+use gho::commands::config as config_cmd;
+use gho::commands::doctor;
+use gho::commands::gitconfig::{self, GitIdentityOptions};
+use gho::commands::history;
+use gho::commands::init::{self, CompletionShell};
+use gho::commands::recent;
+use gho::commands::sshagent;
+use gho::commands::workflow as workflow_cmd;
+use gho::config::{ColorChoice, Config, Settings, TokenPrecedence};
+use gho::conflicts::FixOutcome;
+use gho::contrib::ContribAccounts;
+use gho::error::{AppError, exit_code};
+use gho::fanout::{FanoutChange, FanoutOptions};
+use gho::github;
+use gho::hook::{HookOptions, SecretSource};
 use gho::keychain;
-use gho::models::{AccountKind, Protocol};
+use gho::logging;
+use gho::models::{
+    AccountKind, AccountScoped, CheckRunAnnotation, CloneLayout, CloneOptions, Contributions,
+    DoctorStatus, Execution, ExecutionMode, Label, MergeMethod, Notification, NotificationFilter,
+    OrgRole, PackageType, Protocol, PullRequestDashboard, PullRequestUpdate, PullRequestView,
+    RepoFilter, RepoMove, RepoSort, RepoVisibility, Repository, RequiredChecksChange,
+    RequiredChecksEntry, ReviewEvent, SearchSort, SecretVisibility, Snapshot, TokenSource,
+    WebhookContentType,
+};
+use gho::output;
+use gho::output::style::{self, Tone};
+use gho::pr::{PrCreate, PrMetadata, PrSearch};
+use gho::progress::Progress;
+use gho::protection::ProtectionTargets;
+use gho::release::{CreateOptions, ReleaseNotes, UploadOptions};
 use gho::storage::FilesystemStorage;
-use gho::{account, pr, repo};
+use gho::vault::VaultKind;
+use gho::{
+    access, account, actions, api, auth, branch, checks, commit, conflicts, contrib, daemon,
+    domain, fanout, gist, hook, issue, label, manifest, notify, org, package, pager, pr,
+    protection, queue, release, remote, repo, run, runner, scan, secret, variable, workspace,
+};
+use gho::{esay, say};
+use std::collections::HashMap;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::time::Duration;
 
 #[derive(Parser)]
 #[command(name = "gho")]
 #[command(version)]
 #[command(about = "GitHub operator CLI for multi-account workflows", long_about = None)]
+#[command(after_help = "Exit codes:
+  0  success
+  1  general error
+  2  invalid input or usage
+  3  authentication (no active account, bad token, keychain)
+  4  not found (account, repository, pull request)
+  5  network error
+  6  rate limit exceeded
+  7  checks still pending (pr checks)")]
 struct Cli {
+    /// Run list commands for every account in the named group
+    #[clap(long, global = true, value_name = "GROUP")]
+    accounts: Option<String>,
+
+    /// Print what the command would do (commands, API calls, paths) without doing it
+    #[clap(long, global = true)]
+    dry_run: bool,
+
+    /// Progress output of bulk commands: `json` writes NDJSON events to stderr
+    #[clap(long, global = true, value_enum, default_value = "human")]
+    progress: ProgressArg,
+
+    /// Log API requests, cache hits and git commands to stderr (-vv for more detail; see also
+    /// GHO_LOG)
+    #[clap(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Ignore GH_TOKEN and GITHUB_TOKEN and use the account's own token (like token_source =
+    /// keychain)
+    #[clap(long, global = true)]
+    no_env_token: bool,
+
+    /// When to color output; given before the command, e.g. `gho --color never pr status`
+    /// [default: config `color`]
+    #[clap(long, value_enum, value_name = "WHEN")]
+    color: Option<ColorArg>,
+
+    /// Log-friendly output without emoji or colors; given before the command
+    #[clap(long)]
+    plain: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
 
 #[derive(Subcommand)]
 enum Commands {
+    /// Set up the first account, shell completions and git's credential helper interactively
+    Init,
     /// Manage GitHub accounts
     #[clap(visible_alias = "a")]
     Account {
@@ -28,12 +116,224 @@ enum Commands {
         #[command(subcommand)]
         command: RepoCommands,
     },
+    /// List and clean up remote branches, and change the default branch
+    Branch {
+        #[command(subcommand)]
+        command: BranchCommands,
+    },
+    /// Inspect organizations, their members and teams
+    Org {
+        #[command(subcommand)]
+        command: OrgCommands,
+    },
     /// Manage pull requests
     #[clap(visible_alias = "p")]
     Pr {
         #[command(subcommand)]
         command: PrCommands,
     },
+    /// Inspect commits
+    Commit {
+        #[command(subcommand)]
+        command: CommitCommands,
+    },
+    /// Manage repository webhooks and inspect their deliveries
+    Hook {
+        #[command(subcommand)]
+        command: HookCommands,
+    },
+    /// Manage Actions secrets of a repository or organization
+    Secret {
+        #[command(subcommand)]
+        command: SecretCommands,
+    },
+    /// Manage Actions variables of a repository or organization
+    Variable {
+        #[command(subcommand)]
+        command: VariableCommands,
+    },
+    /// Manage issue and pull request labels
+    Label {
+        #[command(subcommand)]
+        command: LabelCommands,
+    },
+    /// Inspect merge queues
+    Queue {
+        #[command(subcommand)]
+        command: QueueCommands,
+    },
+    /// Inspect workflow runs
+    Run {
+        #[command(subcommand)]
+        command: RunCommands,
+    },
+    /// Manage GitHub Packages (GHCR, npm, ...)
+    #[clap(visible_alias = "pkg")]
+    Package {
+        #[command(subcommand)]
+        command: PackageCommands,
+    },
+    /// Manage self-hosted runners
+    Runner {
+        #[command(subcommand)]
+        command: RunnerCommands,
+    },
+    /// Report GitHub Actions usage
+    Actions {
+        #[command(subcommand)]
+        command: ActionsCommands,
+    },
+    /// Validate GitHub Actions workflows
+    Workflow {
+        #[command(subcommand)]
+        command: WorkflowCommands,
+    },
+    /// Manage releases
+    Release {
+        #[command(subcommand)]
+        command: ReleaseCommands,
+    },
+    /// Call the GitHub API directly, e.g. `gho api /repos/{owner}/{repo}/traffic/views`
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+    Api {
+        #[command(subcommand)]
+        command: Option<ApiCommands>,
+        /// Endpoint path or URL; {owner}, {repo} and {branch} are filled in from git
+        #[clap(required = true)]
+        endpoint: Option<String>,
+        /// HTTP method [default: GET, or POST when fields are given]
+        #[clap(short = 'X', long)]
+        method: Option<String>,
+        /// String field key=value (query parameter for GET, JSON body otherwise)
+        #[clap(short = 'f', long = "raw-field", value_name = "KEY=VALUE")]
+        raw_fields: Vec<String>,
+        /// Typed field key=value: true/false/null/numbers are converted, @file reads a file
+        #[clap(short = 'F', long = "field", value_name = "KEY=VALUE")]
+        typed_fields: Vec<String>,
+        /// Request header "Name: value"
+        #[clap(short = 'H', long = "header", value_name = "HEADER")]
+        headers: Vec<String>,
+        /// Fetch all pages, merging array results
+        #[clap(long)]
+        paginate: bool,
+        /// Repository (owner/repo) for {owner}/{repo}, detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+    },
+    /// Compare what each account can do on a repository
+    Access {
+        #[command(subcommand)]
+        command: AccessCommands,
+    },
+    /// Check the tokens of every account
+    Auth {
+        #[command(subcommand)]
+        command: AuthCommands,
+    },
+    /// Show who the active account's token authenticates as
+    Whoami {
+        /// Check every account concurrently and flag tokens of another user
+        #[clap(long)]
+        all: bool,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// Summarize commits, pull requests, reviews and issues per account
+    Contrib {
+        /// Account ID [default: the active account]
+        #[clap(long, conflicts_with = "all_accounts")]
+        account: Option<String>,
+        /// Summarize every configured account
+        #[clap(long)]
+        all_accounts: bool,
+        /// Start date (YYYY-MM-DD, at most a year ago) [default: 7 days ago]
+        #[clap(long)]
+        since: Option<String>,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// Check out the repositories of an organization tagged with a topic
+    Workspace {
+        #[command(subcommand)]
+        command: WorkspaceCommands,
+    },
+    /// Share files as gists
+    Gist {
+        #[command(subcommand)]
+        command: GistCommands,
+    },
+    /// Triage issues
+    Issue {
+        #[command(subcommand)]
+        command: IssueCommands,
+    },
+    /// Read and triage GitHub notifications
+    Notify {
+        #[command(subcommand)]
+        command: NotifyCommands,
+    },
+    /// Refresh the PR dashboard, notifications and rate limits in the background
+    Daemon {
+        #[command(subcommand)]
+        command: DaemonCommands,
+    },
+    /// Manage user settings (config.toml)
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+    /// Generate a script that recreates this setup on another machine
+    Bootstrap {
+        #[command(subcommand)]
+        command: BootstrapCommands,
+    },
+    /// Show or search previously run gho commands
+    History {
+        #[command(subcommand)]
+        command: HistoryCommands,
+    },
+    /// Re-run a command from the history (the latest if no number is given)
+    Redo {
+        /// Entry number shown by `gho history list`
+        n: Option<usize>,
+        /// Run it as the active account even if another account ran it
+        #[clap(long)]
+        as_active: bool,
+    },
+    /// Show recently used repositories; `-` as a repository argument means the last one
+    Recent {
+        /// Pick a repository interactively, print it and make it the last one
+        #[clap(short, long, conflicts_with = "json")]
+        select: bool,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// Check proxy settings, host reachability and each SSH account's key
+    Doctor {
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// Print the completion script for a shell
+    Completions {
+        /// Shell to complete gho in
+        #[clap(value_enum)]
+        shell: Shell,
+    },
+    /// Create, clone, change, merge and delete a throwaway repository in a sandbox organization
+    #[cfg(feature = "selftest")]
+    #[clap(hide = true)]
+    Selftest {
+        /// Disposable organization the active account may create and delete repositories in
+        #[clap(long)]
+        org: String,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -51,6 +351,9 @@ enum AccountCommands {
         /// GitHub personal access token
         #[clap(short, long)]
         token: String,
+        /// GitHub Enterprise Server hostname (github.com if omitted)
+        #[clap(long)]
+        host: Option<String>,
         /// Default organization
         #[clap(short = 'o', long)]
         default_org: Option<String>,
@@ -60,6 +363,12 @@ enum AccountCommands {
         /// Default clone directory
         #[clap(short = 'd', long)]
         clone_dir: Option<String>,
+        /// Directory layout for clones under the clone directory
+        #[clap(long, value_enum, default_value = "flat")]
+        clone_layout: CloneLayoutArg,
+        /// Token expiry (YYYY-MM-DD); detected from GitHub if omitted
+        #[clap(long)]
+        expires: Option<String>,
     },
     /// List all accounts
     #[clap(visible_alias = "ls")]
@@ -69,6 +378,12 @@ enum AccountCommands {
     Use {
         /// Account ID to switch to (interactive if omitted)
         id: Option<String>,
+        /// Swap the other accounts' SSH keys in ssh-agent for this one's (setting `ssh_agent`)
+        #[clap(long)]
+        ssh_agent: bool,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
     },
     /// Show active account details
     Show,
@@ -78,6 +393,167 @@ enum AccountCommands {
         /// Account ID to remove
         id: String,
     },
+    /// Set the directory layout used when cloning with an account
+    CloneLayout {
+        /// Account ID
+        id: String,
+        /// Layout under the clone directory
+        #[clap(value_enum)]
+        layout: CloneLayoutArg,
+    },
+    /// Set the git clone options this account clones with (none clears them)
+    CloneDefaults {
+        /// Account ID
+        id: String,
+        #[clap(flatten)]
+        options: CloneArgs,
+    },
+    /// Set the command that opens this account's pages, e.g. "firefox -P {account}"
+    Browser {
+        /// Account ID
+        id: String,
+        /// Command template; `{url}` and `{account}` are filled in (URL appended if absent)
+        #[clap(required_unless_present = "clear")]
+        command: Option<String>,
+        /// Use the system browser again
+        #[clap(long, conflicts_with = "command")]
+        clear: bool,
+    },
+    /// Write a gitconfig with this account's identity, included for repositories in its directory
+    SetupGitconfig {
+        /// Account ID
+        id: String,
+        /// Author name (defaults to the GitHub username)
+        #[clap(long)]
+        name: Option<String>,
+        /// Author email (required the first time)
+        #[clap(long)]
+        email: Option<String>,
+        /// GPG key ID or SSH public key file that signs commits and tags
+        #[clap(long)]
+        signing_key: Option<String>,
+        /// Private SSH key for this account's remotes
+        #[clap(long)]
+        ssh_key: Option<String>,
+        /// Directory whose repositories use the identity (defaults to the clone directory)
+        #[clap(long)]
+        gitdir: Option<String>,
+    },
+    /// Move every token and sudo PIN to another keychain service
+    MigrateKeychain {
+        /// New keychain service name
+        #[clap(long, value_name = "NAME")]
+        to_service: String,
+        /// Also move the webhook secret stored for `hook create --secret-keychain <NAME>`
+        /// (repeatable, comma-separated); others stay in the old service
+        #[clap(long = "webhook-secret", value_name = "NAME", value_delimiter = ',')]
+        webhook_secrets: Vec<String>,
+    },
+    /// Require confirmation before destructive API calls with this account
+    Protect {
+        /// Account ID to protect
+        id: String,
+        /// Prompt for a PIN to enter instead of a yes/no confirmation
+        #[clap(long)]
+        pin: bool,
+    },
+    /// Remove protection from an account
+    Unprotect {
+        /// Account ID to unprotect
+        id: String,
+    },
+    /// Export accounts to a backup file, or move a token into a password manager vault
+    #[clap(group(ArgGroup::new("destination").required(true).args(["to", "file"])))]
+    Export {
+        /// Account ID to export (all accounts if omitted with --file)
+        id: Option<String>,
+        /// Move the account's token into this vault
+        #[clap(long, value_enum, requires = "id")]
+        to: Option<VaultArg>,
+        /// Vault name (1Password only, defaults to "Private")
+        #[clap(long, requires = "to")]
+        vault: Option<String>,
+        /// Write a backup file
+        #[clap(short, long)]
+        file: Option<PathBuf>,
+        /// Include tokens in the backup file, created readable only by you; it must not exist yet
+        #[clap(long, requires = "file")]
+        include_tokens: bool,
+        /// Encrypt included tokens with a passphrase
+        #[clap(long, requires = "include_tokens")]
+        encrypt: bool,
+    },
+    /// Import accounts from a backup file
+    Import {
+        /// Backup file written by `account export --file`
+        #[clap(short, long)]
+        file: PathBuf,
+        /// How to handle accounts whose ID already exists
+        #[clap(long, value_enum, default_value = "prompt")]
+        on_conflict: ConflictArg,
+    },
+    /// Manage named account groups
+    Group {
+        #[command(subcommand)]
+        command: GroupCommands,
+    },
+    /// Manage an account's token
+    Token {
+        #[command(subcommand)]
+        command: TokenCommands,
+    },
+}
+
+#[derive(Subcommand)]
+enum TokenCommands {
+    /// Store a new token, read from stdin or prompted for
+    Set {
+        /// Account ID
+        id: String,
+    },
+    /// Print an account's token (masked unless --plain)
+    Get {
+        /// Account ID
+        id: String,
+        /// Print the full token; asks for confirmation when printing to a terminal
+        #[clap(long)]
+        plain: bool,
+    },
+    /// Delete the stored token, keeping the account
+    #[clap(visible_alias = "rm")]
+    Remove {
+        /// Account ID
+        id: String,
+    },
+    /// Replace the token after regenerating an expiring one and record its new expiry
+    Refresh {
+        /// Account ID
+        id: String,
+        /// Token expiry (YYYY-MM-DD); detected from GitHub if omitted
+        #[clap(long)]
+        expires: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum GroupCommands {
+    /// Define or replace a group
+    Set {
+        /// Group name
+        name: String,
+        /// Account IDs in the group
+        #[clap(required = true)]
+        ids: Vec<String>,
+    },
+    /// List groups
+    #[clap(visible_alias = "ls")]
+    List,
+    /// Remove a group
+    #[clap(visible_alias = "rm")]
+    Remove {
+        /// Group name
+        name: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -88,17 +564,64 @@ enum RepoCommands {
         /// Organization to list repos from
         #[clap(short, long)]
         org: Option<String>,
-        /// Maximum number of repositories
-        #[clap(short, long, default_value = "30")]
-        limit: usize,
+        /// Maximum number of repositories [default: config `limit`]
+        #[clap(short, long)]
+        limit: Option<usize>,
+        /// Order of the listed repositories
+        #[clap(short, long, value_enum, default_value = "pushed")]
+        sort: RepoSortArg,
+        /// Only public or only private repositories
+        #[clap(long, value_enum)]
+        visibility: Option<VisibilityArg>,
+        /// Only repositories that are not forks
+        #[clap(long, conflicts_with = "forks_only")]
+        source: bool,
+        /// Only forks
+        #[clap(long)]
+        forks_only: bool,
+        /// Leave out archived repositories
+        #[clap(long)]
+        no_archived: bool,
+        /// Only repositories whose primary language is this, e.g. rust
+        #[clap(long)]
+        language: Option<String>,
+        /// Only repositories with this topic
+        #[clap(long)]
+        topic: Option<String>,
         /// Output as JSON
         #[clap(long)]
         json: bool,
+        /// Print each repository with a template, e.g. '{{.name}}\t{{.url}}' (fields of --json)
+        #[clap(long, value_name = "TEMPLATE", conflicts_with = "json")]
+        format: Option<String>,
+    },
+    /// Search repositories
+    Search {
+        /// Search query (GitHub search syntax)
+        query: String,
+        /// Restrict to an organization
+        #[clap(short, long)]
+        org: Option<String>,
+        /// Restrict to a language
+        #[clap(long)]
+        language: Option<String>,
+        /// Sort order
+        #[clap(short, long, value_enum, default_value = "best-match")]
+        sort: SearchSortArg,
+        /// Maximum number of results [default: config `limit`]
+        #[clap(short, long)]
+        limit: Option<usize>,
+        /// Output as JSON
+        #[clap(long, conflicts_with = "interactive")]
+        json: bool,
+        /// Pick a result interactively and clone it
+        #[clap(short, long)]
+        interactive: bool,
     },
     /// Clone a repository
     #[clap(visible_alias = "cl")]
     Clone {
-        /// Repository to clone (owner/repo)
+        /// Repository to clone (owner/repo, an SSH/HTTPS URL, or a name owned by the account)
         repo: Option<String>,
         /// Organization to bulk clone from
         #[clap(long)]
@@ -106,201 +629,4707 @@ enum RepoCommands {
         /// Maximum repos to clone (for bulk)
         #[clap(short, long, default_value = "10")]
         limit: usize,
+        /// Parallel clones (for bulk) [default: config `clone_jobs`]
+        #[clap(short, long)]
+        jobs: Option<usize>,
+        /// Where to write the clone manifest (for bulk) [default: gho-clones.json in the clone
+        /// directory]
+        #[clap(long, value_name = "PATH")]
+        manifest: Option<PathBuf>,
+        /// Clone the repositories of a clone manifest at their recorded commits
+        #[clap(
+            long,
+            value_name = "PATH",
+            conflicts_with_all = [
+                "repo", "org", "manifest", "depth", "filter", "recurse_submodules", "branch", "bare"
+            ]
+        )]
+        from_manifest: Option<PathBuf>,
+        #[clap(flatten)]
+        options: CloneArgs,
+        /// Hide git's output and, for bulk clones, the per-repository lines
+        #[clap(short, long, conflicts_with = "from_manifest")]
+        quiet: bool,
+        /// Continue the last `--org` clone: repositories not reached yet and failed ones
+        #[clap(
+            long,
+            conflicts_with_all = [
+                "repo", "org", "manifest", "from_manifest", "depth", "filter",
+                "recurse_submodules", "branch", "bare"
+            ]
+        )]
+        resume: bool,
+        /// Output the cloned repositories (or the dry-run plan) as JSON
+        #[clap(long, conflicts_with = "from_manifest")]
+        json: bool,
+    },
+    /// Open a repository page in the browser
+    #[clap(visible_alias = "o")]
+    Open {
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        repo: Option<String>,
+        /// File to open, relative to the repository root
+        #[clap(short, long)]
+        path: Option<String>,
+        /// Branch to open (default branch if omitted)
+        #[clap(short, long)]
+        branch: Option<String>,
+        /// Print the URL instead of opening a browser (e.g. over SSH)
+        #[clap(long)]
+        print: bool,
+    },
+    /// Delete a repository permanently
+    Delete {
+        /// Repository (owner/repo, an SSH/HTTPS URL, or a name owned by the account)
+        repo: String,
+        /// Skip typing the repository name to confirm
+        #[clap(short, long)]
+        yes: bool,
+    },
+    /// Archive a repository, making it read-only
+    Archive {
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        repo: Option<String>,
+        /// Skip the confirmation prompt
+        #[clap(short, long)]
+        yes: bool,
+    },
+    /// Rename a repository and update the origin of its clone in the clone directory
+    Rename {
+        /// Repository (owner/repo, or a name of the active account)
+        repo: String,
+        /// New repository name
+        new_name: String,
+        /// Skip the confirmation prompt
+        #[clap(short, long)]
+        yes: bool,
+    },
+    /// Transfer a repository to another user or organization and update the origin of its clone
+    Transfer {
+        /// Repository (owner/repo, or a name of the active account)
+        repo: String,
+        /// New owner
+        #[clap(long)]
+        to: String,
+        /// Give a team of the new organization access (repeatable, comma-separated)
+        #[clap(long = "team", value_name = "SLUG", value_delimiter = ',')]
+        teams: Vec<String>,
+        /// Skip the confirmation prompt
+        #[clap(short, long)]
+        yes: bool,
+    },
+    /// Unarchive a repository
+    Unarchive {
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        repo: Option<String>,
+    },
+    /// Star a repository
+    Star {
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        repo: Option<String>,
+    },
+    /// Remove your star from a repository
+    Unstar {
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        repo: Option<String>,
+    },
+    /// Get notified of all activity in a repository
+    Watch {
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        repo: Option<String>,
+    },
+    /// Stop watching a repository (back to participating and @mentions)
+    Unwatch {
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        repo: Option<String>,
+    },
+    /// List your starred repositories, most recently starred first
+    Starred {
+        /// Maximum number of repositories [default: config `limit`]
+        #[clap(short, long)]
+        limit: Option<usize>,
+        /// Order of the listed repositories [default: most recently starred]
+        #[clap(short, long, value_enum)]
+        sort: Option<RepoSortArg>,
+        /// Only public or only private repositories
+        #[clap(long, value_enum)]
+        visibility: Option<VisibilityArg>,
+        /// Only repositories that are not forks
+        #[clap(long, conflicts_with = "forks_only")]
+        source: bool,
+        /// Only forks
+        #[clap(long)]
+        forks_only: bool,
+        /// Leave out archived repositories
+        #[clap(long)]
+        no_archived: bool,
+        /// Only repositories whose primary language is this, e.g. rust
+        #[clap(long)]
+        language: Option<String>,
+        /// Only repositories with this topic
+        #[clap(long)]
+        topic: Option<String>,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+        /// Print each repository with a template, e.g. '{{.name}}\t{{.url}}' (fields of --json)
+        #[clap(long, value_name = "TEMPLATE", conflicts_with = "json")]
+        format: Option<String>,
+    },
+    /// Set, add or remove repository topics
+    Topics {
+        #[command(subcommand)]
+        command: TopicsCommands,
+    },
+    /// Find git clones under a directory (the active account's clone directory by default)
+    Scan {
+        /// Directory to scan
+        path: Option<PathBuf>,
+        /// Directory levels to descend
+        #[clap(long, default_value_t = scan::DEFAULT_MAX_DEPTH)]
+        depth: usize,
+        /// Walk again instead of reusing a scan from the last day
+        #[clap(long)]
+        refresh: bool,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// Show branch, local changes and unpushed commits of the active account's clones
+    Status {
+        /// Directory to scan [default: the account's clone directory]
+        path: Option<PathBuf>,
+        /// Directory levels to descend
+        #[clap(long, default_value_t = scan::DEFAULT_MAX_DEPTH)]
+        depth: usize,
+        /// Walk again instead of reusing a scan from the last day
+        #[clap(long)]
+        refresh: bool,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// Run a command in the clone of every repository of an organization
+    Foreach {
+        /// Organization [default: the account's default org, else its own repositories]
+        #[clap(long)]
+        org: Option<String>,
+        /// Maximum number of repositories [default: config `limit`]
+        #[clap(short, long)]
+        limit: Option<usize>,
+        /// Parallel runs; output is then printed per repository [default: config `clone_jobs`]
+        #[clap(short, long)]
+        jobs: Option<usize>,
+        /// Do not clone missing repositories; run the command in the current directory
+        #[clap(long)]
+        no_clone: bool,
+        /// Output the per-repository summary as JSON
+        #[clap(long)]
+        json: bool,
+        /// Command to run after `--`; a single argument is run with `sh -c`
+        #[clap(last = true, required = true)]
+        command: Vec<String>,
+    },
+    /// Inspect and apply branch protection rules
+    Protection {
+        #[command(subcommand)]
+        command: ProtectionCommands,
+    },
+    /// Manage required status checks of protected branches
+    ChecksRequired {
+        #[command(subcommand)]
+        command: ChecksRequiredCommands,
     },
 }
 
 #[derive(Subcommand)]
-enum PrCommands {
-    /// List open pull requests
-    #[clap(visible_alias = "ls")]
-    List {
-        /// Repository (owner/repo), detected from git if omitted
-        repo: Option<String>,
-        /// Maximum number of PRs
-        #[clap(short, long, default_value = "30")]
-        limit: usize,
+enum TopicsCommands {
+    /// Replace all topics of a repository (none to clear them)
+    Set {
+        /// Repository (owner/repo, or - for the last used)
+        repo: String,
+        /// Topics, e.g. rust cli
+        topics: Vec<String>,
+    },
+    /// Add topics to a repository
+    Add {
+        /// Repository (owner/repo, or - for the last used)
+        repo: String,
+        /// Topics to add
+        #[clap(required = true)]
+        topics: Vec<String>,
+    },
+    /// Remove topics from a repository
+    Remove {
+        /// Repository (owner/repo, or - for the last used)
+        repo: String,
+        /// Topics to remove
+        #[clap(required = true)]
+        topics: Vec<String>,
     },
 }
 
-#[derive(Clone, ValueEnum)]
-enum AccountKindArg {
-    Personal,
-    Work,
+#[derive(Subcommand)]
+enum ProtectionCommands {
+    /// Show a branch's protection rules
+    Show {
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        repo: Option<String>,
+        /// Protected branch
+        #[clap(short, long, default_value = "main")]
+        branch: String,
+        /// Output as JSON, in the rules format `apply --from` reads
+        #[clap(long)]
+        json: bool,
+    },
+    /// Replace branch protection with rules from a JSON file where it differs
+    Apply {
+        /// Repositories (owner/repo), detected from git if omitted
+        #[clap(conflicts_with = "org")]
+        repos: Vec<String>,
+        /// Rules file, e.g. saved from `repo protection show --json`
+        #[clap(long, value_name = "PATH")]
+        from: PathBuf,
+        /// Protected branch
+        #[clap(short, long, default_value = "main")]
+        branch: String,
+        /// Apply to every non-archived repository of an organization
+        #[clap(long)]
+        org: Option<String>,
+        /// Maximum number of organization repositories [default: config `limit`]
+        #[clap(short, long, requires = "org")]
+        limit: Option<usize>,
+    },
 }
 
-impl From<AccountKindArg> for AccountKind {
-    fn from(arg: AccountKindArg) -> Self {
-        match arg {
-            AccountKindArg::Personal => AccountKind::Personal,
-            AccountKindArg::Work => AccountKind::Work,
-        }
-    }
+#[derive(Subcommand)]
+enum ChecksRequiredCommands {
+    /// Set the required status check contexts on one or more repositories
+    Set {
+        /// Repositories (owner/repo)
+        #[clap(required = true)]
+        repos: Vec<String>,
+        /// Protected branch
+        #[clap(short, long, default_value = "main")]
+        branch: String,
+        /// Comma-separated check contexts
+        #[clap(short, long, value_delimiter = ',', required = true)]
+        contexts: Vec<String>,
+    },
+    /// Apply required status checks from a TOML file of [[repo]] entries
+    Sync {
+        /// File with `repo`, `branch` and `contexts` per [[repo]] entry
+        #[clap(long, value_name = "PATH")]
+        from_file: PathBuf,
+    },
 }
 
-#[derive(Clone, ValueEnum)]
-enum ProtocolArg {
-    Ssh,
-    Https,
-}
+#[derive(Subcommand)]
+enum PrCommands {
+    /// List open pull requests
+    #[clap(visible_alias = "ls")]
+    #[clap(group = ArgGroup::new("across").args(["org", "owned"]))]
+    List {
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(conflicts_with = "across")]
+        repo: Option<String>,
+        /// Maximum number of PRs [default: config `limit`]
+        #[clap(short, long)]
+        limit: Option<usize>,
+        /// List open PRs across every repository of an organization, grouped by repository
+        #[clap(long)]
+        org: Option<String>,
+        /// List open PRs across every repository you own, grouped by repository
+        #[clap(long)]
+        owned: bool,
+        /// Only PRs you opened (with --org or --owned)
+        #[clap(long, requires = "across")]
+        mine: bool,
+        /// Only PRs requesting your review; with --mine, PRs matching either
+        #[clap(long, requires = "across")]
+        review_requested: bool,
+        /// Output as JSON (with --org or --owned; a single repository is listed as JSON lines)
+        #[clap(long, requires = "across")]
+        json: bool,
+        /// Print each PR with a template, e.g. '{{.number}} {{.title}}' (fields of the JSON)
+        #[clap(long, value_name = "TEMPLATE", conflicts_with = "json")]
+        format: Option<String>,
+    },
+    /// Show pull requests you created, are asked to review, or are assigned to
+    #[clap(visible_alias = "st", alias = "dashboard")]
+    Status {
+        /// Maximum PRs per section [default: config `limit`]
+        #[clap(short, long, conflicts_with = "cached")]
+        limit: Option<usize>,
+        /// Read the last snapshot written by `gho daemon` instead of querying GitHub
+        #[clap(long)]
+        cached: bool,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// Rebase a conflicted pull request onto its base in a temporary worktree and push
+    FixConflicts {
+        /// Pull request number
+        number: u64,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// Finish the rebase after resolving conflicts, then push
+        #[clap(long = "continue", conflicts_with = "abort")]
+        resume: bool,
+        /// Abandon the rebase and remove the worktree
+        #[clap(long)]
+        abort: bool,
+    },
+    /// Open a pull request from a branch
+    Create {
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// Pull request title
+        #[clap(short, long)]
+        title: String,
+        /// Pull request body (in a terminal, written in the `editor` setting when neither this nor
+        /// --body-file is given)
+        #[clap(short, long, conflicts_with = "body_file")]
+        body: Option<String>,
+        /// Read the body from a file (- for stdin)
+        #[clap(short = 'F', long)]
+        body_file: Option<PathBuf>,
+        /// Branch to merge into (defaults to the repository's default branch)
+        #[clap(short = 'B', long)]
+        base: Option<String>,
+        /// Branch to merge, as owner:branch for a fork's (defaults to the upstream of the
+        /// checked-out branch, which must have been pushed)
+        #[clap(short = 'H', long)]
+        head: Option<String>,
+        /// Open the pull request as a draft
+        #[clap(long)]
+        draft: bool,
+        #[clap(flatten)]
+        metadata: PrMetadataArgs,
+    },
+    /// Change a pull request's title, body, base branch, reviewers, labels, assignees or milestone
+    Edit {
+        /// Pull request number
+        number: u64,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// New title
+        #[clap(short, long)]
+        title: Option<String>,
+        /// New body
+        #[clap(short, long, conflicts_with = "body_file")]
+        body: Option<String>,
+        /// Read the new body from a file (- for stdin)
+        #[clap(short = 'F', long)]
+        body_file: Option<PathBuf>,
+        /// New branch to merge into
+        #[clap(short = 'B', long)]
+        base: Option<String>,
+        #[clap(flatten)]
+        metadata: PrMetadataArgs,
+        /// Withdraw a review request (repeatable, comma-separated)
+        #[clap(long = "remove-reviewer", value_name = "LOGIN", value_delimiter = ',')]
+        remove_reviewers: Vec<String>,
+        /// Remove a label (repeatable, comma-separated)
+        #[clap(long = "remove-label", value_name = "NAME", value_delimiter = ',')]
+        remove_labels: Vec<String>,
+        /// Unassign a user (repeatable, comma-separated)
+        #[clap(long = "remove-assignee", value_name = "LOGIN", value_delimiter = ',')]
+        remove_assignees: Vec<String>,
+        /// Clear the milestone
+        #[clap(long, conflicts_with = "milestone")]
+        remove_milestone: bool,
+    },
+    /// Apply the same patch or script to many repositories and open a pull request in each
+    #[clap(group(ArgGroup::new("change").required(true).args(["apply", "run"])))]
+    Fanout {
+        /// File listing one owner/repo per line (`#` starts a comment)
+        #[clap(long)]
+        repos: PathBuf,
+        /// Branch to create in each repository
+        #[clap(short, long)]
+        branch: String,
+        /// Patch file to apply with `git apply`
+        #[clap(long)]
+        apply: Option<PathBuf>,
+        /// Shell command to run in each repository root
+        #[clap(long)]
+        run: Option<String>,
+        /// Pull request title, also used as the commit message
+        #[clap(short, long)]
+        title: String,
+        /// Pull request body
+        #[clap(long)]
+        body: Option<String>,
+        /// Base branch (defaults to each repository's default branch)
+        #[clap(long)]
+        base: Option<String>,
+        /// Open the pull requests as drafts
+        #[clap(long)]
+        draft: bool,
+    },
+    /// Show the diff of a pull request (paged through $PAGER)
+    Diff {
+        /// Pull request number
+        number: u64,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// Only list the changed files
+        #[clap(long)]
+        name_only: bool,
+        /// Only include files matching this glob (repeatable)
+        #[clap(long = "files", value_name = "GLOB")]
+        files: Vec<String>,
+        /// Open the diff with the `review_command` setting
+        #[clap(long, conflicts_with_all = ["name_only", "no_tool"])]
+        review: bool,
+        /// Page the diff even when the `diff_tool` setting is set
+        #[clap(long)]
+        no_tool: bool,
+    },
+    /// Show a condensed history of commits, reviews, pushes and label changes
+    Timeline {
+        /// Pull request number
+        number: u64,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// Check that a pull request is open, conflict-free and green; exits non-zero if not
+    ReadyToMerge {
+        /// Pull request number
+        number: u64,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// Also require every commit to have a verified signature
+        #[clap(long)]
+        require_signed: bool,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// Show details of a pull request
+    #[clap(visible_alias = "v")]
+    View {
+        /// Pull request number
+        number: u64,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// Open the pull request in the browser
+        #[clap(short, long, conflicts_with = "json")]
+        web: bool,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// Merge a pull request, or add it to the merge queue
+    Merge {
+        /// Pull request number
+        number: u64,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// Merge method
+        #[clap(short, long, value_enum, default_value = "merge", conflicts_with = "queue")]
+        method: MergeMethodArg,
+        /// Add the pull request to the merge queue instead of merging directly
+        #[clap(long)]
+        queue: bool,
+    },
+    /// Mark a draft pull request ready for review
+    Ready {
+        /// Pull request number
+        number: u64,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+    },
+    /// Convert a pull request back to a draft
+    Draft {
+        /// Pull request number
+        number: u64,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+    },
+    /// Close a pull request without merging
+    Close {
+        /// Pull request number
+        number: u64,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// Comment explaining why, posted before closing
+        #[clap(short, long, value_name = "TEXT")]
+        comment: Option<String>,
+        /// Also delete the head branch (not for pull requests from forks)
+        #[clap(short, long)]
+        delete_branch: bool,
+    },
+    /// Reopen a closed pull request
+    Reopen {
+        /// Pull request number
+        number: u64,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// Comment explaining why, posted after reopening
+        #[clap(short, long, value_name = "TEXT")]
+        comment: Option<String>,
+    },
+    /// Submit a review on a pull request
+    #[clap(group(
+        ArgGroup::new("event").required(true).args(["approve", "request_changes", "comment"])
+    ))]
+    Review {
+        /// Pull request number
+        number: u64,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// Approve the pull request
+        #[clap(long)]
+        approve: bool,
+        /// Request changes on the pull request
+        #[clap(long)]
+        request_changes: bool,
+        /// Leave a review comment without approval
+        #[clap(long)]
+        comment: bool,
+        /// Review message
+        #[clap(short, long)]
+        message: Option<String>,
+    },
+    /// List the check runs and statuses of a pull request's head commit; fails if any failed,
+    /// exits 7 if any is still pending
+    Checks {
+        /// Pull request number
+        number: u64,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// Only checks required by the base branch's protection
+        #[clap(long)]
+        required_only: bool,
+        /// Keep polling until no check is pending
+        #[clap(long)]
+        watch: bool,
+        /// Seconds between polls while checks change
+        #[clap(short, long, default_value = "10", requires = "watch")]
+        interval: u64,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// List check annotations in file:line: level: message format
+    Annotations {
+        /// Pull request number
+        number: u64,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// Re-run the workflow runs of a pull request that failed, were cancelled or timed out
+    RerunChecks {
+        /// Pull request number
+        number: u64,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// Only re-run the failed jobs (and the jobs that depend on them)
+        #[clap(long)]
+        failed_jobs_only: bool,
+    },
+    /// Comment on the conversation of a pull request
+    Comment {
+        /// Pull request number
+        number: u64,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// Comment text (opens the `editor` setting in a terminal when omitted, like --file)
+        #[clap(short, long, conflicts_with = "file")]
+        message: Option<String>,
+        /// Read the comment from a file (- for stdin)
+        #[clap(short = 'F', long)]
+        file: Option<PathBuf>,
+    },
+    /// List conversation and review comments in chronological order, grouped by thread
+    Comments {
+        /// Pull request number
+        number: u64,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// List reviews on a pull request
+    Reviews {
+        /// Pull request number
+        number: u64,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum CommitCommands {
+    /// Show a commit with its signature verification status
+    #[clap(visible_alias = "v")]
+    View {
+        /// Commit SHA, branch or tag
+        reference: String,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum HookCommands {
+    /// List webhooks with their URL, events and last delivery result
+    #[clap(visible_alias = "ls")]
+    List {
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        repo: Option<String>,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// Create a webhook
+    Create {
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        repo: Option<String>,
+        /// Payload URL
+        #[clap(long)]
+        url: String,
+        /// Payload format
+        #[clap(long, value_enum, default_value = "json")]
+        content_type: ContentTypeArg,
+        /// Events to deliver, comma-separated (`*` for all)
+        #[clap(short, long, value_delimiter = ',', default_value = "push")]
+        events: Vec<String>,
+        /// Prompt for a secret to sign deliveries with (or read it from stdin)
+        #[clap(long, conflicts_with = "secret_keychain")]
+        secret: bool,
+        /// Sign deliveries with the secret in the Keychain entry `webhook:<NAME>`
+        #[clap(long, value_name = "NAME")]
+        secret_keychain: Option<String>,
+        /// Skip TLS certificate verification of the payload URL
+        #[clap(long)]
+        insecure_ssl: bool,
+        /// Create the webhook disabled
+        #[clap(long)]
+        inactive: bool,
+    },
+    /// Delete a webhook
+    #[clap(visible_alias = "rm")]
+    Delete {
+        /// Webhook ID
+        id: u64,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+    },
+    /// Send a ping event to a webhook
+    Ping {
+        /// Webhook ID
+        id: u64,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+    },
+    /// Show recent deliveries of a webhook and how the receiver responded
+    Deliveries {
+        /// Webhook ID
+        id: u64,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// Number of deliveries (at most 100)
+        #[clap(short = 'n', long, default_value_t = 30)]
+        limit: usize,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum SecretCommands {
+    /// List secret names and when they were last updated
+    #[clap(visible_alias = "ls")]
+    List {
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long, conflicts_with = "org")]
+        repo: Option<String>,
+        /// List the secrets of an organization instead
+        #[clap(long)]
+        org: Option<String>,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// Create or update a secret; the value is prompted for or read from stdin
+    Set {
+        /// Secret name
+        name: String,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long, conflicts_with = "org")]
+        repo: Option<String>,
+        /// Set an organization secret instead
+        #[clap(long)]
+        org: Option<String>,
+        /// Repositories of the organization that can use the secret (default: private, or
+        /// unchanged for existing secrets)
+        #[clap(long, value_enum, requires = "org")]
+        visibility: Option<VisibilityScopeArg>,
+    },
+    /// Delete a secret
+    #[clap(visible_alias = "rm")]
+    Delete {
+        /// Secret name
+        name: String,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long, conflicts_with = "org")]
+        repo: Option<String>,
+        /// Delete an organization secret instead
+        #[clap(long)]
+        org: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum VariableCommands {
+    /// List variables and their values
+    #[clap(visible_alias = "ls")]
+    List {
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long, conflicts_with = "org")]
+        repo: Option<String>,
+        /// List the variables of an organization instead
+        #[clap(long)]
+        org: Option<String>,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// Create or update a variable
+    Set {
+        /// Variable name
+        name: String,
+        /// Value
+        value: String,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long, conflicts_with = "org")]
+        repo: Option<String>,
+        /// Set an organization variable instead
+        #[clap(long)]
+        org: Option<String>,
+        /// Repositories of the organization that can use the variable (default: private, or
+        /// unchanged for existing variables)
+        #[clap(long, value_enum, requires = "org")]
+        visibility: Option<VisibilityScopeArg>,
+    },
+    /// Delete a variable
+    #[clap(visible_alias = "rm")]
+    Delete {
+        /// Variable name
+        name: String,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long, conflicts_with = "org")]
+        repo: Option<String>,
+        /// Delete an organization variable instead
+        #[clap(long)]
+        org: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum LabelCommands {
+    /// List labels
+    #[clap(visible_alias = "ls")]
+    List {
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        repo: Option<String>,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// Create a label
+    Create {
+        /// Label name
+        name: String,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// Hex color, e.g. d73a4a
+        #[clap(short, long)]
+        color: Option<String>,
+        /// Description
+        #[clap(short, long)]
+        description: Option<String>,
+    },
+    /// Delete a label
+    #[clap(visible_alias = "rm")]
+    Delete {
+        /// Label name
+        name: String,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+    },
+    /// Make repositories' labels match a YAML or JSON label file
+    Sync {
+        /// Repositories (owner/repo); the current repository if omitted
+        repos: Vec<String>,
+        /// Label file: a list of {name, color, description}
+        #[clap(short, long)]
+        file: PathBuf,
+        /// Delete labels that are not in the file
+        #[clap(long)]
+        prune: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum QueueCommands {
+    /// Show the merge queue for a branch
+    Status {
+        /// Branch (defaults to the repository's default branch)
+        branch: Option<String>,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum PackageCommands {
+    /// List packages
+    #[clap(visible_alias = "ls")]
+    List {
+        /// Organization (defaults to the account's default org, then the user)
+        #[clap(short, long)]
+        org: Option<String>,
+        /// Package type
+        #[clap(short = 't', long = "type", value_enum, default_value = "container")]
+        package_type: PackageTypeArg,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// List the versions of a package
+    Versions {
+        /// Package name
+        name: String,
+        /// Organization (defaults to the account's default org, then the user)
+        #[clap(short, long)]
+        org: Option<String>,
+        /// Package type
+        #[clap(short = 't', long = "type", value_enum, default_value = "container")]
+        package_type: PackageTypeArg,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// Delete a package, or one version of it
+    #[clap(visible_alias = "rm")]
+    Delete {
+        /// Package name
+        name: String,
+        /// Delete only this version ID
+        #[clap(long)]
+        version: Option<u64>,
+        /// Organization (defaults to the account's default org, then the user)
+        #[clap(short, long)]
+        org: Option<String>,
+        /// Package type
+        #[clap(short = 't', long = "type", value_enum, default_value = "container")]
+        package_type: PackageTypeArg,
+    },
+    /// Delete untagged container image versions older than N days
+    Prune {
+        /// Container package name
+        name: String,
+        /// Minimum age in days since the version was last updated
+        #[clap(long, value_name = "DAYS")]
+        older_than: u32,
+        /// Organization (defaults to the account's default org, then the user)
+        #[clap(short, long)]
+        org: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum RunnerCommands {
+    /// List an organization's self-hosted runners
+    #[clap(visible_alias = "ls")]
+    List {
+        /// Organization
+        #[clap(long)]
+        org: String,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// Remove a self-hosted runner
+    #[clap(visible_alias = "rm")]
+    Remove {
+        /// Runner name or ID
+        runner: String,
+        /// Organization
+        #[clap(long)]
+        org: String,
+    },
+    /// Create a registration token for enrolling a new runner
+    RegistrationToken {
+        /// Organization
+        #[clap(long)]
+        org: String,
+        /// Output as JSON (includes the expiry)
+        #[clap(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ActionsCommands {
+    /// Summarize Actions minutes and storage per repository from billing data
+    Usage {
+        /// Organization
+        #[clap(long)]
+        org: String,
+        /// Only include usage on or after this date (YYYY-MM-DD)
+        #[clap(long)]
+        since: Option<String>,
+        /// Output as CSV
+        #[clap(long, conflicts_with = "json")]
+        csv: bool,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum WorkflowCommands {
+    /// Lint workflow files and check referenced secrets and variables exist
+    Lint {
+        /// Workflow file or directory (defaults to .github/workflows)
+        path: Option<PathBuf>,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// Skip checking secrets and variables against the repository
+        #[clap(long)]
+        no_remote: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ReleaseCommands {
+    /// List releases
+    #[clap(visible_alias = "ls")]
+    List {
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        repo: Option<String>,
+        /// Maximum number of releases [default: config `limit`]
+        #[clap(short, long)]
+        limit: Option<usize>,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// Create a release (notes are generated from merged PRs unless given)
+    Create {
+        /// Tag to release; created from the default branch if it does not exist
+        tag: String,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// Release title (defaults to the tag)
+        #[clap(short, long)]
+        title: Option<String>,
+        /// Release notes
+        #[clap(short, long, conflicts_with = "notes_from_tag")]
+        notes: Option<String>,
+        /// Use the annotated tag's message as release notes
+        #[clap(long)]
+        notes_from_tag: bool,
+        /// Save as a draft instead of publishing
+        #[clap(long)]
+        draft: bool,
+        /// Mark as a prerelease
+        #[clap(long)]
+        prerelease: bool,
+    },
+    /// Upload files as release assets
+    Upload {
+        /// Release tag
+        tag: String,
+        /// Files to upload
+        #[clap(required = true)]
+        files: Vec<PathBuf>,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// Replace existing assets with the same name
+        #[clap(long)]
+        clobber: bool,
+        /// Also upload a SHA256SUMS manifest of the files
+        #[clap(long)]
+        generate_checksums: bool,
+        /// Number of concurrent uploads
+        #[clap(short, long, default_value = "4")]
+        jobs: usize,
+        /// Retries per asset after a network or server error
+        #[clap(long, default_value = "3")]
+        retries: u32,
+    },
+    /// Download release assets
+    Download {
+        /// Release tag
+        tag: String,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// Only assets whose name matches this glob (e.g. '*.tar.gz')
+        #[clap(short, long)]
+        pattern: Option<String>,
+        /// Directory to download into
+        #[clap(short = 'D', long, default_value = ".")]
+        dir: PathBuf,
+        /// Overwrite existing files
+        #[clap(long)]
+        clobber: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ApiCommands {
+    /// Show remaining API quota and reset times
+    RateLimit {
+        /// Show every stored account instead of only the active one
+        #[clap(long)]
+        all_accounts: bool,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum DaemonCommands {
+    /// Start refreshing in a detached background process
+    Start {
+        /// Seconds between refreshes
+        #[clap(long, default_value_t = daemon::DEFAULT_INTERVAL_SECS)]
+        interval: u64,
+    },
+    /// Stop the background process
+    Stop,
+    /// Show whether the daemon runs and when snapshots were last refreshed
+    Status {
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// Run the refresh loop in the foreground (for launchd or systemd)
+    Run {
+        /// Seconds between refreshes
+        #[clap(long, default_value_t = daemon::DEFAULT_INTERVAL_SECS)]
+        interval: u64,
+    },
+}
+
+#[derive(Subcommand)]
+enum AccessCommands {
+    /// Show each configured account's permission (none/read/triage/write/maintain/admin)
+    Diff {
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        repo: Option<String>,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum AuthCommands {
+    /// Show where each account's token comes from, who it authenticates as, its scopes and rate
+    /// limit
+    Status {
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// Git credential helper answering HTTPS requests with the matching account's token
+    GitCredential {
+        /// Operation git asks for; only `get` does anything
+        operation: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum GistCommands {
+    /// Create a gist from files and print its URL
+    Create {
+        /// Files to include, named after their file names
+        #[clap(required = true)]
+        files: Vec<PathBuf>,
+        /// Make the gist public (gists are secret by default)
+        #[clap(long)]
+        public: bool,
+        /// Gist description
+        #[clap(short, long = "desc")]
+        description: Option<String>,
+    },
+    /// List your gists
+    #[clap(visible_alias = "ls")]
+    List {
+        /// Maximum number of gists [default: config `limit`]
+        #[clap(short, long)]
+        limit: Option<usize>,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// Clone a gist into the account's clone directory
+    Clone {
+        /// Gist ID
+        id: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum HistoryCommands {
+    /// List the most recent commands, numbered for `gho redo`
+    #[clap(visible_alias = "ls")]
+    List {
+        /// Maximum number of commands [default: config `limit`]
+        #[clap(short, long)]
+        limit: Option<usize>,
+    },
+    /// Find commands containing a term (case-insensitive)
+    Search {
+        /// Text to look for in the command line
+        term: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum BranchCommands {
+    /// List branches with their last commit and whether they are merged
+    #[clap(visible_alias = "ls")]
+    List {
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        repo: Option<String>,
+        /// Only branches the default branch already has (incl. squash-merged pull requests)
+        #[clap(long)]
+        merged: bool,
+        /// Only branches without commits for this long, e.g. 90d or 12w
+        #[clap(long, value_name = "AGE")]
+        stale: Option<String>,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// Delete a remote branch, or every merged one
+    #[clap(visible_alias = "rm")]
+    #[clap(group(ArgGroup::new("target").required(true).args(["name", "merged"])))]
+    Delete {
+        /// Branch name
+        name: Option<String>,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// Delete every branch the default branch already has
+        #[clap(long)]
+        merged: bool,
+        /// With --merged, only branches without commits for this long, e.g. 90d
+        #[clap(long, value_name = "AGE", requires = "merged")]
+        stale: Option<String>,
+    },
+    /// Change the default branch
+    Default {
+        /// Branch name
+        name: String,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum OrgCommands {
+    /// List organizations the active account can see
+    #[clap(visible_alias = "ls")]
+    List {
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// List members of an organization with their role
+    Members {
+        /// Organization login
+        org: String,
+        /// Only members with this role
+        #[clap(long, value_enum)]
+        role: Option<OrgRoleArg>,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// List teams of an organization
+    Teams {
+        /// Organization login
+        org: String,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// Inspect a team
+    Team {
+        #[command(subcommand)]
+        command: TeamCommands,
+    },
+}
+
+#[derive(Subcommand)]
+enum TeamCommands {
+    /// List repositories a team can access, with its permission on each
+    Repos {
+        /// Team as org/team-slug
+        team: String,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum IssueCommands {
+    /// Walk open issues without labels or assignees and label, assign, comment on or close each
+    Triage {
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        repo: Option<String>,
+        /// Maximum number of issues, oldest first [default: config `limit`]
+        #[clap(short, long)]
+        limit: Option<usize>,
+    },
+}
+
+#[derive(Subcommand)]
+enum NotifyCommands {
+    /// List notification threads, most recent first
+    #[clap(visible_alias = "ls")]
+    List {
+        /// Only threads not yet marked as read
+        #[clap(long)]
+        unread: bool,
+        /// Only threads you participate in or are mentioned in
+        #[clap(long)]
+        participating: bool,
+        /// Maximum number of threads [default: config `limit`]
+        #[clap(short, long)]
+        limit: Option<usize>,
+        /// Keep polling and print threads as they change
+        #[clap(short, long, conflicts_with = "json")]
+        watch: bool,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// Mark a thread, or every thread, as read
+    Read {
+        /// Thread ID from `gho notify list`
+        #[clap(required_unless_present = "all")]
+        id: Option<String>,
+        /// Mark every thread as read
+        #[clap(long, conflicts_with = "id")]
+        all: bool,
+    },
+    /// Open a thread's issue, pull request or commit in the browser
+    Open {
+        /// Thread ID from `gho notify list`
+        id: String,
+        /// Only print the URL
+        #[clap(long)]
+        print: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum BootstrapCommands {
+    /// Print a bash script that recreates settings and accounts, prompting for tokens
+    Emit,
+}
+
+#[derive(Subcommand)]
+enum WorkspaceCommands {
+    /// Create or update a workspace manifest from the repositories tagged with a topic
+    Discover {
+        /// Organization [default: the one saved in the manifest]
+        #[clap(long)]
+        org: Option<String>,
+        /// Repository topic, e.g. team-payments [default: the one saved in the manifest]
+        #[clap(long)]
+        topic: Option<String>,
+        /// Maximum number of repositories
+        #[clap(short, long, default_value = "100")]
+        limit: usize,
+        /// Manifest file
+        #[clap(short, long, default_value = workspace::MANIFEST_FILE)]
+        file: PathBuf,
+    },
+    /// Clone the manifest's repositories that are not checked out yet
+    Sync {
+        /// Manifest file
+        #[clap(short, long, default_value = workspace::MANIFEST_FILE)]
+        file: PathBuf,
+        /// Where to write the clone manifest [default: gho-clones.json next to the manifest]
+        #[clap(long, value_name = "PATH")]
+        manifest: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Print a setting
+    Get {
+        /// Setting key (limit, format, clone_jobs, color, editor, diff_tool, review_command,
+        /// token_expiry_warning_days, api_base, keychain_service, token_source, ssh_agent)
+        key: String,
+    },
+    /// Update a setting
+    Set {
+        /// Setting key (limit, format, clone_jobs, color, editor, diff_tool, review_command,
+        /// token_expiry_warning_days, api_base, keychain_service, token_source, ssh_agent)
+        key: String,
+        /// New value
+        value: String,
+    },
+    /// List all settings
+    #[clap(visible_alias = "ls")]
+    List,
+}
+
+#[derive(Subcommand)]
+enum RunCommands {
+    /// List recent workflow runs
+    #[clap(visible_alias = "ls")]
+    List {
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        repo: Option<String>,
+        /// Only runs for this branch
+        #[clap(short, long)]
+        branch: Option<String>,
+        /// Maximum number of runs [default: config `limit`]
+        #[clap(short, long)]
+        limit: Option<usize>,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// Follow a workflow run until it completes; fails if the run does not succeed
+    Watch {
+        /// Workflow run ID
+        id: u64,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// Seconds between polls
+        #[clap(short, long, default_value = "5")]
+        interval: u64,
+    },
+    /// List annotations of a workflow run in file:line: level: message format
+    Annotations {
+        /// Workflow run ID
+        id: u64,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// Re-run a workflow run
+    Rerun {
+        /// Workflow run ID
+        id: u64,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// Only re-run the failed jobs (and the jobs that depend on them)
+        #[clap(long)]
+        failed_jobs_only: bool,
+    },
+    /// Print the logs of a workflow run's jobs, grouped by step
+    Logs {
+        /// Workflow run ID
+        id: u64,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// Only the job with this name
+        #[clap(short, long)]
+        job: Option<String>,
+        /// Only jobs and steps that did not pass
+        #[clap(long)]
+        failed: bool,
+        /// Keep polling and print each job's log as it completes, until the run does
+        #[clap(short, long)]
+        follow: bool,
+        /// Seconds between polls with --follow
+        #[clap(short, long, default_value = "5")]
+        interval: u64,
+    },
+    /// Download and extract the artifacts of a workflow run
+    Download {
+        /// Workflow run ID
+        id: u64,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// Only this artifact, extracted straight into the directory
+        #[clap(short, long)]
+        name: Option<String>,
+        /// Directory to extract into; each artifact gets a subdirectory unless --name is given
+        #[clap(short = 'D', long, default_value = ".")]
+        dir: PathBuf,
+    },
+    /// Trigger a workflow that runs on workflow_dispatch
+    Dispatch {
+        /// Workflow file name (e.g. deploy.yml) or ID
+        workflow: String,
+        /// Repository (owner/repo, or - for the last used), detected from git if omitted
+        #[clap(short = 'R', long)]
+        repo: Option<String>,
+        /// Branch or tag to run the workflow on [default: the default branch]
+        #[clap(long = "ref")]
+        git_ref: Option<String>,
+        /// Workflow input as key=value (repeatable)
+        #[clap(short = 'f', long = "field", value_name = "KEY=VALUE")]
+        inputs: Vec<String>,
+        /// Follow the started run until it completes; fails if it does not succeed
+        #[clap(long)]
+        watch: bool,
+        /// Seconds between polls
+        #[clap(short, long, default_value = "5")]
+        interval: u64,
+    },
+}
+
+#[derive(Clone, ValueEnum)]
+enum ProgressArg {
+    Human,
+    Json,
+}
+
+impl From<ProgressArg> for Progress {
+    fn from(arg: ProgressArg) -> Self {
+        match arg {
+            ProgressArg::Human => Progress::Human,
+            ProgressArg::Json => Progress::Json,
+        }
+    }
+}
+
+#[derive(Clone, ValueEnum)]
+enum ColorArg {
+    Auto,
+    Always,
+    Never,
+}
+
+impl From<ColorArg> for ColorChoice {
+    fn from(arg: ColorArg) -> Self {
+        match arg {
+            ColorArg::Auto => ColorChoice::Auto,
+            ColorArg::Always => ColorChoice::Always,
+            ColorArg::Never => ColorChoice::Never,
+        }
+    }
+}
+
+#[derive(Clone, ValueEnum)]
+enum MergeMethodArg {
+    Merge,
+    Squash,
+    Rebase,
+}
+
+impl From<MergeMethodArg> for MergeMethod {
+    fn from(arg: MergeMethodArg) -> Self {
+        match arg {
+            MergeMethodArg::Merge => MergeMethod::Merge,
+            MergeMethodArg::Squash => MergeMethod::Squash,
+            MergeMethodArg::Rebase => MergeMethod::Rebase,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum OrgRoleArg {
+    Admin,
+    Member,
+}
+
+impl From<OrgRoleArg> for OrgRole {
+    fn from(arg: OrgRoleArg) -> Self {
+        match arg {
+            OrgRoleArg::Admin => OrgRole::Admin,
+            OrgRoleArg::Member => OrgRole::Member,
+        }
+    }
+}
+
+#[derive(Clone, ValueEnum)]
+enum AccountKindArg {
+    Personal,
+    Work,
+}
+
+impl From<AccountKindArg> for AccountKind {
+    fn from(arg: AccountKindArg) -> Self {
+        match arg {
+            AccountKindArg::Personal => AccountKind::Personal,
+            AccountKindArg::Work => AccountKind::Work,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ConflictArg {
+    Prompt,
+    Skip,
+    Overwrite,
+}
+
+#[derive(Clone, ValueEnum)]
+enum VaultArg {
+    #[value(name = "1password")]
+    OnePassword,
+    Bitwarden,
+}
+
+impl From<VaultArg> for VaultKind {
+    fn from(arg: VaultArg) -> Self {
+        match arg {
+            VaultArg::OnePassword => VaultKind::OnePassword,
+            VaultArg::Bitwarden => VaultKind::Bitwarden,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum VisibilityArg {
+    Public,
+    Private,
+}
+
+impl From<VisibilityArg> for RepoVisibility {
+    fn from(arg: VisibilityArg) -> Self {
+        match arg {
+            VisibilityArg::Public => RepoVisibility::Public,
+            VisibilityArg::Private => RepoVisibility::Private,
+        }
+    }
+}
+
+#[derive(Clone, ValueEnum)]
+enum RepoSortArg {
+    Pushed,
+    Stars,
+    Name,
+}
+
+impl From<RepoSortArg> for RepoSort {
+    fn from(arg: RepoSortArg) -> Self {
+        match arg {
+            RepoSortArg::Pushed => RepoSort::Pushed,
+            RepoSortArg::Stars => RepoSort::Stars,
+            RepoSortArg::Name => RepoSort::Name,
+        }
+    }
+}
+
+#[derive(Clone, ValueEnum)]
+enum SearchSortArg {
+    BestMatch,
+    Stars,
+    Updated,
+}
+
+impl From<SearchSortArg> for SearchSort {
+    fn from(arg: SearchSortArg) -> Self {
+        match arg {
+            SearchSortArg::BestMatch => SearchSort::BestMatch,
+            SearchSortArg::Stars => SearchSort::Stars,
+            SearchSortArg::Updated => SearchSort::Updated,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum PackageTypeArg {
+    Container,
+    Npm,
+    Maven,
+    Rubygems,
+    Nuget,
+    Docker,
+}
+
+impl From<PackageTypeArg> for PackageType {
+    fn from(arg: PackageTypeArg) -> Self {
+        match arg {
+            PackageTypeArg::Container => PackageType::Container,
+            PackageTypeArg::Npm => PackageType::Npm,
+            PackageTypeArg::Maven => PackageType::Maven,
+            PackageTypeArg::Rubygems => PackageType::Rubygems,
+            PackageTypeArg::Nuget => PackageType::Nuget,
+            PackageTypeArg::Docker => PackageType::Docker,
+        }
+    }
+}
+
+#[derive(Clone, ValueEnum)]
+enum CloneLayoutArg {
+    Flat,
+    #[value(name = "owner/repo")]
+    OwnerRepo,
+    #[value(name = "host/owner/repo")]
+    HostOwnerRepo,
+}
+
+/// Options passed through to `git clone`.
+#[derive(Args)]
+struct CloneArgs {
+    /// Only fetch this many commits of history
+    #[clap(long, value_name = "N")]
+    depth: Option<u32>,
+    /// Partial clone filter, e.g. blob:none
+    #[clap(long, value_name = "SPEC")]
+    filter: Option<String>,
+    /// Clone submodules too
+    #[clap(long, conflicts_with = "bare")]
+    recurse_submodules: bool,
+    /// Check out this branch instead of the default branch
+    #[clap(long, value_name = "NAME")]
+    branch: Option<String>,
+    /// Clone without a working tree
+    #[clap(long)]
+    bare: bool,
+}
+
+impl From<CloneArgs> for CloneOptions {
+    fn from(args: CloneArgs) -> Self {
+        CloneOptions {
+            depth: args.depth,
+            filter: args.filter,
+            recurse_submodules: args.recurse_submodules,
+            branch: args.branch,
+            bare: args.bare,
+        }
+    }
+}
+
+/// Reviewers, labels, assignees and milestone for `pr create` and `pr edit`.
+#[derive(Args)]
+struct PrMetadataArgs {
+    /// Request a review from a user, or a team as org/team (repeatable, comma-separated)
+    #[clap(long = "add-reviewer", visible_alias = "reviewer", value_name = "LOGIN")]
+    #[clap(value_delimiter = ',')]
+    reviewers: Vec<String>,
+    /// Add a label (repeatable, comma-separated)
+    #[clap(
+        long = "add-label",
+        visible_alias = "label",
+        value_name = "NAME",
+        value_delimiter = ','
+    )]
+    labels: Vec<String>,
+    /// Assign a user (repeatable, comma-separated)
+    #[clap(long = "add-assignee", visible_alias = "assignee", value_name = "LOGIN")]
+    #[clap(value_delimiter = ',')]
+    assignees: Vec<String>,
+    /// Set the milestone, by title or number
+    #[clap(long)]
+    milestone: Option<String>,
+}
+
+impl From<PrMetadataArgs> for PrMetadata {
+    fn from(args: PrMetadataArgs) -> Self {
+        PrMetadata {
+            add_reviewers: args.reviewers,
+            add_labels: args.labels,
+            add_assignees: args.assignees,
+            milestone: args.milestone,
+            ..PrMetadata::default()
+        }
+    }
+}
+
+impl From<CloneLayoutArg> for CloneLayout {
+    fn from(arg: CloneLayoutArg) -> Self {
+        match arg {
+            CloneLayoutArg::Flat => CloneLayout::Flat,
+            CloneLayoutArg::OwnerRepo => CloneLayout::OwnerRepo,
+            CloneLayoutArg::HostOwnerRepo => CloneLayout::HostOwnerRepo,
+        }
+    }
+}
+
+#[derive(Clone, ValueEnum)]
+enum ContentTypeArg {
+    Json,
+    Form,
+}
+
+impl From<ContentTypeArg> for WebhookContentType {
+    fn from(arg: ContentTypeArg) -> Self {
+        match arg {
+            ContentTypeArg::Json => WebhookContentType::Json,
+            ContentTypeArg::Form => WebhookContentType::Form,
+        }
+    }
+}
+
+#[derive(Clone, ValueEnum)]
+enum VisibilityScopeArg {
+    /// Every repository of the organization
+    All,
+    /// Private and internal repositories
+    Private,
+}
+
+impl From<VisibilityScopeArg> for SecretVisibility {
+    fn from(arg: VisibilityScopeArg) -> Self {
+        match arg {
+            VisibilityScopeArg::All => SecretVisibility::All,
+            VisibilityScopeArg::Private => SecretVisibility::Private,
+        }
+    }
+}
+
+#[derive(Clone, ValueEnum)]
+enum ProtocolArg {
+    Ssh,
+    Https,
+}
+
+impl From<ProtocolArg> for Protocol {
+    fn from(arg: ProtocolArg) -> Self {
+        match arg {
+            ProtocolArg::Ssh => Protocol::Ssh,
+            ProtocolArg::Https => Protocol::Https,
+        }
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let result: Result<(), AppError> = run(cli);
+
+    if let Err(e) = result {
+        eprintln!("Error: {e}");
+        std::process::exit(e.exit_code());
+    }
+}
+
+fn run(cli: Cli) -> Result<(), AppError> {
+    logging::init(cli.verbose);
+    let mut config = Config::new_default()?;
+    if cli.plain {
+        config.settings.color = ColorChoice::Never;
+    } else if let Some(color) = cli.color {
+        config.settings.color = color.into();
+    }
+    let storage = FilesystemStorage::new(&config);
+    let settings = &config.settings;
+    style::configure(settings.use_color(std::io::stdout().is_terminal()), cli.plain);
+    keychain::set_service(&settings.keychain_service);
+    github::set_api_base(settings.api_base.as_deref());
+    keychain::set_precedence(if cli.no_env_token {
+        TokenPrecedence::Keychain
+    } else {
+        settings.token_source
+    });
+
+    warn_env_token(&storage);
+    warn_token_expiry(&storage, settings);
+
+    let mode = ExecutionMode::from_dry_run(cli.dry_run);
+    let progress: Progress = cli.progress.into();
+    if mode.is_dry_run() && !supports_dry_run(&cli.command) {
+        return Err(AppError::invalid_input("--dry-run is not supported by this command"));
+    }
+
+    if records_history(&cli.command) {
+        let args: Vec<String> =
+            std::env::args_os().skip(1).map(|a| a.to_string_lossy().into_owned()).collect();
+        let token_source = account::show(&storage).ok().map(|a| keychain::token_source(&a));
+        if let Err(e) = history::record(&config, &storage, &args, token_source) {
+            esay!("⚠️ Could not record command history: {e}");
+        }
+    }
+
+    if let Some(group) = cli.accounts.as_deref() {
+        return run_group_command(&storage, settings, group, cli.command);
+    }
+
+    match cli.command {
+        Commands::Init => run_init_command(&storage),
+        Commands::Account { command } => run_account_command(&storage, &config, mode, command),
+        Commands::Repo { command } => run_repo_command(&storage, settings, mode, progress, command),
+        Commands::Branch { command } => {
+            run_branch_command(&storage, settings, mode, progress, command)
+        }
+        Commands::Org { command } => run_org_command(&storage, settings, command),
+        Commands::Pr { command } => run_pr_command(&storage, &config, mode, progress, command),
+        Commands::Commit { command } => run_commit_command(&storage, settings, command),
+        Commands::Hook { command } => run_hook_command(&storage, settings, command),
+        Commands::Secret { command } => run_secret_command(&storage, settings, command),
+        Commands::Variable { command } => run_variable_command(&storage, settings, command),
+        Commands::Label { command } => {
+            run_label_command(&storage, settings, mode, progress, command)
+        }
+        Commands::Queue { command } => run_queue_command(&storage, settings, command),
+        Commands::Run { command } => run_run_command(&storage, settings, command),
+        Commands::Package { command } => {
+            run_package_command(&storage, settings, mode, progress, command)
+        }
+        Commands::Runner { command } => run_runner_command(&storage, settings, command),
+        Commands::Actions { command } => run_actions_command(&storage, settings, command),
+        Commands::Workflow { command } => run_workflow_command(&storage, command),
+        Commands::Release { command } => run_release_command(&storage, settings, command),
+        Commands::Api {
+            command,
+            endpoint,
+            method,
+            raw_fields,
+            typed_fields,
+            headers,
+            paginate,
+            repo,
+        } => match command {
+            Some(command) => run_api_command(&storage, settings, command),
+            None => {
+                let request = ApiRequest {
+                    endpoint: endpoint.unwrap_or_default(),
+                    method,
+                    raw_fields,
+                    typed_fields,
+                    headers,
+                    paginate,
+                    repo,
+                };
+                let output = api::request(&storage, &request)?;
+                if !output.is_empty() {
+                    println!("{output}");
+                }
+                Ok(())
+            }
+        },
+        Commands::Access { command } => run_access_command(&storage, settings, command),
+        Commands::Auth { command: AuthCommands::GitCredential { operation } } => {
+            if operation != "get" {
+                return Ok(());
+            }
+            let mut input = String::new();
+            std::io::Read::read_to_string(&mut std::io::stdin(), &mut input)?;
+            if let Some(credential) = auth::git_credential(&storage, &input)? {
+                print!("{}", credential.to_git());
+            }
+            Ok(())
+        }
+        Commands::Whoami { all: false, json } => {
+            let identity = auth::whoami(&storage)?;
+            output::present(&identity, settings.wants_json(json))?;
+            match &identity.error {
+                Some(error) => Err(AppError::Unauthorized(error.clone())),
+                None if identity.mismatch => Err(AppError::Unauthorized(format!(
+                    "the token does not belong to {}",
+                    identity.username
+                ))),
+                None => Ok(()),
+            }
+        }
+        Commands::Whoami { all: true, json } => {
+            let identities = auth::whoami_all(&storage)?;
+            output::present(&identities, settings.wants_json(json))?;
+            match identities.iter().filter(|i| !i.is_ok()).count() {
+                0 => Ok(()),
+                failed => Err(AppError::Unauthorized(format!(
+                    "{failed} of {} accounts have a token of another user or none that works",
+                    identities.len()
+                ))),
+            }
+        }
+        Commands::Auth { command: AuthCommands::Status { json } } => {
+            let statuses = auth::status(&storage)?;
+            output::present(&statuses, settings.wants_json(json))?;
+            match statuses.iter().filter(|s| !s.problems.is_empty()).count() {
+                0 => Ok(()),
+                failed => Err(AppError::Unauthorized(format!(
+                    "{failed} of {} accounts have token problems",
+                    statuses.len()
+                ))),
+            }
+        }
+        Commands::Contrib { account, all_accounts, since, json } => {
+            let accounts = match account.as_deref() {
+                Some(id) => ContribAccounts::Account(id),
+                None if all_accounts => ContribAccounts::All,
+                None => ContribAccounts::Active,
+            };
+            print_contributions(
+                settings,
+                &contrib::summary(&storage, accounts, since.as_deref())?,
+                json,
+            )
+        }
+        Commands::Workspace { command } => run_workspace_command(&storage, mode, progress, command),
+        Commands::Gist { command } => run_gist_command(&storage, settings, command),
+        Commands::Issue { command } => run_issue_command(&storage, settings, command),
+        Commands::Notify { command } => run_notify_command(&storage, settings, command),
+        Commands::Daemon { command } => run_daemon_command(&storage, &config, command),
+        Commands::Config { command } => run_config_command(&mut config, command),
+        Commands::Bootstrap { command: BootstrapCommands::Emit } => {
+            print!("{}", bootstrap::emit(settings, &storage)?);
+            Ok(())
+        }
+        Commands::History { command } => run_history_command(&config, command),
+        Commands::Redo { n, as_active } => {
+            let (n, entry) = history::get(&config, n)?;
+            esay!("↻ {n}: gho {}", domain::shell_join(&entry.args));
+            let status = history::replay(&storage, &entry, as_active)?;
+            if !status.success() {
+                std::process::exit(status.code().unwrap_or(1));
+            }
+            Ok(())
+        }
+        Commands::Recent { select, json } => run_recent_command(&storage, settings, select, json),
+        Commands::Doctor { json } => run_doctor_command(&storage, settings, json),
+        Commands::Completions { shell } => {
+            print!("{}", completion_script(shell));
+            Ok(())
+        }
+        #[cfg(feature = "selftest")]
+        Commands::Selftest { org, json } => {
+            let report = gho::selftest::run(&storage, &org)?;
+            output::present(&report, settings.wants_json(json))?;
+            if !report.cleaned_up {
+                esay!("⚠️  Could not delete {}; delete it by hand", report.repo);
+            }
+            match report.failed() {
+                0 => Ok(()),
+                failed => Err(AppError::github_api(format!(
+                    "{failed} of {} selftest steps failed",
+                    report.steps.len()
+                ))),
+            }
+        }
+    }
+}
+
+/// Every command is recorded except those reading the history and the ones other programs run:
+/// the daemon's own loop and git's credential helper.
+fn records_history(command: &Commands) -> bool {
+    !matches!(
+        command,
+        Commands::History { .. }
+            | Commands::Redo { .. }
+            | Commands::Daemon { command: DaemonCommands::Run { .. } }
+            | Commands::Auth { command: AuthCommands::GitCredential { .. } }
+    )
+}
+
+fn run_init_command(storage: &FilesystemStorage) -> Result<(), AppError> {
+    let report = init::run(storage, |shell| {
+        completion_script(match shell {
+            CompletionShell::Bash => Shell::Bash,
+            CompletionShell::Zsh => Shell::Zsh,
+            CompletionShell::Fish => Shell::Fish,
+        })
+    })?;
+    say!("✅ Added account '{}' ({})", report.account, report.username);
+    if let Some((shell, file)) = &report.completions {
+        println!("   Installed {shell} completions to {}", file.display());
+        if *shell == CompletionShell::Zsh {
+            println!("   Load them with `fpath+=~/.zfunc` before `compinit` in ~/.zshrc");
+        }
+    }
+    if report.credential_helper {
+        println!("   git now uses gho's tokens for https://github.com");
+    }
+    Ok(())
+}
+
+/// The completion script of the whole CLI for `shell`.
+fn completion_script(shell: Shell) -> String {
+    let mut script = Vec::new();
+    clap_complete::generate(shell, &mut Cli::command(), "gho", &mut script);
+    String::from_utf8_lossy(&script).into_owned()
+}
+
+/// Commands that honor `--dry-run`; every other command rejects the flag instead of running.
+fn supports_dry_run(command: &Commands) -> bool {
+    matches!(
+        command,
+        Commands::Account { command: AccountCommands::Remove { .. } }
+            | Commands::Repo {
+                command: RepoCommands::Clone { .. }
+                    | RepoCommands::Delete { .. }
+                    | RepoCommands::Archive { .. }
+                    | RepoCommands::Unarchive { .. }
+                    | RepoCommands::Rename { .. }
+                    | RepoCommands::Transfer { .. }
+                    | RepoCommands::Foreach { .. }
+                    | RepoCommands::Protection { command: ProtectionCommands::Apply { .. } }
+            }
+            | Commands::Branch { command: BranchCommands::Delete { .. } }
+            | Commands::Pr {
+                command: PrCommands::Merge { .. }
+                    | PrCommands::Close { .. }
+                    | PrCommands::Fanout { .. },
+            }
+            | Commands::Label { command: LabelCommands::Sync { .. } }
+            | Commands::Package { command: PackageCommands::Prune { .. } }
+            | Commands::Workspace { command: WorkspaceCommands::Sync { .. } }
+    )
+}
+
+/// Print the steps of a dry run.
+fn print_plan(steps: &[String]) {
+    print!("{}", style::line(&output::plan(steps)));
+}
+
+fn print_move(moved: Execution<RepoMove>) {
+    match moved {
+        Execution::Done(m) if m.pending => {
+            say!("📨 Requested the transfer of {} to {}, pending acceptance", m.from, m.to);
+        }
+        Execution::Done(m) => {
+            say!("✅ Moved {} to {} ({})", m.from, m.to, m.html_url);
+            if let Some(dir) = m.clone {
+                say!("🔗 Pointed the origin of {} at {}", dir.display(), m.to);
+            }
+        }
+        Execution::Planned(steps) => print_plan(&steps),
+    }
+}
+
+fn run_token_command(storage: &FilesystemStorage, command: TokenCommands) -> Result<(), AppError> {
+    match command {
+        TokenCommands::Set { id } => {
+            account::keychain_account(storage, &id)?;
+            let token = account::read_new_token(&id)?;
+            account::set_token(storage, &id, &token)?;
+            say!("✅ Stored a new token for '{id}'");
+        }
+        TokenCommands::Get { id, plain } => {
+            let token = account::get_token(storage, &id)?;
+            if !plain {
+                println!("{}", keychain::mask_token(&token));
+            } else if account::confirm_plain_token(&id)? {
+                println!("{token}");
+            } else {
+                return Err(AppError::invalid_input("operation cancelled"));
+            }
+        }
+        TokenCommands::Remove { id } => {
+            account::remove_token(storage, &id)?;
+            say!("✅ Removed the token of '{id}'");
+        }
+        TokenCommands::Refresh { id, expires } => {
+            let expires = expires.as_deref().map(domain::parse_expiry).transpose()?;
+            account::keychain_account(storage, &id)?;
+            let token = account::read_new_token(&id)?;
+            let expires_at = token_expiry(&token, expires);
+            account::refresh_token(storage, &id, &token, expires_at)?;
+            say!("✅ Replaced the token of '{id}'");
+            print_token_expiry(expires_at);
+        }
+    }
+    Ok(())
+}
+
+/// Print a warning when `GH_TOKEN`/`GITHUB_TOKEN` silently replaces the active account's token.
+fn warn_env_token(storage: &FilesystemStorage) {
+    if keychain::precedence() != TokenPrecedence::Auto {
+        return;
+    }
+    let Ok(active) = account::show(storage) else {
+        return;
+    };
+    let source = keychain::token_source(&active);
+    if matches!(source, TokenSource::GhToken | TokenSource::GithubToken) {
+        let var = source.to_string().trim_start_matches("env:").to_string();
+        esay!(
+            "⚠️  {var} overrides the token of account '{}' ({}); pass --no-env-token or set \
+             token_source to keychain or env to choose explicitly",
+            active.id,
+            active.username
+        );
+    }
+}
+
+/// Print a warning when the active account's token expires soon.
+fn warn_token_expiry(storage: &FilesystemStorage, settings: &Settings) {
+    let Ok(active) = account::show(storage) else {
+        return;
+    };
+    if let Some(warning) =
+        account::token_expiry_warning(&active, Utc::now(), settings.token_expiry_warning_days)
+    {
+        esay!("⚠️  {warning}");
+    }
+}
+
+/// The explicit `--expires`, or the expiry GitHub reports for `token` (best effort).
+fn token_expiry(token: &str, expires: Option<DateTime<Utc>>) -> Option<DateTime<Utc>> {
+    expires.or_else(|| account::detect_token_expiry(token).ok().flatten())
+}
+
+fn print_token_expiry(expires_at: Option<DateTime<Utc>>) {
+    if let Some(expires_at) = expires_at {
+        println!("   Token expires {}", expires_at.format("%Y-%m-%d"));
+    }
+}
+
+/// Print repositories as JSON lines, with a `--format` template, or as the `repo list` table.
+fn print_repos(
+    settings: &Settings,
+    repos: &[Repository],
+    json: bool,
+    format: Option<&output::Template>,
+) -> Result<(), AppError> {
+    let rows = || repos.iter().map(repo_row);
+    if let Some(format) = format {
+        output::present_each(&rows().collect::<Vec<_>>(), format)?;
+    } else if settings.wants_json(json) {
+        for row in rows() {
+            println!("{}", serde_json::to_string(&row)?);
+        }
+    } else if !repos.is_empty() {
+        let color = settings.use_color(std::io::stdout().is_terminal());
+        print!("{}", domain::repo_table(repos, color));
+    }
+    Ok(())
+}
+
+/// Row of `repo list --json` and `--format`.
+fn repo_row(r: &Repository) -> serde_json::Value {
+    serde_json::json!({
+        "name": r.name,
+        "url": r.html_url,
+        "pushed_at": r.pushed_at,
+        "owner": r.owner.login,
+        "description": r.description,
+        "language": r.language,
+        "visibility": r.visibility,
+        "stars": r.stargazers_count,
+        "open_issues": r.open_issues_count,
+        "archived": r.archived,
+        "fork": r.fork,
+        "topics": r.topics,
+    })
+}
+
+/// Filter of `repo list` from its flags; `--source` and `--forks-only` conflict.
+fn repo_filter(
+    visibility: Option<VisibilityArg>,
+    source: bool,
+    forks_only: bool,
+    no_archived: bool,
+    language: Option<String>,
+    topic: Option<String>,
+) -> RepoFilter {
+    let fork = match (source, forks_only) {
+        (true, _) => Some(false),
+        (_, true) => Some(true),
+        _ => None,
+    };
+    RepoFilter {
+        visibility: visibility.map(Into::into),
+        fork,
+        exclude_archived: no_archived,
+        language,
+        topic,
+    }
+}
+
+fn run_group_command(
+    storage: &FilesystemStorage,
+    settings: &Settings,
+    group: &str,
+    command: Commands,
+) -> Result<(), AppError> {
+    match command {
+        Commands::Repo {
+            command:
+                RepoCommands::List {
+                    org,
+                    limit,
+                    sort,
+                    visibility,
+                    source,
+                    forks_only,
+                    no_archived,
+                    language,
+                    topic,
+                    json,
+                    format,
+                },
+        } => {
+            let format = format.as_deref().map(output::Template::parse).transpose()?;
+            let filter = repo_filter(visibility, source, forks_only, no_archived, language, topic);
+            let limit = limit.unwrap_or(settings.limit);
+            let mut repos = repo::list_group(storage, group, org.as_deref(), &filter, limit)?;
+            domain::sort_repos(&mut repos, sort.into(), |r| &r.item);
+            let rows = || {
+                repos.iter().map(|r| {
+                    let mut row = repo_row(&r.item);
+                    row["account"] = r.account.clone().into();
+                    row
+                })
+            };
+            if let Some(format) = format {
+                return output::present_each(&rows().collect::<Vec<_>>(), &format);
+            }
+            if settings.wants_json(json) {
+                for row in rows() {
+                    println!("{}", serde_json::to_string(&row)?);
+                }
+            } else {
+                for r in &repos {
+                    println!("[{}] {} {}", r.account, r.item.full_name, r.item.html_url);
+                }
+            }
+        }
+        Commands::Pr {
+            command: PrCommands::List { repo, limit, org: None, owned: false, format, .. },
+        } => {
+            let format = format.as_deref().map(output::Template::parse).transpose()?;
+            let limit = limit.unwrap_or(settings.limit);
+            let prs = pr::list_group(storage, group, repo.as_deref(), limit)?;
+            match &format {
+                Some(format) => output::present_each(&prs, format)?,
+                None => {
+                    for p in &prs {
+                        println!("{}", serde_json::to_string(p)?);
+                    }
+                }
+            }
+            report_partial_prs(prs.iter().filter(|p| p.item.partial).count(), prs.len());
+        }
+        Commands::Contrib { account: None, all_accounts: false, since, json } => {
+            let entries =
+                contrib::summary(storage, ContribAccounts::Group(group), since.as_deref())?;
+            print_contributions(settings, &entries, json)?;
+        }
+        _ => {
+            return Err(AppError::invalid_input(
+                "--accounts is only supported by `repo list`, `pr list` and `contrib` \
+                 (without --account or --all-accounts)",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Summary of `pr list` entries printed with `"partial": true`.
+fn report_partial_prs(partial: usize, total: usize) {
+    if partial > 0 {
+        esay!(
+            "⚠️  {partial} of {total} pull requests are partial (mergeable state unavailable, \
+             likely rate limited)"
+        );
+    }
+}
+
+fn run_account_command(
+    storage: &FilesystemStorage,
+    config: &Config,
+    mode: ExecutionMode,
+    command: AccountCommands,
+) -> Result<(), AppError> {
+    match command {
+        AccountCommands::Add {
+            id,
+            username,
+            kind,
+            token,
+            host,
+            default_org,
+            protocol,
+            clone_dir,
+            clone_layout,
+            expires,
+        } => {
+            let expires = expires.as_deref().map(domain::parse_expiry).transpose()?;
+            let expires_at = token_expiry(&token, expires);
+            account::add(
+                storage,
+                &id,
+                &username,
+                kind.into(),
+                &token,
+                host,
+                default_org,
+                protocol.into(),
+                clone_dir,
+                clone_layout.into(),
+                expires_at,
+            )?;
+            say!("✅ Added account '{id}'");
+            print_token_expiry(expires_at);
+        }
+        AccountCommands::List => {
+            let accounts = account::list(storage)?;
+            let all = accounts.all_accounts();
+
+            if all.is_empty() {
+                println!("No accounts configured.");
+                return Ok(());
+            }
+
+            say!("📋 Accounts:");
+            for acc in all {
+                let active = accounts.active_account_id.as_deref() == Some(&acc.id);
+                let marker = if active { " (active)" } else { "" };
+                let protected = if acc.protected { " [protected]" } else { "" };
+                let expires = acc
+                    .token_expires_at
+                    .map(|t| format!(" [expires {}]", t.format("%Y-%m-%d")))
+                    .unwrap_or_default();
+                println!(
+                    "  {} ({}) - {} [{}]{}{}{}",
+                    acc.id, acc.kind, acc.username, acc.protocol, protected, expires, marker
+                );
+            }
+        }
+        AccountCommands::Use { id, ssh_agent, json } => {
+            let mut result = match id {
+                Some(id) => account::switch(storage, &id)?,
+                None => account::switch_interactive(storage)?,
+            };
+            if ssh_agent || config.settings.ssh_agent {
+                let accounts = account::list(storage)?;
+                result.ssh_agent = Some(sshagent::switch(&accounts, &result.id)?);
+            }
+            output::present(&result, config.settings.wants_json(json))?;
+        }
+        AccountCommands::Show => {
+            let acc = account::show(storage)?;
+            let token =
+                keychain::get_account_token(&acc).unwrap_or_else(|_| "(not found)".to_string());
+            let masked = keychain::mask_token(&token);
+
+            say!("🔑 Active account:");
+            println!("  ID:       {}", acc.id);
+            println!("  Kind:     {}", acc.kind);
+            println!("  Username: {}", acc.username);
+            println!("  Protocol: {}", acc.protocol);
+            println!("  Token:    {}", masked);
+            if let Some(command) = &acc.token_command {
+                println!("  Source:   {}", command);
+            }
+            if let Some(org) = &acc.default_org {
+                println!("  Org:      {}", org);
+            }
+            if let Some(dir) = &acc.clone_dir {
+                println!("  Clone:    {}", dir);
+            }
+            if !acc.clone_layout.is_flat() {
+                println!("  Layout:   {}", acc.clone_layout);
+            }
+            if !acc.clone_options.is_empty() {
+                println!("  Options:  {}", acc.clone_options.args().join(" "));
+            }
+            if let Some(browser) = &acc.browser {
+                println!("  Browser:  {browser}");
+            }
+            if let Some(expires_at) = acc.token_expires_at {
+                println!("  Expires:  {}", expires_at.format("%Y-%m-%d"));
+            }
+        }
+        AccountCommands::Remove { id } => match account::remove(storage, &id, mode)? {
+            Execution::Done(()) => {
+                gitconfig::remove(config, &id)?;
+                say!("🗑️  Removed account '{id}'");
+            }
+            Execution::Planned(steps) => print_plan(&steps),
+        },
+        AccountCommands::SetupGitconfig { id, name, email, signing_key, ssh_key, gitdir } => {
+            let options = GitIdentityOptions { name, email, signing_key, ssh_key, gitdir };
+            let setup = gitconfig::setup(config, storage, &id, options)?;
+            say!("✅ Wrote {}", setup.account_file.display());
+            say!(
+                "✅ {} includes it for repositories under {} ({} <{}>)",
+                setup.global_file.display(),
+                setup.identity.gitdir,
+                setup.identity.name,
+                setup.identity.email
+            );
+        }
+        AccountCommands::CloneLayout { id, layout } => {
+            let layout: CloneLayout = layout.into();
+            account::set_clone_layout(storage, &id, layout)?;
+            say!("✅ Account '{id}' now clones into {layout} layout");
+        }
+        AccountCommands::CloneDefaults { id, options } => {
+            let options = CloneOptions::from(options);
+            let args = options.args();
+            account::set_clone_options(storage, &id, options)?;
+            if args.is_empty() {
+                say!("✅ Account '{id}' now clones with git's defaults");
+            } else {
+                say!("✅ Account '{id}' now clones with {}", args.join(" "));
+            }
+        }
+        AccountCommands::MigrateKeychain { to_service, webhook_secrets } => {
+            let migrated =
+                account::migrate_keychain(storage, &to_service, &webhook_secrets, || {
+                    let mut config = config.clone();
+                    config.settings.keychain_service = to_service.clone();
+                    config.save_settings()
+                })?;
+            say!(
+                "✅ Moved the secrets of {} accounts to keychain service '{to_service}'",
+                migrated.len()
+            );
+            for id in migrated {
+                println!("  - {id}");
+            }
+            for name in &webhook_secrets {
+                println!("  - webhook secret {name}");
+            }
+        }
+        AccountCommands::Browser { id, command, clear: _ } => {
+            account::set_browser(storage, &id, command.clone())?;
+            match command {
+                Some(command) => say!("✅ Account '{id}' now opens pages with `{command}`"),
+                None => say!("✅ Account '{id}' now opens pages in the system browser"),
+            }
+        }
+        AccountCommands::Protect { id, pin } => {
+            let pin = if pin { Some(account::prompt_new_pin()?) } else { None };
+            account::protect(storage, &id, pin.as_deref())?;
+            say!("🔒 Account '{id}' is now protected");
+        }
+        AccountCommands::Token { command } => run_token_command(storage, command)?,
+        AccountCommands::Unprotect { id } => {
+            account::unprotect(storage, &id)?;
+            say!("🔓 Account '{id}' is no longer protected");
+        }
+        AccountCommands::Export { id, to, vault, file, include_tokens, encrypt } => {
+            if let (Some(id), Some(to)) = (&id, to) {
+                let kind: VaultKind = to.into();
+                let command = account::export_to_vault(storage, id, kind, vault.as_deref())?;
+                say!("✅ Moved token for '{id}' to {kind}");
+                println!("  Lookup: {command}");
+            } else if let Some(file) = file {
+                let passphrase =
+                    if encrypt { Some(account::backup_passphrase(true)?) } else { None };
+                let count = account::export_to_file(
+                    storage,
+                    id.as_deref(),
+                    &file,
+                    include_tokens,
+                    passphrase.as_deref(),
+                )?;
+                say!("✅ Exported {count} account(s) to {}", file.display());
+                if include_tokens && !encrypt {
+                    say!("⚠️  Tokens are stored in plain text; keep this file private");
+                }
+            }
+        }
+        AccountCommands::Import { file, on_conflict } => {
+            let report = account::import_from_file(
+                storage,
+                &file,
+                |acc| match on_conflict {
+                    ConflictArg::Prompt => account::prompt_conflict(acc),
+                    ConflictArg::Skip => Ok(ConflictAction::Skip),
+                    ConflictArg::Overwrite => Ok(ConflictAction::Overwrite),
+                },
+                || account::backup_passphrase(false),
+            )?;
+            say!("✅ Imported {} account(s)", report.imported.len());
+            for id in &report.imported {
+                println!("  + {id}");
+            }
+            for id in &report.skipped {
+                println!("  - {id} (skipped)");
+            }
+            if report.tokens_restored > 0 {
+                say!("🔑 Restored {} token(s) into the keychain", report.tokens_restored);
+            }
+        }
+        AccountCommands::Group { command } => match command {
+            GroupCommands::Set { name, ids } => {
+                account::set_group(storage, &name, ids)?;
+                say!("✅ Saved group '{name}'");
+            }
+            GroupCommands::List => {
+                let accounts = account::list(storage)?;
+                if accounts.groups.is_empty() {
+                    println!("No groups configured.");
+                }
+                for (name, members) in &accounts.groups {
+                    println!("  {name}: {}", members.join(", "));
+                }
+            }
+            GroupCommands::Remove { name } => {
+                account::remove_group(storage, &name)?;
+                say!("🗑️  Removed group '{name}'");
+            }
+        },
+    }
+    Ok(())
+}
+
+fn run_repo_command(
+    storage: &FilesystemStorage,
+    settings: &Settings,
+    mode: ExecutionMode,
+    progress: Progress,
+    command: RepoCommands,
+) -> Result<(), AppError> {
+    match command {
+        RepoCommands::List {
+            org,
+            limit,
+            sort,
+            visibility,
+            source,
+            forks_only,
+            no_archived,
+            language,
+            topic,
+            json,
+            format,
+        } => {
+            let format = format.as_deref().map(output::Template::parse).transpose()?;
+            let limit = limit.unwrap_or(settings.limit);
+            let org = org::resolve(storage, org.as_deref(), true)?;
+            let filter = repo_filter(visibility, source, forks_only, no_archived, language, topic);
+            let mut repos = repo::list(storage, org.as_deref(), &filter, limit)?;
+            domain::sort_repos(&mut repos, sort.into(), |r| r);
+            print_repos(settings, &repos, json, format.as_ref())?;
+        }
+        RepoCommands::Starred {
+            limit,
+            sort,
+            visibility,
+            source,
+            forks_only,
+            no_archived,
+            language,
+            topic,
+            json,
+            format,
+        } => {
+            let format = format.as_deref().map(output::Template::parse).transpose()?;
+            let filter = repo_filter(visibility, source, forks_only, no_archived, language, topic);
+            let mut repos = repo::starred(storage, &filter, limit.unwrap_or(settings.limit))?;
+            if let Some(sort) = sort {
+                domain::sort_repos(&mut repos, sort.into(), |r| r);
+            }
+            print_repos(settings, &repos, json, format.as_ref())?;
+        }
+        RepoCommands::Star { repo: spec } => {
+            say!("⭐ Starred {}", repo::set_starred(storage, spec.as_deref(), true)?);
+        }
+        RepoCommands::Unstar { repo: spec } => {
+            say!("✅ Unstarred {}", repo::set_starred(storage, spec.as_deref(), false)?);
+        }
+        RepoCommands::Watch { repo: spec } => {
+            say!("👀 Watching {}", repo::set_watched(storage, spec.as_deref(), true)?);
+        }
+        RepoCommands::Unwatch { repo: spec } => {
+            say!("✅ Stopped watching {}", repo::set_watched(storage, spec.as_deref(), false)?);
+        }
+        RepoCommands::Topics { command } => {
+            let (spec, change, topics) = match command {
+                TopicsCommands::Set { repo, topics } => (repo, repo::TopicsChange::Set, topics),
+                TopicsCommands::Add { repo, topics } => (repo, repo::TopicsChange::Add, topics),
+                TopicsCommands::Remove { repo, topics } => {
+                    (repo, repo::TopicsChange::Remove, topics)
+                }
+            };
+            let topics = repo::update_topics(storage, &spec, change, &topics)?;
+            if topics.is_empty() {
+                say!("✅ No topics");
+            } else {
+                say!("✅ Topics: {}", topics.join(", "));
+            }
+        }
+        RepoCommands::Search { query, org, language, sort, limit, json, interactive } => {
+            let repos = repo::search(
+                storage,
+                &query,
+                org.as_deref(),
+                language.as_deref(),
+                sort.into(),
+                limit.unwrap_or(settings.limit),
+            )?;
+
+            if interactive {
+                let selected = repo::select_interactive(repos)?;
+                let options = CloneOptions::default();
+                let report =
+                    repo::clone(storage, &selected.full_name, options, false, ExecutionMode::Live)?;
+                output::present_execution(&report, false)?;
+            } else if settings.wants_json(json) {
+                for r in repos {
+                    println!("{}", serde_json::to_string(&r)?);
+                }
+            } else if repos.is_empty() {
+                println!("No repositories found.");
+            } else {
+                print_search_results(&repos);
+            }
+        }
+        RepoCommands::Clone { resume: true, jobs, quiet, json, .. } => {
+            let jobs = jobs.unwrap_or(settings.clone_jobs);
+            let report = repo::resume_clone_org(storage, jobs, quiet, mode, progress)?;
+            output::present_execution(&report, settings.wants_json(json))?;
+        }
+        RepoCommands::Clone { from_manifest: Some(path), .. } => {
+            let checkouts = match manifest::restore(storage, &path, mode, progress)? {
+                Execution::Done(checkouts) => checkouts,
+                Execution::Planned(steps) => {
+                    print_plan(&steps);
+                    return Ok(());
+                }
+            };
+
+            let mut failed = 0;
+            for c in &checkouts {
+                if let Some(error) = &c.error {
+                    failed += 1;
+                    if !progress.is_json() {
+                        esay!("⚠️  {}: {error}", c.repo);
+                    }
+                } else if c.cloned {
+                    say!("✅ {}: cloned into {}", c.repo, c.dir.display());
+                } else {
+                    say!("⏭️  {}: {} already exists", c.repo, c.dir.display());
+                }
+            }
+            if failed > 0 {
+                return Err(AppError::git(format!(
+                    "{failed} of {} repositories failed",
+                    checkouts.len()
+                )));
+            }
+        }
+        RepoCommands::Clone {
+            repo,
+            org,
+            limit,
+            jobs,
+            manifest,
+            from_manifest: None,
+            options,
+            quiet,
+            resume: false,
+            json,
+        } => {
+            let options = CloneOptions::from(options);
+            let org = match repo {
+                Some(_) => org,
+                None => org::resolve(storage, org.as_deref(), false)?,
+            };
+            if let Some(org) = org {
+                let jobs = jobs.unwrap_or(settings.clone_jobs);
+                let manifest = manifest.as_deref();
+                let report = repo::clone_org(
+                    storage, &org, limit, jobs, manifest, options, quiet, mode, progress,
+                )?;
+                output::present_execution(&report, settings.wants_json(json))?;
+            } else if let Some(repo_spec) = repo {
+                let report = repo::clone(storage, &repo_spec, options, quiet, mode)?;
+                output::present_execution(&report, settings.wants_json(json))?;
+            } else {
+                return Err(AppError::invalid_input(
+                    "provide either a repo (owner/repo), --org or --from-manifest",
+                ));
+            }
+        }
+        RepoCommands::Scan { path, depth, refresh, json } => {
+            let root = match path {
+                Some(path) => path,
+                None => account::show(storage)
+                    .ok()
+                    .and_then(|a| a.clone_dir)
+                    .map(PathBuf::from)
+                    .unwrap_or_else(|| PathBuf::from(".")),
+            };
+            let repos = scan::discover(storage, &root, depth, refresh)?;
+            if settings.wants_json(json) {
+                println!("{}", serde_json::to_string_pretty(&repos)?);
+            } else {
+                for repo in &repos {
+                    println!("{}", repo.display());
+                }
+            }
+        }
+        RepoCommands::Status { path, depth, refresh, json } => {
+            let statuses = clones::status(storage, path.as_deref(), depth, refresh)?;
+            output::present(&statuses, settings.wants_json(json))?;
+        }
+        RepoCommands::Delete { repo: spec, yes } => {
+            if !yes && !mode.is_dry_run() && !repo::confirm_delete(&spec)? {
+                return Err(AppError::invalid_input("repository name did not match, not deleted"));
+            }
+            match repo::delete(storage, &spec, mode)? {
+                Execution::Done(()) => say!("🗑️  Deleted {spec}"),
+                Execution::Planned(steps) => print_plan(&steps),
+            }
+        }
+        RepoCommands::Archive { repo: spec, yes } => {
+            let (owner, name) = remote::resolve_repo(storage, spec.as_deref())?;
+            let spec = format!("{owner}/{name}");
+            if !yes && !mode.is_dry_run() && !repo::confirm_archive(&spec)? {
+                return Err(AppError::invalid_input("operation cancelled"));
+            }
+            match repo::set_archived(storage, Some(&spec), true, mode)? {
+                Execution::Done(r) => say!("📦 Archived {}", r.full_name),
+                Execution::Planned(steps) => print_plan(&steps),
+            }
+        }
+        RepoCommands::Rename { repo: spec, new_name, yes } => {
+            let (owner, name) =
+                remote::parse_spec(storage, &spec)?.resolve(|| remote::default_owner(storage))?;
+            let (from, to) = (format!("{owner}/{name}"), format!("{owner}/{new_name}"));
+            if !yes && !mode.is_dry_run() && !repo::confirm_move(&from, &to)? {
+                return Err(AppError::invalid_input("operation cancelled"));
+            }
+            print_move(repo::rename(storage, &from, &new_name, mode)?);
+        }
+        RepoCommands::Transfer { repo: spec, to: new_owner, teams, yes } => {
+            let (owner, name) =
+                remote::parse_spec(storage, &spec)?.resolve(|| remote::default_owner(storage))?;
+            let (from, to) = (format!("{owner}/{name}"), format!("{new_owner}/{name}"));
+            if !yes && !mode.is_dry_run() && !repo::confirm_move(&from, &to)? {
+                return Err(AppError::invalid_input("operation cancelled"));
+            }
+            print_move(repo::transfer(storage, &from, &new_owner, &teams, mode)?);
+        }
+        RepoCommands::Unarchive { repo: spec } => {
+            match repo::set_archived(storage, spec.as_deref(), false, mode)? {
+                Execution::Done(r) => say!("✅ Unarchived {}", r.full_name),
+                Execution::Planned(steps) => print_plan(&steps),
+            }
+        }
+        RepoCommands::Open { repo, path, branch, print } => {
+            let url = repo::web_url(storage, repo.as_deref(), branch.as_deref(), path.as_deref())?;
+            if print {
+                println!("{url}");
+            } else {
+                let active = account::show(storage).ok();
+                browser::open_for(active.as_ref(), &url).map_err(|e| {
+                    AppError::config(format!("{e}\nhint: use --print to show the URL instead"))
+                })?;
+                say!("🌐 Opened {url}");
+            }
+        }
+        RepoCommands::Foreach { org, limit, jobs, no_clone, json, command } => {
+            let limit = limit.unwrap_or(settings.limit);
+            let jobs = jobs.unwrap_or(settings.clone_jobs);
+            let org = org::resolve(storage, org.as_deref(), true)?;
+            let results = match repo::foreach(
+                storage,
+                org.as_deref(),
+                limit,
+                jobs,
+                &command,
+                !no_clone,
+                mode,
+                progress,
+            )? {
+                Execution::Done(results) => results,
+                Execution::Planned(steps) => {
+                    print_plan(&steps);
+                    return Ok(());
+                }
+            };
+
+            let failed = results.iter().filter(|r| r.error.is_some()).count();
+            if settings.wants_json(json) {
+                for r in &results {
+                    println!("{}", serde_json::to_string(r)?);
+                }
+            } else {
+                println!();
+                for r in &results {
+                    match &r.error {
+                        Some(error) => say!("⚠️  {}: {error}", r.repo),
+                        None if r.cloned => say!("✅ {} (cloned)", r.repo),
+                        None => say!("✅ {}", r.repo),
+                    }
+                }
+            }
+            if failed > 0 {
+                return Err(AppError::git(format!(
+                    "{failed} of {} repositories failed",
+                    results.len()
+                )));
+            }
+        }
+        RepoCommands::Protection { command: ProtectionCommands::Show { repo, branch, json } } => {
+            let rules = protection::show(storage, repo.as_deref(), &branch)?;
+            if settings.wants_json(json) {
+                println!("{}", serde_json::to_string_pretty(&rules)?);
+            } else {
+                for (rule, value) in protection::describe(&rules) {
+                    println!("{rule:<28} {value}");
+                }
+            }
+        }
+        RepoCommands::Protection {
+            command: ProtectionCommands::Apply { repos, from, branch, org, limit },
+        } => {
+            let rules = protection::load_file(&from)?;
+            let targets = match org.as_deref() {
+                Some(org) => ProtectionTargets::Org { org, limit: limit.unwrap_or(settings.limit) },
+                None => ProtectionTargets::Repos(&repos),
+            };
+            let changes = protection::apply(storage, targets, &branch, &rules, mode, progress)?;
+
+            let mut failed = 0;
+            for c in &changes {
+                if let Some(error) = &c.error {
+                    failed += 1;
+                    if !progress.is_json() {
+                        esay!("⚠️  {} ({}): {error}", c.repo, c.branch);
+                    }
+                } else if c.changed.is_empty() {
+                    say!("⏭️  {} ({}): unchanged", c.repo, c.branch);
+                } else {
+                    let prefix = if mode.is_dry_run() { "🔍" } else { "✅" };
+                    say!("{prefix} {} ({}): {}", c.repo, c.branch, c.changed.join(", "));
+                }
+            }
+            if failed > 0 {
+                return Err(AppError::github_api(format!(
+                    "{failed} of {} repositories failed",
+                    changes.len()
+                )));
+            }
+        }
+        RepoCommands::ChecksRequired { command } => {
+            let entries = match command {
+                ChecksRequiredCommands::Set { repos, branch, contexts } => repos
+                    .into_iter()
+                    .map(|repo| RequiredChecksEntry {
+                        repo,
+                        branch: branch.clone(),
+                        contexts: contexts.clone(),
+                    })
+                    .collect(),
+                ChecksRequiredCommands::Sync { from_file } => checks::load_file(&from_file)?,
+            };
+            print_required_checks(&checks::apply(storage, &entries)?)?;
+        }
+    }
+    Ok(())
+}
+
+fn print_required_checks(changes: &[RequiredChecksChange]) -> Result<(), AppError> {
+    let mut failed = 0;
+    for c in changes {
+        if let Some(error) = &c.error {
+            failed += 1;
+            esay!("⚠️  {} ({}): {error}", c.repo, c.branch);
+        } else if c.added.is_empty() && c.removed.is_empty() {
+            say!("⏭️  {} ({}): unchanged", c.repo, c.branch);
+        } else {
+            let diff: Vec<String> = c
+                .added
+                .iter()
+                .map(|a| format!("+{a}"))
+                .chain(c.removed.iter().map(|r| format!("-{r}")))
+                .collect();
+            say!("✅ {} ({}): {}", c.repo, c.branch, diff.join(" "));
+        }
+    }
+
+    if failed > 0 {
+        return Err(AppError::github_api(format!(
+            "{failed} of {} repositories failed",
+            changes.len()
+        )));
+    }
+    Ok(())
+}
+
+fn run_pr_command(
+    storage: &FilesystemStorage,
+    config: &Config,
+    mode: ExecutionMode,
+    progress: Progress,
+    command: PrCommands,
+) -> Result<(), AppError> {
+    let settings = &config.settings;
+    match command {
+        PrCommands::List { limit, org, owned, mine, review_requested, json, format, .. }
+            if org.is_some() || owned =>
+        {
+            let format = format.as_deref().map(output::Template::parse).transpose()?;
+            let search = PrSearch { org: org.as_deref(), mine, review_requested };
+            let limit = limit.unwrap_or(settings.limit);
+            let prs = pr::search(storage, &search, limit, settings.clone_jobs)?;
+            match &format {
+                Some(format) => output::present_each(&prs, format)?,
+                None => output::present(&prs, settings.wants_json(json))?,
+            }
+            report_partial_prs(prs.iter().filter(|p| p.pull_request.partial).count(), prs.len());
+        }
+        PrCommands::List { repo, limit, format, .. } => {
+            let format = format.as_deref().map(output::Template::parse).transpose()?;
+            let prs = pr::list(storage, repo.as_deref(), limit.unwrap_or(settings.limit))?;
+
+            match &format {
+                Some(format) => output::present_each(&prs, format)?,
+                None => {
+                    for p in &prs {
+                        let output = serde_json::to_string(p)?;
+                        println!("{output}");
+                    }
+                }
+            }
+            report_partial_prs(prs.iter().filter(|p| p.partial).count(), prs.len());
+        }
+        PrCommands::Status { limit, cached, json } => {
+            let dashboard = if cached {
+                let snapshot: Snapshot<PullRequestDashboard> =
+                    daemon::load_snapshot(config, daemon::DASHBOARD)?;
+                if !settings.wants_json(json) {
+                    let age = (Utc::now() - snapshot.refreshed_at).num_minutes();
+                    eprintln!("(cached {age} min ago)");
+                }
+                snapshot.data
+            } else {
+                pr::status(storage, limit.unwrap_or(settings.limit))?
+            };
+
+            if settings.wants_json(json) {
+                println!("{}", serde_json::to_string_pretty(&dashboard)?);
+            } else {
+                print_pr_dashboard(&dashboard);
+            }
+        }
+        PrCommands::FixConflicts { number, repo, resume, abort } => {
+            if abort {
+                let worktree = conflicts::abort(storage, repo.as_deref(), number)?;
+                say!("✅ Aborted; removed {}", worktree.display());
+                return Ok(());
+            }
+            let outcome = if resume {
+                conflicts::resume(storage, repo.as_deref(), number)?
+            } else {
+                conflicts::start(storage, repo.as_deref(), number)?
+            };
+            match outcome {
+                FixOutcome::Pushed { branch, sha } => {
+                    say!("✅ Rebased #{number} and pushed {branch} ({})", &sha[..sha.len().min(7)]);
+                }
+                FixOutcome::Conflicts { worktree, files } => {
+                    esay!("⚠️  Conflicts in {}:", worktree.display());
+                    for file in &files {
+                        eprintln!("  {file}");
+                    }
+                    eprintln!("Resolve them there, `git add` the files, then run:");
+                    eprintln!("  gho pr fix-conflicts {number} --continue   (or --abort)");
+                    return Err(AppError::git(format!("#{number} has unresolved conflicts")));
+                }
+            }
+        }
+        PrCommands::Create { repo, title, body, body_file, base, head, draft, metadata } => {
+            let body = match (body, body_file) {
+                (Some(body), _) => Some(body),
+                (None, Some(path)) => Some(pr::read_body(&path)?),
+                (None, None) if std::io::stdin().is_terminal() => {
+                    let help = format!("Body of \"{title}\". Lines starting with '#' are ignored.");
+                    Some(pager::compose(&settings.editor(), "pr-body", &help)?)
+                        .filter(|body| !body.is_empty())
+                }
+                (None, None) => None,
+            };
+            let new = PrCreate { title, body, base, head, draft };
+            let created = pr::create(storage, repo.as_deref(), new, &metadata.into())?;
+            say!("✅ Opened #{} {}", created.number, created.html_url.unwrap_or_default());
+        }
+        PrCommands::Edit {
+            number,
+            repo,
+            title,
+            body,
+            body_file,
+            base,
+            metadata,
+            remove_reviewers,
+            remove_labels,
+            remove_assignees,
+            remove_milestone,
+        } => {
+            let body = match (body, body_file) {
+                (Some(body), _) => Some(body),
+                (None, Some(path)) => Some(pr::read_body(&path)?),
+                (None, None) => None,
+            };
+            let update = PullRequestUpdate { title, body, base };
+            let metadata = PrMetadata {
+                remove_reviewers,
+                remove_labels,
+                remove_assignees,
+                remove_milestone,
+                ..metadata.into()
+            };
+            let edited = pr::edit(storage, repo.as_deref(), number, &update, &metadata)?;
+            say!("✅ Updated #{number} {}", edited.html_url.unwrap_or_default());
+        }
+        PrCommands::Fanout { repos, branch, apply, run, title, body, base, draft } => {
+            let repos = fanout::load_repo_list(&repos)?;
+            let change = match (apply, run) {
+                (Some(patch), _) => FanoutChange::Patch(patch),
+                (None, Some(script)) => FanoutChange::Script(script),
+                (None, None) => unreachable!("clap requires --apply or --run"),
+            };
+            let options = FanoutOptions { branch, title, body, base, draft, mode, progress };
+            let results = fanout::fanout(storage, &repos, &change, &options)?;
+            let mut failed = 0;
+            for r in &results {
+                if let Some(error) = &r.error {
+                    failed += 1;
+                    if !progress.is_json() {
+                        esay!("⚠️  {}: {error}", r.repo);
+                    }
+                } else if r.unchanged {
+                    say!("⏭️  {}: unchanged", r.repo);
+                } else if let Some(url) = &r.url {
+                    say!("✅ {}: {url}", r.repo);
+                } else {
+                    say!("🔍 {}: committed {} locally", r.repo, options.branch);
+                }
+            }
+            if failed > 0 {
+                return Err(AppError::github_api(format!(
+                    "{failed} of {} repositories failed",
+                    results.len()
+                )));
+            }
+        }
+        PrCommands::Diff { number, repo, name_only, files, review, no_tool } => {
+            let review_command = match (review, &settings.review_command) {
+                (true, None) => {
+                    return Err(AppError::invalid_input(
+                        "--review needs a review_command (gho config set review_command ...)",
+                    ));
+                }
+                (true, Some(template)) => Some(template),
+                (false, _) => None,
+            };
+            let sections = pr::diff(storage, repo.as_deref(), number, &files)?;
+            if name_only {
+                let names: String = sections.iter().map(|(path, _)| format!("{path}\n")).collect();
+                return pager::page(&names);
+            }
+
+            let diff: String = sections.into_iter().map(|(_, section)| section).collect();
+            let is_terminal = std::io::stdout().is_terminal();
+            match (review_command, &settings.diff_tool) {
+                (Some(template), _) => pager::review_diff(template, &diff, number)?,
+                (None, Some(tool)) if is_terminal && !no_tool => pager::pipe_to(tool, &diff)?,
+                _ if settings.use_color(is_terminal) => pager::page(&domain::colorize_diff(&diff))?,
+                _ => pager::page(&diff)?,
+            }
+        }
+        PrCommands::Timeline { number, repo, json } => {
+            let entries = pr::timeline(storage, repo.as_deref(), number)?;
+            if settings.wants_json(json) {
+                println!("{}", serde_json::to_string_pretty(&entries)?);
+            } else {
+                for e in &entries {
+                    let time = e.time.get(..16).unwrap_or(&e.time).replace('T', " ");
+                    println!("{time}  {:<16} {:<14} {}", e.actor, e.kind, e.summary);
+                }
+            }
+        }
+        PrCommands::View { number, repo, web, json } => {
+            if web {
+                let url = pr::web_url(storage, repo.as_deref(), number)?;
+                browser::open_for(account::show(storage).ok().as_ref(), &url)?;
+                say!("🌐 Opened {url}");
+                return Ok(());
+            }
+
+            let view = pr::view(storage, repo.as_deref(), number)?;
+
+            if settings.wants_json(json) {
+                println!("{}", serde_json::to_string_pretty(&view)?);
+                return Ok(());
+            }
+
+            print_pr_view(&view);
+        }
+        PrCommands::ReadyToMerge { number, repo, require_signed, json } => {
+            let readiness = pr::ready_to_merge(storage, repo.as_deref(), number, require_signed)?;
+            if settings.wants_json(json) {
+                println!("{}", serde_json::to_string_pretty(&readiness)?);
+            } else if readiness.ready {
+                say!("✅ #{number} is ready to merge");
+            } else {
+                for blocker in &readiness.blockers {
+                    esay!("❌ {blocker}");
+                }
+            }
+            if !readiness.ready {
+                return Err(AppError::github_api(format!("#{number} is not ready to merge")));
+            }
+        }
+        PrCommands::Ready { number, repo } => {
+            if pr::set_draft(storage, repo.as_deref(), number, false)? {
+                say!("✅ #{number} is ready for review");
+            } else {
+                say!("⏭️  #{number} is already ready for review");
+            }
+        }
+        PrCommands::Draft { number, repo } => {
+            if pr::set_draft(storage, repo.as_deref(), number, true)? {
+                say!("✅ #{number} is now a draft");
+            } else {
+                say!("⏭️  #{number} is already a draft");
+            }
+        }
+        PrCommands::Close { number, repo, comment, delete_branch } => {
+            match pr::close(
+                storage,
+                repo.as_deref(),
+                number,
+                comment.as_deref(),
+                delete_branch,
+                mode,
+            )? {
+                Execution::Done(change) => {
+                    if let Some(url) = &change.comment_url {
+                        say!("💬 Commented on #{number}: {url}");
+                    }
+                    if change.changed {
+                        say!("✅ Closed #{number}");
+                    } else {
+                        say!("⏭️  #{number} is already closed");
+                    }
+                    if let Some(branch) = &change.deleted_branch {
+                        say!("🗑️  Deleted branch {branch}");
+                    }
+                }
+                Execution::Planned(steps) => print_plan(&steps),
+            }
+        }
+        PrCommands::Reopen { number, repo, comment } => {
+            let change = pr::reopen(storage, repo.as_deref(), number, comment.as_deref())?;
+            if change.changed {
+                say!("✅ Reopened #{number}");
+            } else {
+                say!("⏭️  #{number} is already open");
+            }
+            if let Some(url) = &change.comment_url {
+                say!("💬 Commented on #{number}: {url}");
+            }
+        }
+        PrCommands::Merge { number, repo, method, queue } => {
+            if queue {
+                match pr::enqueue(storage, repo.as_deref(), number, mode)? {
+                    Execution::Done(entry) => say!(
+                        "✅ Queued #{number} at position {} (ETA {})",
+                        entry.position,
+                        queue::format_eta(entry.estimated_time_to_merge)
+                    ),
+                    Execution::Planned(steps) => print_plan(&steps),
+                }
+            } else {
+                match pr::merge(storage, repo.as_deref(), number, method.into(), mode)? {
+                    Execution::Done(result) => {
+                        let sha = result.sha.as_deref().unwrap_or("unknown");
+                        say!("✅ Merged #{number} ({sha})");
+                    }
+                    Execution::Planned(steps) => print_plan(&steps),
+                }
+            }
+        }
+        PrCommands::Review { number, repo, approve, request_changes, comment: _, message } => {
+            let event = if approve {
+                ReviewEvent::Approve
+            } else if request_changes {
+                ReviewEvent::RequestChanges
+            } else {
+                ReviewEvent::Comment
+            };
+            let review = pr::review(storage, repo.as_deref(), number, event, message.as_deref())?;
+            say!("✅ Submitted review on #{number} ({})", review.state);
+        }
+        PrCommands::Checks { number, repo, required_only, watch, interval, json } => {
+            let json = settings.wants_json(json);
+            let watch = watch.then(|| Duration::from_secs(interval.max(1)));
+            let mut seen: HashMap<String, String> = HashMap::new();
+            let checks =
+                pr::checks(storage, repo.as_deref(), number, required_only, watch, |checks| {
+                    for check in checks.iter().filter(|_| !json) {
+                        let state = run::state(&check.status, check.conclusion.as_deref());
+                        if seen.get(&check.name).map(String::as_str) != Some(state) {
+                            println!("{}", pr::format_check(check));
+                            seen.insert(check.name.clone(), state.to_string());
+                        }
+                    }
+                })?;
+            if json {
+                println!("{}", serde_json::to_string(&checks)?);
+            } else if checks.is_empty() {
+                let which = if required_only { "required checks" } else { "checks" };
+                println!("No {which} on #{number}.");
+            }
+            let failed = checks.iter().filter(|c| c.is_failure()).count();
+            if failed > 0 {
+                return Err(AppError::github_api(format!(
+                    "{failed} of {} checks failed on #{number}",
+                    checks.len()
+                )));
+            }
+            let pending = checks.iter().filter(|c| c.is_pending()).count();
+            if pending > 0 {
+                eprintln!("{pending} of {} checks pending on #{number}", checks.len());
+                std::process::exit(exit_code::PENDING);
+            }
+        }
+        PrCommands::Annotations { number, repo, json } => {
+            let annotations = pr::annotations(storage, repo.as_deref(), number)?;
+            print_annotations(&annotations, settings.wants_json(json))?;
+        }
+        PrCommands::RerunChecks { number, repo, failed_jobs_only } => {
+            let runs = pr::rerun_checks(storage, repo.as_deref(), number, failed_jobs_only)?;
+            if runs.is_empty() {
+                println!("No failed workflow runs for #{number}.");
+            }
+            for r in &runs {
+                let state = run::state(&r.status, r.conclusion.as_deref());
+                say!(
+                    "↻ Re-running {} #{} ({})",
+                    r.name.as_deref().unwrap_or("run"),
+                    r.run_number,
+                    style::status(state)
+                );
+            }
+        }
+        PrCommands::Comment { number, repo, message, file } => {
+            let body = match (message, file) {
+                (Some(message), _) => message,
+                (None, Some(path)) => pr::read_body(&path)?,
+                (None, None) if std::io::stdin().is_terminal() => pager::compose(
+                    &settings.editor(),
+                    "comment",
+                    &format!("Comment on #{number}. Lines starting with '#' are ignored."),
+                )?,
+                (None, None) => {
+                    return Err(AppError::invalid_input(
+                        "pass the comment with --message or --file",
+                    ));
+                }
+            };
+            let comment = pr::comment(storage, repo.as_deref(), number, &body)?;
+            say!("✅ Commented on #{number}: {}", comment.html_url);
+        }
+        PrCommands::Comments { number, repo, json } => {
+            let comments = pr::comments(storage, repo.as_deref(), number)?;
+            if settings.wants_json(json) {
+                println!("{}", serde_json::to_string_pretty(&comments)?);
+            } else if comments.is_empty() {
+                println!("No comments on #{number}.");
+            } else {
+                for c in &comments {
+                    let indent = if c.in_reply_to.is_some() { "    " } else { "" };
+                    let time = c.created_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M");
+                    let location =
+                        c.location.as_deref().map(|l| format!(" on {l}")).unwrap_or_default();
+                    println!("{indent}{time}  {}{location}", c.author);
+                    for line in c.body.lines() {
+                        println!("{indent}    {line}");
+                    }
+                }
+            }
+        }
+        PrCommands::Reviews { number, repo, json } => {
+            let reviews = pr::reviews(storage, repo.as_deref(), number)?;
 
-impl From<ProtocolArg> for Protocol {
-    fn from(arg: ProtocolArg) -> Self {
-        match arg {
-            ProtocolArg::Ssh => Protocol::Ssh,
-            ProtocolArg::Https => Protocol::Https,
+            if settings.wants_json(json) {
+                for r in reviews {
+                    println!("{}", serde_json::to_string(&r)?);
+                }
+            } else if reviews.is_empty() {
+                println!("No reviews on #{number}.");
+            } else {
+                for r in reviews {
+                    let submitted = r.submitted_at.as_deref().unwrap_or("-");
+                    println!("{} {} {}", r.user.login, r.state, submitted);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn run_run_command(
+    storage: &FilesystemStorage,
+    settings: &Settings,
+    command: RunCommands,
+) -> Result<(), AppError> {
+    match command {
+        RunCommands::List { repo, branch, limit, json } => {
+            let limit = limit.unwrap_or(settings.limit);
+            let runs = run::list(storage, repo.as_deref(), branch.as_deref(), limit)?;
+            if settings.wants_json(json) {
+                println!("{}", serde_json::to_string_pretty(&runs)?);
+            } else {
+                for r in &runs {
+                    println!(
+                        "{:<12} {} {:<24} {:<20} {}",
+                        r.id,
+                        style::status(&format!(
+                            "{:<12}",
+                            run::state(&r.status, r.conclusion.as_deref())
+                        )),
+                        r.name.as_deref().unwrap_or("-"),
+                        r.head_branch.as_deref().unwrap_or("-"),
+                        r.display_title
+                    );
+                }
+            }
+        }
+        RunCommands::Watch { id, repo, interval } => {
+            let interval = Duration::from_secs(interval.max(1));
+            watch_run(storage, repo.as_deref(), id, interval)?;
+        }
+        RunCommands::Annotations { id, repo, json } => {
+            let annotations = run::annotations(storage, repo.as_deref(), id)?;
+            print_annotations(&annotations, settings.wants_json(json))?;
+        }
+        RunCommands::Logs { id, repo, job, failed, follow, interval } => {
+            let filter = run::LogFilter { job: job.as_deref(), failed };
+            let follow = follow.then(|| Duration::from_secs(interval.max(1)));
+            let running = run::logs(storage, repo.as_deref(), id, filter, follow, |log| {
+                for step in &log.steps {
+                    let conclusion = step.conclusion.as_deref().unwrap_or_default();
+                    say!("▶ {} › {} {}", log.job.name, step.name, style::status(conclusion));
+                    for line in &step.lines {
+                        println!("{line}");
+                    }
+                }
+            })?;
+            if !running.is_empty() {
+                esay!(
+                    "⏳ Still running, so no logs yet: {} (use --follow to wait)",
+                    running.join(", ")
+                );
+            }
+        }
+        RunCommands::Download { id, repo, name, dir } => {
+            let results = run::download(storage, repo.as_deref(), id, name.as_deref(), &dir)?;
+            let mut failed = 0;
+            for r in &results {
+                match &r.error {
+                    Some(error) => {
+                        failed += 1;
+                        esay!("⚠️  {}: {error}", r.name);
+                    }
+                    None => {
+                        say!("✅ Extracted {} ({} files) to {}", r.name, r.files, r.path.display())
+                    }
+                }
+            }
+            if failed > 0 {
+                return Err(AppError::github_api(format!(
+                    "{failed} of {} artifacts failed to download",
+                    results.len()
+                )));
+            }
+        }
+        RunCommands::Dispatch { workflow, repo, git_ref, inputs, watch, interval } => {
+            let interval = Duration::from_secs(interval.max(1));
+            let dispatch = run::dispatch(
+                storage,
+                repo.as_deref(),
+                &workflow,
+                git_ref.as_deref(),
+                &inputs,
+                interval,
+            )?;
+            let Some(r) = dispatch.run else {
+                say!("▶ Dispatched {workflow} on {}", dispatch.git_ref);
+                esay!("⚠️  The started run did not show up yet; see `gho run list`");
+                return Ok(());
+            };
+            if watch {
+                say!("▶ Dispatched {workflow} on {}", dispatch.git_ref);
+                watch_run(storage, repo.as_deref(), r.id, interval)?;
+            } else {
+                say!(
+                    "▶ Dispatched {workflow} on {}: {} #{}: {}",
+                    dispatch.git_ref,
+                    r.name.as_deref().unwrap_or("run"),
+                    r.run_number,
+                    r.html_url
+                );
+            }
+        }
+        RunCommands::Rerun { id, repo, failed_jobs_only } => {
+            let r = run::rerun(storage, repo.as_deref(), id, failed_jobs_only)?;
+            let jobs = if failed_jobs_only { "failed jobs of " } else { "" };
+            say!(
+                "↻ Re-running {jobs}{} #{}: {}",
+                r.name.as_deref().unwrap_or("run"),
+                r.run_number,
+                r.html_url
+            );
+        }
+    }
+    Ok(())
+}
+
+fn run_package_command(
+    storage: &FilesystemStorage,
+    settings: &Settings,
+    mode: ExecutionMode,
+    progress: Progress,
+    command: PackageCommands,
+) -> Result<(), AppError> {
+    match command {
+        PackageCommands::List { org, package_type, json } => {
+            let org = org::resolve(storage, org.as_deref(), true)?;
+            let packages = package::list(storage, org.as_deref(), package_type.into())?;
+            if settings.wants_json(json) {
+                println!("{}", serde_json::to_string_pretty(&packages)?);
+            } else {
+                for p in &packages {
+                    println!(
+                        "{:<40} {:<10} {:>6} versions  updated {}",
+                        p.name,
+                        p.visibility,
+                        p.version_count,
+                        p.updated_at.format("%Y-%m-%d")
+                    );
+                }
+            }
+        }
+        PackageCommands::Versions { name, org, package_type, json } => {
+            let org = org::resolve(storage, org.as_deref(), true)?;
+            let versions = package::versions(storage, org.as_deref(), package_type.into(), &name)?;
+            if settings.wants_json(json) {
+                println!("{}", serde_json::to_string_pretty(&versions)?);
+            } else {
+                for v in &versions {
+                    let tags = v.tags().join(",");
+                    println!(
+                        "{:<12} {:<20} {:<72} {}",
+                        v.id,
+                        v.updated_at.format("%Y-%m-%d %H:%M"),
+                        v.name,
+                        if tags.is_empty() { "(untagged)" } else { &tags }
+                    );
+                }
+            }
+        }
+        PackageCommands::Delete { name, version, org, package_type } => {
+            let org = org::resolve(storage, org.as_deref(), true)?;
+            package::delete(storage, org.as_deref(), package_type.into(), &name, version)?;
+            match version {
+                Some(id) => say!("✅ Deleted version {id} of {name}"),
+                None => say!("✅ Deleted package {name}"),
+            }
+        }
+        PackageCommands::Prune { name, older_than, org } => {
+            let org = org::resolve(storage, org.as_deref(), true)?;
+            let result =
+                package::prune(storage, org.as_deref(), &name, older_than, mode, progress)?;
+            let verb = if mode.is_dry_run() { "Would delete" } else { "Deleted" };
+            for v in &result.deleted {
+                println!("{verb} {} {} ({})", v.id, v.name, v.updated_at.format("%Y-%m-%d"));
+            }
+            if !progress.is_json() {
+                for (v, e) in &result.failed {
+                    esay!("⚠️  {} {}: {e}", v.id, v.name);
+                }
+            }
+            println!("{verb} {} untagged versions of {name}", result.deleted.len());
+            if !result.failed.is_empty() {
+                return Err(AppError::github_api(format!(
+                    "{} versions failed to delete",
+                    result.failed.len()
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn run_runner_command(
+    storage: &FilesystemStorage,
+    settings: &Settings,
+    command: RunnerCommands,
+) -> Result<(), AppError> {
+    match command {
+        RunnerCommands::List { org, json } => {
+            let runners = runner::list(storage, &org)?;
+            if settings.wants_json(json) {
+                println!("{}", serde_json::to_string_pretty(&runners)?);
+            } else if runners.is_empty() {
+                println!("No self-hosted runners in {org}");
+            } else {
+                for r in &runners {
+                    let state = if r.busy { "busy" } else { r.status.as_str() };
+                    let labels: Vec<&str> = r.labels.iter().map(|l| l.name.as_str()).collect();
+                    println!(
+                        "{:<8} {:<30} {:<8} {:<8} {}",
+                        r.id,
+                        r.name,
+                        r.os,
+                        state,
+                        labels.join(",")
+                    );
+                }
+            }
+        }
+        RunnerCommands::Remove { runner: name, org } => {
+            let removed = runner::remove(storage, &org, &name)?;
+            say!("✅ Removed runner {} ({}) from {org}", removed.name, removed.id);
+        }
+        RunnerCommands::RegistrationToken { org, json } => {
+            let token = runner::registration_token(storage, &org)?;
+            if settings.wants_json(json) {
+                println!("{}", serde_json::to_string_pretty(&token)?);
+            } else {
+                println!("{}", token.token);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn run_actions_command(
+    storage: &FilesystemStorage,
+    settings: &Settings,
+    command: ActionsCommands,
+) -> Result<(), AppError> {
+    match command {
+        ActionsCommands::Usage { org, since, csv, json } => {
+            let rows = actions::usage(storage, &org, since.as_deref())?;
+            if csv {
+                print!("{}", actions::to_csv(&rows));
+            } else if settings.wants_json(json) {
+                println!("{}", serde_json::to_string_pretty(&rows)?);
+            } else if rows.is_empty() {
+                println!("No Actions usage found for {org}");
+            } else {
+                println!("{:<40} {:>10} {:>12} {:>10}", "REPO", "MINUTES", "STORAGE GBh", "COST $");
+                for r in &rows {
+                    println!(
+                        "{:<40} {:>10.0} {:>12.2} {:>10.2}",
+                        r.repo, r.minutes, r.storage_gb_hours, r.net_amount
+                    );
+                }
+                let total: f64 = rows.iter().map(|r| r.net_amount).sum();
+                println!("Total: ${total:.2}");
+            }
+        }
+    }
+    Ok(())
+}
+
+fn run_workflow_command(
+    storage: &FilesystemStorage,
+    command: WorkflowCommands,
+) -> Result<(), AppError> {
+    match command {
+        WorkflowCommands::Lint { path, repo, no_remote } => {
+            let mut reports = workflow_cmd::lint(path.as_deref())?;
+            if !no_remote {
+                workflow_cmd::check_remote(storage, repo.as_deref(), &mut reports)?;
+            }
+
+            let mut errors = 0;
+            for file in &reports {
+                for d in &file.report.diagnostics {
+                    let location = match d.line {
+                        Some(line) => format!("{}:{line}", file.path.display()),
+                        None => file.path.display().to_string(),
+                    };
+                    println!("{location}: {}: {}", d.level, d.message);
+                }
+                if file.report.has_errors() {
+                    errors += 1;
+                }
+            }
+
+            if errors > 0 {
+                return Err(AppError::invalid_input(format!(
+                    "{errors} of {} workflow files have errors",
+                    reports.len()
+                )));
+            }
+            say!("✅ {} workflow files OK", reports.len());
+        }
+    }
+    Ok(())
+}
+
+fn run_release_command(
+    storage: &FilesystemStorage,
+    settings: &Settings,
+    command: ReleaseCommands,
+) -> Result<(), AppError> {
+    match command {
+        ReleaseCommands::List { repo, limit, json } => {
+            let limit = limit.unwrap_or(settings.limit);
+            let releases = release::list(storage, repo.as_deref(), limit)?;
+            if settings.wants_json(json) {
+                println!("{}", serde_json::to_string_pretty(&releases)?);
+            } else {
+                for r in &releases {
+                    let kind = if r.draft {
+                        "draft"
+                    } else if r.prerelease {
+                        "prerelease"
+                    } else {
+                        "release"
+                    };
+                    println!(
+                        "{:<20} {:<10} {:<12} {}",
+                        r.tag_name,
+                        kind,
+                        r.published_at.as_deref().and_then(|d| d.get(..10)).unwrap_or("-"),
+                        r.name.as_deref().unwrap_or("")
+                    );
+                }
+            }
+        }
+        ReleaseCommands::Create { tag, repo, title, notes, notes_from_tag, draft, prerelease } => {
+            let notes = match notes {
+                Some(text) => ReleaseNotes::Text(text),
+                None if notes_from_tag => ReleaseNotes::FromTag,
+                None => ReleaseNotes::Generated,
+            };
+            let options = CreateOptions { title, notes, draft, prerelease };
+            let created = release::create(storage, repo.as_deref(), &tag, &options)?;
+            say!(
+                "✅ Created {} {}",
+                if created.draft { "draft" } else { "release" },
+                created.html_url
+            );
+        }
+        ReleaseCommands::Download { tag, repo, pattern, dir, clobber } => {
+            let results = release::download(
+                storage,
+                repo.as_deref(),
+                &tag,
+                pattern.as_deref(),
+                &dir,
+                clobber,
+            )?;
+
+            let mut failed = 0;
+            for r in &results {
+                match &r.error {
+                    Some(error) => {
+                        failed += 1;
+                        esay!("⚠️  {}: {error}", r.name);
+                    }
+                    None if r.skipped => {
+                        say!("⏭️  {} exists (use --clobber to overwrite)", r.path.display())
+                    }
+                    None => say!("✅ Downloaded {}", r.path.display()),
+                }
+            }
+            if failed > 0 {
+                return Err(AppError::github_api(format!(
+                    "{failed} of {} assets failed to download",
+                    results.len()
+                )));
+            }
+        }
+        ReleaseCommands::Upload {
+            tag,
+            files,
+            repo,
+            clobber,
+            generate_checksums,
+            jobs,
+            retries,
+        } => {
+            let options = UploadOptions { clobber, generate_checksums, jobs, retries };
+            let results = release::upload(storage, repo.as_deref(), &tag, &files, &options)?;
+
+            let mut failed = 0;
+            for r in &results {
+                match &r.error {
+                    Some(error) => {
+                        failed += 1;
+                        esay!("⚠️  {}: {error}", r.name);
+                    }
+                    None => say!("✅ Uploaded {}", r.url.as_deref().unwrap_or(&r.name)),
+                }
+            }
+            if failed > 0 {
+                return Err(AppError::github_api(format!(
+                    "{failed} of {} assets failed to upload",
+                    results.len()
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn run_daemon_command(
+    storage: &FilesystemStorage,
+    config: &Config,
+    command: DaemonCommands,
+) -> Result<(), AppError> {
+    match command {
+        DaemonCommands::Start { interval } => {
+            let pid = daemon::start(config, interval)?;
+            say!("✅ Started daemon (pid {pid}), refreshing every {interval}s");
+            println!("   Snapshots: {}", config.cache_path().display());
+        }
+        DaemonCommands::Stop => {
+            let pid = daemon::stop(config)?;
+            say!("✅ Stopped daemon (pid {pid})");
+        }
+        DaemonCommands::Status { json } => {
+            let status = daemon::status(config)?;
+            if config.settings.wants_json(json) {
+                println!("{}", serde_json::to_string_pretty(&status)?);
+                return Ok(());
+            }
+            match status.pid {
+                Some(pid) => println!("running (pid {pid})"),
+                None => println!("stopped"),
+            }
+            let now = Utc::now();
+            for s in &status.snapshots {
+                match s.refreshed_at {
+                    Some(at) => {
+                        println!("  {:<14} {} min ago", s.name, (now - at).num_minutes())
+                    }
+                    None => println!("  {:<14} never", s.name),
+                }
+            }
+        }
+        DaemonCommands::Run { interval } => daemon::run(storage, config, interval)?,
+    }
+    Ok(())
+}
+
+fn run_api_command(
+    storage: &FilesystemStorage,
+    settings: &Settings,
+    command: ApiCommands,
+) -> Result<(), AppError> {
+    match command {
+        ApiCommands::RateLimit { all_accounts, json } => {
+            let reports = api::rate_limit(storage, all_accounts)?;
+            if settings.wants_json(json) {
+                println!("{}", serde_json::to_string_pretty(&reports)?);
+                return Ok(());
+            }
+
+            let now = Utc::now();
+            for report in &reports {
+                println!("{}", report.account);
+                if let Some(error) = &report.error {
+                    esay!("  ⚠️  {error}");
+                    continue;
+                }
+                for (name, limit) in &report.resources {
+                    println!(
+                        "  {:<8} {:>6} / {:<6} resets {}",
+                        name,
+                        limit.remaining,
+                        limit.limit,
+                        api::format_reset(limit.reset, now)
+                    );
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn run_config_command(config: &mut Config, command: ConfigCommands) -> Result<(), AppError> {
+    match command {
+        ConfigCommands::Get { key } => {
+            println!("{}", config_cmd::get(config, &key)?);
+        }
+        ConfigCommands::Set { key, value } => {
+            config_cmd::set(config, &key, &value)?;
+            say!("✅ Set {key} = {}", config_cmd::get(config, &key)?);
+        }
+        ConfigCommands::List => {
+            for (key, value) in config_cmd::list(config) {
+                println!("{key} = {value}");
+            }
+        }
+    }
+    Ok(())
+}
+
+fn run_access_command(
+    storage: &FilesystemStorage,
+    settings: &Settings,
+    command: AccessCommands,
+) -> Result<(), AppError> {
+    match command {
+        AccessCommands::Diff { repo, json } => {
+            let entries = access::diff(storage, repo.as_deref())?;
+            for entry in entries {
+                if settings.wants_json(json) {
+                    println!("{}", serde_json::to_string(&entry)?);
+                    continue;
+                }
+                let access = entry.item;
+                let permission = match (access.permission, access.error) {
+                    (Some(permission), _) => permission.to_string(),
+                    (None, error) => format!("⚠️ {}", error.unwrap_or_default()),
+                };
+                println!("{:<16} {:<20} {}", entry.account, access.username, permission);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn run_workspace_command(
+    storage: &FilesystemStorage,
+    mode: ExecutionMode,
+    progress: Progress,
+    command: WorkspaceCommands,
+) -> Result<(), AppError> {
+    match command {
+        WorkspaceCommands::Discover { org, topic, limit, file } => {
+            let discovery =
+                workspace::discover(storage, &file, org.as_deref(), topic.as_deref(), limit)?;
+            for repo in &discovery.added {
+                println!("+ {repo}");
+            }
+            for repo in &discovery.removed {
+                println!("- {repo}");
+            }
+            say!("✅ Wrote {} ({} repositories)", file.display(), discovery.total);
+        }
+        WorkspaceCommands::Sync { file, manifest } => {
+            let (checkouts, manifest) =
+                match workspace::sync(storage, &file, manifest.as_deref(), mode, progress)? {
+                    Execution::Done(done) => done,
+                    Execution::Planned(steps) => {
+                        print_plan(&steps);
+                        return Ok(());
+                    }
+                };
+
+            let mut failed = 0;
+            for c in &checkouts {
+                if let Some(error) = &c.error {
+                    failed += 1;
+                    if !progress.is_json() {
+                        esay!("⚠️  {}: {error}", c.repo);
+                    }
+                } else if c.cloned {
+                    say!("✅ {}: cloned into {}", c.repo, c.dir.display());
+                } else {
+                    say!("⏭️  {}: already checked out", c.repo);
+                }
+            }
+            say!("📝 Wrote {}", manifest.display());
+            if failed > 0 {
+                return Err(AppError::git(format!(
+                    "{failed} of {} repositories failed",
+                    checkouts.len()
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_contributions(
+    settings: &Settings,
+    entries: &[AccountScoped<Contributions>],
+    json: bool,
+) -> Result<(), AppError> {
+    for entry in entries {
+        if settings.wants_json(json) {
+            println!("{}", serde_json::to_string(entry)?);
+            continue;
+        }
+        let c = &entry.item;
+        let Some(counts) = &c.counts else {
+            esay!("⚠️  {} ({}): {}", entry.account, c.username, c.error.as_deref().unwrap_or(""));
+            continue;
+        };
+        println!(
+            "{} ({}) since {}: {} commits, {} pull requests, {} reviews, {} issues",
+            entry.account,
+            c.username,
+            c.since,
+            counts.commits,
+            counts.pull_requests,
+            counts.reviews,
+            counts.issues
+        );
+        if counts.private > 0 {
+            println!("  + {} in private repositories", counts.private);
+        }
+        for r in &counts.repositories {
+            println!("  {:<40} {} commits", r.repo, r.commits);
         }
     }
+    Ok(())
 }
 
-fn main() {
-    let cli = Cli::parse();
-
-    let result: Result<(), AppError> = run(cli);
-
-    if let Err(e) = result {
-        eprintln!("Error: {e}");
-        std::process::exit(1);
+fn run_branch_command(
+    storage: &FilesystemStorage,
+    settings: &Settings,
+    mode: ExecutionMode,
+    progress: Progress,
+    command: BranchCommands,
+) -> Result<(), AppError> {
+    match command {
+        BranchCommands::List { repo, merged, stale, json } => {
+            let stale = stale.as_deref().map(branch::parse_age).transpose()?;
+            let filter = BranchFilter { merged, stale };
+            let branches = branch::list(storage, repo.as_deref(), filter)?;
+            output::present(&branches, settings.wants_json(json))?;
+        }
+        BranchCommands::Delete { name: Some(name), repo, .. } => {
+            match branch::delete(storage, repo.as_deref(), &name, mode)? {
+                Execution::Done(()) => say!("🗑️  Deleted branch {name}"),
+                Execution::Planned(steps) => print_plan(&steps),
+            }
+        }
+        BranchCommands::Delete { name: None, repo, stale, .. } => {
+            let stale = stale.as_deref().map(branch::parse_age).transpose()?;
+            let result = branch::delete_merged(storage, repo.as_deref(), stale, mode, progress)?;
+            let verb = if mode.is_dry_run() { "Would delete" } else { "Deleted" };
+            for b in &result.deleted {
+                let date =
+                    b.committed_at.map_or("-".to_string(), |at| at.format("%Y-%m-%d").to_string());
+                println!("{verb} {} ({date})", b.name);
+            }
+            if !progress.is_json() {
+                for (b, e) in &result.failed {
+                    esay!("⚠️  {}: {e}", b.name);
+                }
+            }
+            println!("{verb} {} merged branches", result.deleted.len());
+            if !result.failed.is_empty() {
+                return Err(AppError::github_api(format!(
+                    "{} branches failed to delete",
+                    result.failed.len()
+                )));
+            }
+        }
+        BranchCommands::Default { name, repo } => {
+            if branch::set_default(storage, repo.as_deref(), &name)? {
+                say!("✅ Default branch is now {name}");
+            } else {
+                say!("⏭️  {name} already is the default branch");
+            }
+        }
     }
+    Ok(())
 }
 
-fn run(cli: Cli) -> Result<(), AppError> {
-    let storage = FilesystemStorage::new_default()?;
-
-    match cli.command {
-        Commands::Account { command } => run_account_command(&storage, command),
-        Commands::Repo { command } => run_repo_command(&storage, command),
-        Commands::Pr { command } => run_pr_command(&storage, command),
+fn run_org_command(
+    storage: &FilesystemStorage,
+    settings: &Settings,
+    command: OrgCommands,
+) -> Result<(), AppError> {
+    match command {
+        OrgCommands::List { json } => {
+            for o in org::list(storage)? {
+                if settings.wants_json(json) {
+                    println!("{}", serde_json::to_string(&o)?);
+                } else {
+                    println!("{:<24} {}", o.login, o.description.as_deref().unwrap_or(""));
+                }
+            }
+        }
+        OrgCommands::Members { org: name, role, json } => {
+            for m in org::members(storage, &name, role.map(Into::into))? {
+                if settings.wants_json(json) {
+                    println!("{}", serde_json::to_string(&m)?);
+                } else {
+                    println!("{:<24} {}", m.login, m.role.as_str());
+                }
+            }
+        }
+        OrgCommands::Teams { org: name, json } => {
+            for t in org::teams(storage, &name)? {
+                if settings.wants_json(json) {
+                    println!("{}", serde_json::to_string(&t)?);
+                } else {
+                    println!(
+                        "{:<24} {:<8} {}",
+                        t.slug,
+                        t.privacy.as_deref().unwrap_or("-"),
+                        t.description.as_deref().unwrap_or("")
+                    );
+                }
+            }
+        }
+        OrgCommands::Team { command: TeamCommands::Repos { team, json } } => {
+            for r in org::team_repos(storage, &team)? {
+                if settings.wants_json(json) {
+                    println!("{}", serde_json::to_string(&r)?);
+                } else {
+                    println!("{:<40} {}", r.full_name, r.permission);
+                }
+            }
+        }
     }
+    Ok(())
 }
 
-fn run_account_command(
+fn run_gist_command(
     storage: &FilesystemStorage,
-    command: AccountCommands,
+    settings: &Settings,
+    command: GistCommands,
 ) -> Result<(), AppError> {
     match command {
-        AccountCommands::Add { id, username, kind, token, default_org, protocol, clone_dir } => {
-            account::add(
-                storage,
-                &id,
-                &username,
-                kind.into(),
-                &token,
-                default_org,
-                protocol.into(),
-                clone_dir,
-            )?;
-            println!("✅ Added account '{id}'");
+        GistCommands::Create { files, public, description } => {
+            let gist = gist::create(storage, &files, public, description.as_deref())?;
+            println!("{}", gist.html_url);
         }
-        AccountCommands::List => {
-            let accounts = account::list(storage)?;
-            let all = accounts.all_accounts();
+        GistCommands::List { limit, json } => {
+            let gists = gist::list(storage, limit.unwrap_or(settings.limit))?;
+            if settings.wants_json(json) {
+                for g in &gists {
+                    println!("{}", serde_json::to_string(g)?);
+                }
+            } else if gists.is_empty() {
+                println!("No gists found.");
+            } else {
+                for g in &gists {
+                    let files: Vec<&str> = g.files.keys().map(String::as_str).collect();
+                    println!(
+                        "{:<34} {:<7} {} {}",
+                        g.id,
+                        if g.public { "public" } else { "secret" },
+                        g.description.as_deref().filter(|d| !d.is_empty()).unwrap_or("-"),
+                        files.join(", ")
+                    );
+                }
+            }
+        }
+        GistCommands::Clone { id } => {
+            let dir = gist::clone(storage, &id)?;
+            say!("✅ Cloned gist {id} into {}", dir.display());
+        }
+    }
+    Ok(())
+}
 
-            if all.is_empty() {
-                println!("No accounts configured.");
+fn run_issue_command(
+    storage: &FilesystemStorage,
+    settings: &Settings,
+    command: IssueCommands,
+) -> Result<(), AppError> {
+    match command {
+        IssueCommands::Triage { repo, limit } => {
+            let limit = limit.unwrap_or(settings.limit);
+            let summary = issue::triage(storage, repo.as_deref(), limit)?;
+            if summary.reviewed == 0 {
+                say!("✅ No issues need triage");
                 return Ok(());
             }
-
-            println!("📋 Accounts:");
-            for acc in all {
-                let active = accounts.active_account_id.as_deref() == Some(&acc.id);
-                let marker = if active { " (active)" } else { "" };
-                println!(
-                    "  {} ({}) - {} [{}]{}",
-                    acc.id, acc.kind, acc.username, acc.protocol, marker
-                );
+            say!(
+                "✅ Triaged {} issues: {} updated, {} closed",
+                summary.reviewed,
+                summary.updated,
+                summary.closed
+            );
+            if summary.remaining > 0 {
+                say!("{} more left for next time", summary.remaining);
             }
         }
-        AccountCommands::Use { id } => {
-            let selected = match id {
-                Some(id) => {
-                    account::switch(storage, &id)?;
-                    id
+    }
+    Ok(())
+}
+
+fn run_notify_command(
+    storage: &FilesystemStorage,
+    settings: &Settings,
+    command: NotifyCommands,
+) -> Result<(), AppError> {
+    match command {
+        NotifyCommands::List { unread, participating, limit, watch, json } => {
+            let filter = NotificationFilter { unread, participating };
+            let limit = limit.unwrap_or(settings.limit);
+            if watch {
+                let mut seen: HashMap<String, String> = HashMap::new();
+                return notify::watch(storage, filter, limit, |notifications| {
+                    for n in notifications.iter().rev() {
+                        if seen.get(&n.id) != Some(&n.updated_at) {
+                            println!("{}", format_notification(n));
+                            seen.insert(n.id.clone(), n.updated_at.clone());
+                        }
+                    }
+                });
+            }
+
+            let notifications = notify::list(storage, filter, limit)?;
+            if settings.wants_json(json) {
+                for n in &notifications {
+                    println!("{}", serde_json::to_string(n)?);
                 }
-                None => account::switch_interactive(storage)?,
+            } else if notifications.is_empty() {
+                println!("No notifications.");
+            } else {
+                for n in &notifications {
+                    println!("{}", format_notification(n));
+                }
+            }
+        }
+        NotifyCommands::Read { id, all: _ } => {
+            notify::mark_read(storage, id.as_deref())?;
+            match id {
+                Some(id) => say!("✅ Marked thread {id} as read"),
+                None => say!("✅ Marked all notifications as read"),
+            }
+        }
+        NotifyCommands::Open { id, print } => {
+            let url = notify::web_url(storage, &id)?;
+            if print {
+                println!("{url}");
+            } else {
+                let active = account::show(storage).ok();
+                browser::open_for(active.as_ref(), &url)?;
+                say!("🌐 Opened {url}");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// One line per thread: unread marker, id, repository, subject type and title, reason.
+fn format_notification(n: &Notification) -> String {
+    let marker = if n.unread { "●" } else { " " };
+    format!(
+        "{marker} {:<12} {:<30} {:<12} {} ({})",
+        n.id, n.repository.full_name, n.subject.kind, n.subject.title, n.reason
+    )
+}
+
+fn run_history_command(config: &Config, command: HistoryCommands) -> Result<(), AppError> {
+    let entries = match command {
+        HistoryCommands::List { limit } => {
+            let all = history::entries(config)?;
+            let skip = all.len().saturating_sub(limit.unwrap_or(config.settings.limit));
+            all.into_iter().enumerate().skip(skip).map(|(i, e)| (i + 1, e)).collect()
+        }
+        HistoryCommands::Search { term } => history::search(config, &term)?,
+    };
+    for (n, entry) in entries {
+        println!(
+            "{n:>5}  {}  {:<12} {:<16} gho {}",
+            entry.at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M"),
+            entry.account.as_deref().unwrap_or("-"),
+            entry.token_source.map_or_else(|| "-".to_string(), |s| s.to_string()),
+            domain::shell_join(&entry.args)
+        );
+    }
+    Ok(())
+}
+
+fn run_recent_command(
+    storage: &FilesystemStorage,
+    settings: &Settings,
+    select: bool,
+    json: bool,
+) -> Result<(), AppError> {
+    if select {
+        println!("{}", recent::select(storage)?);
+        return Ok(());
+    }
+    let history = recent::history(storage)?;
+    if settings.wants_json(json) {
+        println!("{}", serde_json::to_string(&history)?);
+        return Ok(());
+    }
+    if history.repos.is_empty() {
+        println!("No recently used repositories.");
+    }
+    for (i, repo) in history.repos.iter().enumerate() {
+        let marker = if i == 0 { " (-)" } else { "" };
+        println!("{repo}{marker}");
+    }
+    if let Some(org) = history.last_org {
+        println!("Last organization: {org}");
+    }
+    Ok(())
+}
+
+fn run_doctor_command(
+    storage: &FilesystemStorage,
+    settings: &Settings,
+    json: bool,
+) -> Result<(), AppError> {
+    let checks = doctor::run(storage)?;
+    if settings.wants_json(json) {
+        println!("{}", serde_json::to_string(&checks)?);
+    } else {
+        for c in &checks {
+            let icon = match c.status {
+                DoctorStatus::Ok => "✅",
+                DoctorStatus::Warning => "⚠️ ",
+                DoctorStatus::Failed => "❌",
             };
-            println!("✅ Switched to account '{selected}'");
+            say!("{icon} {}: {}", c.check, c.detail);
         }
-        AccountCommands::Show => {
-            let acc = account::show(storage)?;
-            let token = keychain::get_token(&acc.id).unwrap_or_else(|_| "(not found)".to_string());
-            let masked = keychain::mask_token(&token);
+    }
 
-            println!("🔑 Active account:");
-            println!("  ID:       {}", acc.id);
-            println!("  Kind:     {}", acc.kind);
-            println!("  Username: {}", acc.username);
-            println!("  Protocol: {}", acc.protocol);
-            println!("  Token:    {}", masked);
-            if let Some(org) = &acc.default_org {
-                println!("  Org:      {}", org);
+    let failed = checks.iter().filter(|c| c.status == DoctorStatus::Failed).count();
+    if failed > 0 {
+        return Err(AppError::network(format!("{failed} of {} checks failed", checks.len())));
+    }
+    Ok(())
+}
+
+/// Follow run `id`, printing each job as its state changes; fails unless the run succeeds.
+fn watch_run(
+    storage: &FilesystemStorage,
+    repo: Option<&str>,
+    id: u64,
+    interval: Duration,
+) -> Result<(), AppError> {
+    let mut seen: HashMap<u64, String> = HashMap::new();
+    let mut first_poll = true;
+    let finished = run::watch(storage, repo, id, interval, |r, jobs| {
+        if std::mem::take(&mut first_poll) {
+            println!("{} #{}: {}", r.name.as_deref().unwrap_or("run"), r.run_number, r.html_url);
+        }
+        for job in jobs {
+            let state = run::state(&job.status, job.conclusion.as_deref());
+            if seen.get(&job.id).map(String::as_str) != Some(state) {
+                println!("  {} {}", style::status(&format!("{state:<12}")), job.name);
+                seen.insert(job.id, state.to_string());
             }
-            if let Some(dir) = &acc.clone_dir {
-                println!("  Clone:    {}", dir);
+        }
+    })?;
+
+    let conclusion = finished.conclusion.as_deref().unwrap_or("unknown");
+    if !run::is_success(finished.conclusion.as_deref()) {
+        return Err(AppError::github_api(format!("run {id} concluded with {conclusion}")));
+    }
+    say!("✅ Run {id} {conclusion}");
+    Ok(())
+}
+
+fn print_annotations(annotations: &[CheckRunAnnotation], json: bool) -> Result<(), AppError> {
+    for a in annotations {
+        if json {
+            println!("{}", serde_json::to_string(a)?);
+        } else {
+            println!("{}", run::format_annotation(a));
+        }
+    }
+    Ok(())
+}
+
+fn run_commit_command(
+    storage: &FilesystemStorage,
+    settings: &Settings,
+    command: CommitCommands,
+) -> Result<(), AppError> {
+    match command {
+        CommitCommands::View { reference, repo, json } => {
+            let c = commit::view(storage, repo.as_deref(), &reference)?;
+            if settings.wants_json(json) {
+                println!("{}", serde_json::to_string_pretty(&c)?);
+                return Ok(());
+            }
+            println!("{} {}", c.sha, c.subject);
+            println!("  Author:    {}", c.author);
+            if let Some(date) = &c.date {
+                println!("  Date:      {date}");
             }
+            println!("  Signature: {}", signature_label(c.verified, &c.reason));
+            if let Some(url) = &c.url {
+                println!("  URL:       {url}");
+            }
+        }
+    }
+    Ok(())
+}
+
+fn signature_label(verified: bool, reason: &str) -> String {
+    if verified { "✅ verified".to_string() } else { format!("⚠️  unverified ({reason})") }
+}
+
+fn run_hook_command(
+    storage: &FilesystemStorage,
+    settings: &Settings,
+    command: HookCommands,
+) -> Result<(), AppError> {
+    match command {
+        HookCommands::List { repo, json } => {
+            output::present(&hook::list(storage, repo.as_deref())?, settings.wants_json(json))?;
+        }
+        HookCommands::Create {
+            repo,
+            url,
+            content_type,
+            events,
+            secret,
+            secret_keychain,
+            insecure_ssl,
+            inactive,
+        } => {
+            let secret = match (&secret_keychain, secret) {
+                (Some(name), _) => SecretSource::Keychain(name),
+                (None, true) => SecretSource::Prompt,
+                (None, false) => SecretSource::None,
+            };
+            let options = HookOptions {
+                url,
+                content_type: content_type.into(),
+                events,
+                secret,
+                insecure_ssl,
+                active: !inactive,
+            };
+            let created = hook::create(storage, repo.as_deref(), options)?;
+            say!("✅ Created webhook {} for {}", created.id, created.events.join(", "));
         }
-        AccountCommands::Remove { id } => {
-            account::remove(storage, &id)?;
-            println!("🗑️  Removed account '{id}'");
+        HookCommands::Delete { id, repo } => {
+            hook::delete(storage, repo.as_deref(), id)?;
+            say!("🗑️  Deleted webhook {id}");
+        }
+        HookCommands::Ping { id, repo } => {
+            hook::ping(storage, repo.as_deref(), id)?;
+            say!("📡 Pinged webhook {id}; see `gho hook deliveries {id}` for the response");
+        }
+        HookCommands::Deliveries { id, repo, limit, json } => {
+            let deliveries = hook::deliveries(storage, repo.as_deref(), id, limit)?;
+            output::present(&deliveries, settings.wants_json(json))?;
         }
     }
     Ok(())
 }
 
-fn run_repo_command(storage: &FilesystemStorage, command: RepoCommands) -> Result<(), AppError> {
+fn run_secret_command(
+    storage: &FilesystemStorage,
+    settings: &Settings,
+    command: SecretCommands,
+) -> Result<(), AppError> {
     match command {
-        RepoCommands::List { org, limit, json } => {
-            let repos = repo::list(storage, org.as_deref(), limit)?;
+        SecretCommands::List { repo, org, json } => {
+            let secrets = secret::list(storage, repo.as_deref(), org.as_deref())?;
+            output::present(&secrets, settings.wants_json(json))?;
+        }
+        SecretCommands::Set { name, repo, org, visibility } => {
+            let owner = secret::set(
+                storage,
+                repo.as_deref(),
+                org.as_deref(),
+                &name,
+                visibility.map(Into::into),
+            )?;
+            say!("✅ Set secret {} on {owner}", name.to_ascii_uppercase());
+        }
+        SecretCommands::Delete { name, repo, org } => {
+            let owner = secret::delete(storage, repo.as_deref(), org.as_deref(), &name)?;
+            say!("🗑️  Deleted secret {} from {owner}", name.to_ascii_uppercase());
+        }
+    }
+    Ok(())
+}
 
-            if json {
-                for r in repos {
-                    let output = serde_json::json!({
-                        "name": r.name,
-                        "url": r.html_url,
-                        "pushed_at": r.pushed_at,
-                        "owner": r.owner.login,
-                    });
-                    println!("{}", serde_json::to_string(&output)?);
-                }
+fn run_variable_command(
+    storage: &FilesystemStorage,
+    settings: &Settings,
+    command: VariableCommands,
+) -> Result<(), AppError> {
+    match command {
+        VariableCommands::List { repo, org, json } => {
+            let variables = variable::list(storage, repo.as_deref(), org.as_deref())?;
+            output::present(&variables, settings.wants_json(json))?;
+        }
+        VariableCommands::Set { name, value, repo, org, visibility } => {
+            let owner = variable::set(
+                storage,
+                repo.as_deref(),
+                org.as_deref(),
+                &name,
+                &value,
+                visibility.map(Into::into),
+            )?;
+            say!("✅ Set variable {} on {owner}", name.to_ascii_uppercase());
+        }
+        VariableCommands::Delete { name, repo, org } => {
+            let owner = variable::delete(storage, repo.as_deref(), org.as_deref(), &name)?;
+            say!("🗑️  Deleted variable {} from {owner}", name.to_ascii_uppercase());
+        }
+    }
+    Ok(())
+}
+
+fn run_label_command(
+    storage: &FilesystemStorage,
+    settings: &Settings,
+    mode: ExecutionMode,
+    progress: Progress,
+    command: LabelCommands,
+) -> Result<(), AppError> {
+    match command {
+        LabelCommands::List { repo, json } => {
+            let labels = label::list(storage, repo.as_deref())?;
+            if settings.wants_json(json) {
+                println!("{}", serde_json::to_string_pretty(&labels)?);
             } else {
-                for r in repos {
-                    println!("{} {}", r.full_name, r.html_url);
+                for l in &labels {
+                    println!(
+                        "{:<30} #{:<7} {}",
+                        l.name,
+                        l.color.as_deref().unwrap_or(""),
+                        l.description.as_deref().unwrap_or("")
+                    );
                 }
             }
         }
-        RepoCommands::Clone { repo, org, limit } => {
-            if let Some(org) = org {
-                let cloned = repo::clone_org(storage, &org, limit)?;
-                if cloned.is_empty() {
-                    println!("No repositories cloned.");
-                } else {
-                    println!("✅ Cloned {} repositories:", cloned.len());
-                    for name in cloned {
-                        println!("  - {name}");
+        LabelCommands::Create { name, repo, color, description } => {
+            let created =
+                label::create(storage, repo.as_deref(), Label { name, color, description })?;
+            say!("✅ Created label {}", created.name);
+        }
+        LabelCommands::Delete { name, repo } => {
+            label::delete(storage, repo.as_deref(), &name)?;
+            say!("✅ Deleted label {name}");
+        }
+        LabelCommands::Sync { repos, file, prune } => {
+            let labels = label::load_file(&file)?;
+            let changes = label::sync(storage, &repos, &labels, prune, mode, progress)?;
+
+            let mut failed = 0;
+            for c in &changes {
+                let diff: Vec<String> = c
+                    .created
+                    .iter()
+                    .map(|n| format!("+{n}"))
+                    .chain(c.updated.iter().map(|n| format!("~{n}")))
+                    .chain(c.deleted.iter().map(|n| format!("-{n}")))
+                    .collect();
+                if let Some(error) = &c.error {
+                    failed += 1;
+                    if !progress.is_json() {
+                        esay!("⚠️  {}: {error}", c.repo);
                     }
+                } else if diff.is_empty() {
+                    say!("⏭️  {}: unchanged", c.repo);
+                } else {
+                    let prefix = if mode.is_dry_run() { "🔍" } else { "✅" };
+                    say!("{prefix} {}: {}", c.repo, diff.join(" "));
                 }
-            } else if let Some(repo_spec) = repo {
-                repo::clone(storage, &repo_spec)?;
-                println!("✅ Cloned '{repo_spec}'");
-            } else {
-                return Err(AppError::invalid_input(
-                    "provide either a repo (owner/repo) or --org flag",
-                ));
+            }
+            if failed > 0 {
+                return Err(AppError::github_api(format!(
+                    "{failed} of {} repositories failed",
+                    changes.len()
+                )));
             }
         }
     }
     Ok(())
 }
 
-fn run_pr_command(storage: &FilesystemStorage, command: PrCommands) -> Result<(), AppError> {
+fn run_queue_command(
+    storage: &FilesystemStorage,
+    settings: &Settings,
+    command: QueueCommands,
+) -> Result<(), AppError> {
     match command {
-        PrCommands::List { repo, limit } => {
-            let prs = pr::list(storage, repo.as_deref(), limit)?;
+        QueueCommands::Status { branch, repo, json } => {
+            let merge_queue = queue::status(storage, repo.as_deref(), branch.as_deref())?;
+
+            if settings.wants_json(json) {
+                println!("{}", serde_json::to_string_pretty(&merge_queue)?);
+                return Ok(());
+            }
+
+            let entries = &merge_queue.entries.nodes;
+            if entries.is_empty() {
+                println!("Merge queue is empty.");
+                return Ok(());
+            }
 
-            for p in prs {
-                let output = serde_json::to_string(&p)?;
-                println!("{output}");
+            say!("🚦 Merge queue ({} entries):", entries.len());
+            for entry in entries {
+                let (number, title) = match &entry.pull_request {
+                    Some(p) => (format!("#{}", p.number), p.title.as_str()),
+                    None => ("#?".to_string(), ""),
+                };
+                println!(
+                    "  {:>3}. {number} {title} [{}] ETA {}",
+                    entry.position,
+                    entry.state.to_lowercase(),
+                    queue::format_eta(entry.estimated_time_to_merge)
+                );
             }
         }
     }
     Ok(())
 }
+
+fn print_search_results(repos: &[Repository]) {
+    let name_width = repos.iter().map(|r| r.full_name.len()).max().unwrap_or(0);
+    let lang_width = repos.iter().filter_map(|r| r.language.as_deref()).map(str::len).max();
+    let lang_width = lang_width.unwrap_or(0).max(1);
+
+    for r in repos {
+        let language = r.language.as_deref().unwrap_or("-");
+        let description = r.description.as_deref().unwrap_or("");
+        println!(
+            "{:<name_width$}  ★ {:>6}  {:<lang_width$}  {}",
+            r.full_name, r.stargazers_count, language, description
+        );
+    }
+}
+
+fn print_pr_dashboard(dashboard: &PullRequestDashboard) {
+    let sections = [
+        ("Created by you", &dashboard.created),
+        ("Requesting your review", &dashboard.review_requested),
+        ("Assigned to you", &dashboard.assigned),
+    ];
+
+    for (index, (title, prs)) in sections.into_iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+        println!("{title}");
+        if prs.is_empty() {
+            println!("  (none)");
+        }
+        for entry in prs {
+            let p = &entry.pull_request;
+            let review = p.review_decision.as_deref().unwrap_or("no review").replace('_', " ");
+            println!(
+                "  {}#{} {} [checks: {}] [{}]",
+                entry.repo,
+                p.number,
+                p.title,
+                style::status(&p.ci_status),
+                style::status(&review)
+            );
+        }
+    }
+}
+
+fn print_pr_view(view: &PullRequestView) {
+    let p = &view.pull_request;
+    let state = p.state.as_deref().unwrap_or("unknown");
+    let draft = if p.draft { " (draft)" } else { "" };
+    let base = p.base.as_ref().map(|b| b.branch.as_str()).unwrap_or("?");
+
+    println!("#{} {}", p.number, p.title);
+    println!("  State:     {state}{draft}");
+    println!("  Author:    {}", p.user.login);
+    println!("  Branch:    {} → {base}", p.head.branch);
+
+    if !p.labels.is_empty() {
+        let labels: Vec<&str> = p.labels.iter().map(|l| l.name.as_str()).collect();
+        println!("  Labels:    {}", labels.join(", "));
+    }
+    if !p.requested_reviewers.is_empty() {
+        let reviewers: Vec<&str> = p.requested_reviewers.iter().map(|r| r.login.as_str()).collect();
+        println!("  Reviewers: {}", reviewers.join(", "));
+    }
+
+    let c = &view.checks;
+    let count = |n: usize, state: &str, tone: Tone| {
+        let text = format!("{n} {state}");
+        if n > 0 && style::color() { style::paint(&text, tone) } else { text }
+    };
+    println!(
+        "  Checks:    {}, {}, {}, {} skipped",
+        count(c.passed, "passed", Tone::Success),
+        count(c.failed, "failed", Tone::Failure),
+        count(c.pending, "pending", Tone::Pending),
+        c.skipped
+    );
+
+    let mergeable = match p.mergeable {
+        Some(true) => "yes",
+        Some(false) => "no",
+        None => "unknown",
+    };
+    match &p.mergeable_state {
+        Some(detail) => println!("  Mergeable: {mergeable} ({detail})"),
+        None => println!("  Mergeable: {mergeable}"),
+    }
+
+    if let (Some(add), Some(del), Some(files)) = (p.additions, p.deletions, p.changed_files) {
+        println!("  Changes:   +{add} -{del} across {files} files");
+    }
+    if let Some(url) = &p.html_url {
+        println!("  URL:       {url}");
+    }
+
+    if !view.commits.is_empty() {
+        println!("  Commits:");
+        for c in &view.commits {
+            println!(
+                "    {} {} ({})",
+                &c.sha[..c.sha.len().min(7)],
+                c.subject,
+                signature_label(c.verified, &c.reason)
+            );
+        }
+    }
+
+    if let Some(body) = p.body.as_deref().filter(|b| !b.trim().is_empty()) {
+        println!();
+        println!("{}", body.trim_end());
+    }
+}
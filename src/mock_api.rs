@@ -0,0 +1,162 @@
+//! In-process stand-in for the GitHub API, for unit tests of API-backed commands.
+//!
+//! [`server`] starts one HTTP server per test binary and points the client at it through
+//! [`github::set_api_base`]. Tests register canned responses by method and path and read back
+//! the requests they caused. Tests run in parallel against the same server, so each one uses
+//! its own owner (e.g. `mock-pr-comment/api`) in the paths it registers.
+
+use crate::config::Config;
+use crate::github;
+use crate::models::{Account, AccountKind, AccountsFile, CloneLayout, CloneOptions, Protocol};
+use crate::storage::{FilesystemStorage, Storage};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+
+/// A request the server received.
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    /// Path with query string, e.g. `/orgs/acme/repos?per_page=100&page=1`.
+    pub path: String,
+    pub body: String,
+}
+
+struct Route {
+    method: String,
+    path: String,
+    status: u16,
+    body: String,
+}
+
+#[derive(Default)]
+struct State {
+    routes: Vec<Route>,
+    requests: Vec<Request>,
+}
+
+/// The shared mock server.
+pub struct MockApi {
+    state: Arc<Mutex<State>>,
+}
+
+/// The mock server of this test binary, started on first use.
+pub fn server() -> &'static MockApi {
+    static SERVER: OnceLock<MockApi> = OnceLock::new();
+    SERVER.get_or_init(|| {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock API");
+        let addr = listener.local_addr().expect("mock API address");
+        let state = Arc::new(Mutex::new(State::default()));
+        let shared = Arc::clone(&state);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let state = Arc::clone(&shared);
+                thread::spawn(move || handle(stream, &state));
+            }
+        });
+        github::set_api_base(Some(&format!("http://{addr}")));
+        MockApi { state }
+    })
+}
+
+impl MockApi {
+    /// Answer `method path` with `status` and the JSON `body`.
+    ///
+    /// `path` matches requests with exactly that path and query, or, without a query, any
+    /// query. A later registration of the same route replaces the earlier one.
+    pub fn route(&self, method: &str, path: &str, status: u16, body: serde_json::Value) {
+        let route = Route {
+            method: method.to_string(),
+            path: path.to_string(),
+            status,
+            body: body.to_string(),
+        };
+        self.state.lock().unwrap().routes.push(route);
+    }
+
+    /// Requests received so far whose path starts with `prefix`.
+    pub fn requests(&self, prefix: &str) -> Vec<Request> {
+        let state = self.state.lock().unwrap();
+        state.requests.iter().filter(|r| r.path.starts_with(prefix)).cloned().collect()
+    }
+}
+
+/// Storage in `dir` with an active account whose token comes from a command, so no keychain
+/// is needed.
+pub fn storage(dir: &Path) -> FilesystemStorage {
+    let storage = FilesystemStorage::new(&Config::with_path(dir.to_path_buf()));
+    let account = Account {
+        id: "mock".to_string(),
+        kind: AccountKind::Personal,
+        username: "mock-user".to_string(),
+        default_org: None,
+        protocol: Protocol::Https,
+        clone_dir: None,
+        clone_layout: CloneLayout::Flat,
+        clone_options: CloneOptions::default(),
+        browser: None,
+        token_command: Some("echo mock-token".to_string()),
+        token_expires_at: None,
+        host: None,
+        protected: false,
+        git: None,
+    };
+    let accounts = AccountsFile {
+        personal: vec![account],
+        active_account_id: Some("mock".to_string()),
+        ..Default::default()
+    };
+    storage.save_accounts(&accounts).expect("save mock account");
+    storage
+}
+
+fn handle(stream: TcpStream, state: &Mutex<State>) {
+    let mut reader = BufReader::new(&stream);
+    let mut line = String::new();
+    if reader.read_line(&mut line).is_err() {
+        return;
+    }
+    let mut parts = line.split_whitespace();
+    let (method, path) = (parts.next().unwrap_or_default(), parts.next().unwrap_or_default());
+
+    let mut length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).is_err() || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            length = value.trim().parse().unwrap_or(0);
+        }
+    }
+    let mut body = vec![0; length];
+    let _ = reader.read_exact(&mut body);
+
+    let (status, response) = {
+        let mut state = state.lock().unwrap();
+        state.requests.push(Request {
+            method: method.to_string(),
+            path: path.to_string(),
+            body: String::from_utf8_lossy(&body).into_owned(),
+        });
+        let bare = path.split('?').next().unwrap_or_default();
+        state
+            .routes
+            .iter()
+            .rev()
+            .find(|r| r.method == method && (r.path == path || r.path == bare))
+            .map(|r| (r.status, r.body.clone()))
+            .unwrap_or((404, r#"{"message":"Not Found"}"#.to_string()))
+    };
+    let _ = write!(
+        &stream,
+        "HTTP/1.1 {status} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n{response}",
+        response.len()
+    );
+    let _ = (&stream).flush();
+}
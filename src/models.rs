@@ -1,6 +1,15 @@
 //! Data models for gho.
 
+use crate::error::AppError;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// How long a sudo confirmation for a protected account stays valid.
+pub const SUDO_GRANT_TTL_MINUTES: i64 = 15;
+/// How many recently used repositories the state file keeps.
+pub const RECENT_REPO_LIMIT: usize = 20;
 
 /// Git protocol for cloning.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -20,6 +29,38 @@ impl std::fmt::Display for Protocol {
     }
 }
 
+/// Directory layout for clones under an account's clone directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum CloneLayout {
+    /// `<clone_dir>/<repo>`
+    #[default]
+    #[serde(rename = "flat")]
+    Flat,
+    /// `<clone_dir>/<owner>/<repo>`
+    #[serde(rename = "owner/repo")]
+    OwnerRepo,
+    /// `<clone_dir>/<host>/<owner>/<repo>`
+    #[serde(rename = "host/owner/repo")]
+    HostOwnerRepo,
+}
+
+impl CloneLayout {
+    /// Whether this is the default flat layout.
+    pub fn is_flat(&self) -> bool {
+        *self == CloneLayout::Flat
+    }
+}
+
+impl std::fmt::Display for CloneLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CloneLayout::Flat => write!(f, "flat"),
+            CloneLayout::OwnerRepo => write!(f, "owner/repo"),
+            CloneLayout::HostOwnerRepo => write!(f, "host/owner/repo"),
+        }
+    }
+}
+
 /// Account kind.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -46,6 +87,9 @@ pub struct Account {
     pub kind: AccountKind,
     /// GitHub username.
     pub username: String,
+    /// GitHub Enterprise Server hostname; github.com when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
     /// Default organization for operations.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub default_org: Option<String>,
@@ -55,6 +99,100 @@ pub struct Account {
     /// Directory for cloning repositories.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub clone_dir: Option<String>,
+    /// Directory layout for clones under `clone_dir`.
+    #[serde(default, skip_serializing_if = "CloneLayout::is_flat")]
+    pub clone_layout: CloneLayout,
+    /// Default `git clone` options, set with `account clone-defaults`.
+    #[serde(default, skip_serializing_if = "CloneOptions::is_empty")]
+    pub clone_options: CloneOptions,
+    /// Command template for opening this account's pages, e.g. `firefox -P {account}`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub browser: Option<String>,
+    /// Shell command printing the token, used instead of the Keychain when set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_command: Option<String>,
+    /// When the token expires (fine-grained and expiring classic tokens).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_expires_at: Option<DateTime<Utc>>,
+    /// Require confirmation before the first destructive API call in a session.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub protected: bool,
+    /// Git identity written by `account setup-gitconfig`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git: Option<GitIdentity>,
+}
+
+/// Git identity used for repositories under an account's directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GitIdentity {
+    pub name: String,
+    pub email: String,
+    /// GPG key ID or SSH public key (file) that signs commits and tags.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing_key: Option<String>,
+    /// Private SSH key used for pushes and fetches.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssh_key: Option<String>,
+    /// Directory whose repositories use this identity, ending with `/`.
+    pub gitdir: String,
+}
+
+/// Options passed through to `git clone`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CloneOptions {
+    /// Truncate history to this many commits (`--depth`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depth: Option<u32>,
+    /// Partial clone filter such as `blob:none` (`--filter`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+    /// Clone submodules as well (`--recurse-submodules`).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub recurse_submodules: bool,
+    /// Branch to check out instead of the default branch (`--branch`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// Clone without a working tree (`--bare`).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub bare: bool,
+}
+
+impl CloneOptions {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// These options, with unset ones taken from `defaults`.
+    pub fn or(self, defaults: &CloneOptions) -> CloneOptions {
+        CloneOptions {
+            depth: self.depth.or(defaults.depth),
+            filter: self.filter.or_else(|| defaults.filter.clone()),
+            recurse_submodules: self.recurse_submodules || defaults.recurse_submodules,
+            branch: self.branch.or_else(|| defaults.branch.clone()),
+            bare: self.bare || defaults.bare,
+        }
+    }
+
+    /// Arguments for `git clone`, before the URL.
+    pub fn args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(depth) = self.depth {
+            args.push(format!("--depth={depth}"));
+        }
+        if let Some(filter) = &self.filter {
+            args.push(format!("--filter={filter}"));
+        }
+        if self.recurse_submodules {
+            args.push("--recurse-submodules".to_string());
+        }
+        if let Some(branch) = &self.branch {
+            args.push(format!("--branch={branch}"));
+        }
+        if self.bare {
+            args.push("--bare".to_string());
+        }
+        args
+    }
 }
 
 /// Container for all accounts.
@@ -69,6 +207,9 @@ pub struct AccountsFile {
     /// Currently active account ID.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub active_account_id: Option<String>,
+    /// Named groups of account IDs for batch operations.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub groups: BTreeMap<String, Vec<String>>,
 }
 
 impl AccountsFile {
@@ -100,8 +241,60 @@ impl AccountsFile {
         }
     }
 
+    /// Register a new account, activating it if no account is active yet.
+    pub fn register(&mut self, account: Account) -> Result<(), AppError> {
+        if self.find_account(&account.id).is_some() {
+            return Err(AppError::invalid_input(format!(
+                "account '{}' already exists",
+                account.id
+            )));
+        }
+        if self.active_account_id.is_none() {
+            self.active_account_id = Some(account.id.clone());
+        }
+        self.add_account(account);
+        Ok(())
+    }
+
+    /// Make an existing account the active one.
+    pub fn set_active(&mut self, id: &str) -> Result<(), AppError> {
+        if self.find_account(id).is_none() {
+            return Err(AppError::AccountNotFound(id.to_string()));
+        }
+        self.active_account_id = Some(id.to_string());
+        Ok(())
+    }
+
+    /// Define (or replace) a named group of existing accounts.
+    pub fn set_group(&mut self, name: &str, ids: Vec<String>) -> Result<(), AppError> {
+        if ids.is_empty() {
+            return Err(AppError::invalid_input("a group needs at least one account"));
+        }
+        if let Some(missing) = ids.iter().find(|id| self.find_account(id).is_none()) {
+            return Err(AppError::AccountNotFound(missing.clone()));
+        }
+        self.groups.insert(name.to_string(), ids);
+        Ok(())
+    }
+
+    /// Resolve the accounts belonging to a named group.
+    pub fn group_accounts(&self, name: &str) -> Result<Vec<&Account>, AppError> {
+        let ids = self
+            .groups
+            .get(name)
+            .ok_or_else(|| AppError::invalid_input(format!("account group '{name}' not found")))?;
+        ids.iter()
+            .map(|id| self.find_account(id).ok_or_else(|| AppError::AccountNotFound(id.clone())))
+            .collect()
+    }
+
     /// Remove an account by ID.
     pub fn remove_account(&mut self, id: &str) -> Option<Account> {
+        for members in self.groups.values_mut() {
+            members.retain(|member| member != id);
+        }
+        self.groups.retain(|_, members| !members.is_empty());
+
         if let Some(pos) = self.personal.iter().position(|a| a.id == id) {
             if self.active_account_id.as_deref() == Some(id) {
                 self.active_account_id = None;
@@ -118,6 +311,14 @@ impl AccountsFile {
     }
 }
 
+/// A result item tagged with the account that produced it.
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountScoped<T> {
+    pub account: String,
+    #[serde(flatten)]
+    pub item: T,
+}
+
 /// Application state for gho.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct StateFile {
@@ -127,6 +328,150 @@ pub struct StateFile {
     /// Last used repository.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub last_repo: Option<String>,
+    /// Recently used repositories, most recent first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recent_repos: Vec<String>,
+    /// Confirmations granted for protected accounts, scoped to a shell session.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sudo_grants: Vec<SudoGrant>,
+    /// Git repositories found under scanned directories, keyed by the scanned path.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub repo_scans: BTreeMap<String, RepoScan>,
+    /// The last `repo clone --org` until all its repositories are cloned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bulk_clone: Option<BulkClone>,
+}
+
+impl StateFile {
+    /// Record `full_name` as the last used repository, moving it to the front of the history.
+    pub fn touch_repo(&mut self, full_name: &str) {
+        self.recent_repos.retain(|r| !r.eq_ignore_ascii_case(full_name));
+        self.recent_repos.insert(0, full_name.to_string());
+        self.recent_repos.truncate(RECENT_REPO_LIMIT);
+        self.last_repo = Some(full_name.to_string());
+    }
+
+    /// Whether a protected account has been confirmed in this session.
+    pub fn has_sudo_grant(&self, account_id: &str, session: u32, now: DateTime<Utc>) -> bool {
+        self.sudo_grants
+            .iter()
+            .any(|g| g.account_id == account_id && g.session == session && g.expires_at > now)
+    }
+
+    /// Record a confirmation for a protected account, pruning expired grants.
+    pub fn grant_sudo(&mut self, account_id: &str, session: u32, now: DateTime<Utc>) {
+        self.sudo_grants.retain(|g| g.expires_at > now && g.account_id != account_id);
+        self.sudo_grants.push(SudoGrant {
+            account_id: account_id.to_string(),
+            session,
+            expires_at: now + Duration::minutes(SUDO_GRANT_TTL_MINUTES),
+        });
+    }
+}
+
+/// Recently used repositories and organization, as shown by `gho recent`.
+#[derive(Debug, Clone, Serialize)]
+pub struct RecentHistory {
+    /// Most recent first.
+    pub repos: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_org: Option<String>,
+}
+
+/// One recorded gho invocation, as kept in `history.jsonl`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub at: DateTime<Utc>,
+    /// Active account when the command ran.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>,
+    /// Working directory, which decides the repository detected from git.
+    pub cwd: PathBuf,
+    /// Arguments after `gho`, with secrets removed.
+    pub args: Vec<String>,
+    /// Where the active account's token came from. Absent in entries recorded before it was
+    /// tracked and when no account was active.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_source: Option<TokenSource>,
+}
+
+/// Where a token was read from, in order of precedence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TokenSource {
+    /// The `GH_TOKEN` environment override.
+    #[serde(rename = "env:GH_TOKEN")]
+    GhToken,
+    /// The `GITHUB_TOKEN` environment override.
+    #[serde(rename = "env:GITHUB_TOKEN")]
+    GithubToken,
+    /// The account's `token_command`.
+    #[serde(rename = "token_command")]
+    TokenCommand,
+    /// The Keychain entry of the account.
+    #[serde(rename = "keychain")]
+    Keychain,
+}
+
+impl std::fmt::Display for TokenSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokenSource::GhToken => write!(f, "env:GH_TOKEN"),
+            TokenSource::GithubToken => write!(f, "env:GITHUB_TOKEN"),
+            TokenSource::TokenCommand => write!(f, "token_command"),
+            TokenSource::Keychain => write!(f, "keychain"),
+        }
+    }
+}
+
+/// Session-scoped confirmation for a protected account.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SudoGrant {
+    pub account_id: String,
+    /// Identifier of the parent shell session.
+    pub session: u32,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// An organization clone, kept in state for `repo clone --resume`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BulkClone {
+    pub org: String,
+    /// Account whose protocol and clone directory the plan was made with.
+    pub account: String,
+    /// Clone manifest written when the clones finish.
+    pub manifest: PathBuf,
+    #[serde(default, skip_serializing_if = "CloneOptions::is_empty")]
+    pub options: CloneOptions,
+    pub repos: Vec<BulkCloneRepo>,
+}
+
+/// One repository of a [`BulkClone`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BulkCloneRepo {
+    pub full_name: String,
+    pub name: String,
+    pub url: String,
+    pub dir: PathBuf,
+    /// How the last attempt ended; unset until the repository is tried.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<ProgressOutcome>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl BulkCloneRepo {
+    /// Whether the repository needs no further attempt: cloned, or its directory existed.
+    pub fn is_done(&self) -> bool {
+        matches!(self.outcome, Some(ProgressOutcome::Succeeded | ProgressOutcome::Skipped))
+    }
+}
+
+/// Cached result of scanning a directory for git repositories.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepoScan {
+    pub scanned_at: DateTime<Utc>,
+    pub max_depth: usize,
+    pub repos: Vec<PathBuf>,
 }
 
 /// Repository information from GitHub API.
@@ -140,6 +485,201 @@ pub struct Repository {
     #[serde(default)]
     pub pushed_at: Option<String>,
     pub owner: RepositoryOwner,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub stargazers_count: u64,
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default)]
+    pub fork: bool,
+    /// `public`, `private` or `internal`.
+    #[serde(default)]
+    pub visibility: Option<String>,
+    #[serde(default)]
+    pub open_issues_count: u64,
+    #[serde(default)]
+    pub topics: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_branch: Option<String>,
+    /// The authenticated user's permissions; only present on authenticated requests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<RepositoryPermissions>,
+}
+
+/// Request and response body of the repository topics endpoints.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RepositoryTopics {
+    pub names: Vec<String>,
+}
+
+/// Permission flags of the authenticated user on a repository.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct RepositoryPermissions {
+    #[serde(default)]
+    pub admin: bool,
+    #[serde(default)]
+    pub maintain: bool,
+    #[serde(default)]
+    pub push: bool,
+    #[serde(default)]
+    pub triage: bool,
+    #[serde(default)]
+    pub pull: bool,
+}
+
+/// Repository role, from no access up to admin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RepoPermission {
+    None,
+    Read,
+    Triage,
+    Write,
+    Maintain,
+    Admin,
+}
+
+impl RepoPermission {
+    /// Highest role granted by a set of permission flags.
+    pub fn from_flags(flags: &RepositoryPermissions) -> Self {
+        if flags.admin {
+            RepoPermission::Admin
+        } else if flags.maintain {
+            RepoPermission::Maintain
+        } else if flags.push {
+            RepoPermission::Write
+        } else if flags.triage {
+            RepoPermission::Triage
+        } else if flags.pull {
+            RepoPermission::Read
+        } else {
+            RepoPermission::None
+        }
+    }
+}
+
+impl std::fmt::Display for RepoPermission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            RepoPermission::None => "none",
+            RepoPermission::Read => "read",
+            RepoPermission::Triage => "triage",
+            RepoPermission::Write => "write",
+            RepoPermission::Maintain => "maintain",
+            RepoPermission::Admin => "admin",
+        };
+        f.write_str(name)
+    }
+}
+
+/// One account's access to a repository, as compared by `gho access diff`.
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoAccess {
+    pub username: String,
+    /// `None` when the check failed; see `error`.
+    pub permission: Option<RepoPermission>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Sort order for repository search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchSort {
+    #[default]
+    BestMatch,
+    Stars,
+    Updated,
+}
+
+impl SearchSort {
+    /// Value for the search API `sort` parameter, if any.
+    pub fn as_param(&self) -> Option<&'static str> {
+        match self {
+            SearchSort::BestMatch => None,
+            SearchSort::Stars => Some("stars"),
+            SearchSort::Updated => Some("updated"),
+        }
+    }
+}
+
+/// Repository visibility accepted by `repo list --visibility`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepoVisibility {
+    Public,
+    Private,
+}
+
+impl RepoVisibility {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RepoVisibility::Public => "public",
+            RepoVisibility::Private => "private",
+        }
+    }
+}
+
+/// Which repositories `repo list` shows; the default matches every repository.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RepoFilter {
+    pub visibility: Option<RepoVisibility>,
+    /// `Some(true)` for forks only, `Some(false)` for sources (non-forks) only.
+    pub fork: Option<bool>,
+    pub exclude_archived: bool,
+    /// Primary language, compared case-insensitively.
+    pub language: Option<String>,
+    pub topic: Option<String>,
+}
+
+impl RepoFilter {
+    /// Whether `repo` passes every filter.
+    pub fn matches(&self, repo: &Repository) -> bool {
+        let visibility = repo.visibility.as_deref().unwrap_or("public");
+        self.visibility.is_none_or(|v| v.as_str() == visibility)
+            && self.fork.is_none_or(|fork| repo.fork == fork)
+            && !(self.exclude_archived && repo.archived)
+            && self.language.as_deref().is_none_or(|language| {
+                repo.language.as_deref().is_some_and(|l| l.eq_ignore_ascii_case(language))
+            })
+            && self
+                .topic
+                .as_deref()
+                .is_none_or(|topic| repo.topics.iter().any(|t| t.eq_ignore_ascii_case(topic)))
+    }
+
+    /// The organization repository API's `type` parameter that covers part of the filter.
+    ///
+    /// The API takes a single type, so visibility wins over forks/sources; whatever it does
+    /// not cover is left to [`RepoFilter::matches`].
+    pub fn org_type_param(&self) -> Option<&'static str> {
+        match (self.visibility, self.fork) {
+            (Some(visibility), _) => Some(visibility.as_str()),
+            (None, Some(true)) => Some("forks"),
+            (None, Some(false)) => Some("sources"),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Order of `repo list` output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RepoSort {
+    /// Most recently pushed first.
+    #[default]
+    Pushed,
+    /// Most stars first.
+    Stars,
+    /// By `owner/name`.
+    Name,
+}
+
+/// Search API response.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SearchResponse<T> {
+    pub total_count: u64,
+    pub items: Vec<T>,
 }
 
 /// Repository owner information.
@@ -148,6 +688,64 @@ pub struct RepositoryOwner {
     pub login: String,
 }
 
+/// The user a token authenticates as (`GET /user`).
+#[derive(Debug, Clone, Deserialize)]
+pub struct AuthenticatedUser {
+    pub login: String,
+}
+
+/// What GitHub reports about a token on `GET /user`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenInfo {
+    pub login: String,
+    /// OAuth scopes of a classic token; `None` for fine-grained and app tokens, which have none.
+    pub scopes: Option<Vec<String>>,
+    pub rate_limit_remaining: Option<u64>,
+}
+
+/// Token health of one account (`gho auth status`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AuthStatus {
+    pub account: String,
+    pub username: String,
+    pub active: bool,
+    /// Where gho reads the token from; an `env:` source overrides the stored secret.
+    pub source: TokenSource,
+    /// Whether the stored secret (Keychain entry or token command output) could be read.
+    pub stored_secret: bool,
+    /// User the token in use authenticates as.
+    pub login: Option<String>,
+    pub scopes: Option<Vec<String>>,
+    pub rate_limit_remaining: Option<u64>,
+    /// Everything that makes gho use another identity than expected, or fail to authenticate.
+    pub problems: Vec<String>,
+}
+
+/// Who an account's token authenticates as (`gho whoami`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Identity {
+    pub account: String,
+    /// Username configured for the account.
+    pub username: String,
+    pub active: bool,
+    pub source: TokenSource,
+    /// User the token authenticates as; `None` when it could not be checked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub login: Option<String>,
+    /// The token authenticates as another user than the configured one.
+    pub mismatch: bool,
+    /// Why the token could not be checked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl Identity {
+    /// Whether the token works and belongs to the configured user.
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none() && !self.mismatch
+    }
+}
+
 /// Pull request information from GitHub API.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PullRequest {
@@ -157,6 +755,30 @@ pub struct PullRequest {
     pub head: PullRequestHead,
     #[serde(default)]
     pub mergeable: Option<bool>,
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub state: Option<String>,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub html_url: Option<String>,
+    #[serde(default)]
+    pub base: Option<PullRequestHead>,
+    #[serde(default)]
+    pub labels: Vec<Label>,
+    #[serde(default)]
+    pub requested_reviewers: Vec<PullRequestUser>,
+    #[serde(default)]
+    pub mergeable_state: Option<String>,
+    #[serde(default)]
+    pub additions: Option<u64>,
+    #[serde(default)]
+    pub deletions: Option<u64>,
+    #[serde(default)]
+    pub changed_files: Option<u64>,
+    #[serde(default)]
+    pub merged_at: Option<DateTime<Utc>>,
 }
 
 /// Pull request author.
@@ -170,17 +792,2078 @@ pub struct PullRequestUser {
 pub struct PullRequestHead {
     #[serde(rename = "ref")]
     pub branch: String,
+    #[serde(default)]
+    pub sha: String,
+    /// Repository the branch lives in; differs from the base repository for forks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo: Option<PullRequestRepo>,
 }
 
-/// Output format for PR list.
+/// Repository of a pull request branch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PullRequestRepo {
+    pub full_name: String,
+}
+
+/// Issue or pull request label.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Label {
+    pub name: String,
+    /// Hex color without the leading `#`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Check run information from GitHub API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckRun {
+    #[serde(default)]
+    pub id: u64,
+    pub name: String,
+    pub status: String,
+    #[serde(default)]
+    pub conclusion: Option<String>,
+    #[serde(default)]
+    pub output: Option<CheckRunOutput>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Page of the check on the integration's site, e.g. a CI build.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub html_url: Option<String>,
+}
+
+/// Summary output attached to a check run.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CheckRunOutput {
+    #[serde(default)]
+    pub annotations_count: u64,
+}
+
+/// Check runs list response from GitHub API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CheckRunsResponse {
+    pub check_runs: Vec<CheckRun>,
+}
+
+/// Latest commit status of one context, as reported through the statuses API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitStatus {
+    pub context: String,
+    /// `pending`, `success`, `failure` or `error`.
+    pub state: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub target_url: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Combined status response from GitHub API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CombinedStatusResponse {
+    pub statuses: Vec<CommitStatus>,
+}
+
+/// Where a [`PullRequestCheck`] comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckKind {
+    CheckRun,
+    Status,
+    /// A required check that has not reported yet.
+    Expected,
+}
+
+/// One check of a pull request's head commit: a check run or a commit status context.
 #[derive(Debug, Clone, Serialize)]
-pub struct PullRequestOutput {
-    pub number: u64,
-    pub title: String,
-    pub author: String,
-    pub branch: String,
+pub struct PullRequestCheck {
+    pub name: String,
+    pub kind: CheckKind,
+    /// `queued`, `in_progress`, `pending` or `expected` until `completed`.
+    pub status: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub mergeable: Option<bool>,
-    pub actions_in_progress: bool,
-    pub ci_status: String,
+    pub conclusion: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl PullRequestCheck {
+    /// Whether the check has yet to conclude.
+    pub fn is_pending(&self) -> bool {
+        self.status != "completed"
+    }
+
+    /// Whether the check concluded with anything but success, neutral or skipped.
+    pub fn is_failure(&self) -> bool {
+        !self.is_pending()
+            && !matches!(self.conclusion.as_deref(), Some("success" | "skipped" | "neutral"))
+    }
+
+    /// Seconds from start to completion, for completed checks.
+    pub fn duration_secs(&self) -> Option<i64> {
+        Some((self.completed_at? - self.started_at?).num_seconds().max(0))
+    }
+}
+
+/// Annotation reported by a check run (lint error, test failure, ...).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckAnnotation {
+    pub path: String,
+    pub start_line: u64,
+    #[serde(default)]
+    pub start_column: Option<u64>,
+    /// `notice`, `warning` or `failure`.
+    pub annotation_level: String,
+    pub message: String,
+    #[serde(default)]
+    pub title: Option<String>,
+}
+
+/// An annotation tagged with the check run that produced it.
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckRunAnnotation {
+    pub check: String,
+    #[serde(flatten)]
+    pub annotation: CheckAnnotation,
+}
+
+/// Job of a workflow run; its ID doubles as a check run ID.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowJob {
+    pub id: u64,
+    pub name: String,
+    /// `queued`, `in_progress` or `completed`.
+    #[serde(default)]
+    pub status: String,
+    /// Set once completed: `success`, `failure`, `cancelled`, `skipped`, ...
+    #[serde(default)]
+    pub conclusion: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub steps: Vec<WorkflowStep>,
+}
+
+/// Step of a workflow job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowStep {
+    pub name: String,
+    pub number: u64,
+    #[serde(default)]
+    pub status: String,
+    #[serde(default)]
+    pub conclusion: Option<String>,
+    /// When the step finished, to the second (RFC 3339).
+    #[serde(default)]
+    pub completed_at: Option<String>,
+}
+
+/// GitHub Actions workflow run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowRun {
+    pub id: u64,
+    pub run_number: u64,
+    pub name: Option<String>,
+    pub display_title: String,
+    pub status: String,
+    pub conclusion: Option<String>,
+    pub event: String,
+    pub head_branch: Option<String>,
+    pub html_url: String,
+    pub created_at: String,
+}
+
+/// Workflow runs list response from GitHub API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct WorkflowRunsResponse {
+    pub workflow_runs: Vec<WorkflowRun>,
+}
+
+/// Artifact uploaded by a workflow run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Artifact {
+    pub id: u64,
+    pub name: String,
+    #[serde(default)]
+    pub size_in_bytes: u64,
+    /// Past its retention period; the archive can no longer be downloaded.
+    #[serde(default)]
+    pub expired: bool,
+}
+
+/// Workflow run artifacts list response from GitHub API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ArtifactsResponse {
+    pub artifacts: Vec<Artifact>,
+}
+
+/// Outcome of downloading and extracting one artifact.
+#[derive(Debug, Clone, Serialize)]
+pub struct ArtifactDownloadResult {
+    pub name: String,
+    /// Directory the archive was extracted into.
+    pub path: PathBuf,
+    /// Number of files extracted.
+    pub files: usize,
+    /// Error message when the download or extraction failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Workflow run jobs list response from GitHub API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct WorkflowJobsResponse {
+    pub jobs: Vec<WorkflowJob>,
+}
+
+/// Aggregated check run counts for a commit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ChecksSummary {
+    pub passed: usize,
+    pub failed: usize,
+    pub pending: usize,
+    pub skipped: usize,
+}
+
+/// Output format for PR list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PullRequestOutput {
+    pub number: u64,
+    pub title: String,
+    pub author: String,
+    pub branch: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mergeable: Option<bool>,
+    pub actions_in_progress: bool,
+    pub ci_status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub review_decision: Option<String>,
+    /// Set when `mergeable` could not be looked up, e.g. because of rate limiting.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub partial: bool,
+}
+
+/// Detailed view of a single pull request.
+#[derive(Debug, Clone, Serialize)]
+pub struct PullRequestView {
+    #[serde(flatten)]
+    pub pull_request: PullRequest,
+    pub checks: ChecksSummary,
+    pub commits: Vec<CommitSignature>,
+}
+
+/// Review action submitted on a pull request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReviewEvent {
+    Approve,
+    RequestChanges,
+    Comment,
+}
+
+impl ReviewEvent {
+    /// Whether GitHub requires a body for this review event.
+    pub fn requires_body(&self) -> bool {
+        matches!(self, ReviewEvent::RequestChanges | ReviewEvent::Comment)
+    }
+}
+
+/// Pull request review from GitHub API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Review {
+    pub id: u64,
+    pub user: PullRequestUser,
+    pub state: String,
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub submitted_at: Option<String>,
+}
+
+/// Issue from GitHub API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Issue {
+    pub number: u64,
+    pub title: String,
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub user: Option<PullRequestUser>,
+    #[serde(default)]
+    pub labels: Vec<Label>,
+    #[serde(default)]
+    pub assignees: Vec<PullRequestUser>,
+    #[serde(default)]
+    pub comments: u64,
+    pub created_at: DateTime<Utc>,
+    pub html_url: String,
+    /// Set when the issue is a pull request, which the issues endpoints also return.
+    #[serde(default, skip_serializing)]
+    pub pull_request: Option<serde_json::Value>,
+}
+
+/// Fields to change on an issue; `None` leaves a field as it is.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct IssueUpdate {
+    /// Replaces every label of the issue.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<Vec<String>>,
+    /// Replaces every assignee of the issue.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignees: Option<Vec<String>>,
+    /// `open` or `closed`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    /// Why the issue was closed: `completed` or `not_planned`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_reason: Option<String>,
+    /// Number of the milestone to set; `Some(None)` clears it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub milestone: Option<Option<u64>>,
+}
+
+/// Milestone of a repository.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Milestone {
+    pub number: u64,
+    pub title: String,
+    #[serde(default)]
+    pub state: String,
+}
+
+/// Comment on the conversation of an issue or pull request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueComment {
+    pub id: u64,
+    #[serde(default)]
+    pub user: Option<PullRequestUser>,
+    #[serde(default)]
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+    pub html_url: String,
+}
+
+/// Comment on a line of a pull request's diff.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReviewComment {
+    pub id: u64,
+    #[serde(default)]
+    pub user: Option<PullRequestUser>,
+    #[serde(default)]
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+    pub html_url: String,
+    pub path: String,
+    /// `None` when the line is no longer part of the diff.
+    #[serde(default)]
+    pub line: Option<u64>,
+    /// First comment of the thread this one replies to.
+    #[serde(default)]
+    pub in_reply_to_id: Option<u64>,
+}
+
+/// Conversation or review comment as listed by `pr comments`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PullRequestComment {
+    pub id: u64,
+    pub author: String,
+    pub created_at: DateTime<Utc>,
+    /// `file:line` of a review comment; `None` for conversation comments.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    /// First comment of the review thread this one replies to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub in_reply_to: Option<u64>,
+    pub body: String,
+    pub url: String,
+}
+
+/// GraphQL response envelope.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GraphQlResponse<T> {
+    #[serde(default = "Option::default")]
+    pub data: Option<T>,
+    #[serde(default)]
+    pub errors: Vec<GraphQlError>,
+}
+
+/// GraphQL error entry.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GraphQlError {
+    pub message: String,
+}
+
+/// `repository.pullRequests` GraphQL payload used by `pr list`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PullRequestStatusData {
+    pub repository: Option<PullRequestStatusRepository>,
+}
+
+/// Repository node in the `pr list` GraphQL payload.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PullRequestStatusRepository {
+    pub pull_requests: GraphQlNodes<PullRequestStatusNode>,
+}
+
+/// Generic GraphQL connection with `nodes`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphQlNodes<T> {
+    pub nodes: Vec<T>,
+}
+
+/// GraphQL connection with `nodes` and the cursor of the next page.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQlPage<T> {
+    pub page_info: PageInfo,
+    pub nodes: Vec<T>,
+}
+
+/// Pagination state of a GraphQL connection.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageInfo {
+    pub has_next_page: bool,
+    #[serde(default)]
+    pub end_cursor: Option<String>,
+}
+
+/// `repository.refs` GraphQL payload used by `branch list`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BranchRefsData {
+    pub repository: Option<BranchRefsRepository>,
+}
+
+/// Repository node in the `branch list` GraphQL payload.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BranchRefsRepository {
+    pub refs: GraphQlPage<BranchRefNode>,
+}
+
+/// Branch with its head commit, how far it is from the default branch and its last merged
+/// pull request.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BranchRefNode {
+    pub name: String,
+    pub target: BranchTarget,
+    /// Comparison with the default branch as head; `None` for the default branch itself.
+    #[serde(default)]
+    pub compare: Option<BranchComparison>,
+    pub associated_pull_requests: GraphQlNodes<MergedPullRequestNode>,
+}
+
+/// Commit a branch points to.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BranchTarget {
+    pub oid: String,
+    #[serde(default)]
+    pub committed_date: Option<DateTime<Utc>>,
+}
+
+/// Comparison of a branch (base) with the default branch (head).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BranchComparison {
+    /// Commits on the branch that the default branch does not have.
+    pub behind_count: u64,
+}
+
+/// Merged pull request of a branch.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergedPullRequestNode {
+    pub number: u64,
+    pub head_ref_oid: String,
+}
+
+/// Remote branch as listed by `gho branch list`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Branch {
+    pub name: String,
+    pub sha: String,
+    pub committed_at: Option<DateTime<Utc>>,
+    pub default: bool,
+    /// Whether the default branch has all of its work: every commit, or the tip of a merged
+    /// (e.g. squashed) pull request.
+    pub merged: bool,
+    /// The merged pull request whose head is the branch tip.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pull_request: Option<u64>,
+}
+
+/// Pull request with CI rollup and review decision from GraphQL.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PullRequestStatusNode {
+    pub number: u64,
+    pub title: String,
+    #[serde(default)]
+    pub author: Option<PullRequestUser>,
+    pub head_ref_name: String,
+    /// `MERGEABLE`, `CONFLICTING` or `UNKNOWN`.
+    pub mergeable: String,
+    #[serde(default)]
+    pub review_decision: Option<String>,
+    pub commits: GraphQlNodes<PullRequestCommitNode>,
+}
+
+/// Commit wrapper node in a pull request's commit connection.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PullRequestCommitNode {
+    pub commit: CommitStatusNode,
+}
+
+/// Commit node carrying the aggregated status check rollup.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitStatusNode {
+    #[serde(default)]
+    pub status_check_rollup: Option<StatusCheckRollup>,
+}
+
+/// Aggregated status of all checks on a commit.
+#[derive(Debug, Clone, Deserialize)]
+pub struct StatusCheckRollup {
+    /// `SUCCESS`, `FAILURE`, `PENDING`, `ERROR` or `EXPECTED`.
+    pub state: String,
+}
+
+/// Merge strategy for pull requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeMethod {
+    #[default]
+    Merge,
+    Squash,
+    Rebase,
+}
+
+impl std::fmt::Display for MergeMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MergeMethod::Merge => write!(f, "merge"),
+            MergeMethod::Squash => write!(f, "squash"),
+            MergeMethod::Rebase => write!(f, "rebase"),
+        }
+    }
+}
+
+/// Result of merging a pull request via the REST API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeResult {
+    #[serde(default)]
+    pub sha: Option<String>,
+    pub merged: bool,
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+/// Outcome of closing or reopening a pull request.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PullRequestStateChange {
+    /// False when the pull request already was in the requested state.
+    pub changed: bool,
+    /// URL of the comment posted along with the change.
+    pub comment_url: Option<String>,
+    /// Head branch deleted after closing.
+    pub deleted_branch: Option<String>,
+}
+
+/// Entry in a branch's merge queue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeQueueEntry {
+    pub position: u64,
+    /// `QUEUED`, `AWAITING_CHECKS`, `MERGEABLE`, `UNMERGEABLE` or `LOCKED`.
+    pub state: String,
+    /// Estimated seconds until the entry is merged.
+    #[serde(default)]
+    pub estimated_time_to_merge: Option<u64>,
+    #[serde(default)]
+    pub enqueued_at: Option<String>,
+    #[serde(default)]
+    pub pull_request: Option<QueuedPullRequest>,
+}
+
+/// Pull request referenced by a merge queue entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedPullRequest {
+    pub number: u64,
+    pub title: String,
+}
+
+/// Merge queue for a branch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeQueue {
+    #[serde(default)]
+    pub url: Option<String>,
+    pub entries: GraphQlNodes<MergeQueueEntry>,
+}
+
+/// Required status checks of a protected branch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequiredStatusChecks {
+    #[serde(default)]
+    pub strict: bool,
+    #[serde(default)]
+    pub contexts: Vec<String>,
+}
+
+/// Desired required status checks for one repository branch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RequiredChecksEntry {
+    /// Repository in `owner/repo` form.
+    pub repo: String,
+    #[serde(default = "default_branch")]
+    pub branch: String,
+    pub contexts: Vec<String>,
+}
+
+fn default_branch() -> String {
+    "main".to_string()
+}
+
+/// Result of applying required status checks to one repository branch.
+#[derive(Debug, Clone, Serialize)]
+pub struct RequiredChecksChange {
+    pub repo: String,
+    pub branch: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    /// Error message when the update failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Protection of a branch as returned by the API; absent sections are disabled.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BranchProtection {
+    #[serde(default)]
+    pub required_pull_request_reviews: Option<RequiredReviews>,
+    #[serde(default)]
+    pub required_status_checks: Option<RequiredStatusChecks>,
+    #[serde(default)]
+    pub required_linear_history: Option<EnabledFlag>,
+    #[serde(default)]
+    pub enforce_admins: Option<EnabledFlag>,
+}
+
+/// Pull request review requirements of a protected branch.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RequiredReviews {
+    #[serde(default)]
+    pub required_approving_review_count: u32,
+    #[serde(default)]
+    pub dismiss_stale_reviews: bool,
+    #[serde(default)]
+    pub require_code_owner_reviews: bool,
+}
+
+/// `{"enabled": bool}` setting of a protected branch.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub struct EnabledFlag {
+    pub enabled: bool,
+}
+
+/// Declarative branch protection, printed by `repo protection show` and read by `apply`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BranchProtectionRules {
+    /// Approving reviews required before merging; omitted to not require reviews.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required_reviews: Option<u32>,
+    #[serde(default)]
+    pub dismiss_stale_reviews: bool,
+    #[serde(default)]
+    pub require_code_owner_reviews: bool,
+    /// Status check contexts that must pass; omitted to not require checks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_checks: Option<Vec<String>>,
+    /// Require the branch to be up to date with the base before merging.
+    #[serde(default)]
+    pub strict_status_checks: bool,
+    #[serde(default)]
+    pub linear_history: bool,
+    /// Apply the rules to administrators too.
+    #[serde(default)]
+    pub enforce_admins: bool,
+}
+
+impl From<BranchProtection> for BranchProtectionRules {
+    fn from(protection: BranchProtection) -> Self {
+        let reviews = protection.required_pull_request_reviews;
+        let checks = protection.required_status_checks;
+        BranchProtectionRules {
+            required_reviews: reviews.as_ref().map(|r| r.required_approving_review_count),
+            dismiss_stale_reviews: reviews.as_ref().is_some_and(|r| r.dismiss_stale_reviews),
+            require_code_owner_reviews: reviews
+                .as_ref()
+                .is_some_and(|r| r.require_code_owner_reviews),
+            strict_status_checks: checks.as_ref().is_some_and(|c| c.strict),
+            status_checks: checks.map(|c| c.contexts),
+            linear_history: protection.required_linear_history.is_some_and(|f| f.enabled),
+            enforce_admins: protection.enforce_admins.is_some_and(|f| f.enabled),
+        }
+    }
+}
+
+/// Workspace manifest (`gho-workspace.toml`): the repositories a team checks out together.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkspaceManifest {
+    /// Organization the repositories are discovered in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub org: Option<String>,
+    /// Topic the repositories are discovered by.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+    #[serde(default, rename = "repo")]
+    pub repos: Vec<WorkspaceRepo>,
+}
+
+/// Repository of a workspace and where it is checked out.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkspaceRepo {
+    /// Repository in `owner/repo` form.
+    pub repo: String,
+    /// Checkout directory, relative to the manifest.
+    pub path: String,
+}
+
+/// Repositories `workspace discover` added to and removed from a manifest.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct WorkspaceDiscovery {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    /// Repositories in the manifest afterwards.
+    pub total: usize,
+}
+
+/// Result of checking out one workspace repository with `workspace sync`.
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkspaceCheckout {
+    pub repo: String,
+    pub dir: PathBuf,
+    /// Whether the repository was cloned; false when it was already checked out.
+    pub cloned: bool,
+    /// Error message when the clone failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Record of the clones made by `repo clone --org` or `workspace sync` (`gho-clones.json`),
+/// replayed with `repo clone --from-manifest`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CloneManifest {
+    pub generated_at: DateTime<Utc>,
+    pub repos: Vec<ClonedRepo>,
+}
+
+/// One checkout of a [`CloneManifest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClonedRepo {
+    /// `owner/repo`.
+    pub repo: String,
+    /// Commit checked out when the manifest was written.
+    pub commit: String,
+    /// Checkout directory, relative to the manifest's directory when it lies below it.
+    pub path: PathBuf,
+    /// Account that cloned it.
+    pub account: String,
+}
+
+/// Result of restoring one checkout with `repo clone --from-manifest`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ManifestCheckout {
+    pub repo: String,
+    pub dir: PathBuf,
+    /// Whether the repository was cloned; false when the directory already existed.
+    pub cloned: bool,
+    /// Error message when the clone or checkout failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Result of running a command in one repository with `repo foreach`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ForeachResult {
+    pub repo: String,
+    /// Directory the command ran in.
+    pub dir: PathBuf,
+    /// Whether the repository was cloned for this run.
+    pub cloned: bool,
+    /// Error message when the clone failed or the command did not succeed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Result of applying branch protection to one repository.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProtectionChange {
+    pub repo: String,
+    pub branch: String,
+    /// Rules that differed, as `name: old -> new`; empty when already compliant.
+    pub changed: Vec<String>,
+    /// Error message when the repository could not be updated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Release information from GitHub API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Release {
+    pub id: u64,
+    pub tag_name: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub prerelease: bool,
+    #[serde(default)]
+    pub html_url: String,
+    #[serde(default)]
+    pub published_at: Option<String>,
+    /// Hypermedia upload URL, e.g. `https://uploads.github.com/.../assets{?name,label}`.
+    pub upload_url: String,
+    #[serde(default)]
+    pub assets: Vec<ReleaseAsset>,
+}
+
+/// Request body for creating a repository.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct NewRepository {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub private: bool,
+    /// Create an initial commit with a README, so the default branch exists.
+    pub auto_init: bool,
+}
+
+/// Request body for creating a release.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct NewRelease {
+    pub tag_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    pub draft: bool,
+    pub prerelease: bool,
+    /// Let GitHub generate notes from merged pull requests when `body` is empty.
+    pub generate_release_notes: bool,
+}
+
+/// Gist from the GitHub API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Gist {
+    pub id: String,
+    pub html_url: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub public: bool,
+    /// Files keyed by file name.
+    #[serde(default)]
+    pub files: BTreeMap<String, GistFile>,
+    pub updated_at: String,
+}
+
+/// File of a gist, without its content.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GistFile {
+    pub filename: String,
+    #[serde(default)]
+    pub size: u64,
+}
+
+/// Request body for creating a gist.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct NewGist {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub public: bool,
+    /// File contents keyed by file name.
+    pub files: BTreeMap<String, NewGistFile>,
+}
+
+/// Content of a file in a new gist.
+#[derive(Debug, Clone, Serialize)]
+pub struct NewGistFile {
+    pub content: String,
+}
+
+/// File attached to a release.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseAsset {
+    pub id: u64,
+    pub name: String,
+    #[serde(default)]
+    pub size: u64,
+    /// `uploaded` once complete; `starter` for an interrupted upload.
+    #[serde(default)]
+    pub state: Option<String>,
+    #[serde(default)]
+    pub browser_download_url: Option<String>,
+}
+
+/// Outcome of uploading one release asset.
+#[derive(Debug, Clone, Serialize)]
+pub struct AssetUploadResult {
+    pub name: String,
+    pub attempts: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Error message when the upload failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Outcome of downloading one release asset.
+#[derive(Debug, Clone, Serialize)]
+pub struct AssetDownloadResult {
+    pub name: String,
+    pub path: PathBuf,
+    /// The file already existed and was left untouched.
+    pub skipped: bool,
+    /// Error message when the download failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Item identified by name (secret, variable, ...).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NamedItem {
+    pub name: String,
+}
+
+/// Which repositories of an organization can use an organization secret or variable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SecretVisibility {
+    /// Every repository of the organization.
+    All,
+    /// Private and internal repositories only.
+    #[default]
+    Private,
+    /// Repositories picked on GitHub.
+    Selected,
+}
+
+impl std::fmt::Display for SecretVisibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::All => write!(f, "all"),
+            Self::Private => write!(f, "private"),
+            Self::Selected => write!(f, "selected"),
+        }
+    }
+}
+
+/// Actions secret. Its value is never readable.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActionsSecret {
+    pub name: String,
+    pub updated_at: DateTime<Utc>,
+    /// Set for organization secrets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<SecretVisibility>,
+}
+
+/// Actions configuration variable.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActionsVariable {
+    pub name: String,
+    pub value: String,
+    pub updated_at: DateTime<Utc>,
+    /// Set for organization variables.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<SecretVisibility>,
+}
+
+/// Public key secrets must be encrypted with before they are sent.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SecretsPublicKey {
+    pub key_id: String,
+    /// Base64-encoded Curve25519 public key.
+    pub key: String,
+}
+
+/// Actions secrets list response from GitHub API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SecretsResponse {
+    pub total_count: usize,
+    pub secrets: Vec<ActionsSecret>,
+}
+
+/// Actions variables list response from GitHub API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct VariablesResponse {
+    pub total_count: usize,
+    pub variables: Vec<ActionsVariable>,
+}
+
+/// Billing usage report from the enhanced billing platform API.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BillingUsageResponse {
+    #[serde(default)]
+    pub usage_items: Vec<BillingUsageItem>,
+}
+
+/// A single metered usage line (one product/SKU on one day for one repository).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BillingUsageItem {
+    /// ISO 8601 timestamp of the usage day.
+    pub date: String,
+    /// e.g. `actions`, `packages`.
+    pub product: String,
+    #[serde(default)]
+    pub sku: String,
+    pub quantity: f64,
+    /// e.g. `minutes`, `GigabyteHours`.
+    pub unit_type: String,
+    #[serde(default)]
+    pub net_amount: f64,
+    #[serde(default)]
+    pub repository_name: Option<String>,
+}
+
+/// Actions consumption aggregated for one repository.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ActionsUsage {
+    pub repo: String,
+    pub minutes: f64,
+    pub storage_gb_hours: f64,
+    /// Net cost in USD after discounts.
+    pub net_amount: f64,
+}
+
+/// Search sections returned by the `pr status` dashboard query.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PullRequestDashboardData {
+    pub created: GraphQlNodes<PullRequestSearchNode>,
+    pub review_requested: GraphQlNodes<PullRequestSearchNode>,
+    pub assigned: GraphQlNodes<PullRequestSearchNode>,
+}
+
+/// `search` GraphQL payload used by `pr list --org`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PullRequestSearchData {
+    pub search: GraphQlPage<PullRequestSearchNode>,
+}
+
+/// Pull request search result with its repository.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PullRequestSearchNode {
+    pub repository: RepositoryNameNode,
+    #[serde(default, rename = "updatedAt")]
+    pub updated_at: Option<DateTime<Utc>>,
+    #[serde(flatten)]
+    pub pull_request: PullRequestStatusNode,
+}
+
+/// Repository reference in GraphQL results.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepositoryNameNode {
+    pub name_with_owner: String,
+}
+
+/// Pull request output tagged with its repository.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepoPullRequest {
+    pub repo: String,
+    #[serde(flatten)]
+    pub pull_request: PullRequestOutput,
+}
+
+/// Pull requests involving the current user, by relationship.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PullRequestDashboard {
+    pub created: Vec<RepoPullRequest>,
+    pub review_requested: Vec<RepoPullRequest>,
+    pub assigned: Vec<RepoPullRequest>,
+}
+
+/// `viewer.contributionsCollection` GraphQL payload used by `gho contrib`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ContributionsData {
+    pub viewer: ContributionsViewer,
+}
+
+/// Authenticated user with their contributions over a period.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContributionsViewer {
+    pub login: String,
+    pub contributions_collection: ContributionsCollection,
+}
+
+/// Contribution totals from the GraphQL contributions collection.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContributionsCollection {
+    pub total_commit_contributions: u64,
+    pub total_pull_request_contributions: u64,
+    pub total_pull_request_review_contributions: u64,
+    pub total_issue_contributions: u64,
+    /// Contributions to private repositories the token cannot see.
+    pub restricted_contributions_count: u64,
+    pub commit_contributions_by_repository: Vec<RepositoryCommitContributions>,
+}
+
+/// Commit contributions to one repository.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RepositoryCommitContributions {
+    pub repository: RepositoryNameNode,
+    pub contributions: GraphQlCount,
+}
+
+/// GraphQL connection where only the total is requested.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQlCount {
+    pub total_count: u64,
+}
+
+/// Contributions of one account since a date (`gho contrib`).
+#[derive(Debug, Clone, Serialize)]
+pub struct Contributions {
+    pub username: String,
+    pub since: NaiveDate,
+    #[serde(flatten)]
+    pub counts: Option<ContributionCounts>,
+    /// Error message when the account's contributions could not be read.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Contribution totals, with commits broken down by repository.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ContributionCounts {
+    pub commits: u64,
+    pub pull_requests: u64,
+    pub reviews: u64,
+    pub issues: u64,
+    /// Contributions to private repositories the token cannot see.
+    pub private: u64,
+    /// Most commits first.
+    pub repositories: Vec<RepoCommitCount>,
+}
+
+/// Commits to one repository in a contribution summary.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RepoCommitCount {
+    pub repo: String,
+    pub commits: u64,
+}
+
+impl From<ContributionsCollection> for ContributionCounts {
+    fn from(collection: ContributionsCollection) -> Self {
+        let mut repositories: Vec<RepoCommitCount> = collection
+            .commit_contributions_by_repository
+            .into_iter()
+            .map(|c| RepoCommitCount {
+                repo: c.repository.name_with_owner,
+                commits: c.contributions.total_count,
+            })
+            .collect();
+        repositories.sort_by(|a, b| b.commits.cmp(&a.commits).then_with(|| a.repo.cmp(&b.repo)));
+        Self {
+            commits: collection.total_commit_contributions,
+            pull_requests: collection.total_pull_request_contributions,
+            reviews: collection.total_pull_request_review_contributions,
+            issues: collection.total_issue_contributions,
+            private: collection.restricted_contributions_count,
+            repositories,
+        }
+    }
+}
+
+/// Payload format of webhook deliveries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookContentType {
+    /// `application/json`
+    #[default]
+    Json,
+    /// `application/x-www-form-urlencoded`
+    Form,
+}
+
+/// Delivery settings of a webhook. GitHub masks the secret as `********` when reading.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub content_type: Option<WebhookContentType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    /// `"1"` when TLS certificates are not verified.
+    #[serde(default)]
+    pub insecure_ssl: Option<String>,
+}
+
+/// Response to a webhook's most recent delivery.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebhookResponse {
+    #[serde(default)]
+    pub code: Option<u16>,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+/// Repository webhook.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Webhook {
+    pub id: u64,
+    pub active: bool,
+    pub events: Vec<String>,
+    pub config: WebhookConfig,
+    #[serde(default)]
+    pub last_response: Option<WebhookResponse>,
+}
+
+/// Body of a webhook creation request.
+#[derive(Debug, Clone, Serialize)]
+pub struct NewWebhook {
+    /// Always `web` for repository webhooks.
+    pub name: String,
+    pub active: bool,
+    pub events: Vec<String>,
+    pub config: WebhookConfig,
+}
+
+/// One delivery attempt of a webhook.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HookDelivery {
+    pub id: u64,
+    pub delivered_at: DateTime<Utc>,
+    pub redelivery: bool,
+    /// Seconds the receiver took to respond.
+    pub duration: f64,
+    /// e.g. `OK` or `Invalid HTTP Response: 503`.
+    pub status: String,
+    /// HTTP status of the response; 0 when there was none.
+    pub status_code: u16,
+    pub event: String,
+    #[serde(default)]
+    pub action: Option<String>,
+}
+
+/// Self-hosted Actions runner.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Runner {
+    pub id: u64,
+    pub name: String,
+    pub os: String,
+    /// `online` or `offline`.
+    pub status: String,
+    pub busy: bool,
+    #[serde(default)]
+    pub labels: Vec<NamedItem>,
+}
+
+/// Page of runners from the Actions API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RunnersResponse {
+    pub total_count: usize,
+    pub runners: Vec<Runner>,
+}
+
+/// Short-lived token for `config.sh --token` when registering a runner.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunnerRegistrationToken {
+    pub token: String,
+    pub expires_at: String,
+}
+
+/// GitHub Packages registry type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PackageType {
+    Container,
+    Npm,
+    Maven,
+    Rubygems,
+    Nuget,
+    Docker,
+}
+
+impl PackageType {
+    /// Value for the packages API `package_type` parameter and path segment.
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            PackageType::Container => "container",
+            PackageType::Npm => "npm",
+            PackageType::Maven => "maven",
+            PackageType::Rubygems => "rubygems",
+            PackageType::Nuget => "nuget",
+            PackageType::Docker => "docker",
+        }
+    }
+}
+
+/// Package in GitHub Packages.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Package {
+    pub id: u64,
+    pub name: String,
+    pub package_type: PackageType,
+    pub visibility: String,
+    #[serde(default)]
+    pub version_count: u64,
+    pub html_url: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Version of a package; for containers, `name` is the image digest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageVersion {
+    pub id: u64,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub metadata: Option<PackageVersionMetadata>,
+}
+
+impl PackageVersion {
+    /// Container image tags; empty for untagged images and non-container packages.
+    pub fn tags(&self) -> &[String] {
+        self.metadata
+            .as_ref()
+            .and_then(|m| m.container.as_ref())
+            .map(|c| c.tags.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+/// Registry-specific package version metadata.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageVersionMetadata {
+    #[serde(default)]
+    pub container: Option<ContainerMetadata>,
+}
+
+/// Container image metadata.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContainerMetadata {
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// Outcome of syncing labels to one repository.
+#[derive(Debug, Clone, Serialize)]
+pub struct LabelSyncChange {
+    pub repo: String,
+    pub created: Vec<String>,
+    pub updated: Vec<String>,
+    pub deleted: Vec<String>,
+    /// Error message when the repository could not be synced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Raw issue timeline event; which fields are set depends on `event`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TimelineEvent {
+    #[serde(default)]
+    pub event: String,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    /// Set on `reviewed` events instead of `created_at`.
+    #[serde(default)]
+    pub submitted_at: Option<String>,
+    #[serde(default)]
+    pub actor: Option<PullRequestUser>,
+    /// Reviewer on `reviewed` events, commenter on `commented` events.
+    #[serde(default)]
+    pub user: Option<PullRequestUser>,
+    /// Git author on `committed` events.
+    #[serde(default)]
+    pub author: Option<CommitAuthor>,
+    #[serde(default)]
+    pub sha: Option<String>,
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub body: Option<String>,
+    /// Review state on `reviewed` events.
+    #[serde(default)]
+    pub state: Option<String>,
+    #[serde(default)]
+    pub label: Option<Label>,
+    #[serde(default)]
+    pub requested_reviewer: Option<PullRequestUser>,
+    #[serde(default)]
+    pub rename: Option<TimelineRename>,
+}
+
+/// Git author of a commit.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CommitAuthor {
+    pub name: String,
+    pub date: String,
+}
+
+/// Title change on a `renamed` event.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TimelineRename {
+    pub from: String,
+    pub to: String,
+}
+
+/// Condensed, display-ready timeline entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TimelineEntry {
+    pub time: String,
+    pub actor: String,
+    /// Event kind, e.g. `commit`, `review`, `force-push`, `label`.
+    pub kind: String,
+    pub summary: String,
+}
+
+/// `/rate_limit` response from GitHub API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RateLimitResponse {
+    pub resources: BTreeMap<String, RateLimit>,
+}
+
+/// Quota of one rate limit resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimit {
+    pub limit: u64,
+    pub remaining: u64,
+    pub used: u64,
+    /// Unix timestamp when the quota resets.
+    pub reset: i64,
+}
+
+/// Rate limits of one account.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountRateLimits {
+    pub account: String,
+    /// Quotas by resource (`core`, `search`, `graphql`).
+    pub resources: BTreeMap<String, RateLimit>,
+    /// Error message when the limits could not be fetched.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Request body for opening a pull request.
+#[derive(Debug, Clone, Serialize)]
+pub struct NewPullRequest {
+    pub title: String,
+    pub head: String,
+    pub base: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    pub draft: bool,
+}
+
+/// Fields to change on a pull request; `None` leaves a field as it is.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PullRequestUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    /// Branch to merge into.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+}
+
+/// Users and teams (by slug) to request or un-request reviews from.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ReviewRequest {
+    pub reviewers: Vec<String>,
+    pub team_reviewers: Vec<String>,
+}
+
+/// Outcome of `pr fanout` for one repository.
+#[derive(Debug, Clone, Serialize)]
+pub struct FanoutResult {
+    pub repo: String,
+    /// URL of the opened pull request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// The change produced no diff, so nothing was pushed.
+    pub unchanged: bool,
+    /// Error message when the repository failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Notification thread from the GitHub inbox.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Notification {
+    pub id: String,
+    pub reason: String,
+    pub unread: bool,
+    pub updated_at: String,
+    pub subject: NotificationSubject,
+    pub repository: PullRequestRepo,
+}
+
+/// What a notification thread is about.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationSubject {
+    pub title: String,
+    /// `PullRequest`, `Issue`, `Release`, `CheckSuite`, ...
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// API URL of the subject; absent for check suites and discussions.
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+/// Which notification threads to list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NotificationFilter {
+    /// Only threads not yet marked as read.
+    pub unread: bool,
+    /// Only threads the user participates in or is mentioned in.
+    pub participating: bool,
+}
+
+/// Cached result written by `gho daemon`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot<T> {
+    pub refreshed_at: DateTime<Utc>,
+    pub data: T,
+}
+
+/// Age of one `gho daemon` snapshot.
+#[derive(Debug, Clone, Serialize)]
+pub struct SnapshotStatus {
+    pub name: String,
+    /// `None` when the snapshot has not been written yet.
+    pub refreshed_at: Option<DateTime<Utc>>,
+}
+
+/// State reported by `gho daemon status`.
+#[derive(Debug, Clone, Serialize)]
+pub struct DaemonStatus {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+    pub running: bool,
+    pub snapshots: Vec<SnapshotStatus>,
+}
+
+/// Commit returned by the REST API (`/commits/{ref}`, `/pulls/{number}/commits`).
+#[derive(Debug, Clone, Deserialize)]
+pub struct Commit {
+    pub sha: String,
+    pub commit: CommitDetail,
+    /// GitHub user the commit is attributed to, when the email is linked to one.
+    #[serde(default)]
+    pub author: Option<PullRequestUser>,
+    #[serde(default)]
+    pub html_url: Option<String>,
+}
+
+/// Git data of a [`Commit`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct CommitDetail {
+    pub message: String,
+    #[serde(default)]
+    pub author: Option<CommitAuthor>,
+    #[serde(default)]
+    pub verification: Option<CommitVerification>,
+}
+
+/// Signature verification of a commit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitVerification {
+    pub verified: bool,
+    /// `valid`, `unsigned`, `unknown_key`, `bad_email`, `expired_key`, ...
+    pub reason: String,
+}
+
+/// Commit with its signature status, as shown by `commit view` and `pr view`.
+#[derive(Debug, Clone, Serialize)]
+pub struct CommitSignature {
+    pub sha: String,
+    pub subject: String,
+    pub author: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    pub verified: bool,
+    pub reason: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// Result of `pr ready-to-merge`.
+#[derive(Debug, Clone, Serialize)]
+pub struct MergeReadiness {
+    pub number: u64,
+    pub ready: bool,
+    /// Reasons the pull request cannot be merged yet.
+    pub blockers: Vec<String>,
+}
+
+/// Whether commands perform their side effects or only describe them (`--dry-run`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExecutionMode {
+    #[default]
+    Live,
+    DryRun,
+}
+
+impl ExecutionMode {
+    pub fn from_dry_run(dry_run: bool) -> Self {
+        if dry_run { ExecutionMode::DryRun } else { ExecutionMode::Live }
+    }
+
+    pub fn is_dry_run(self) -> bool {
+        self == ExecutionMode::DryRun
+    }
+}
+
+/// Result of a command run under an [`ExecutionMode`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Execution<T> {
+    /// The side effects happened.
+    Done(T),
+    /// Dry run: the steps that would have happened (commands, API calls, paths).
+    Planned(Vec<String>),
+}
+
+/// Result of `account use`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SwitchResult {
+    /// The account that is now active.
+    pub id: String,
+    pub username: String,
+    /// The account that was active before, if any.
+    pub previous: Option<String>,
+    /// What `--ssh-agent` changed in the agent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ssh_agent: Option<SshAgentSwitch>,
+}
+
+/// SSH keys swapped in the agent by `account use --ssh-agent`.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub struct SshAgentSwitch {
+    /// Key of the new account, unless it has none.
+    pub added: Option<String>,
+    /// Keys of other accounts the agent held.
+    pub removed: Vec<String>,
+}
+
+/// Result of `repo clone`, for a single repository or a whole organization.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub struct CloneReport {
+    /// Repositories cloned: `owner/repo` for a single clone, names for an organization.
+    pub cloned: Vec<String>,
+    /// Checkout directory of a single clone.
+    pub dir: Option<PathBuf>,
+    /// Clone manifest written by an organization clone.
+    pub manifest: Option<PathBuf>,
+    /// Repositories of an organization whose directory already existed.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<String>,
+    /// Repositories of an organization that failed to clone.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub failed: Vec<CloneFailure>,
+}
+
+/// A repository `repo clone --org` could not clone.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CloneFailure {
+    pub repo: String,
+    /// Why, usually git's last line of output.
+    pub error: String,
+}
+
+/// Working state of one local clone (`gho repo status`).
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub struct CloneStatus {
+    /// `owner/repo` of the `origin` remote.
+    pub repo: String,
+    pub path: PathBuf,
+    /// Checked-out branch; `None` on a detached HEAD.
+    pub branch: Option<String>,
+    /// Remote-tracking branch of `branch`, e.g. `origin/main`.
+    pub upstream: Option<String>,
+    /// Commits on the branch but not its upstream, as of the last fetch.
+    pub ahead: usize,
+    /// Commits on the upstream but not the branch, as of the last fetch.
+    pub behind: usize,
+    /// Commits of HEAD no remote branch has; `ahead` when there is an upstream.
+    pub unpushed: usize,
+    /// Staged or modified tracked files, including conflicts.
+    pub changed: usize,
+    pub untracked: usize,
+    /// Why git could not report on the clone.
+    pub error: Option<String>,
+}
+
+impl CloneStatus {
+    /// Whether the clone holds work that exists nowhere else: local changes or unpushed commits.
+    pub fn in_flight(&self) -> bool {
+        self.changed > 0 || self.untracked > 0 || self.unpushed > 0
+    }
+}
+
+/// One step of `gho selftest`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SelftestStep {
+    pub step: String,
+    /// Why the step failed; `None` when it succeeded.
+    pub error: Option<String>,
+    pub elapsed_ms: u64,
+}
+
+/// Result of `gho selftest`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SelftestReport {
+    /// The throwaway repository (`org/name`).
+    pub repo: String,
+    /// Steps in the order they ran; steps after a failure are skipped, except cleanup.
+    pub steps: Vec<SelftestStep>,
+    /// Whether the repository was deleted again (or never created).
+    pub cleaned_up: bool,
+}
+
+impl SelftestReport {
+    /// Number of failed steps.
+    pub fn failed(&self) -> usize {
+        self.steps.iter().filter(|s| s.error.is_some()).count()
+    }
+}
+
+/// Progress of one item of a bulk operation, written as NDJSON with `--progress json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "lowercase")]
+pub enum ProgressEvent {
+    Started {
+        operation: String,
+        item: String,
+        /// 1-based position of the item.
+        index: usize,
+        total: usize,
+    },
+    Updated {
+        operation: String,
+        item: String,
+        message: String,
+    },
+    Completed {
+        operation: String,
+        item: String,
+        outcome: ProgressOutcome,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+}
+
+/// How an item of a bulk operation ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProgressOutcome {
+    Succeeded,
+    Skipped,
+    Failed,
+}
+
+/// How a `gho doctor` check ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DoctorStatus {
+    Ok,
+    Warning,
+    Failed,
+}
+
+/// Result of one `gho doctor` check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DoctorCheck {
+    /// What was checked, e.g. `api.github.com:443` or `ssh (work)`.
+    pub check: String,
+    pub status: DoctorStatus,
+    pub detail: String,
+}
+
+/// Organization the authenticated user belongs to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Organization {
+    pub login: String,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Role of a member in an organization.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OrgRole {
+    Admin,
+    Member,
+}
+
+impl OrgRole {
+    /// Value of the `role` query parameter of the members API.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrgRole::Admin => "admin",
+            OrgRole::Member => "member",
+        }
+    }
+}
+
+/// Member of an organization.
+#[derive(Debug, Clone, Serialize)]
+pub struct OrgMember {
+    pub login: String,
+    pub role: OrgRole,
+}
+
+/// Team of an organization.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Team {
+    #[serde(default)]
+    pub id: u64,
+    pub name: String,
+    pub slug: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// `closed` (visible to all members) or `secret`.
+    #[serde(default)]
+    pub privacy: Option<String>,
+}
+
+/// A repository renamed or transferred by `repo rename` or `repo transfer`.
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoMove {
+    /// `owner/repo` before the move.
+    pub from: String,
+    /// `owner/repo` after the move.
+    pub to: String,
+    pub html_url: String,
+    /// Transfer to a user that waits for them to accept it; the repository keeps `from` until
+    /// then.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub pending: bool,
+    /// Local clone whose `origin` now points at the new name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clone: Option<PathBuf>,
+}
+
+/// Repository a team can access, with the team's role on it.
+#[derive(Debug, Clone, Serialize)]
+pub struct TeamRepository {
+    pub full_name: String,
+    pub permission: RepoPermission,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(id: &str, kind: AccountKind) -> Account {
+        Account {
+            id: id.to_string(),
+            kind,
+            username: format!("{id}-user"),
+            default_org: None,
+            protocol: Protocol::Ssh,
+            clone_dir: None,
+            clone_layout: CloneLayout::Flat,
+            clone_options: CloneOptions::default(),
+            browser: None,
+            token_command: None,
+            token_expires_at: None,
+            host: None,
+            protected: false,
+            git: None,
+        }
+    }
+
+    #[test]
+    fn register_activates_first_account_only() {
+        let mut accounts = AccountsFile::default();
+        accounts.register(account("home", AccountKind::Personal)).unwrap();
+        accounts.register(account("office", AccountKind::Work)).unwrap();
+
+        assert_eq!(accounts.active_account_id.as_deref(), Some("home"));
+        assert_eq!(accounts.work.len(), 1);
+    }
+
+    #[test]
+    fn clone_options_fall_back_to_account_defaults() {
+        let defaults =
+            CloneOptions { depth: Some(1), filter: Some("blob:none".into()), ..Default::default() };
+        let flags =
+            CloneOptions { depth: Some(50), branch: Some("dev".into()), ..Default::default() };
+        assert_eq!(
+            flags.or(&defaults).args(),
+            ["--depth=50", "--filter=blob:none", "--branch=dev"]
+        );
+        assert!(CloneOptions::default().or(&CloneOptions::default()).args().is_empty());
+    }
+
+    #[test]
+    fn register_rejects_duplicate_id() {
+        let mut accounts = AccountsFile::default();
+        accounts.register(account("home", AccountKind::Personal)).unwrap();
+        let result = accounts.register(account("home", AccountKind::Work));
+        assert!(matches!(result, Err(AppError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn set_group_validates_members() {
+        let mut accounts = AccountsFile::default();
+        accounts.register(account("home", AccountKind::Personal)).unwrap();
+        let result = accounts.set_group("clients", vec!["home".into(), "ghost".into()]);
+        assert!(matches!(result, Err(AppError::AccountNotFound(id)) if id == "ghost"));
+
+        accounts.set_group("clients", vec!["home".into()]).unwrap();
+        let members = accounts.group_accounts("clients").unwrap();
+        assert_eq!(members[0].id, "home");
+    }
+
+    #[test]
+    fn remove_account_prunes_groups() {
+        let mut accounts = AccountsFile::default();
+        accounts.register(account("a", AccountKind::Work)).unwrap();
+        accounts.register(account("b", AccountKind::Work)).unwrap();
+        accounts.set_group("both", vec!["a".into(), "b".into()]).unwrap();
+        accounts.set_group("only-a", vec!["a".into()]).unwrap();
+
+        accounts.remove_account("a");
+
+        assert_eq!(accounts.groups.get("both"), Some(&vec!["b".to_string()]));
+        assert!(!accounts.groups.contains_key("only-a"));
+    }
+
+    #[test]
+    fn sudo_grant_is_scoped_to_session_and_expires() {
+        let now = Utc::now();
+        let mut state = StateFile::default();
+        state.grant_sudo("admin", 42, now);
+
+        assert!(state.has_sudo_grant("admin", 42, now));
+        assert!(!state.has_sudo_grant("admin", 7, now));
+        assert!(!state.has_sudo_grant("other", 42, now));
+        let later = now + Duration::minutes(SUDO_GRANT_TTL_MINUTES + 1);
+        assert!(!state.has_sudo_grant("admin", 42, later));
+    }
+
+    #[test]
+    fn repo_permission_is_the_highest_flag() {
+        let flags: RepositoryPermissions =
+            serde_json::from_str(r#"{"admin":false,"push":true,"pull":true}"#).unwrap();
+        assert_eq!(RepoPermission::from_flags(&flags), RepoPermission::Write);
+        assert_eq!(
+            RepoPermission::from_flags(&RepositoryPermissions::default()),
+            RepoPermission::None
+        );
+        assert_eq!(RepoPermission::Maintain.to_string(), "maintain");
+    }
+
+    #[test]
+    fn branch_protection_converts_to_rules() {
+        let protection: BranchProtection = serde_json::from_str(
+            r#"{
+                "required_pull_request_reviews": {"required_approving_review_count": 2},
+                "required_status_checks": {"strict": true, "contexts": ["build"]},
+                "required_linear_history": {"enabled": true},
+                "enforce_admins": {"enabled": false}
+            }"#,
+        )
+        .unwrap();
+        let rules = BranchProtectionRules::from(protection);
+        assert_eq!(rules.required_reviews, Some(2));
+        assert_eq!(rules.status_checks, Some(vec!["build".to_string()]));
+        assert!(rules.strict_status_checks && rules.linear_history && !rules.enforce_admins);
+        assert_eq!(
+            BranchProtectionRules::from(BranchProtection::default()),
+            BranchProtectionRules::default()
+        );
+    }
+
+    #[test]
+    fn repo_filter_matches_every_condition() {
+        let repo: Repository = serde_json::from_value(serde_json::json!({
+            "name": "api",
+            "full_name": "acme/api",
+            "html_url": "https://github.com/acme/api",
+            "ssh_url": "git@github.com:acme/api.git",
+            "clone_url": "https://github.com/acme/api.git",
+            "owner": {"login": "acme"},
+            "language": "Rust",
+            "visibility": "private",
+            "topics": ["payments"],
+        }))
+        .unwrap();
+
+        assert!(RepoFilter::default().matches(&repo));
+        let filter = RepoFilter {
+            visibility: Some(RepoVisibility::Private),
+            fork: Some(false),
+            exclude_archived: true,
+            language: Some("rust".into()),
+            topic: Some("payments".into()),
+        };
+        assert!(filter.matches(&repo));
+        assert!(!RepoFilter { fork: Some(true), ..filter.clone() }.matches(&repo));
+        assert!(!RepoFilter { topic: Some("web".into()), ..filter.clone() }.matches(&repo));
+        assert!(!RepoFilter { visibility: Some(RepoVisibility::Public), ..filter }.matches(&repo));
+    }
+
+    #[test]
+    fn repo_filter_prefers_visibility_for_org_type() {
+        let forks = RepoFilter { fork: Some(true), ..Default::default() };
+        assert_eq!(forks.org_type_param(), Some("forks"));
+        let private = RepoFilter { visibility: Some(RepoVisibility::Private), ..forks };
+        assert_eq!(private.org_type_param(), Some("private"));
+        assert_eq!(RepoFilter::default().org_type_param(), None);
+    }
+
+    #[test]
+    fn touch_repo_moves_repo_to_front_without_duplicates() {
+        let mut state = StateFile::default();
+        state.touch_repo("acme/api");
+        state.touch_repo("acme/web");
+        state.touch_repo("Acme/API");
+
+        assert_eq!(state.recent_repos, vec!["Acme/API", "acme/web"]);
+        assert_eq!(state.last_repo.as_deref(), Some("Acme/API"));
+        for i in 0..RECENT_REPO_LIMIT {
+            state.touch_repo(&format!("acme/r{i}"));
+        }
+        assert_eq!(state.recent_repos.len(), RECENT_REPO_LIMIT);
+    }
+
+    #[test]
+    fn set_active_requires_existing_account() {
+        let mut accounts = AccountsFile::default();
+        assert!(matches!(accounts.set_active("ghost"), Err(AppError::AccountNotFound(_))));
+
+        accounts.register(account("home", AccountKind::Personal)).unwrap();
+        accounts.register(account("office", AccountKind::Work)).unwrap();
+        accounts.set_active("office").unwrap();
+        assert_eq!(accounts.active_account().map(|a| a.id.as_str()), Some("office"));
+    }
 }
@@ -0,0 +1,497 @@
+//! Presenter of command results.
+//!
+//! Commands return typed results instead of printing. [`present`] renders them as the
+//! human-readable lines of a [`Render`] implementation or, with `--json` (or the `json` output
+//! setting), as JSON, so embedders get the same values the CLI prints. List commands also take a
+//! `--format` [`Template`], rendered per item with [`present_each`]. Colors and emoji follow
+//! [`style`].
+
+pub mod style;
+pub mod template;
+
+pub use template::Template;
+
+use crate::error::AppError;
+use crate::models::{
+    ActionsSecret, ActionsVariable, AuthStatus, Branch, CloneReport, CloneStatus, Execution,
+    HookDelivery, Identity, RepoPullRequest, SelftestReport, SwitchResult, Webhook,
+};
+use serde::Serialize;
+use std::fmt::Write as _;
+
+/// A command result with a human-readable form; the JSON form is its `Serialize` output.
+pub trait Render: Serialize {
+    /// Lines to print, each ending with a newline.
+    fn render(&self) -> String;
+}
+
+/// Print `result` as pretty JSON when `json` is set, otherwise in its human-readable form.
+pub fn present<T: Render>(result: &T, json: bool) -> Result<(), AppError> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(result)?);
+    } else {
+        print!("{}", style::line(&result.render()));
+    }
+    Ok(())
+}
+
+/// Print one line per item, rendered with `template` from the item's JSON form.
+pub fn present_each<T: Serialize>(items: &[T], template: &Template) -> Result<(), AppError> {
+    for item in items {
+        println!("{}", template.render(item)?);
+    }
+    Ok(())
+}
+
+/// [`present`] for commands that honor `--dry-run`; a plan prints its steps, or in JSON
+/// `{"planned": [...]}`.
+pub fn present_execution<T: Render>(execution: &Execution<T>, json: bool) -> Result<(), AppError> {
+    match execution {
+        Execution::Done(result) => present(result, json),
+        Execution::Planned(steps) if json => {
+            let plan = serde_json::json!({ "planned": steps });
+            println!("{}", serde_json::to_string_pretty(&plan)?);
+            Ok(())
+        }
+        Execution::Planned(steps) => {
+            print!("{}", style::line(&plan(steps)));
+            Ok(())
+        }
+    }
+}
+
+/// The steps of a dry run, one `🔍` line each.
+pub fn plan(steps: &[String]) -> String {
+    steps.iter().map(|step| format!("🔍 {step}\n")).collect()
+}
+
+impl Render for SwitchResult {
+    fn render(&self) -> String {
+        let mut out = format!("✅ Switched to account '{}'\n", self.id);
+        if let Some(agent) = &self.ssh_agent {
+            for key in &agent.removed {
+                let _ = writeln!(out, "🔑 Removed {key} from ssh-agent");
+            }
+            match &agent.added {
+                Some(key) => {
+                    let _ = writeln!(out, "🔑 Added {key} to ssh-agent");
+                }
+                None => out.push_str(
+                    "⚠️  No SSH key for this account (account setup-gitconfig --ssh-key)\n",
+                ),
+            }
+        }
+        out
+    }
+}
+
+impl Render for CloneReport {
+    fn render(&self) -> String {
+        let mut out = String::new();
+        let (skipped, failed) = (self.skipped.len(), self.failed.len());
+        match (&self.dir, self.cloned.len()) {
+            (Some(_), 1) => {
+                let _ = writeln!(out, "✅ Cloned '{}'", self.cloned[0]);
+            }
+            (_, 0) if skipped + failed == 0 => out.push_str("No repositories cloned.\n"),
+            (_, cloned) => {
+                let icon = if failed > 0 { "⚠️ " } else { "✅" };
+                let _ = writeln!(
+                    out,
+                    "{icon} Cloned {cloned} of {} repositories ({skipped} skipped, {failed} failed):",
+                    cloned + skipped + failed
+                );
+                let rows: Vec<(&str, &str, &str)> = (self.cloned.iter())
+                    .map(|name| ("cloned", name.as_str(), ""))
+                    .chain(
+                        self.skipped
+                            .iter()
+                            .map(|name| ("skipped", name.as_str(), "already exists")),
+                    )
+                    .chain(
+                        self.failed.iter().map(|f| ("failed", f.repo.as_str(), f.error.as_str())),
+                    )
+                    .collect();
+                let width = rows.iter().map(|(_, name, _)| name.chars().count()).max().unwrap_or(0);
+                for (state, name, reason) in rows {
+                    let state = style::status(&format!("{state:7}"));
+                    let line = format!("  {state}  {name:width$}  {reason}");
+                    let _ = writeln!(out, "{}", line.trim_end());
+                }
+            }
+        }
+        if let Some(manifest) = &self.manifest {
+            let _ = writeln!(out, "📝 Wrote {}", manifest.display());
+        }
+        if !self.failed.is_empty() {
+            out.push_str("↻ Retry the failed clones with `gho repo clone --resume`\n");
+        }
+        out
+    }
+}
+
+impl Render for Vec<AuthStatus> {
+    fn render(&self) -> String {
+        if self.is_empty() {
+            return "No accounts configured.\n".to_string();
+        }
+        let mut out = String::new();
+        for status in self {
+            let icon = if status.problems.is_empty() { "✅" } else { "❌" };
+            let active = if status.active { " (active)" } else { "" };
+            let _ = writeln!(
+                out,
+                "{icon} {} ({}){active}: token from {}",
+                status.account, status.username, status.source
+            );
+            if let Some(login) = &status.login {
+                let scopes = match &status.scopes {
+                    Some(scopes) if scopes.is_empty() => "no scopes".to_string(),
+                    Some(scopes) => format!("scopes {}", scopes.join(", ")),
+                    None => "no OAuth scopes (fine-grained or app token)".to_string(),
+                };
+                let remaining = status
+                    .rate_limit_remaining
+                    .map(|n| format!(", {n} requests left"))
+                    .unwrap_or_default();
+                let _ = writeln!(out, "   authenticated as {login}; {scopes}{remaining}");
+            }
+            for problem in &status.problems {
+                let _ = writeln!(out, "   - {problem}");
+            }
+        }
+        out
+    }
+}
+
+impl Render for Identity {
+    fn render(&self) -> String {
+        match (&self.login, &self.error) {
+            (_, Some(error)) => format!("❌ {}: {error}\n", self.account),
+            (Some(login), None) if self.mismatch => format!(
+                "⚠️  The token of '{}' authenticates as {login}, not the configured {}\n",
+                self.account, self.username
+            ),
+            (login, None) => format!(
+                "👤 {} (account '{}', token from {})\n",
+                login.as_deref().unwrap_or(&self.username),
+                self.account,
+                self.source
+            ),
+        }
+    }
+}
+
+impl Render for Vec<Identity> {
+    fn render(&self) -> String {
+        if self.is_empty() {
+            return "No accounts configured.\n".to_string();
+        }
+        let name = |identity: &Identity| {
+            let active = if identity.active { " (active)" } else { "" };
+            format!("{}{active}", identity.account)
+        };
+        let width = self.iter().map(|i| name(i).chars().count()).max().unwrap_or(0);
+        let user_width = self.iter().map(|i| i.username.chars().count()).max().unwrap_or(0);
+        let mut out = String::new();
+        for identity in self {
+            let (icon, detail) = match (&identity.login, &identity.error) {
+                (_, Some(error)) => ("❌", error.clone()),
+                (Some(login), None) if identity.mismatch => {
+                    ("⚠️ ", format!("token authenticates as {login}"))
+                }
+                _ => ("✅", format!("token from {}", identity.source)),
+            };
+            let _ = writeln!(
+                out,
+                "{icon} {:<width$}  {:<user_width$}  {detail}",
+                name(identity),
+                identity.username
+            );
+        }
+        out
+    }
+}
+
+impl Render for Vec<CloneStatus> {
+    fn render(&self) -> String {
+        if self.is_empty() {
+            return "No clones of the active account found.\n".to_string();
+        }
+        let mut out = String::new();
+        for clone in self {
+            let branch = clone.branch.as_deref().unwrap_or("detached HEAD");
+            let tracking = match (&clone.upstream, clone.ahead, clone.behind) {
+                (None, ..) => " (no upstream)".to_string(),
+                (Some(_), 0, 0) => String::new(),
+                (Some(_), ahead, behind) => format!(" ↑{ahead} ↓{behind}"),
+            };
+            let mut work = Vec::new();
+            if clone.changed > 0 {
+                work.push(format!("{} changed", clone.changed));
+            }
+            if clone.untracked > 0 {
+                work.push(format!("{} untracked", clone.untracked));
+            }
+            if clone.unpushed > 0 {
+                work.push(format!("{} unpushed commit(s)", clone.unpushed));
+            }
+            let (icon, summary) = match &clone.error {
+                Some(error) => ("❌", error.clone()),
+                None if work.is_empty() => ("✅", "clean".to_string()),
+                None => ("✏️ ", work.join(", ")),
+            };
+            let _ = writeln!(
+                out,
+                "{icon} {} on {branch}{tracking}: {summary} ({})",
+                clone.repo,
+                clone.path.display()
+            );
+        }
+        let in_flight = self.iter().filter(|c| c.in_flight()).count();
+        let _ = writeln!(out, "{in_flight} of {} clones have work in flight", self.len());
+        out
+    }
+}
+
+impl Render for Vec<Webhook> {
+    fn render(&self) -> String {
+        if self.is_empty() {
+            return "No webhooks.\n".to_string();
+        }
+        let mut out = String::new();
+        for hook in self {
+            let icon = if hook.active { "🟢" } else { "⚪" };
+            let last = hook
+                .last_response
+                .as_ref()
+                .map(|r| r.code.map(|c| c.to_string()).into_iter().chain(r.status.clone()))
+                .map(|parts| parts.collect::<Vec<_>>().join(" "))
+                .filter(|last| !last.is_empty())
+                .map(|last| format!(" (last delivery: {last})"))
+                .unwrap_or_default();
+            let _ = writeln!(
+                out,
+                "{icon} {:<10} {} [{}]{last}",
+                hook.id,
+                hook.config.url.as_deref().unwrap_or("-"),
+                hook.events.join(", ")
+            );
+        }
+        out
+    }
+}
+
+impl Render for Vec<HookDelivery> {
+    fn render(&self) -> String {
+        if self.is_empty() {
+            return "No deliveries.\n".to_string();
+        }
+        let mut out = String::new();
+        for delivery in self {
+            let icon = if (200..300).contains(&delivery.status_code) { "✅" } else { "❌" };
+            let event = match &delivery.action {
+                Some(action) => format!("{}.{action}", delivery.event),
+                None => delivery.event.clone(),
+            };
+            let redelivery = if delivery.redelivery { " (redelivery)" } else { "" };
+            let _ = writeln!(
+                out,
+                "{icon} {:<12} {} {:<28} {} in {:.2}s{redelivery}",
+                delivery.id,
+                delivery.delivered_at.format("%Y-%m-%d %H:%M:%S"),
+                event,
+                delivery.status,
+                delivery.duration
+            );
+        }
+        out
+    }
+}
+
+impl Render for Vec<Branch> {
+    fn render(&self) -> String {
+        if self.is_empty() {
+            return "No branches.\n".to_string();
+        }
+        let mut out = String::new();
+        for branch in self {
+            let state = match (branch.default, branch.merged, branch.pull_request) {
+                (true, _, _) => " (default)".to_string(),
+                (false, true, Some(number)) => format!(" (merged in #{number})"),
+                (false, true, None) => " (merged)".to_string(),
+                (false, false, _) => String::new(),
+            };
+            let date =
+                branch.committed_at.map_or("-".to_string(), |at| at.format("%Y-%m-%d").to_string());
+            let _ = writeln!(
+                out,
+                "{:<40} {} {date}{state}",
+                branch.name,
+                branch.sha.get(..7).unwrap_or(&branch.sha)
+            );
+        }
+        out
+    }
+}
+
+impl Render for Vec<RepoPullRequest> {
+    /// Pull requests under a heading per repository; entries of a repository must be adjacent.
+    fn render(&self) -> String {
+        if self.is_empty() {
+            return "No open pull requests.\n".to_string();
+        }
+        let mut out = String::new();
+        for (index, entry) in self.iter().enumerate() {
+            if index == 0 || self[index - 1].repo != entry.repo {
+                let count = self.iter().filter(|e| e.repo == entry.repo).count();
+                let blank = if index == 0 { "" } else { "\n" };
+                let _ = writeln!(out, "{blank}{} ({count})", entry.repo);
+            }
+            let p = &entry.pull_request;
+            let review = p.review_decision.as_deref().unwrap_or("no review").replace('_', " ");
+            let _ = writeln!(
+                out,
+                "  #{} {} ({}) [checks: {}] [{}]",
+                p.number,
+                p.title,
+                p.author,
+                style::status(&p.ci_status),
+                style::status(&review)
+            );
+        }
+        out
+    }
+}
+
+impl Render for Vec<ActionsSecret> {
+    fn render(&self) -> String {
+        if self.is_empty() {
+            return "No secrets.\n".to_string();
+        }
+        let mut out = String::new();
+        for secret in self {
+            let visibility =
+                secret.visibility.map(|v| format!(" ({v} repositories)")).unwrap_or_default();
+            let _ = writeln!(
+                out,
+                "🔒 {:<32} updated {}{visibility}",
+                secret.name,
+                secret.updated_at.format("%Y-%m-%d")
+            );
+        }
+        out
+    }
+}
+
+impl Render for Vec<ActionsVariable> {
+    fn render(&self) -> String {
+        if self.is_empty() {
+            return "No variables.\n".to_string();
+        }
+        let mut out = String::new();
+        for variable in self {
+            let visibility =
+                variable.visibility.map(|v| format!(" ({v} repositories)")).unwrap_or_default();
+            // Multi-line values would break the listing; show their first line.
+            let value = match variable.value.split_once('\n') {
+                Some((first, _)) => format!("{first} …"),
+                None => variable.value.clone(),
+            };
+            let _ = writeln!(out, "{:<32} {value}{visibility}", variable.name);
+        }
+        out
+    }
+}
+
+impl Render for SelftestReport {
+    fn render(&self) -> String {
+        let mut out = format!("🧪 Self-test in {}\n", self.repo);
+        for step in &self.steps {
+            let _ = match &step.error {
+                None => writeln!(out, "✅ {} ({} ms)", step.step, step.elapsed_ms),
+                Some(error) => writeln!(out, "❌ {}: {error}", step.step),
+            };
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::CloneFailure;
+    use std::path::PathBuf;
+
+    #[test]
+    fn clone_report_renders_single_and_bulk_clones() {
+        let single = CloneReport {
+            cloned: vec!["acme/api".into()],
+            dir: Some(PathBuf::from("api")),
+            ..Default::default()
+        };
+        assert_eq!(single.render(), "✅ Cloned 'acme/api'\n");
+        assert!(serde_json::to_value(&single).unwrap().get("failed").is_none());
+
+        let bulk = CloneReport {
+            cloned: vec!["api".into(), "web".into()],
+            manifest: Some(PathBuf::from("gho-clones.json")),
+            skipped: vec!["docs".into()],
+            failed: vec![CloneFailure {
+                repo: "legacy-app".into(),
+                error: "fatal: repository not found".into(),
+            }],
+            ..Default::default()
+        };
+        assert_eq!(
+            bulk.render(),
+            "⚠️  Cloned 2 of 4 repositories (1 skipped, 1 failed):\n\
+             \x20 cloned   api\n\
+             \x20 cloned   web\n\
+             \x20 skipped  docs        already exists\n\
+             \x20 failed   legacy-app  fatal: repository not found\n\
+             📝 Wrote gho-clones.json\n\
+             ↻ Retry the failed clones with `gho repo clone --resume`\n"
+        );
+        let json = serde_json::to_value(&bulk).unwrap();
+        assert_eq!(json["cloned"][1], "web");
+        assert_eq!(json["manifest"], "gho-clones.json");
+        assert_eq!(json["failed"][0]["repo"], "legacy-app");
+    }
+
+    #[test]
+    fn plan_prefixes_each_step() {
+        assert_eq!(
+            plan(&["git clone a".into(), "git clone b".into()]),
+            "🔍 git clone a\n🔍 git clone b\n"
+        );
+    }
+
+    #[test]
+    fn clone_statuses_render_work_in_flight() {
+        let dirty = CloneStatus {
+            repo: "acme/api".into(),
+            path: PathBuf::from("/src/api"),
+            branch: Some("main".into()),
+            upstream: Some("origin/main".into()),
+            ahead: 2,
+            behind: 1,
+            unpushed: 2,
+            changed: 3,
+            ..Default::default()
+        };
+        let clean = CloneStatus {
+            repo: "acme/web".into(),
+            path: PathBuf::from("/src/web"),
+            branch: Some("main".into()),
+            upstream: Some("origin/main".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            vec![dirty, clean].render(),
+            "✏️  acme/api on main ↑2 ↓1: 3 changed, 2 unpushed commit(s) (/src/api)\n\
+             ✅ acme/web on main: clean (/src/web)\n\
+             1 of 2 clones have work in flight\n"
+        );
+    }
+}
@@ -0,0 +1,139 @@
+//! Colors and emoji of human-readable output.
+//!
+//! `main` calls [`configure`] once, with the `color` setting (or `--color`) resolved against the
+//! terminal and the `--plain` flag. Renderers then color state words with [`status`], and
+//! messages go through [`line`] (or the [`say!`](crate::say) and [`esay!`](crate::esay) macros),
+//! which drops their emoji in plain mode. Until `configure` is called, output is uncolored and
+//! keeps its emoji, so embedders see what they saw before.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static COLOR: AtomicBool = AtomicBool::new(false);
+static PLAIN: AtomicBool = AtomicBool::new(false);
+
+/// `println!` for a message, without its emoji in plain mode.
+#[macro_export]
+macro_rules! say {
+    ($($arg:tt)*) => {
+        println!("{}", $crate::output::style::line(&format!($($arg)*)))
+    };
+}
+
+/// `eprintln!` for a message, without its emoji in plain mode.
+#[macro_export]
+macro_rules! esay {
+    ($($arg:tt)*) => {
+        eprintln!("{}", $crate::output::style::line(&format!($($arg)*)))
+    };
+}
+
+/// How a state reads: good, bad, or not decided yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tone {
+    Success,
+    Failure,
+    Pending,
+}
+
+impl Tone {
+    /// The tone of a check, run or review state such as `success`, `timed_out` or `queued`,
+    /// in any case and with spaces for underscores.
+    pub fn of(state: &str) -> Option<Self> {
+        match state.trim().to_ascii_lowercase().replace(' ', "_").as_str() {
+            "success" | "passed" | "ok" | "approved" | "merged" | "online" => Some(Self::Success),
+            "failure" | "failed" | "error" | "cancelled" | "timed_out" | "action_required"
+            | "startup_failure" | "changes_requested" | "offline" => Some(Self::Failure),
+            "pending" | "expected" | "queued" | "in_progress" | "waiting" | "requested"
+            | "review_required" => Some(Self::Pending),
+            _ => None,
+        }
+    }
+
+    fn ansi(self) -> &'static str {
+        match self {
+            Self::Success => "32",
+            Self::Failure => "31",
+            Self::Pending => "33",
+        }
+    }
+}
+
+/// Set how later output looks: `color` enables ANSI colors, `plain` strips emoji.
+pub fn configure(color: bool, plain: bool) {
+    COLOR.store(color, Ordering::Relaxed);
+    PLAIN.store(plain, Ordering::Relaxed);
+}
+
+/// Whether output is colored.
+pub fn color() -> bool {
+    COLOR.load(Ordering::Relaxed)
+}
+
+/// `text` colored by the tone of the state it names (surrounding padding is kept, so pad
+/// first); unknown states and uncolored output are returned as is.
+pub fn status(text: &str) -> String {
+    match Tone::of(text) {
+        Some(tone) if color() => paint(text, tone),
+        _ => text.to_string(),
+    }
+}
+
+/// `text` in the color of `tone`, ignoring whether output is colored.
+pub fn paint(text: &str, tone: Tone) -> String {
+    format!("\x1b[{}m{text}\x1b[0m", tone.ansi())
+}
+
+/// `text` as printed: unchanged, or in plain mode without the emoji that start its lines.
+pub fn line(text: &str) -> String {
+    if PLAIN.load(Ordering::Relaxed) { strip_emoji(text) } else { text.to_string() }
+}
+
+/// Drop the emoji (and the spaces after it) at the start of each line, after any indentation.
+/// Warnings and errors keep a `warning:` or `error:` prefix instead.
+fn strip_emoji(text: &str) -> String {
+    text.split_inclusive('\n')
+        .map(|line| {
+            let body = line.trim_start_matches(' ');
+            let indent = &line[..line.len() - body.len()];
+            let Some(first) = body.chars().next().filter(|&c| is_emoji(c)) else {
+                return line.to_string();
+            };
+            let rest = body[first.len_utf8()..]
+                .trim_start_matches(['\u{fe0f}', '\u{200d}'])
+                .trim_start_matches(' ');
+            let prefix = match first {
+                '⚠' => "warning: ",
+                '❌' => "error: ",
+                _ => "",
+            };
+            format!("{indent}{prefix}{rest}")
+        })
+        .collect()
+}
+
+/// Pictographs and symbols gho starts messages with (✅, ⚠️, 🔍, ▶, ↻, ...).
+fn is_emoji(c: char) -> bool {
+    matches!(c, '\u{2190}'..='\u{2BFF}' | '\u{1F000}'..='\u{1FAFF}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip_emoji_keeps_warnings_and_indentation() {
+        assert_eq!(strip_emoji("✅ Cloned 'api'\n  🔒 NPM_TOKEN\n"), "Cloned 'api'\n  NPM_TOKEN\n");
+        assert_eq!(strip_emoji("⚠️  Token expires soon"), "warning: Token expires soon");
+        assert_eq!(strip_emoji("❌ ci: failed"), "error: ci: failed");
+        assert_eq!(strip_emoji("#12 Fix → main"), "#12 Fix → main");
+    }
+
+    #[test]
+    fn tone_reads_check_and_run_states() {
+        assert_eq!(Tone::of("SUCCESS"), Some(Tone::Success));
+        assert_eq!(Tone::of("timed_out   "), Some(Tone::Failure));
+        assert_eq!(Tone::of("in_progress"), Some(Tone::Pending));
+        assert_eq!(Tone::of("none"), None);
+        assert_eq!(paint("failure", Tone::Failure), "\x1b[31mfailure\x1b[0m");
+    }
+}
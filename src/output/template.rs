@@ -0,0 +1,120 @@
+//! Format strings for list output (`--format '{{.number}} {{.title}}'`).
+//!
+//! A template is text with `{{.field}}` placeholders, rendered once per item against the item's
+//! JSON form, so scripts can pull out the fields they need without `jq`. Nested fields are
+//! reached with dots (`{{.owner.login}}`), and `\t` and `\n` in the template stand for a tab and
+//! a newline, which single-quoted shell arguments cannot carry.
+
+use crate::error::AppError;
+use serde::Serialize;
+use serde_json::Value;
+
+/// A parsed format string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Text(String),
+    /// Path of a field, e.g. `["owner", "login"]`.
+    Field(Vec<String>),
+}
+
+impl Template {
+    /// Parse a format string, rejecting unclosed or empty placeholders.
+    pub fn parse(format: &str) -> Result<Self, AppError> {
+        let invalid = |reason: &str| {
+            AppError::invalid_input(format!("invalid --format '{format}': {reason}"))
+        };
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        let mut rest = format;
+        while let Some(start) = rest.find("{{") {
+            text.push_str(&rest[..start]);
+            let after = &rest[start + 2..];
+            let end = after.find("}}").ok_or_else(|| invalid("unclosed {{"))?;
+            let path = after[..end].trim().strip_prefix('.').ok_or_else(|| {
+                invalid("placeholders name a field with a leading dot, e.g. {{.name}}")
+            })?;
+            if path.is_empty() || path.split('.').any(str::is_empty) {
+                return Err(invalid("empty field name"));
+            }
+            if !text.is_empty() {
+                parts.push(Part::Text(unescape(&std::mem::take(&mut text))));
+            }
+            parts.push(Part::Field(path.split('.').map(str::to_string).collect()));
+            rest = &after[end + 2..];
+        }
+        text.push_str(rest);
+        if !text.is_empty() {
+            parts.push(Part::Text(unescape(&text)));
+        }
+        Ok(Self { parts })
+    }
+
+    /// Render the template for one item.
+    ///
+    /// Strings are inserted without quotes, arrays of scalars comma-separated and objects as
+    /// JSON. Missing and null fields render empty, since absent optional fields are left out of
+    /// the JSON form.
+    pub fn render<T: Serialize>(&self, item: &T) -> Result<String, AppError> {
+        let value = serde_json::to_value(item)?;
+        let mut out = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Text(text) => out.push_str(text),
+                Part::Field(path) => {
+                    let field = path.iter().try_fold(&value, |value, key| value.get(key));
+                    out.push_str(&scalar(field.unwrap_or(&Value::Null)));
+                }
+            }
+        }
+        Ok(out)
+    }
+}
+
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Array(items) if !items.iter().any(|i| i.is_array() || i.is_object()) => {
+            items.iter().map(scalar).collect::<Vec<_>>().join(",")
+        }
+        other => other.to_string(),
+    }
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("\\t", "\t").replace("\\n", "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn render_fills_nested_fields_and_escapes() {
+        let template = Template::parse(r"#{{.number}}\t{{ .title }} by {{.user.login}}").unwrap();
+        let item = json!({ "number": 7, "title": "Fix", "user": { "login": "octocat" } });
+        assert_eq!(template.render(&item).unwrap(), "#7\tFix by octocat");
+    }
+
+    #[test]
+    fn render_leaves_missing_fields_empty_and_joins_lists() {
+        let template = Template::parse("{{.name}}:{{.mergeable}}:{{.topics}}").unwrap();
+        let item = json!({ "name": "api", "topics": ["rust", "cli"] });
+        assert_eq!(template.render(&item).unwrap(), "api::rust,cli");
+    }
+
+    #[test]
+    fn parse_rejects_malformed_placeholders() {
+        assert!(Template::parse("{{.name").is_err());
+        assert!(Template::parse("{{name}}").is_err());
+        assert!(Template::parse("{{.}}").is_err());
+        assert!(Template::parse("{{.owner..login}}").is_err());
+        assert_eq!(Template::parse("plain").unwrap().render(&json!({})).unwrap(), "plain");
+    }
+}
@@ -0,0 +1,88 @@
+//! Paging long output through `$PAGER`, handing diffs to external diff tools, and composing
+//! messages in an editor.
+
+use crate::error::AppError;
+use crate::platform;
+use std::io::{self, IsTerminal, Write};
+use std::process::Stdio;
+
+/// Pager used when `PAGER` is unset; `-R` keeps ANSI colors.
+const DEFAULT_PAGER: &str = "less -R";
+
+/// Write `content` to stdout, through `$PAGER` when stdout is a terminal.
+///
+/// An empty `PAGER` or `cat` prints directly. A reader closing the output early (quitting the
+/// pager, `| head`) is not an error.
+pub fn page(content: &str) -> Result<(), AppError> {
+    let pager = std::env::var("PAGER").unwrap_or_else(|_| DEFAULT_PAGER.to_string());
+    let pager = pager.trim();
+    if !io::stdout().is_terminal() || pager.is_empty() || pager == "cat" {
+        return ignore_broken_pipe(io::stdout().lock().write_all(content.as_bytes()));
+    }
+
+    pipe_to(pager, content)
+}
+
+/// Pipe `content` into `command`, run with `sh -c` (`cmd /C` on Windows) and writing straight to the terminal, e.g. a
+/// diff into `delta`.
+pub fn pipe_to(command: &str, content: &str) -> Result<(), AppError> {
+    let mut child = platform::shell(command)
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| AppError::config(format!("failed to run '{command}': {e}")))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        ignore_broken_pipe(stdin.write_all(content.as_bytes()))?;
+    }
+    child.wait()?;
+    Ok(())
+}
+
+/// Run a review command template on the diff of pull request `number`.
+///
+/// The diff is saved to a temporary file whose path replaces `{diff}`; `{number}` is replaced
+/// by the pull request number.
+pub fn review_diff(template: &str, diff: &str, number: u64) -> Result<(), AppError> {
+    if !template.contains("{diff}") {
+        return Err(AppError::invalid_input("review_command must contain {diff}"));
+    }
+    let path = std::env::temp_dir().join(format!("gho-pr-{number}-{}.diff", std::process::id()));
+    std::fs::write(&path, diff)?;
+    let command = template
+        .replace("{diff}", &platform::shell_quote(&path.display().to_string()))
+        .replace("{number}", &number.to_string());
+
+    let status = platform::shell(&command).status();
+    let _ = std::fs::remove_file(&path);
+    let status = status.map_err(|e| AppError::config(format!("failed to run '{command}': {e}")))?;
+    if !status.success() {
+        return Err(AppError::config(format!("review command failed with status {status}")));
+    }
+    Ok(())
+}
+
+/// Compose a message in `editor`, e.g. the `editor` setting, starting from `help` as `#`
+/// comment lines, which are dropped from the result along with surrounding whitespace.
+pub fn compose(editor: &str, name: &str, help: &str) -> Result<String, AppError> {
+    let path = std::env::temp_dir().join(format!("gho-{name}-{}.md", std::process::id()));
+    let comments: String = help.lines().map(|line| format!("# {line}\n")).collect();
+    std::fs::write(&path, format!("\n{comments}"))?;
+    let command = format!("{editor} {}", platform::shell_quote(&path.display().to_string()));
+
+    let status = platform::shell(&command).status();
+    let message = std::fs::read_to_string(&path);
+    let _ = std::fs::remove_file(&path);
+    let status = status.map_err(|e| AppError::config(format!("failed to run '{command}': {e}")))?;
+    if !status.success() {
+        return Err(AppError::config(format!("editor failed with status {status}")));
+    }
+    let message = message?;
+    let lines: Vec<&str> = message.lines().filter(|line| !line.starts_with('#')).collect();
+    Ok(lines.join("\n").trim().to_string())
+}
+
+fn ignore_broken_pipe(result: io::Result<()>) -> Result<(), AppError> {
+    match result {
+        Err(e) if e.kind() != io::ErrorKind::BrokenPipe => Err(e.into()),
+        _ => Ok(()),
+    }
+}
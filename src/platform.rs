@@ -0,0 +1,77 @@
+//! Differences between the operating systems gho runs on.
+//!
+//! gho keeps its files under `~/.config/gho` everywhere, runs user commands (token commands,
+//! pagers, `repo foreach` scripts) with `sh -c`, or `cmd /C` on Windows, and writes paths into
+//! git configuration with forward slashes, which Git for Windows accepts and its `sh` does not
+//! mangle.
+
+use crate::domain::shell_join;
+use crate::error::AppError;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The user's home directory: `HOME`, or on Windows `USERPROFILE`.
+pub fn home_dir() -> Result<PathBuf, AppError> {
+    std::env::home_dir()
+        .filter(|home| !home.as_os_str().is_empty())
+        .ok_or_else(|| AppError::config("cannot find the home directory (HOME is not set)"))
+}
+
+/// `path` with a leading `~` replaced by the home directory, when there is one.
+pub fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix('~'), home_dir()) {
+        (Some(rest), Ok(home)) if rest.is_empty() || rest.starts_with(['/', '\\']) => {
+            home.join(rest.trim_start_matches(['/', '\\']))
+        }
+        _ => PathBuf::from(path),
+    }
+}
+
+/// A command running `script` in the platform shell.
+#[cfg(not(windows))]
+pub fn shell(script: &str) -> Command {
+    let mut command = Command::new("sh");
+    command.arg("-c").arg(script);
+    command
+}
+
+/// A command running `script` in the platform shell.
+///
+/// The script is passed to `cmd` verbatim: the usual argument escaping would add backslashes
+/// that `cmd` does not understand.
+#[cfg(windows)]
+pub fn shell(script: &str) -> Command {
+    use std::os::windows::process::CommandExt;
+    let mut command = Command::new("cmd");
+    command.arg("/C").raw_arg(script);
+    command
+}
+
+/// Quote `arg` as one word for [`shell`].
+pub fn shell_quote(arg: &str) -> String {
+    if cfg!(windows) {
+        let plain = !arg.is_empty() && !arg.contains([' ', '\t', '"', '&', '|', '<', '>', '^']);
+        if plain { arg.to_string() } else { format!("\"{}\"", arg.replace('"', "\"\"")) }
+    } else {
+        shell_join(&[arg.to_string()])
+    }
+}
+
+/// `path` as written into git configuration: with forward slashes on Windows.
+pub fn git_path(path: &Path) -> String {
+    let path = path.display().to_string();
+    if cfg!(windows) { path.replace('\\', "/") } else { path }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(windows, ignore = "cmd's echo prints the quotes")]
+    fn shell_runs_scripts_with_quoted_arguments() {
+        let script = format!("echo {}", shell_quote("two words"));
+        let output = shell(&script).output().unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "two words");
+    }
+}
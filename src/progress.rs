@@ -0,0 +1,104 @@
+//! Machine-readable progress of bulk operations (`--progress json`).
+//!
+//! In JSON mode each [`ProgressEvent`] is written to stderr as one line, so GUIs and CI
+//! wrappers can follow long runs without scraping the human-oriented output. Bulk commands
+//! leave their per-item stderr messages out in this mode.
+
+use crate::models::{ProgressEvent, ProgressOutcome};
+
+/// Where bulk operations report per-item progress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Progress {
+    /// Only the commands' usual human-oriented output.
+    #[default]
+    Human,
+    /// NDJSON progress events on stderr.
+    Json,
+}
+
+impl Progress {
+    pub fn is_json(self) -> bool {
+        self == Progress::Json
+    }
+
+    /// Work on `item` (the `index`-th of `total`, 1-based) has begun.
+    pub fn started(self, operation: &str, item: &str, index: usize, total: usize) {
+        self.emit(|| ProgressEvent::Started {
+            operation: operation.to_string(),
+            item: item.to_string(),
+            index,
+            total,
+        });
+    }
+
+    /// `item` reached a new stage, e.g. `pushing`.
+    pub fn updated(self, operation: &str, item: &str, message: &str) {
+        self.emit(|| ProgressEvent::Updated {
+            operation: operation.to_string(),
+            item: item.to_string(),
+            message: message.to_string(),
+        });
+    }
+
+    /// `item` is done; `error` explains a failure or skip.
+    pub fn completed(
+        self,
+        operation: &str,
+        item: &str,
+        outcome: ProgressOutcome,
+        error: Option<&str>,
+    ) {
+        self.emit(|| ProgressEvent::Completed {
+            operation: operation.to_string(),
+            item: item.to_string(),
+            outcome,
+            error: error.map(str::to_string),
+        });
+    }
+
+    fn emit(self, event: impl FnOnce() -> ProgressEvent) {
+        if !self.is_json() {
+            return;
+        }
+        // `eprintln!` locks stderr per call, so lines from parallel workers never interleave.
+        if let Ok(line) = serde_json::to_string(&event()) {
+            eprintln!("{line}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn events_serialize_with_event_tag() {
+        let event = ProgressEvent::Completed {
+            operation: "repo clone".to_string(),
+            item: "acme/api".to_string(),
+            outcome: ProgressOutcome::Failed,
+            error: Some("exit status 128".to_string()),
+        };
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            json!({
+                "event": "completed",
+                "operation": "repo clone",
+                "item": "acme/api",
+                "outcome": "failed",
+                "error": "exit status 128"
+            })
+        );
+
+        let event = ProgressEvent::Started {
+            operation: "label sync".to_string(),
+            item: "acme/web".to_string(),
+            index: 1,
+            total: 2,
+        };
+        let line = serde_json::to_string(&event).unwrap();
+        assert!(line.starts_with(r#"{"event":"started","#));
+        assert_eq!(serde_json::from_str::<ProgressEvent>(&line).unwrap(), event);
+    }
+}
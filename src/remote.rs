@@ -0,0 +1,162 @@
+//! Repository detection from the current directory's git remote.
+//!
+//! Remotes are recognized on github.com and on the Enterprise hosts configured on accounts,
+//! including through `Host` aliases in `~/.ssh/config` (e.g. `git@github-work:owner/repo`).
+
+use crate::domain::{
+    GITHUB_HOST, RepoSpec, parse_repo_spec, parse_ssh_config_aliases, resolve_remote_url,
+};
+use crate::error::AppError;
+use crate::logging;
+use crate::platform;
+use crate::storage::Storage;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// Repository argument that stands for the last used repository.
+pub const LAST_REPO: &str = "-";
+
+/// Resolve `owner/repo` from an explicit spec, `GITHUB_REPOSITORY`, or the `origin` remote.
+///
+/// A spec is parsed as a [`RepoSpec`]; a bare name belongs to the active account's default
+/// org, or its user when it has none, and `-` means the last used repository. The resolved
+/// repository is recorded as the last used one.
+pub fn resolve_repo(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+) -> Result<(String, String), AppError> {
+    resolve_repo_with_host(storage, repo_spec).map(|(_host, owner, repo)| (owner, repo))
+}
+
+/// Like [`resolve_repo`], but also returns the host of a repository given by URL or detected
+/// from the `origin` remote; `None` leaves the host to the caller, e.g. the active account's.
+pub fn resolve_repo_with_host(
+    storage: &impl Storage,
+    repo_spec: Option<&str>,
+) -> Result<(Option<String>, String, String), AppError> {
+    let (host, owner, repo) = match repo_spec {
+        Some(spec) => {
+            let spec = parse_spec(storage, &expand_recent(storage, spec)?)?;
+            let host = spec.host().map(str::to_string);
+            let (owner, repo) = spec.resolve(|| default_owner(storage))?;
+            (host, owner, repo)
+        }
+        None => match std::env::var("GITHUB_REPOSITORY") {
+            Ok(repo) => parse_repo_spec(&repo).map(|(owner, repo)| (None, owner, repo))?,
+            Err(_) => {
+                detect_remote(storage).map(|(host, owner, repo)| (Some(host), owner, repo))?
+            }
+        },
+    };
+    remember_repo(storage, &format!("{owner}/{repo}"))?;
+    Ok((host, owner, repo))
+}
+
+/// Parse a repository argument as a [`RepoSpec`], rejecting a URL whose host is not github.com
+/// or an account's Enterprise host.
+pub fn parse_spec(storage: &impl Storage, spec: &str) -> Result<RepoSpec, AppError> {
+    let parsed = RepoSpec::parse(spec)?;
+    match parsed.host() {
+        Some(host) if !is_known_host(storage, host)? => Err(AppError::invalid_input(format!(
+            "host '{host}' of '{spec}' is not github.com or an account's Enterprise host \
+             (set one with `gho account add --host`)"
+        ))),
+        _ => Ok(parsed),
+    }
+}
+
+/// Owner of bare repository names: the active account's default org, or else its user.
+pub fn default_owner(storage: &impl Storage) -> Result<String, AppError> {
+    let accounts = storage.load_accounts()?;
+    let account = accounts.active_account().ok_or(AppError::NoActiveAccount)?;
+    Ok(account.default_org.clone().unwrap_or_else(|| account.username.clone()))
+}
+
+/// `repo_spec` itself, or the last used repository when it is `-`.
+pub fn expand_recent(storage: &impl Storage, repo_spec: &str) -> Result<String, AppError> {
+    if repo_spec != LAST_REPO {
+        return Ok(repo_spec.to_string());
+    }
+    storage.load_state()?.last_repo.ok_or_else(|| {
+        AppError::invalid_input("no recently used repository, pass owner/repo instead of '-'")
+    })
+}
+
+/// Record `full_name` as the last used repository.
+pub fn remember_repo(storage: &impl Storage, full_name: &str) -> Result<(), AppError> {
+    let mut state = storage.load_state()?;
+    if state.recent_repos.first().map(String::as_str) != Some(full_name) {
+        state.touch_repo(full_name);
+        storage.save_state(&state)?;
+    }
+    Ok(())
+}
+
+/// `(host, owner, repo)` of the `origin` remote of the repository in the current directory.
+///
+/// Fails unless the host is github.com or an account's Enterprise host.
+pub fn detect_remote(storage: &impl Storage) -> Result<(String, String, String), AppError> {
+    let (host, owner, repo) = detect_remote_any_host()?;
+    if !is_known_host(storage, &host)? {
+        return Err(AppError::git(format!(
+            "remote host '{host}' is not github.com or an account's Enterprise host \
+             (set one with `gho account add --host`)"
+        )));
+    }
+    Ok((host, owner, repo))
+}
+
+/// Like [`detect_remote`], but accepts any host, e.g. for building web links.
+pub fn detect_remote_any_host() -> Result<(String, String, String), AppError> {
+    resolve_remote_url(&origin_remote_url()?, &ssh_aliases())
+}
+
+/// `(host, owner, repo)` of the `origin` remote of the clone in `dir`, on any host; `None` when
+/// it has no GitHub-style origin.
+pub fn origin_of(dir: &Path) -> Option<(String, String, String)> {
+    let mut command = Command::new("git");
+    let output = logging::command(command.arg("-C").arg(dir).args(["remote", "get-url", "origin"]))
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    resolve_remote_url(String::from_utf8_lossy(&output.stdout).trim(), &ssh_aliases()).ok()
+}
+
+/// Whether `host` is github.com or an Enterprise host configured on an account.
+fn is_known_host(storage: &impl Storage, host: &str) -> Result<bool, AppError> {
+    if host.eq_ignore_ascii_case(GITHUB_HOST) {
+        return Ok(true);
+    }
+    let accounts = storage.load_accounts()?;
+    Ok(accounts
+        .all_accounts()
+        .into_iter()
+        .any(|a| a.host.as_deref().is_some_and(|h| h.eq_ignore_ascii_case(host))))
+}
+
+/// Host aliases from `~/.ssh/config`; empty when the file is missing or unreadable.
+fn ssh_aliases() -> BTreeMap<String, String> {
+    platform::home_dir()
+        .ok()
+        .map(|home| home.join(".ssh").join("config"))
+        .and_then(|path| fs::read_to_string(path).ok())
+        .map(|content| parse_ssh_config_aliases(&content))
+        .unwrap_or_default()
+}
+
+/// URL of the `origin` remote of the repository in the current directory.
+pub fn origin_remote_url() -> Result<String, AppError> {
+    let output = logging::command(Command::new("git").args(["remote", "get-url", "origin"]))
+        .output()
+        .map_err(|e| AppError::git(format!("failed to run git: {e}")))?;
+
+    if !output.status.success() {
+        return Err(AppError::git("no repository detected, provide owner/repo argument"));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
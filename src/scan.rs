@@ -0,0 +1,129 @@
+//! Discovery of local git clones.
+//!
+//! Directory trees are walked in parallel with the `ignore` crate: hidden and ignored
+//! directories are skipped and the walk never descends into a repository it has found.
+//! Results are cached per scanned directory in the state file, so repeated scans of a large
+//! home directory are instant.
+
+use crate::error::AppError;
+use crate::models::RepoScan;
+use crate::storage::Storage;
+use chrono::{Duration, Utc};
+use ignore::{WalkBuilder, WalkState};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Default number of directory levels below the scanned root.
+pub const DEFAULT_MAX_DEPTH: usize = 4;
+/// How long a cached scan is reused.
+const CACHE_TTL_HOURS: i64 = 24;
+
+/// Git repositories under `root`, from the state cache when a recent scan exists.
+///
+/// A cached scan is reused for a day if it was made with the same depth; `refresh` forces a
+/// new walk. Cached repositories that have since been removed are left out.
+pub fn discover(
+    storage: &impl Storage,
+    root: &Path,
+    max_depth: usize,
+    refresh: bool,
+) -> Result<Vec<PathBuf>, AppError> {
+    let root = root
+        .canonicalize()
+        .map_err(|e| AppError::invalid_input(format!("cannot scan '{}': {e}", root.display())))?;
+    let key = root.to_string_lossy().to_string();
+    let mut state = storage.load_state()?;
+    let now = Utc::now();
+
+    if !refresh
+        && let Some(scan) = state.repo_scans.get(&key).filter(|s| {
+            s.max_depth == max_depth && now - s.scanned_at < Duration::hours(CACHE_TTL_HOURS)
+        })
+    {
+        tracing::debug!(root = %root.display(), scanned_at = %scan.scanned_at, "scan cache hit");
+        return Ok(scan.repos.iter().filter(|r| is_repo(r)).cloned().collect());
+    }
+
+    let repos = find_repos(&root, max_depth);
+    state.repo_scans.insert(key, RepoScan { scanned_at: now, max_depth, repos: repos.clone() });
+    storage.save_state(&state)?;
+    Ok(repos)
+}
+
+/// Walk `root` in parallel for git repositories at most `max_depth` levels deep, sorted.
+pub fn find_repos(root: &Path, max_depth: usize) -> Vec<PathBuf> {
+    let found = Mutex::new(Vec::new());
+    WalkBuilder::new(root)
+        .max_depth(Some(max_depth))
+        .require_git(false)
+        // Only directories can be repositories; skip files before they are processed.
+        .filter_entry(|entry| entry.file_type().is_some_and(|t| t.is_dir()))
+        .build_parallel()
+        .run(|| {
+            let found = &found;
+            Box::new(move |entry| {
+                let Ok(entry) = entry else {
+                    return WalkState::Continue;
+                };
+                if !is_repo(entry.path()) {
+                    return WalkState::Continue;
+                }
+                found.lock().expect("scan results lock poisoned").push(entry.into_path());
+                WalkState::Skip
+            })
+        });
+
+    let mut repos = found.into_inner().expect("scan results lock poisoned");
+    repos.sort();
+    repos
+}
+
+/// Whether `dir` is the root of a git checkout or worktree.
+fn is_repo(dir: &Path) -> bool {
+    dir.join(".git").exists()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::storage::FilesystemStorage;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn make_repo(root: &Path, path: &str) {
+        fs::create_dir_all(root.join(path).join(".git")).unwrap();
+    }
+
+    #[test]
+    fn find_repos_stops_at_repos_and_depth() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        make_repo(root, "api");
+        make_repo(root, "api/vendor/nested");
+        make_repo(root, "acme/web");
+        make_repo(root, ".hidden/dotfiles");
+        make_repo(root, "a/b/c/d/too-deep");
+
+        let repos = find_repos(root, DEFAULT_MAX_DEPTH);
+        assert_eq!(repos, vec![root.join("acme/web"), root.join("api")]);
+    }
+
+    #[test]
+    fn discover_caches_until_refresh() {
+        let dir = TempDir::new().unwrap();
+        let storage = FilesystemStorage::new(&Config::with_path(dir.path().join("config")));
+        let root = dir.path().join("src");
+        make_repo(&root, "api");
+
+        let first = discover(&storage, &root, 2, false).unwrap();
+        assert_eq!(first.len(), 1);
+
+        make_repo(&root, "web");
+        assert_eq!(discover(&storage, &root, 2, false).unwrap(), first);
+        assert_eq!(discover(&storage, &root, 2, true).unwrap().len(), 2);
+
+        fs::remove_dir_all(root.join("api")).unwrap();
+        assert_eq!(discover(&storage, &root, 2, false).unwrap().len(), 1);
+    }
+}
@@ -85,7 +85,7 @@ impl Storage for FilesystemStorage {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{Account, AccountKind, Protocol};
+    use crate::models::{Account, AccountKind, CloneLayout, CloneOptions, Protocol};
     use tempfile::TempDir;
 
     fn test_storage() -> (TempDir, FilesystemStorage) {
@@ -115,6 +115,14 @@ mod tests {
             default_org: None,
             protocol: Protocol::Ssh,
             clone_dir: None,
+            clone_layout: CloneLayout::Flat,
+            clone_options: CloneOptions::default(),
+            browser: None,
+            token_command: None,
+            token_expires_at: None,
+            host: None,
+            protected: false,
+            git: None,
         });
         accounts.active_account_id = Some("test".to_string());
 
@@ -129,7 +137,7 @@ mod tests {
     #[test]
     fn save_and_load_state() {
         let (_tmp, storage) = test_storage();
-        let state = StateFile { last_org: Some("myorg".to_string()), last_repo: None };
+        let state = StateFile { last_org: Some("myorg".to_string()), ..Default::default() };
 
         storage.save_state(&state).expect("save should succeed");
         let loaded = storage.load_state().expect("load should succeed");
@@ -0,0 +1,132 @@
+//! Password manager vault integration (1Password `op`, Bitwarden `bw`).
+
+use crate::error::AppError;
+use crate::platform::shell_quote;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+const DEFAULT_OP_VAULT: &str = "Private";
+
+/// Supported password manager vaults.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VaultKind {
+    OnePassword,
+    Bitwarden,
+}
+
+impl std::fmt::Display for VaultKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VaultKind::OnePassword => write!(f, "1password"),
+            VaultKind::Bitwarden => write!(f, "bitwarden"),
+        }
+    }
+}
+
+/// Name of the vault item holding an account's token.
+pub fn item_name(account_id: &str) -> String {
+    format!("gho-{account_id}")
+}
+
+/// Store a token as a new vault item via the vault's CLI.
+///
+/// The token is passed on stdin so it never appears in the process list.
+pub fn store_token(
+    kind: VaultKind,
+    account_id: &str,
+    token: &str,
+    vault: Option<&str>,
+) -> Result<(), AppError> {
+    let name = item_name(account_id);
+    match kind {
+        VaultKind::OnePassword => {
+            let template = serde_json::json!({
+                "title": name,
+                "category": "API_CREDENTIAL",
+                "fields": [{
+                    "id": "credential",
+                    "label": "credential",
+                    "type": "CONCEALED",
+                    "value": token,
+                }],
+            });
+            let vault = vault.unwrap_or(DEFAULT_OP_VAULT);
+            pipe("op", &["item", "create", "--vault", vault], &template.to_string())?;
+        }
+        VaultKind::Bitwarden => {
+            let template = serde_json::json!({
+                "type": 1,
+                "name": name,
+                "login": { "password": token },
+            });
+            let encoded = pipe("bw", &["encode"], &template.to_string())?;
+            pipe("bw", &["create", "item"], encoded.trim())?;
+        }
+    }
+    Ok(())
+}
+
+/// Shell command that prints the token stored by [`store_token`], quoted for the platform
+/// shell that runs token commands.
+pub fn lookup_command(kind: VaultKind, account_id: &str, vault: Option<&str>) -> String {
+    let name = item_name(account_id);
+    match kind {
+        VaultKind::OnePassword => {
+            let vault = vault.unwrap_or(DEFAULT_OP_VAULT);
+            format!("op read {}", shell_quote(&format!("op://{vault}/{name}/credential")))
+        }
+        VaultKind::Bitwarden => format!("bw get password {}", shell_quote(&name)),
+    }
+}
+
+fn pipe(program: &str, args: &[&str], input: &str) -> Result<String, AppError> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| AppError::keychain(format!("failed to run {program}: {e}")))?;
+
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(input.as_bytes())
+        .map_err(|e| AppError::keychain(format!("failed to write to {program}: {e}")))?;
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| AppError::keychain(format!("failed to wait for {program}: {e}")))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(AppError::keychain(format!("{program} failed: {}", stderr.trim())));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup_command_one_password_uses_vault() {
+        let cmd = lookup_command(VaultKind::OnePassword, "work", Some("Team"));
+        assert_eq!(cmd, "op read op://Team/gho-work/credential");
+
+        let cmd = lookup_command(VaultKind::OnePassword, "work", None);
+        assert_eq!(cmd, "op read op://Private/gho-work/credential");
+
+        let cmd = lookup_command(VaultKind::OnePassword, "work", Some("Shared Team"));
+        let quoted = shell_quote("op://Shared Team/gho-work/credential");
+        assert_eq!(cmd, format!("op read {quoted}"));
+        assert_ne!(quoted, "op://Shared Team/gho-work/credential");
+    }
+
+    #[test]
+    fn lookup_command_bitwarden() {
+        let cmd = lookup_command(VaultKind::Bitwarden, "work", None);
+        assert_eq!(cmd, "bw get password gho-work");
+    }
+}
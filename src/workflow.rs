@@ -0,0 +1,344 @@
+//! GitHub Actions workflow file validation.
+//!
+//! Checks the structure of a workflow against GitHub's workflow syntax (required keys,
+//! allowed keys, job and step shape, `needs` references) and collects the secrets and
+//! variables it references so they can be verified against a repository.
+
+use serde_yaml::{Mapping, Value};
+use std::collections::BTreeSet;
+
+const WORKFLOW_KEYS: &[&str] =
+    &["name", "run-name", "on", "permissions", "env", "defaults", "concurrency", "jobs"];
+
+const JOB_KEYS: &[&str] = &[
+    "name",
+    "permissions",
+    "needs",
+    "if",
+    "runs-on",
+    "environment",
+    "concurrency",
+    "outputs",
+    "env",
+    "defaults",
+    "steps",
+    "timeout-minutes",
+    "strategy",
+    "continue-on-error",
+    "container",
+    "services",
+    "uses",
+    "with",
+    "secrets",
+];
+
+/// Secrets provided by GitHub to every workflow run.
+const BUILTIN_SECRETS: &[&str] = &["GITHUB_TOKEN"];
+
+/// Severity of a lint finding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Error,
+    Warning,
+}
+
+impl std::fmt::Display for Level {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Level::Error => write!(f, "error"),
+            Level::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A lint finding, with a best-effort 1-based line number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub line: Option<usize>,
+    pub level: Level,
+    pub message: String,
+}
+
+/// Result of linting one workflow file.
+#[derive(Debug, Clone, Default)]
+pub struct LintReport {
+    pub diagnostics: Vec<Diagnostic>,
+    /// Secret names referenced via `secrets.NAME`, excluding built-ins.
+    pub secrets: BTreeSet<String>,
+    /// Variable names referenced via `vars.NAME`.
+    pub variables: BTreeSet<String>,
+    /// Deployment environments used by jobs.
+    pub environments: BTreeSet<String>,
+}
+
+impl LintReport {
+    fn error(&mut self, content: &str, needle: Option<&str>, message: impl Into<String>) {
+        self.push(Level::Error, content, needle, message);
+    }
+
+    /// Record a finding, locating `needle` in `content` for the line number.
+    pub fn push(
+        &mut self,
+        level: Level,
+        content: &str,
+        needle: Option<&str>,
+        message: impl Into<String>,
+    ) {
+        let line = needle.and_then(|n| find_line(content, n));
+        self.diagnostics.push(Diagnostic { line, level, message: message.into() });
+    }
+
+    /// Whether any finding is an error.
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics.iter().any(|d| d.level == Level::Error)
+    }
+}
+
+/// Lint the contents of a workflow file.
+pub fn lint(content: &str) -> LintReport {
+    let mut report = LintReport {
+        secrets: expression_refs(content, "secrets.")
+            .into_iter()
+            .filter(|s| !BUILTIN_SECRETS.contains(&s.as_str()))
+            .collect(),
+        variables: expression_refs(content, "vars."),
+        ..Default::default()
+    };
+
+    let root: Value = match serde_yaml::from_str(content) {
+        Ok(value) => value,
+        Err(e) => {
+            let line = e.location().map(|l| l.line());
+            report.diagnostics.push(Diagnostic {
+                line,
+                level: Level::Error,
+                message: format!("invalid YAML: {e}"),
+            });
+            return report;
+        }
+    };
+
+    let Some(root) = root.as_mapping() else {
+        report.error(content, None, "workflow must be a mapping");
+        return report;
+    };
+
+    for key in root.keys().filter_map(Value::as_str) {
+        if !WORKFLOW_KEYS.contains(&key) {
+            report.error(content, Some(&format!("{key}:")), format!("unknown key '{key}'"));
+        }
+    }
+    if !root.contains_key("on") {
+        report.error(content, None, "missing trigger ('on')");
+    }
+
+    match root.get("jobs") {
+        None => report.error(content, None, "missing 'jobs'"),
+        Some(Value::Mapping(jobs)) if !jobs.is_empty() => lint_jobs(&mut report, content, jobs),
+        Some(_) => report.error(content, Some("jobs:"), "'jobs' must be a non-empty mapping"),
+    }
+
+    report
+}
+
+fn lint_jobs(report: &mut LintReport, content: &str, jobs: &Mapping) {
+    let ids: Vec<&str> = jobs.keys().filter_map(Value::as_str).collect();
+
+    for (id, job) in jobs.iter().filter_map(|(k, v)| Some((k.as_str()?, v))) {
+        let anchor = format!("{id}:");
+        let anchor = Some(anchor.as_str());
+
+        if !is_valid_job_id(id) {
+            let message = format!("invalid job id '{id}' (use letters, digits, '-' and '_')");
+            report.error(content, anchor, message);
+        }
+
+        let Some(job) = job.as_mapping() else {
+            report.error(content, anchor, format!("job '{id}' must be a mapping"));
+            continue;
+        };
+
+        for key in job.keys().filter_map(Value::as_str) {
+            if !JOB_KEYS.contains(&key) {
+                report.error(content, anchor, format!("job '{id}': unknown key '{key}'"));
+            }
+        }
+
+        let reusable = job.contains_key("uses");
+        if reusable && job.contains_key("steps") {
+            report.error(
+                content,
+                anchor,
+                format!("job '{id}': 'uses' cannot be combined with 'steps'"),
+            );
+        }
+        if !reusable && !job.contains_key("runs-on") {
+            report.error(content, anchor, format!("job '{id}': missing 'runs-on'"));
+        }
+        if !reusable && !job.contains_key("steps") {
+            report.error(content, anchor, format!("job '{id}': missing 'steps'"));
+        }
+
+        for need in string_list(job.get("needs")) {
+            if !ids.contains(&need.as_str()) {
+                report.error(content, anchor, format!("job '{id}': needs unknown job '{need}'"));
+            }
+        }
+
+        match job.get("environment") {
+            Some(Value::String(env)) => {
+                report.environments.insert(env.clone());
+            }
+            Some(Value::Mapping(env)) => {
+                if let Some(name) = env.get("name").and_then(Value::as_str) {
+                    report.environments.insert(name.to_string());
+                }
+            }
+            _ => {}
+        }
+
+        match job.get("steps") {
+            None => {}
+            Some(Value::Sequence(steps)) => {
+                for (index, step) in steps.iter().enumerate() {
+                    lint_step(report, content, id, index, step);
+                }
+            }
+            Some(_) => report.error(content, anchor, format!("job '{id}': 'steps' must be a list")),
+        }
+    }
+}
+
+fn lint_step(report: &mut LintReport, content: &str, job: &str, index: usize, step: &Value) {
+    let label = match step.get("name").and_then(Value::as_str) {
+        Some(name) => format!("job '{job}' step '{name}'"),
+        None => format!("job '{job}' step {}", index + 1),
+    };
+    let anchor = step.get("name").and_then(Value::as_str);
+
+    let Some(step) = step.as_mapping() else {
+        report.error(content, None, format!("{label} must be a mapping"));
+        return;
+    };
+    match (step.contains_key("uses"), step.contains_key("run")) {
+        (true, true) => {
+            report.error(content, anchor, format!("{label}: 'uses' and 'run' are exclusive"))
+        }
+        (false, false) => report.error(content, anchor, format!("{label}: needs 'uses' or 'run'")),
+        _ => {}
+    }
+}
+
+fn string_list(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::String(s)) => vec![s.clone()],
+        Some(Value::Sequence(items)) => {
+            items.iter().filter_map(Value::as_str).map(str::to_string).collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn is_valid_job_id(id: &str) -> bool {
+    let mut chars = id.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Names referenced as `<prefix>NAME` (e.g. `secrets.NPM_TOKEN`), ignoring comment lines.
+fn expression_refs(content: &str, prefix: &str) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
+    for line in content.lines().filter(|l| !l.trim_start().starts_with('#')) {
+        let mut rest = line;
+        while let Some(pos) = rest.find(prefix) {
+            let preceded_by_ident = rest[..pos]
+                .chars()
+                .next_back()
+                .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+            rest = &rest[pos + prefix.len()..];
+            let name: String =
+                rest.chars().take_while(|c| c.is_ascii_alphanumeric() || *c == '_').collect();
+            if !preceded_by_ident && !name.is_empty() {
+                names.insert(name);
+            }
+        }
+    }
+    names
+}
+
+fn find_line(content: &str, needle: &str) -> Option<usize> {
+    content.lines().position(|line| line.contains(needle)).map(|i| i + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALID: &str = r#"
+name: CI
+on: [push]
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    environment: production
+    steps:
+      - uses: actions/checkout@v4
+      - name: Publish
+        run: npm publish
+        env:
+          NODE_AUTH_TOKEN: ${{ secrets.NPM_TOKEN }}
+          TOKEN: ${{ secrets.GITHUB_TOKEN }}
+          REGION: ${{ vars.AWS_REGION }}
+  test:
+    needs: build
+    uses: acme/workflows/.github/workflows/test.yml@main
+    secrets: inherit
+"#;
+
+    #[test]
+    fn lint_accepts_valid_workflow_and_collects_refs() {
+        let report = lint(VALID);
+        assert!(report.diagnostics.is_empty(), "{:?}", report.diagnostics);
+        assert_eq!(report.secrets.iter().collect::<Vec<_>>(), ["NPM_TOKEN"]);
+        assert_eq!(report.variables.iter().collect::<Vec<_>>(), ["AWS_REGION"]);
+        assert_eq!(report.environments.iter().collect::<Vec<_>>(), ["production"]);
+    }
+
+    #[test]
+    fn lint_reports_structural_errors() {
+        let report = lint(concat!(
+            "on: push\n",
+            "trigger: x\n",
+            "jobs:\n",
+            "  build:\n",
+            "    needs: lint\n",
+            "    steps:\n",
+            "      - name: Both\n",
+            "        uses: a/b@v1\n",
+            "        run: echo\n",
+        ));
+        let messages: Vec<&str> = report.diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert!(messages.contains(&"unknown key 'trigger'"));
+        assert!(messages.contains(&"job 'build': missing 'runs-on'"));
+        assert!(messages.contains(&"job 'build': needs unknown job 'lint'"));
+        assert!(messages.contains(&"job 'build' step 'Both': 'uses' and 'run' are exclusive"));
+        assert_eq!(report.diagnostics[0].line, Some(2));
+    }
+
+    #[test]
+    fn lint_reports_yaml_errors_with_line() {
+        let report = lint("on: push\njobs:\n  build: [unclosed\n");
+        assert!(report.has_errors());
+        assert!(report.diagnostics[0].message.starts_with("invalid YAML"));
+        assert!(report.diagnostics[0].line.is_some());
+    }
+
+    #[test]
+    fn expression_refs_ignores_comments_and_prefixes() {
+        let refs = expression_refs(
+            "# ${{ secrets.OLD }}\nx: ${{ secrets.A }} ${{ mysecrets.B }}",
+            "secrets.",
+        );
+        assert_eq!(refs.into_iter().collect::<Vec<_>>(), ["A"]);
+    }
+}
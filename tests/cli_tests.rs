@@ -75,6 +75,7 @@ fn account_show_without_active_fails() {
         .args(["account", "show"])
         .assert()
         .failure()
+        .code(3)
         .stderr(predicate::str::contains("No active account"));
 }
 
@@ -99,9 +100,36 @@ fn account_use_nonexistent_fails() {
         .args(["account", "use", "nonexistent"])
         .assert()
         .failure()
+        .code(4)
         .stderr(predicate::str::contains("Account not found"));
 }
 
+#[test]
+#[serial]
+fn account_use_json_reports_switch() {
+    let ctx = TestContext::new();
+    ctx.write_accounts(
+        r#"{
+        "personal": [{"id": "me", "kind": "personal", "username": "dev"}],
+        "work": [{"id": "acme", "kind": "work", "username": "acme-dev"}],
+        "active_account_id": "me"
+    }"#,
+    );
+
+    let output = ctx.cli().args(["account", "use", "acme", "--json"]).output().unwrap();
+    assert!(output.status.success());
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["id"], "acme");
+    assert_eq!(result["username"], "acme-dev");
+    assert_eq!(result["previous"], "me");
+
+    ctx.cli()
+        .args(["account", "use", "me"])
+        .assert()
+        .success()
+        .stdout("✅ Switched to account 'me'\n");
+}
+
 #[test]
 #[serial]
 fn repo_list_without_account_fails() {
@@ -114,6 +142,19 @@ fn repo_list_without_account_fails() {
         .stderr(predicate::str::contains("No active account"));
 }
 
+#[test]
+#[serial]
+fn repo_list_source_conflicts_with_forks_only() {
+    let ctx = TestContext::new();
+
+    ctx.cli()
+        .args(["repo", "list", "--source", "--forks-only"])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
 #[test]
 #[serial]
 fn repo_clone_requires_arg_or_org() {
@@ -123,9 +164,31 @@ fn repo_clone_requires_arg_or_org() {
         .args(["repo", "clone"])
         .assert()
         .failure()
+        .code(2)
         .stderr(predicate::str::contains("provide either a repo"));
 }
 
+#[test]
+#[serial]
+fn repo_clone_from_manifest_validates_the_file() {
+    let ctx = TestContext::new();
+
+    ctx.cli()
+        .args(["repo", "clone", "--from-manifest", "gho-clones.json", "--org", "acme"])
+        .assert()
+        .failure()
+        .code(2);
+
+    let missing = ctx.home().join("gho-clones.json");
+    ctx.cli()
+        .args(["repo", "clone", "--from-manifest"])
+        .arg(&missing)
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("cannot read clone manifest"));
+}
+
 #[test]
 #[serial]
 fn pr_list_without_account_fails() {
@@ -137,3 +200,1288 @@ fn pr_list_without_account_fails() {
         .failure()
         .stderr(predicate::str::contains("No active account"));
 }
+
+#[test]
+#[serial]
+fn pr_review_requires_event_flag() {
+    let ctx = TestContext::new();
+
+    ctx.cli()
+        .args(["pr", "review", "1", "--repo", "owner/repo"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--approve"));
+}
+
+#[test]
+#[serial]
+fn pr_review_request_changes_requires_message() {
+    let ctx = TestContext::new();
+
+    ctx.cli()
+        .args(["pr", "review", "1", "--repo", "owner/repo", "--request-changes"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("message (-m) is required"));
+}
+
+#[test]
+#[serial]
+fn pr_ready_and_draft_require_active_account() {
+    let ctx = TestContext::new();
+
+    ctx.cli().args(["pr", "ready", "1", "-R", "acme/api"]).assert().failure().code(3);
+    ctx.cli().args(["pr", "draft", "1", "-R", "acme/api"]).assert().failure().code(3);
+}
+
+#[test]
+#[serial]
+fn pr_diff_review_requires_review_command() {
+    let ctx = TestContext::new();
+
+    ctx.cli()
+        .args(["pr", "diff", "1", "-R", "acme/api", "--review"])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("review_command"));
+    ctx.cli().args(["config", "set", "review_command", "less"]).assert().failure().code(2);
+}
+
+#[test]
+#[serial]
+fn pr_comment_requires_a_body() {
+    let ctx = TestContext::new();
+
+    ctx.cli().args(["pr", "comment", "1", "-R", "owner/repo"]).assert().failure().code(2);
+    ctx.cli()
+        .args(["pr", "comment", "1", "-R", "owner/repo", "-m", "  "])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("must not be empty"));
+    ctx.cli()
+        .args(["pr", "comment", "1", "-R", "owner/repo", "-F", "/nonexistent/body.md"])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("cannot read"));
+}
+
+#[test]
+#[serial]
+fn accounts_flag_with_unknown_group_fails() {
+    let ctx = TestContext::new();
+
+    ctx.cli()
+        .args(["--accounts", "clients", "repo", "list"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("account group 'clients' not found"));
+}
+
+#[test]
+#[serial]
+fn accounts_flag_rejected_for_unsupported_commands() {
+    let ctx = TestContext::new();
+
+    ctx.cli()
+        .args(["--accounts", "clients", "account", "show"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("only supported by"));
+}
+
+#[test]
+#[serial]
+fn account_protect_nonexistent_fails() {
+    let ctx = TestContext::new();
+
+    ctx.cli()
+        .args(["account", "protect", "nonexistent"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Account not found"));
+}
+
+#[test]
+#[serial]
+fn account_export_requires_destination() {
+    let ctx = TestContext::new();
+
+    ctx.cli()
+        .args(["account", "export"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--to").and(predicate::str::contains("--file")));
+}
+
+#[test]
+#[serial]
+fn queue_status_without_account_fails() {
+    let ctx = TestContext::new();
+
+    ctx.cli()
+        .args(["queue", "status", "--repo", "owner/repo"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No active account"));
+}
+
+#[test]
+#[serial]
+fn help_documents_exit_codes() {
+    let ctx = TestContext::new();
+
+    ctx.cli().arg("--help").assert().success().stdout(
+        predicate::str::contains("Exit codes:").and(predicate::str::contains("6  rate limit")),
+    );
+}
+
+#[test]
+#[serial]
+fn pr_annotations_requires_active_account() {
+    let ctx = TestContext::new();
+
+    ctx.cli()
+        .args(["pr", "annotations", "42", "-R", "octocat/hello-world"])
+        .assert()
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains("No active account"));
+}
+
+#[test]
+#[serial]
+fn run_annotations_requires_numeric_id() {
+    let ctx = TestContext::new();
+
+    ctx.cli().args(["run", "annotations", "latest"]).assert().failure().code(2);
+}
+
+#[test]
+#[serial]
+fn config_set_get_and_list() {
+    let ctx = TestContext::new();
+
+    ctx.cli()
+        .args(["config", "set", "limit", "50"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Set limit = 50"));
+
+    ctx.cli().args(["config", "get", "limit"]).assert().success().stdout("50\n");
+
+    ctx.cli().args(["config", "list"]).assert().success().stdout(
+        predicate::str::contains("limit = 50").and(predicate::str::contains("format = text")),
+    );
+}
+
+#[test]
+#[serial]
+fn bootstrap_emit_includes_changed_settings() {
+    let ctx = TestContext::new();
+    ctx.cli().args(["config", "set", "clone_jobs", "8"]).assert().success();
+
+    ctx.cli().args(["bootstrap", "emit"]).assert().success().stdout(
+        predicate::str::starts_with("#!/usr/bin/env bash")
+            .and(predicate::str::contains("gho config set clone_jobs 8\n"))
+            .and(predicate::str::contains("config set limit").not()),
+    );
+}
+
+#[test]
+#[serial]
+fn config_set_rejects_unknown_key() {
+    let ctx = TestContext::new();
+
+    ctx.cli()
+        .args(["config", "set", "colour", "never"])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("unknown config key"));
+}
+
+#[test]
+#[serial]
+fn config_set_help_lists_every_key() {
+    let ctx = TestContext::new();
+
+    let output = ctx.cli().args(["config", "set", "--help"]).output().unwrap();
+    let help = String::from_utf8(output.stdout).unwrap();
+    for key in gho::config::Settings::KEYS {
+        assert!(help.contains(key), "config set --help is missing {key}");
+    }
+}
+
+#[test]
+#[serial]
+fn checks_required_set_requires_contexts() {
+    let ctx = TestContext::new();
+
+    ctx.cli()
+        .args(["repo", "checks-required", "set", "acme/api"])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("--contexts"));
+}
+
+#[test]
+#[serial]
+fn checks_required_sync_rejects_invalid_file() {
+    let ctx = TestContext::new();
+    let path = ctx.home().join("checks.toml");
+    std::fs::write(&path, "repo = 1").unwrap();
+
+    ctx.cli()
+        .args(["repo", "checks-required", "sync", "--from-file"])
+        .arg(&path)
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("invalid"));
+}
+
+#[test]
+#[serial]
+fn repo_foreach_requires_command() {
+    let ctx = TestContext::new();
+
+    ctx.cli().args(["repo", "foreach", "--org", "acme"]).assert().failure().code(2);
+}
+
+#[test]
+#[serial]
+fn repo_foreach_requires_account() {
+    let ctx = TestContext::new();
+
+    ctx.cli()
+        .args(["repo", "foreach", "--org", "acme", "--", "git", "status"])
+        .assert()
+        .failure()
+        .code(3);
+}
+
+#[test]
+#[serial]
+fn protection_apply_rejects_unknown_rules() {
+    let ctx = TestContext::new();
+    let path = ctx.home().join("rules.json");
+    std::fs::write(&path, r#"{"required_review": 1}"#).unwrap();
+
+    ctx.cli()
+        .args(["repo", "protection", "apply", "acme/api", "--from"])
+        .arg(&path)
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("required_review"));
+}
+
+#[test]
+#[serial]
+fn protection_show_requires_account() {
+    let ctx = TestContext::new();
+
+    ctx.cli().args(["repo", "protection", "show", "acme/api"]).assert().failure().code(3);
+}
+
+#[test]
+#[serial]
+fn release_upload_rejects_missing_file() {
+    let ctx = TestContext::new();
+
+    ctx.cli()
+        .args(["release", "upload", "v1.0.0", "missing.tar.gz", "-R", "acme/api"])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("is not a file"));
+}
+
+#[test]
+#[serial]
+fn repo_open_print_builds_file_url() {
+    let ctx = TestContext::new();
+
+    ctx.cli()
+        .args(["repo", "open", "octocat/hello-world", "--path", "src/lib.rs", "--print"])
+        .assert()
+        .success()
+        .stdout("https://github.com/octocat/hello-world/blob/HEAD/src/lib.rs\n");
+}
+
+#[test]
+#[serial]
+fn repo_open_detects_enterprise_remote() {
+    let ctx = TestContext::new();
+    let git = |args: &[&str]| {
+        let status = std::process::Command::new("git")
+            .args(args)
+            .current_dir(ctx.work_dir())
+            .status()
+            .expect("git should run");
+        assert!(status.success());
+    };
+    git(&["init", "-q"]);
+    git(&["remote", "add", "origin", "git@github.acme.com:platform/api.git"]);
+
+    ctx.cli_in(ctx.work_dir())
+        .args(["repo", "open", "--branch", "develop", "--print"])
+        .assert()
+        .success()
+        .stdout("https://github.acme.com/platform/api/tree/develop\n");
+}
+
+#[test]
+#[serial]
+fn workflow_lint_reports_errors_in_quickfix_format() {
+    let ctx = TestContext::new();
+    let dir = ctx.work_dir().join(".github").join("workflows");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("ok.yml"),
+        "on: push\njobs:\n  a:\n    runs-on: x\n    steps:\n      - run: echo\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("bad.yml"),
+        "on: push\njobs:\n  build:\n    steps:\n      - run: echo\n",
+    )
+    .unwrap();
+
+    ctx.cli_in(ctx.work_dir())
+        .args(["workflow", "lint", "--no-remote"])
+        .assert()
+        .failure()
+        .code(2)
+        .stdout(predicate::str::contains(
+            ".github/workflows/bad.yml:3: error: job 'build': missing 'runs-on'",
+        ))
+        .stderr(predicate::str::contains("1 of 2 workflow files have errors"));
+}
+
+#[test]
+#[serial]
+fn workflow_lint_remote_check_requires_account() {
+    let ctx = TestContext::new();
+    let path = ctx.work_dir().join("ci.yml");
+    std::fs::write(&path, "on: push\njobs:\n  a:\n    runs-on: x\n    steps:\n      - run: echo\n")
+        .unwrap();
+
+    ctx.cli().args(["workflow", "lint", "-R", "acme/api"]).arg(&path).assert().failure().code(3);
+}
+
+#[test]
+#[serial]
+fn actions_usage_rejects_invalid_since() {
+    let ctx = TestContext::new();
+    ctx.cli()
+        .args(["actions", "usage", "--org", "acme", "--since", "last-month"])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("expected YYYY-MM-DD"));
+}
+
+#[test]
+#[serial]
+fn pr_status_requires_active_account() {
+    let ctx = TestContext::new();
+
+    ctx.cli()
+        .args(["pr", "status"])
+        .assert()
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains("No active account"));
+}
+
+#[test]
+#[serial]
+fn runner_remove_requires_active_account() {
+    let ctx = TestContext::new();
+    ctx.cli().args(["runner", "remove", "build-1", "--org", "acme"]).assert().failure().code(3);
+}
+
+#[test]
+#[serial]
+fn run_watch_requires_active_account() {
+    let ctx = TestContext::new();
+    ctx.cli().args(["run", "watch", "123", "-R", "acme/api"]).assert().failure().code(3);
+}
+
+#[test]
+#[serial]
+fn package_delete_version_requires_active_account() {
+    let ctx = TestContext::new();
+    ctx.cli().args(["package", "delete", "api", "--version", "42"]).assert().failure().code(3);
+}
+
+#[test]
+#[serial]
+fn package_prune_requires_older_than() {
+    let ctx = TestContext::new();
+    ctx.cli().args(["package", "prune", "api", "--dry-run"]).assert().failure().code(2);
+}
+
+#[test]
+#[serial]
+fn release_create_rejects_conflicting_notes_sources() {
+    let ctx = TestContext::new();
+    ctx.cli()
+        .args(["release", "create", "v1.0.0", "-R", "acme/api", "-n", "Notes", "--notes-from-tag"])
+        .assert()
+        .failure()
+        .code(2);
+}
+
+#[test]
+#[serial]
+fn release_download_requires_active_account() {
+    let ctx = TestContext::new();
+    ctx.cli()
+        .args(["release", "download", "v1.0.0", "-R", "acme/api", "-p", "*.tar.gz"])
+        .assert()
+        .failure()
+        .code(3);
+}
+
+#[test]
+#[serial]
+fn pr_fix_conflicts_abort_without_fix_in_progress_fails() {
+    let ctx = TestContext::new();
+    ctx.cli()
+        .args(["pr", "fix-conflicts", "987654", "-R", "acme/api", "--abort"])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("no fix-conflicts in progress"));
+}
+
+#[test]
+#[serial]
+fn label_create_rejects_invalid_color() {
+    let ctx = TestContext::new();
+    ctx.cli()
+        .args(["label", "create", "bug", "-R", "acme/api", "--color", "red"])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("expected 6 hex digits"));
+}
+
+#[test]
+#[serial]
+fn label_sync_rejects_duplicate_labels() {
+    let ctx = TestContext::new();
+    let path = ctx.work_dir().join("labels.yml");
+    std::fs::write(&path, "- name: bug\n- name: Bug\n").unwrap();
+
+    ctx.cli()
+        .args(["label", "sync", "acme/api", "--file"])
+        .arg(&path)
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("duplicate label 'Bug'"));
+}
+
+#[test]
+#[serial]
+fn pr_timeline_requires_active_account() {
+    let ctx = TestContext::new();
+    ctx.cli().args(["pr", "timeline", "7", "-R", "acme/api"]).assert().failure().code(3);
+}
+
+#[test]
+#[serial]
+fn api_rate_limit_requires_active_account() {
+    let ctx = TestContext::new();
+    ctx.cli().args(["api", "rate-limit"]).assert().failure().code(3);
+}
+
+#[test]
+#[serial]
+fn api_rate_limit_all_accounts_without_accounts_is_empty() {
+    let ctx = TestContext::new();
+    ctx.cli()
+        .args(["api", "rate-limit", "--all-accounts", "--json"])
+        .assert()
+        .success()
+        .stdout("[]\n");
+}
+
+#[test]
+#[serial]
+fn api_requires_endpoint_or_subcommand() {
+    let ctx = TestContext::new();
+    ctx.cli().arg("api").assert().failure().code(2);
+}
+
+#[test]
+#[serial]
+fn api_rejects_malformed_field_before_auth() {
+    let ctx = TestContext::new();
+    ctx.cli()
+        .args(["api", "/repos/{owner}/{repo}/labels", "-R", "acme/api", "-f", "name"])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("expected key=value"));
+}
+
+#[test]
+#[serial]
+fn api_request_requires_active_account() {
+    let ctx = TestContext::new();
+    ctx.cli()
+        .args(["api", "/repos/{owner}/{repo}/traffic/views", "-R", "acme/api", "--paginate"])
+        .assert()
+        .failure()
+        .code(3);
+}
+
+#[test]
+#[serial]
+fn pr_fanout_requires_apply_or_run() {
+    let ctx = TestContext::new();
+    let repos = ctx.home().join("repos.txt");
+    std::fs::write(&repos, "acme/api\n").unwrap();
+    ctx.cli()
+        .args([
+            "pr",
+            "fanout",
+            "--repos",
+            repos.to_str().unwrap(),
+            "-b",
+            "chore/bump",
+            "-t",
+            "Bump",
+        ])
+        .assert()
+        .failure()
+        .code(2);
+}
+
+#[test]
+#[serial]
+fn pr_fanout_rejects_invalid_repo_list_before_auth() {
+    let ctx = TestContext::new();
+    let repos = ctx.home().join("repos.txt");
+    std::fs::write(&repos, "acme/api\nnot-a-repo\n").unwrap();
+    ctx.cli()
+        .args(["pr", "fanout", "--repos", repos.to_str().unwrap(), "-b", "chore/bump"])
+        .args(["-t", "Bump", "--run", "true"])
+        .assert()
+        .failure()
+        .code(2);
+}
+
+#[test]
+#[serial]
+fn pr_fanout_requires_active_account() {
+    let ctx = TestContext::new();
+    let repos = ctx.home().join("repos.txt");
+    std::fs::write(&repos, "acme/api\n").unwrap();
+    ctx.cli()
+        .args(["pr", "fanout", "--repos", repos.to_str().unwrap(), "-b", "chore/bump"])
+        .args(["-t", "Bump", "--run", "true", "--dry-run"])
+        .assert()
+        .failure()
+        .code(3);
+}
+
+#[test]
+#[serial]
+fn daemon_status_reports_stopped_without_snapshots() {
+    let ctx = TestContext::new();
+    ctx.cli()
+        .args(["daemon", "status"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("stopped"))
+        .stdout(predicate::str::contains("never"));
+}
+
+#[test]
+#[serial]
+fn daemon_stop_when_not_running_fails() {
+    let ctx = TestContext::new();
+    ctx.cli().args(["daemon", "stop"]).assert().failure().code(4);
+}
+
+#[test]
+#[serial]
+fn daemon_start_rejects_short_interval() {
+    let ctx = TestContext::new();
+    ctx.cli().args(["daemon", "start", "--interval", "5"]).assert().failure().code(2);
+}
+
+#[test]
+#[serial]
+fn pr_status_cached_without_snapshot_fails() {
+    let ctx = TestContext::new();
+    ctx.cli()
+        .args(["pr", "status", "--cached"])
+        .assert()
+        .failure()
+        .code(4)
+        .stderr(predicate::str::contains("gho daemon start"));
+}
+
+#[test]
+#[serial]
+fn commit_view_requires_active_account() {
+    let ctx = TestContext::new();
+    ctx.cli().args(["commit", "view", "main", "-R", "acme/api"]).assert().failure().code(3);
+}
+
+#[test]
+#[serial]
+fn pr_ready_to_merge_requires_active_account() {
+    let ctx = TestContext::new();
+    ctx.cli()
+        .args(["pr", "ready-to-merge", "12", "-R", "acme/api", "--require-signed"])
+        .assert()
+        .failure()
+        .code(3);
+}
+
+#[test]
+#[serial]
+fn repo_scan_lists_clones_under_path() {
+    let ctx = TestContext::new();
+    let root = ctx.work_dir().join("src");
+    std::fs::create_dir_all(root.join("acme/api/.git")).unwrap();
+    std::fs::create_dir_all(root.join("notes")).unwrap();
+
+    ctx.cli()
+        .args(["repo", "scan", root.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("acme/api"))
+        .stdout(predicate::str::contains("notes").not());
+}
+
+#[test]
+#[serial]
+fn verbose_logs_cache_hits_and_ignores_invalid_gho_log() {
+    let ctx = TestContext::new();
+    let root = ctx.work_dir().join("src");
+    std::fs::create_dir_all(root.join("acme/api/.git")).unwrap();
+
+    ctx.cli().args(["repo", "scan", root.to_str().unwrap()]).assert().success();
+    ctx.cli()
+        .args(["-v", "repo", "scan", root.to_str().unwrap()])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("DEBUG gho::scan: scan cache hit"));
+
+    ctx.cli()
+        .env("GHO_LOG", "loud")
+        .args(["config", "get", "limit"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Ignoring GHO_LOG=loud"));
+}
+
+#[test]
+#[serial]
+fn doctor_reports_invalid_proxy() {
+    let ctx = TestContext::new();
+
+    ctx.cli()
+        .env("HTTPS_PROXY", "ftp://proxy.invalid")
+        .args(["doctor", "--json"])
+        .assert()
+        .failure()
+        .code(5)
+        .stdout(predicate::str::contains(r#"{"check":"HTTPS_PROXY","status":"failed""#))
+        .stdout(predicate::str::contains("unsupported proxy scheme 'ftp'"));
+}
+
+#[test]
+#[serial]
+fn auth_status_reports_token_source_and_failures() {
+    let ctx = TestContext::new();
+    ctx.write_accounts(
+        r#"{
+        "personal": [{"id": "me", "kind": "personal", "username": "dev",
+                      "token_command": "echo ghp_test"}],
+        "active_account_id": "me"
+    }"#,
+    );
+
+    let output = ctx
+        .cli()
+        .env_remove("GH_TOKEN")
+        .env_remove("GITHUB_TOKEN")
+        .env("GHO_API_BASE", "http://127.0.0.1:1")
+        .args(["auth", "status", "--json"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(3));
+    let statuses: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let status = &statuses[0];
+    assert_eq!(status["account"], "me");
+    assert_eq!(status["source"], "token_command");
+    assert_eq!(status["stored_secret"], true);
+    assert!(status["problems"][0].as_str().unwrap().starts_with("token check failed"));
+
+    ctx.cli()
+        .env("GH_TOKEN", "ghp_override")
+        .env("GHO_API_BASE", "http://127.0.0.1:1")
+        .args(["auth", "status"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("❌ me (dev) (active): token from env:GH_TOKEN"));
+}
+
+#[test]
+#[serial]
+fn env_token_override_is_announced_and_can_be_disabled() {
+    let ctx = TestContext::new();
+    ctx.write_accounts(
+        r#"{
+        "personal": [{"id": "me", "kind": "personal", "username": "dev",
+                      "token_command": "echo ghp_test"}],
+        "active_account_id": "me"
+    }"#,
+    );
+    let auth_status = || {
+        let mut cmd = ctx.cli();
+        cmd.env("GHO_API_BASE", "http://127.0.0.1:1").args(["auth", "status"]);
+        cmd
+    };
+
+    auth_status()
+        .env("GH_TOKEN", "ghp_override")
+        .assert()
+        .stderr(predicate::str::contains("GH_TOKEN overrides the token of account 'me' (dev)"))
+        .stdout(predicate::str::contains("token from env:GH_TOKEN"));
+
+    auth_status()
+        .env("GH_TOKEN", "ghp_override")
+        .arg("--no-env-token")
+        .assert()
+        .stderr(predicate::str::contains("overrides").not())
+        .stdout(predicate::str::contains("token from token_command"));
+
+    ctx.cli().args(["config", "set", "token_source", "env"]).assert().success();
+    auth_status()
+        .env_remove("GH_TOKEN")
+        .env_remove("GITHUB_TOKEN")
+        .assert()
+        .code(3)
+        .stdout(predicate::str::contains("token_source is env, but neither GH_TOKEN"));
+}
+
+#[test]
+#[serial]
+fn auth_git_credential_answers_with_the_account_token() {
+    let ctx = TestContext::new();
+    ctx.write_accounts(
+        r#"{
+        "personal": [{"id": "me", "kind": "personal", "username": "dev",
+                      "token_command": "echo ghp_stored"}],
+        "active_account_id": "me"
+    }"#,
+    );
+    let credential = |input: &str| {
+        let mut cmd = ctx.cli();
+        cmd.env_remove("GH_TOKEN")
+            .env_remove("GITHUB_TOKEN")
+            .args(["auth", "git-credential", "get"])
+            .write_stdin(input.to_string());
+        cmd
+    };
+
+    credential("protocol=https\nhost=github.com\n\n")
+        .assert()
+        .success()
+        .stdout("username=dev\npassword=ghp_stored\n");
+    credential("protocol=https\nhost=gitlab.com\n\n").assert().success().stdout("");
+    ctx.cli().args(["auth", "git-credential", "store"]).assert().success().stdout("");
+}
+
+#[test]
+#[serial]
+fn init_requires_tty_and_completions_print_a_script() {
+    let ctx = TestContext::new();
+    ctx.cli().arg("init").assert().failure().code(2);
+    assert!(ctx.read_accounts().is_empty());
+
+    ctx.cli()
+        .args(["completions", "bash"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("_gho()"));
+}
+
+#[test]
+#[serial]
+fn repo_scan_rejects_missing_directory() {
+    let ctx = TestContext::new();
+    ctx.cli().args(["repo", "scan", "/nonexistent/gho-scan"]).assert().failure().code(2);
+}
+
+#[test]
+#[serial]
+fn repo_status_reports_the_active_accounts_clones() {
+    let ctx = TestContext::new();
+    ctx.write_accounts(
+        r#"{
+        "personal": [{"id": "me", "kind": "personal", "username": "dev"}],
+        "active_account_id": "me"
+    }"#,
+    );
+    let src = ctx.home().join("src");
+    for (name, url) in
+        [("api", "git@github.com:dev/api.git"), ("lib", "https://github.com/other/lib")]
+    {
+        let dir = src.join(name);
+        std::fs::create_dir_all(&dir).unwrap();
+        for args in [&["init", "-q"][..], &["remote", "add", "origin", url][..]] {
+            let status =
+                std::process::Command::new("git").args(args).current_dir(&dir).status().unwrap();
+            assert!(status.success());
+        }
+    }
+    std::fs::write(src.join("api").join("notes.txt"), "wip").unwrap();
+
+    let output = ctx.cli().args(["repo", "status", "--json"]).arg(&src).output().unwrap();
+    assert!(output.status.success());
+    let statuses: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let statuses = statuses.as_array().unwrap();
+    assert_eq!(statuses.len(), 1);
+    assert_eq!(statuses[0]["repo"], "dev/api");
+    assert_eq!(statuses[0]["untracked"], 1);
+    assert_eq!(statuses[0]["upstream"], serde_json::Value::Null);
+}
+
+fn init_repo_with_origin(ctx: &TestContext, url: &str) {
+    for args in [&["init", "-q"][..], &["remote", "add", "origin", url][..]] {
+        let status = std::process::Command::new("git")
+            .args(args)
+            .current_dir(ctx.work_dir())
+            .status()
+            .expect("git should run");
+        assert!(status.success());
+    }
+}
+
+#[test]
+#[serial]
+fn repo_open_resolves_ssh_config_alias() {
+    let ctx = TestContext::new();
+    std::fs::create_dir_all(ctx.home().join(".ssh")).unwrap();
+    std::fs::write(
+        ctx.home().join(".ssh/config"),
+        "Host github-work\n  HostName github.com\n  IdentityFile ~/.ssh/work\n",
+    )
+    .unwrap();
+    init_repo_with_origin(&ctx, "git@github-work:acme/api.git");
+
+    ctx.cli()
+        .args(["repo", "open", "--print"])
+        .assert()
+        .success()
+        .stdout("https://github.com/acme/api\n");
+}
+
+#[test]
+#[serial]
+fn repo_detection_rejects_unknown_hosts() {
+    let ctx = TestContext::new();
+    init_repo_with_origin(&ctx, "git@gitlab.example.com:acme/api.git");
+
+    ctx.cli()
+        .env_remove("GITHUB_REPOSITORY")
+        .args(["api", "/repos/{owner}/{repo}"])
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains("not github.com or an account's Enterprise host"));
+}
+
+#[test]
+#[serial]
+fn repo_detection_accepts_account_enterprise_host() {
+    let ctx = TestContext::new();
+    ctx.write_accounts(
+        r#"{
+        "personal": [],
+        "work": [
+            {"id": "acme", "kind": "work", "username": "dev", "host": "ghe.acme.com"}
+        ]
+    }"#,
+    );
+    init_repo_with_origin(&ctx, "ssh://git@ghe.acme.com/platform/api.git");
+
+    // Detection succeeds, so the request only fails for lack of an active account.
+    ctx.cli()
+        .env_remove("GITHUB_REPOSITORY")
+        .args(["api", "/repos/{owner}/{repo}"])
+        .assert()
+        .failure()
+        .code(3);
+}
+
+#[test]
+#[serial]
+fn account_setup_gitconfig_writes_and_updates_include() {
+    let ctx = TestContext::new();
+    ctx.write_accounts(
+        r#"{
+        "personal": [],
+        "work": [
+            {"id": "acme", "kind": "work", "username": "dev", "clone_dir": "~/src/acme"}
+        ]
+    }"#,
+    );
+    let global = ctx.home().join(".gitconfig");
+    std::fs::write(&global, "[user]\n\tname = Dev\n").unwrap();
+
+    ctx.cli()
+        .env_remove("GIT_CONFIG_GLOBAL")
+        .args(["account", "setup-gitconfig", "acme"])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("--email is required"));
+    ctx.cli()
+        .env_remove("GIT_CONFIG_GLOBAL")
+        .args(["account", "setup-gitconfig", "acme", "--email", "dev@acme.com"])
+        .assert()
+        .success();
+    ctx.cli()
+        .env_remove("GIT_CONFIG_GLOBAL")
+        .args(["account", "setup-gitconfig", "acme", "--ssh-key", "~/.ssh/id_acme"])
+        .assert()
+        .success();
+
+    let account_file = ctx.config_dir().join("git").join("acme.gitconfig");
+    let generated = std::fs::read_to_string(&account_file).unwrap();
+    assert!(generated.contains("\temail = \"dev@acme.com\"\n"));
+    assert!(generated.contains("sshCommand"));
+    let content = std::fs::read_to_string(&global).unwrap();
+    assert!(content.starts_with("[user]\n\tname = Dev\n"));
+    assert_eq!(content.matches("[includeIf \"gitdir:~/src/acme/\"]").count(), 1);
+
+    ctx.cli()
+        .env_remove("GIT_CONFIG_GLOBAL")
+        .args(["account", "remove", "acme"])
+        .assert()
+        .success();
+    assert!(!std::fs::read_to_string(&global).unwrap().contains("includeIf"));
+    assert!(!account_file.exists());
+}
+
+#[test]
+#[serial]
+fn repo_arguments_accept_urls_and_bare_names() {
+    let ctx = TestContext::new();
+    ctx.cli().args(["repo", "open", "api", "--print"]).assert().failure().code(3);
+
+    ctx.write_accounts(
+        r#"{
+        "personal": [{"id": "me", "kind": "personal", "username": "dev"}],
+        "work": [{"id": "acme", "kind": "work", "username": "dev", "default_org": "acme"}],
+        "active_account_id": "acme"
+    }"#,
+    );
+    ctx.cli()
+        .args(["repo", "open", "api", "--print"])
+        .assert()
+        .success()
+        .stdout("https://github.com/acme/api\n");
+    ctx.cli()
+        .args(["repo", "open", "git@github.com:octo/web.git", "--print"])
+        .assert()
+        .success()
+        .stdout("https://github.com/octo/web\n");
+
+    ctx.cli().args(["account", "use", "me"]).assert().success();
+    ctx.cli()
+        .args(["repo", "open", "dotfiles", "--print"])
+        .assert()
+        .success()
+        .stdout("https://github.com/dev/dotfiles\n");
+}
+
+#[test]
+#[serial]
+fn dry_run_is_rejected_by_unsupported_commands() {
+    let ctx = TestContext::new();
+    ctx.cli()
+        .args(["label", "delete", "bug", "--dry-run"])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("--dry-run is not supported"));
+}
+
+#[test]
+#[serial]
+fn pr_merge_dry_run_requires_active_account() {
+    let ctx = TestContext::new();
+    ctx.cli().args(["pr", "merge", "1", "-R", "acme/api", "--dry-run"]).assert().failure().code(3);
+}
+
+#[test]
+#[serial]
+fn progress_rejects_unknown_format() {
+    let ctx = TestContext::new();
+    ctx.cli()
+        .args(["--progress", "xml", "repo", "clone", "--org", "acme"])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("possible values: human, json"));
+}
+
+#[test]
+#[serial]
+fn repo_open_uses_active_account_browser() {
+    let ctx = TestContext::new();
+    ctx.write_accounts(
+        r#"{
+        "personal": [],
+        "work": [
+            {"id": "work", "kind": "work", "username": "dev", "browser": "echo profile={account}"}
+        ],
+        "active_account_id": "work"
+    }"#,
+    );
+
+    ctx.cli()
+        .args(["repo", "open", "acme/api"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("profile=work https://github.com/acme/api\n"));
+}
+
+#[test]
+#[serial]
+fn repo_delete_requires_confirmation_without_tty() {
+    let ctx = TestContext::new();
+    ctx.cli()
+        .args(["repo", "delete", "acme/api"])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("pass --yes"));
+}
+
+#[test]
+#[serial]
+fn repo_delete_with_yes_requires_active_account() {
+    let ctx = TestContext::new();
+    ctx.cli().args(["repo", "delete", "acme/api", "--yes"]).assert().failure().code(3);
+    ctx.cli().args(["repo", "delete", "acme/api/extra", "--yes"]).assert().failure().code(2);
+}
+
+#[test]
+#[serial]
+fn repo_archive_requires_confirmation_without_tty() {
+    let ctx = TestContext::new();
+    ctx.cli()
+        .args(["repo", "archive", "acme/api"])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("refusing to archive 'acme/api'"));
+}
+
+#[test]
+#[serial]
+fn explicit_org_is_remembered_for_the_picker() {
+    let ctx = TestContext::new();
+    ctx.cli().args(["repo", "list", "--org", "acme"]).assert().failure().code(3);
+
+    let state = std::fs::read_to_string(ctx.config_dir().join("state.json")).unwrap();
+    assert!(state.contains(r#""last_org": "acme""#));
+}
+
+#[test]
+#[serial]
+fn keychain_service_is_only_changed_by_migration() {
+    let ctx = TestContext::new();
+    ctx.cli()
+        .args(["config", "set", "keychain_service", "gho-work"])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("account migrate-keychain"));
+    ctx.cli()
+        .args(["account", "migrate-keychain", "--to-service", "gho"])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("already stored in keychain service 'gho'"));
+}
+
+#[test]
+#[serial]
+fn pr_diff_requires_active_account() {
+    let ctx = TestContext::new();
+    ctx.cli()
+        .args(["pr", "diff", "1", "-R", "acme/api", "--name-only", "--files", "src/*.rs"])
+        .assert()
+        .failure()
+        .code(3);
+}
+
+#[test]
+#[serial]
+fn dash_refers_to_the_last_used_repo() {
+    let ctx = TestContext::new();
+    ctx.cli()
+        .args(["repo", "open", "-", "--print"])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("no recently used repository"));
+    ctx.cli().args(["recent"]).assert().success().stdout("No recently used repositories.\n");
+
+    ctx.cli().args(["repo", "open", "acme/api", "--print"]).assert().success();
+    ctx.cli().args(["repo", "open", "acme/web", "--print"]).assert().success();
+    ctx.cli()
+        .args(["repo", "open", "-", "--print"])
+        .assert()
+        .success()
+        .stdout("https://github.com/acme/web\n");
+    ctx.cli().args(["recent"]).assert().success().stdout("acme/web (-)\nacme/api\n");
+    ctx.cli()
+        .args(["recent", "--json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""repos":["acme/web","acme/api"]"#));
+}
+
+#[test]
+#[serial]
+fn notify_validates_thread_ids_before_requiring_an_account() {
+    let ctx = TestContext::new();
+    ctx.cli()
+        .args(["notify", "open", "not-a-number"])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("invalid notification id"));
+    ctx.cli().args(["notify", "read"]).assert().failure().code(2);
+    ctx.cli().args(["notify", "read", "--all"]).assert().failure().code(3);
+    ctx.cli().args(["notify", "list", "--unread", "--participating"]).assert().failure().code(3);
+}
+
+#[test]
+#[serial]
+fn access_diff_needs_a_repo_and_accounts() {
+    let ctx = TestContext::new();
+    ctx.cli().args(["access", "diff", "acme/api/extra"]).assert().failure().code(2);
+    ctx.cli()
+        .args(["access", "diff", "acme/api"])
+        .assert()
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains("No active account"));
+}
+
+#[test]
+#[serial]
+fn workspace_needs_org_topic_and_manifest() {
+    let ctx = TestContext::new();
+    ctx.cli()
+        .args(["workspace", "discover", "--topic", "team-payments"])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("--org is required"));
+    ctx.cli()
+        .args(["workspace", "sync"])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("gho-workspace.toml"));
+}
+
+#[test]
+#[serial]
+fn contrib_validates_since_before_requiring_an_account() {
+    let ctx = TestContext::new();
+    ctx.cli()
+        .args(["contrib", "--since", "2020-01-01"])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("more than a year ago"));
+    ctx.cli().args(["contrib", "--all-accounts"]).assert().failure().code(3);
+}
+
+#[test]
+#[serial]
+fn gist_create_validates_files_before_requiring_an_account() {
+    let ctx = TestContext::new();
+    let file = ctx.work_dir().join("notes.md");
+    ctx.cli()
+        .args(["gist", "create", file.to_str().unwrap()])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("cannot read"));
+
+    std::fs::write(&file, "# Notes\n").unwrap();
+    ctx.cli()
+        .args(["gist", "create", file.to_str().unwrap(), "--public", "--desc", "Notes"])
+        .assert()
+        .failure()
+        .code(3);
+    ctx.cli().args(["gist", "clone", "../evil"]).assert().failure().code(2);
+}
+
+#[test]
+#[serial]
+fn redo_replays_recorded_commands() {
+    let ctx = TestContext::new();
+    ctx.cli().args(["redo"]).assert().failure().code(4);
+
+    ctx.cli().args(["repo", "open", "acme/api", "--print"]).assert().success();
+    ctx.cli().args(["config", "get", "limit"]).assert().success();
+    ctx.cli()
+        .args(["history", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("gho repo open acme/api --print"))
+        .stdout(predicate::str::contains("gho config get limit"));
+    ctx.cli()
+        .args(["history", "search", "ACME"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("    1  "))
+        .stdout(predicate::str::contains("config").not());
+
+    ctx.cli()
+        .args(["redo", "1"])
+        .assert()
+        .success()
+        .stdout("https://github.com/acme/api\n")
+        .stderr(predicate::str::contains("↻ 1: gho repo open acme/api --print"));
+}
+
+#[test]
+#[serial]
+fn org_team_repos_validates_team_before_requiring_an_account() {
+    let ctx = TestContext::new();
+    ctx.cli()
+        .args(["org", "team", "repos", "acme"])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicate::str::contains("expected org/team-slug"));
+    ctx.cli().args(["org", "team", "repos", "acme/platform"]).assert().failure().code(3);
+    ctx.cli().args(["org", "members", "acme", "--role", "admin"]).assert().failure().code(3);
+    ctx.cli().args(["org", "members", "acme", "--role", "owner"]).assert().failure().code(2);
+}
@@ -91,3 +91,256 @@ fn account_remove_deletes_account() {
     let content = ctx.read_accounts();
     assert!(!content.contains("todelete"));
 }
+
+#[test]
+#[serial]
+fn account_remove_dry_run_keeps_account() {
+    let ctx = TestContext::new();
+
+    ctx.write_accounts(
+        r#"{
+        "personal": [
+            {
+                "id": "keep",
+                "kind": "personal",
+                "username": "testuser",
+                "protocol": "ssh"
+            }
+        ],
+        "work": [],
+        "active_account_id": "keep"
+    }"#,
+    );
+
+    ctx.cli()
+        .args(["--dry-run", "account", "remove", "keep"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("remove account 'keep' (testuser)"))
+        .stdout(predicates::str::contains("leave no account active"));
+
+    let content = ctx.read_accounts();
+    assert!(content.contains(r#""active_account_id": "keep""#));
+}
+
+#[test]
+#[serial]
+fn account_browser_sets_and_clears_command() {
+    let ctx = TestContext::new();
+
+    ctx.write_accounts(
+        r#"{
+        "personal": [],
+        "work": [
+            {"id": "work", "kind": "work", "username": "dev", "protocol": "ssh"}
+        ]
+    }"#,
+    );
+
+    ctx.cli().args(["account", "browser", "work", "firefox -P {account}"]).assert().success();
+    assert!(ctx.read_accounts().contains(r#""browser": "firefox -P {account}""#));
+
+    ctx.cli().args(["account", "browser", "work", "--clear"]).assert().success();
+    assert!(!ctx.read_accounts().contains("browser"));
+
+    ctx.cli()
+        .args(["account", "browser", "work", "chrome --profile-directory='Profile 1"])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicates::str::contains("unterminated quote"));
+}
+
+#[test]
+#[serial]
+fn account_group_set_persists_members() {
+    let ctx = TestContext::new();
+
+    ctx.write_accounts(
+        r#"{
+        "personal": [],
+        "work": [
+            {"id": "acme", "kind": "work", "username": "u1", "protocol": "ssh"},
+            {"id": "globex", "kind": "work", "username": "u2", "protocol": "ssh"}
+        ],
+        "active_account_id": "acme"
+    }"#,
+    );
+
+    ctx.cli().args(["account", "group", "set", "clients", "acme", "globex"]).assert().success();
+
+    let content = ctx.read_accounts();
+    assert!(content.contains(r#""clients": ["#));
+
+    ctx.cli()
+        .args(["account", "group", "list"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("clients: acme, globex"));
+}
+
+#[test]
+#[serial]
+fn account_export_import_roundtrip() {
+    let ctx = TestContext::new();
+
+    ctx.write_accounts(
+        r#"{
+        "personal": [{"id": "home", "kind": "personal", "username": "me", "protocol": "ssh"}],
+        "work": [],
+        "active_account_id": "home"
+    }"#,
+    );
+    let backup = ctx.work_dir().join("accounts.backup");
+
+    ctx.cli()
+        .args(["account", "export", "--file"])
+        .arg(&backup)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Exported 1 account(s)"));
+
+    ctx.cli().args(["account", "remove", "home"]).assert().success();
+    ctx.cli().args(["account", "import", "--file"]).arg(&backup).assert().success();
+
+    let content = ctx.read_accounts();
+    assert!(content.contains(r#""id": "home""#));
+    assert!(content.contains(r#""active_account_id": "home""#));
+}
+
+#[test]
+#[serial]
+fn account_clone_layout_persists() {
+    let ctx = TestContext::new();
+
+    ctx.write_accounts(
+        r#"{
+        "personal": [
+            {
+                "id": "dev",
+                "kind": "personal",
+                "username": "user1",
+                "protocol": "ssh"
+            }
+        ],
+        "work": [],
+        "active_account_id": "dev"
+    }"#,
+    );
+
+    ctx.cli()
+        .args(["account", "clone-layout", "dev", "owner/repo"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("owner/repo layout"));
+
+    assert!(ctx.read_accounts().contains(r#""clone_layout": "owner/repo""#));
+}
+
+#[test]
+#[serial]
+fn expired_token_warns_on_any_command() {
+    let ctx = TestContext::new();
+
+    ctx.write_accounts(
+        r#"{
+        "personal": [
+            {
+                "id": "home",
+                "kind": "personal",
+                "username": "testuser",
+                "protocol": "ssh",
+                "token_expires_at": "2020-01-01T00:00:00Z"
+            }
+        ],
+        "work": [],
+        "active_account_id": "home"
+    }"#,
+    );
+
+    ctx.cli()
+        .args(["account", "list"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("[expires 2020-01-01]"))
+        .stderr(predicates::str::contains("token of 'home' expired on 2020-01-01"))
+        .stderr(predicates::str::contains("gho account token refresh home"));
+}
+
+#[test]
+#[serial]
+fn token_refresh_rejects_unknown_account() {
+    let ctx = TestContext::new();
+
+    ctx.cli()
+        .args(["account", "token", "refresh", "ghost", "--expires", "2030-01-01"])
+        .assert()
+        .failure()
+        .code(4);
+}
+
+#[test]
+#[serial]
+fn token_refresh_rejects_invalid_expiry() {
+    let ctx = TestContext::new();
+
+    ctx.cli()
+        .args(["account", "token", "refresh", "home", "--expires", "soon"])
+        .assert()
+        .failure()
+        .code(2);
+}
+
+const TOKEN_COMMAND_ACCOUNTS: &str = r#"{
+    "personal": [
+        {
+            "id": "vaulted",
+            "kind": "personal",
+            "username": "testuser",
+            "protocol": "ssh",
+            "token_command": "echo ghp_abcdefgh12345678"
+        }
+    ],
+    "work": [],
+    "active_account_id": "vaulted"
+}"#;
+
+#[test]
+#[serial]
+fn token_get_masks_unless_plain() {
+    let ctx = TestContext::new();
+    ctx.write_accounts(TOKEN_COMMAND_ACCOUNTS);
+
+    ctx.cli()
+        .args(["account", "token", "get", "vaulted"])
+        .assert()
+        .success()
+        .stdout("ghp_...5678\n");
+    ctx.cli()
+        .args(["account", "token", "get", "vaulted", "--plain"])
+        .assert()
+        .success()
+        .stdout("ghp_abcdefgh12345678\n");
+}
+
+#[test]
+#[serial]
+fn token_set_rejects_token_command_accounts() {
+    let ctx = TestContext::new();
+    ctx.write_accounts(TOKEN_COMMAND_ACCOUNTS);
+
+    ctx.cli()
+        .args(["account", "token", "set", "vaulted"])
+        .write_stdin("ghp_new\n")
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicates::str::contains("update the secret there"));
+}
+
+#[test]
+#[serial]
+fn token_get_unknown_account_fails() {
+    let ctx = TestContext::new();
+    ctx.cli().args(["account", "token", "get", "ghost"]).assert().failure().code(4);
+}